@@ -0,0 +1,66 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import {
+  getAdvertisedRecords,
+  getLanAddresses,
+  getMdnsAnswers,
+  HTTP_SERVICE_TYPE,
+  parseAdvertiseConfig,
+} from './advertise.js';
+import { decodeMdnsPacket, decodeMdnsQuestions, encodeMdnsQuery, encodeMdnsResponse } from './discovery.js';
+
+const CONFIG = { hostname: 'sv2-ui', instance: 'SV2 Dashboard' };
+const RECORDS = getAdvertisedRecords(CONFIG, { port: 3000, basePath: '/sv2', addresses: ['192.168.1.10'] });
+
+test('parseAdvertiseConfig is off unless set and validates the hostname', () => {
+  assert.equal(parseAdvertiseConfig({}), null);
+  assert.equal(parseAdvertiseConfig({ MDNS_ADVERTISE: 'false' }), null);
+  assert.deepEqual(parseAdvertiseConfig({ MDNS_ADVERTISE: 'true' }), CONFIG);
+  assert.equal(parseAdvertiseConfig({ MDNS_ADVERTISE: 'true', MDNS_HOSTNAME: 'Rack2.local' })?.hostname, 'rack2');
+  assert.throws(() => parseAdvertiseConfig({ MDNS_ADVERTISE: 'yes' }), /MDNS_ADVERTISE must be/);
+  assert.throws(() => parseAdvertiseConfig({ MDNS_ADVERTISE: 'true', MDNS_HOSTNAME: 'my.box' }), /single DNS label/);
+});
+
+test('only LAN IPv4 addresses are advertised', () => {
+  assert.deepEqual(getLanAddresses({
+    lo: [{ address: '127.0.0.1', family: 'IPv4', internal: true, netmask: '255.0.0.0', mac: '', cidr: null }],
+    eth0: [
+      { address: '192.168.1.10', family: 'IPv4', internal: false, netmask: '255.255.255.0', mac: '', cidr: null },
+      { address: 'fe80::1', family: 'IPv6', internal: false, netmask: 'ffff::', mac: '', cidr: null, scopeid: 2 },
+    ],
+    wlan0: [{ address: '169.254.3.4', family: 'IPv4', internal: false, netmask: '255.255.0.0', mac: '', cidr: null }],
+  }), ['192.168.1.10']);
+});
+
+test('a service browse gets the whole advertisement in one response', () => {
+  const questions = decodeMdnsQuestions(encodeMdnsQuery(HTTP_SERVICE_TYPE));
+  assert.deepEqual(questions, [{ name: HTTP_SERVICE_TYPE, type: 12 }]);
+
+  const { answers, additionals } = getMdnsAnswers(questions, RECORDS);
+  assert.deepEqual(answers.map((record) => record.type), ['PTR']);
+  assert.deepEqual(additionals.map((record) => record.type), ['SRV', 'TXT', 'A']);
+
+  const response = encodeMdnsResponse(answers, { additionals, id: 7 });
+  assert.equal(response.readUInt16BE(0), 7);
+  assert.deepEqual(decodeMdnsQuestions(response), []);
+  assert.deepEqual(decodeMdnsPacket(response), [
+    { type: 'PTR', name: HTTP_SERVICE_TYPE, target: `SV2 Dashboard.${HTTP_SERVICE_TYPE}` },
+    { type: 'SRV', name: `SV2 Dashboard.${HTTP_SERVICE_TYPE}`, port: 3000, target: 'sv2-ui.local' },
+    { type: 'TXT', name: `SV2 Dashboard.${HTTP_SERVICE_TYPE}`, entries: { path: '/sv2' } },
+    { type: 'A', name: 'sv2-ui.local', address: '192.168.1.10' },
+  ]);
+});
+
+test('hostname lookups are answered case-insensitively and other names are ignored', () => {
+  const { answers, additionals } = getMdnsAnswers([{ name: 'SV2-UI.local', type: 1 }], RECORDS);
+  assert.deepEqual(answers, [{ type: 'A', name: 'sv2-ui.local', address: '192.168.1.10' }]);
+  assert.deepEqual(additionals, []);
+
+  assert.deepEqual(getMdnsAnswers([{ name: 'sv2-ui.local', type: 28 }], RECORDS).answers, []);
+  assert.deepEqual(getMdnsAnswers([{ name: 'other.local', type: 255 }], RECORDS).answers, []);
+});
+
+test('encodes IPv6 addresses', () => {
+  const response = encodeMdnsResponse([{ type: 'AAAA', name: 'sv2-ui.local', address: 'fd00::1:2' }]);
+  assert.deepEqual(decodeMdnsPacket(response), [{ type: 'AAAA', name: 'sv2-ui.local', address: 'fd00:0:0:0:0:0:1:2' }]);
+});
@@ -0,0 +1,172 @@
+/**
+ * mDNS/DNS-SD advertisement of the dashboard itself.
+ *
+ * With MDNS_ADVERTISE set, the server answers mDNS queries for
+ * `<MDNS_HOSTNAME>.local` (default sv2-ui.local) and advertises an
+ * `_http._tcp` service named "SV2 Dashboard", so users on the LAN can open
+ * the UI without looking up the IP of the box running the stack. It announces
+ * itself on startup and sends a goodbye on shutdown.
+ *
+ * Multicast only reaches the LAN when the server shares the host's network,
+ * e.g. `network_mode: host` in Docker.
+ */
+
+import dgram from 'dgram';
+import os from 'os';
+import {
+  answersQuestion,
+  decodeMdnsQuestions,
+  encodeMdnsResponse,
+  MDNS_ADDRESS,
+  MDNS_PORT,
+} from './discovery.js';
+import type { MdnsQuestion, MdnsRecord } from './discovery.js';
+import { createLogger } from './logger.js';
+
+const log = createLogger('advertise');
+
+export const HTTP_SERVICE_TYPE = '_http._tcp.local';
+export const DASHBOARD_INSTANCE_NAME = 'SV2 Dashboard';
+export const DEFAULT_MDNS_HOSTNAME = 'sv2-ui';
+
+const ANNOUNCE_DELAY_MS = 1000;
+
+export interface AdvertiseConfig {
+  hostname: string;
+  instance: string;
+}
+
+export interface AdvertisedService {
+  port: number;
+  basePath: string;
+  addresses: string[];
+}
+
+export function parseAdvertiseConfig(env: NodeJS.ProcessEnv = process.env): AdvertiseConfig | null {
+  const value = env.MDNS_ADVERTISE?.trim().toLowerCase();
+  if (!value || value === 'off' || value === 'false') return null;
+  if (value !== 'on' && value !== 'true') {
+    throw new Error(`MDNS_ADVERTISE must be true or false, got "${env.MDNS_ADVERTISE}"`);
+  }
+
+  const hostname = (env.MDNS_HOSTNAME?.trim() || DEFAULT_MDNS_HOSTNAME).toLowerCase().replace(/\.local\.?$/, '');
+  if (!/^[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?$/.test(hostname)) {
+    throw new Error(`MDNS_HOSTNAME must be a single DNS label, got "${env.MDNS_HOSTNAME}"`);
+  }
+  return { hostname, instance: DASHBOARD_INSTANCE_NAME };
+}
+
+/**
+ * The LAN's IPv4 addresses of this host; loopback and link-local are skipped.
+ */
+export function getLanAddresses(interfaces: ReturnType<typeof os.networkInterfaces> = os.networkInterfaces()): string[] {
+  return Object.values(interfaces)
+    .flatMap((entries) => entries ?? [])
+    .filter((entry) => entry.family === 'IPv4' && !entry.internal && !entry.address.startsWith('169.254.'))
+    .map((entry) => entry.address);
+}
+
+/**
+ * The PTR, SRV, TXT and A records that make up the advertisement.
+ */
+export function getAdvertisedRecords(config: AdvertiseConfig, service: AdvertisedService): MdnsRecord[] {
+  const instance = `${config.instance}.${HTTP_SERVICE_TYPE}`;
+  const host = `${config.hostname}.local`;
+  return [
+    { type: 'PTR', name: HTTP_SERVICE_TYPE, target: instance },
+    { type: 'SRV', name: instance, port: service.port, target: host },
+    // `path` is the DNS-SD convention for where an HTTP service lives.
+    { type: 'TXT', name: instance, entries: { path: service.basePath || '/' } },
+    ...service.addresses.map((address): MdnsRecord => ({ type: 'A', name: host, address })),
+  ];
+}
+
+/**
+ * The records that answer `questions`, and the ones that save the asker a
+ * second round trip: a service browse also gets the SRV, TXT and A records.
+ */
+export function getMdnsAnswers(
+  questions: MdnsQuestion[],
+  records: MdnsRecord[],
+): { answers: MdnsRecord[]; additionals: MdnsRecord[] } {
+  const answers = records.filter((record) => questions.some((question) => answersQuestion(record, question)));
+  const additionals = answers.some((record) => record.type === 'PTR' || record.type === 'SRV')
+    ? records.filter((record) => record.type !== 'PTR' && !answers.includes(record))
+    : [];
+  return { answers, additionals };
+}
+
+export function createMdnsAdvertiser(
+  config: AdvertiseConfig,
+  { port, basePath, getAddresses = () => getLanAddresses() }: {
+    port: number;
+    basePath: string;
+    getAddresses?: () => string[];
+  },
+) {
+  let socket: dgram.Socket | null = null;
+
+  const getRecords = () => getAdvertisedRecords(config, { port, basePath, addresses: getAddresses() });
+
+  function send(packet: Buffer, address = MDNS_ADDRESS, targetPort = MDNS_PORT): void {
+    socket?.send(packet, targetPort, address);
+  }
+
+  function announce(): void {
+    send(encodeMdnsResponse(getRecords()));
+  }
+
+  function onQuery(packet: Buffer, remote: dgram.RemoteInfo): void {
+    let questions: MdnsQuestion[];
+    try {
+      questions = decodeMdnsQuestions(packet);
+    } catch {
+      return;
+    }
+    const { answers, additionals } = getMdnsAnswers(questions, getRecords());
+    if (answers.length === 0) return;
+
+    // Legacy unicast queries (sent from a port other than 5353, like
+    // discovery.ts's browse) expect a direct reply carrying their ID.
+    if (remote.port !== MDNS_PORT) {
+      send(encodeMdnsResponse(answers, { additionals, ttlSecs: 10, id: packet.readUInt16BE(0) }), remote.address, remote.port);
+    } else {
+      send(encodeMdnsResponse(answers, { additionals }));
+    }
+  }
+
+  function start(): Promise<void> {
+    return new Promise((resolve, reject) => {
+      const next = dgram.createSocket({ type: 'udp4', reuseAddr: true });
+      next.once('error', reject);
+      next.on('message', onQuery);
+      next.bind(MDNS_PORT, () => {
+        next.off('error', reject);
+        next.on('error', (error) => log.error('mDNS advertisement error:', error));
+        next.addMembership(MDNS_ADDRESS);
+        next.setMulticastTTL(255);
+        socket = next;
+        // RFC 6762 section 8.3: announce twice, a second apart.
+        announce();
+        setTimeout(announce, ANNOUNCE_DELAY_MS).unref();
+        resolve();
+      });
+    });
+  }
+
+  function stop(): Promise<void> {
+    const current = socket;
+    if (!current) return Promise.resolve();
+    socket = null;
+    return new Promise((resolve) => {
+      current.send(encodeMdnsResponse(getRecords(), { ttlSecs: 0 }), MDNS_PORT, MDNS_ADDRESS, () => {
+        current.close();
+        resolve();
+      });
+    });
+  }
+
+  return { start, stop, records: getRecords };
+}
+
+export type MdnsAdvertiser = ReturnType<typeof createMdnsAdvertiser>;
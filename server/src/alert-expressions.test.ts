@@ -0,0 +1,89 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { compileAlertExpression, getAlertVariables, type AlertVariables } from './alert-expressions.js';
+import type { StackSummary } from './summary.js';
+
+const variables = (overrides: Partial<AlertVariables> = {}): AlertVariables => ({
+  online: true,
+  hashrate: 2e12,
+  workers: 2,
+  miners: 2,
+  shares_accepted: 900,
+  shares_submitted: 1000,
+  shares_rejected: 100,
+  reject_rate: 0.1,
+  best_difficulty: 5e6,
+  blocks_found: 0,
+  uptime_secs: 3600,
+  ...overrides,
+});
+
+const holds = (source: string, overrides?: Partial<AlertVariables>) => compileAlertExpression(source).evaluate(variables(overrides));
+
+test('evaluates with Rhai precedence', () => {
+  assert.equal(holds('reject_rate > 0.05 && miners < 3'), true);
+  assert.equal(holds('reject_rate > 0.05 && miners < 3', { miners: 3 }), false);
+  assert.equal(holds('false && true || true'), true);
+  assert.equal(holds('!(hashrate >= 1_000e9) || workers == 2'), true);
+  assert.equal(holds('hashrate / workers < 1.5e12 - 2 * 100e9'), true);
+  assert.equal(holds('-uptime_secs < -3000 != online'), false);
+  assert.equal(holds('shares_rejected / shares_submitted == reject_rate'), true);
+});
+
+test('an unknown value leaves the condition unknown unless it is skipped', () => {
+  assert.equal(holds('reject_rate > 0.05', { reject_rate: null }), null);
+  assert.equal(holds('online && reject_rate > 0.05', { online: false, reject_rate: null }), false);
+  assert.equal(holds('!online || hashrate < 1e12', { online: false, hashrate: null }), true);
+  assert.equal(holds('blocks_found / workers > 1', { workers: 0 }), null);
+});
+
+test('rejects expressions that are not valid or not true or false', () => {
+  assert.throws(() => compileAlertExpression('temperature > 80'), /unknown variable "temperature" at column 1/);
+  assert.throws(() => compileAlertExpression('hashrate > '), /unexpected end of expression/);
+  assert.throws(() => compileAlertExpression('(hashrate > 1'), /expected "\)"/);
+  assert.throws(() => compileAlertExpression('hashrate > 1 miners'), /unexpected "miners" at column 14/);
+  assert.throws(() => compileAlertExpression('hashrate = 1'), /unexpected "=" at column 10/);
+  assert.throws(() => compileAlertExpression('hashrate * 2'), /must be true or false/);
+  assert.throws(() => compileAlertExpression('online > 1'), /> needs numbers/);
+  assert.throws(() => compileAlertExpression('workers && online'), /&& needs true or false/);
+  assert.throws(() => compileAlertExpression('workers == online'), /different types/);
+  assert.throws(() => compileAlertExpression('0 < 1 < 2'), /< needs numbers/);
+});
+
+test('reads variables from the sample and the one before it', () => {
+  const sample = (overrides: Partial<StackSummary>): StackSummary => ({
+    generatedAt: '2026-01-01T00:00:00.000Z',
+    miningMode: 'pool',
+    mode: 'no-jd',
+    network: null,
+    poolName: null,
+    online: true,
+    hashrate: 1e12,
+    workers: 3,
+    sharesAccepted: 900,
+    sharesSubmitted: 1000,
+    sharesRejected: 100,
+    bestDifficulty: 0,
+    blocksFound: 0,
+    uptimeSecs: 60,
+    ...overrides,
+  });
+
+  const previous = sample({});
+  assert.equal(getAlertVariables(previous, sample({ sharesSubmitted: 1200, sharesRejected: 150 })).reject_rate, 0.25);
+  assert.equal(getAlertVariables(null, previous).reject_rate, null);
+  assert.equal(getAlertVariables(previous, previous).reject_rate, null);
+  assert.deepEqual(getAlertVariables(previous, sample({ online: false })), {
+    online: false,
+    hashrate: null,
+    workers: null,
+    miners: null,
+    shares_accepted: null,
+    shares_submitted: null,
+    shares_rejected: null,
+    reject_rate: null,
+    best_difficulty: null,
+    blocks_found: null,
+    uptime_secs: null,
+  });
+});
@@ -0,0 +1,243 @@
+/**
+ * Conditions for `expression` alert rules, written in the expression subset
+ * of Rhai, e.g. `reject_rate > 0.05 && miners < 3`.
+ *
+ * Supported: numbers (1e12, 100_000), true and false, the variables below,
+ * parentheses, `!`, `-`, `* /`, `+ -`, `< <= > >=`, `== !=`, `&&` and `||`,
+ * with Rhai's precedence. There are no statements, loops or function calls,
+ * so an expression always finishes. Types are checked when the rule is saved,
+ * and the whole expression must be true or false.
+ *
+ * Variables come from the sampled stack summary. While the stack is offline
+ * every variable but `online` is unknown, and `reject_rate` is unknown until
+ * shares were submitted since the previous sample. A sample where the
+ * expression reads an unknown variable leaves the rule as it was, like
+ * hashrate-below does while offline; `&&` and `||` short-circuit, so
+ * `online && reject_rate > 0.05` is false while offline.
+ */
+
+import type { StackSummary } from './summary.js';
+
+type ValueType = 'number' | 'bool';
+type Value = number | boolean;
+
+type Node =
+  | { type: 'literal'; value: Value }
+  | { type: 'variable'; name: AlertVariable }
+  | { type: 'unary'; op: '!' | '-'; arg: Node }
+  | { type: 'binary'; op: string; left: Node; right: Node };
+
+export const ALERT_VARIABLES = {
+  online: 'bool',
+  hashrate: 'number',
+  workers: 'number',
+  // The same as workers, as the dashboard calls them.
+  miners: 'number',
+  shares_accepted: 'number',
+  shares_submitted: 'number',
+  shares_rejected: 'number',
+  // Rejected over submitted shares since the previous sample, 0-1.
+  reject_rate: 'number',
+  best_difficulty: 'number',
+  blocks_found: 'number',
+  uptime_secs: 'number',
+} as const satisfies Record<string, ValueType>;
+
+export type AlertVariable = keyof typeof ALERT_VARIABLES;
+export type AlertVariables = Record<AlertVariable, Value | null>;
+
+const MAX_EXPRESSION_LENGTH = 500;
+
+// Binding power per binary operator; higher binds tighter.
+const PRECEDENCE: Record<string, number> = {
+  '||': 1,
+  '&&': 2,
+  '==': 3, '!=': 3,
+  '<': 4, '<=': 4, '>': 4, '>=': 4,
+  '+': 5, '-': 5,
+  '*': 6, '/': 6,
+};
+
+const TOKEN = /\s*(?:(\d[\d_]*(?:\.\d[\d_]*)?(?:[eE][+-]?\d+)?)|([A-Za-z_]\w*)|(&&|\|\||[<>=!]=|[-+*/<>!()]))/y;
+
+class Unknown extends Error {}
+
+interface Token {
+  text: string;
+  kind: 'number' | 'name' | 'op' | 'end';
+  column: number;
+}
+
+function tokenize(source: string): Token[] {
+  const tokens: Token[] = [];
+  TOKEN.lastIndex = 0;
+  while (TOKEN.lastIndex < source.length) {
+    if (/^\s*$/.test(source.slice(TOKEN.lastIndex))) break;
+    const start = TOKEN.lastIndex;
+    const match = TOKEN.exec(source);
+    if (!match) {
+      const column = start + source.slice(start).search(/\S/) + 1;
+      throw new Error(`unexpected "${source[column - 1]}" at column ${column}`);
+    }
+    const text = match[1] ?? match[2] ?? match[3];
+    tokens.push({ text, kind: match[1] ? 'number' : match[2] ? 'name' : 'op', column: match.index + match[0].length - text.length + 1 });
+  }
+  tokens.push({ text: '', kind: 'end', column: source.length + 1 });
+  return tokens;
+}
+
+function parse(source: string): Node {
+  const tokens = tokenize(source);
+  let position = 0;
+  const peek = () => tokens[position];
+  const describe = (token: Token) => (token.kind === 'end' ? 'end of expression' : `"${token.text}" at column ${token.column}`);
+
+  function primary(): Node {
+    const token = tokens[position++];
+    if (token.kind === 'number') return { type: 'literal', value: Number(token.text.replaceAll('_', '')) };
+    if (token.kind === 'name') {
+      if (token.text === 'true' || token.text === 'false') return { type: 'literal', value: token.text === 'true' };
+      if (!Object.hasOwn(ALERT_VARIABLES, token.text)) {
+        throw new Error(`unknown variable "${token.text}" at column ${token.column}; use ${Object.keys(ALERT_VARIABLES).join(', ')}`);
+      }
+      return { type: 'variable', name: token.text as AlertVariable };
+    }
+    if (token.text === '!' || token.text === '-') return { type: 'unary', op: token.text, arg: primary() };
+    if (token.text === '(') {
+      const node = binary(0);
+      if (peek().text !== ')') throw new Error(`expected ")" but found ${describe(peek())}`);
+      position++;
+      return node;
+    }
+    throw new Error(`unexpected ${describe(token)}`);
+  }
+
+  function binary(minPrecedence: number): Node {
+    let left = primary();
+    for (;;) {
+      const token = peek();
+      const precedence = token.kind === 'op' ? PRECEDENCE[token.text] : undefined;
+      if (precedence === undefined || precedence <= minPrecedence) return left;
+      position++;
+      left = { type: 'binary', op: token.text, left, right: binary(precedence) };
+    }
+  }
+
+  const node = binary(0);
+  if (peek().kind !== 'end') throw new Error(`unexpected ${describe(peek())}`);
+  return node;
+}
+
+function typeOf(node: Node): ValueType {
+  const expect = (child: Node, type: ValueType, op: string) => {
+    if (typeOf(child) !== type) throw new Error(`${op} needs ${type === 'bool' ? 'true or false' : 'numbers'}`);
+  };
+  switch (node.type) {
+    case 'literal':
+      return typeof node.value === 'boolean' ? 'bool' : 'number';
+    case 'variable':
+      return ALERT_VARIABLES[node.name];
+    case 'unary':
+      expect(node.arg, node.op === '!' ? 'bool' : 'number', node.op);
+      return node.op === '!' ? 'bool' : 'number';
+    case 'binary':
+      if (node.op === '&&' || node.op === '||') {
+        expect(node.left, 'bool', node.op);
+        expect(node.right, 'bool', node.op);
+        return 'bool';
+      }
+      if (node.op === '==' || node.op === '!=') {
+        if (typeOf(node.left) !== typeOf(node.right)) throw new Error(`${node.op} compares values of different types`);
+        return 'bool';
+      }
+      expect(node.left, 'number', node.op);
+      expect(node.right, 'number', node.op);
+      return PRECEDENCE[node.op] === PRECEDENCE['<'] ? 'bool' : 'number';
+  }
+}
+
+function evaluateNode(node: Node, variables: AlertVariables): Value {
+  switch (node.type) {
+    case 'literal':
+      return node.value;
+    case 'variable': {
+      const value = variables[node.name];
+      if (value === null) throw new Unknown();
+      return value;
+    }
+    case 'unary': {
+      const arg = evaluateNode(node.arg, variables);
+      return node.op === '!' ? !arg : -(arg as number);
+    }
+    case 'binary': {
+      const left = evaluateNode(node.left, variables);
+      if (node.op === '&&') return left && evaluateNode(node.right, variables);
+      if (node.op === '||') return left || evaluateNode(node.right, variables);
+      const right = evaluateNode(node.right, variables);
+      if (node.op === '==') return left === right;
+      if (node.op === '!=') return left !== right;
+      const [a, b] = [left as number, right as number];
+      switch (node.op) {
+        case '<': return a < b;
+        case '<=': return a <= b;
+        case '>': return a > b;
+        case '>=': return a >= b;
+        case '+': return a + b;
+        case '-': return a - b;
+        case '*': return a * b;
+        default: {
+          // A division by zero has no answer; treat it like an unknown variable.
+          if (b === 0) throw new Unknown();
+          return a / b;
+        }
+      }
+    }
+  }
+}
+
+export interface AlertExpression {
+  source: string;
+  // Whether the condition holds, or null when it reads an unknown variable.
+  evaluate(variables: AlertVariables): boolean | null;
+}
+
+export function compileAlertExpression(source: string): AlertExpression {
+  if (source.length > MAX_EXPRESSION_LENGTH) throw new Error(`must be at most ${MAX_EXPRESSION_LENGTH} characters`);
+  const node = parse(source);
+  if (typeOf(node) !== 'bool') throw new Error('must be true or false, e.g. a comparison');
+
+  return {
+    source,
+    evaluate(variables) {
+      try {
+        return evaluateNode(node, variables) as boolean;
+      } catch (error) {
+        if (error instanceof Unknown) return null;
+        throw error;
+      }
+    },
+  };
+}
+
+/**
+ * What an expression can read from a sample and the one before it.
+ */
+export function getAlertVariables(previous: StackSummary | null, current: StackSummary): AlertVariables {
+  const known = <T>(value: T) => (current.online ? value : null);
+  // Counters are cumulative, so compare the shares since the last sample.
+  const submitted = previous?.online ? current.sharesSubmitted - previous.sharesSubmitted : 0;
+  const rejected = previous?.online ? current.sharesRejected - previous.sharesRejected : 0;
+  return {
+    online: current.online,
+    hashrate: known(current.hashrate),
+    workers: known(current.workers),
+    miners: known(current.workers),
+    shares_accepted: known(current.sharesAccepted),
+    shares_submitted: known(current.sharesSubmitted),
+    shares_rejected: known(current.sharesRejected),
+    reject_rate: known(submitted > 0 && rejected >= 0 ? rejected / submitted : null),
+    best_difficulty: known(current.bestDifficulty),
+    blocks_found: known(current.blocksFound),
+    uptime_secs: known(current.uptimeSecs),
+  };
+}
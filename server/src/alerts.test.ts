@@ -0,0 +1,113 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { createAlertEngine, parseAlertRules, type AlertRule } from './alerts.js';
+import type { StackSummary } from './summary.js';
+
+function summary(minute: number, overrides: Partial<StackSummary> = {}): StackSummary {
+  return {
+    generatedAt: new Date(Date.UTC(2026, 0, 1, 0, minute)).toISOString(),
+    miningMode: 'pool',
+    mode: 'no-jd',
+    network: null,
+    poolName: null,
+    online: true,
+    hashrate: 1e12,
+    workers: 1,
+    sharesAccepted: 0,
+    sharesSubmitted: 0,
+    sharesRejected: 0,
+    bestDifficulty: 0,
+    blocksFound: 0,
+    uptimeSecs: 60,
+    ...overrides,
+  };
+}
+
+const hashrateRule: AlertRule = { id: 'low', kind: 'hashrate-below', threshold: 5e11, forMinutes: 2 };
+
+test('fires only after the condition holds for the configured time', () => {
+  const engine = createAlertEngine();
+  assert.deepEqual(engine.evaluate(summary(0, { hashrate: 1e11 }), [hashrateRule]), []);
+  assert.equal(engine.list([hashrateRule])[0].status, 'pending');
+  assert.deepEqual(engine.evaluate(summary(1, { hashrate: 1e11 }), [hashrateRule]), []);
+
+  const fired = engine.evaluate(summary(2, { hashrate: 1e11 }), [hashrateRule]);
+  assert.deepEqual(fired.map((t) => t.status), ['firing']);
+  assert.equal(engine.list([hashrateRule])[0].since, summary(0).generatedAt);
+
+  // Still firing: no repeat transition.
+  assert.deepEqual(engine.evaluate(summary(3, { hashrate: 1e11 }), [hashrateRule]), []);
+  assert.deepEqual(engine.evaluate(summary(4), [hashrateRule]).map((t) => t.status), ['resolved']);
+  assert.deepEqual(engine.transitions().map((t) => t.status), ['resolved', 'firing']);
+});
+
+test('a recovered sample clears a pending rule', () => {
+  const engine = createAlertEngine();
+  engine.evaluate(summary(0, { hashrate: 1e11 }), [hashrateRule]);
+  engine.evaluate(summary(1), [hashrateRule]);
+  assert.deepEqual(engine.evaluate(summary(2, { hashrate: 1e11 }), [hashrateRule]), []);
+  assert.equal(engine.list([hashrateRule])[0].since, summary(2).generatedAt);
+});
+
+test('measures the reject ratio between samples', () => {
+  const rule: AlertRule = { id: 'rejects', kind: 'reject-ratio-above', threshold: 0.1, forMinutes: 0 };
+  const engine = createAlertEngine();
+  assert.deepEqual(engine.evaluate(summary(0, { sharesSubmitted: 1000, sharesRejected: 500 }), [rule]), []);
+  // No new shares: nothing to measure.
+  assert.deepEqual(engine.evaluate(summary(1, { sharesSubmitted: 1000, sharesRejected: 500 }), [rule]), []);
+
+  const fired = engine.evaluate(summary(2, { sharesSubmitted: 1100, sharesRejected: 520 }), [rule]);
+  assert.equal(fired[0].status, 'firing');
+  assert.match(fired[0].message, /20\.0% above 10\.0%/);
+});
+
+test('unreachable rules ignore hashrate while offline', () => {
+  const rules = parseAlertRules({ rules: [{ kind: 'unreachable', forMinutes: 0 }, hashrateRule] });
+  const engine = createAlertEngine();
+  const fired = engine.evaluate(summary(0, { online: false, hashrate: 0 }), rules);
+  assert.deepEqual(fired.map((t) => t.kind), ['unreachable']);
+});
+
+test('expression rules fire on their condition and skip samples it cannot read', () => {
+  const [rule] = parseAlertRules({ rules: [{ id: 'few', kind: 'expression', expression: ' reject_rate > 0.05 && miners < 3 ', forMinutes: 0 }] });
+  assert.equal(rule.expression, 'reject_rate > 0.05 && miners < 3');
+  const engine = createAlertEngine();
+  // No previous sample, so no reject rate yet.
+  assert.deepEqual(engine.evaluate(summary(0, { sharesSubmitted: 1000, sharesRejected: 10 }), [rule]), []);
+  assert.equal(engine.list([rule])[0].value, null);
+
+  const [fired] = engine.evaluate(summary(1, { sharesSubmitted: 1100, sharesRejected: 20 }), [rule]);
+  assert.deepEqual({ ...fired, id: undefined }, {
+    id: undefined,
+    ruleId: 'few',
+    kind: 'expression',
+    status: 'firing',
+    message: 'Alert condition: reject_rate > 0.05 && miners < 3',
+    at: summary(1).generatedAt,
+    threshold: null,
+    value: 1,
+    params: { expression: 'reject_rate > 0.05 && miners < 3' },
+  });
+  assert.deepEqual(engine.evaluate(summary(2, { sharesSubmitted: 1100, sharesRejected: 20 }), [rule]), []);
+  assert.deepEqual(engine.evaluate(summary(3, { sharesSubmitted: 1200, sharesRejected: 20, workers: 3 }), [rule]).map((t) => t.status), ['resolved']);
+});
+
+test('reset forgets state without transitions', () => {
+  const rule: AlertRule = { ...hashrateRule, forMinutes: 0 };
+  const engine = createAlertEngine();
+  engine.evaluate(summary(0, { hashrate: 1e11 }), [rule]);
+  engine.reset();
+  assert.equal(engine.list([rule])[0].status, 'ok');
+  assert.deepEqual(engine.evaluate(summary(1), [rule]), []);
+});
+
+test('validates rules', () => {
+  assert.equal(parseAlertRules({ rules: [{ kind: 'hashrate-below', threshold: 1e12 }] })[0].forMinutes, 5);
+  assert.throws(() => parseAlertRules({}), /array/);
+  assert.throws(() => parseAlertRules({ rules: [{ kind: 'temperature' }] }), /kind/);
+  assert.throws(() => parseAlertRules({ rules: [{ kind: 'hashrate-below' }] }), /threshold/);
+  assert.throws(() => parseAlertRules({ rules: [{ kind: 'reject-ratio-above', threshold: 5 }] }), /ratio/);
+  assert.throws(() => parseAlertRules({ rules: [{ kind: 'unreachable', forMinutes: -1 }] }), /forMinutes/);
+  assert.throws(() => parseAlertRules({ rules: [{ kind: 'expression' }] }), /rules\[0\]\.expression is required/);
+  assert.throws(() => parseAlertRules({ rules: [{ kind: 'expression', expression: 'hashrate <' }] }), /rules\[0\]\.expression: unexpected end/);
+});
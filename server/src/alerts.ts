@@ -0,0 +1,348 @@
+/**
+ * Threshold alerting on the sampled stack summary.
+ *
+ * Rules live in CONFIG_DIR/alerts.json and are edited through
+ * PUT /api/alerts/rules. Each rule must hold for `forMinutes` before it
+ * fires; a firing rule resolves on the first sample where it no longer holds.
+ * Besides the fixed kinds, an `expression` rule's condition is a small Rhai
+ * expression over the sample (see alert-expressions.ts).
+ *
+ * Share anomalies (see share-anomalies.ts) are built in and need no rules. They
+ * fire as soon as they are seen and resolve after ANOMALY_QUIET_MS without a
+ * recurrence.
+ */
+
+import crypto from 'crypto';
+import fs from 'fs/promises';
+import path from 'path';
+import { formatHashrate } from '@sv2-ui/shared';
+import { compileAlertExpression, getAlertVariables } from './alert-expressions.js';
+import type { StackData } from './monitoring.js';
+import {
+  collectChannelShares,
+  detectShareAnomalies,
+  findSharedExtranonces,
+  type ChannelShareSample,
+  type ShareAnomaly,
+  type ShareAnomalyKind,
+} from './share-anomalies.js';
+import type { StackSummary } from './summary.js';
+
+export type AlertRuleKind = 'hashrate-below' | 'reject-ratio-above' | 'unreachable' | 'expression';
+
+export interface AlertRule {
+  id: string;
+  kind: AlertRuleKind;
+  // H/s for hashrate-below, a 0-1 ratio for reject-ratio-above; unused otherwise.
+  threshold: number;
+  forMinutes: number;
+  // The condition of an expression rule, e.g. `reject_rate > 0.05 && miners < 3`.
+  expression?: string;
+}
+
+export type AlertStatus = 'ok' | 'pending' | 'firing';
+
+export interface AlertState {
+  rule: AlertRule;
+  status: AlertStatus;
+  // When the condition started holding (pending or firing).
+  since: string | null;
+  value: number | null;
+}
+
+export interface ActiveAnomaly extends ShareAnomaly {
+  since: string;
+  lastSeen: string;
+}
+
+export interface AlertTransition {
+  id: string;
+  // Rule id, the anomaly key for share anomalies, or `image-update:<service>`.
+  ruleId: string;
+  kind: AlertRuleKind | ShareAnomalyKind | 'image-update';
+  status: 'firing' | 'resolved';
+  message: string;
+  at: string;
+  // Rule transitions only: the rule's threshold and the value that crossed it.
+  threshold?: number | null;
+  value?: number | null;
+  // Values for the localized message (see notification-messages.ts).
+  params?: Record<string, string | number>;
+}
+
+const RULE_KINDS: AlertRuleKind[] = ['hashrate-below', 'reject-ratio-above', 'unreachable', 'expression'];
+const DEFAULT_FOR_MINUTES = 5;
+const TRANSITION_LIMIT = 100;
+export const ANOMALY_QUIET_MS = 5 * 60_000;
+
+export const DEFAULT_ALERT_RULES: AlertRule[] = [
+  { id: 'unreachable', kind: 'unreachable', threshold: 0, forMinutes: 2 },
+];
+
+/**
+ * Validate a rules document, filling in ids for new rules.
+ */
+export function parseAlertRules(body: unknown): AlertRule[] {
+  const { rules } = (body ?? {}) as { rules?: unknown };
+  if (!Array.isArray(rules)) {
+    throw new Error('rules must be an array');
+  }
+
+  return rules.map((value, index) => {
+    const rule = (value ?? {}) as Record<string, unknown>;
+    const field = `rules[${index}]`;
+
+    if (!RULE_KINDS.includes(rule.kind as AlertRuleKind)) {
+      throw new Error(`${field}.kind must be one of ${RULE_KINDS.join(', ')}`);
+    }
+    const kind = rule.kind as AlertRuleKind;
+
+    const forMinutes = rule.forMinutes ?? DEFAULT_FOR_MINUTES;
+    if (typeof forMinutes !== 'number' || !Number.isFinite(forMinutes) || forMinutes < 0) {
+      throw new Error(`${field}.forMinutes must be a non-negative number`);
+    }
+
+    if (kind === 'expression') {
+      if (typeof rule.expression !== 'string' || !rule.expression.trim()) {
+        throw new Error(`${field}.expression is required`);
+      }
+      try {
+        compileAlertExpression(rule.expression);
+      } catch (error) {
+        throw new Error(`${field}.expression: ${error instanceof Error ? error.message : error}`);
+      }
+    }
+
+    let threshold = 0;
+    if (kind !== 'unreachable' && kind !== 'expression') {
+      threshold = rule.threshold as number;
+      if (typeof threshold !== 'number' || !Number.isFinite(threshold) || threshold <= 0) {
+        throw new Error(`${field}.threshold must be a positive number`);
+      }
+      if (kind === 'reject-ratio-above' && threshold >= 1) {
+        throw new Error(`${field}.threshold must be a ratio below 1`);
+      }
+    }
+
+    return {
+      id: typeof rule.id === 'string' && rule.id ? rule.id : crypto.randomUUID(),
+      kind,
+      threshold,
+      forMinutes,
+      ...(kind === 'expression' ? { expression: (rule.expression as string).trim() } : {}),
+    };
+  });
+}
+
+export function createAlertRuleStore(filePath: string) {
+  async function get(): Promise<AlertRule[]> {
+    try {
+      return parseAlertRules(JSON.parse(await fs.readFile(filePath, 'utf-8')));
+    } catch {
+      return DEFAULT_ALERT_RULES;
+    }
+  }
+
+  async function save(rules: AlertRule[]): Promise<void> {
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    await fs.writeFile(filePath, JSON.stringify({ rules }, null, 2));
+  }
+
+  return { get, save };
+}
+
+export type AlertRuleStore = ReturnType<typeof createAlertRuleStore>;
+
+/**
+ * Current value a rule compares against, or null when the sample says
+ * nothing about it (leaving the rule's status unchanged).
+ */
+function measure(rule: AlertRule, previous: StackSummary | null, current: StackSummary): number | null {
+  switch (rule.kind) {
+    case 'unreachable':
+      return current.online ? 0 : 1;
+    case 'expression': {
+      // Expressions are checked when saved, so compiling one again can't fail.
+      const holds = compileAlertExpression(rule.expression ?? '').evaluate(getAlertVariables(previous, current));
+      return holds === null ? null : Number(holds);
+    }
+    case 'hashrate-below':
+      return current.online ? current.hashrate : null;
+    case 'reject-ratio-above': {
+      if (!current.online || !previous?.online) return null;
+      // Counters are cumulative, so compare the shares since the last sample.
+      const submitted = current.sharesSubmitted - previous.sharesSubmitted;
+      const rejected = current.sharesRejected - previous.sharesRejected;
+      if (submitted <= 0 || rejected < 0) return null;
+      return rejected / submitted;
+    }
+  }
+}
+
+function isBreached(rule: AlertRule, value: number): boolean {
+  switch (rule.kind) {
+    case 'unreachable':
+    case 'expression':
+      return value === 1;
+    case 'hashrate-below':
+      return value < rule.threshold;
+    case 'reject-ratio-above':
+      return value > rule.threshold;
+  }
+}
+
+function describe(rule: AlertRule, value: number | null): string {
+  switch (rule.kind) {
+    case 'unreachable':
+      return 'Monitoring API unreachable';
+    case 'expression':
+      return `Alert condition: ${rule.expression}`;
+    case 'hashrate-below':
+      return `Hashrate ${formatHashrate(value ?? 0)} below ${formatHashrate(rule.threshold)}`;
+    case 'reject-ratio-above':
+      return `Rejected shares ${((value ?? 0) * 100).toFixed(1)}% above ${(rule.threshold * 100).toFixed(1)}%`;
+  }
+}
+
+export function createAlertEngine() {
+  const states = new Map<string, AlertState>();
+  const transitions: AlertTransition[] = [];
+  const anomalies = new Map<string, ActiveAnomaly>();
+  let previous: StackSummary | null = null;
+  let previousShares: { at: string; samples: ChannelShareSample[] } | null = null;
+
+  function push(entry: Omit<AlertTransition, 'id'>): AlertTransition {
+    const transition = { id: crypto.randomUUID(), ...entry };
+    transitions.unshift(transition);
+    transitions.length = Math.min(transitions.length, TRANSITION_LIMIT);
+    return transition;
+  }
+
+  function transition(state: AlertState, status: AlertTransition['status'], at: string): AlertTransition {
+    const message = status === 'firing'
+      ? describe(state.rule, state.value)
+      : `Resolved: ${describe(state.rule, state.value)}`;
+    return push({
+      ruleId: state.rule.id,
+      kind: state.rule.kind,
+      status,
+      message,
+      at,
+      threshold: state.rule.kind === 'unreachable' || state.rule.kind === 'expression' ? null : state.rule.threshold,
+      value: state.value,
+      ...(state.rule.expression ? { params: { expression: state.rule.expression } } : {}),
+    });
+  }
+
+  /**
+   * Feed the next summary sample; returns the transitions it caused.
+   */
+  function evaluate(current: StackSummary, rules: AlertRule[]): AlertTransition[] {
+    const now = Date.parse(current.generatedAt);
+    const changed: AlertTransition[] = [];
+
+    // Drop state for rules that were removed.
+    for (const id of states.keys()) {
+      if (!rules.some((rule) => rule.id === id)) states.delete(id);
+    }
+
+    for (const rule of rules) {
+      const existing = states.get(rule.id);
+      // Rules edited in place start over.
+      const state = existing && JSON.stringify(existing.rule) === JSON.stringify(rule)
+        ? existing
+        : { rule, status: 'ok' as AlertStatus, since: null, value: null };
+      states.set(rule.id, state);
+
+      const value = measure(rule, previous, current);
+      if (value === null) continue;
+
+      if (isBreached(rule, value)) {
+        state.value = value;
+        state.since = state.since ?? current.generatedAt;
+        if (state.status !== 'firing' && now - Date.parse(state.since) >= rule.forMinutes * 60_000) {
+          state.status = 'firing';
+          changed.push(transition(state, 'firing', current.generatedAt));
+        } else if (state.status === 'ok') {
+          state.status = 'pending';
+        }
+      } else {
+        if (state.status === 'firing') {
+          changed.push(transition(state, 'resolved', current.generatedAt));
+        }
+        state.value = value;
+        state.status = 'ok';
+        state.since = null;
+      }
+    }
+
+    previous = current;
+    return changed;
+  }
+
+  /**
+   * Feed the raw monitoring data behind the same sample; returns the share
+   * anomaly transitions it caused.
+   */
+  function evaluateAnomalies(data: StackData, at: string): AlertTransition[] {
+    const samples = collectChannelShares(data);
+    const seen = [
+      ...(previousShares ? detectShareAnomalies(previousShares.samples, samples, Date.parse(at) - Date.parse(previousShares.at)) : []),
+      ...findSharedExtranonces(data),
+    ];
+    previousShares = { at, samples };
+
+    const changed: AlertTransition[] = [];
+    for (const anomaly of seen) {
+      const active = anomalies.get(anomaly.key);
+      if (active) {
+        Object.assign(active, anomaly, { lastSeen: at });
+        continue;
+      }
+      anomalies.set(anomaly.key, { ...anomaly, since: at, lastSeen: at });
+      changed.push(push({ ruleId: anomaly.key, kind: anomaly.kind, status: 'firing', message: anomaly.message, params: anomaly.params, at }));
+    }
+
+    for (const [key, active] of anomalies) {
+      if (Date.parse(at) - Date.parse(active.lastSeen) >= ANOMALY_QUIET_MS) {
+        anomalies.delete(key);
+        changed.push(push({
+          ruleId: key,
+          kind: active.kind,
+          status: 'resolved',
+          message: `Resolved: ${active.message}`,
+          params: active.params,
+          at,
+        }));
+      }
+    }
+
+    return changed;
+  }
+
+  /**
+   * Forget all state without emitting transitions, e.g. when the stack is
+   * stopped on purpose.
+   */
+  function reset(): void {
+    states.clear();
+    anomalies.clear();
+    previous = null;
+    previousShares = null;
+  }
+
+  function list(rules: AlertRule[]): AlertState[] {
+    return rules.map((rule) => states.get(rule.id) ?? { rule, status: 'ok', since: null, value: null });
+  }
+
+  return {
+    evaluate,
+    evaluateAnomalies,
+    reset,
+    list,
+    anomalies: () => [...anomalies.values()],
+    transitions: () => [...transitions],
+  };
+}
+
+export type AlertEngine = ReturnType<typeof createAlertEngine>;
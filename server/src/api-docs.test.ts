@@ -0,0 +1,52 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { test } from 'node:test';
+import { fileURLToPath } from 'node:url';
+
+import { API_SCHEMAS, MEDIA_RESPONSES, ROUTE_DOCS } from './api-docs.js';
+
+const SOURCE_DIR = path.dirname(fileURLToPath(import.meta.url));
+const ROUTE_PATTERN = /\b(?:router|app)\.(get|post|put|patch|delete)\(\s*'([^']+)'(?:\s*,\s*(requireAdmin|requireOperator)\b)?/g;
+
+// Every route registration in index.ts and routes/, as `METHOD /openapi/{path}`,
+// with the role guard in front of it.
+async function getRegisteredRoutes(): Promise<Map<string, string | undefined>> {
+  const files = [
+    path.join(SOURCE_DIR, 'index.ts'),
+    ...(await fs.readdir(path.join(SOURCE_DIR, 'routes')))
+      .filter((name) => name.endsWith('.ts') && !name.endsWith('.test.ts'))
+      .map((name) => path.join(SOURCE_DIR, 'routes', name)),
+  ];
+  const routes = new Map<string, string | undefined>();
+  for (const file of files) {
+    for (const [, method, route, guard] of (await fs.readFile(file, 'utf-8')).matchAll(ROUTE_PATTERN)) {
+      if (route.includes('*')) continue;
+      routes.set(`${method.toUpperCase()} ${route.replace(/:(\w+)/g, '{$1}')}`, guard);
+    }
+  }
+  return routes;
+}
+
+test('every registered route is documented, with the right admin and operator flags', async () => {
+  const routes = await getRegisteredRoutes();
+  assert.ok(routes.size > 50);
+  for (const [route, guard] of routes) {
+    assert.ok(ROUTE_DOCS[route], `${route} has no entry in ROUTE_DOCS`);
+    assert.equal(Boolean(ROUTE_DOCS[route].admin), guard === 'requireAdmin', `${route} admin flag`);
+    assert.equal(Boolean(ROUTE_DOCS[route].operator), guard === 'requireOperator', `${route} operator flag`);
+  }
+  for (const route of Object.keys(ROUTE_DOCS)) {
+    assert.ok(routes.has(route), `ROUTE_DOCS documents ${route}, which isn't registered`);
+  }
+});
+
+test('documented responses and bodies name a schema or a media type', () => {
+  const refs = JSON.stringify(API_SCHEMAS).matchAll(/#\/components\/schemas\/(\w+)/g);
+  for (const [, name] of refs) assert.ok(API_SCHEMAS[name], `${name} is referenced but not defined`);
+
+  for (const [route, doc] of Object.entries(ROUTE_DOCS)) {
+    if (doc.response) assert.ok(API_SCHEMAS[doc.response] || MEDIA_RESPONSES.includes(doc.response), `${route} response ${doc.response}`);
+    if (doc.body) assert.ok(API_SCHEMAS[doc.body], `${route} body ${doc.body}`);
+  }
+});
@@ -0,0 +1,867 @@
+/**
+ * Descriptions of the server's own routes for /api/openapi.json.
+ *
+ * The router says which routes exist (see api-explorer.ts); this catalog adds
+ * what it can't: a summary, whether the route is admin-only, its query
+ * parameters and the shape of its response. api-docs.test.ts fails when a
+ * route is registered without an entry here, so new routes get documented
+ * as they're added.
+ */
+
+import type { OpenApiParameter } from './api-explorer.js';
+
+export type JsonSchema = Record<string, unknown>;
+
+export interface RouteDoc {
+  summary: string;
+  description?: string;
+  // Requires ADMIN_TOKEN (or an admin role from a user account, auth hook or tunnel).
+  admin?: boolean;
+  // Requires the operator or admin role (see auth.ts).
+  operator?: boolean;
+  query?: OpenApiParameter[];
+  // A schema in API_SCHEMAS, or a media type for responses that aren't JSON.
+  response?: string;
+  // A schema in API_SCHEMAS for the JSON request body.
+  body?: string;
+}
+
+const ref = (name: string) => ({ $ref: `#/components/schemas/${name}` });
+const nullable = (type: string) => ({ type: [type, 'null'] });
+const query = (name: string, description: string): OpenApiParameter => ({ name, in: 'query', description });
+
+// Media types for routes that don't answer JSON.
+export const MEDIA_RESPONSES = ['text/html', 'image/svg+xml', 'image/png', 'application/atom+xml', 'text/calendar', 'text/event-stream', 'text/csv'];
+
+export const API_SCHEMAS: Record<string, JsonSchema> = {
+  Error: {
+    type: 'object',
+    required: ['error'],
+    properties: { error: { type: 'string' } },
+  },
+  Success: {
+    type: 'object',
+    required: ['success'],
+    properties: { success: { type: 'boolean' }, error: { type: 'string' } },
+  },
+  ContainerStatus: {
+    type: 'object',
+    required: ['id', 'name', 'status', 'ports'],
+    properties: {
+      id: { type: 'string' },
+      name: { type: 'string' },
+      status: { enum: ['healthy', 'unhealthy', 'starting', 'stopped'] },
+      ports: { type: 'object', additionalProperties: { type: 'string' } },
+    },
+  },
+  StatusResponse: {
+    type: 'object',
+    required: ['configured', 'running', 'miningMode', 'mode', 'poolName', 'network', 'containers'],
+    properties: {
+      configured: { type: 'boolean' },
+      running: { type: 'boolean' },
+      autoStarting: { type: 'boolean' },
+      shouldBeRunning: { type: 'boolean' },
+      miningMode: { enum: ['solo', 'pool', null] },
+      mode: { enum: ['jd', 'no-jd', null] },
+      poolName: nullable('string'),
+      network: { enum: ['mainnet', 'testnet4', 'signet', 'regtest', null] },
+      containers: {
+        type: 'object',
+        properties: {
+          translator: { oneOf: [{ type: 'null' }, ref('ContainerStatus')] },
+          jdc: { oneOf: [{ type: 'null' }, ref('ContainerStatus')] },
+        },
+      },
+    },
+  },
+  StackSummary: {
+    type: 'object',
+    required: ['generatedAt', 'online', 'hashrate', 'workers', 'sharesAccepted', 'sharesSubmitted', 'sharesRejected', 'bestDifficulty', 'blocksFound', 'uptimeSecs'],
+    properties: {
+      generatedAt: { type: 'string', format: 'date-time' },
+      miningMode: { enum: ['solo', 'pool', null] },
+      mode: { enum: ['jd', 'no-jd', null] },
+      network: { enum: ['mainnet', 'testnet4', 'signet', 'regtest', null] },
+      poolName: nullable('string'),
+      online: { type: 'boolean' },
+      hashrate: { type: 'number', description: 'Hashes per second' },
+      workers: { type: 'integer' },
+      sharesAccepted: { type: 'integer' },
+      sharesSubmitted: { type: 'integer' },
+      sharesRejected: { type: 'integer' },
+      bestDifficulty: { type: 'number' },
+      blocksFound: { type: 'integer' },
+      uptimeSecs: { type: 'integer' },
+    },
+  },
+  SummaryResponse: {
+    allOf: [ref('StackSummary'), {
+      type: 'object',
+      properties: {
+        freshness: { type: 'object', description: 'When each section of the snapshot was last fetched' },
+        errors: { type: 'array', items: { type: 'object' }, description: 'Why sections of the snapshot are missing' },
+        shareWindows: ref('ShareWindows'),
+        lifetime: ref('LifetimeCounters'),
+      },
+    }],
+  },
+  PollSchedule: {
+    type: 'object',
+    required: ['services'],
+    properties: {
+      services: {
+        type: 'array',
+        items: {
+          type: 'object',
+          required: ['service', 'intervalMs', 'jitterMs', 'sections', 'lastPolledAt', 'nextPollAt'],
+          properties: {
+            service: { type: 'string', enum: ['translator', 'jdc'] },
+            intervalMs: { type: 'integer' },
+            jitterMs: { type: 'integer', description: 'Each poll lands up to this much before or after its interval' },
+            sections: { type: 'array', items: { type: 'string' }, description: 'What a poll of the service fetches in the current mode' },
+            lastPolledAt: { ...nullable('string'), format: 'date-time' },
+            nextPollAt: { ...nullable('string'), format: 'date-time' },
+          },
+        },
+      },
+    },
+  },
+  LifetimeCounters: {
+    type: 'object',
+    description: 'Stack counters added up across restarts of sv2-ui, the Translator and the JDC',
+    required: ['since', 'sharesAccepted', 'sharesSubmitted', 'sharesRejected', 'blocksFound', 'uptimeSecs', 'sessions', 'bestDifficulty'],
+    properties: {
+      since: { ...nullable('string'), format: 'date-time', description: 'When counting started; null before the first poll' },
+      sharesAccepted: { type: 'integer' },
+      sharesSubmitted: { type: 'integer' },
+      sharesRejected: { type: 'integer' },
+      blocksFound: { type: 'integer' },
+      uptimeSecs: { type: 'integer', description: 'Uptime of the Translator, or the JDC in JD mode, across its restarts' },
+      sessions: { type: 'integer', description: 'How many times it was seen starting' },
+      bestDifficulty: { type: 'number', description: 'The best share overall, as in /api/v1/best-shares' },
+    },
+  },
+  ShareWindows: {
+    type: 'object',
+    description: 'Accepted and rejected shares over the last 5m, 1h and 24h, counted from polled counters since the server started',
+    required: ['coveredSecs', 'total', 'miners'],
+    properties: {
+      coveredSecs: { type: 'integer', description: 'How much of the windows has been seen, up to 86400' },
+      total: ref('ShareWindowCounts'),
+      miners: {
+        type: 'array',
+        items: { allOf: [ref('ShareWindowCounts'), { type: 'object', properties: { id: { type: 'string', description: 'User identity of the channel' } } }] },
+      },
+    },
+  },
+  ShareWindowCounts: {
+    type: 'object',
+    properties: { '5m': ref('ShareCounts'), '1h': ref('ShareCounts'), '24h': ref('ShareCounts') },
+  },
+  ShareCounts: {
+    type: 'object',
+    required: ['accepted', 'rejected', 'rejectRate'],
+    properties: {
+      accepted: { type: 'integer' },
+      rejected: { type: 'integer' },
+      rejectRate: { ...nullable('number'), description: 'Rejected out of all shares; null before any' },
+    },
+  },
+  StackEvent: {
+    type: 'object',
+    required: ['id', 'kind', 'title', 'detail', 'at'],
+    properties: {
+      id: { type: 'string' },
+      kind: { type: 'string' },
+      title: { type: 'string' },
+      detail: { type: 'string' },
+      at: { type: 'string', format: 'date-time' },
+    },
+  },
+  StackEvents: {
+    type: 'object',
+    required: ['events'],
+    properties: { events: { type: 'array', items: ref('StackEvent') } },
+  },
+  ServiceHealth: {
+    type: 'object',
+    required: ['id', 'service', 'label', 'stack', 'reachable', 'httpStatus', 'latencyMs', 'lastSuccessAt', 'checkedAt', 'error'],
+    properties: {
+      id: { type: 'string', description: "'translator', or '<stack id>/translator' for a remote stack" },
+      service: { enum: ['translator', 'jdc'] },
+      label: { type: 'string' },
+      stack: { oneOf: [{ type: 'null' }, { type: 'object', properties: { id: { type: 'string' }, name: { type: 'string' } } }] },
+      reachable: { type: 'boolean' },
+      httpStatus: nullable('integer'),
+      latencyMs: nullable('number'),
+      lastSuccessAt: { ...nullable('string'), format: 'date-time' },
+      checkedAt: { type: 'string', format: 'date-time' },
+      error: nullable('string'),
+    },
+  },
+  Sv1ProbeResult: {
+    type: 'object',
+    required: ['ok', 'host', 'port', 'user', 'steps', 'checkedAt'],
+    properties: {
+      ok: { type: 'boolean' },
+      host: { type: 'string' },
+      port: { type: 'integer' },
+      user: { type: 'string' },
+      steps: {
+        type: 'array',
+        items: {
+          type: 'object',
+          properties: {
+            step: { enum: ['connect', 'subscribe', 'authorize'] },
+            ok: { type: 'boolean' },
+            durationMs: { type: 'number' },
+            detail: nullable('string'),
+            error: nullable('string'),
+          },
+        },
+      },
+      extranonce1: nullable('string'),
+      extranonce2Size: nullable('integer'),
+      checkedAt: { type: 'string', format: 'date-time' },
+    },
+  },
+  SiteHealth: {
+    type: 'object',
+    required: ['id', 'name', 'reachable', 'httpStatus', 'latencyMs', 'status', 'lastSuccessAt', 'checkedAt', 'error'],
+    properties: {
+      id: { type: 'string' },
+      name: { type: 'string' },
+      reachable: { type: 'boolean', description: 'The site answered with its health report' },
+      httpStatus: nullable('integer'),
+      latencyMs: nullable('number'),
+      status: { oneOf: [{ type: 'null' }, { enum: ['ok', 'degraded', 'down'] }], description: "The site's own health status" },
+      lastSuccessAt: { ...nullable('string'), format: 'date-time' },
+      checkedAt: { type: 'string', format: 'date-time' },
+      error: nullable('string'),
+    },
+  },
+  HealthReport: {
+    type: 'object',
+    required: ['status', 'checkedAt', 'docker', 'services', 'sv1', 'sites'],
+    properties: {
+      status: { enum: ['ok', 'degraded', 'down'] },
+      checkedAt: { type: 'string', format: 'date-time' },
+      docker: { type: 'boolean' },
+      services: { type: 'array', items: ref('ServiceHealth') },
+      sv1: { oneOf: [{ type: 'null' }, ref('Sv1ProbeResult')] },
+      sites: { type: 'array', items: ref('SiteHealth'), description: 'Federated sv2-ui instances, as of their last background check' },
+    },
+  },
+  Sv1ListenerStatus: {
+    type: 'object',
+    required: ['state', 'accepting', 'connections', 'host', 'port', 'checkedAt'],
+    properties: {
+      state: { type: 'string' },
+      accepting: { type: 'boolean' },
+      connections: { ...nullable('integer'), description: "Null when the Translator's monitoring API couldn't be reached" },
+      host: { type: 'string' },
+      port: { type: 'integer' },
+      checkedAt: { type: 'string', format: 'date-time' },
+      error: { type: 'string' },
+    },
+  },
+  MinerHistory: {
+    type: 'object',
+    required: ['id', 'resolutionSecs', 'samples'],
+    properties: {
+      id: { type: 'string', description: 'The worker name the Translator reports' },
+      resolutionSecs: { ...nullable('integer'), description: 'Null for raw samples; otherwise the samples are rollup averages at this resolution' },
+      samples: {
+        type: 'array',
+        items: {
+          type: 'object',
+          properties: {
+            at: { type: 'string', format: 'date-time' },
+            hashrate: { ...nullable('number'), description: 'Hashes per second' },
+          },
+        },
+      },
+    },
+  },
+  FederationSiteInput: {
+    type: 'object',
+    required: ['id', 'name', 'url'],
+    properties: {
+      id: { type: 'string', description: '1-32 lowercase letters, digits or dashes' },
+      name: { type: 'string' },
+      url: { type: 'string', description: "The instance's root, including any BASE_PATH" },
+      token: { ...nullable('string'), description: 'Bearer token for the site, ideally one of its API keys. Left out, the stored one is kept; null clears it' },
+    },
+  },
+  FederationSitesInput: {
+    type: 'object',
+    required: ['sites'],
+    properties: {
+      sites: { type: 'array', items: ref('FederationSiteInput') },
+    },
+  },
+  FederationSites: {
+    type: 'object',
+    required: ['sites'],
+    properties: {
+      sites: { type: 'array', items: ref('FederationSite') },
+    },
+  },
+  FederationSite: {
+    type: 'object',
+    required: ['id', 'name', 'url', 'hasToken'],
+    properties: {
+      id: { type: 'string' },
+      name: { type: 'string' },
+      url: { type: 'string' },
+      hasToken: { type: 'boolean', description: 'Tokens are never returned' },
+    },
+  },
+  FleetView: {
+    type: 'object',
+    required: ['generatedAt', 'total', 'sites'],
+    properties: {
+      generatedAt: { type: 'string', format: 'date-time' },
+      total: {
+        type: 'object',
+        description: 'Sums over the sites that are online; bestDifficulty is the highest',
+        properties: {
+          sites: { type: 'integer' },
+          sitesOnline: { type: 'integer' },
+          hashrate: { type: 'number' },
+          workers: { type: 'integer' },
+          sharesAccepted: { type: 'integer' },
+          sharesSubmitted: { type: 'integer' },
+          sharesRejected: { type: 'integer' },
+          bestDifficulty: { type: 'number' },
+          blocksFound: { type: 'integer' },
+        },
+      },
+      sites: {
+        type: 'array',
+        items: {
+          allOf: [ref('FederationSite'), {
+            type: 'object',
+            properties: {
+              ok: { type: 'boolean' },
+              error: nullable('string'),
+              fetchedAt: { ...nullable('string'), format: 'date-time' },
+              summary: { oneOf: [{ type: 'null' }, ref('StackSummary')] },
+            },
+          }],
+        },
+      },
+    },
+  },
+  FleetHistory: {
+    type: 'object',
+    required: ['metric', 'range', 'resolution', 'total', 'sites'],
+    properties: {
+      metric: { type: 'string' },
+      range: { type: 'string' },
+      resolution: { type: 'string' },
+      total: { type: 'array', description: 'Summed per timestamp over the sites with a sample there', items: ref('FleetHistoryPoint') },
+      sites: {
+        type: 'array',
+        items: {
+          type: 'object',
+          properties: {
+            id: { type: 'string' },
+            error: nullable('string'),
+            samples: { type: 'array', items: ref('FleetHistoryPoint') },
+          },
+        },
+      },
+    },
+  },
+  FleetHistoryPoint: {
+    type: 'object',
+    properties: {
+      at: { type: 'string', format: 'date-time' },
+      value: { type: 'number' },
+    },
+  },
+  MinerLabelInput: {
+    type: 'object',
+    properties: {
+      name: { ...nullable('string'), description: 'At most 64 characters' },
+      location: { ...nullable('string'), description: 'At most 64 characters' },
+      tags: { type: 'array', items: { type: 'string' }, description: 'At most 16 tags of up to 32 characters' },
+    },
+  },
+  MinerLabel: {
+    type: 'object',
+    required: ['id', 'name', 'location', 'tags', 'updatedAt'],
+    properties: {
+      id: { type: 'string', description: 'The worker name the Translator reports' },
+      name: nullable('string'),
+      location: nullable('string'),
+      tags: { type: 'array', items: { type: 'string' } },
+      updatedAt: { type: 'string', format: 'date-time' },
+    },
+  },
+  MinerLabels: {
+    type: 'object',
+    required: ['labels'],
+    properties: {
+      labels: { type: 'array', items: ref('MinerLabel') },
+    },
+  },
+  VardiffResponse: {
+    type: 'object',
+    required: ['enabled', 'sharesPerMinute', 'channels', 'retargets'],
+    properties: {
+      enabled: { ...nullable('boolean'), description: "The Translator's enable_vardiff; null until the stack is configured" },
+      sharesPerMinute: { ...nullable('number'), description: "The Translator's configured shares per minute" },
+      channels: {
+        type: 'array',
+        items: {
+          type: 'object',
+          properties: {
+            key: { type: 'string', description: 'translator:sv1:<client_id> or jdc:client:<client_id>:<channel_id>' },
+            service: { type: 'string', enum: ['translator', 'jdc'] },
+            worker: { type: 'string' },
+            targetHex: { type: 'string' },
+            difficulty: nullable('number'),
+            configuredSharesPerMinute: nullable('number'),
+            achievedSharesPerMinute: { ...nullable('number'), description: 'Since the previous poll; null when the share counters are unknown' },
+            retargets: { type: 'integer', description: 'Seen since the channel was first polled' },
+            lastRetargetAt: { ...nullable('string'), format: 'date-time' },
+          },
+        },
+      },
+      retargets: {
+        type: 'array',
+        description: 'Most recent first',
+        items: {
+          type: 'object',
+          properties: {
+            at: { type: 'string', format: 'date-time' },
+            key: { type: 'string' },
+            worker: { type: 'string' },
+            fromDifficulty: nullable('number'),
+            toDifficulty: nullable('number'),
+          },
+        },
+      },
+    },
+  },
+  VardiffHistory: {
+    type: 'object',
+    required: ['channel', 'resolutionSecs', 'samples'],
+    properties: {
+      channel: { type: 'string' },
+      resolutionSecs: { ...nullable('integer'), description: 'Null for raw samples; otherwise the samples are rollup averages at this resolution' },
+      samples: {
+        type: 'array',
+        items: {
+          type: 'object',
+          properties: {
+            at: { type: 'string', format: 'date-time' },
+            difficulty: nullable('number'),
+            sharesPerMinute: nullable('number'),
+          },
+        },
+      },
+    },
+  },
+  BestShares: {
+    type: 'object',
+    required: ['overall', 'miners'],
+    properties: {
+      overall: {
+        oneOf: [{ type: 'null' }, {
+          type: 'object',
+          properties: {
+            difficulty: { type: 'number' },
+            at: { type: 'string', format: 'date-time', description: 'When a poll first saw it' },
+            miner: { type: 'string' },
+          },
+        }],
+      },
+      miners: {
+        type: 'array',
+        description: 'Best first',
+        items: {
+          type: 'object',
+          properties: {
+            id: { type: 'string', description: 'User identity of the channel' },
+            difficulty: { type: 'number' },
+            at: { type: 'string', format: 'date-time' },
+          },
+        },
+      },
+    },
+  },
+  PriceStatus: {
+    type: 'object',
+    required: ['enabled', 'provider', 'currency', 'price', 'fetchedAt', 'stale', 'error'],
+    properties: {
+      enabled: { type: 'boolean', description: 'False without PRICE_PROVIDER' },
+      provider: { enum: ['mempool', 'coingecko', null] },
+      currency: { ...nullable('string'), description: 'ISO 4217 code, e.g. USD' },
+      price: { ...nullable('number'), description: 'Null until the provider has answered once' },
+      fetchedAt: { ...nullable('string'), format: 'date-time' },
+      stale: { type: 'boolean', description: 'The last fetch failed, so the price is older than PRICE_CACHE_SECS' },
+      error: nullable('string'),
+    },
+  },
+  User: {
+    type: 'object',
+    required: ['id', 'username', 'role', 'createdAt', 'updatedAt'],
+    properties: {
+      id: { type: 'string' },
+      username: { type: 'string' },
+      role: { enum: ['viewer', 'operator', 'admin'] },
+      createdAt: { type: 'string', format: 'date-time' },
+      updatedAt: { type: 'string', format: 'date-time' },
+    },
+  },
+  UserList: {
+    type: 'object',
+    required: ['users'],
+    properties: { users: { type: 'array', items: ref('User') } },
+  },
+  LoginInput: {
+    type: 'object',
+    required: ['username', 'password'],
+    properties: {
+      username: { type: 'string' },
+      password: { type: 'string' },
+    },
+  },
+  Session: {
+    type: 'object',
+    required: ['user', 'expiresAt', 'idleTimeoutSecs'],
+    properties: {
+      user: ref('User'),
+      expiresAt: { type: 'string', format: 'date-time', description: 'When the session ends at the latest (SESSION_LIFETIME_SECS)' },
+      idleTimeoutSecs: { type: 'number', description: 'The session also ends this long after its last request (SESSION_IDLE_SECS)' },
+    },
+  },
+  Me: {
+    type: 'object',
+    required: ['role', 'user'],
+    properties: {
+      role: { enum: ['viewer', 'operator', 'admin'] },
+      user: nullable('string'),
+    },
+  },
+  PluginStatus: {
+    type: 'object',
+    required: ['id', 'name', 'intervalSecs', 'metrics', 'updatedAt', 'error'],
+    properties: {
+      id: { type: 'string', description: 'The manifest file name, without .json' },
+      name: { type: 'string' },
+      intervalSecs: { type: 'integer' },
+      metrics: {
+        type: 'array',
+        items: {
+          type: 'object',
+          required: ['name', 'value', 'unit'],
+          properties: { name: { type: 'string' }, value: nullable('number'), unit: nullable('string') },
+        },
+      },
+      updatedAt: { ...nullable('string'), format: 'date-time', description: 'Null before the first successful run' },
+      error: { ...nullable('string'), description: 'Why the last run failed; metrics are from the run before' },
+    },
+  },
+  PluginList: {
+    type: 'object',
+    required: ['plugins', 'errors'],
+    properties: {
+      plugins: { type: 'array', items: ref('PluginStatus') },
+      errors: {
+        type: 'array',
+        description: 'Manifests in PLUGINS_DIR that did not load',
+        items: { type: 'object', required: ['file', 'error'], properties: { file: { type: 'string' }, error: { type: 'string' } } },
+      },
+    },
+  },
+  LatencyResponse: {
+    type: 'object',
+    required: ['enabled', 'intervalSecs', 'handshake', 'latest', 'series'],
+    properties: {
+      enabled: { type: 'boolean' },
+      intervalSecs: nullable('integer'),
+      handshake: { type: 'boolean' },
+      resolutionSecs: { ...nullable('integer'), description: 'Null for raw samples; otherwise the series are rollup averages at this resolution' },
+      latest: {
+        type: 'array',
+        items: {
+          type: 'object',
+          properties: {
+            target: { enum: ['pool', 'jds'] },
+            at: { type: 'string', format: 'date-time' },
+            connectMs: nullable('number'),
+            handshakeMs: nullable('number'),
+            error: nullable('string'),
+          },
+        },
+      },
+      series: {
+        type: 'object',
+        additionalProperties: {
+          type: 'array',
+          items: {
+            type: 'object',
+            properties: {
+              at: { type: 'string', format: 'date-time' },
+              connectMs: nullable('number'),
+              handshakeMs: nullable('number'),
+            },
+          },
+        },
+      },
+    },
+  },
+  SetupData: {
+    type: 'object',
+    description: 'What the setup wizard collects; see shared/src/types.ts',
+    properties: {
+      miningMode: { enum: ['solo', 'pool', null] },
+      mode: { enum: ['jd', 'no-jd', null] },
+      pool: { type: ['object', 'null'] },
+      bitcoin: { type: ['object', 'null'] },
+      jdc: { type: ['object', 'null'] },
+      translator: { type: ['object', 'null'] },
+    },
+  },
+};
+
+const backend = query('backend', 'translator, jdc or all (default all)');
+
+/**
+ * Keyed by method and OpenAPI path, as in `GET /api/stacks/{id}/summary`.
+ */
+export const ROUTE_DOCS: Record<string, RouteDoc> = {
+  'GET /api': { summary: 'API explorer with a try-it form per endpoint', response: 'text/html' },
+  'GET /docs': { summary: 'API explorer with a try-it form per endpoint', response: 'text/html' },
+  'GET /api/openapi.json': { summary: "OpenAPI document for the server's routes and the proxied monitoring APIs" },
+  'GET /api/status': { summary: 'Current stack status', response: 'StatusResponse' },
+  'GET /api/config': { summary: 'Current configuration' },
+  'PUT /api/config': { summary: 'Update configuration and restart with new values', admin: true, body: 'SetupData', response: 'Success' },
+  'POST /api/setup': { summary: 'Configure and start the stack', admin: true, body: 'SetupData', response: 'Success' },
+  'POST /api/stop': { summary: 'Stop the stack', operator: true, response: 'Success' },
+  'POST /api/restart': { summary: 'Restart the stack', operator: true, response: 'Success' },
+  'POST /api/reset': { summary: 'Reset configuration (stop containers and delete config)', admin: true, response: 'Success' },
+  'GET /api/env': { summary: 'Host environment variables relevant to the UI' },
+  'GET /api/health': { summary: 'Health check with Docker availability' },
+  'GET /api/v1/health': {
+    summary: 'Reachability, HTTP status and latency of every monitoring API, plus the SV1 self-test and federated sites',
+    description: 'Answers 503 unless every service is healthy and every federated site can be reached.',
+    response: 'HealthReport',
+  },
+  'GET /api/v1/monitoring/{service}/health': {
+    summary: 'Monitoring API health of the Translator or JDC',
+    description: 'service is translator or jdc. This and the other /api/v1/monitoring reads answer in sv2-ui\'s v1 schema, the shapes of the monitoring API below, whatever the upstream release sends.',
+  },
+  'GET /api/v1/monitoring/{service}/global': { summary: 'Global statistics of the Translator or JDC, normalized' },
+  'GET /api/v1/monitoring/{service}/server/channels': {
+    summary: 'Upstream channels of the Translator or JDC, normalized',
+    query: [query('offset', 'First channel to return'), query('limit', 'Channels to return')],
+  },
+  'GET /api/v1/monitoring/{service}/sv1/clients': {
+    summary: 'SV1 miners connected to the Translator, normalized, with their labels',
+    query: [query('offset', 'First client to return'), query('limit', 'Clients to return')],
+  },
+  'GET /api/v1/monitoring/{service}/clients': {
+    summary: 'SV2 clients connected to the JDC, normalized',
+    query: [query('offset', 'First client to return'), query('limit', 'Clients to return')],
+  },
+  'GET /api/v1/monitoring/{service}/clients/{id}/channels': {
+    summary: 'Channels of one SV2 client, normalized',
+    query: [query('offset', 'First channel to return'), query('limit', 'Channels to return')],
+  },
+  'GET /livez': { summary: 'Process liveness; answers as long as the server is serving requests' },
+  'GET /readyz': { summary: '200 once the UI build is in place and the stack is reachable (or the grace period is over), 503 before' },
+  'GET /api/debug/runtime': { summary: 'Process memory, task, and cache introspection', admin: true },
+  'GET /api/v1/polling': { summary: "Each service's poll interval and jitter, when it was last polled and when it's next due", response: 'PollSchedule' },
+  'GET /api/summary': { summary: 'Aggregate hashrate, shares, and best difficulty for the stack, with per-section freshness', response: 'SummaryResponse' },
+  'GET /api/summary/stream': { summary: 'Server-sent events with the summary, then JSON Patch operations with what changed after each poll', response: 'text/event-stream' },
+  'GET /api/v1/snapshot': {
+    summary: 'Everything the server currently knows in one JSON document, for bug reports and backups',
+    description: 'Health, the stack summary, active alerts, connected miners and the configuration. Integrations are listed as on or off, never with their credentials.',
+  },
+  'GET /api/events': { summary: 'Recent notable events (blocks found, best shares, outages)', response: 'StackEvents' },
+  'GET /api/v1/best-shares': { summary: 'The best share difficulty per miner and overall, kept across restarts', response: 'BestShares' },
+  'DELETE /api/v1/best-shares': { summary: 'Forget every best share', admin: true, response: 'Success' },
+  'GET /api/realtime': { summary: 'Server-sent events with refresh hints, stack events and new blocks', response: 'text/event-stream' },
+  'GET /api/alerts': { summary: 'Current status of each alert rule, active share anomalies, and recent transitions' },
+  'GET /api/alerts/rules': { summary: 'Configured alert rules' },
+  'PUT /api/alerts/rules': { summary: 'Replace the alert rules', admin: true },
+  'GET /api/audit-log': {
+    summary: 'Mutating proxied requests, newest first',
+    admin: true,
+    query: [query('service', 'translator or jdc'), query('since', 'ISO 8601 timestamp'), query('limit', 'Maximum entries')],
+  },
+  'POST /api/poll-now': { summary: 'Force an immediate refresh of the monitoring snapshot', operator: true, query: [backend] },
+  'POST /api/warm-restart': {
+    summary: 'Restart services without recreating them',
+    description: 'Pauses alerts, restarts, waits for the monitoring API to answer and polls, streaming progress as newline-delimited JSON.',
+    operator: true,
+    query: [backend],
+  },
+  'GET /api/retarget': { summary: 'Blocks and time until the next difficulty retarget, and its effect on expected time to block' },
+  'GET /api/schedule': { summary: 'Planned maintenance windows and report runs' },
+  'PUT /api/schedule': { summary: 'Replace the maintenance and report schedule', admin: true },
+  'GET /calendar.ics': { summary: 'iCalendar feed of the maintenance and report schedule', response: 'text/calendar' },
+  'GET /api/stacks': { summary: 'Remote stacks with their latest sampled summary' },
+  'PUT /api/stacks': { summary: 'Replace the remote stacks', admin: true },
+  'GET /api/stacks/{id}/summary': { summary: 'Live summary of one remote stack', response: 'StackSummary' },
+  'GET /api/stacks/{id}/alerts': { summary: 'Alert status of one remote stack, using the shared rules' },
+  'GET /api/stacks/{id}/events': { summary: 'Recent notable events of one remote stack', response: 'StackEvents' },
+  'GET /api/v1/federation': { summary: "Every federated site's summary and the fleet totals", response: 'FleetView' },
+  'GET /api/v1/federation/history': {
+    summary: "Each federated site's hashrate history and the fleet total",
+    query: [query('range', 'How far back, e.g. 30m, 24h, 7d, or all (default 24h)'), query('resolution', 'raw or a rollup resolution of the sites, such as 1m or 1h (default 1m)')],
+    response: 'FleetHistory',
+  },
+  'GET /api/v1/federation/sites': { summary: 'The federated sv2-ui instances, without their tokens', response: 'FederationSites' },
+  'PUT /api/v1/federation/sites': { summary: 'Replace the federated sv2-ui instances', admin: true, body: 'FederationSitesInput', response: 'FederationSites' },
+  'POST /api/v1/federation/sites': {
+    summary: 'Register one sv2-ui instance',
+    description: 'Answers 201 with the site, or 409 if its id is taken.',
+    admin: true,
+    body: 'FederationSiteInput',
+    response: 'FederationSite',
+  },
+  'DELETE /api/v1/federation/sites/{id}': { summary: 'Deregister one sv2-ui instance', admin: true, response: 'Success' },
+  'GET /api/v1/discovered': { summary: 'Translator and JDC services found over mDNS that no stack uses yet' },
+  'GET /api/sv1-listener': { summary: "Whether the Translator's SV1 port accepts miners, and how many are attached", response: 'Sv1ListenerStatus' },
+  'GET /api/v1/probe/sv1': {
+    summary: "Subscribe and authorize on the Translator's SV1 port the way a miner would",
+    query: [query('user', 'Worker name to authorize as (default sv2-ui-probe)')],
+    response: 'Sv1ProbeResult',
+  },
+  'POST /api/v1/probe/sv2': {
+    summary: 'Connect, run the Noise handshake, check the certificate and send SetupConnection, reporting each step',
+    description: 'The JSON body may set host, port, authorityPubkey, protocol (mining or job-declaration) and flags; each defaults to the configured pool.',
+    operator: true,
+  },
+  'GET /api/v1/latency': {
+    summary: 'Latest and historical TCP connect/handshake times to the pool and JDS',
+    query: [query('target', 'pool or jds'), query('since', 'ISO 8601 timestamp (default 24 hours ago)'), query('limit', 'Samples per series, 1-10000 (default 1000)')],
+    response: 'LatencyResponse',
+  },
+  'GET /api/v1/plugins': { summary: 'Loaded WebAssembly plugins with their latest metrics, and those that failed to load', response: 'PluginList' },
+  'GET /api/v1/plugins/{id}': {
+    summary: "One plugin's latest metrics, with each metric's history as series of { at, value }",
+    query: [query('since', 'ISO 8601 timestamp (default 24 hours ago)'), query('limit', 'Samples per series, 1-10000 (default 1000)')],
+  },
+  'POST /api/v1/plugins/{id}/run': { summary: "Fetch and normalize a plugin's source now", operator: true, response: 'PluginStatus' },
+  'GET /api/v1/miners/{id}/history': {
+    summary: "One miner's hashrate over time, by worker name",
+    query: [query('since', 'ISO 8601 timestamp (default 24 hours ago)'), query('limit', 'Samples, 1-10000 (default 1000); the most recent are kept')],
+    response: 'MinerHistory',
+  },
+  'GET /api/v1/miners/labels': { summary: 'Names, locations and tags attached to miners', response: 'MinerLabels' },
+  'GET /api/v1/miners/{id}/label': { summary: 'The label of one miner, by worker name', response: 'MinerLabel' },
+  'PUT /api/v1/miners/{id}/label': {
+    summary: "Set a miner's name, location and tags",
+    admin: true,
+    body: 'MinerLabelInput',
+    response: 'MinerLabel',
+  },
+  'DELETE /api/v1/miners/{id}/label': { summary: "Remove a miner's label", admin: true, response: 'Success' },
+  'GET /api/v1/vardiff': {
+    summary: 'Current difficulty, retargets and achieved share rate per channel',
+    description: "Retargets are seen as a channel's target changing between polls. SV1 share rates are only known when each miner has its own upstream channel.",
+    query: [query('limit', 'Recent retargets, 1-500 (default 100)')],
+    response: 'VardiffResponse',
+  },
+  'GET /api/v1/vardiff/history': {
+    summary: "One channel's difficulty and achieved share rate over time",
+    query: [
+      query('channel', 'Channel key from /api/v1/vardiff'),
+      query('since', 'ISO 8601 timestamp (default 24 hours ago)'),
+      query('limit', 'Samples per series, 1-10000 (default 1000)'),
+    ],
+    response: 'VardiffHistory',
+  },
+  'GET /api/v1/history/export': {
+    summary: 'Stream stored time series (hashrate, shares, latency) as CSV or Parquet',
+    description: 'CSV has one row per sample: timestamp, metric, key and value. Parquet has timestamp, key and value columns for a single metric. Samples taken while the stack was unreachable have an empty (null) value.',
+    query: [
+      query('format', 'csv (the default) or parquet'),
+      query('metric', 'Comma-separated metrics, e.g. stack.hashrate,stack.shares_accepted (default: all); exactly one for parquet'),
+      query('range', 'How far back, e.g. 30m, 24h, 7d, or all (default 24h)'),
+      query('resolution', 'raw (the default) or a HISTORY_RETENTION rollup resolution such as 1m or 1h'),
+    ],
+    response: 'text/csv',
+  },
+  'GET /api/logs/diagnostics': { summary: 'Collated log diagnostics for the deployed stack' },
+  'GET /api/logs/raw': { summary: 'Raw collated log lines for the deployed stack', query: [query('tail', 'Lines per container (default 200, at most 500), or all')] },
+  'GET /api/v1/logs/{service}': {
+    summary: 'Follow the translator or JDC container log',
+    query: [query('tail', 'Lines of history to start with'), query('format', 'ndjson or sse')],
+    response: 'text/event-stream',
+  },
+  'GET /api/v1/services/{service}/logs': {
+    summary: 'Tail the translator or JDC log, from Docker or journald',
+    query: [
+      query('tail', 'Lines of history to start with'),
+      query('level', 'Only lines at this level or above, e.g. warn'),
+      query('follow', 'true to keep streaming new lines'),
+      query('format', 'ndjson or sse'),
+    ],
+    response: 'text/event-stream',
+  },
+  'GET /api/share-trace': {
+    summary: 'Trace one share through the service logs and upstream counters',
+    admin: true,
+    query: [query('worker', 'Worker name'), query('at', 'ISO 8601 time of the share'), query('jobId', 'Job id instead of a time'), query('window', 'Seconds around the time to search')],
+  },
+  'GET /api/v1/updates': { summary: 'Running image digests against the registry, and the services with a newer image' },
+  'GET /api/v1/price': { summary: 'The cached BTC price in PRICE_CURRENCY, fetched by the backend', response: 'PriceStatus' },
+  'POST /api/v1/services/{service}/{action}': { summary: 'Start, stop or restart the translator or JDC container', operator: true, response: 'Success' },
+  'GET /api/v1/qr/miner-config': {
+    summary: "QR code with the Translator's SV1 stratum URL and worker name",
+    query: [query('format', 'svg, png or json (default svg)'), query('scale', 'Pixels per module'), query('host', 'Stratum host miners connect to'), query('worker', 'Worker name')],
+    response: 'image/svg+xml',
+  },
+  'POST /api/v1/validate/coinbase-script': { summary: 'Parse an addr()/raw() descriptor or address into its scriptPubKey and address type' },
+  'GET /api/validate/bitcoin-rpc': { summary: 'Auto-discover Bitcoin Core RPC nodes' },
+  'POST /api/validate/bitcoin-socket': { summary: 'Check if a Bitcoin Core IPC socket is listening', operator: true },
+  'POST /api/v1/docker-env': {
+    summary: 'Validate setup wizard data and render the docker_env file',
+    description: 'Answers 400 with the validation report when the data is invalid.',
+    body: 'SetupData',
+  },
+  'POST /api/v1/keygen': { summary: "Generate a secp256k1 authority keypair in SV2's base58check format", admin: true },
+  'GET /api/admin/keys': { summary: 'List API keys and their quotas', admin: true },
+  'POST /api/admin/keys': { summary: 'Create a viewer API key with request and stream quotas', admin: true },
+  'DELETE /api/admin/keys/{id}': { summary: 'Revoke an API key', admin: true },
+  'GET /api/admin/keys/{id}/usage': { summary: 'Requests today and open streams for an API key', admin: true },
+  'GET /api/me': { summary: 'The role of the current request, and the user account or tunnel user it signed in as', response: 'Me' },
+  'POST /api/v1/auth/login': {
+    summary: 'Sign in to a user account, setting an httpOnly session cookie',
+    description: 'Answers 401 for a wrong username or password, and 429 after too many tries from one client.',
+    body: 'LoginInput',
+    response: 'Session',
+  },
+  'POST /api/v1/auth/logout': { summary: 'End the current session and clear its cookie', response: 'Success' },
+  'GET /api/admin/users': { summary: 'List user accounts and their roles', admin: true, response: 'UserList' },
+  'POST /api/admin/users': {
+    summary: 'Create a user account',
+    description: 'The JSON body sets username, password (8-256 characters) and role. The first account must be an admin.',
+    admin: true,
+    response: 'User',
+  },
+  'PATCH /api/admin/users/{id}': {
+    summary: "Change a user's username, password or role",
+    description: 'Answers 409 when it would leave no admin.',
+    admin: true,
+    response: 'User',
+  },
+  'DELETE /api/admin/users/{id}': { summary: 'Remove a user account', admin: true, response: 'Success' },
+  'GET /api/share-links': { summary: 'List active share links', admin: true },
+  'POST /api/share-links': { summary: 'Mint an expiring public link to a snapshot or live summary', admin: true },
+  'DELETE /api/share-links/{token}': { summary: 'Revoke a share link', admin: true },
+  'GET /share/{token}': { summary: 'Public, read-only summary behind an expiring share link', response: 'text/html' },
+  'GET /badge/{metric}.svg': { summary: 'Embeddable badge for a single stat (status, hashrate, workers, ...)', response: 'image/svg+xml' },
+  'GET /card/summary.svg': { summary: 'Embeddable summary card with the headline dashboard stats', response: 'image/svg+xml' },
+  'GET /card/summary.png': { summary: 'Summary card as a PNG, for sites that strip SVG', response: 'image/png' },
+  'GET /feed.xml': { summary: 'Atom feed of recent events', response: 'application/atom+xml' },
+};
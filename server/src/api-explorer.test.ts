@@ -0,0 +1,88 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { fileURLToPath } from 'node:url';
+import express from 'express';
+
+import { buildApiDocument, getMonitoringSpecPath, getRouterOperations, readMonitoringSpec, renderApiExplorer } from './api-explorer.js';
+import type { OpenApiDocument } from './api-explorer.js';
+
+function createRouter() {
+  const nested = express.Router();
+  nested.post('/api/v1/services/:service/:action', (_req, res) => res.end());
+  const router = express.Router();
+  router.get('/api/summary', (_req, res) => res.end());
+  router.use(nested);
+  router.get('*', (_req, res) => res.end());
+  return router;
+}
+
+const MONITORING_SPEC: OpenApiDocument = {
+  openapi: '3.1.0',
+  info: { title: 'SRI Monitoring API', version: '0.1.0' },
+  paths: {
+    '/api/v1/global': { get: { summary: 'Get global statistics' } },
+  },
+};
+
+test('getRouterOperations lists routes of mounted routers and skips catch-alls', () => {
+  assert.deepEqual(getRouterOperations(createRouter()), {
+    '/api/summary': { get: { tags: ['sv2-ui'], parameters: [] } },
+    '/api/v1/services/{service}/{action}': {
+      post: {
+        tags: ['sv2-ui'],
+        parameters: [
+          { name: 'service', in: 'path', required: true },
+          { name: 'action', in: 'path', required: true },
+        ],
+      },
+    },
+  });
+});
+
+test('buildApiDocument adds the monitoring API behind both proxies', () => {
+  const document = buildApiDocument(createRouter(), MONITORING_SPEC);
+  assert.equal(document.paths['/translator-api/v1/global']?.get?.summary, 'Get global statistics');
+  assert.deepEqual(document.paths['/jdc-api/v1/global']?.get?.tags, ['jdc']);
+  assert.ok(document.paths['/api/summary']);
+});
+
+test('buildApiDocument describes the server routes from ROUTE_DOCS', () => {
+  const document = buildApiDocument(createRouter(), MONITORING_SPEC);
+  const control = document.paths['/api/v1/services/{service}/{action}']?.post;
+  assert.equal(control?.summary, 'Start, stop or restart the translator or JDC container');
+  assert.deepEqual(control?.security, [{ bearer: [] }]);
+  assert.equal(control?.parameters?.length, 2);
+  assert.deepEqual(document.paths['/api/summary']?.get?.responses?.['200'].content, {
+    'application/json': { schema: { $ref: '#/components/schemas/SummaryResponse' } },
+  });
+  assert.ok(document.components?.schemas?.StackSummary);
+  assert.deepEqual(document.components?.securitySchemes?.bearer, { type: 'http', scheme: 'bearer' });
+});
+
+test('renderApiExplorer renders a form per operation and a nonce on its script', () => {
+  const html = renderApiExplorer(buildApiDocument(createRouter(), null), { basePath: '/mining', nonce: 'abc123' });
+  assert.match(html, /<form data-method="post" data-path="\/api\/v1\/services\/\{service\}\/\{action\}">/);
+  assert.match(html, /<input name="service" data-in="path" required/);
+  assert.match(html, /<textarea name="body"/);
+  assert.match(html, /<span class="admin">admin<\/span>/);
+  assert.match(html, /<script nonce="abc123">/);
+  assert.match(html, /const basePath = "\/mining";/);
+});
+
+test('finds shared/openapi.json in the Docker image and in the repo', async () => {
+  const serverDir = path.dirname(fileURLToPath(import.meta.url));
+  const repoSpec = getMonitoringSpecPath(serverDir, false);
+  assert.equal((await readMonitoringSpec(repoSpec)).info.title, 'SRI Monitoring API');
+
+  // The image runs /app/dist/index.js, with the shared package at /app/shared.
+  const app = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-image-'));
+  await fs.mkdir(path.join(app, 'dist'));
+  await fs.mkdir(path.join(app, 'shared'));
+  await fs.copyFile(repoSpec, path.join(app, 'shared/openapi.json'));
+  const imageSpec = getMonitoringSpecPath(path.join(app, 'dist'), true);
+  assert.equal(imageSpec, path.join(app, 'shared/openapi.json'));
+  assert.equal((await readMonitoringSpec(imageSpec)).info.title, 'SRI Monitoring API');
+});
@@ -0,0 +1,283 @@
+/**
+ * The built-in API explorer at /api (and /docs).
+ *
+ * GET /api/openapi.json describes the server's own routes, read from the
+ * router itself so it can't drift, plus the Translator and JDC monitoring
+ * APIs behind /translator-api and /jdc-api (from shared/openapi.json). The
+ * summaries, admin requirements and response shapes of the server's routes
+ * come from api-docs.ts. GET /api renders that document as a page with a
+ * try-it form per operation.
+ *
+ * Requests from the page carry the bearer token entered at the top (an
+ * ADMIN_TOKEN or API key), so admin-only routes answer as they would for any
+ * other client. The token stays in the tab's sessionStorage.
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import type { Router } from 'express';
+import { API_SCHEMAS, MEDIA_RESPONSES, ROUTE_DOCS } from './api-docs.js';
+import type { JsonSchema } from './api-docs.js';
+import { escapeMarkup } from './escape.js';
+
+export type HttpMethod = 'get' | 'post' | 'put' | 'patch' | 'delete';
+
+export interface OpenApiParameter {
+  name: string;
+  in: 'path' | 'query';
+  description?: string;
+  required?: boolean;
+}
+
+export interface OpenApiResponse {
+  description: string;
+  content?: Record<string, { schema?: JsonSchema }>;
+}
+
+export interface OpenApiOperation {
+  summary?: string;
+  description?: string;
+  tags?: string[];
+  parameters?: OpenApiParameter[];
+  security?: Record<string, string[]>[];
+  // The least role that may call the operation, when a viewer can't.
+  'x-role'?: 'operator' | 'admin';
+  requestBody?: { content: Record<string, { schema: JsonSchema }> };
+  responses?: Record<string, OpenApiResponse>;
+}
+
+export interface OpenApiDocument {
+  openapi: string;
+  info: { title: string; version: string };
+  paths: Record<string, Partial<Record<HttpMethod, OpenApiOperation>>>;
+  components?: {
+    schemas?: Record<string, JsonSchema>;
+    securitySchemes?: Record<string, JsonSchema>;
+  };
+}
+
+interface RouterLayer {
+  route?: { path: unknown; methods: Record<string, boolean> };
+  handle?: { stack?: RouterLayer[] };
+}
+
+const METHODS: HttpMethod[] = ['get', 'post', 'put', 'patch', 'delete'];
+const BODY_METHODS: HttpMethod[] = ['post', 'put', 'patch'];
+
+// The monitoring proxies map /translator-api/v1/... to the service's /api/v1/...
+export const MONITORING_PROXIES = [
+  { prefix: '/translator-api', tag: 'translator' },
+  { prefix: '/jdc-api', tag: 'jdc' },
+];
+
+/**
+ * Every method and path registered on `router` and the routers mounted in it.
+ * Express's `:param` segments become OpenAPI `{param}` path parameters.
+ */
+export function getRouterOperations(router: Router): OpenApiDocument['paths'] {
+  const paths: OpenApiDocument['paths'] = {};
+
+  function visit(stack: RouterLayer[]): void {
+    for (const layer of stack) {
+      if (layer.handle?.stack && !layer.route) {
+        visit(layer.handle.stack);
+        continue;
+      }
+      const route = layer.route;
+      // Catch-alls like the SPA fallback aren't endpoints.
+      if (!route || typeof route.path !== 'string' || route.path.includes('*')) continue;
+
+      const names = [...route.path.matchAll(/:(\w+)/g)].map((match) => match[1]);
+      const openApiPath = route.path.replace(/:(\w+)/g, '{$1}');
+      for (const method of METHODS.filter((name) => route.methods[name])) {
+        paths[openApiPath] = {
+          ...paths[openApiPath],
+          [method]: {
+            tags: ['sv2-ui'],
+            parameters: names.map((name): OpenApiParameter => ({ name, in: 'path', required: true })),
+          },
+        };
+      }
+    }
+  }
+
+  visit((router as unknown as { stack: RouterLayer[] }).stack);
+  return paths;
+}
+
+const schemaRef = (name: string): JsonSchema => ({ $ref: `#/components/schemas/${name}` });
+
+/**
+ * Fill in an operation read from the router with its entry in ROUTE_DOCS.
+ */
+function describeOperation(method: HttpMethod, path: string, operation: OpenApiOperation): OpenApiOperation {
+  const doc = ROUTE_DOCS[`${method.toUpperCase()} ${path}`];
+  if (!doc) return operation;
+
+  const role = doc.admin ? 'admin' : doc.operator ? 'operator' : null;
+  const content = doc.response && MEDIA_RESPONSES.includes(doc.response)
+    ? { [doc.response]: {} }
+    : { 'application/json': doc.response ? { schema: schemaRef(doc.response) } : {} };
+  return {
+    ...operation,
+    summary: doc.summary,
+    ...(doc.description ? { description: doc.description } : {}),
+    parameters: [...(operation.parameters ?? []), ...(doc.query ?? [])],
+    ...(role ? { security: [{ bearer: [] }], 'x-role': role } : {}),
+    ...(doc.body ? { requestBody: { content: { 'application/json': { schema: schemaRef(doc.body) } } } } : {}),
+    responses: {
+      200: { description: 'OK', content },
+      ...(role ? { 403: { description: role === 'admin' ? 'Admin only' : 'Operator or admin only', content: { 'application/json': { schema: schemaRef('Error') } } } } : {}),
+    },
+  };
+}
+
+/**
+ * Where shared/openapi.json is, seen from the server's own directory. Like
+ * UI_DIR: in Docker (NODE_ENV=production) the server runs from /app/dist next
+ * to /app/shared; in development from server/src or server/dist.
+ */
+export function getMonitoringSpecPath(serverDir: string, production = process.env.NODE_ENV === 'production'): string {
+  return path.join(serverDir, production ? '../shared/openapi.json' : '../../shared/openapi.json');
+}
+
+export async function readMonitoringSpec(file: string): Promise<OpenApiDocument> {
+  return JSON.parse(await fs.readFile(file, 'utf-8')) as OpenApiDocument;
+}
+
+export function buildApiDocument(router: Router, monitoringSpec: OpenApiDocument | null): OpenApiDocument {
+  const paths: OpenApiDocument['paths'] = {};
+  for (const [path, operations] of Object.entries(getRouterOperations(router))) {
+    paths[path] = Object.fromEntries(Object.entries(operations).map(([method, operation]) => [
+      method,
+      describeOperation(method as HttpMethod, path, operation!),
+    ]));
+  }
+  for (const [path, operations] of Object.entries(monitoringSpec?.paths ?? {})) {
+    for (const { prefix, tag } of MONITORING_PROXIES) {
+      paths[path.replace(/^\/api/, prefix)] = Object.fromEntries(Object.entries(operations).map(([method, operation]) => [
+        method,
+        {
+          summary: operation?.summary,
+          description: operation?.description,
+          parameters: operation?.parameters,
+          responses: operation?.responses,
+          tags: [tag],
+        },
+      ]));
+    }
+  }
+  return {
+    openapi: '3.1.0',
+    info: { title: 'sv2-ui API', version: '1' },
+    paths,
+    components: {
+      schemas: { ...monitoringSpec?.components?.schemas, ...API_SCHEMAS },
+      // ADMIN_TOKEN or an API key.
+      securitySchemes: { bearer: { type: 'http', scheme: 'bearer' } },
+    },
+  };
+}
+
+function renderOperation(path: string, method: HttpMethod, operation: OpenApiOperation): string {
+  const fields = (operation.parameters ?? [])
+    .filter((parameter) => parameter.in === 'path' || parameter.in === 'query')
+    .map((parameter) => `          <label>${escapeMarkup(parameter.name)} <small>${parameter.in}${parameter.required ? ', required' : ''}</small>
+            <input name="${escapeMarkup(parameter.name)}" data-in="${parameter.in}"${parameter.required ? ' required' : ''} placeholder="${escapeMarkup(parameter.description ?? '')}" />
+          </label>`);
+  if (BODY_METHODS.includes(method)) {
+    fields.push('          <label>JSON body <textarea name="body" data-in="body" rows="4"></textarea></label>');
+  }
+
+  return `      <details>
+        <summary><span class="method ${method}">${method.toUpperCase()}</span> <code>${escapeMarkup(path)}</code>${operation.security?.length ? ` <span class="admin">${operation['x-role'] ?? 'admin'}</span>` : ''}${operation.summary ? ` <span class="summary">${escapeMarkup(operation.summary.split('\n')[0])}</span>` : ''}</summary>
+        <form data-method="${method}" data-path="${escapeMarkup(path)}">
+${operation.description ? `          <p class="description">${escapeMarkup(operation.description)}</p>\n` : ''}${fields.join('\n')}
+          <button type="submit">Send</button>
+          <pre class="response"></pre>
+        </form>
+      </details>`;
+}
+
+export function renderApiExplorer(document: OpenApiDocument, { basePath, nonce }: { basePath: string; nonce: string }): string {
+  const groups = new Map<string, string[]>();
+  for (const [path, operations] of Object.entries(document.paths)) {
+    for (const method of METHODS) {
+      const operation = operations[method];
+      if (!operation) continue;
+      const tag = operation.tags?.[0] ?? 'other';
+      groups.set(tag, [...(groups.get(tag) ?? []), renderOperation(path, method, operation)]);
+    }
+  }
+  const sections = [...groups].map(([tag, operations]) => `    <section>
+      <h2>${escapeMarkup(tag)}</h2>
+${operations.join('\n')}
+    </section>`).join('\n');
+
+  return `<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <meta name="robots" content="noindex" />
+    <title>sv2-ui API</title>
+    <style>
+      body { font-family: system-ui, sans-serif; background: #0b0b0c; color: #e5e5e5; max-width: 60rem; margin: 0 auto; padding: 2rem; }
+      a { color: #8ab4f8; }
+      details { border-bottom: 1px solid #222; padding: 0.4rem 0; }
+      summary { cursor: pointer; }
+      .method { display: inline-block; min-width: 4rem; font-weight: 600; }
+      .get { color: #2ecc71; } .post, .put, .patch { color: #f1c40f; } .delete { color: #e74c3c; }
+      .summary, .description { color: #8a8a8a; }
+      .description { margin: 0; white-space: pre-line; }
+      .admin { font-size: 0.75rem; border: 1px solid #e67e22; color: #e67e22; border-radius: 0.25rem; padding: 0 0.3rem; }
+      form { display: grid; gap: 0.5rem; padding: 0.75rem 0 0.25rem 1rem; }
+      input, textarea, button { font: inherit; background: #161618; color: inherit; border: 1px solid #333; padding: 0.3rem 0.5rem; }
+      label { display: grid; gap: 0.2rem; }
+      pre { white-space: pre-wrap; word-break: break-all; margin: 0; }
+    </style>
+  </head>
+  <body>
+    <h1>sv2-ui API</h1>
+    <p>Generated from <a href="${escapeMarkup(basePath)}/api/openapi.json">openapi.json</a>.</p>
+    <label>Bearer token (ADMIN_TOKEN or API key) <input id="token" type="password" autocomplete="off" /></label>
+${sections}
+    <script nonce="${escapeMarkup(nonce)}">
+      const basePath = ${JSON.stringify(basePath).replace(/</g, '\\u003c')};
+      const token = document.getElementById('token');
+      token.value = sessionStorage.getItem('sv2-ui-api-token') ?? '';
+      token.addEventListener('input', () => sessionStorage.setItem('sv2-ui-api-token', token.value));
+
+      for (const form of document.querySelectorAll('form[data-path]')) {
+        form.addEventListener('submit', async (event) => {
+          event.preventDefault();
+          const output = form.querySelector('.response');
+          let path = form.dataset.path;
+          const query = new URLSearchParams();
+          let body;
+          for (const input of form.querySelectorAll('[data-in]')) {
+            if (!input.value) continue;
+            if (input.dataset.in === 'path') path = path.replace('{' + input.name + '}', encodeURIComponent(input.value));
+            else if (input.dataset.in === 'query') query.set(input.name, input.value);
+            else body = input.value;
+          }
+          const headers = { Accept: 'application/json' };
+          if (token.value) headers.Authorization = 'Bearer ' + token.value;
+          if (body !== undefined) headers['Content-Type'] = 'application/json';
+          output.textContent = '…';
+          try {
+            const response = await fetch(basePath + path + (query.size ? '?' + query : ''), { method: form.dataset.method.toUpperCase(), headers, body });
+            const text = await response.text();
+            let shown = text;
+            try { shown = JSON.stringify(JSON.parse(text), null, 2); } catch {}
+            output.textContent = response.status + ' ' + response.statusText + '\\n' + shown;
+          } catch (error) {
+            output.textContent = String(error);
+          }
+        });
+      }
+    </script>
+  </body>
+</html>
+`;
+}
@@ -0,0 +1,143 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import http from 'node:http';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import express from 'express';
+import {
+  createApiKeyQuotaMiddleware,
+  createApiKeyStore,
+  createApiKeyStreamMiddleware,
+  createApiKeyUsageTracker,
+  describeApiKey,
+  parseApiKeyRequest,
+} from './api-keys.js';
+
+async function createStore() {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-api-keys-'));
+  return { filePath: path.join(dir, 'api-keys.json'), store: createApiKeyStore(path.join(dir, 'api-keys.json')) };
+}
+
+test('parses API key requests with optional quotas', () => {
+  assert.deepEqual(parseApiKeyRequest({ name: ' Grafana ', requestsPerDay: 5000 }), {
+    name: 'Grafana',
+    quota: { requestsPerDay: 5000, concurrentStreams: null },
+  });
+  assert.throws(() => parseApiKeyRequest({}), /name is required/);
+  assert.throws(() => parseApiKeyRequest({ name: 'x', concurrentStreams: 0 }), /concurrentStreams must be an integer/);
+  assert.throws(() => parseApiKeyRequest({ name: 'x', requestsPerDay: 1.5 }), /requestsPerDay must be an integer/);
+});
+
+test('stores only a hash of each key and finds keys by token', async () => {
+  const { filePath, store } = await createStore();
+  const { key, token } = await store.create({ name: 'Grafana', quota: { requestsPerDay: 10, concurrentStreams: 1 } });
+
+  const saved = await fs.readFile(filePath, 'utf-8');
+  assert.ok(!saved.includes(token));
+  assert.equal('tokenHash' in describeApiKey(key), false);
+
+  assert.equal((await store.findByToken(token))?.id, key.id);
+  assert.equal(await store.findByToken('sv2k_wrong'), null);
+  assert.equal((await createApiKeyStore(filePath).findByToken(token))?.id, key.id);
+
+  assert.equal(await store.revoke(key.id), true);
+  assert.equal(await store.findByToken(token), null);
+  assert.equal(await store.revoke(key.id), false);
+});
+
+test('daily request quotas reset at UTC midnight', () => {
+  let now = Date.parse('2026-04-17T23:59:00Z');
+  const usage = createApiKeyUsageTracker(() => now);
+  const key = { id: 'k1', name: 'x', tokenHash: '', createdAt: '', quota: { requestsPerDay: 2, concurrentStreams: null } };
+
+  assert.equal(usage.takeRequest(key), 0);
+  assert.equal(usage.takeRequest(key), 0);
+  assert.equal(usage.takeRequest(key), 60);
+  assert.deepEqual(usage.getUsage(key), {
+    id: 'k1',
+    day: '2026-04-17',
+    requests: 2,
+    requestsPerDay: 2,
+    remaining: 0,
+    streams: 0,
+    concurrentStreams: null,
+    resetsAt: '2026-04-18T00:00:00.000Z',
+  });
+
+  now = Date.parse('2026-04-18T00:00:01Z');
+  assert.equal(usage.takeRequest(key), 0);
+  assert.equal(usage.getUsage(key).requests, 1);
+});
+
+test('concurrent stream quotas free a slot when a stream closes', () => {
+  const usage = createApiKeyUsageTracker();
+  const key = { id: 'k1', name: 'x', tokenHash: '', createdAt: '', quota: { requestsPerDay: null, concurrentStreams: 1 } };
+
+  const release = usage.openStream(key);
+  assert.ok(release);
+  assert.equal(usage.openStream(key), null);
+  release();
+  release();
+  assert.equal(usage.getUsage(key).streams, 0);
+  assert.ok(usage.openStream(key));
+});
+
+test('the quota middleware rejects requests once a key is over quota', async (t) => {
+  const { store } = await createStore();
+  const { token } = await store.create({ name: 'Grafana', quota: { requestsPerDay: 1, concurrentStreams: null } });
+
+  const app = express();
+  app.use(createApiKeyQuotaMiddleware(store, createApiKeyUsageTracker()));
+  app.get('/api/status', (_req, res) => res.json({ apiKey: res.locals.apiKeyId ?? null }));
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = `http://127.0.0.1:${port}/api/status`;
+
+  const first = await fetch(url, { headers: { Authorization: `Bearer ${token}` } });
+  assert.equal(first.status, 200);
+  assert.ok((await first.json()).apiKey);
+
+  const second = await fetch(url, { headers: { Authorization: `Bearer ${token}` } });
+  assert.equal(second.status, 429);
+  assert.ok(Number(second.headers.get('retry-after')) > 0);
+
+  // Other tokens and anonymous requests aren't keys and aren't counted.
+  assert.equal((await fetch(url, { headers: { Authorization: 'Bearer admin-token' } })).status, 200);
+  assert.equal((await fetch(url)).status, 200);
+});
+
+test('routes marked as streams hold one of the key\'s concurrent streams while open', async (t) => {
+  const { store } = await createStore();
+  const { key, token } = await store.create({ name: 'Grafana', quota: { requestsPerDay: null, concurrentStreams: 1 } });
+  const usage = createApiKeyUsageTracker();
+
+  const app = express();
+  app.use(createApiKeyQuotaMiddleware(store, usage));
+  app.get('/api/v1/logs/:service', createApiKeyStreamMiddleware(usage), (_req, res) => {
+    res.type('application/x-ndjson').flushHeaders();
+  });
+  app.get('/api/status', (_req, res) => res.json({}));
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = (route: string) => `http://127.0.0.1:${port}${route}`;
+  const headers = { Authorization: `Bearer ${token}` };
+
+  const controller = new AbortController();
+  const first = await fetch(url('/api/v1/logs/translator'), { headers, signal: controller.signal });
+  assert.equal(first.status, 200);
+  assert.equal((await fetch(url('/api/v1/logs/jdc'), { headers })).status, 429);
+  // Other routes aren't streams, and requests without a key aren't counted.
+  assert.equal((await fetch(url('/api/status'), { headers })).status, 200);
+  const anonymous = new AbortController();
+  assert.equal((await fetch(url('/api/v1/logs/jdc'), { signal: anonymous.signal })).status, 200);
+  anonymous.abort();
+
+  controller.abort();
+  await new Promise((resolve) => setTimeout(resolve, 50));
+  assert.equal(usage.getUsage(key).streams, 0);
+});
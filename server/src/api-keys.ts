@@ -0,0 +1,267 @@
+/**
+ * API keys for third-party dashboards and scripts, each with its own quota.
+ *
+ * A key is a bearer token like ADMIN_TOKEN, but it only ever gets the viewer
+ * role. Requests presenting one count against its daily request quota and,
+ * for streams, its concurrent stream quota, so a shared key can't
+ * monopolize the server. Only a hash of each key is stored; the token itself
+ * is shown once, when the key is created.
+ *
+ * Usage is kept in memory and starts over when the server restarts.
+ */
+
+import crypto from 'crypto';
+import fs from 'fs/promises';
+import path from 'path';
+import type { RequestHandler } from 'express';
+import { getBearerToken } from './auth.js';
+
+export interface ApiKeyQuota {
+  // null means unlimited.
+  requestsPerDay: number | null;
+  concurrentStreams: number | null;
+}
+
+export interface ApiKey {
+  id: string;
+  name: string;
+  tokenHash: string;
+  createdAt: string;
+  quota: ApiKeyQuota;
+}
+
+export interface ApiKeyUsage {
+  id: string;
+  // UTC day the request count covers, YYYY-MM-DD.
+  day: string;
+  requests: number;
+  requestsPerDay: number | null;
+  remaining: number | null;
+  streams: number;
+  concurrentStreams: number | null;
+  resetsAt: string;
+}
+
+const MAX_NAME_LENGTH = 64;
+const MAX_REQUESTS_PER_DAY = 10_000_000;
+const MAX_CONCURRENT_STREAMS = 1_000;
+
+function parseLimit(value: unknown, name: string, max: number): number | null {
+  if (value === undefined || value === null) return null;
+  if (typeof value !== 'number' || !Number.isInteger(value) || value < 1 || value > max) {
+    throw new Error(`${name} must be an integer between 1 and ${max}, or null for unlimited`);
+  }
+  return value;
+}
+
+export function parseApiKeyRequest(body: unknown): { name: string; quota: ApiKeyQuota } {
+  const { name, requestsPerDay, concurrentStreams } = (body ?? {}) as {
+    name?: unknown;
+    requestsPerDay?: unknown;
+    concurrentStreams?: unknown;
+  };
+
+  if (typeof name !== 'string' || !name.trim() || name.trim().length > MAX_NAME_LENGTH) {
+    throw new Error(`name is required, up to ${MAX_NAME_LENGTH} characters`);
+  }
+
+  return {
+    name: name.trim(),
+    quota: {
+      requestsPerDay: parseLimit(requestsPerDay, 'requestsPerDay', MAX_REQUESTS_PER_DAY),
+      concurrentStreams: parseLimit(concurrentStreams, 'concurrentStreams', MAX_CONCURRENT_STREAMS),
+    },
+  };
+}
+
+/**
+ * A key as shown to the admin, without its token hash.
+ */
+export function describeApiKey({ id, name, createdAt, quota }: ApiKey) {
+  return { id, name, createdAt, quota };
+}
+
+export function hashApiKey(token: string): string {
+  return crypto.createHash('sha256').update(token).digest('hex');
+}
+
+export function createApiKeyStore(filePath: string) {
+  // Every authenticated request looks a key up, so keep them in memory.
+  let cache: ApiKey[] | null = null;
+
+  async function list(): Promise<ApiKey[]> {
+    if (cache) return cache;
+    try {
+      const keys = JSON.parse(await fs.readFile(filePath, 'utf-8')) as ApiKey[];
+      cache = Array.isArray(keys) ? keys : [];
+    } catch {
+      cache = [];
+    }
+    return cache;
+  }
+
+  async function write(keys: ApiKey[]): Promise<void> {
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    await fs.writeFile(filePath, JSON.stringify(keys, null, 2));
+    cache = keys;
+  }
+
+  async function create(
+    { name, quota }: { name: string; quota: ApiKeyQuota },
+    now = new Date(),
+  ): Promise<{ key: ApiKey; token: string }> {
+    const token = `sv2k_${crypto.randomBytes(24).toString('base64url')}`;
+    const key: ApiKey = {
+      id: crypto.randomBytes(6).toString('hex'),
+      name,
+      tokenHash: hashApiKey(token),
+      createdAt: now.toISOString(),
+      quota,
+    };
+    await write([...(await list()), key]);
+    return { key, token };
+  }
+
+  async function get(id: string): Promise<ApiKey | null> {
+    return (await list()).find((key) => key.id === id) ?? null;
+  }
+
+  async function findByToken(token: string): Promise<ApiKey | null> {
+    const hash = hashApiKey(token);
+    return (await list()).find((key) => key.tokenHash === hash) ?? null;
+  }
+
+  async function revoke(id: string): Promise<boolean> {
+    const keys = await list();
+    const remaining = keys.filter((key) => key.id !== id);
+    if (remaining.length === keys.length) return false;
+    await write(remaining);
+    return true;
+  }
+
+  return { list, create, get, findByToken, revoke };
+}
+
+export type ApiKeyStore = ReturnType<typeof createApiKeyStore>;
+
+function getUtcDay(time: number): string {
+  return new Date(time).toISOString().slice(0, 10);
+}
+
+function getNextUtcMidnight(time: number): number {
+  const date = new Date(time);
+  return Date.UTC(date.getUTCFullYear(), date.getUTCMonth(), date.getUTCDate() + 1);
+}
+
+export function createApiKeyUsageTracker(now: () => number = Date.now) {
+  const counters = new Map<string, { day: string; requests: number; streams: number }>();
+
+  function getCounter(id: string) {
+    const day = getUtcDay(now());
+    let counter = counters.get(id);
+    if (!counter) {
+      counter = { day, requests: 0, streams: 0 };
+      counters.set(id, counter);
+    } else if (counter.day !== day) {
+      counter.day = day;
+      counter.requests = 0;
+    }
+    return counter;
+  }
+
+  /**
+   * Count a request. Returns 0 when allowed, otherwise the number of seconds
+   * until the key's daily quota resets.
+   */
+  function takeRequest(key: ApiKey): number {
+    const counter = getCounter(key.id);
+    if (key.quota.requestsPerDay !== null && counter.requests >= key.quota.requestsPerDay) {
+      return Math.max(1, Math.ceil((getNextUtcMidnight(now()) - now()) / 1000));
+    }
+    counter.requests++;
+    return 0;
+  }
+
+  /**
+   * Hold a stream slot, returning the function that releases it, or null when
+   * the key already has as many streams open as it may.
+   */
+  function openStream(key: ApiKey): (() => void) | null {
+    const counter = getCounter(key.id);
+    if (key.quota.concurrentStreams !== null && counter.streams >= key.quota.concurrentStreams) return null;
+    counter.streams++;
+    let released = false;
+    return () => {
+      if (released) return;
+      released = true;
+      counter.streams--;
+    };
+  }
+
+  function getUsage(key: ApiKey): ApiKeyUsage {
+    const { day, requests, streams } = getCounter(key.id);
+    return {
+      id: key.id,
+      day,
+      requests,
+      requestsPerDay: key.quota.requestsPerDay,
+      remaining: key.quota.requestsPerDay === null ? null : Math.max(0, key.quota.requestsPerDay - requests),
+      streams,
+      concurrentStreams: key.quota.concurrentStreams,
+      resetsAt: new Date(getNextUtcMidnight(now())).toISOString(),
+    };
+  }
+
+  function forget(id: string): void {
+    counters.delete(id);
+  }
+
+  return { takeRequest, openStream, getUsage, forget, size: () => counters.size };
+}
+
+export type ApiKeyUsageTracker = ReturnType<typeof createApiKeyUsageTracker>;
+
+/**
+ * Enforce quotas for requests that present an API key. Other requests,
+ * admin ones included, pass through untouched.
+ */
+export function createApiKeyQuotaMiddleware(store: ApiKeyStore, usage: ApiKeyUsageTracker): RequestHandler {
+  return (req, res, next) => {
+    const token = getBearerToken(req.headers.authorization);
+    if (!token) return next();
+
+    store.findByToken(token).then((key) => {
+      if (!key) return next();
+      res.locals.apiKeyId = key.id;
+      res.locals.apiKey = key;
+
+      const retryAfterSecs = usage.takeRequest(key);
+      if (retryAfterSecs > 0) {
+        res.set('Retry-After', String(retryAfterSecs));
+        res.status(429).json({ error: 'Daily request quota exceeded for this API key' });
+        return;
+      }
+
+      next();
+    }, next);
+  };
+}
+
+/**
+ * Mark a route as a long-lived stream (SSE, a followed log). A request with
+ * an API key holds one of the key's concurrent streams until it closes.
+ */
+export function createApiKeyStreamMiddleware(usage: ApiKeyUsageTracker): RequestHandler {
+  return (_req, res, next) => {
+    const key = res.locals.apiKey as ApiKey | undefined;
+    if (!key) return next();
+
+    const release = usage.openStream(key);
+    if (!release) {
+      res.status(429).json({ error: 'Concurrent stream quota exceeded for this API key' });
+      return;
+    }
+    res.on('close', release);
+    next();
+  };
+}
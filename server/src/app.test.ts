@@ -0,0 +1,80 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp, parseTrustProxy } from './app.js';
+import { parseIpFilter } from './ip-filter.js';
+import { getRequestId, getRequestIdHeaders } from './request-context.js';
+
+async function listen(t: { after: (fn: () => void) => void }, app: express.Express): Promise<string> {
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  return `http://127.0.0.1:${port}`;
+}
+
+test('parseTrustProxy accepts booleans, hop counts and addresses', () => {
+  assert.equal(parseTrustProxy(undefined), undefined);
+  assert.equal(parseTrustProxy(' '), undefined);
+  assert.equal(parseTrustProxy('true'), true);
+  assert.equal(parseTrustProxy('2'), 2);
+  assert.equal(parseTrustProxy('loopback'), 'loopback');
+});
+
+test('mounts routes under BASE_PATH and probes at the root, ahead of the IP filter', async (t) => {
+  const router = express.Router();
+  router.post('/api/echo', (req, res) => res.json(req.body));
+  const probes = express.Router();
+  probes.get('/livez', (_req, res) => res.json({ status: 'ok' }));
+
+  const url = await listen(t, createApp({
+    router,
+    basePath: '/sv2',
+    probes,
+    trustProxy: undefined,
+    ipFilter: parseIpFilter({}),
+  }));
+  const echo = await fetch(`${url}/sv2/api/echo`, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify({ hello: 'world' }),
+  });
+  assert.equal(echo.status, 200);
+  assert.deepEqual(await echo.json(), { hello: 'world' });
+  assert.equal((await fetch(`${url}/api/echo`, { method: 'POST' })).status, 404);
+
+  const denied = await listen(t, createApp({
+    router,
+    probes,
+    trustProxy: undefined,
+    ipFilter: parseIpFilter({ DENY_CIDR: '127.0.0.1/32' }),
+  }));
+  assert.equal((await fetch(`${denied}/livez`)).status, 200);
+  assert.equal((await fetch(`${denied}/api/echo`, { method: 'POST' })).status, 403);
+});
+
+test('every request gets an ID, returned as X-Request-Id and kept through body parsing', async (t) => {
+  const router = express.Router();
+  router.post('/api/echo', async (req, res) => {
+    await new Promise((resolve) => setTimeout(resolve, 1));
+    res.json({ body: req.body, requestId: getRequestId(), upstream: getRequestIdHeaders() });
+  });
+  const url = await listen(t, createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({ DENY_CIDR: '10.0.0.0/8' }) }));
+
+  const reused = await fetch(`${url}/api/echo`, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json', 'X-Request-Id': 'panel-42' },
+    body: JSON.stringify({ hello: 'world' }),
+  });
+  assert.equal(reused.headers.get('x-request-id'), 'panel-42');
+  assert.deepEqual(await reused.json(), { body: { hello: 'world' }, requestId: 'panel-42', upstream: { 'X-Request-Id': 'panel-42' } });
+
+  const minted = await fetch(`${url}/api/echo`, { method: 'POST', headers: { 'X-Request-Id': 'not a valid id' } });
+  const id = minted.headers.get('x-request-id');
+  assert.match(id ?? '', /^[0-9a-f-]{36}$/);
+  assert.equal((await minted.json()).requestId, id);
+
+  const denied = await listen(t, createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({ DENY_CIDR: '127.0.0.1/32' }) }));
+  assert.match((await fetch(`${denied}/api/echo`, { method: 'POST' })).headers.get('x-request-id') ?? '', /^[0-9a-f-]{36}$/);
+});
@@ -0,0 +1,79 @@
+/**
+ * The Express app builder: the middleware every request passes through,
+ * then the routes mounted under BASE_PATH.
+ *
+ * index.ts wires the real routes and starts listening. Tests build an app
+ * around just the routers they exercise, with the same middleware in front.
+ */
+
+import express from 'express';
+import type { Express, Router } from 'express';
+import { createCorsMiddleware, parseCorsPolicies } from './cors-origins.js';
+import type { CorsPolicy } from './cors-origins.js';
+import { createIpFilterMiddleware, parseIpFilter } from './ip-filter.js';
+import type { IpFilter } from './ip-filter.js';
+import { createRequestIdMiddleware } from './request-context.js';
+import { createSecurityHeadersMiddleware, parseSecurityHeadersConfig } from './security-headers.js';
+import type { SecurityHeadersConfig } from './security-headers.js';
+import { createTunnelMiddleware, parseTunnelConfig } from './tunnel.js';
+import type { TunnelConfig } from './tunnel.js';
+
+export interface AppOptions {
+  // Everything under BASE_PATH.
+  router: Router;
+  basePath?: string;
+  // Served at the root ahead of the IP filter, for orchestrator probes.
+  probes?: Router;
+  trustProxy?: string;
+  ipFilter?: IpFilter;
+  securityHeaders?: SecurityHeadersConfig;
+  corsPolicies?: CorsPolicy[];
+  tunnel?: TunnelConfig | null;
+}
+
+/**
+ * TRUST_PROXY accepts anything Express's "trust proxy" does.
+ */
+export function parseTrustProxy(value: string | undefined): boolean | number | string | undefined {
+  const trustProxy = value?.trim();
+  if (!trustProxy) return undefined;
+  return trustProxy === 'true' ? true : /^\d+$/.test(trustProxy) ? Number(trustProxy) : trustProxy;
+}
+
+export function createApp({
+  router,
+  basePath = '',
+  probes,
+  trustProxy = process.env.TRUST_PROXY,
+  ipFilter = parseIpFilter(),
+  securityHeaders = parseSecurityHeadersConfig(),
+  corsPolicies = parseCorsPolicies(),
+  tunnel = parseTunnelConfig(),
+}: AppOptions): Express {
+  const app = express();
+
+  // Lets req.ip (used for rate limiting and X-Forwarded-For) see the real
+  // client behind a reverse proxy.
+  const trust = parseTrustProxy(trustProxy);
+  if (trust !== undefined) app.set('trust proxy', trust);
+
+  // Every response carries its X-Request-Id, rejections included.
+  const requestId = createRequestIdMiddleware();
+  app.use(requestId);
+
+  // The probes are exempt from ALLOW_CIDR / DENY_CIDR, since orchestrators
+  // probe from their own addresses.
+  if (probes) app.use(probes);
+
+  // Tunnel identity comes first so the IP filter sees the tunnel client's address.
+  if (tunnel) app.use(createTunnelMiddleware(tunnel));
+  // ALLOW_CIDR / DENY_CIDR are checked before anything else, auth and proxying included.
+  app.use(createIpFilterMiddleware(ipFilter));
+  app.use(createSecurityHeadersMiddleware(securityHeaders));
+  app.use(createCorsMiddleware(corsPolicies, basePath));
+  app.use(express.json());
+  app.use(requestId);
+
+  app.use(basePath || '/', router);
+  return app;
+}
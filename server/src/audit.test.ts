@@ -0,0 +1,90 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import express from 'express';
+import { createAuditLog, createAuditMiddleware, digestBody, parseAuditQuery, type AuditEntry } from './audit.js';
+
+async function tempLogPath(): Promise<string> {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-audit-'));
+  return path.join(dir, 'audit.log');
+}
+
+function entry(overrides: Partial<AuditEntry> = {}): AuditEntry {
+  return {
+    at: '2026-01-01T00:00:00.000Z',
+    service: 'translator',
+    method: 'POST',
+    path: '/translator-api/v1/example',
+    status: 200,
+    client: '127.0.0.1',
+    role: 'admin',
+    requestId: null,
+    bodyDigest: null,
+    ...overrides,
+  };
+}
+
+test('digests bodies the same way the proxy re-serializes them', () => {
+  assert.equal(digestBody(undefined), null);
+  assert.equal(digestBody({}), null);
+  assert.match(digestBody({ a: 1 }) ?? '', /^sha256:[0-9a-f]{64}$/);
+  assert.equal(digestBody({ a: 1 }), digestBody('{"a":1}'));
+});
+
+test('queries newest first with filters', async () => {
+  const log = createAuditLog(await tempLogPath());
+  await log.append(entry({ at: '2026-01-01T00:00:00.000Z' }));
+  await log.append(entry({ at: '2026-01-02T00:00:00.000Z', service: 'jdc' }));
+  await log.append(entry({ at: '2026-01-03T00:00:00.000Z' }));
+
+  assert.deepEqual((await log.query()).map((e) => e.at.slice(0, 10)), ['2026-01-03', '2026-01-02', '2026-01-01']);
+  assert.deepEqual((await log.query({ service: 'jdc' })).map((e) => e.service), ['jdc']);
+  assert.equal((await log.query({ since: new Date('2026-01-02T00:00:00.000Z') })).length, 2);
+  assert.equal((await log.query({ limit: 1 }))[0].at, '2026-01-03T00:00:00.000Z');
+});
+
+test('rotates the log and still reads the previous generation', async () => {
+  const filePath = await tempLogPath();
+  const log = createAuditLog(filePath, 200);
+  for (let i = 0; i < 5; i++) {
+    await log.append(entry({ at: `2026-01-0${i + 1}T00:00:00.000Z` }));
+  }
+
+  await fs.stat(`${filePath}.1`);
+  assert.equal((await log.query())[0].at, '2026-01-05T00:00:00.000Z');
+});
+
+test('validates query parameters', () => {
+  assert.deepEqual(parseAuditQuery({}), {});
+  assert.equal(parseAuditQuery({ limit: '10' }).limit, 10);
+  assert.throws(() => parseAuditQuery({ service: 'pool' }), /service/);
+  assert.throws(() => parseAuditQuery({ since: 'yesterday' }), /since/);
+  assert.throws(() => parseAuditQuery({ limit: '0' }), /limit/);
+});
+
+test('records mutating requests only, with the final status', async (t) => {
+  const log = createAuditLog(await tempLogPath());
+  const app = express();
+  app.use(express.json());
+  app.use('/translator-api', createAuditMiddleware(log, 'translator'), (req, res) => {
+    res.status(req.method === 'DELETE' ? 502 : 200).json({ ok: true });
+  });
+  const server = app.listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const base = `http://127.0.0.1:${port}/translator-api/v1/example`;
+
+  await fetch(base);
+  await fetch(base, { method: 'POST', headers: { 'Content-Type': 'application/json' }, body: '{"a":1}' });
+  await fetch(base, { method: 'DELETE' });
+  // res 'finish' fires after the response is flushed; give the append a tick.
+  await new Promise((resolve) => setTimeout(resolve, 50));
+
+  const entries = await log.query();
+  assert.deepEqual(entries.map((e) => [e.method, e.status]), [['DELETE', 502], ['POST', 200]]);
+  assert.equal(entries[1].path, '/translator-api/v1/example');
+  assert.equal(entries[1].bodyDigest, digestBody({ a: 1 }));
+});
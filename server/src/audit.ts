@@ -0,0 +1,172 @@
+/**
+ * Audit log of mutating requests proxied to the Translator/JDC monitoring APIs,
+ * and of start/stop/restart actions on their containers.
+ *
+ * Entries are appended as JSON lines to CONFIG_DIR/audit.log. The file is
+ * rotated to audit.log.1 once it grows past MAX_AUDIT_LOG_BYTES, so at most
+ * two generations are kept.
+ */
+
+import crypto from 'crypto';
+import fs from 'fs/promises';
+import path from 'path';
+import type { Request, RequestHandler } from 'express';
+import { getRequestRole, type Role } from './auth.js';
+import { createLogger } from './logger.js';
+import type { MonitoringService } from './monitoring.js';
+
+const log = createLogger('audit');
+
+export interface AuditEntry {
+  at: string;
+  service: MonitoringService;
+  method: string;
+  path: string;
+  status: number;
+  client: string | null;
+  role: Role;
+  // The user account or tunnel user behind the request, if any.
+  user?: string | null;
+  requestId: string | null;
+  // sha256 of the request body as forwarded upstream, so changes can be
+  // matched against a known payload without storing its contents.
+  bodyDigest: string | null;
+}
+
+export interface AuditQuery {
+  service?: MonitoringService;
+  since?: Date;
+  limit?: number;
+}
+
+const MUTATING_METHODS = new Set(['POST', 'PUT', 'PATCH', 'DELETE']);
+export const MAX_AUDIT_LOG_BYTES = 5 * 1024 * 1024;
+const DEFAULT_QUERY_LIMIT = 100;
+
+export function digestBody(body: unknown): string | null {
+  if (body === undefined || body === null || (typeof body === 'object' && Object.keys(body).length === 0)) {
+    return null;
+  }
+  const serialized = typeof body === 'string' ? body : JSON.stringify(body);
+  return `sha256:${crypto.createHash('sha256').update(serialized).digest('hex')}`;
+}
+
+export function createAuditLog(filePath: string, maxBytes = MAX_AUDIT_LOG_BYTES) {
+  // Serialize appends so rotation never races a concurrent write.
+  let pending: Promise<void> = Promise.resolve();
+
+  async function rotateIfNeeded(): Promise<void> {
+    try {
+      const { size } = await fs.stat(filePath);
+      if (size >= maxBytes) {
+        await fs.rename(filePath, `${filePath}.1`);
+      }
+    } catch {
+      // No log yet.
+    }
+  }
+
+  function append(entry: AuditEntry): Promise<void> {
+    pending = pending.then(async () => {
+      await fs.mkdir(path.dirname(filePath), { recursive: true });
+      await rotateIfNeeded();
+      await fs.appendFile(filePath, `${JSON.stringify(entry)}\n`);
+    }).catch((error) => {
+      log.error('Audit log write failed:', error);
+    });
+    return pending;
+  }
+
+  async function readEntries(file: string): Promise<AuditEntry[]> {
+    try {
+      const content = await fs.readFile(file, 'utf-8');
+      return content.split('\n').filter(Boolean).flatMap((line) => {
+        try {
+          return [JSON.parse(line) as AuditEntry];
+        } catch {
+          return [];
+        }
+      });
+    } catch {
+      return [];
+    }
+  }
+
+  /**
+   * Most recent first.
+   */
+  async function query({ service, since, limit = DEFAULT_QUERY_LIMIT }: AuditQuery = {}): Promise<AuditEntry[]> {
+    await pending;
+    const entries = [...await readEntries(`${filePath}.1`), ...await readEntries(filePath)];
+
+    return entries
+      .filter((entry) => !service || entry.service === service)
+      .filter((entry) => !since || Date.parse(entry.at) >= since.getTime())
+      .reverse()
+      .slice(0, limit);
+  }
+
+  return { append, query };
+}
+
+export type AuditLog = ReturnType<typeof createAuditLog>;
+
+function headerValue(req: Request, name: string): string | null {
+  const value = req.headers[name];
+  return (Array.isArray(value) ? value[0] : value) ?? null;
+}
+
+/**
+ * Record mutating requests once the proxied response has been sent.
+ * Must run before the proxy handler so req.url still carries the full path.
+ */
+export function createAuditMiddleware(auditLog: AuditLog, service: MonitoringService): RequestHandler {
+  return (req, res, next) => {
+    if (!MUTATING_METHODS.has(req.method)) return next();
+
+    const requestPath = req.originalUrl;
+    res.on('finish', () => {
+      void auditLog.append({
+        at: new Date().toISOString(),
+        service,
+        method: req.method,
+        path: requestPath,
+        status: res.statusCode,
+        client: req.ip ?? null,
+        role: getRequestRole(req),
+        user: res.locals.user ?? res.locals.tunnelUser ?? null,
+        // Set by the proxy on the response, whether reused or minted.
+        requestId: (res.getHeader('X-Request-Id') as string | undefined) ?? headerValue(req, 'x-request-id'),
+        bodyDigest: digestBody(req.body),
+      });
+    });
+    next();
+  };
+}
+
+export function parseAuditQuery(query: Record<string, unknown>): AuditQuery {
+  const { service, since, limit } = query;
+  const parsed: AuditQuery = {};
+
+  if (service !== undefined) {
+    if (service !== 'translator' && service !== 'jdc') {
+      throw new Error('service must be "translator" or "jdc"');
+    }
+    parsed.service = service;
+  }
+  if (since !== undefined) {
+    if (typeof since !== 'string' || Number.isNaN(Date.parse(since))) {
+      throw new Error('since must be an ISO 8601 timestamp');
+    }
+    parsed.since = new Date(since);
+  }
+  if (limit !== undefined) {
+    const value = Number(limit);
+    if (!Number.isInteger(value) || value < 1 || value > 1000) {
+      throw new Error('limit must be between 1 and 1000');
+    }
+    parsed.limit = value;
+  }
+
+  return parsed;
+}
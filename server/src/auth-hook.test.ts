@@ -0,0 +1,150 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import http from 'node:http';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { createAuthHook, mapHookResponse, MAX_CONCURRENT_CALLS, parseAuthHookConfig, parseCredentials } from './auth-hook.js';
+import { createTokenBucketLimiter } from './rate-limit.js';
+import type { AuthHookConfig } from './auth-hook.js';
+
+function basic(username: string, password: string): string {
+  return `Basic ${Buffer.from(`${username}:${password}`).toString('base64')}`;
+}
+
+test('parses the hook configuration', () => {
+  assert.equal(parseAuthHookConfig({}), null);
+  assert.deepEqual(parseAuthHookConfig({ AUTH_HOOK_COMMAND: '/usr/local/bin/check-ldap --realm mining', AUTH_HOOK_ADMIN_GROUPS: 'ops, admins' }), {
+    target: { kind: 'command', file: '/usr/local/bin/check-ldap', args: ['--realm', 'mining'] },
+    adminGroups: ['ops', 'admins'],
+    cacheMs: 60_000,
+    timeoutMs: 5000,
+  });
+  assert.equal(parseAuthHookConfig({ AUTH_HOOK_URL: 'https://sso.lan/verify', AUTH_HOOK_CACHE_SECS: '0' })?.cacheMs, 0);
+  assert.throws(() => parseAuthHookConfig({ AUTH_HOOK_URL: 'ftp://sso.lan' }), /must be http\(s\)/);
+  assert.throws(() => parseAuthHookConfig({ AUTH_HOOK_URL: 'https://sso.lan', AUTH_HOOK_COMMAND: 'x' }), /only one of/);
+});
+
+test('parses basic and bearer credentials', () => {
+  assert.deepEqual(parseCredentials(basic('alice', 'p:ss')), { scheme: 'basic', username: 'alice', password: 'p:ss', token: null });
+  assert.deepEqual(parseCredentials('Bearer abc'), { scheme: 'bearer', username: null, password: null, token: 'abc' });
+  assert.equal(parseCredentials(`Basic ${Buffer.from('nocolon').toString('base64')}`), null);
+  assert.equal(parseCredentials(undefined), null);
+});
+
+test('maps hook responses to roles', () => {
+  assert.equal(mapHookResponse({ role: 'admin' }, []), 'admin');
+  assert.equal(mapHookResponse({ groups: ['miners', 'ops'] }, ['ops']), 'admin');
+  assert.equal(mapHookResponse({ groups: ['miners'] }, ['ops']), 'viewer');
+  assert.equal(mapHookResponse({ role: 'root' }, []), 'viewer');
+});
+
+test('caches verdicts but not hook failures', async () => {
+  let calls = 0;
+  let fail = false;
+  const config: AuthHookConfig = { target: { kind: 'http', url: 'http://hook' }, adminGroups: [], cacheMs: 1000, timeoutMs: 100 };
+  let now = 0;
+  const hook = createAuthHook(config, {
+    now: () => now,
+    call: async (_target, credentials) => {
+      calls++;
+      if (fail) throw new Error('hook down');
+      return credentials.password === 'right' ? { role: 'admin' } : null;
+    },
+  });
+
+  const alice = parseCredentials(basic('alice', 'right'))!;
+  assert.equal(await hook.verify(alice), 'admin');
+  assert.equal(await hook.verify(alice), 'admin');
+  assert.equal(calls, 1);
+  assert.equal(await hook.verify(parseCredentials(basic('alice', 'wrong'))!), 'viewer');
+
+  now = 2000;
+  fail = true;
+  assert.equal(await hook.verify(alice), 'viewer');
+  assert.equal(await hook.verify(alice), 'viewer');
+  assert.equal(calls, 4);
+});
+
+test('HTTP hooks receive the credentials and answer with a role', async (t) => {
+  const server = http.createServer((req, res) => {
+    let body = '';
+    req.on('data', (chunk) => { body += chunk; });
+    req.on('end', () => {
+      const { username, password } = JSON.parse(body);
+      if (password !== 'secret') return res.writeHead(401).end();
+      res.writeHead(200, { 'Content-Type': 'application/json' }).end(JSON.stringify({ groups: username === 'ops' ? ['admins'] : [] }));
+    });
+  }).listen(0, '127.0.0.1');
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+
+  const hook = createAuthHook(parseAuthHookConfig({ AUTH_HOOK_URL: `http://127.0.0.1:${port}/verify`, AUTH_HOOK_ADMIN_GROUPS: 'admins' })!);
+  assert.equal(await hook.verify(parseCredentials(basic('ops', 'secret'))!), 'admin');
+  assert.equal(await hook.verify(parseCredentials(basic('bob', 'secret'))!), 'viewer');
+  assert.equal(await hook.verify(parseCredentials(basic('ops', 'guess'))!), 'viewer');
+});
+
+test('command hooks read credentials on stdin and accept with exit code 0', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-auth-hook-'));
+  const script = path.join(dir, 'hook.mjs');
+  await fs.writeFile(script, `
+let input = '';
+process.stdin.on('data', (chunk) => { input += chunk; });
+process.stdin.on('end', () => {
+  const { token } = JSON.parse(input);
+  if (token !== 'letmein') process.exit(1);
+  process.stdout.write(JSON.stringify({ role: 'admin' }));
+});
+`);
+
+  const hook = createAuthHook({
+    target: { kind: 'command', file: process.execPath, args: [script] },
+    adminGroups: [],
+    cacheMs: 0,
+    timeoutMs: 5000,
+  });
+  assert.equal(await hook.verify(parseCredentials('Bearer letmein')!), 'admin');
+  assert.equal(await hook.verify(parseCredentials('Bearer nope')!), 'viewer');
+  assert.equal(hook.size(), 0);
+});
+
+test('limits concurrent hook calls and failed checks per client', async () => {
+  const config: AuthHookConfig = { target: { kind: 'http', url: 'http://hook' }, adminGroups: [], cacheMs: 1000, timeoutMs: 100 };
+  const pending: Array<() => void> = [];
+  let running = 0;
+  let peak = 0;
+  const hook = createAuthHook(config, {
+    failures: createTokenBucketLimiter({ ratePerSecond: 1 / 6, burst: 2 }),
+    call: async (_target, credentials) => {
+      running++;
+      peak = Math.max(peak, running);
+      await new Promise<void>((resolve) => pending.push(resolve));
+      running--;
+      return credentials.password === 'right' ? { role: 'admin' } : null;
+    },
+  });
+
+  const users = Array.from({ length: MAX_CONCURRENT_CALLS + 2 }, (_, index) => `user${index}`);
+  const verdicts = Promise.all(users.map((user) => hook.verify(parseCredentials(basic(user, 'right'))!, '10.0.0.1')));
+  await new Promise((resolve) => setImmediate(resolve));
+  assert.equal(hook.inFlight(), MAX_CONCURRENT_CALLS);
+  while (running > 0 || pending.length > 0) {
+    pending.shift()?.();
+    await new Promise((resolve) => setImmediate(resolve));
+  }
+  assert.deepEqual(await verdicts, users.map(() => 'admin'));
+  assert.equal(peak, MAX_CONCURRENT_CALLS);
+  assert.equal(hook.inFlight(), 0);
+
+  for (const guess of ['guess 1', 'guess 2']) {
+    const verdict = hook.verify(parseCredentials(basic('user0', guess))!, '10.0.0.1');
+    await new Promise((resolve) => setImmediate(resolve));
+    pending.shift()!();
+    assert.equal(await verdict, 'viewer');
+  }
+  assert.ok(hook.retryAfterMs(parseCredentials(basic('user0', 'guess 3'))!, '10.0.0.1') > 0);
+  assert.equal(hook.retryAfterMs(parseCredentials(basic('user0', 'right'))!, '10.0.0.1'), 0);
+  assert.equal(hook.retryAfterMs(parseCredentials(basic('user0', 'guess 3'))!, '10.0.0.2'), 0);
+});
@@ -0,0 +1,304 @@
+/**
+ * Delegated credential checks, for environments where the operator's
+ * identities live elsewhere (LDAP, PAM, a custom SSO).
+ *
+ * With AUTH_HOOK_URL or AUTH_HOOK_COMMAND set, credentials sent as
+ * `Authorization: Basic ...` or as a bearer token that isn't ADMIN_TOKEN or
+ * an API key are passed to the hook, which decides the role:
+ *
+ * - AUTH_HOOK_URL gets a JSON `POST` of the credentials. A 2xx response
+ *   accepts them, 401/403 rejects them.
+ * - AUTH_HOOK_COMMAND is run without a shell, with the credentials as JSON on
+ *   stdin. Exit code 0 accepts them, anything else rejects them.
+ *
+ * An accepted response's JSON body (or the command's stdout) names the role
+ * as `{"role": "admin"}` (or operator or viewer), or lists `groups` that are
+ * admin when one of them is in AUTH_HOOK_ADMIN_GROUPS. Anything else is a
+ * viewer. Rejected credentials, and requests without any, are viewers too:
+ * once a hook is configured, nobody is admin by default.
+ *
+ * At most MAX_CONCURRENT_CALLS hook calls run at once; the rest wait their
+ * turn. Rejections and hook failures count against the client's login rate
+ * limit (see rate-limit.ts); past it, credentials the hook hasn't accepted
+ * lately get 429.
+ */
+
+import { spawn } from 'child_process';
+import crypto from 'crypto';
+import type { RequestHandler } from 'express';
+import { resolveRole, ROLES } from './auth.js';
+import type { Role } from './auth.js';
+import { createLogger } from './logger.js';
+import { createTokenBucketLimiter, LOGIN_RATE_LIMIT, sendRateLimited } from './rate-limit.js';
+import type { TokenBucketLimiter } from './rate-limit.js';
+
+const log = createLogger('auth-hook');
+
+export interface AuthCredentials {
+  scheme: 'basic' | 'bearer';
+  username: string | null;
+  password: string | null;
+  token: string | null;
+}
+
+export type AuthHookTarget =
+  | { kind: 'http'; url: string }
+  | { kind: 'command'; file: string; args: string[] };
+
+export interface AuthHookConfig {
+  target: AuthHookTarget;
+  adminGroups: string[];
+  cacheMs: number;
+  timeoutMs: number;
+}
+
+// Raw verdict from the hook: null when the credentials were rejected.
+export type AuthHookResult = unknown | null;
+
+export interface AuthHookDeps {
+  call?: (target: AuthHookTarget, credentials: AuthCredentials, timeoutMs: number) => Promise<AuthHookResult>;
+  now?: () => number;
+  // Charged for each failed check, per client.
+  failures?: TokenBucketLimiter;
+}
+
+const DEFAULT_CACHE_SECS = 60;
+const HOOK_TIMEOUT_MS = 5000;
+const MAX_CACHE_ENTRIES = 1000;
+const MAX_OUTPUT_BYTES = 64 * 1024;
+export const MAX_CONCURRENT_CALLS = 4;
+const MAX_QUEUED_CALLS = 100;
+
+export function parseAuthHookConfig(env: NodeJS.ProcessEnv = process.env): AuthHookConfig | null {
+  const url = env.AUTH_HOOK_URL?.trim();
+  const command = env.AUTH_HOOK_COMMAND?.trim();
+  if (!url && !command) return null;
+  if (url && command) {
+    throw new Error('Set only one of AUTH_HOOK_URL and AUTH_HOOK_COMMAND');
+  }
+
+  let target: AuthHookTarget;
+  if (url) {
+    let parsed: URL;
+    try {
+      parsed = new URL(url);
+    } catch {
+      throw new Error(`Invalid AUTH_HOOK_URL "${url}"`);
+    }
+    if (parsed.protocol !== 'http:' && parsed.protocol !== 'https:') {
+      throw new Error('AUTH_HOOK_URL must be http(s)');
+    }
+    target = { kind: 'http', url: parsed.toString() };
+  } else {
+    const [file, ...args] = command!.split(/\s+/);
+    target = { kind: 'command', file, args };
+  }
+
+  const rawCache = env.AUTH_HOOK_CACHE_SECS?.trim();
+  const cacheSecs = rawCache ? Number(rawCache) : DEFAULT_CACHE_SECS;
+  if (!Number.isFinite(cacheSecs) || cacheSecs < 0) {
+    throw new Error(`Invalid AUTH_HOOK_CACHE_SECS "${rawCache}"`);
+  }
+
+  return {
+    target,
+    adminGroups: (env.AUTH_HOOK_ADMIN_GROUPS ?? '').split(',').map((group) => group.trim()).filter(Boolean),
+    cacheMs: cacheSecs * 1000,
+    timeoutMs: HOOK_TIMEOUT_MS,
+  };
+}
+
+export function parseCredentials(authorization: string | undefined): AuthCredentials | null {
+  const match = authorization?.match(/^(Basic|Bearer)\s+(.+)$/i);
+  if (!match) return null;
+
+  if (match[1].toLowerCase() === 'bearer') {
+    return { scheme: 'bearer', username: null, password: null, token: match[2].trim() };
+  }
+
+  const decoded = Buffer.from(match[2].trim(), 'base64').toString('utf-8');
+  const separator = decoded.indexOf(':');
+  if (separator <= 0) return null;
+  return { scheme: 'basic', username: decoded.slice(0, separator), password: decoded.slice(separator + 1), token: null };
+}
+
+/**
+ * The role an accepted hook response grants.
+ */
+export function mapHookResponse(body: unknown, adminGroups: string[]): Role {
+  const { role, groups } = (body ?? {}) as { role?: unknown; groups?: unknown };
+  if (ROLES.includes(role as Role)) return role as Role;
+  if (Array.isArray(groups) && groups.some((group) => typeof group === 'string' && adminGroups.includes(group))) {
+    return 'admin';
+  }
+  return 'viewer';
+}
+
+function parseJson(text: string): unknown {
+  try {
+    return text.trim() ? JSON.parse(text) : {};
+  } catch {
+    return {};
+  }
+}
+
+async function callHttpHook(url: string, credentials: AuthCredentials, timeoutMs: number): Promise<AuthHookResult> {
+  const response = await fetch(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json', Accept: 'application/json' },
+    body: JSON.stringify(credentials),
+    signal: AbortSignal.timeout(timeoutMs),
+  });
+  const text = await response.text();
+  if (response.status === 401 || response.status === 403) return null;
+  if (!response.ok) {
+    throw new Error(`Auth hook returned HTTP ${response.status}`);
+  }
+  return parseJson(text);
+}
+
+function callCommandHook(
+  file: string,
+  args: string[],
+  credentials: AuthCredentials,
+  timeoutMs: number,
+): Promise<AuthHookResult> {
+  return new Promise((resolve, reject) => {
+    const child = spawn(file, args, { stdio: ['pipe', 'pipe', 'inherit'], timeout: timeoutMs });
+    let stdout = '';
+    child.stdout.setEncoding('utf-8');
+    child.stdout.on('data', (chunk: string) => {
+      if (stdout.length < MAX_OUTPUT_BYTES) stdout += chunk;
+    });
+    child.on('error', reject);
+    child.on('close', (code, signal) => {
+      if (signal) return reject(new Error(`Auth hook command killed by ${signal}`));
+      resolve(code === 0 ? parseJson(stdout) : null);
+    });
+    // The command may exit without reading its input.
+    child.stdin.on('error', () => undefined);
+    child.stdin.end(JSON.stringify(credentials));
+  });
+}
+
+function callHook(target: AuthHookTarget, credentials: AuthCredentials, timeoutMs: number): Promise<AuthHookResult> {
+  return target.kind === 'http'
+    ? callHttpHook(target.url, credentials, timeoutMs)
+    : callCommandHook(target.file, target.args, credentials, timeoutMs);
+}
+
+export function createAuthHook(config: AuthHookConfig, deps: AuthHookDeps = {}) {
+  const call = deps.call ?? callHook;
+  const now = deps.now ?? Date.now;
+  const failures = deps.failures ?? createTokenBucketLimiter(LOGIN_RATE_LIMIT);
+  // Keyed by a hash of the credentials, so secrets aren't kept in memory.
+  const cache = new Map<string, { role: Role; expiresAt: number }>();
+  // Calls waiting for a slot, each woken by the call that hands it over.
+  const queue: Array<() => void> = [];
+  let inFlight = 0;
+
+  const keyOf = (credentials: AuthCredentials) => crypto.createHash('sha256').update(JSON.stringify(credentials)).digest('hex');
+
+  function cached(key: string): Role | null {
+    const entry = cache.get(key);
+    return entry && entry.expiresAt > now() ? entry.role : null;
+  }
+
+  /**
+   * Run `fn` once fewer than MAX_CONCURRENT_CALLS hook calls are running, so
+   * a flood of new credentials can't start the hook command without limit.
+   */
+  async function withSlot<T>(fn: () => Promise<T>): Promise<T> {
+    if (inFlight < MAX_CONCURRENT_CALLS) {
+      inFlight += 1;
+    } else {
+      if (queue.length >= MAX_QUEUED_CALLS) throw new Error('Too many auth hook calls waiting');
+      await new Promise<void>((resolve) => queue.push(resolve));
+    }
+    try {
+      return await fn();
+    } finally {
+      const next = queue.shift();
+      if (next) next();
+      else inFlight -= 1;
+    }
+  }
+
+  function remember(key: string, role: Role): void {
+    if (config.cacheMs === 0) return;
+    if (cache.size >= MAX_CACHE_ENTRIES) {
+      for (const [entryKey, entry] of cache) {
+        if (entry.expiresAt <= now()) cache.delete(entryKey);
+      }
+      if (cache.size >= MAX_CACHE_ENTRIES) cache.delete(cache.keys().next().value!);
+    }
+    cache.set(key, { role, expiresAt: now() + config.cacheMs });
+  }
+
+  /**
+   * The role for `credentials`. Rejected credentials and hook failures are
+   * viewers; failures aren't cached, so the next request tries again.
+   */
+  async function verify(credentials: AuthCredentials, client = 'unknown'): Promise<Role> {
+    const key = keyOf(credentials);
+    const hit = cached(key);
+    if (hit) return hit;
+
+    let result: AuthHookResult;
+    try {
+      result = await withSlot(() => call(config.target, credentials, config.timeoutMs));
+    } catch (error) {
+      log.error('Auth hook failed:', error instanceof Error ? error.message : error);
+      failures.take(client, now());
+      return 'viewer';
+    }
+
+    if (result === null) failures.take(client, now());
+    const role = result === null ? 'viewer' : mapHookResponse(result, config.adminGroups);
+    remember(key, role);
+    return role;
+  }
+
+  /**
+   * How long `client` must wait before the hook checks `credentials`, once
+   * it has failed too often lately; 0 when they are cached.
+   */
+  function retryAfterMs(credentials: AuthCredentials, client = 'unknown'): number {
+    return cached(keyOf(credentials)) ? 0 : failures.peek(client, now());
+  }
+
+  return { verify, retryAfterMs, size: () => cache.size, inFlight: () => inFlight };
+}
+
+export type AuthHook = ReturnType<typeof createAuthHook>;
+
+/**
+ * Resolve every request's role through the hook, except API keys and
+ * ADMIN_TOKEN, which are checked locally first.
+ */
+export function createAuthHookMiddleware(
+  hook: AuthHook,
+  adminToken: string | undefined = process.env.ADMIN_TOKEN,
+): RequestHandler {
+  return (req, res, next) => {
+    if (res.locals.apiKeyId) return next();
+    // Already identified by the tunnel (see tunnel.ts) or signed in as a user (see users.ts).
+    if (res.locals.tunnelUser !== undefined || res.locals.user !== undefined) return next();
+    if (adminToken && resolveRole(req.headers.authorization, adminToken) === 'admin') return next();
+
+    const credentials = parseCredentials(req.headers.authorization);
+    if (!credentials) {
+      res.locals.role = 'viewer';
+      return next();
+    }
+
+    const client = req.ip ?? 'unknown';
+    const retryAfterMs = hook.retryAfterMs(credentials, client);
+    if (retryAfterMs > 0) return sendRateLimited(res, retryAfterMs);
+
+    hook.verify(credentials, client).then((role) => {
+      res.locals.role = role;
+      next();
+    }, next);
+  };
+}
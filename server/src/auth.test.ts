@@ -0,0 +1,83 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import path from 'node:path';
+import { test } from 'node:test';
+import { fileURLToPath } from 'node:url';
+import * as auth from './auth.js';
+import { getBearerToken, getRequestRole, hasRole, requireOperator, requireOperatorForWrites, resolveRole } from './auth.js';
+
+// Run a guard for a request with this role; the status it answered, or 0 when it let the request through.
+function guard(handler: (req: never, res: never, next: () => void) => void, role: string, method = 'POST') {
+  let status = 0;
+  const res = { status: (code: number) => ((status = code), { json: () => {} }) };
+  let passed = false;
+  handler({ method, headers: {}, res: { locals: { role } } } as never, res as never, () => (passed = true));
+  return passed ? 0 : status;
+}
+
+test('every request is admin while no admin token is configured', () => {
+  assert.equal(resolveRole(undefined, undefined), 'admin');
+  assert.equal(resolveRole('Bearer anything', ''), 'admin');
+});
+
+test('only the configured bearer token grants the admin role', () => {
+  assert.equal(resolveRole('Bearer s3cret', 's3cret'), 'admin');
+  assert.equal(resolveRole('bearer s3cret', 's3cret'), 'admin');
+  assert.equal(resolveRole('Bearer wrong', 's3cret'), 'viewer');
+  assert.equal(resolveRole('Basic czNjcmV0', 's3cret'), 'viewer');
+  assert.equal(resolveRole(undefined, 's3cret'), 'viewer');
+});
+
+test('extracts bearer tokens from Authorization headers', () => {
+  assert.equal(getBearerToken('Bearer  abc '), 'abc');
+  assert.equal(getBearerToken('Token abc'), null);
+  assert.equal(getBearerToken(undefined), null);
+});
+
+test('operators can do what viewers can, and admins what operators can', () => {
+  assert.equal(hasRole('admin', 'operator'), true);
+  assert.equal(hasRole('operator', 'operator'), true);
+  assert.equal(hasRole('viewer', 'operator'), false);
+  assert.equal(hasRole('operator', 'admin'), false);
+
+  const request = (role: string) => ({ headers: {}, res: { locals: { role } } }) as never;
+  assert.equal(getRequestRole(request('operator')), 'operator');
+  let status = 0;
+  const res = { status: (code: number) => ((status = code), { json: () => {} }) } as never;
+  let passed = false;
+  requireOperator(request('operator'), res, () => (passed = true));
+  assert.equal(passed, true);
+  requireOperator(request('viewer'), res, () => assert.fail('viewers are not operators'));
+  assert.equal(status, 403);
+});
+
+test('viewers get 403 from stack configuration and control', async () => {
+  const index = await fs.readFile(path.join(path.dirname(fileURLToPath(import.meta.url)), 'index.ts'), 'utf-8');
+  const routes: Record<string, 'admin' | 'operator'> = {
+    "put('/api/config'": 'admin',
+    "post('/api/setup'": 'admin',
+    "post('/api/reset'": 'admin',
+    "post('/api/stop'": 'operator',
+    "post('/api/restart'": 'operator',
+    "post('/api/validate/bitcoin-socket'": 'operator',
+    "post('/api/poll-now'": 'operator',
+    "post('/api/warm-restart'": 'operator',
+  };
+  for (const [route, required] of Object.entries(routes)) {
+    const name = index.match(new RegExp(`router\\.${route.replace(/[()/.]/g, '\\$&')},\\s*(\\w+)`))?.[1];
+    const handler = name ? (auth as unknown as Record<string, unknown>)[name] : undefined;
+    assert.equal(typeof handler, 'function', `${route} has no role guard`);
+    assert.equal(guard(handler as never, 'viewer'), 403, `${route} as a viewer`);
+    assert.equal(guard(handler as never, 'operator'), required === 'operator' ? 0 : 403, `${route} as an operator`);
+    assert.equal(guard(handler as never, 'admin'), 0, `${route} as an admin`);
+  }
+});
+
+test('the monitoring proxies let viewers read but not write', () => {
+  assert.equal(guard(requireOperatorForWrites, 'viewer', 'GET'), 0);
+  assert.equal(guard(requireOperatorForWrites, 'viewer', 'HEAD'), 0);
+  for (const method of ['POST', 'PUT', 'PATCH', 'DELETE']) {
+    assert.equal(guard(requireOperatorForWrites, 'viewer', method), 403, method);
+    assert.equal(guard(requireOperatorForWrites, 'operator', method), 0, method);
+  }
+});
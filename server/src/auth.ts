@@ -0,0 +1,97 @@
+/**
+ * Role-based access control.
+ *
+ * Viewers can read, operators can also trigger actions such as restarting a
+ * service or forcing a poll, and admins can also change settings and manage
+ * users.
+ *
+ * sv2-ui started as a single-operator tool: while ADMIN_TOKEN is unset and
+ * there are no user accounts, every request is treated as the admin, which is
+ * how the server behaved before access control existed. Once a token is
+ * configured, requests presenting it as `Authorization: Bearer <token>` get
+ * the admin role; everyone else is a read-only viewer.
+ *
+ * API keys (see api-keys.ts) always get the viewer role. User accounts
+ * (see users.ts) sign in with `Authorization: Basic` or a session cookie
+ * (see sessions.ts) and get their own role.
+ * Other credentials can be checked by an external hook instead (see
+ * auth-hook.ts), and requests through a Cloudflare or Tailscale tunnel can be
+ * identified by the tunnel (see tunnel.ts).
+ */
+
+import crypto from 'crypto';
+import type { Request, RequestHandler } from 'express';
+
+export type Role = 'admin' | 'operator' | 'viewer';
+
+// Lowest first; each role can do everything the ones before it can.
+export const ROLES: Role[] = ['viewer', 'operator', 'admin'];
+
+export function hasRole(role: Role, required: Role): boolean {
+  return ROLES.indexOf(role) >= ROLES.indexOf(required);
+}
+
+function tokensMatch(provided: string, expected: string): boolean {
+  const providedBuffer = Buffer.from(provided);
+  const expectedBuffer = Buffer.from(expected);
+  return providedBuffer.length === expectedBuffer.length
+    && crypto.timingSafeEqual(providedBuffer, expectedBuffer);
+}
+
+export function getBearerToken(authorization: string | undefined): string | null {
+  const match = authorization?.match(/^Bearer\s+(.+)$/i);
+  return match ? match[1].trim() : null;
+}
+
+export function resolveRole(
+  authorization: string | undefined,
+  adminToken: string | undefined = process.env.ADMIN_TOKEN,
+): Role {
+  if (!adminToken) {
+    return 'admin';
+  }
+
+  const token = getBearerToken(authorization);
+  return token !== null && tokensMatch(token, adminToken) ? 'admin' : 'viewer';
+}
+
+export function getRequestRole(req: Request): Role {
+  // API keys are viewer-only, even while ADMIN_TOKEN is unset.
+  if (req.res?.locals.apiKeyId) return 'viewer';
+  // Decided by the tunnel identity (see tunnel.ts), a user account (see
+  // users.ts) or the auth hook (see auth-hook.ts), when one is configured.
+  const role = req.res?.locals.role;
+  if (ROLES.includes(role)) return role;
+  return resolveRole(req.headers.authorization);
+}
+
+/**
+ * Reject requests that don't carry the admin role.
+ */
+export const requireAdmin: RequestHandler = (req, res, next) => {
+  if (getRequestRole(req) !== 'admin') {
+    res.status(403).json({ error: 'Admin access required' });
+    return;
+  }
+  next();
+};
+
+/**
+ * Reject requests that don't carry the operator or admin role.
+ */
+export const requireOperator: RequestHandler = (req, res, next) => {
+  if (!hasRole(getRequestRole(req), 'operator')) {
+    res.status(403).json({ error: 'Operator access required' });
+    return;
+  }
+  next();
+};
+
+/**
+ * Let reads through, and hold other methods to requireOperator. For the
+ * monitoring proxies, which forward every method upstream.
+ */
+export const requireOperatorForWrites: RequestHandler = (req, res, next) => {
+  if (req.method === 'GET' || req.method === 'HEAD') return next();
+  requireOperator(req, res, next);
+};
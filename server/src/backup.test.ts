@@ -0,0 +1,47 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+
+import { backupHistory, parseBackupArgs, restoreHistory } from './backup.js';
+import { createHistoryStore } from './history.js';
+
+test('parseBackupArgs takes the file and --force', () => {
+  assert.deepEqual(parseBackupArgs('restore', ['old.db', '--force']), { file: path.resolve('old.db'), force: true });
+  assert.equal(parseBackupArgs('backup', ['--data-dir', '/srv/sv2', 'out.db']).file, path.resolve('out.db'));
+  assert.throws(() => parseBackupArgs('backup', []), /backup requires a file/);
+  assert.throws(() => parseBackupArgs('restore', ['old.db', '--yes']), /Unknown restore option "--yes"/);
+});
+
+test('a backup carries the history and config files to a new config directory', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-backup-'));
+  const source = { configDir: path.join(dir, 'old'), historyDb: path.join(dir, 'old', 'history.db') };
+  await fs.mkdir(path.join(source.configDir, 'stacks'), { recursive: true });
+  await fs.writeFile(path.join(source.configDir, 'state.json'), '{"configured":true}');
+  await fs.writeFile(path.join(source.configDir, 'stacks', 'rig.json'), '{}');
+  const history = createHistoryStore(source.historyDb);
+  history.record([{ metric: 'stack.hashrate', key: '', at: '2026-01-01T00:00:00.000Z', value: 1e12 }]);
+
+  // Taken while the store is still open, as with a running server.
+  const file = path.join(dir, 'backup.db');
+  assert.deepEqual(backupHistory(source, file), { file, configFiles: 2 });
+  history.close();
+  assert.throws(() => backupHistory(source, file), /already exists/);
+
+  const target = { configDir: path.join(dir, 'new'), historyDb: path.join(dir, 'new', 'history.db') };
+  const open = createHistoryStore(target.historyDb);
+  open.record([{ metric: 'stack.hashrate', key: '', at: '2026-02-01T00:00:00.000Z', value: 1 }]);
+  assert.throws(() => restoreHistory(target, { file, force: false }), /stop the server first/);
+  open.close();
+
+  assert.deepEqual(restoreHistory(target, { file, force: false }), { file, configFiles: 2 });
+  assert.equal(await fs.readFile(path.join(target.configDir, 'state.json'), 'utf8'), '{"configured":true}');
+  assert.equal(await fs.readFile(path.join(target.configDir, 'stacks', 'rig.json'), 'utf8'), '{}');
+  const restored = createHistoryStore(target.historyDb);
+  assert.deepEqual(restored.query('stack.hashrate').map(({ value }) => value), [1e12]);
+  restored.close();
+
+  await fs.writeFile(path.join(dir, 'other.db'), 'not a database');
+  assert.throws(() => restoreHistory(target, { file: path.join(dir, 'other.db'), force: false }));
+});
@@ -0,0 +1,153 @@
+/**
+ * `backup <file>` and `restore <file>`: the history database and everything
+ * else in CONFIG_DIR (setup state, stores, generated TOML) in one file, for
+ * moving a dashboard to new hardware.
+ *
+ * A backup is itself a SQLite database: an online snapshot of the history
+ * database (VACUUM INTO, which is consistent while the server keeps
+ * writing), plus a table holding the config directory's files. Backing up
+ * works with the server running; restoring replaces the history database
+ * and so refuses to while it's open.
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { DatabaseSync } from 'node:sqlite';
+
+export const BACKUP_FORMAT_VERSION = 1;
+
+export interface BackupPaths {
+  historyDb: string;
+  configDir: string;
+}
+
+export interface BackupArgs {
+  file: string;
+  // Restore over a history database whose WAL is still around, e.g. after a crash.
+  force: boolean;
+}
+
+export interface BackupSummary {
+  file: string;
+  configFiles: number;
+}
+
+const BACKUP_SCHEMA = `
+  CREATE TABLE sv2ui_backup (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+  CREATE TABLE sv2ui_config_files (path TEXT PRIMARY KEY, content BLOB NOT NULL);
+`;
+
+/**
+ * The file and flags after `backup` or `restore`.
+ */
+export function parseBackupArgs(command: 'backup' | 'restore', argv: string[]): BackupArgs {
+  const files = argv.filter((arg) => !arg.startsWith('--'));
+  const unknown = argv.find((arg) => arg.startsWith('--') && arg !== '--force' && !arg.startsWith('--data-dir'));
+  if (unknown) throw new Error(`Unknown ${command} option "${unknown}"`);
+  // --data-dir <path> is read by resolveConfigDir, so its value isn't the file.
+  const dataDirIndex = argv.indexOf('--data-dir');
+  const file = files.find((arg) => dataDirIndex === -1 || arg !== argv[dataDirIndex + 1]);
+  if (!file) throw new Error(`${command} requires a file, e.g. ${command} sv2-ui-backup.db`);
+  return { file: path.resolve(file), force: argv.includes('--force') };
+}
+
+const sqlString = (value: string) => `'${value.replace(/'/g, "''")}'`;
+
+function isHistoryFile(file: string, historyDb: string): boolean {
+  const database = path.resolve(historyDb);
+  return [database, `${database}-wal`, `${database}-shm`, `${database}-journal`].includes(file);
+}
+
+function listConfigFiles(dir: string): string[] {
+  if (!fs.existsSync(dir)) return [];
+  return fs.readdirSync(dir, { recursive: true, withFileTypes: true })
+    .filter((entry) => entry.isFile())
+    .map((entry) => path.join(entry.parentPath, entry.name));
+}
+
+export function backupHistory({ historyDb, configDir }: BackupPaths, file: string, now = new Date()): BackupSummary {
+  if (fs.existsSync(file)) throw new Error(`${file} already exists; pick a new file for the backup`);
+  fs.mkdirSync(path.dirname(file), { recursive: true });
+
+  if (fs.existsSync(historyDb)) {
+    const source = new DatabaseSync(historyDb);
+    try {
+      source.exec(`VACUUM INTO ${sqlString(file)}`);
+    } finally {
+      source.close();
+    }
+  }
+
+  const backup = new DatabaseSync(file);
+  try {
+    // A single self-contained file, rather than the WAL the history database uses.
+    backup.exec('PRAGMA journal_mode = DELETE');
+    backup.exec(BACKUP_SCHEMA);
+    const meta = backup.prepare('INSERT INTO sv2ui_backup (key, value) VALUES (?, ?)');
+    meta.run('version', String(BACKUP_FORMAT_VERSION));
+    meta.run('createdAt', now.toISOString());
+    meta.run('history', String(fs.existsSync(historyDb)));
+
+    const insert = backup.prepare('INSERT INTO sv2ui_config_files (path, content) VALUES (?, ?)');
+    const files = listConfigFiles(configDir).filter((configFile) => configFile !== file && !isHistoryFile(configFile, historyDb));
+    for (const configFile of files) {
+      insert.run(path.relative(configDir, configFile).split(path.sep).join('/'), fs.readFileSync(configFile));
+    }
+    return { file, configFiles: files.length };
+  } finally {
+    backup.close();
+  }
+}
+
+export function restoreHistory({ historyDb, configDir }: BackupPaths, { file, force }: BackupArgs): BackupSummary {
+  if (!fs.existsSync(file)) throw new Error(`${file} doesn't exist`);
+  if (fs.existsSync(`${historyDb}-wal`) && !force) {
+    throw new Error(`${historyDb} is open (its -wal file exists); stop the server first, or pass --force if it crashed`);
+  }
+
+  const backup = new DatabaseSync(file, { readOnly: true });
+  try {
+    let meta: Map<string, string>;
+    try {
+      const rows = backup.prepare('SELECT key, value FROM sv2ui_backup').all() as unknown as { key: string; value: string }[];
+      meta = new Map(rows.map(({ key, value }) => [key, value]));
+    } catch {
+      throw new Error(`${file} isn't an sv2-ui backup`);
+    }
+    if (meta.get('version') !== String(BACKUP_FORMAT_VERSION)) {
+      throw new Error(`${file} is a version ${meta.get('version')} backup; this sv2-ui reads version ${BACKUP_FORMAT_VERSION}`);
+    }
+
+    const configFiles = backup.prepare('SELECT path, content FROM sv2ui_config_files').all() as unknown as { path: string; content: Uint8Array }[];
+    const root = path.resolve(configDir);
+    for (const { path: relative } of configFiles) {
+      const target = path.resolve(root, relative);
+      if (!target.startsWith(root + path.sep)) throw new Error(`${file} has a file outside the config directory: ${relative}`);
+    }
+
+    if (meta.get('history') === 'true') {
+      // Copy to a temporary file and swap it in, so a failed restore leaves the old database alone.
+      const temporary = `${historyDb}.restore-${process.pid}`;
+      fs.mkdirSync(path.dirname(historyDb), { recursive: true });
+      fs.rmSync(temporary, { force: true });
+      backup.exec(`VACUUM INTO ${sqlString(temporary)}`);
+      const restored = new DatabaseSync(temporary);
+      try {
+        restored.exec('DROP TABLE sv2ui_backup; DROP TABLE sv2ui_config_files; VACUUM');
+      } finally {
+        restored.close();
+      }
+      for (const suffix of ['-wal', '-shm']) fs.rmSync(`${historyDb}${suffix}`, { force: true });
+      fs.renameSync(temporary, historyDb);
+    }
+
+    for (const { path: relative, content } of configFiles) {
+      const target = path.resolve(root, relative);
+      fs.mkdirSync(path.dirname(target), { recursive: true });
+      fs.writeFileSync(target, content);
+    }
+    return { file, configFiles: configFiles.length };
+  } finally {
+    backup.close();
+  }
+}
@@ -0,0 +1,67 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import zlib from 'node:zlib';
+import { BADGE_METRICS, isBadgeMetric, renderBadgeSvg, renderSummaryCardPng, renderSummaryCardSvg } from './badges.js';
+import type { StackSummary } from './summary.js';
+
+const SUMMARY: StackSummary = {
+  generatedAt: '2026-01-01T00:00:00.000Z',
+  miningMode: 'pool',
+  mode: 'no-jd',
+  network: null,
+  poolName: 'Pool & Friends',
+  online: true,
+  hashrate: 1.5e12,
+  workers: 3,
+  sharesAccepted: 10,
+  sharesSubmitted: 11,
+  sharesRejected: 1,
+  bestDifficulty: 2048,
+  blocksFound: 0,
+  uptimeSecs: 3660,
+};
+
+test('only known metrics are badge metrics', () => {
+  assert.equal(isBadgeMetric('hashrate'), true);
+  assert.equal(isBadgeMetric('best-diff'), true);
+  assert.equal(isBadgeMetric('toString'), false);
+  assert.equal(isBadgeMetric('nope'), false);
+});
+
+test('badges format stats like the dashboard does', () => {
+  assert.deepEqual(BADGE_METRICS.hashrate(SUMMARY), { label: 'hashrate', value: '1.50 TH/s', color: '#007ec6' });
+  assert.equal(BADGE_METRICS.uptime(SUMMARY).value, '1h 1m');
+  assert.equal(BADGE_METRICS.status({ ...SUMMARY, online: false }).value, 'offline');
+  assert.equal(BADGE_METRICS.workers({ ...SUMMARY, workers: '[redacted]' as unknown as number }).value, '[redacted]');
+});
+
+test('renders well-formed, escaped SVG', () => {
+  const badge = renderBadgeSvg({ label: 'a<b', value: '1 & 2', color: '#4c1' });
+  assert.match(badge, /^<svg xmlns="http:\/\/www\.w3\.org\/2000\/svg"/);
+  assert.match(badge, /a&lt;b/);
+  assert.match(badge, /1 &amp; 2/);
+
+  const card = renderSummaryCardSvg(SUMMARY);
+  assert.match(card, /Pool &amp; Friends/);
+  assert.match(card, /1\.50 TH\/s/);
+  assert.match(card, /10 \/ 11/);
+});
+
+test('renders the summary card as an RGB PNG', () => {
+  const png = renderSummaryCardPng(SUMMARY);
+  assert.deepEqual([...png.subarray(0, 8)], [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]);
+  assert.equal(png.subarray(12, 16).toString('ascii'), 'IHDR');
+  assert.equal(png.readUInt32BE(16), 360);
+  assert.equal(png.readUInt32BE(20), 180);
+  assert.equal(png[25], 2);
+
+  const idatLength = png.readUInt32BE(33);
+  assert.equal(png.subarray(37, 41).toString('ascii'), 'IDAT');
+  const raw = zlib.inflateSync(png.subarray(41, 41 + idatLength));
+  assert.equal(raw.length, (360 * 3 + 1) * 180);
+  // The status dot is green while online, and text is drawn on the background.
+  const pixel = (x: number, y: number) => [...raw.subarray(y * 1081 + 1 + x * 3, y * 1081 + 4 + x * 3)];
+  assert.deepEqual(pixel(26, 30), [0x44, 0xcc, 0x11]);
+  assert.deepEqual(pixel(0, 0), [0x0b, 0x0b, 0x0c]);
+  assert.ok(raw.includes(Buffer.from([0xe5, 0xe5, 0xe5])));
+});
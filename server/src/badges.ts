@@ -0,0 +1,217 @@
+/**
+ * Embeddable SVG badges and summary cards for forum signatures, READMEs, and
+ * status pages.
+ *
+ * Badges follow the shields.io "flat" layout so they sit nicely next to other
+ * badges. Text widths are estimated rather than measured, which is close
+ * enough for the short labels and values rendered here. The summary card also
+ * comes as a PNG, drawn with a bitmap font, for sites that don't accept SVG.
+ */
+
+import { formatDifficulty, formatHashrate, formatUptime } from '@sv2-ui/shared';
+import { formatSummaryValue as show, getTestNetworkLabel } from './summary.js';
+import type { StackSummary } from './summary.js';
+import { escapeMarkup } from './escape.js';
+import { GLYPH_HEIGHT, GLYPH_WIDTH, getGlyph } from './bitmap-font.js';
+import { encodePng } from './qr.js';
+
+const COLORS = {
+  ok: '#4c1',
+  warn: '#dfb317',
+  down: '#e05d44',
+  neutral: '#007ec6',
+  label: '#555',
+};
+
+interface BadgeContent {
+  label: string;
+  value: string;
+  color: string;
+}
+
+export const BADGE_METRICS: Record<string, (summary: StackSummary) => BadgeContent> = {
+  status: (summary) => ({
+    label: 'miner',
+    value: summary.online ? 'online' : 'offline',
+    color: summary.online ? COLORS.ok : COLORS.down,
+  }),
+  hashrate: (summary) => ({
+    label: 'hashrate',
+    value: show(summary.hashrate, formatHashrate),
+    color: summary.online ? COLORS.neutral : COLORS.down,
+  }),
+  workers: (summary) => ({
+    label: 'workers',
+    value: show(summary.workers),
+    color: summary.workers === 0 ? COLORS.warn : COLORS.neutral,
+  }),
+  shares: (summary) => ({
+    label: 'shares',
+    value: `${show(summary.sharesAccepted)} / ${show(summary.sharesSubmitted)}`,
+    color: summary.sharesRejected > 0 ? COLORS.warn : COLORS.neutral,
+  }),
+  'best-diff': (summary) => ({
+    label: 'best diff',
+    value: show(summary.bestDifficulty, formatDifficulty),
+    color: COLORS.neutral,
+  }),
+  blocks: (summary) => ({
+    label: 'blocks found',
+    value: show(summary.blocksFound),
+    color: summary.blocksFound > 0 ? COLORS.ok : COLORS.neutral,
+  }),
+  uptime: (summary) => ({
+    label: 'uptime',
+    value: show(summary.uptimeSecs, formatUptime),
+    color: summary.online ? COLORS.neutral : COLORS.down,
+  }),
+};
+
+export function isBadgeMetric(metric: string): boolean {
+  return Object.prototype.hasOwnProperty.call(BADGE_METRICS, metric);
+}
+
+// Average advance of 11px Verdana, which is what the badge text uses.
+function estimateTextWidth(text: string): number {
+  return Math.round(text.length * 6.5);
+}
+
+export function renderBadgeSvg({ label, value, color }: BadgeContent): string {
+  const labelWidth = estimateTextWidth(label) + 10;
+  const valueWidth = estimateTextWidth(value) + 10;
+  const width = labelWidth + valueWidth;
+  const safeLabel = escapeMarkup(label);
+  const safeValue = escapeMarkup(value);
+
+  return `<svg xmlns="http://www.w3.org/2000/svg" width="${width}" height="20" role="img" aria-label="${safeLabel}: ${safeValue}">
+  <title>${safeLabel}: ${safeValue}</title>
+  <linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+  <clipPath id="r"><rect width="${width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="${labelWidth}" height="20" fill="${COLORS.label}"/>
+    <rect x="${labelWidth}" width="${valueWidth}" height="20" fill="${color}"/>
+    <rect width="${width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="${labelWidth / 2}" y="15" fill="#010101" fill-opacity=".3">${safeLabel}</text>
+    <text x="${labelWidth / 2}" y="14">${safeLabel}</text>
+    <text x="${labelWidth + valueWidth / 2}" y="15" fill="#010101" fill-opacity=".3">${safeValue}</text>
+    <text x="${labelWidth + valueWidth / 2}" y="14">${safeValue}</text>
+  </g>
+</svg>
+`;
+}
+
+function getCardContent(summary: StackSummary): { title: string; rows: [string, string][] } {
+  const name = summary.poolName ?? (summary.miningMode === 'solo' ? 'Solo Mining' : 'SV2 Mining');
+  const networkLabel = getTestNetworkLabel(summary.network);
+  return {
+    title: networkLabel ? `${name} · ${networkLabel}` : name,
+    rows: [
+      ['Hashrate', show(summary.hashrate, formatHashrate)],
+      ['Workers', show(summary.workers)],
+      ['Shares', `${show(summary.sharesAccepted)} / ${show(summary.sharesSubmitted)}`],
+      ['Best diff', show(summary.bestDifficulty, formatDifficulty)],
+      ['Blocks found', show(summary.blocksFound)],
+      ['Uptime', show(summary.uptimeSecs, formatUptime)],
+    ],
+  };
+}
+
+/**
+ * A 360x180 card with the headline numbers from the dashboard.
+ */
+export function renderSummaryCardSvg(summary: StackSummary): string {
+  const { title, rows } = getCardContent(summary);
+
+  const cells = rows.map(([label, value], index) => {
+    const x = 20 + (index % 2) * 170;
+    const y = 70 + Math.floor(index / 2) * 36;
+    return `    <text x="${x}" y="${y}" fill="#8a8a8a" font-size="11">${escapeMarkup(label)}</text>
+    <text x="${x}" y="${y + 16}" fill="#e5e5e5" font-size="15" font-weight="600">${escapeMarkup(value)}</text>`;
+  }).join('\n');
+
+  return `<svg xmlns="http://www.w3.org/2000/svg" width="360" height="180" viewBox="0 0 360 180" role="img" aria-label="${escapeMarkup(title)} mining summary">
+  <rect width="360" height="180" rx="10" fill="#0b0b0c"/>
+  <g font-family="system-ui,-apple-system,Segoe UI,Roboto,sans-serif">
+    <circle cx="26" cy="30" r="5" fill="${summary.online ? COLORS.ok : COLORS.down}"/>
+    <text x="38" y="35" fill="#e5e5e5" font-size="15" font-weight="600">${escapeMarkup(title)}</text>
+    <text x="340" y="35" fill="#8a8a8a" font-size="11" text-anchor="end">${summary.online ? 'online' : 'offline'}</text>
+${cells}
+  </g>
+</svg>
+`;
+}
+
+type Rgb = [number, number, number];
+
+// Accepts the #rgb and #rrggbb forms used in COLORS.
+function parseColor(hex: string): Rgb {
+  const digits = hex.length === 4 ? [...hex.slice(1)].map((d) => d + d) : [hex.slice(1, 3), hex.slice(3, 5), hex.slice(5, 7)];
+  return digits.map((d) => parseInt(d, 16)) as Rgb;
+}
+
+interface Canvas {
+  width: number;
+  height: number;
+  pixels: Buffer;
+}
+
+function fillRect(canvas: Canvas, x: number, y: number, width: number, height: number, [r, g, b]: Rgb): void {
+  for (let py = Math.max(0, y); py < Math.min(canvas.height, y + height); py++) {
+    for (let px = Math.max(0, x); px < Math.min(canvas.width, x + width); px++) {
+      const offset = (py * canvas.width + px) * 3;
+      canvas.pixels[offset] = r;
+      canvas.pixels[offset + 1] = g;
+      canvas.pixels[offset + 2] = b;
+    }
+  }
+}
+
+function measureText(text: string, scale: number): number {
+  return [...text].length * (GLYPH_WIDTH + 1) * scale - scale;
+}
+
+// Draws from the top-left corner, clipped to `maxWidth`.
+function drawText(canvas: Canvas, text: string, x: number, y: number, color: Rgb, scale: number, maxWidth = canvas.width - x): void {
+  let cursor = x;
+  for (const char of text) {
+    if (cursor + GLYPH_WIDTH * scale > x + maxWidth) break;
+    getGlyph(char).forEach((column, col) => {
+      for (let row = 0; row < GLYPH_HEIGHT; row++) {
+        if (column & (1 << row)) fillRect(canvas, cursor + col * scale, y + row * scale, scale, scale, color);
+      }
+    });
+    cursor += (GLYPH_WIDTH + 1) * scale;
+  }
+}
+
+/**
+ * The summary card as a 360x180 PNG, laid out like the SVG one.
+ */
+export function renderSummaryCardPng(summary: StackSummary): Buffer {
+  const { title, rows } = getCardContent(summary);
+  const canvas: Canvas = { width: 360, height: 180, pixels: Buffer.alloc(360 * 180 * 3) };
+  const muted = parseColor('#8a8a8a');
+  const bright = parseColor('#e5e5e5');
+  fillRect(canvas, 0, 0, canvas.width, canvas.height, parseColor('#0b0b0c'));
+
+  const dot = parseColor(summary.online ? COLORS.ok : COLORS.down);
+  for (let dy = -5; dy <= 5; dy++) {
+    const half = Math.floor(Math.sqrt(25 - dy * dy));
+    fillRect(canvas, 26 - half, 30 + dy, half * 2 + 1, 1, dot);
+  }
+  const status = summary.online ? 'online' : 'offline';
+  drawText(canvas, status, 340 - measureText(status, 1), 24, muted, 1);
+  drawText(canvas, title, 38, 23, bright, 2, 290 - measureText(status, 1));
+
+  rows.forEach(([label, value], index) => {
+    const x = 20 + (index % 2) * 170;
+    const y = 61 + Math.floor(index / 2) * 36;
+    drawText(canvas, label, x, y, muted, 1);
+    // Long values drop to the small size rather than run into the next column.
+    drawText(canvas, value, x, y + 11, bright, measureText(value, 2) <= 160 ? 2 : 1, 160);
+  });
+
+  return encodePng(canvas.width, canvas.height, 3, canvas.pixels);
+}
@@ -0,0 +1,44 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { normalizeBasePath, renderIndexHtml } from './base-path.js';
+
+test('normalizes base paths to a leading slash without a trailing one', () => {
+  assert.equal(normalizeBasePath(undefined), '');
+  assert.equal(normalizeBasePath('/'), '');
+  assert.equal(normalizeBasePath('sv2'), '/sv2');
+  assert.equal(normalizeBasePath('/sv2/'), '/sv2');
+  assert.equal(normalizeBasePath('/mining/sv2'), '/mining/sv2');
+});
+
+test('rejects base paths that could escape the mount point or break HTML', () => {
+  assert.throws(() => normalizeBasePath('/../etc'), /Invalid BASE_PATH/);
+  assert.throws(() => normalizeBasePath('/sv2"><script>'), /Invalid BASE_PATH/);
+  assert.throws(() => normalizeBasePath('/a//b'), /Invalid BASE_PATH/);
+});
+
+test('injects a base href right after the head tag', () => {
+  const html = '<!doctype html>\n<html>\n  <head>\n    <title>SV2</title>\n  </head>\n</html>';
+
+  assert.equal(
+    renderIndexHtml(html, '/sv2'),
+    '<!doctype html>\n<html>\n  <head>\n    <base href="/sv2/" />\n    <title>SV2</title>\n  </head>\n</html>',
+  );
+  assert.match(renderIndexHtml(html, ''), /<base href="\/" \/>/);
+});
+
+test('replaces an existing base tag instead of adding a second one', () => {
+  const html = '<html><head><base href="/old/"><title>SV2</title></head></html>';
+  const rendered = renderIndexHtml(html, '/new');
+
+  assert.equal(rendered.match(/<base /g)?.length, 1);
+  assert.match(rendered, /<base href="\/new\/" \/>/);
+});
+
+test('injects runtime config and nonces every script', () => {
+  const html = '<html><head></head><body><script type="module" src="./assets/index.js"></script></body></html>';
+  const rendered = renderIndexHtml(html, '/sv2', { nonce: 'abc123', runtimeConfig: { basePath: '/sv2', note: '</script>' } });
+
+  assert.match(rendered, /<script nonce="abc123">window\.__SV2_UI_CONFIG__ = \{"basePath":"\/sv2","note":"\\u003c\/script>"\};<\/script>/);
+  assert.match(rendered, /<script nonce="abc123" type="module" src=".\/assets\/index.js">/);
+  assert.equal(rendered.match(/<\/script>/g)?.length, 2);
+});
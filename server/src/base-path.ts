@@ -0,0 +1,68 @@
+/**
+ * Support for serving the UI under a subpath (e.g. behind a reverse proxy at
+ * https://host/sv2/).
+ *
+ * The router is mounted at BASE_PATH and index.html gets a matching
+ * `<base href>` at serve time, so the relative asset URLs emitted by Vite and
+ * the frontend's API calls resolve under the subpath.
+ */
+
+export function normalizeBasePath(value: string | undefined): string {
+  const trimmed = (value ?? '').trim().replace(/^\/+|\/+$/g, '');
+  if (!trimmed) {
+    return '';
+  }
+
+  if (!/^[\w\-.~/]+$/.test(trimmed) || trimmed.split('/').some((segment) => segment === '' || segment === '..')) {
+    throw new Error(`Invalid BASE_PATH "${value}"`);
+  }
+
+  return `/${trimmed}`;
+}
+
+export interface RenderIndexOptions {
+  // CSP nonce for the page's scripts, including the injected config.
+  nonce?: string;
+  // Exposed to the frontend as `window.__SV2_UI_CONFIG__`.
+  runtimeConfig?: Record<string, unknown>;
+}
+
+/**
+ * JSON that's safe inside an inline `<script>`: `</script>` and HTML comment
+ * openers can't end the element early.
+ */
+function toInlineJson(value: unknown): string {
+  return JSON.stringify(value)
+    .replace(/</g, '\\u003c')
+    .replace(/\u2028/g, '\\u2028')
+    .replace(/\u2029/g, '\\u2029');
+}
+
+/**
+ * Inject `<base href>` right after `<head>`, replacing one if the build
+ * already emitted it, followed by the runtime config script when given.
+ *
+ * With a nonce, every `<script>` in the page gets it, so a policy using
+ * `'strict-dynamic'` still loads the bundle.
+ */
+export function renderIndexHtml(html: string, basePath: string, options: RenderIndexOptions = {}): string {
+  const { nonce, runtimeConfig } = options;
+  const nonceAttr = nonce ? ` nonce="${nonce}"` : '';
+  let head = `<base href="${basePath}/" />`;
+  if (runtimeConfig) {
+    head += `\n    <script${nonceAttr}>window.__SV2_UI_CONFIG__ = ${toInlineJson(runtimeConfig)};</script>`;
+  }
+
+  let withoutBase = html.replace(/<base\s[^>]*>\s*/i, '');
+  if (nonce) {
+    withoutBase = withoutBase.replace(/<script\b(?![^>]*\snonce=)/gi, `<script${nonceAttr}`);
+  }
+
+  const headMatch = withoutBase.match(/<head[^>]*>/i);
+  if (!headMatch || headMatch.index === undefined) {
+    return `${head}${withoutBase}`;
+  }
+
+  const insertAt = headMatch.index + headMatch[0].length;
+  return `${withoutBase.slice(0, insertAt)}\n    ${head}${withoutBase.slice(insertAt)}`;
+}
@@ -0,0 +1,44 @@
+/**
+ * Base58check, the encoding SV2 uses for authority keys.
+ */
+
+import crypto from 'crypto';
+
+const BASE58_ALPHABET = '123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz';
+
+function checksum(payload: Uint8Array): Buffer {
+  return crypto.createHash('sha256').update(crypto.createHash('sha256').update(payload).digest()).digest().subarray(0, 4);
+}
+
+export function encodeBase58Check(payload: Uint8Array): string {
+  const bytes = Buffer.concat([payload, checksum(payload)]);
+  let number = BigInt(`0x${bytes.toString('hex') || '0'}`);
+  let encoded = '';
+  while (number > 0n) {
+    encoded = BASE58_ALPHABET[Number(number % 58n)] + encoded;
+    number /= 58n;
+  }
+  // Each leading zero byte is written as a leading '1'.
+  const leadingZeros = bytes.length - bytes.toString('hex').replace(/^(00)+/, '').length / 2;
+  return '1'.repeat(leadingZeros) + encoded;
+}
+
+/**
+ * The payload of a base58check string; null if it isn't base58 or the
+ * checksum doesn't match.
+ */
+export function decodeBase58Check(value: string): Buffer | null {
+  let number = 0n;
+  for (const char of value) {
+    const digit = BASE58_ALPHABET.indexOf(char);
+    if (digit < 0) return null;
+    number = number * 58n + BigInt(digit);
+  }
+  const hex = number === 0n ? '' : number.toString(16);
+  const leadingZeros = value.length - value.replace(/^1+/, '').length;
+  const bytes = Buffer.concat([Buffer.alloc(leadingZeros), Buffer.from(hex.padStart(hex.length + (hex.length % 2), '0'), 'hex')]);
+  if (bytes.length < 5) return null;
+
+  const payload = bytes.subarray(0, -4);
+  return checksum(payload).equals(bytes.subarray(-4)) ? payload : null;
+}
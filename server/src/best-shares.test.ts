@@ -0,0 +1,55 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+
+import { createBestShareStore } from './best-shares.js';
+import type { ServerChannelInfo, StackData } from './monitoring.js';
+
+const channel = (channel_id: number, user_identity: string, best_diff: number): ServerChannelInfo => ({
+  channel_id,
+  user_identity,
+  target_hex: '00',
+  best_diff,
+  blocks_found: 0,
+  shares_acknowledged: 0,
+  shares_submitted: 0,
+  shares_rejected: 0,
+  shares_rejected_by_reason: {},
+});
+
+const stack = (...channels: ServerChannelInfo[]): StackData => ({
+  mode: 'no-jd',
+  upstream: 'translator',
+  global: null,
+  serverChannels: { extended_channels: channels, standard_channels: [], total_extended: channels.length, total_standard: 0 },
+  sv1Clients: null,
+  sv2Clients: null,
+});
+
+test('best shares only grow and survive channel resets and restarts', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-best-shares-'));
+  const filePath = path.join(dir, 'best-shares.json');
+  const store = createBestShareStore(filePath);
+
+  assert.equal(await store.record(stack(channel(1, 'bitaxe', 5000), channel(2, 's19', 0)), '2026-01-01T00:00:00.000Z'), true);
+  assert.equal(await store.record(stack(channel(1, 'bitaxe', 5000), channel(2, 's19', 2000)), '2026-01-01T00:01:00.000Z'), true);
+  // The Translator restarted, so the channels' bests start over.
+  assert.equal(await store.record(stack(channel(1, 'bitaxe', 10), channel(2, 's19', 10)), '2026-01-01T00:02:00.000Z'), false);
+
+  const reopened = createBestShareStore(filePath);
+  assert.deepEqual(await reopened.list(), {
+    overall: { difficulty: 5000, at: '2026-01-01T00:00:00.000Z', miner: 'bitaxe' },
+    miners: [
+      { id: 'bitaxe', difficulty: 5000, at: '2026-01-01T00:00:00.000Z' },
+      { id: 's19', difficulty: 2000, at: '2026-01-01T00:01:00.000Z' },
+    ],
+  });
+
+  await reopened.record(stack(channel(1, 's19', 8000)), '2026-01-02T00:00:00.000Z');
+  assert.deepEqual((await reopened.list()).overall, { difficulty: 8000, at: '2026-01-02T00:00:00.000Z', miner: 's19' });
+
+  await reopened.reset();
+  assert.deepEqual(await createBestShareStore(filePath).list(), { overall: null, miners: [] });
+});
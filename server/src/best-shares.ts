@@ -0,0 +1,116 @@
+/**
+ * The best share difficulty seen per miner and overall, with when it was
+ * first seen, kept in CONFIG_DIR/best-shares.json.
+ *
+ * Channels report their best share only since they opened, so restarting
+ * the Translator, the JDC or a miner loses it. Each poll's bests are
+ * compared with the stored ones instead, which only ever grow. Miners are
+ * the channels' user identities: the JDC's downstream channels in JD mode,
+ * the upstream channels otherwise (as in the stack summary).
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import type { StackData } from './monitoring.js';
+
+export interface BestShare {
+  difficulty: number;
+  // When a poll first saw it.
+  at: string;
+}
+
+export interface BestShares {
+  overall: (BestShare & { miner: string }) | null;
+  miners: Record<string, BestShare>;
+}
+
+export interface BestShareList {
+  overall: BestShares['overall'];
+  // Best first; an array rather than a map, so REDACT_PATHS can reach the ids.
+  miners: Array<BestShare & { id: string }>;
+}
+
+export function readChannelBests(data: StackData): { miner: string; difficulty: number }[] {
+  const channels = data.mode === 'jd'
+    ? (data.sv2Clients ?? []).flatMap((client) => [...client.extended_channels, ...client.standard_channels])
+    : data.serverChannels ? [...data.serverChannels.extended_channels, ...data.serverChannels.standard_channels] : [];
+  return channels
+    .filter((channel) => channel.best_diff > 0)
+    .map((channel) => ({ miner: channel.user_identity, difficulty: channel.best_diff }));
+}
+
+export function createBestShareStore(filePath: string) {
+  let loaded: Promise<BestShares> | null = null;
+  // Serialize writes so an older state never lands after a newer one.
+  let pending: Promise<void> = Promise.resolve();
+
+  async function read(): Promise<BestShares> {
+    try {
+      const stored = JSON.parse(await fs.readFile(filePath, 'utf-8')) as Partial<BestShares>;
+      return { overall: stored.overall ?? null, miners: stored.miners ?? {} };
+    } catch {
+      return { overall: null, miners: {} };
+    }
+  }
+
+  function write(operation: () => Promise<void>): Promise<void> {
+    const next = pending.then(operation);
+    pending = next.catch(() => {});
+    return next;
+  }
+
+  function get(): Promise<BestShares> {
+    loaded ??= read();
+    return loaded;
+  }
+
+  async function list(): Promise<BestShareList> {
+    const { overall, miners } = await get();
+    return {
+      overall,
+      miners: Object.entries(miners)
+        .map(([id, best]) => ({ id, ...best }))
+        .sort((a, b) => b.difficulty - a.difficulty),
+    };
+  }
+
+  /**
+   * Fold in a poll's channels; resolves to whether any best improved.
+   */
+  async function record(data: StackData, at: string): Promise<boolean> {
+    const state = await get();
+    let changed = false;
+    for (const { miner, difficulty } of readChannelBests(data)) {
+      if (difficulty > (state.miners[miner]?.difficulty ?? 0)) {
+        state.miners[miner] = { difficulty, at };
+        changed = true;
+      }
+      if (difficulty > (state.overall?.difficulty ?? 0)) {
+        state.overall = { difficulty, at, miner };
+        changed = true;
+      }
+    }
+    if (!changed) return false;
+
+    const snapshot = JSON.stringify(state, null, 2);
+    await write(async () => {
+      await fs.mkdir(path.dirname(filePath), { recursive: true });
+      await fs.writeFile(filePath, snapshot);
+    });
+    return true;
+  }
+
+  /**
+   * Forget every best, e.g. to start counting from a new season.
+   */
+  async function reset(): Promise<void> {
+    const state = await get();
+    state.overall = null;
+    state.miners = {};
+    await write(() => fs.rm(filePath, { force: true }));
+  }
+
+  return { list, record, reset };
+}
+
+export type BestShareStore = ReturnType<typeof createBestShareStore>;
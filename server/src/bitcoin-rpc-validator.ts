@@ -1,4 +1,6 @@
-export const bitcoinRpcValidatorScript = `const http = require('http');
+// Shared by the helper scripts below: resolves cookie or bitcoin.conf
+// credentials and defines makeRpcCall(method, params).
+const bitcoinRpcClientScript = `const http = require('http');
 const fs = require('fs');
 
 const dataDir = process.argv[1];
@@ -11,9 +13,9 @@ if (!dataDir || !port) {
   process.exit(1);
 }
 
-const cookiePath = network === 'testnet4'
-  ? dataDir + '/testnet4/.cookie'
-  : dataDir + '/.cookie';
+const cookiePath = network === 'mainnet'
+  ? dataDir + '/.cookie'
+  : dataDir + '/' + network + '/.cookie';
 
 function parseBitcoinConf(filePath, targetNetwork) {
   const content = fs.readFileSync(filePath, 'utf8');
@@ -140,7 +142,9 @@ function makeRpcCall(method, params) {
     req.end();
   });
 }
+`;
 
+export const bitcoinRpcValidatorScript = bitcoinRpcClientScript + `
 (async () => {
   try {
     const blockchainInfo = await makeRpcCall('getblockchaininfo', []);
@@ -162,3 +166,62 @@ function makeRpcCall(method, params) {
   }
 })();
 `;
+
+/**
+ * Prints the chain tip and the first block of the current difficulty epoch,
+ * for the retarget estimate (see retarget.ts).
+ */
+export const bitcoinChainStatsScript = bitcoinRpcClientScript + `
+(async () => {
+  try {
+    const info = await makeRpcCall('getblockchaininfo', []);
+    const epochStartHeight = info.blocks - (info.blocks % 2016);
+    const epochStartHash = await makeRpcCall('getblockhash', [epochStartHeight]);
+    const epochStart = await makeRpcCall('getblockheader', [epochStartHash]);
+    const tip = await makeRpcCall('getblockheader', [info.bestblockhash]);
+
+    console.log(JSON.stringify({
+      chain: info.chain,
+      height: info.blocks,
+      difficulty: info.difficulty,
+      tipTime: tip.time,
+      epochStartHeight,
+      epochStartTime: epochStart.time,
+    }));
+    process.exit(0);
+  } catch (err) {
+    console.error(err.message);
+    process.exit(1);
+  }
+})();
+`;
+
+/**
+ * Prints the last few blocks with their coinbase output scripts, to find the
+ * hash of a block the stack found (see found-blocks.ts).
+ */
+export const bitcoinRecentBlocksScript = bitcoinRpcClientScript + `
+(async () => {
+  try {
+    const info = await makeRpcCall('getblockchaininfo', []);
+    const blocks = [];
+    let hash = info.bestblockhash;
+    for (let i = 0; i < 6 && hash; i++) {
+      const block = await makeRpcCall('getblock', [hash, 2]);
+      blocks.push({
+        hash: block.hash,
+        height: block.height,
+        time: block.time,
+        coinbaseScripts: block.tx[0].vout.map((output) => output.scriptPubKey.hex),
+      });
+      hash = block.previousblockhash;
+    }
+
+    console.log(JSON.stringify(blocks));
+    process.exit(0);
+  } catch (err) {
+    console.error(err.message);
+    process.exit(1);
+  }
+})();
+`;
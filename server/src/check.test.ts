@@ -0,0 +1,85 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import net from 'node:net';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { checkConfig, checkTarget, formatCheckReport, parseCheckArgs, urlTarget } from './check.js';
+
+test('parseCheckArgs reads --json and --timeout, and leaves --data-dir to resolveConfigDir', () => {
+  assert.deepEqual(parseCheckArgs([]), { json: false, timeoutMs: 5000 });
+  assert.deepEqual(parseCheckArgs(['--data-dir', '/srv/sv2', '--json', '--timeout', '2']), { json: true, timeoutMs: 2000 });
+  assert.throws(() => parseCheckArgs(['--timeout', '0']), /--timeout must be/);
+  assert.throws(() => parseCheckArgs(['--fix']), /Unknown check option "--fix"/);
+});
+
+test('configured sections are parsed, and the services they name become targets', async (t) => {
+  const configDir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-check-'));
+  t.after(() => fs.rm(configDir, { recursive: true, force: true }));
+  await fs.writeFile(path.join(configDir, 'federation.json'), JSON.stringify({
+    sites: [{ id: 'north', name: 'North', url: 'https://north.example/sv2', token: null }],
+  }));
+
+  const { results, targets } = checkConfig({
+    LISTEN: '127.0.0.1:8080',
+    MEMPOOL_URL: 'http://10.0.0.5:3006',
+    PRICE_PROVIDER: 'mempool',
+    PRICE_CACHE_SECS: '1',
+  }, configDir);
+
+  const byName = Object.fromEntries(results.map((result) => [result.name, result]));
+  assert.deepEqual(byName['Listen addresses'], { name: 'Listen addresses', status: 'ok', detail: '127.0.0.1:8080' });
+  assert.equal(byName['Price feed'].status, 'fail');
+  assert.match(byName['Price feed'].detail, /PRICE_CACHE_SECS must be between/);
+  assert.equal(byName['Federation sites'].detail, '1 registered');
+  // Sections that aren't configured are left out.
+  assert.equal(byName.Telemetry, undefined);
+
+  assert.deepEqual(targets, [
+    { name: 'mempool', host: '10.0.0.5', port: 3006, tls: false, optional: false },
+    { name: 'Site North', host: 'north.example', port: 443, tls: true, optional: false },
+  ]);
+});
+
+test('targets are resolved and connected to; optional ones only warn', async (t) => {
+  const server = net.createServer((socket) => socket.end()).listen(0, '127.0.0.1');
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as net.AddressInfo;
+
+  const up = await checkTarget({ name: 'Pool', host: '127.0.0.1', port, tls: false }, 1000);
+  assert.equal(up.status, 'ok');
+  assert.equal(up.name, `Pool (127.0.0.1:${port})`);
+  assert.match(up.detail, /^reachable in \d+ms$/);
+
+  // Nothing listens on port 1.
+  const down = await checkTarget({ name: 'JDS', host: '127.0.0.1', port: 1, tls: false }, 1000);
+  assert.equal(down.status, 'fail');
+  assert.match(down.detail, /^Cannot connect: /);
+  assert.equal((await checkTarget({ name: 'JDC', host: '127.0.0.1', port: 1, tls: false, optional: true }, 1000)).status, 'warn');
+
+  const unresolved = await checkTarget(urlTarget('Webhook', 'https://sv2-ui.invalid/hook'), 1000);
+  assert.equal(unresolved.status, 'fail');
+  assert.match(unresolved.detail, /^Cannot resolve sv2-ui\.invalid/);
+});
+
+test('the report lines up the checks and sums them up', () => {
+  assert.equal(formatCheckReport([
+    { name: 'Listen addresses', status: 'ok', detail: '*:3001' },
+    { name: 'Price feed', status: 'fail', detail: 'PRICE_URL must be http(s)' },
+    { name: 'JDC (localhost:9091)', status: 'warn', detail: 'Cannot connect: connect ECONNREFUSED' },
+  ]), [
+    'ok    Listen addresses      *:3001',
+    'FAIL  Price feed            PRICE_URL must be http(s)',
+    'warn  JDC (localhost:9091)  Cannot connect: connect ECONNREFUSED',
+    '',
+    '1 of 3 checks failed, 1 warning',
+    '',
+  ].join('\n'));
+});
+
+test('hints are printed under the finding they belong to', () => {
+  assert.equal(formatCheckReport([
+    { name: 'Clock', status: 'warn', detail: '8s ahead', hint: 'Turn on time synchronisation.' },
+  ]), 'warn  Clock  8s ahead\n             -> Turn on time synchronisation.\n\nAll 1 checks passed, 1 warning\n');
+});
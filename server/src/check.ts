@@ -0,0 +1,449 @@
+/**
+ * `check`: validate a deployment before it takes traffic.
+ *
+ * The command parses the environment variables, flags and stored config
+ * files the server reads on startup. For every service they point at, it
+ * resolves the host name and opens a TCP connection. On TLS endpoints it
+ * completes a handshake that verifies the certificate. Each check is
+ * reported as ok, warn or FAIL, and the command exits 1 when any failed, so
+ * CI and provisioning scripts can gate on it.
+ *
+ * Services sv2-ui starts itself, the local Translator and JDC, only warn
+ * when they can't be reached: the stack may simply not be running yet.
+ */
+
+import { accessSync, constants, existsSync, readFileSync } from 'fs';
+import dns from 'dns/promises';
+import net from 'net';
+import path from 'path';
+import tls from 'tls';
+import { parseAdvertiseConfig } from './advertise.js';
+import { parseTrustProxy } from './app.js';
+import { parseAuthHookConfig } from './auth-hook.js';
+import { normalizeBasePath } from './base-path.js';
+import { parseCorsPolicies } from './cors-origins.js';
+import { parseDiscordConfig } from './discord.js';
+import { parseDiscoveryMode } from './discovery.js';
+import { parseDockerAutoconfig } from './docker-autoconfig.js';
+import { parseEmailConfig } from './email.js';
+import { parseFederationSites } from './federation.js';
+import { parseGrpcListen } from './grpc.js';
+import { parseHistoryRetention } from './history.js';
+import { parseIpFilter } from './ip-filter.js';
+import { parseKubernetesDiscovery, SERVICE_ACCOUNT_DIR } from './kubernetes-discovery.js';
+import { getLatencyEndpoints, parseLatencyConfig } from './latency.js';
+import { parseListenAddresses } from './listen.js';
+import { getMonitoringBaseUrl, MONITORING_SERVICES } from './monitoring.js';
+import type { MonitoringService } from './monitoring.js';
+import { loadMessageCatalogs, parseNotificationLocales } from './notification-messages.js';
+import { parseBitcoinPollIntervalMs, parsePollIntervals, parsePollJitter } from './poller.js';
+import { parsePriceConfig } from './price.js';
+import { parseReadyGraceMs } from './probes.js';
+import { parseMempoolUrl } from './proxy.js';
+import { parsePushConfig } from './push.js';
+import { parseRateLimitOptions } from './rate-limit.js';
+import { parseRedactionPaths } from './redact.js';
+import { parseSecurityHeadersConfig } from './security-headers.js';
+import { normalizeSavedState } from './state.js';
+import type { SavedState } from './state.js';
+import { getStackEndpoints, parseRemoteStacks } from './stacks.js';
+import { parseAuthorityPubkey } from './sv2-probe.js';
+import { parseTelegramConfig } from './telegram.js';
+import { parseTelemetryConfig } from './telemetry.js';
+import { parseTunnelConfig } from './tunnel.js';
+import { parseUpdateCheckConfig } from './updates.js';
+import { parseWebhookConfig } from './webhooks.js';
+import { parseZmqEndpoints } from './zmq.js';
+
+export type CheckStatus = 'ok' | 'warn' | 'fail';
+
+export interface CheckResult {
+  name: string;
+  status: CheckStatus;
+  detail: string;
+  // What to do about it; `doctor` adds one to findings that aren't ok.
+  hint?: string;
+}
+
+export interface CheckTarget {
+  name: string;
+  host: string;
+  port: number;
+  tls: boolean;
+  // Failing to reach it only warns.
+  optional?: boolean;
+}
+
+export interface CheckArgs {
+  json: boolean;
+  timeoutMs: number;
+}
+
+interface ConfigCheck {
+  name: string;
+  // Checked when any of these is set; always when empty.
+  vars: string[];
+  run: (env: NodeJS.ProcessEnv, configDir: string) => { detail?: string; targets?: CheckTarget[] };
+}
+
+const DEFAULT_TIMEOUT_SECS = 5;
+// A certificate this close to expiry is a warning.
+const CERT_EXPIRY_WARN_DAYS = 14;
+const DAY_MS = 86_400_000;
+
+export function parseCheckArgs(argv: string[], command = 'check'): CheckArgs {
+  let json = false;
+  let timeoutMs = DEFAULT_TIMEOUT_SECS * 1000;
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i];
+    if (arg === '--json') {
+      json = true;
+    } else if (arg === '--timeout') {
+      const secs = Number(argv[++i]);
+      if (!Number.isFinite(secs) || secs <= 0 || secs > 60) throw new Error('--timeout must be between 0 and 60 seconds');
+      timeoutMs = secs * 1000;
+    } else if (arg === '--data-dir') {
+      // Read by resolveConfigDir.
+      i++;
+    } else if (!arg.startsWith('--data-dir=')) {
+      throw new Error(`Unknown ${command} option "${arg}"`);
+    }
+  }
+  return { json, timeoutMs };
+}
+
+/**
+ * A target for the host and port of `url`.
+ */
+export function urlTarget(name: string, url: string, optional = false): CheckTarget {
+  const parsed = new URL(url);
+  const secure = parsed.protocol === 'https:';
+  return {
+    name,
+    host: parsed.hostname.replace(/^\[|\]$/g, ''),
+    port: parsed.port ? Number(parsed.port) : secure ? 443 : 80,
+    tls: secure,
+    optional,
+  };
+}
+
+const CONFIG_CHECKS: ConfigCheck[] = [
+  {
+    name: 'Listen addresses',
+    vars: [],
+    run: (env) => ({
+      detail: parseListenAddresses(env.LISTEN, Number(env.PORT || 3001))
+        .map(({ host, port }) => `${host ? (host.includes(':') ? `[${host}]` : host) : '*'}:${port}`)
+        .join(', '),
+    }),
+  },
+  { name: 'gRPC API', vars: ['GRPC_LISTEN'], run: (env) => ({ detail: `${parseGrpcListen(env)?.length ?? 0} listen addresses` }) },
+  { name: 'Base path', vars: ['BASE_PATH'], run: (env) => ({ detail: normalizeBasePath(env.BASE_PATH) || '/' }) },
+  { name: 'Trust proxy', vars: ['TRUST_PROXY'], run: (env) => ({ detail: String(parseTrustProxy(env.TRUST_PROXY)) }) },
+  {
+    name: 'IP filter',
+    vars: ['ALLOW_CIDR', 'DENY_CIDR'],
+    run: (env) => {
+      parseIpFilter(env);
+      return {};
+    },
+  },
+  {
+    name: 'CORS',
+    vars: ['CORS_ALLOW_ORIGIN', 'CORS_PUBLIC_ALLOW_ORIGIN'],
+    run: (env) => {
+      parseCorsPolicies(env);
+      return {};
+    },
+  },
+  {
+    name: 'Security headers',
+    vars: ['CSP_DIRECTIVES', 'HSTS_MAX_AGE', 'REFERRER_POLICY'],
+    run: (env) => {
+      parseSecurityHeadersConfig(env);
+      return {};
+    },
+  },
+  { name: 'Rate limit', vars: ['RATE_LIMIT_RPS', 'RATE_LIMIT_BURST'], run: (env) => ({ detail: parseRateLimitOptions(env) ? 'on' : 'off' }) },
+  { name: 'Redaction', vars: ['REDACT_PATHS'], run: (env) => ({ detail: `${parseRedactionPaths(env.REDACT_PATHS).length} paths` }) },
+  {
+    name: 'Auth hook',
+    vars: ['AUTH_HOOK_URL', 'AUTH_HOOK_COMMAND', 'AUTH_HOOK_ADMIN_GROUPS', 'AUTH_HOOK_CACHE_SECS'],
+    run: (env) => {
+      const target = parseAuthHookConfig(env)?.target;
+      if (target?.kind === 'command' && !existsSync(target.file)) throw new Error(`AUTH_HOOK_COMMAND ${target.file} does not exist`);
+      return { targets: target?.kind === 'http' ? [urlTarget('Auth hook', target.url)] : [] };
+    },
+  },
+  { name: 'Tunnel identity', vars: ['TUNNEL_IDENTITY', 'TUNNEL_ADMIN_USERS', 'TUNNEL_CONNECTOR_CIDR'], run: (env) => ({ detail: parseTunnelConfig(env)?.provider ?? 'off' }) },
+  {
+    name: 'Poll interval',
+    vars: ['POLL_INTERVAL_SECS', 'TRANSLATOR_POLL_INTERVAL_SECS', 'JDC_POLL_INTERVAL_SECS', 'BITCOIN_POLL_INTERVAL_SECS', 'POLL_JITTER_PERCENT'],
+    run: (env) => {
+      const { translator, jdc } = parsePollIntervals(env);
+      const bitcoin = parseBitcoinPollIntervalMs(env.BITCOIN_POLL_INTERVAL_SECS);
+      const jitter = Math.round(parsePollJitter(env.POLL_JITTER_PERCENT) * 100);
+      return { detail: `translator ${translator / 1000}s, JDC ${jdc / 1000}s, bitcoind ${bitcoin / 1000}s, ±${jitter}%` };
+    },
+  },
+  { name: 'Readiness grace', vars: ['READY_GRACE_SECS'], run: (env) => ({ detail: `${parseReadyGraceMs(env.READY_GRACE_SECS) / 1000}s` }) },
+  {
+    name: 'History retention',
+    vars: ['HISTORY_RETENTION'],
+    run: (env) => {
+      parseHistoryRetention(env);
+      return {};
+    },
+  },
+  { name: 'Latency probes', vars: ['LATENCY_PROBE_INTERVAL_SECS', 'LATENCY_PROBE_HANDSHAKE'], run: (env) => ({ detail: parseLatencyConfig(env) ? 'on' : 'off' }) },
+  { name: 'mDNS discovery', vars: ['MDNS_DISCOVERY'], run: (env) => ({ detail: parseDiscoveryMode(env.MDNS_DISCOVERY) ?? 'off' }) },
+  { name: 'mDNS advertisement', vars: ['MDNS_ADVERTISE', 'MDNS_HOSTNAME'], run: (env) => ({ detail: parseAdvertiseConfig(env) ? 'on' : 'off' }) },
+  { name: 'Docker auto-configuration', vars: ['DOCKER_AUTOCONFIG'], run: (env) => ({ detail: parseDockerAutoconfig(env.DOCKER_AUTOCONFIG) ? 'on' : 'off' }) },
+  {
+    name: 'Kubernetes discovery',
+    vars: ['K8S_DISCOVERY', 'K8S_NAMESPACE', 'K8S_TRANSLATOR_SELECTOR', 'K8S_JDC_SELECTOR', 'K8S_DISCOVERY_INTERVAL_SECS'],
+    run: (env) => {
+      const config = parseKubernetesDiscovery(env);
+      if (!config) return { detail: 'off' };
+      if (parseDockerAutoconfig(env.DOCKER_AUTOCONFIG)) throw new Error('DOCKER_AUTOCONFIG and K8S_DISCOVERY cannot both be on');
+      for (const file of ['token', 'ca.crt']) accessSync(path.join(SERVICE_ACCOUNT_DIR, file), constants.R_OK);
+      if (!env.KUBERNETES_SERVICE_HOST) throw new Error('Not running in a Kubernetes pod (KUBERNETES_SERVICE_HOST is unset)');
+      // The API server's certificate is signed by the cluster CA, which the handshake check doesn't know.
+      const target = { name: 'Kubernetes API', host: env.KUBERNETES_SERVICE_HOST, port: Number(env.KUBERNETES_SERVICE_PORT) || 443, tls: false };
+      return { detail: `namespace ${config.namespace}`, targets: [target] };
+    },
+  },
+  {
+    name: 'ZMQ',
+    vars: ['BITCOIN_ZMQ_URL'],
+    run: (env) => ({ targets: parseZmqEndpoints(env).map(({ host, port }) => ({ name: 'bitcoind ZMQ', host, port, tls: false })) }),
+  },
+  {
+    name: 'Webhooks',
+    vars: ['ALERT_WEBHOOK_URLS', 'ALERT_WEBHOOK_SECRET', 'ALERT_WEBHOOK_ATTEMPTS'],
+    run: (env) => ({ targets: (parseWebhookConfig(env)?.urls ?? []).map((url) => urlTarget('Webhook', url)) }),
+  },
+  {
+    name: 'Telegram',
+    vars: ['TELEGRAM_BOT_TOKEN', 'TELEGRAM_CHAT_ID'],
+    run: (env) => ({ targets: parseTelegramConfig(env) ? [urlTarget('Telegram', 'https://api.telegram.org')] : [] }),
+  },
+  {
+    name: 'Email',
+    vars: ['SMTP_HOST', 'SMTP_PORT', 'SMTP_TLS', 'SMTP_USER', 'SMTP_PASSWORD', 'ALERT_EMAIL_FROM', 'ALERT_EMAIL_TO', 'ALERT_EMAIL_DAILY_AT'],
+    run: (env) => {
+      const config = parseEmailConfig(env);
+      // STARTTLS is negotiated after EHLO, so only implicit TLS gets the handshake check.
+      return { targets: config ? [{ name: 'SMTP', host: config.host, port: config.port, tls: config.tls === 'tls' }] : [] };
+    },
+  },
+  {
+    name: 'Push',
+    vars: ['NTFY_URL', 'NTFY_TOKEN', 'GOTIFY_URL', 'GOTIFY_TOKEN'],
+    run: (env) => {
+      const config = parsePushConfig(env);
+      return {
+        targets: [
+          ...(config?.ntfy ? [urlTarget('ntfy', config.ntfy.url)] : []),
+          ...(config?.gotify ? [urlTarget('Gotify', config.gotify.url)] : []),
+        ],
+      };
+    },
+  },
+  {
+    name: 'Discord',
+    vars: ['DISCORD_WEBHOOK_URLS'],
+    run: (env) => ({ targets: (parseDiscordConfig(env)?.urls ?? []).map((url) => urlTarget('Discord', url)) }),
+  },
+  {
+    name: 'Notification languages',
+    vars: ['NOTIFY_LOCALE', 'TELEGRAM_LOCALE', 'EMAIL_LOCALE', 'PUSH_LOCALE', 'DISCORD_LOCALE', 'MESSAGES_DIR'],
+    run: (env, configDir) => {
+      const catalogs = loadMessageCatalogs(env.MESSAGES_DIR || path.join(configDir, 'messages'));
+      return { detail: Object.values(parseNotificationLocales(env, Object.keys(catalogs))).join(', ') };
+    },
+  },
+  {
+    name: 'Update checks',
+    vars: ['UPDATE_CHECK_INTERVAL_HOURS', 'UPDATE_NOTIFY'],
+    run: (env) => {
+      parseUpdateCheckConfig(env);
+      return {};
+    },
+  },
+  {
+    name: 'Mempool proxy',
+    vars: ['MEMPOOL_URL'],
+    run: (env) => {
+      const url = parseMempoolUrl(env);
+      return url ? { detail: url, targets: [urlTarget('mempool', url)] } : { detail: 'off' };
+    },
+  },
+  {
+    name: 'Price feed',
+    vars: ['PRICE_PROVIDER', 'PRICE_URL', 'PRICE_CURRENCY', 'PRICE_CACHE_SECS'],
+    run: (env) => {
+      const config = parsePriceConfig(env);
+      return config ? { detail: `${config.provider}, ${config.currency}`, targets: [urlTarget('Price provider', config.url)] } : { detail: 'off' };
+    },
+  },
+  {
+    name: 'Telemetry',
+    vars: ['TELEMETRY', 'TELEMETRY_URL', 'TELEMETRY_INTERVAL_HOURS'],
+    run: (env) => {
+      const config = parseTelemetryConfig(env);
+      return config ? { targets: [urlTarget('Telemetry', config.url)] } : { detail: 'off' };
+    },
+  },
+];
+
+function readJson(file: string): unknown {
+  return JSON.parse(readFileSync(file, 'utf-8'));
+}
+
+const STORED_CHECKS: ConfigCheck[] = [
+  {
+    name: 'Config directory',
+    vars: [],
+    run: (_env, configDir) => {
+      if (!existsSync(configDir)) return { detail: `${configDir} (created on first start)` };
+      accessSync(configDir, constants.W_OK);
+      return { detail: configDir };
+    },
+  },
+  {
+    name: 'Setup',
+    vars: [],
+    run: (_env, configDir) => {
+      const file = path.join(configDir, 'state.json');
+      if (!existsSync(file)) return { detail: 'not configured yet' };
+      const state = normalizeSavedState(readJson(file) as Partial<SavedState>);
+      if (!state.configured) return { detail: 'not configured yet' };
+
+      const pool = state.data?.pool;
+      if (pool && !(state.mode === 'jd' && state.miningMode === 'solo') && !parseAuthorityPubkey(pool.authority_public_key)) {
+        throw new Error(`The pool authority public key "${pool.authority_public_key}" is not a valid key`);
+      }
+      const services = state.mode === 'jd' ? ['translator', 'jdc'] as const : ['translator'] as const;
+      return {
+        detail: `${state.mode} mode`,
+        targets: [
+          ...services.map((service) => urlTarget(`${MONITORING_SERVICES[service].label} monitoring API`, getMonitoringBaseUrl(service), true)),
+          ...getLatencyEndpoints(state.data).map(({ target, host, port }) => ({ name: target === 'pool' ? 'Pool' : 'JDS', host, port, tls: false })),
+        ],
+      };
+    },
+  },
+  {
+    name: 'Remote stacks',
+    vars: [],
+    run: (_env, configDir) => {
+      const file = path.join(configDir, 'stacks.json');
+      if (!existsSync(file)) return { detail: 'none' };
+      const stacks = parseRemoteStacks(readJson(file));
+      return {
+        detail: `${stacks.length} registered`,
+        targets: stacks.flatMap((stack) => Object.entries(getStackEndpoints(stack))
+          .map(([service, url]) => urlTarget(`${stack.name} ${MONITORING_SERVICES[service as MonitoringService].label}`, url))),
+      };
+    },
+  },
+  {
+    name: 'Federation sites',
+    vars: [],
+    run: (_env, configDir) => {
+      const file = path.join(configDir, 'federation.json');
+      if (!existsSync(file)) return { detail: 'none' };
+      const sites = parseFederationSites(readJson(file));
+      return { detail: `${sites.length} registered`, targets: sites.map((site) => urlTarget(`Site ${site.name}`, site.url)) };
+    },
+  },
+];
+
+/**
+ * Parse every configured section. Returns one result per section and the
+ * services the valid ones point at.
+ */
+export function checkConfig(env: NodeJS.ProcessEnv, configDir: string): { results: CheckResult[]; targets: CheckTarget[] } {
+  const results: CheckResult[] = [];
+  const targets: CheckTarget[] = [];
+  for (const check of [...CONFIG_CHECKS, ...STORED_CHECKS]) {
+    if (check.vars.length > 0 && !check.vars.some((name) => env[name]?.trim())) continue;
+    try {
+      const outcome = check.run(env, configDir);
+      results.push({ name: check.name, status: 'ok', detail: outcome.detail ?? 'valid' });
+      targets.push(...outcome.targets ?? []);
+    } catch (error) {
+      results.push({ name: check.name, status: 'fail', detail: error instanceof Error ? error.message : String(error) });
+    }
+  }
+  return { results, targets };
+}
+
+function connect(target: CheckTarget, timeoutMs: number): Promise<net.Socket> {
+  return new Promise((resolve, reject) => {
+    const socket = target.tls
+      ? tls.connect({ host: target.host, port: target.port, servername: net.isIP(target.host) ? undefined : target.host })
+      : net.connect({ host: target.host, port: target.port });
+    socket.setTimeout(timeoutMs, () => socket.destroy(new Error(`No answer within ${timeoutMs}ms`)));
+    socket.once(target.tls ? 'secureConnect' : 'connect', () => resolve(socket));
+    socket.once('error', reject);
+  });
+}
+
+/**
+ * Resolve, connect to and, over TLS, verify one target.
+ */
+export async function checkTarget(target: CheckTarget, timeoutMs: number, now: () => number = Date.now): Promise<CheckResult> {
+  const name = `${target.name} (${target.host.includes(':') ? `[${target.host}]` : target.host}:${target.port})`;
+  const failed = (detail: string): CheckResult => ({ name, status: target.optional ? 'warn' : 'fail', detail });
+
+  if (!net.isIP(target.host)) {
+    try {
+      await dns.lookup(target.host);
+    } catch (error) {
+      return failed(`Cannot resolve ${target.host} (${(error as NodeJS.ErrnoException).code ?? 'lookup failed'})`);
+    }
+  }
+
+  const startedAt = now();
+  let socket: net.Socket;
+  try {
+    socket = await connect(target, timeoutMs);
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    return failed(target.tls ? `TLS handshake failed: ${message}` : `Cannot connect: ${message}`);
+  }
+  const elapsed = now() - startedAt;
+
+  try {
+    if (!(socket instanceof tls.TLSSocket)) return { name, status: 'ok', detail: `reachable in ${elapsed}ms` };
+    const validTo = Date.parse(socket.getPeerCertificate().valid_to);
+    const days = Math.floor((validTo - now()) / DAY_MS);
+    const until = new Date(validTo).toISOString().slice(0, 10);
+    return days < CERT_EXPIRY_WARN_DAYS
+      ? { name, status: 'warn', detail: `certificate expires in ${days} days (${until})` }
+      : { name, status: 'ok', detail: `TLS in ${elapsed}ms, certificate valid until ${until}` };
+  } finally {
+    socket.destroy();
+  }
+}
+
+export async function runChecks(env: NodeJS.ProcessEnv, configDir: string, timeoutMs: number): Promise<CheckResult[]> {
+  const { results, targets } = checkConfig(env, configDir);
+  return [...results, ...await Promise.all(targets.map((target) => checkTarget(target, timeoutMs)))];
+}
+
+export function formatCheckReport(results: CheckResult[]): string {
+  const labels: Record<CheckStatus, string> = { ok: 'ok', warn: 'warn', fail: 'FAIL' };
+  const width = Math.max(0, ...results.map(({ name }) => name.length));
+  const lines = results.flatMap(({ name, status, detail, hint }) => [
+    `${labels[status].padEnd(4)}  ${name.padEnd(width)}  ${detail}`,
+    ...hint ? [`${' '.repeat(width + 8)}-> ${hint}`] : [],
+  ]);
+  const failures = results.filter(({ status }) => status === 'fail').length;
+  const warnings = results.filter(({ status }) => status === 'warn').length;
+  const summary = `${failures > 0 ? `${failures} of ${results.length} checks failed` : `All ${results.length} checks passed`}`
+    + (warnings > 0 ? `, ${warnings} ${warnings === 1 ? 'warning' : 'warnings'}` : '');
+  return `${[...lines, '', summary].join('\n')}\n`;
+}
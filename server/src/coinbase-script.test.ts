@@ -0,0 +1,45 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import { classifyScript, getDescriptorChecksum, validateCoinbaseScript } from './coinbase-script.js';
+
+// BIP 173 / BIP 86 example addresses.
+const P2WPKH = 'bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4';
+const P2WPKH_TESTNET = 'tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx';
+const P2TR = 'bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr';
+
+test('validateCoinbaseScript returns the scriptPubKey of addr() descriptors', () => {
+  assert.deepEqual(validateCoinbaseScript(`addr(${P2WPKH})`, 'mainnet'), {
+    valid: true,
+    descriptor: `addr(${P2WPKH})`,
+    scriptPubKey: '0014751e76e8199196d454941c45d1b3a323f1433bd6',
+    addressType: 'p2wpkh',
+    errors: [],
+    warnings: [],
+  });
+  assert.equal(validateCoinbaseScript(`addr(${P2TR})`, 'mainnet').scriptPubKey, '5120a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c');
+  assert.equal(validateCoinbaseScript('addr(1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa)', 'mainnet').addressType, 'p2pkh');
+  assert.equal(validateCoinbaseScript(`addr(${P2WPKH.toUpperCase()})`, 'mainnet').valid, true);
+});
+
+test('validateCoinbaseScript flags wrong networks, bad checksums and burned rewards', () => {
+  assert.deepEqual(validateCoinbaseScript(`addr(${P2WPKH_TESTNET})`, 'mainnet').errors, ['Address is for testnet4/signet, not mainnet']);
+  assert.equal(validateCoinbaseScript(`addr(${P2WPKH_TESTNET})`, 'signet').valid, true);
+  assert.deepEqual(validateCoinbaseScript(`addr(${P2WPKH.slice(0, -1)}5)`, 'mainnet').errors, ['Not a valid Bitcoin address']);
+  assert.match(validateCoinbaseScript('raw(deadbeef)#89f8spxn', 'mainnet').errors[0], /expected 89f8spxm/);
+  assert.match(validateCoinbaseScript('raw(6a00)', 'mainnet').errors[0], /OP_RETURN/);
+  assert.match(validateCoinbaseScript('wpkh(02aa)', 'mainnet').errors[0], /Unsupported descriptor wpkh/);
+});
+
+test('validateCoinbaseScript wraps bare addresses and warns about odd scripts', () => {
+  const bare = validateCoinbaseScript(P2WPKH, 'mainnet');
+  assert.equal(bare.descriptor, `addr(${P2WPKH})`);
+  assert.match(bare.warnings[0], /expects a descriptor/);
+  assert.match(validateCoinbaseScript('raw(deadbeef)#89f8spxm', 'mainnet').warnings[0], /Not a standard output script/);
+});
+
+test('getDescriptorChecksum and classifyScript match BIP 380 and the standard templates', () => {
+  assert.equal(getDescriptorChecksum('raw(deadbeef)'), '89f8spxm');
+  assert.equal(classifyScript(Buffer.from('a914751e76e8199196d454941c45d1b3a323f1433bd687', 'hex')), 'p2sh');
+  assert.equal(classifyScript(Buffer.from(`0020${'00'.repeat(32)}`, 'hex')), 'p2wsh');
+});
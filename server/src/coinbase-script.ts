@@ -0,0 +1,226 @@
+/**
+ * Checks for the JDC's `coinbase_reward_script` before it is deployed.
+ *
+ * The JDC takes an output descriptor, `addr(<address>)` as sv2-ui writes it
+ * or `raw(<hex script>)`, and only reports a bad one in its logs after the
+ * container is up. This parses the same forms (a bare address too), checks
+ * the address against the network and the optional `#checksum`, and returns
+ * the scriptPubKey the coinbase would pay.
+ */
+
+import type { BitcoinNetwork } from '@sv2-ui/shared';
+import { decodeBase58Check } from './base58.js';
+
+export type AddressType = 'p2pkh' | 'p2sh' | 'p2wpkh' | 'p2wsh' | 'p2tr' | 'witness-unknown' | 'nonstandard';
+
+export interface CoinbaseScriptReport {
+  valid: boolean;
+  // The descriptor to deploy, with `addr(...)` added to a bare address.
+  descriptor: string | null;
+  scriptPubKey: string | null;
+  addressType: AddressType | null;
+  errors: string[];
+  warnings: string[];
+}
+
+const NETWORK_PARAMS: Record<BitcoinNetwork, { hrp: string; p2pkh: number; p2sh: number }> = {
+  mainnet: { hrp: 'bc', p2pkh: 0x00, p2sh: 0x05 },
+  testnet4: { hrp: 'tb', p2pkh: 0x6f, p2sh: 0xc4 },
+  signet: { hrp: 'tb', p2pkh: 0x6f, p2sh: 0xc4 },
+  regtest: { hrp: 'bcrt', p2pkh: 0x6f, p2sh: 0xc4 },
+};
+
+const BECH32_CHARSET = 'qpzry9x8gf2tvdw0s3jn54khce6mua7l';
+const BECH32_CONST = 1;
+const BECH32M_CONST = 0x2bc830a3;
+
+function bech32Polymod(values: number[]): number {
+  const generator = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+  let checksum = 1;
+  for (const value of values) {
+    const top = checksum >>> 25;
+    checksum = ((checksum & 0x1ffffff) << 5) ^ value;
+    for (let i = 0; i < 5; i++) {
+      if ((top >>> i) & 1) checksum ^= generator[i];
+    }
+  }
+  return checksum >>> 0;
+}
+
+function expandHrp(hrp: string): number[] {
+  return [...hrp].map((char) => char.charCodeAt(0) >> 5).concat(0, [...hrp].map((char) => char.charCodeAt(0) & 31));
+}
+
+/**
+ * Decode a segwit address (BIP 173 / BIP 350); null if it isn't one.
+ */
+export function decodeSegwitAddress(address: string): { hrp: string; version: number; program: Buffer } | null {
+  if (address !== address.toLowerCase() && address !== address.toUpperCase()) return null;
+  const lower = address.toLowerCase();
+  const separator = lower.lastIndexOf('1');
+  if (separator < 1 || separator + 7 > lower.length || lower.length > 90) return null;
+
+  const hrp = lower.slice(0, separator);
+  const data = [...lower.slice(separator + 1)].map((char) => BECH32_CHARSET.indexOf(char));
+  if (data.includes(-1)) return null;
+  const constant = bech32Polymod([...expandHrp(hrp), ...data]);
+  const [version, ...words] = data.slice(0, -6);
+  // Version 0 uses bech32, later versions bech32m.
+  if (version === undefined || constant !== (version === 0 ? BECH32_CONST : BECH32M_CONST) || version > 16) return null;
+
+  // Regroup the 5-bit words into bytes; leftover padding must be zero.
+  const bytes: number[] = [];
+  let accumulator = 0;
+  let bits = 0;
+  for (const word of words) {
+    accumulator = ((accumulator << 5) | word) & 0xfff;
+    bits += 5;
+    if (bits >= 8) {
+      bits -= 8;
+      bytes.push((accumulator >> bits) & 0xff);
+    }
+  }
+  if (bits >= 5 || (accumulator & ((1 << bits) - 1)) !== 0) return null;
+
+  const program = Buffer.from(bytes);
+  if (program.length < 2 || program.length > 40 || (version === 0 && program.length !== 20 && program.length !== 32)) return null;
+  return { hrp, version, program };
+}
+
+/**
+ * The BIP 380 checksum of a descriptor (the part before `#`).
+ */
+export function getDescriptorChecksum(descriptor: string): string | null {
+  const inputCharset = '0123456789()[],\'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#"\\ ';
+  const generator = [0xf5dee51989n, 0xa9fdca3312n, 0x1bab10e32dn, 0x3706b1677an, 0x644d626ffdn];
+
+  const symbols: number[] = [];
+  let groups: number[] = [];
+  for (const char of descriptor) {
+    const value = inputCharset.indexOf(char);
+    if (value < 0) return null;
+    symbols.push(value & 31);
+    groups.push(value >> 5);
+    if (groups.length === 3) {
+      symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+      groups = [];
+    }
+  }
+  if (groups.length === 1) symbols.push(groups[0]);
+  if (groups.length === 2) symbols.push(groups[0] * 3 + groups[1]);
+
+  let checksum = 1n;
+  for (const value of [...symbols, 0, 0, 0, 0, 0, 0, 0, 0]) {
+    const top = checksum >> 35n;
+    checksum = ((checksum & 0x7ffffffffn) << 5n) ^ BigInt(value);
+    for (let i = 0; i < 5; i++) {
+      if ((top >> BigInt(i)) & 1n) checksum ^= generator[i];
+    }
+  }
+  checksum ^= 1n;
+  return Array.from({ length: 8 }, (_, i) => BECH32_CHARSET[Number((checksum >> BigInt(5 * (7 - i))) & 31n)]).join('');
+}
+
+function networksOf(match: (params: (typeof NETWORK_PARAMS)[BitcoinNetwork]) => boolean): BitcoinNetwork[] {
+  return (Object.keys(NETWORK_PARAMS) as BitcoinNetwork[]).filter((network) => match(NETWORK_PARAMS[network]));
+}
+
+/**
+ * The scriptPubKey an address pays to, or an error saying why it can't be used on `network`.
+ */
+export function getAddressScript(
+  address: string,
+  network: BitcoinNetwork,
+): { scriptPubKey: Buffer; addressType: AddressType } | { error: string } {
+  const params = NETWORK_PARAMS[network];
+  const segwit = decodeSegwitAddress(address);
+  if (segwit) {
+    if (segwit.hrp !== params.hrp) {
+      const other = networksOf((candidate) => candidate.hrp === segwit.hrp);
+      return { error: other.length > 0 ? `Address is for ${other.join('/')}, not ${network}` : `Unknown address prefix "${segwit.hrp}"` };
+    }
+    const { version, program } = segwit;
+    const addressType: AddressType = version === 0
+      ? (program.length === 20 ? 'p2wpkh' : 'p2wsh')
+      : version === 1 && program.length === 32 ? 'p2tr' : 'witness-unknown';
+    // OP_0, or OP_1..OP_16 (0x51..0x60), then a push of the program.
+    return { scriptPubKey: Buffer.from([version === 0 ? 0 : 0x50 + version, program.length, ...program]), addressType };
+  }
+
+  const decoded = decodeBase58Check(address);
+  if (!decoded || decoded.length !== 21) return { error: 'Not a valid Bitcoin address' };
+  const [version] = decoded;
+  const hash = decoded.subarray(1);
+  if (version === params.p2pkh) {
+    return { scriptPubKey: Buffer.from([0x76, 0xa9, 0x14, ...hash, 0x88, 0xac]), addressType: 'p2pkh' };
+  }
+  if (version === params.p2sh) {
+    return { scriptPubKey: Buffer.from([0xa9, 0x14, ...hash, 0x87]), addressType: 'p2sh' };
+  }
+  const other = networksOf((candidate) => candidate.p2pkh === version || candidate.p2sh === version);
+  return { error: other.length > 0 ? `Address is for ${other.join('/')}, not ${network}` : 'Not a valid Bitcoin address' };
+}
+
+/**
+ * What kind of output a raw script is; the templates addresses encode to.
+ */
+export function classifyScript(script: Buffer): AddressType {
+  const hex = script.toString('hex');
+  if (/^76a914[0-9a-f]{40}88ac$/.test(hex)) return 'p2pkh';
+  if (/^a914[0-9a-f]{40}87$/.test(hex)) return 'p2sh';
+  if (/^0014[0-9a-f]{40}$/.test(hex)) return 'p2wpkh';
+  if (/^0020[0-9a-f]{64}$/.test(hex)) return 'p2wsh';
+  if (/^5120[0-9a-f]{64}$/.test(hex)) return 'p2tr';
+  const [op, length] = script;
+  if (op >= 0x51 && op <= 0x60 && length >= 2 && length <= 40 && script.length === length + 2) return 'witness-unknown';
+  return 'nonstandard';
+}
+
+export function validateCoinbaseScript(value: string, network: BitcoinNetwork): CoinbaseScriptReport {
+  const report: CoinbaseScriptReport = { valid: false, descriptor: null, scriptPubKey: null, addressType: null, errors: [], warnings: [] };
+  const fail = (error: string) => ({ ...report, errors: [...report.errors, error] });
+
+  if (value !== value.trim()) return fail('Leading or trailing whitespace is not allowed');
+  if (!value) return fail('Script is required');
+
+  const [body, checksum, ...extra] = value.split('#');
+  if (extra.length > 0) return fail('A descriptor has at most one "#" checksum');
+  if (checksum !== undefined) {
+    const expected = getDescriptorChecksum(body);
+    if (checksum !== expected) return fail(`Descriptor checksum is ${checksum}, expected ${expected ?? 'none (invalid characters)'}`);
+  }
+
+  const match = body.match(/^(\w+)\((.*)\)$/);
+  let script: Buffer;
+  if (!match) {
+    // A bare address is accepted for convenience; the JDC wants it wrapped.
+    const result = getAddressScript(body, network);
+    if ('error' in result) return fail(result.error);
+    report.warnings.push(`The JDC expects a descriptor; use addr(${body})`);
+    report.descriptor = `addr(${body})`;
+    report.addressType = result.addressType;
+    script = result.scriptPubKey;
+  } else if (match[1] === 'addr') {
+    const result = getAddressScript(match[2], network);
+    if ('error' in result) return fail(result.error);
+    report.descriptor = value;
+    report.addressType = result.addressType;
+    script = result.scriptPubKey;
+  } else if (match[1] === 'raw') {
+    if (!/^([0-9a-fA-F]{2})+$/.test(match[2])) return fail('raw() needs an even-length hex script');
+    script = Buffer.from(match[2], 'hex');
+    report.descriptor = value;
+    report.addressType = classifyScript(script);
+  } else {
+    return fail(`Unsupported descriptor ${match[1]}(); use addr(<address>) or raw(<hex>)`);
+  }
+
+  if (script[0] === 0x6a) return fail('OP_RETURN outputs are unspendable; the block reward would be burned');
+  if (report.addressType === 'witness-unknown') {
+    report.warnings.push('Witness version not defined yet; coins sent there can be taken by anyone until it is');
+  } else if (report.addressType === 'nonstandard') {
+    report.warnings.push('Not a standard output script; make sure you can spend it');
+  }
+
+  return { ...report, valid: true, scriptPubKey: script.toString('hex') };
+}
@@ -1,6 +1,7 @@
 import assert from 'node:assert/strict';
 import { test } from 'node:test';
-import { generateJdcConfig, generateTranslatorConfig, normalizeSetupData } from './config-generator.js';
+import { generateJdcConfig, generateTranslatorConfig, getSetupDataError, normalizeSetupData } from './config-generator.js';
+import { BITCOIN_ERROR_MESSAGES } from './messages.js';
 import type { SetupData } from './types.js';
 
 const BASE_DATA_30: SetupData = {
@@ -209,3 +210,11 @@ test('no-jd mode: translator uses new format (user_identity inside [[upstreams]]
   assert.ok(identityIdx > upstreamIdx);
   assert.match(config, /\[\[upstreams\]\][\s\S]*user_identity = "miner\.solo"/);
 });
+
+test('setup data errors name the first missing piece', () => {
+  assert.equal(getSetupDataError(BASE_DATA_31), null);
+  assert.equal(getSetupDataError(BASE_DATA_31_SOLO), null);
+  assert.equal(getSetupDataError(NO_JD_DATA), null);
+  assert.equal(getSetupDataError({ ...NO_JD_DATA, pool: null }), BITCOIN_ERROR_MESSAGES.missingConfig);
+  assert.equal(getSetupDataError({ ...BASE_DATA_31, jdc: null }), BITCOIN_ERROR_MESSAGES.jdConfig);
+});
@@ -6,13 +6,16 @@
 import {
   JDC_AUTHORITY_PUBLIC_KEY,
   JDC_PORT,
+  POOL_JDS_PORT,
   TRANSLATOR_PORT,
   shouldAggregateTranslatorChannels,
   DEFAULT_SHARES_PER_MINUTE,
   DEFAULT_DOWNSTREAM_EXTRANONCE2_SIZE,
   bitcoinCoreVersionToIpcMajor,
   formatSupportedVersions,
+  isSupportedBitcoinCoreVersion,
 } from '@sv2-ui/shared';
+import { BITCOIN_ERROR_MESSAGES } from './messages.js';
 import type { SetupData } from './types.js';
 
 function positiveNumber(value: number | undefined, fallback: number): number {
@@ -57,6 +60,35 @@ export function normalizeSetupData(data: SetupData): SetupData {
   };
 }
 
+export function getBitcoinCoreVersionError(data: SetupData): string | null {
+  if (data.mode !== 'jd') {
+    return null;
+  }
+
+  if (!isSupportedBitcoinCoreVersion(data.bitcoin?.core_version)) {
+    return BITCOIN_ERROR_MESSAGES.selectVersion;
+  }
+
+  return null;
+}
+
+/**
+ * The reason `data` can't be deployed, or null when it's complete.
+ */
+export function getSetupDataError(data: SetupData): string | null {
+  const requiresPool = !(data.miningMode === 'solo' && data.mode === 'jd');
+
+  if (!data.mode || !data.translator || (requiresPool && !data.pool)) {
+    return BITCOIN_ERROR_MESSAGES.missingConfig;
+  }
+
+  if (data.mode === 'jd' && (!data.jdc || !data.bitcoin)) {
+    return BITCOIN_ERROR_MESSAGES.jdConfig;
+  }
+
+  return getBitcoinCoreVersionError(data);
+}
+
 /**
  * Generate Translator Proxy config (tproxy-config.toml)
  */
@@ -169,7 +201,7 @@ authority_pubkey = "${pool.authority_public_key}"
 pool_address = "${pool.address}"
 pool_port = ${pool.port}
 jds_address = "${pool.address}"
-jds_port = 3334
+jds_port = ${POOL_JDS_PORT}
 ${userIdentityLine}
 
 `
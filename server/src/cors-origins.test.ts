@@ -0,0 +1,43 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { getCorsOptions, getCorsPolicy, parseCorsOrigins, parseCorsPolicies } from './cors-origins.js';
+
+test('defaults to same-origin only', () => {
+  assert.deepEqual(parseCorsOrigins(undefined), []);
+  assert.deepEqual(getCorsOptions([]), { origin: false });
+});
+
+test('accepts explicit origins and a wildcard', () => {
+  assert.deepEqual(
+    parseCorsOrigins('https://dashboard.example.com, http://10.0.0.5:8080/'),
+    ['https://dashboard.example.com', 'http://10.0.0.5:8080'],
+  );
+  assert.equal(parseCorsOrigins('https://a.example.com,*'), '*');
+  assert.deepEqual(getCorsOptions('*'), { origin: true });
+});
+
+test('rejects entries that are not bare origins', () => {
+  assert.throws(() => parseCorsOrigins('dashboard.example.com'), /Invalid CORS_ALLOW_ORIGIN/);
+  assert.throws(() => parseCorsOrigins('https://dashboard.example.com/sv2'), /Invalid CORS_ALLOW_ORIGIN/);
+});
+
+test('public embeds allow any origin while the API stays same-origin', () => {
+  const policies = parseCorsPolicies({});
+  assert.equal(getCorsPolicy(policies, '/badge/hashrate.svg')?.origins, '*');
+  assert.equal(getCorsPolicy(policies, '/card/summary.svg')?.origins, '*');
+  assert.equal(getCorsPolicy(policies, '/feed.xml')?.origins, '*');
+  assert.equal(getCorsPolicy(policies, '/calendar.ics')?.origins, '*');
+  assert.deepEqual(getCorsPolicy(policies, '/api/status')?.origins, []);
+  assert.deepEqual(getCorsPolicy(policies, '/translator-api/v1/global')?.origins, []);
+});
+
+test('each route group has its own origins and honours BASE_PATH', () => {
+  const policies = parseCorsPolicies({
+    CORS_ALLOW_ORIGIN: 'https://dashboard.example.com',
+    CORS_PUBLIC_ALLOW_ORIGIN: 'https://wiki.example.com',
+  });
+  assert.equal(getCorsPolicy(policies, '/sv2/badge/workers.svg', '/sv2')?.name, 'public');
+  assert.deepEqual(getCorsPolicy(policies, '/sv2/badge/workers.svg', '/sv2')?.origins, ['https://wiki.example.com']);
+  assert.deepEqual(getCorsPolicy(policies, '/sv2/api/status', '/sv2')?.origins, ['https://dashboard.example.com']);
+  assert.throws(() => parseCorsPolicies({ CORS_PUBLIC_ALLOW_ORIGIN: 'wiki' }), /Invalid CORS_PUBLIC_ALLOW_ORIGIN/);
+});
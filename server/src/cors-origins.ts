@@ -0,0 +1,97 @@
+/**
+ * CORS policies per route group.
+ *
+ * The API (everything but the public group) follows CORS_ALLOW_ORIGIN, and
+ * by default no cross-origin access is granted: the UI is served by this
+ * same server, and the Vite dev server proxies /api. Badges, summary cards,
+ * the event and calendar feeds and share pages are meant to be embedded
+ * elsewhere, so their group follows CORS_PUBLIC_ALLOW_ORIGIN, which allows
+ * any origin unless set.
+ */
+
+import cors from 'cors';
+import type { CorsOptions } from 'cors';
+import type { RequestHandler } from 'express';
+import { REQUEST_ID_HEADER } from './request-context.js';
+
+export type CorsRouteGroupName = 'public' | 'api';
+
+interface CorsRouteGroup {
+  name: CorsRouteGroupName;
+  // Path prefixes under BASE_PATH. Empty matches everything.
+  paths: string[];
+  env: string;
+  fallback: string;
+  methods?: string[];
+}
+
+// Checked in order; the first group with a matching prefix applies.
+export const CORS_ROUTE_GROUPS: readonly CorsRouteGroup[] = [
+  { name: 'public', paths: ['/badge/', '/card/', '/feed.xml', '/calendar.ics', '/share/'], env: 'CORS_PUBLIC_ALLOW_ORIGIN', fallback: '*', methods: ['GET', 'HEAD'] },
+  { name: 'api', paths: [], env: 'CORS_ALLOW_ORIGIN', fallback: '' },
+];
+
+export interface CorsPolicy {
+  name: CorsRouteGroupName;
+  paths: string[];
+  origins: string[] | '*';
+  methods?: string[];
+}
+
+export function parseCorsOrigins(value: string | undefined, name = 'CORS_ALLOW_ORIGIN'): string[] | '*' {
+  const entries = (value ?? '').split(',').map((entry) => entry.trim()).filter(Boolean);
+  if (entries.includes('*')) return '*';
+
+  return entries.map((entry) => {
+    let origin: string;
+    try {
+      origin = new URL(entry).origin;
+    } catch {
+      throw new Error(`Invalid ${name} entry "${entry}"`);
+    }
+    // Origins are scheme://host[:port] only; a path here is almost certainly a mistake.
+    if (origin === 'null' || origin !== entry.replace(/\/$/, '')) {
+      throw new Error(`Invalid ${name} entry "${entry}" (expected e.g. https://dashboard.example.com)`);
+    }
+    return origin;
+  });
+}
+
+export function getCorsOptions(origins: string[] | '*'): CorsOptions {
+  if (origins === '*') return { origin: true };
+  return { origin: origins.length > 0 ? origins : false };
+}
+
+export function parseCorsPolicies(env: NodeJS.ProcessEnv = process.env): CorsPolicy[] {
+  return CORS_ROUTE_GROUPS.map(({ name, paths, env: variable, fallback, methods }) => ({
+    name,
+    paths,
+    // An empty value means "unset" so compose files can blank a variable.
+    origins: parseCorsOrigins(env[variable]?.trim() || fallback, variable),
+    ...(methods ? { methods } : {}),
+  }));
+}
+
+export function getCorsPolicy(policies: CorsPolicy[], requestPath: string, basePath = ''): CorsPolicy | undefined {
+  const relative = requestPath.startsWith(basePath) ? requestPath.slice(basePath.length) : requestPath;
+  return policies.find(({ paths }) => paths.length === 0 || paths.some((prefix) => relative.startsWith(prefix)));
+}
+
+/**
+ * Apply the CORS policy of the route group a request falls in.
+ */
+export function createCorsMiddleware(policies: CorsPolicy[], basePath = ''): RequestHandler {
+  const handlers = new Map(policies.map((policy) => [policy.name, cors({
+    ...getCorsOptions(policy.origins),
+    ...(policy.methods ? { methods: policy.methods } : {}),
+    // Cross-origin dashboards can report it with a failed read.
+    exposedHeaders: [REQUEST_ID_HEADER],
+  })]));
+
+  return (req, res, next) => {
+    const policy = getCorsPolicy(policies, req.path, basePath);
+    const handler = policy && handlers.get(policy.name);
+    if (!handler) return next();
+    handler(req, res, next);
+  };
+}
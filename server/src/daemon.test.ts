@@ -0,0 +1,62 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { claimPidFile, daemonize, getChildArgs, parseDaemonArgs } from './daemon.js';
+
+test('parseDaemonArgs resolves the paths, and the child gets the rest of the arguments', () => {
+  assert.deepEqual(parseDaemonArgs([]), { daemon: false, pidFile: null, logFile: null });
+  assert.deepEqual(parseDaemonArgs(['--daemon', '--pid-file', '/run/sv2-ui.pid', '--log-file=/var/log/sv2-ui.log']), {
+    daemon: true,
+    pidFile: '/run/sv2-ui.pid',
+    logFile: '/var/log/sv2-ui.log',
+  });
+  assert.equal(parseDaemonArgs(['--pid-file', 'sv2-ui.pid']).pidFile, path.resolve('sv2-ui.pid'));
+  assert.throws(() => parseDaemonArgs(['--log-file', 'sv2-ui.log']), /only applies with --daemon/);
+  assert.throws(() => parseDaemonArgs(['--daemon', '--pid-file', '--headless']), /--pid-file requires a path/);
+
+  assert.deepEqual(
+    getChildArgs(['--daemon', '--headless', '--log-file', '/tmp/sv2-ui.log', '--pid-file', '/tmp/sv2-ui.pid']),
+    ['--headless', '--pid-file', '/tmp/sv2-ui.pid'],
+  );
+  assert.deepEqual(getChildArgs(['--log-file=/tmp/sv2-ui.log', '--daemon']), []);
+});
+
+test('a PID file is claimed unless its process still runs, and only its owner removes it', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-daemon-'));
+  t.after(() => fs.rm(dir, { recursive: true, force: true }));
+  const file = path.join(dir, 'run', 'sv2-ui.pid');
+
+  const release = claimPidFile(file, 4_000_001);
+  assert.equal(await fs.readFile(file, 'utf-8'), '4000001\n');
+  // That PID isn't running, so the file is stale.
+  claimPidFile(file, 4_000_002);
+  release();
+  assert.equal(await fs.readFile(file, 'utf-8'), '4000002\n');
+
+  await fs.writeFile(file, `${process.pid}\n`);
+  assert.throws(() => claimPidFile(file, 4_000_003), new RegExp(`already running with PID ${process.pid}`));
+});
+
+test('daemonize resolves once the child is listening, and fails when it exits first', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-daemon-'));
+  t.after(() => fs.rm(dir, { recursive: true, force: true }));
+  const script = path.join(dir, 'server.mjs');
+  await fs.writeFile(script, [
+    "console.log(JSON.stringify(process.argv.slice(2)));",
+    "if (process.argv.includes('--fail')) process.exit(3);",
+    "process.send('sv2-ui:ready');",
+    'setTimeout(() => process.exit(0), 100);',
+  ].join('\n'));
+  const logFile = path.join(dir, 'logs', 'sv2-ui.log');
+
+  const pid = await daemonize({ logFile, pidFile: null, argv: [process.execPath, script, '--daemon', '--headless'], execArgv: [] });
+  assert.ok(pid > 0);
+  assert.equal(await fs.readFile(logFile, 'utf-8'), '["--headless"]\n');
+
+  await assert.rejects(
+    daemonize({ logFile, pidFile: null, argv: [process.execPath, script, '--daemon', '--fail'], execArgv: [] }),
+    /exited while starting \(code 3\); see .*sv2-ui\.log/,
+  );
+});
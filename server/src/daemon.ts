@@ -0,0 +1,146 @@
+/**
+ * `--daemon` and `--pid-file`: running unattended without systemd or Docker.
+ *
+ * `--daemon` starts the server again as a detached child in its own session,
+ * with the same arguments minus `--daemon` and `--log-file`. Its stdin is
+ * closed and its stdout and stderr are appended to `--log-file`
+ * (CONFIG_DIR/sv2-ui.log by default). The parent waits until the child is
+ * listening, prints its PID and exits 0. If the child exits first, the
+ * parent exits 1 and points at the log.
+ *
+ * `--pid-file` works with or without `--daemon`. The server writes its PID
+ * there on startup, refuses to start while the PID in it is still running,
+ * and removes the file on exit. SIGTERM stops a daemon the way Ctrl-C stops
+ * the foreground server.
+ */
+
+import { spawn } from 'child_process';
+import fs from 'fs';
+import path from 'path';
+
+export interface DaemonArgs {
+  daemon: boolean;
+  pidFile: string | null;
+  logFile: string | null;
+}
+
+// Set on the child, which reports back once it's listening.
+const DAEMON_CHILD_ENV = 'SV2_UI_DAEMON_CHILD';
+const READY_MESSAGE = 'sv2-ui:ready';
+
+function readPathArg(argv: string[], name: string): { value: string; consumed: number } | null {
+  const index = argv.findIndex((arg) => arg === name || arg.startsWith(`${name}=`));
+  if (index === -1) return null;
+  const inline = argv[index].startsWith(`${name}=`);
+  const value = (inline ? argv[index].slice(name.length + 1) : argv[index + 1])?.trim();
+  if (!value || value.startsWith('--')) throw new Error(`${name} requires a path`);
+  return { value: path.resolve(value), consumed: inline ? 1 : 2 };
+}
+
+export function parseDaemonArgs(argv: string[] = process.argv.slice(2)): DaemonArgs {
+  const daemon = argv.includes('--daemon');
+  const logFile = readPathArg(argv, '--log-file')?.value ?? null;
+  if (logFile && !daemon) throw new Error('--log-file only applies with --daemon');
+  return { daemon, pidFile: readPathArg(argv, '--pid-file')?.value ?? null, logFile };
+}
+
+/**
+ * `argv` without the options only the parent acts on.
+ */
+export function getChildArgs(argv: string[]): string[] {
+  const args = argv.filter((arg) => arg !== '--daemon');
+  const log = readPathArg(args, '--log-file');
+  if (!log) return args;
+  const index = args.findIndex((arg) => arg === '--log-file' || arg.startsWith('--log-file='));
+  return [...args.slice(0, index), ...args.slice(index + log.consumed)];
+}
+
+function readPid(file: string): number | null {
+  try {
+    const pid = Number(fs.readFileSync(file, 'utf-8').trim());
+    return Number.isInteger(pid) && pid > 0 ? pid : null;
+  } catch {
+    return null;
+  }
+}
+
+function isRunning(pid: number): boolean {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch (error) {
+    // EPERM: it runs, as another user.
+    return (error as NodeJS.ErrnoException).code === 'EPERM';
+  }
+}
+
+function assertNotRunning(file: string, pid = process.pid): void {
+  const running = readPid(file);
+  if (running !== null && running !== pid && isRunning(running)) {
+    throw new Error(`sv2-ui is already running with PID ${running} (${file})`);
+  }
+}
+
+/**
+ * Write this process's PID to `file`, replacing a stale one. Returns the
+ * function that removes it again, unless another process took it over.
+ */
+export function claimPidFile(file: string, pid = process.pid): () => void {
+  assertNotRunning(file, pid);
+  fs.mkdirSync(path.dirname(file), { recursive: true });
+  fs.writeFileSync(file, `${pid}\n`);
+  return () => {
+    if (readPid(file) === pid) fs.rmSync(file, { force: true });
+  };
+}
+
+export interface DaemonizeOptions {
+  logFile: string;
+  pidFile: string | null;
+  argv?: string[];
+  execArgv?: string[];
+  env?: NodeJS.ProcessEnv;
+}
+
+/**
+ * Start the detached child and resolve with its PID once it's listening.
+ */
+export function daemonize({
+  logFile,
+  pidFile,
+  argv = process.argv,
+  execArgv = process.execArgv,
+  env = process.env,
+}: DaemonizeOptions): Promise<number> {
+  if (pidFile) assertNotRunning(pidFile);
+  fs.mkdirSync(path.dirname(logFile), { recursive: true });
+  const log = fs.openSync(logFile, 'a');
+
+  const child = spawn(process.execPath, [...execArgv, argv[1], ...getChildArgs(argv.slice(2))], {
+    detached: true,
+    stdio: ['ignore', log, log, 'ipc'],
+    env: { ...env, [DAEMON_CHILD_ENV]: '1' },
+  });
+  fs.closeSync(log);
+
+  return new Promise((resolve, reject) => {
+    child.once('error', reject);
+    child.once('exit', (code, signal) => {
+      reject(new Error(`sv2-ui exited while starting (${signal ?? `code ${code}`}); see ${logFile}`));
+    });
+    child.on('message', (message) => {
+      if (message !== READY_MESSAGE) return;
+      child.removeAllListeners('exit');
+      child.disconnect();
+      child.unref();
+      resolve(child.pid!);
+    });
+  });
+}
+
+/**
+ * Tell the parent of a `--daemon` start that the server is listening.
+ */
+export function notifyDaemonParent(): void {
+  if (process.env[DAEMON_CHILD_ENV] === '1' && process.send) process.send(READY_MESSAGE);
+}
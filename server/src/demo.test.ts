@@ -0,0 +1,67 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import { createDemoSimulation, parseDemoMode, startDemoServers } from './demo.js';
+import { fetchStackData } from './monitoring.js';
+import { setContainerOrigins } from './proxy.js';
+
+// A small deterministic generator so the simulation is reproducible.
+function seeded(seed: number): () => number {
+  return () => {
+    seed = (seed * 48271) % 2147483647;
+    return seed / 2147483647;
+  };
+}
+
+test('parseDemoMode takes --demo or DEMO=true', () => {
+  assert.equal(parseDemoMode([], {}), false);
+  assert.equal(parseDemoMode(['--demo'], {}), true);
+  assert.equal(parseDemoMode([], { DEMO: 'true' }), true);
+  assert.equal(parseDemoMode([], { DEMO: 'false' }), false);
+  assert.throws(() => parseDemoMode([], { DEMO: 'yes' }), /DEMO must be true or false/);
+});
+
+test('the simulation moves with the clock: shares, rejects and miners coming and going', () => {
+  let now = 0;
+  const sim = createDemoSimulation({ random: seeded(7), now: () => now });
+  assert.equal(sim.miners().length, 4);
+  assert.equal(sim.totals().accepted, 0);
+
+  const seen = new Set<number>();
+  const counts = new Set<number>();
+  for (let i = 0; i < 2000; i++) {
+    now += 10_000;
+    for (const miner of sim.miners()) seen.add(miner.clientId);
+    counts.add(sim.miners().length);
+    assert.ok(sim.miners().length >= 2 && sim.miners().length <= 8);
+  }
+
+  const totals = sim.totals();
+  assert.ok(totals.accepted > 0);
+  assert.ok(totals.rejected > 0 && totals.rejected < totals.accepted / 10);
+  assert.ok(totals.hashrate > 0);
+  assert.ok(seen.size > 4, 'miners should join');
+  assert.ok(counts.size > 1, 'the miner count should change');
+  assert.equal(sim.uptimeSecs(), 20_000);
+});
+
+test('the demo servers answer everything the dashboard poller reads', async () => {
+  const sim = createDemoSimulation({ random: seeded(1) });
+  const demo = await startDemoServers(sim);
+  try {
+    setContainerOrigins(demo.origins);
+    const data = await fetchStackData('jd');
+    assert.deepEqual(data.errors, []);
+    assert.equal(data.upstream, 'jdc');
+    assert.equal(data.sv1Clients?.total, sim.miners().length);
+    assert.equal(data.serverChannels?.extended_channels.length, 1);
+    assert.equal(data.sv2Clients?.[0].extended_channels.length, 1);
+    assert.ok((data.global?.server?.total_hashrate ?? 0) > 0);
+
+    const response = await fetch(`${Object.values(demo.origins)[0]}/api/v1/sv1/clients/999`);
+    assert.equal(response.status, 404);
+  } finally {
+    setContainerOrigins({});
+    await demo.close();
+  }
+});
@@ -0,0 +1,425 @@
+/**
+ * Demo mode: synthetic Translator and JDC monitoring APIs.
+ *
+ * With `--demo` (or DEMO=true) the server doesn't proxy to real mining
+ * services. It serves both monitoring APIs itself, on loopback ports, from a
+ * simulated JD pool-mining stack. Hashrate fluctuates, shares are submitted
+ * with the occasional reject, and SV1 miners join and leave. The saved setup
+ * is kept in memory and the containers are reported healthy, so the dashboard
+ * can be evaluated, screenshotted or developed without Docker.
+ */
+
+import crypto from 'crypto';
+import express from 'express';
+import http from 'http';
+import type { AddressInfo } from 'net';
+import type { RequestHandler, Router } from 'express';
+import { CONTAINER_NAMES } from '@sv2-ui/shared';
+import { MONITORING_SERVICES } from './monitoring.js';
+import type { MonitoringService } from './monitoring.js';
+import { getDefaultState } from './state.js';
+import type { SavedState, StateStore, StateStoreOptions } from './state.js';
+import type { ContainerStatus, SetupData, StatusResponse } from './types.js';
+
+export const DEMO_SETUP: SetupData = {
+  miningMode: 'pool',
+  mode: 'jd',
+  pool: {
+    name: 'Demo Pool',
+    address: 'pool.demo.invalid',
+    port: 34254,
+    authority_public_key: '9auqWEzQDVyd2oe1JVGFLMLHZtCo2FFqZwtKA5gd9xbuEu7PH72',
+  },
+  bitcoin: {
+    core_version: '30',
+    network: 'mainnet',
+    os: 'linux',
+    customDataDir: '',
+    socket_path: '',
+  },
+  jdc: {
+    user_identity: 'demo',
+    jdc_signature: 'sv2-ui demo',
+    coinbase_reward_address: 'bc1qdemo0000000000000000000000000000000000',
+  },
+  translator: {
+    user_identity: 'demo',
+    enable_vardiff: true,
+    aggregate_channels: true,
+    min_hashrate: 100_000_000_000_000,
+    shares_per_minute: 6,
+    downstream_extranonce2_size: 4,
+  },
+};
+
+const MIN_MINERS = 2;
+const MAX_MINERS = 8;
+const INITIAL_MINERS = 4;
+// On average a miner joins or leaves every ten minutes.
+const MEAN_CHURN_SECS = 600;
+const SHARES_PER_MINUTE = 6;
+const REJECT_RATE = 0.02;
+const REJECT_REASONS = ['stale-share', 'difficulty-too-low'];
+// Longer gaps between requests are simulated as this long.
+const MAX_STEP_SECS = 60;
+// Requests that would start, stop or reconfigure real containers.
+const STACK_CONTROL_PATHS = /^\/api\/(setup|config|stop|restart|reset|warm-restart|v1\/services\/.*)$/;
+const TARGET_HEX = '00000000ffff0000000000000000000000000000000000000000000000000000';
+const MINER_MODELS = [
+  { name: 'antminer-s19', hashrate: 95e12 },
+  { name: 'antminer-s21', hashrate: 200e12 },
+  { name: 'whatsminer-m50', hashrate: 118e12 },
+  { name: 'bitaxe', hashrate: 1.2e12 },
+];
+
+export interface DemoMiner {
+  clientId: number;
+  workerName: string;
+  nominalHashrate: number;
+  hashrate: number;
+  extranonce1: string;
+  accepted: number;
+  rejected: number;
+  rejectedByReason: Record<string, number>;
+  bestDiff: number;
+}
+
+export interface DemoOptions {
+  random?: () => number;
+  now?: () => number;
+}
+
+export function parseDemoMode(argv: string[] = process.argv.slice(2), env: NodeJS.ProcessEnv = process.env): boolean {
+  if (argv.includes('--demo')) return true;
+  const value = env.DEMO?.trim().toLowerCase();
+  if (!value || value === 'false') return false;
+  if (value === 'true') return true;
+  throw new Error(`DEMO must be true or false, got "${env.DEMO}"`);
+}
+
+/**
+ * The simulated stack. Time moves on whenever it is read, so the numbers
+ * change between dashboard polls without a background timer.
+ */
+export function createDemoSimulation({ random = Math.random, now = Date.now }: DemoOptions = {}) {
+  const startedAt = now();
+  let lastStep = startedAt;
+  let nextClientId = 1;
+  let totalAccepted = 0;
+  let totalRejected = 0;
+  const rejectedByReason: Record<string, number> = {};
+  let bestDiff = 0;
+  const miners: DemoMiner[] = [];
+
+  function addMiner(): void {
+    const model = MINER_MODELS[Math.floor(random() * MINER_MODELS.length)];
+    const clientId = nextClientId++;
+    miners.push({
+      clientId,
+      workerName: `demo.${model.name}-${clientId}`,
+      nominalHashrate: model.hashrate,
+      hashrate: model.hashrate,
+      extranonce1: crypto.randomBytes(4).toString('hex'),
+      accepted: 0,
+      rejected: 0,
+      rejectedByReason: {},
+      bestDiff: 0,
+    });
+  }
+
+  function step(secs: number): void {
+    for (const miner of miners) {
+      miner.hashrate = miner.nominalHashrate * (0.9 + random() * 0.2);
+      const shares = Math.floor(secs * SHARES_PER_MINUTE / 60 + random());
+      for (let i = 0; i < shares; i++) {
+        if (random() < REJECT_RATE) {
+          const reason = REJECT_REASONS[Math.floor(random() * REJECT_REASONS.length)];
+          miner.rejected++;
+          miner.rejectedByReason[reason] = (miner.rejectedByReason[reason] ?? 0) + 1;
+          rejectedByReason[reason] = (rejectedByReason[reason] ?? 0) + 1;
+          totalRejected++;
+        } else {
+          // A share that meets the channel difficulty beats it by a factor of 1/U.
+          const diff = Math.round(miner.nominalHashrate * 60 / SHARES_PER_MINUTE / 2 ** 32 / (1 - random()));
+          miner.accepted++;
+          miner.bestDiff = Math.max(miner.bestDiff, diff);
+          bestDiff = Math.max(bestDiff, diff);
+          totalAccepted++;
+        }
+      }
+    }
+
+    if (miners.length < MAX_MINERS && random() < secs / MEAN_CHURN_SECS) addMiner();
+    if (miners.length > MIN_MINERS && random() < secs / MEAN_CHURN_SECS) {
+      miners.splice(Math.floor(random() * miners.length), 1);
+    }
+  }
+
+  function advance(): void {
+    const elapsed = Math.floor((now() - lastStep) / 1000);
+    if (elapsed < 1) return;
+    lastStep += elapsed * 1000;
+    step(Math.min(elapsed, MAX_STEP_SECS));
+  }
+
+  for (let i = 0; i < INITIAL_MINERS; i++) addMiner();
+
+  return {
+    advance,
+    miners: () => {
+      advance();
+      return miners;
+    },
+    totals: () => {
+      advance();
+      return {
+        hashrate: miners.reduce((sum, miner) => sum + miner.hashrate, 0),
+        accepted: totalAccepted,
+        rejected: totalRejected,
+        rejectedByReason: { ...rejectedByReason },
+        bestDiff,
+      };
+    },
+    uptimeSecs: () => Math.floor((now() - startedAt) / 1000),
+  };
+}
+
+export type DemoSimulation = ReturnType<typeof createDemoSimulation>;
+
+function page<T>(items: T[], query: Record<string, unknown>) {
+  const offset = Number(query.offset) || 0;
+  const limit = Number(query.limit) || 25;
+  return { items: items.slice(offset, offset + limit), offset, limit, total: items.length };
+}
+
+// The aggregated channel a service holds with its upstream.
+function upstreamChannel(sim: DemoSimulation, userIdentity: string) {
+  const totals = sim.totals();
+  return {
+    channel_id: 1,
+    user_identity: userIdentity,
+    nominal_hashrate: totals.hashrate,
+    target_hex: TARGET_HEX,
+    extranonce_prefix_hex: '00000001',
+    full_extranonce_size: 8,
+    rollable_extranonce_size: 4,
+    version_rolling: true,
+    best_diff: totals.bestDiff,
+    blocks_found: 0,
+    shares_submitted: totals.accepted + totals.rejected,
+    shares_acknowledged: totals.accepted,
+    shares_rejected: totals.rejected,
+    shares_rejected_by_reason: totals.rejectedByReason,
+    acknowledged_work_sum: totals.accepted * 2 ** 32,
+    validated_work_sum: totals.accepted * 2 ** 32,
+  };
+}
+
+function createTranslatorApi(sim: DemoSimulation): Router {
+  const router = express.Router();
+  const toClient = (miner: DemoMiner) => ({
+    client_id: miner.clientId,
+    channel_id: 1,
+    authorized_worker_name: miner.workerName,
+    user_identity: DEMO_SETUP.translator!.user_identity,
+    hashrate: miner.hashrate,
+    stable_hashrate: true,
+    target_hex: TARGET_HEX,
+    extranonce1_hex: miner.extranonce1,
+    extranonce2_len: DEMO_SETUP.translator!.downstream_extranonce2_size,
+    version_rolling_mask: '1fffe000',
+    version_rolling_min_bit: null,
+  });
+
+  router.get('/global', (_req, res) => {
+    const { hashrate } = sim.totals();
+    res.json({
+      server: { extended_channels: 1, standard_channels: 0, total_channels: 1, total_hashrate: hashrate },
+      sv1_clients: { total_clients: sim.miners().length, total_hashrate: hashrate },
+      sv2_clients: null,
+      uptime_secs: sim.uptimeSecs(),
+    });
+  });
+  router.get('/server', (_req, res) => {
+    res.json({ extended_channels_count: 1, standard_channels_count: 0, total_hashrate: sim.totals().hashrate });
+  });
+  router.get('/server/channels', (req, res) => {
+    const { items, offset, limit } = page([upstreamChannel(sim, DEMO_SETUP.translator!.user_identity)], req.query);
+    res.json({ extended_channels: items, standard_channels: [], offset, limit, total_extended: 1, total_standard: 0 });
+  });
+  router.get('/sv1/clients', (req, res) => {
+    res.json(page(sim.miners().map(toClient), req.query));
+  });
+  router.get('/sv1/clients/:id', (req, res) => {
+    const miner = sim.miners().find((candidate) => candidate.clientId === Number(req.params.id));
+    if (!miner) return res.status(404).json({ error: 'Client not found' });
+    res.json(toClient(miner));
+  });
+
+  return router;
+}
+
+function createJdcApi(sim: DemoSimulation): Router {
+  const router = express.Router();
+  // The Translator is the JDC's only SV2 client, with one aggregated channel.
+  const translatorChannel = () => {
+    const totals = sim.totals();
+    return {
+      channel_id: 1,
+      user_identity: DEMO_SETUP.translator!.user_identity,
+      nominal_hashrate: totals.hashrate,
+      target_hex: TARGET_HEX,
+      requested_max_target_hex: TARGET_HEX,
+      expected_shares_per_minute: SHARES_PER_MINUTE * sim.miners().length,
+      extranonce_prefix_hex: '0000000100000001',
+      full_extranonce_size: 8,
+      rollable_extranonce_size: 4,
+      best_diff: totals.bestDiff,
+      blocks_found: 0,
+      shares_accepted: totals.accepted,
+      shares_rejected: totals.rejected,
+      shares_rejected_by_reason: totals.rejectedByReason,
+      share_work_sum: totals.accepted * 2 ** 32,
+      share_batch_size: 1,
+      last_batch_accepted: 1,
+      last_batch_work_sum: 2 ** 32,
+      last_share_sequence_number: totals.accepted + totals.rejected,
+      stable_hashrate: true,
+    };
+  };
+  const isTranslator = (id: string) => Number(id) === 1;
+
+  router.get('/global', (_req, res) => {
+    const { hashrate } = sim.totals();
+    res.json({
+      server: { extended_channels: 1, standard_channels: 0, total_channels: 1, total_hashrate: hashrate },
+      sv1_clients: null,
+      sv2_clients: { total_clients: 1, extended_channels: 1, standard_channels: 0, total_channels: 1, total_hashrate: hashrate },
+      uptime_secs: sim.uptimeSecs(),
+    });
+  });
+  router.get('/server', (_req, res) => {
+    res.json({ extended_channels_count: 1, standard_channels_count: 0, total_hashrate: sim.totals().hashrate });
+  });
+  router.get('/server/channels', (req, res) => {
+    const { items, offset, limit } = page([upstreamChannel(sim, DEMO_SETUP.jdc!.user_identity)], req.query);
+    res.json({ extended_channels: items, standard_channels: [], offset, limit, total_extended: 1, total_standard: 0 });
+  });
+  router.get('/clients', (req, res) => {
+    const metadata = { client_id: 1, extended_channels_count: 1, standard_channels_count: 0, total_hashrate: sim.totals().hashrate };
+    res.json(page([metadata], req.query));
+  });
+  router.get('/clients/:id', (req, res) => {
+    if (!isTranslator(req.params.id)) return res.status(404).json({ error: 'Client not found' });
+    res.json({ client_id: 1, extended_channels_count: 1, standard_channels_count: 0, total_hashrate: sim.totals().hashrate });
+  });
+  router.get('/clients/:id/channels', (req, res) => {
+    if (!isTranslator(req.params.id)) return res.status(404).json({ error: 'Client not found' });
+    const { items, offset, limit } = page([translatorChannel()], req.query);
+    res.json({ client_id: 1, extended_channels: items, standard_channels: [], offset, limit, total_extended: 1, total_standard: 0 });
+  });
+
+  return router;
+}
+
+function createMonitoringApp(routes: Router): express.Express {
+  const app = express();
+  app.get('/api/v1/health', (_req, res) => {
+    res.json({ status: 'ok', timestamp: Math.floor(Date.now() / 1000) });
+  });
+  app.use('/api/v1', routes);
+  app.use((_req, res) => {
+    res.status(404).json({ error: 'Not found' });
+  });
+  return app;
+}
+
+export interface LoopbackServers {
+  // Keyed by the container name each server stands in for (see setContainerOrigins).
+  origins: Record<string, string>;
+  close: () => Promise<void>;
+}
+
+/**
+ * Serve a stand-in for each monitoring API on a loopback port.
+ */
+export async function serveOnLoopback(apps: Record<MonitoringService, http.RequestListener>): Promise<LoopbackServers> {
+  const servers: http.Server[] = [];
+  const origins: Record<string, string> = {};
+
+  for (const service of Object.keys(apps) as MonitoringService[]) {
+    const server = http.createServer(apps[service]);
+    await new Promise<void>((resolve, reject) => {
+      server.once('error', reject);
+      server.listen(0, '127.0.0.1', resolve);
+    });
+    servers.push(server);
+    const { port } = server.address() as AddressInfo;
+    origins[MONITORING_SERVICES[service].containerName] = `http://127.0.0.1:${port}`;
+  }
+
+  return {
+    origins,
+    close: async () => {
+      await Promise.all(servers.map((server) => new Promise<void>((resolve) => server.close(() => resolve()))));
+    },
+  };
+}
+
+/**
+ * Serve the simulated monitoring APIs on loopback.
+ */
+export function startDemoServers(sim: DemoSimulation): Promise<LoopbackServers> {
+  return serveOnLoopback({
+    translator: createMonitoringApp(createTranslatorApi(sim)),
+    jdc: createMonitoringApp(createJdcApi(sim)),
+  });
+}
+
+/**
+ * An in-memory state store holding the demo setup, so demo mode never touches
+ * the real state.json.
+ */
+export function createDemoStateStore(setup: SetupData = DEMO_SETUP, { onChange }: StateStoreOptions = {}): StateStore {
+  let state: SavedState = {
+    configured: true,
+    miningMode: setup.miningMode,
+    mode: setup.mode,
+    data: setup,
+    shouldBeRunning: true,
+  };
+
+  return {
+    load: async () => state,
+    save: async (data: SetupData, shouldBeRunning = true) => {
+      state = { configured: true, miningMode: data.miningMode, mode: data.mode, data, shouldBeRunning };
+      onChange?.();
+    },
+    clear: async () => {
+      state = getDefaultState();
+      onChange?.();
+    },
+  };
+}
+
+/**
+ * Container statuses for the simulated stack: every service it runs is healthy.
+ */
+export function getDemoStackStatus(mode: SavedState['mode']): StatusResponse['containers'] {
+  const status = (name: string): ContainerStatus => ({ id: `demo-${name}`, name, status: 'healthy', ports: {} });
+  return {
+    translator: status(CONTAINER_NAMES.translator),
+    jdc: mode === 'jd' ? status(CONTAINER_NAMES.jdc) : null,
+  };
+}
+
+/**
+ * Refuse requests that would start, stop or reconfigure containers, so a demo
+ * never touches a real stack running on the same Docker host.
+ */
+export function rejectStackControl(mode = 'demo mode'): RequestHandler {
+  return (req, res, next) => {
+    if (req.method === 'GET' || !STACK_CONTROL_PATHS.test(req.path)) return next();
+    res.status(409).json({ success: false, error: `Not available in ${mode}` });
+  };
+}
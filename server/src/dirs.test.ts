@@ -0,0 +1,40 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { ensureWritableDir, getPlatformConfigDir, parseDataDirArg, resolveConfigDir } from './dirs.js';
+
+test('uses each platform\'s per-user config directory', () => {
+  assert.equal(getPlatformConfigDir('linux', {}, '/home/miner'), '/home/miner/.config/sv2-ui');
+  assert.equal(getPlatformConfigDir('linux', { XDG_CONFIG_HOME: '/xdg' }, '/home/miner'), '/xdg/sv2-ui');
+  assert.equal(getPlatformConfigDir('linux', { XDG_CONFIG_HOME: 'relative' }, '/home/miner'), '/home/miner/.config/sv2-ui');
+  assert.equal(getPlatformConfigDir('darwin', {}, '/Users/miner'), '/Users/miner/Library/Application Support/sv2-ui');
+  assert.equal(getPlatformConfigDir('win32', { APPDATA: 'C:\\Users\\miner\\AppData\\Roaming' }, 'C:\\Users\\miner'), 'C:\\Users\\miner\\AppData\\Roaming\\sv2-ui');
+});
+
+test('parses --data-dir in both forms', () => {
+  assert.equal(parseDataDirArg([]), null);
+  assert.equal(parseDataDirArg(['--data-dir', '/srv/sv2']), '/srv/sv2');
+  assert.equal(parseDataDirArg(['--data-dir=/srv/sv2']), '/srv/sv2');
+  assert.throws(() => parseDataDirArg(['--data-dir']), /requires a path/);
+  assert.throws(() => parseDataDirArg(['--data-dir', '--other']), /requires a path/);
+});
+
+test('--data-dir wins over CONFIG_DIR, which wins over an existing legacy directory', () => {
+  const options = { platform: 'linux' as const, home: '/home/miner', legacyDir: '/app/data/config' };
+  assert.equal(resolveConfigDir({ ...options, argv: ['--data-dir', '/srv/sv2'], env: { CONFIG_DIR: '/etc/sv2' } }), '/srv/sv2');
+  assert.equal(resolveConfigDir({ ...options, argv: [], env: { CONFIG_DIR: '/etc/sv2' } }), '/etc/sv2');
+  assert.equal(resolveConfigDir({ ...options, argv: [], env: {}, exists: () => true }), '/app/data/config');
+  assert.equal(resolveConfigDir({ ...options, argv: [], env: {}, exists: () => false }), '/home/miner/.config/sv2-ui');
+});
+
+test('creates the directory and rejects ones it cannot write to', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-dirs-'));
+  await ensureWritableDir(path.join(dir, 'nested', 'config'));
+  assert.ok((await fs.stat(path.join(dir, 'nested', 'config'))).isDirectory());
+
+  // A file where the directory should be.
+  await fs.writeFile(path.join(dir, 'taken'), '');
+  await assert.rejects(ensureWritableDir(path.join(dir, 'taken', 'config')), /is not writable/);
+});
@@ -0,0 +1,97 @@
+/**
+ * Where sv2-ui keeps its state when CONFIG_DIR isn't set.
+ *
+ * Everything the server writes (setup state, generated TOML, event and audit
+ * logs, stores) lives in one directory. It is, in order:
+ *
+ * 1. `--data-dir <path>` on the command line
+ * 2. CONFIG_DIR
+ * 3. `data/config` in the checkout, when an earlier version already created it
+ * 4. the platform's per-user config directory: `$XDG_CONFIG_HOME/sv2-ui`
+ *    (`~/.config/sv2-ui`) on Linux, `~/Library/Application Support/sv2-ui`
+ *    on macOS and `%APPDATA%\sv2-ui` on Windows
+ *
+ * The directory is created if needed and checked for write access at startup,
+ * so a read-only location fails fast instead of on the first save.
+ */
+
+import { constants, existsSync } from 'fs';
+import fs from 'fs/promises';
+import os from 'os';
+import path from 'path';
+
+export const APP_DIR_NAME = 'sv2-ui';
+
+export interface ConfigDirOptions {
+  argv?: string[];
+  env?: NodeJS.ProcessEnv;
+  platform?: NodeJS.Platform;
+  home?: string;
+  // The pre-existing `data/config` directory next to the server.
+  legacyDir?: string;
+  exists?: (dir: string) => boolean;
+}
+
+export function getPlatformConfigDir(
+  platform: NodeJS.Platform = process.platform,
+  env: NodeJS.ProcessEnv = process.env,
+  home: string = os.homedir(),
+): string {
+  if (platform === 'win32') {
+    return path.win32.join(env.APPDATA || path.win32.join(home, 'AppData', 'Roaming'), APP_DIR_NAME);
+  }
+  if (platform === 'darwin') {
+    return path.posix.join(home, 'Library', 'Application Support', APP_DIR_NAME);
+  }
+  // XDG_CONFIG_HOME must be absolute to count (XDG Base Directory spec).
+  const xdg = env.XDG_CONFIG_HOME;
+  return path.posix.join(xdg && path.posix.isAbsolute(xdg) ? xdg : path.posix.join(home, '.config'), APP_DIR_NAME);
+}
+
+/**
+ * The value of `--data-dir <path>` or `--data-dir=<path>`, if given.
+ */
+export function parseDataDirArg(argv: string[] = process.argv.slice(2)): string | null {
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i];
+    if (arg.startsWith('--data-dir=')) {
+      const value = arg.slice('--data-dir='.length).trim();
+      if (!value) throw new Error('--data-dir requires a path');
+      return value;
+    }
+    if (arg === '--data-dir') {
+      const value = argv[i + 1]?.trim();
+      if (!value || value.startsWith('--')) throw new Error('--data-dir requires a path');
+      return value;
+    }
+  }
+  return null;
+}
+
+export function resolveConfigDir({
+  argv,
+  env = process.env,
+  platform,
+  home,
+  legacyDir,
+  exists = existsSync,
+}: ConfigDirOptions = {}): string {
+  const fromArg = parseDataDirArg(argv);
+  if (fromArg) return path.resolve(fromArg);
+  if (env.CONFIG_DIR) return env.CONFIG_DIR;
+  if (legacyDir && exists(legacyDir)) return legacyDir;
+  return getPlatformConfigDir(platform, env, home);
+}
+
+/**
+ * Create `dir` if it doesn't exist and make sure the server can write to it.
+ */
+export async function ensureWritableDir(dir: string): Promise<void> {
+  try {
+    await fs.mkdir(dir, { recursive: true });
+    await fs.access(dir, constants.W_OK);
+  } catch (error) {
+    const reason = error instanceof Error ? error.message : String(error);
+    throw new Error(`Config directory ${dir} is not writable (${reason}). Set CONFIG_DIR or --data-dir to a writable path.`);
+  }
+}
@@ -0,0 +1,75 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import { test } from 'node:test';
+import type { AlertTransition } from './alerts.js';
+import { createDiscordNotifier, formatAlertEmbed, getAlertService, parseDiscordConfig } from './discord.js';
+
+const WEBHOOK = 'https://discord.com/api/webhooks/123/abc';
+
+const alert: AlertTransition = {
+  id: 'a1',
+  ruleId: 'low-hashrate',
+  kind: 'hashrate-below',
+  status: 'firing',
+  message: 'Hashrate 50.00 TH/s below 100.00 TH/s',
+  at: '2026-01-01T00:00:00.000Z',
+  threshold: 100e12,
+  value: 50e12,
+};
+
+test('parses Discord webhook URLs', () => {
+  assert.equal(parseDiscordConfig({}), null);
+  assert.deepEqual(parseDiscordConfig({ DISCORD_WEBHOOK_URLS: `${WEBHOOK}, ${WEBHOOK}2` }), { urls: [WEBHOOK, `${WEBHOOK}2`] });
+  assert.throws(() => parseDiscordConfig({ DISCORD_WEBHOOK_URLS: 'https://example.com/hook' }), /Discord webhook URLs/);
+  assert.throws(() => parseDiscordConfig({ DISCORD_WEBHOOK_URLS: 'http://discord.com/api/webhooks/1/a' }), /Discord webhook URLs/);
+});
+
+test('formats alerts as embeds with service, metric, threshold and value', () => {
+  const embed = formatAlertEmbed(alert, { poolName: 'Demo', network: 'testnet4', mode: 'jd' });
+  assert.equal(embed.title, '🚨 Alert');
+  assert.equal(embed.color, 0xe74c3c);
+  assert.deepEqual(embed.fields.map(({ name, value }) => [name, value]), [
+    ['Service', 'JDC'],
+    ['Metric', 'Hashrate'],
+    ['Threshold', '100.00 TH/s'],
+    ['Current value', '50.00 TH/s'],
+  ]);
+  assert.deepEqual(embed.footer, { text: 'Demo · Testnet4' });
+});
+
+test('attributes share anomalies to the service that reported them', () => {
+  const anomaly = { ...alert, kind: 'shared-extranonce' as const, threshold: undefined, value: undefined };
+  assert.equal(getAlertService({ ...anomaly, ruleId: 'shared-extranonce:sv1:abcd' }, 'jd'), 'translator');
+  assert.equal(getAlertService({ ...anomaly, ruleId: 'duplicate-shares:jdc:client:1:2' }, 'no-jd'), 'jdc');
+  assert.equal(getAlertService(alert, 'no-jd'), 'translator');
+  assert.equal(formatAlertEmbed(anomaly).fields.length, 2);
+});
+
+test('retries rate-limited posts', async (t) => {
+  const bodies: Array<{ embeds: unknown[] }> = [];
+  const server = http.createServer((req, res) => {
+    let body = '';
+    req.on('data', (chunk) => { body += chunk; });
+    req.on('end', () => {
+      bodies.push(JSON.parse(body));
+      res.setHeader('Content-Type', 'application/json');
+      res.statusCode = bodies.length === 1 ? 429 : 204;
+      res.end(bodies.length === 1 ? JSON.stringify({ message: 'You are being rate limited.', retry_after: 0.25 }) : undefined);
+    });
+  });
+  server.listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+
+  const delays: number[] = [];
+  const notifier = createDiscordNotifier(
+    { urls: [`http://127.0.0.1:${port}/api/webhooks/1/a`] },
+    { sleep: async (ms) => { delays.push(ms); } },
+  );
+  await notifier.notifyAlert(alert);
+
+  assert.equal(bodies.length, 2);
+  assert.equal(bodies[1].embeds.length, 1);
+  assert.deepEqual(delays, [250]);
+});
@@ -0,0 +1,162 @@
+/**
+ * Discord notifications for alert transitions and blocks found, posted as
+ * embeds to the channel webhooks in DISCORD_WEBHOOK_URLS (comma-separated).
+ *
+ * Create a webhook under Server Settings → Integrations → Webhooks and copy
+ * its URL.
+ */
+
+import type { AlertTransition } from './alerts.js';
+import type { StackEvent } from './events.js';
+import { getUpstreamService, MONITORING_SERVICES } from './monitoring.js';
+import type { MonitoringService } from './monitoring.js';
+import { DEFAULT_MESSAGES, formatAlertText, formatAlertValue, formatEventText } from './notification-messages.js';
+import type { Messages } from './notification-messages.js';
+import { getTestNetworkLabel } from './summary.js';
+import type { NotificationContext } from './webhooks.js';
+
+export interface DiscordConfig {
+  urls: string[];
+}
+
+export interface DiscordEmbed {
+  title: string;
+  description: string;
+  color: number;
+  timestamp: string;
+  fields: Array<{ name: string; value: string; inline: boolean }>;
+  footer?: { text: string };
+}
+
+export interface DiscordNotifierDeps {
+  sleep?: (ms: number) => Promise<void>;
+  messages?: Messages;
+}
+
+const SEND_TIMEOUT_MS = 10_000;
+const MAX_ATTEMPTS = 3;
+const COLORS = { firing: 0xe74c3c, resolved: 0x2ecc71, event: 0xf1c40f };
+
+export function parseDiscordConfig(env: NodeJS.ProcessEnv = process.env): DiscordConfig | null {
+  const urls = (env.DISCORD_WEBHOOK_URLS ?? '').split(',').map((url) => url.trim()).filter(Boolean);
+  if (urls.length === 0) return null;
+
+  for (const url of urls) {
+    let parsed: URL;
+    try {
+      parsed = new URL(url);
+    } catch {
+      throw new Error(`Invalid DISCORD_WEBHOOK_URLS entry "${url}"`);
+    }
+    if (parsed.protocol !== 'https:' || !parsed.pathname.startsWith('/api/webhooks/')) {
+      throw new Error('DISCORD_WEBHOOK_URLS entries must be Discord webhook URLs (https://…/api/webhooks/…)');
+    }
+  }
+
+  return { urls };
+}
+
+/**
+ * The service an alert is about. Share anomaly keys name the service; rule
+ * alerts concern whichever service owns the pool connection.
+ */
+export function getAlertService(alert: AlertTransition, mode: NotificationContext['mode'] = null): MonitoringService {
+  const [, scope] = alert.ruleId.split(':');
+  if (scope === 'translator' || scope === 'sv1') return 'translator';
+  if (scope === 'jdc' || scope === 'sv2') return 'jdc';
+  return getUpstreamService(mode ?? null);
+}
+
+function getFooter({ poolName, network }: NotificationContext): { text: string } | undefined {
+  const text = [poolName, getTestNetworkLabel(network)].filter(Boolean).join(' · ');
+  return text ? { text } : undefined;
+}
+
+export function formatAlertEmbed(
+  alert: AlertTransition,
+  context: NotificationContext = {},
+  messages: Messages = DEFAULT_MESSAGES,
+): DiscordEmbed {
+  const firing = alert.status === 'firing';
+  const fields = [
+    { name: messages.t('field.service'), value: MONITORING_SERVICES[getAlertService(alert, context.mode)].label, inline: true },
+    { name: messages.t('field.metric'), value: messages.t(`metric.${alert.kind}`), inline: true },
+  ];
+  if (alert.threshold != null) {
+    fields.push({ name: messages.t('field.threshold'), value: formatAlertValue(alert.kind, alert.threshold, messages), inline: true });
+  }
+  if (alert.value != null) {
+    fields.push({ name: messages.t('field.currentValue'), value: formatAlertValue(alert.kind, alert.value, messages), inline: true });
+  }
+
+  const footer = getFooter(context);
+  return {
+    title: firing ? `🚨 ${messages.t('alert.firing')}` : `✅ ${messages.t('alert.resolved')}`,
+    description: formatAlertText(alert, messages),
+    color: firing ? COLORS.firing : COLORS.resolved,
+    timestamp: alert.at,
+    fields,
+    ...(footer ? { footer } : {}),
+  };
+}
+
+export function formatEventEmbed(
+  event: StackEvent,
+  context: NotificationContext = {},
+  messages: Messages = DEFAULT_MESSAGES,
+): DiscordEmbed {
+  const footer = getFooter(context);
+  const { title, detail } = formatEventText(event, messages);
+  return {
+    title: `🎉 ${title}`,
+    description: detail,
+    color: COLORS.event,
+    timestamp: event.at,
+    fields: [],
+    ...(footer ? { footer } : {}),
+  };
+}
+
+export function createDiscordNotifier(config: DiscordConfig, deps: DiscordNotifierDeps = {}) {
+  const sleep = deps.sleep ?? ((ms: number) => new Promise<void>((resolve) => setTimeout(resolve, ms)));
+  const messages = deps.messages ?? DEFAULT_MESSAGES;
+
+  async function sendTo(url: string, embed: DiscordEmbed): Promise<void> {
+    for (let attempt = 1; ; attempt++) {
+      const response = await fetch(url, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ username: 'SV2 UI', embeds: [embed], allowed_mentions: { parse: [] } }),
+        signal: AbortSignal.timeout(SEND_TIMEOUT_MS),
+      });
+      if (response.ok) return;
+
+      const body = await response.json().catch(() => ({})) as { message?: string; retry_after?: number };
+      // Discord rate limits per webhook and says how long to back off, in seconds.
+      if (response.status === 429 && attempt < MAX_ATTEMPTS) {
+        await sleep(Math.ceil((body.retry_after ?? 1) * 1000));
+        continue;
+      }
+      // The webhook token is part of the URL, so leave it out.
+      throw new Error(`Discord webhook failed (HTTP ${response.status}): ${body.message ?? 'unknown error'}`);
+    }
+  }
+
+  /**
+   * Post `embed` to every configured webhook. Rejects with the first failure
+   * after attempting all of them.
+   */
+  async function send(embed: DiscordEmbed): Promise<void> {
+    const results = await Promise.allSettled(config.urls.map((url) => sendTo(url, embed)));
+    const failure = results.find((result): result is PromiseRejectedResult => result.status === 'rejected');
+    if (failure) throw failure.reason;
+  }
+
+  return {
+    send,
+    notifyAlert: (alert: AlertTransition, context?: NotificationContext) => send(formatAlertEmbed(alert, context, messages)),
+    notifyEvent: (event: StackEvent, context?: NotificationContext) => send(formatEventEmbed(event, context, messages)),
+  };
+}
+
+export type DiscordNotifier = ReturnType<typeof createDiscordNotifier>;
@@ -0,0 +1,113 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import {
+  decodeMdnsPacket,
+  encodeMdnsQuery,
+  getDiscoveredServices,
+  getUnconfiguredServices,
+  groupDiscoveredStacks,
+  MDNS_SERVICE_TYPE,
+  parseDiscoveryMode,
+} from './discovery.js';
+import type { MdnsRecord } from './discovery.js';
+
+function name(value: string): Buffer {
+  return Buffer.concat([
+    ...value.split('.').map((label) => Buffer.concat([Buffer.from([label.length]), Buffer.from(label)])),
+    Buffer.from([0]),
+  ]);
+}
+
+function record(owner: Buffer, type: number, data: Buffer): Buffer {
+  const fixed = Buffer.alloc(10);
+  fixed.writeUInt16BE(type, 0);
+  fixed.writeUInt16BE(1, 2);
+  fixed.writeUInt32BE(120, 4);
+  fixed.writeUInt16BE(data.length, 8);
+  return Buffer.concat([owner, fixed, data]);
+}
+
+function srv(port: number, target: string): Buffer {
+  const fixed = Buffer.alloc(6);
+  fixed.writeUInt16BE(port, 4);
+  return Buffer.concat([fixed, name(target)]);
+}
+
+function txt(...entries: string[]): Buffer {
+  return Buffer.concat(entries.map((entry) => Buffer.concat([Buffer.from([entry.length]), Buffer.from(entry)])));
+}
+
+function response(records: Buffer[]): Buffer {
+  const header = Buffer.alloc(12);
+  header.writeUInt16BE(0x8400, 2);
+  header.writeUInt16BE(records.length, 6);
+  return Buffer.concat([header, ...records]);
+}
+
+test('parseDiscoveryMode is off unless set', () => {
+  assert.equal(parseDiscoveryMode(undefined), null);
+  assert.equal(parseDiscoveryMode('off'), null);
+  assert.equal(parseDiscoveryMode('true'), 'browse');
+  assert.equal(parseDiscoveryMode('register'), 'register');
+  assert.throws(() => parseDiscoveryMode('always'), /MDNS_DISCOVERY must be/);
+});
+
+test('queries and decodes mDNS records, following compression pointers', () => {
+  const query = encodeMdnsQuery(MDNS_SERVICE_TYPE);
+  assert.equal(query.readUInt16BE(4), 1);
+  assert.ok(query.includes(Buffer.from('_sv2-monitoring')));
+
+  // The SRV owner name points back at the PTR's owner name at offset 12.
+  const ptrOwner = name(MDNS_SERVICE_TYPE);
+  const instance = Buffer.concat([Buffer.from([7]), Buffer.from('rack2-t'), Buffer.from([0xc0, 12])]);
+  const packet = response([
+    record(ptrOwner, 12, instance),
+    record(Buffer.concat([Buffer.from([7]), Buffer.from('rack2-t'), Buffer.from([0xc0, 12])]), 33, srv(9092, 'rack2.local')),
+    record(name('rack2.local'), 1, Buffer.from([192, 168, 1, 20])),
+    record(name('rack2.local'), 99, Buffer.from([1, 2, 3])),
+  ]);
+
+  assert.deepEqual(decodeMdnsPacket(packet), [
+    { type: 'PTR', name: MDNS_SERVICE_TYPE, target: `rack2-t.${MDNS_SERVICE_TYPE}` },
+    { type: 'SRV', name: `rack2-t.${MDNS_SERVICE_TYPE}`, port: 9092, target: 'rack2.local' },
+    { type: 'A', name: 'rack2.local', address: '192.168.1.20' },
+  ]);
+  assert.throws(() => decodeMdnsPacket(packet.subarray(0, packet.length - 2)), /Truncated|out of range/);
+});
+
+test('assembles services and groups them into stacks per host', () => {
+  const records: MdnsRecord[] = [
+    ...decodeMdnsPacket(response([
+      record(name(MDNS_SERVICE_TYPE), 12, name(`t.${MDNS_SERVICE_TYPE}`)),
+      record(name(`t.${MDNS_SERVICE_TYPE}`), 33, srv(9092, 'rack2.local')),
+      record(name(`t.${MDNS_SERVICE_TYPE}`), 16, txt('network=testnet4', 'mining=solo')),
+      record(name(MDNS_SERVICE_TYPE), 12, name(`j.${MDNS_SERVICE_TYPE}`)),
+      record(name(`j.${MDNS_SERVICE_TYPE}`), 33, srv(8100, 'rack2.local')),
+      record(name(`j.${MDNS_SERVICE_TYPE}`), 16, txt('role=jdc', 'path=/monitoring/')),
+      record(name('rack2.local'), 1, Buffer.from([192, 168, 1, 20])),
+      // No role and not on a well-known port.
+      record(name(MDNS_SERVICE_TYPE), 12, name(`x.${MDNS_SERVICE_TYPE}`)),
+      record(name(`x.${MDNS_SERVICE_TYPE}`), 33, srv(1234, 'other.local')),
+    ])),
+  ];
+
+  const services = getDiscoveredServices(records);
+  assert.deepEqual(services.map(({ role, url }) => ({ role, url })), [
+    { role: 'translator', url: 'http://192.168.1.20:9092/api/v1' },
+    { role: 'jdc', url: 'http://192.168.1.20:8100/monitoring' },
+  ]);
+
+  assert.deepEqual(groupDiscoveredStacks(services), [{
+    id: 'rack2',
+    name: 'rack2',
+    network: 'testnet4',
+    mode: 'jd',
+    miningMode: 'solo',
+    translatorUrl: 'http://192.168.1.20:9092/api/v1',
+    jdcUrl: 'http://192.168.1.20:8100/monitoring',
+  }]);
+
+  const [stack] = groupDiscoveredStacks(services);
+  assert.deepEqual(getUnconfiguredServices(services, [stack]), []);
+  assert.equal(getUnconfiguredServices(services, []).length, 2);
+});
@@ -0,0 +1,413 @@
+/**
+ * mDNS/zeroconf discovery of Translator and JDC monitoring APIs on the LAN.
+ *
+ * With MDNS_DISCOVERY set, the server sends a one-shot mDNS query for
+ * `_sv2-monitoring._tcp.local` on startup and then every few minutes. A
+ * service's role comes from its `role=translator|jdc` TXT entry, or from the
+ * port when it runs on the well-known monitoring port. Optional TXT entries
+ * `path` (default /api/v1), `network` and `mining` fill in the rest.
+ *
+ * Discovered services that no remote stack uses yet are listed at
+ * /api/v1/discovered, grouped by host into ready-made stack entries. With
+ * MDNS_DISCOVERY=register, those entries are added to the stack registry
+ * automatically.
+ */
+
+import dgram from 'dgram';
+import {
+  JDC_MONITORING_PORT,
+  SUPPORTED_NETWORKS,
+  TRANSLATOR_MONITORING_PORT,
+} from '@sv2-ui/shared';
+import type { BitcoinNetwork } from '@sv2-ui/shared';
+import { createLogger } from './logger.js';
+import type { MonitoringService } from './monitoring.js';
+import type { RemoteStack } from './stacks.js';
+
+const log = createLogger('discovery');
+
+export type DiscoveryMode = 'browse' | 'register';
+
+export const MDNS_SERVICE_TYPE = '_sv2-monitoring._tcp.local';
+
+export const MDNS_ADDRESS = '224.0.0.251';
+export const MDNS_PORT = 5353;
+const BROWSE_TIMEOUT_MS = 2000;
+export const DISCOVERY_INTERVAL_MS = 5 * 60_000;
+
+const TYPE_A = 1;
+const TYPE_PTR = 12;
+const TYPE_TXT = 16;
+const TYPE_AAAA = 28;
+const TYPE_SRV = 33;
+const TYPE_ANY = 255;
+const CLASS_IN = 1;
+// Set on the records only one responder owns (RFC 6762 section 10.2).
+const CACHE_FLUSH = 0x8000;
+
+const RECORD_TYPES: Record<MdnsRecord['type'], number> = {
+  A: TYPE_A,
+  PTR: TYPE_PTR,
+  TXT: TYPE_TXT,
+  AAAA: TYPE_AAAA,
+  SRV: TYPE_SRV,
+};
+
+export type MdnsRecord =
+  | { type: 'PTR'; name: string; target: string }
+  | { type: 'SRV'; name: string; port: number; target: string }
+  | { type: 'TXT'; name: string; entries: Record<string, string> }
+  | { type: 'A' | 'AAAA'; name: string; address: string };
+
+export interface DiscoveredService {
+  instance: string;
+  role: MonitoringService;
+  host: string;
+  address: string | null;
+  port: number;
+  // Monitoring API base URL, ending in /api/v1.
+  url: string;
+  network: BitcoinNetwork | null;
+  miningMode: 'pool' | 'solo' | null;
+}
+
+export function parseDiscoveryMode(value: string | undefined = process.env.MDNS_DISCOVERY): DiscoveryMode | null {
+  const mode = value?.trim().toLowerCase();
+  if (!mode || mode === 'off' || mode === 'false') return null;
+  if (mode === 'true' || mode === 'browse') return 'browse';
+  if (mode === 'register') return 'register';
+  throw new Error(`MDNS_DISCOVERY must be off, browse or register, got "${value}"`);
+}
+
+function encodeName(name: string): Buffer {
+  const labels = name.split('.').filter(Boolean).map((label) => {
+    const bytes = Buffer.from(label, 'utf-8');
+    return Buffer.concat([Buffer.from([bytes.length]), bytes]);
+  });
+  return Buffer.concat([...labels, Buffer.from([0])]);
+}
+
+/**
+ * A PTR query for `name`, as a legacy unicast query: sent from an ephemeral
+ * port, so responders answer that port directly.
+ */
+export function encodeMdnsQuery(name: string): Buffer {
+  const header = Buffer.alloc(12);
+  header.writeUInt16BE(1, 4);
+  const question = Buffer.alloc(4);
+  question.writeUInt16BE(TYPE_PTR, 0);
+  question.writeUInt16BE(1, 2);
+  return Buffer.concat([header, encodeName(name), question]);
+}
+
+export interface MdnsQuestion {
+  name: string;
+  // A record type, or 255 for any.
+  type: number;
+}
+
+/**
+ * The questions of an mDNS query. Responses have none.
+ */
+export function decodeMdnsQuestions(packet: Buffer): MdnsQuestion[] {
+  if (packet.readUInt16BE(2) & 0x8000) return [];
+  const questions: MdnsQuestion[] = [];
+  let offset = 12;
+  for (let i = packet.readUInt16BE(4); i > 0; i--) {
+    const { name, next } = readName(packet, offset);
+    questions.push({ name, type: packet.readUInt16BE(next) });
+    offset = next + 4;
+  }
+  return questions;
+}
+
+/**
+ * Whether `record` answers `question`. Names compare case-insensitively.
+ */
+export function answersQuestion(record: MdnsRecord, question: MdnsQuestion): boolean {
+  return record.name.toLowerCase() === question.name.toLowerCase()
+    && (question.type === TYPE_ANY || question.type === RECORD_TYPES[record.type]);
+}
+
+function encodeIpv6(address: string): Buffer {
+  const [head, tail] = address.split('::');
+  const first = head ? head.split(':') : [];
+  const last = tail ? tail.split(':') : [];
+  const groups = [...first, ...new Array(8 - first.length - last.length).fill('0'), ...last];
+  const bytes = Buffer.alloc(16);
+  groups.forEach((group, i) => bytes.writeUInt16BE(parseInt(group, 16), i * 2));
+  return bytes;
+}
+
+function encodeRecordData(record: MdnsRecord): Buffer {
+  switch (record.type) {
+    case 'PTR':
+      return encodeName(record.target);
+    case 'SRV': {
+      const fixed = Buffer.alloc(6);
+      fixed.writeUInt16BE(record.port, 4);
+      return Buffer.concat([fixed, encodeName(record.target)]);
+    }
+    case 'TXT': {
+      const entries = Object.entries(record.entries).map(([key, value]) => Buffer.from(`${key}=${value}`, 'utf-8'));
+      // A TXT record always holds at least one (possibly empty) string.
+      if (entries.length === 0) return Buffer.from([0]);
+      return Buffer.concat(entries.map((entry) => Buffer.concat([Buffer.from([entry.length]), entry])));
+    }
+    case 'A':
+      return Buffer.from(record.address.split('.').map(Number));
+    case 'AAAA':
+      return encodeIpv6(record.address);
+  }
+}
+
+function encodeRecord(record: MdnsRecord, ttlSecs: number): Buffer {
+  const data = encodeRecordData(record);
+  const fixed = Buffer.alloc(10);
+  fixed.writeUInt16BE(RECORD_TYPES[record.type], 0);
+  fixed.writeUInt16BE(record.type === 'PTR' ? CLASS_IN : CLASS_IN | CACHE_FLUSH, 2);
+  fixed.writeUInt32BE(ttlSecs, 4);
+  fixed.writeUInt16BE(data.length, 8);
+  return Buffer.concat([encodeName(record.name), fixed, data]);
+}
+
+/**
+ * An authoritative mDNS response. A TTL of 0 tells caches to drop the
+ * records (a goodbye). `id` is echoed for legacy unicast queries.
+ */
+export function encodeMdnsResponse(
+  answers: MdnsRecord[],
+  { additionals = [], ttlSecs = 120, id = 0 }: { additionals?: MdnsRecord[]; ttlSecs?: number; id?: number } = {},
+): Buffer {
+  const header = Buffer.alloc(12);
+  header.writeUInt16BE(id, 0);
+  header.writeUInt16BE(0x8400, 2);
+  header.writeUInt16BE(answers.length, 6);
+  header.writeUInt16BE(additionals.length, 10);
+  return Buffer.concat([header, ...[...answers, ...additionals].map((record) => encodeRecord(record, ttlSecs))]);
+}
+
+function readName(packet: Buffer, offset: number): { name: string; next: number } {
+  const labels: string[] = [];
+  let position = offset;
+  let next = -1;
+  // Bounds the walk when compression pointers loop.
+  for (let jumps = 0; jumps < 32; jumps++) {
+    const length = packet.readUInt8(position);
+    if (length === 0) {
+      return { name: labels.join('.'), next: next === -1 ? position + 1 : next };
+    }
+    if ((length & 0xc0) === 0xc0) {
+      if (next === -1) next = position + 2;
+      position = packet.readUInt16BE(position) & 0x3fff;
+      continue;
+    }
+    labels.push(packet.toString('utf-8', position + 1, position + 1 + length));
+    position += length + 1;
+    if (position >= packet.length) break;
+  }
+  throw new Error('Malformed DNS name');
+}
+
+function readTxt(packet: Buffer, start: number, end: number): Record<string, string> {
+  const entries: Record<string, string> = {};
+  let position = start;
+  while (position < end) {
+    const length = packet.readUInt8(position);
+    const entry = packet.toString('utf-8', position + 1, position + 1 + length);
+    const separator = entry.indexOf('=');
+    if (separator > 0) entries[entry.slice(0, separator).toLowerCase()] = entry.slice(separator + 1);
+    position += length + 1;
+  }
+  return entries;
+}
+
+/**
+ * The PTR, SRV, TXT, A and AAAA records of an mDNS response. Other records
+ * are skipped. Throws on truncated or malformed packets.
+ */
+export function decodeMdnsPacket(packet: Buffer): MdnsRecord[] {
+  const questions = packet.readUInt16BE(4);
+  const records = packet.readUInt16BE(6) + packet.readUInt16BE(8) + packet.readUInt16BE(10);
+  let offset = 12;
+
+  for (let i = 0; i < questions; i++) {
+    offset = readName(packet, offset).next + 4;
+  }
+
+  const result: MdnsRecord[] = [];
+  for (let i = 0; i < records; i++) {
+    const { name, next } = readName(packet, offset);
+    const type = packet.readUInt16BE(next);
+    const length = packet.readUInt16BE(next + 8);
+    const data = next + 10;
+    if (data + length > packet.length) throw new Error('Truncated DNS record');
+
+    switch (type) {
+      case TYPE_PTR:
+        result.push({ type: 'PTR', name, target: readName(packet, data).name });
+        break;
+      case TYPE_SRV:
+        result.push({ type: 'SRV', name, port: packet.readUInt16BE(data + 4), target: readName(packet, data + 6).name });
+        break;
+      case TYPE_TXT:
+        result.push({ type: 'TXT', name, entries: readTxt(packet, data, data + length) });
+        break;
+      case TYPE_A:
+        result.push({ type: 'A', name, address: [...packet.subarray(data, data + 4)].join('.') });
+        break;
+      case TYPE_AAAA: {
+        const groups = [];
+        for (let j = 0; j < 16; j += 2) groups.push(packet.readUInt16BE(data + j).toString(16));
+        result.push({ type: 'AAAA', name, address: groups.join(':') });
+        break;
+      }
+    }
+    offset = data + length;
+  }
+  return result;
+}
+
+function getRole(entries: Record<string, string>, port: number): MonitoringService | null {
+  const role = entries.role?.toLowerCase();
+  if (role === 'translator' || role === 'jdc') return role;
+  if (port === TRANSLATOR_MONITORING_PORT) return 'translator';
+  if (port === JDC_MONITORING_PORT) return 'jdc';
+  return null;
+}
+
+/**
+ * Assemble services from the records of one or more responses.
+ */
+export function getDiscoveredServices(records: MdnsRecord[]): DiscoveredService[] {
+  const instances = new Set(records
+    .filter((record): record is Extract<MdnsRecord, { type: 'PTR' }> => record.type === 'PTR' && record.name === MDNS_SERVICE_TYPE)
+    .map((record) => record.target));
+
+  const services: DiscoveredService[] = [];
+  for (const instance of instances) {
+    const srv = records.find((record): record is Extract<MdnsRecord, { type: 'SRV' }> => record.type === 'SRV' && record.name === instance);
+    if (!srv) continue;
+    const entries = records.find((record): record is Extract<MdnsRecord, { type: 'TXT' }> => record.type === 'TXT' && record.name === instance)?.entries ?? {};
+    const role = getRole(entries, srv.port);
+    if (!role) continue;
+
+    // Prefer IPv4; Docker's default bridge network has no IPv6 route to the LAN.
+    const addresses = records.filter((record): record is Extract<MdnsRecord, { type: 'A' | 'AAAA' }> =>
+      (record.type === 'A' || record.type === 'AAAA') && record.name === srv.target);
+    const address = addresses.find((record) => record.type === 'A') ?? addresses[0];
+    const hostForUrl = address ? (address.type === 'AAAA' ? `[${address.address}]` : address.address) : srv.target;
+    const apiPath = (entries.path || '/api/v1').replace(/\/+$/, '');
+    const network = SUPPORTED_NETWORKS.find((value) => value === entries.network) ?? null;
+    const mining = entries.mining === 'pool' || entries.mining === 'solo' ? entries.mining : null;
+
+    services.push({
+      instance,
+      role,
+      host: srv.target,
+      address: address?.address ?? null,
+      port: srv.port,
+      url: `http://${hostForUrl}:${srv.port}${apiPath.startsWith('/') ? apiPath : `/${apiPath}`}`,
+      network,
+      miningMode: mining,
+    });
+  }
+  return services;
+}
+
+/**
+ * Stack entries for hosts that run a Translator, JD mode when the same host
+ * also runs a JDC.
+ */
+export function groupDiscoveredStacks(services: DiscoveredService[]): RemoteStack[] {
+  const byHost = new Map<string, DiscoveredService[]>();
+  for (const service of services) {
+    byHost.set(service.host, [...(byHost.get(service.host) ?? []), service]);
+  }
+
+  const stacks: RemoteStack[] = [];
+  for (const [host, hostServices] of byHost) {
+    const translator = hostServices.find((service) => service.role === 'translator');
+    if (!translator) continue;
+    const jdc = hostServices.find((service) => service.role === 'jdc');
+    const name = host.replace(/\.local$/, '');
+    stacks.push({
+      id: name.toLowerCase().replace(/[^a-z0-9-]+/g, '-').replace(/^-+/, '').slice(0, 32) || 'discovered',
+      name,
+      network: translator.network ?? jdc?.network ?? 'mainnet',
+      mode: jdc ? 'jd' : 'no-jd',
+      miningMode: translator.miningMode ?? jdc?.miningMode ?? 'pool',
+      translatorUrl: translator.url,
+      jdcUrl: jdc?.url ?? null,
+    });
+  }
+  return stacks;
+}
+
+/**
+ * Send one query and collect every answer that arrives within `timeoutMs`.
+ */
+export function browseMdns(timeoutMs = BROWSE_TIMEOUT_MS): Promise<MdnsRecord[]> {
+  return new Promise((resolve, reject) => {
+    const socket = dgram.createSocket('udp4');
+    const records: MdnsRecord[] = [];
+    socket.on('message', (packet) => {
+      try {
+        records.push(...decodeMdnsPacket(packet));
+      } catch {
+        // Ignore answers we can't parse.
+      }
+    });
+    socket.on('error', (error) => {
+      socket.close();
+      reject(error);
+    });
+    socket.bind(0, () => {
+      socket.send(encodeMdnsQuery(MDNS_SERVICE_TYPE), MDNS_PORT, MDNS_ADDRESS);
+      setTimeout(() => {
+        socket.close();
+        resolve(records);
+      }, timeoutMs);
+    });
+  });
+}
+
+/**
+ * Services whose URL isn't used by any stack in `configured`.
+ */
+export function getUnconfiguredServices(services: DiscoveredService[], configured: RemoteStack[]): DiscoveredService[] {
+  const urls = new Set(configured.flatMap((stack) => [stack.translatorUrl, stack.jdcUrl]));
+  return services.filter((service) => !urls.has(service.url));
+}
+
+export function createDiscovery(browse: () => Promise<MdnsRecord[]> = () => browseMdns()) {
+  let services: DiscoveredService[] = [];
+  let browsedAt: string | null = null;
+  const listeners = new Set<(services: DiscoveredService[]) => void>();
+
+  async function refresh(): Promise<DiscoveredService[]> {
+    try {
+      services = getDiscoveredServices(await browse());
+      browsedAt = new Date().toISOString();
+    } catch (error) {
+      log.error('mDNS discovery failed:', error);
+      return services;
+    }
+    for (const listener of listeners) listener(services);
+    return services;
+  }
+
+  function onDiscovered(listener: (services: DiscoveredService[]) => void): () => void {
+    listeners.add(listener);
+    return () => listeners.delete(listener);
+  }
+
+  return {
+    refresh,
+    onDiscovered,
+    get: () => ({ browsedAt, services }),
+    size: () => services.length,
+  };
+}
+
+export type Discovery = ReturnType<typeof createDiscovery>;
@@ -0,0 +1,92 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import {
+  createDockerAutoconfig,
+  getContainerRole,
+  getDockerOrigins,
+  MONITORING_PORT_LABEL,
+  parseDockerAutoconfig,
+  ROLE_LABEL,
+} from './docker-autoconfig.js';
+import type { RunningContainer } from './docker.js';
+
+function container(overrides: Partial<RunningContainer>): RunningContainer {
+  return { name: 'c', image: 'alpine', labels: {}, networks: ['bridge'], ports: [], ...overrides };
+}
+
+test('parseDockerAutoconfig is off unless set', () => {
+  assert.equal(parseDockerAutoconfig(undefined), false);
+  assert.equal(parseDockerAutoconfig('true'), true);
+  assert.throws(() => parseDockerAutoconfig('maybe'), /DOCKER_AUTOCONFIG must be/);
+});
+
+test('roles come from the label, then from the image', () => {
+  assert.equal(getContainerRole(container({ image: 'stratumv2/translator_sv2:main' })), 'translator');
+  assert.equal(getContainerRole(container({ image: 'registry.local:5000/jd_client_sv2@sha256:abc' })), 'jdc');
+  assert.equal(getContainerRole(container({ labels: { [ROLE_LABEL]: 'JDC' } })), 'jdc');
+  // An explicit label that isn't a role opts the container out.
+  assert.equal(getContainerRole(container({ image: 'stratumv2/translator_sv2:main', labels: { [ROLE_LABEL]: 'pool' } })), null);
+  assert.equal(getContainerRole(container({ image: 'redis:7' })), null);
+});
+
+test('uses the container name on a shared network, and published ports from outside Docker', () => {
+  const translator = container({
+    name: 'stack-translator-1',
+    image: 'stratumv2/translator_sv2:main',
+    networks: ['stack_default'],
+    ports: [{ privatePort: 9092, publicPort: 19092, ip: '0.0.0.0' }],
+  });
+  const jdc = container({
+    name: 'stack-jdc-1',
+    labels: { [ROLE_LABEL]: 'jdc', [MONITORING_PORT_LABEL]: '8200' },
+    networks: ['stack_default'],
+    ports: [{ privatePort: 8200, publicPort: 8200, ip: '127.0.0.1' }],
+  });
+
+  assert.deepEqual(getDockerOrigins([translator, jdc], ['stack_default']), {
+    translator: { container: 'stack-translator-1', origin: 'http://stack-translator-1:9092' },
+    jdc: { container: 'stack-jdc-1', origin: 'http://stack-jdc-1:8200' },
+  });
+  assert.deepEqual(getDockerOrigins([translator, jdc], []), {
+    translator: { container: 'stack-translator-1', origin: 'http://localhost:19092' },
+    jdc: { container: 'stack-jdc-1', origin: 'http://127.0.0.1:8200' },
+  });
+  // Inside Docker on another network, a published port on the host isn't reachable as localhost.
+  assert.deepEqual(getDockerOrigins([translator], ['sv2-network']), {});
+});
+
+test('prefers the container sv2-ui manages when several match', () => {
+  const origins = getDockerOrigins([
+    container({ name: 'other-translator', image: 'stratumv2/translator_sv2:main', networks: ['sv2-network'] }),
+    container({ name: 'sv2-translator', image: 'stratumv2/translator_sv2:main', networks: ['sv2-network'] }),
+  ], ['sv2-network']);
+  assert.equal(origins.translator?.container, 'sv2-translator');
+});
+
+test('applies origins keyed by container name and keeps them when Docker is unreachable', async () => {
+  let containers: RunningContainer[] | null = [
+    container({ name: 'stack-translator-1', image: 'stratumv2/translator_sv2:main', networks: ['net'] }),
+  ];
+  const applied: Record<string, string>[] = [];
+  const autoconfig = createDockerAutoconfig({
+    listContainers: async () => {
+      if (!containers) throw new Error('Docker is not reachable');
+      return containers;
+    },
+    getOwnNetworks: async () => ['net'],
+    watch: async () => () => {},
+    apply: (origins) => applied.push(origins),
+  });
+
+  await autoconfig.refresh();
+  assert.deepEqual(applied, [{ 'sv2-translator': 'http://stack-translator-1:9092' }]);
+
+  containers = null;
+  await autoconfig.refresh();
+  assert.equal(applied.length, 1);
+  assert.equal(autoconfig.get().translator?.origin, 'http://stack-translator-1:9092');
+
+  containers = [];
+  await autoconfig.refresh();
+  assert.deepEqual(applied[1], {});
+});
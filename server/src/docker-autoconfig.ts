@@ -0,0 +1,154 @@
+/**
+ * Docker-based auto-configuration of the local monitoring API URLs.
+ *
+ * With DOCKER_AUTOCONFIG set, the server inspects the running containers on
+ * startup and whenever one starts, stops or restarts. A container is taken to
+ * be the Translator or JDC when it carries an `org.stratumv2.role` label, or
+ * else when it runs a translator_sv2 or jd_client_sv2 image. Its monitoring
+ * port comes from `org.stratumv2.monitoring-port`, defaulting to the
+ * well-known port.
+ *
+ * The URL uses the container name when sv2-ui shares a Docker network with
+ * it, and the published port on localhost when sv2-ui runs outside Docker.
+ * The proxies, poller and health checks then follow these URLs instead of
+ * the containers sv2-ui starts itself.
+ */
+
+import { createLogger } from './logger.js';
+import { MONITORING_SERVICES } from './monitoring.js';
+import type { MonitoringService } from './monitoring.js';
+import type { RunningContainer } from './docker.js';
+
+const log = createLogger('docker-autoconfig');
+
+export const ROLE_LABEL = 'org.stratumv2.role';
+export const MONITORING_PORT_LABEL = 'org.stratumv2.monitoring-port';
+
+// Container restarts come in bursts of events; inspect once they settle.
+const REFRESH_DEBOUNCE_MS = 1000;
+
+const ROLE_IMAGES: Record<MonitoringService, string> = {
+  translator: 'translator_sv2',
+  jdc: 'jd_client_sv2',
+};
+
+export type DockerOrigins = Partial<Record<MonitoringService, { container: string; origin: string }>>;
+
+export function parseDockerAutoconfig(value: string | undefined = process.env.DOCKER_AUTOCONFIG): boolean {
+  const enabled = value?.trim().toLowerCase();
+  if (!enabled || enabled === 'false' || enabled === 'off') return false;
+  if (enabled === 'true' || enabled === 'on') return true;
+  throw new Error(`DOCKER_AUTOCONFIG must be true or false, got "${value}"`);
+}
+
+export function getContainerRole(container: RunningContainer): MonitoringService | null {
+  const role = container.labels[ROLE_LABEL]?.trim().toLowerCase();
+  if (role === 'translator' || role === 'jdc') return role;
+  if (role) return null;
+
+  // e.g. stratumv2/translator_sv2:main
+  const repository = container.image.split('@')[0].replace(/:[^/]*$/, '').split('/').pop();
+  return (Object.keys(ROLE_IMAGES) as MonitoringService[]).find((service) => ROLE_IMAGES[service] === repository) ?? null;
+}
+
+/**
+ * Where sv2-ui can reach the container's monitoring API, or null if it can't.
+ * `ownNetworks` is empty when sv2-ui itself runs outside Docker.
+ */
+export function getContainerOrigin(container: RunningContainer, role: MonitoringService, ownNetworks: string[]): string | null {
+  const labelled = Number(container.labels[MONITORING_PORT_LABEL]);
+  const port = Number.isInteger(labelled) && labelled > 0 && labelled < 65536 ? labelled : MONITORING_SERVICES[role].port;
+
+  if (container.networks.some((network) => ownNetworks.includes(network))) {
+    return `http://${container.name}:${port}`;
+  }
+  if (ownNetworks.length > 0) return null;
+
+  const published = container.ports.find((entry) => entry.privatePort === port && entry.publicPort);
+  if (!published) return null;
+  const host = !published.ip || published.ip === '0.0.0.0' || published.ip === '::' ? 'localhost' : published.ip;
+  return `http://${host.includes(':') ? `[${host}]` : host}:${published.publicPort}`;
+}
+
+/**
+ * The Translator and JDC among `containers`. When several match a role, the
+ * container sv2-ui manages itself wins, then the first one listed.
+ */
+export function getDockerOrigins(containers: RunningContainer[], ownNetworks: string[]): DockerOrigins {
+  const origins: DockerOrigins = {};
+  for (const service of Object.keys(MONITORING_SERVICES) as MonitoringService[]) {
+    const candidates = containers
+      .filter((container) => getContainerRole(container) === service)
+      .sort((a, b) => Number(b.name === MONITORING_SERVICES[service].containerName) - Number(a.name === MONITORING_SERVICES[service].containerName));
+    for (const container of candidates) {
+      const origin = getContainerOrigin(container, service, ownNetworks);
+      if (origin) {
+        origins[service] = { container: container.name, origin };
+        break;
+      }
+    }
+  }
+  return origins;
+}
+
+export interface DockerAutoconfigDeps {
+  listContainers: () => Promise<RunningContainer[]>;
+  getOwnNetworks: () => Promise<string[]>;
+  watch: (onChange: () => void) => Promise<() => void>;
+  // Receives the origins keyed by the container name they stand in for.
+  apply: (origins: Record<string, string>) => void;
+}
+
+export function createDockerAutoconfig({ listContainers, getOwnNetworks, watch, apply }: DockerAutoconfigDeps) {
+  let origins: DockerOrigins = {};
+  let timer: NodeJS.Timeout | null = null;
+  let unwatch: (() => void) | null = null;
+
+  async function refresh(): Promise<DockerOrigins> {
+    try {
+      const [containers, ownNetworks] = await Promise.all([listContainers(), getOwnNetworks()]);
+      const next = getDockerOrigins(containers, ownNetworks);
+      for (const service of Object.keys(MONITORING_SERVICES) as MonitoringService[]) {
+        if (next[service]?.origin !== origins[service]?.origin) {
+          log.info(`Docker autoconfig: ${MONITORING_SERVICES[service].label} ${next[service] ? `at ${next[service].origin} (${next[service].container})` : 'not found'}`);
+        }
+      }
+      origins = next;
+      apply(Object.fromEntries(Object.entries(origins).map(([service, entry]) => [
+        MONITORING_SERVICES[service as MonitoringService].containerName,
+        entry.origin,
+      ])));
+    } catch (error) {
+      // Keep the last known origins; Docker may just be restarting.
+      log.error('Docker autoconfig failed:', error);
+    }
+    return origins;
+  }
+
+  function scheduleRefresh(): void {
+    if (timer) clearTimeout(timer);
+    timer = setTimeout(() => {
+      timer = null;
+      void refresh();
+    }, REFRESH_DEBOUNCE_MS);
+  }
+
+  async function start(): Promise<void> {
+    await refresh();
+    try {
+      unwatch = await watch(scheduleRefresh);
+    } catch (error) {
+      log.error('Docker autoconfig: cannot watch container events:', error);
+    }
+  }
+
+  function stop(): void {
+    if (timer) clearTimeout(timer);
+    unwatch?.();
+    unwatch = null;
+  }
+
+  return { start, stop, refresh, get: () => origins };
+}
+
+export type DockerAutoconfig = ReturnType<typeof createDockerAutoconfig>;
@@ -0,0 +1,57 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { BRAIINS_POOL_AUTHORITY_KEY } from '@sv2-ui/shared';
+
+import { renderDockerEnv, validateDockerEnvRequest } from './docker-env.js';
+
+const POOL = { name: 'Braiins Pool', address: 'stratum.braiins.com', port: 3333, authority_public_key: BRAIINS_POOL_AUTHORITY_KEY };
+
+test('validateDockerEnvRequest renders a no-JD pool setup with defaults reported as warnings', () => {
+  const { input, report } = validateDockerEnvRequest({
+    mode: 'no-jd',
+    miningMode: 'pool',
+    pool: POOL,
+    translator: { user_identity: 'worker.1', enable_vardiff: true, min_hashrate: 0, shares_per_minute: 10 },
+  });
+
+  assert.equal(report.valid, true);
+  assert.deepEqual(report.warnings.map((warning) => warning.field), ['translator.min_hashrate', 'translator.downstream_extranonce2_size']);
+  const env = renderDockerEnv(input!);
+  assert.match(env, /^POOL_ADDRESS=stratum\.braiins\.com$/m);
+  assert.match(env, /^TPROXY_SHARES_PER_MINUTE=10$/m);
+  assert.match(env, /^TPROXY_AGGREGATE_CHANNELS=true$/m);
+  assert.doesNotMatch(env, /JDC_/);
+});
+
+test('validateDockerEnvRequest reports every invalid field', () => {
+  const { input, report } = validateDockerEnvRequest({
+    mode: 'jd',
+    miningMode: 'pool',
+    bitcoin: { network: 'mainnet' },
+    pool: { ...POOL, port: 70000, authority_public_key: 'not-a-key' },
+    translator: { user_identity: 'worker\n1' },
+    jdc: { user_identity: 'worker', coinbase_reward_address: 'tb1qexample' },
+  });
+
+  assert.equal(input, null);
+  assert.deepEqual(report.errors.map((error) => error.field), ['pool.port', 'pool.authority_public_key', 'translator.user_identity']);
+  assert.deepEqual(report.warnings.find((warning) => warning.field === 'jdc.coinbase_reward_address')?.message, "doesn't look like a mainnet address");
+});
+
+test('validateDockerEnvRequest needs no pool for solo mining through the JDC', () => {
+  const { input, report } = validateDockerEnvRequest({
+    mode: 'jd',
+    miningMode: 'solo',
+    bitcoin: { network: 'signet' },
+    pool: null,
+    translator: { user_identity: 'solo' },
+    jdc: { user_identity: 'solo', jdc_signature: '', coinbase_reward_address: 'tb1qexample' },
+  });
+
+  assert.equal(report.valid, true);
+  const env = renderDockerEnv(input!);
+  assert.match(env, /^JDC_MODE=SOLOMINING$/m);
+  assert.match(env, /^JDC_SIGNATURE=solo$/m);
+  assert.match(env, /^BITCOIN_NETWORK=signet$/m);
+  assert.doesNotMatch(env, /POOL_/);
+});
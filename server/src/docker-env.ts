@@ -0,0 +1,222 @@
+/**
+ * The `docker_env` file for running the sv2-apps containers by hand.
+ *
+ * POST /api/v1/docker-env takes the setup wizard's data (the same shape as
+ * POST /api/setup), checks every field and renders the env file, so the
+ * server owns the templating instead of each client.
+ *
+ * Docker's --env-file takes values literally: no quoting, no escapes, one
+ * variable per line. Values that can't be written that way are rejected.
+ */
+
+import {
+  DEFAULT_DOWNSTREAM_EXTRANONCE2_SIZE,
+  DEFAULT_SHARES_PER_MINUTE,
+  SUPPORTED_NETWORKS,
+  shouldAggregateTranslatorChannels,
+} from '@sv2-ui/shared';
+import type { BitcoinNetwork, MiningMode, SetupMode } from '@sv2-ui/shared';
+import { decodeBase58Check } from './base58.js';
+
+export const DEFAULT_MIN_HASHRATE = 100_000_000_000_000;
+
+export interface DockerEnvInput {
+  mode: SetupMode;
+  miningMode: MiningMode;
+  network: BitcoinNetwork | null;
+  pool: { name: string; address: string; port: number; authorityPublicKey: string } | null;
+  translator: {
+    userIdentity: string;
+    enableVardiff: boolean;
+    minHashrate: number;
+    sharesPerMinute: number;
+    downstreamExtranonce2Size: number;
+    aggregateChannels: boolean;
+  };
+  jdc: { userIdentity: string; signature: string; coinbaseRewardAddress: string } | null;
+}
+
+export interface DockerEnvIssue {
+  // Path into the request body, e.g. `pool.port`.
+  field: string;
+  message: string;
+}
+
+export interface DockerEnvReport {
+  valid: boolean;
+  errors: DockerEnvIssue[];
+  warnings: DockerEnvIssue[];
+}
+
+// Anything that would end the line or be misread by --env-file.
+// eslint-disable-next-line no-control-regex
+const ENV_UNSAFE_CHARS = /[\u0000-\u001F\u007F]/;
+const ADDRESS_PREFIXES: Record<BitcoinNetwork, string[]> = {
+  mainnet: ['bc1', '1', '3'],
+  testnet4: ['tb1', 'm', 'n', '2'],
+  signet: ['tb1', 'm', 'n', '2'],
+  regtest: ['bcrt1', 'm', 'n', '2'],
+};
+
+/**
+ * Check the wizard's data and turn it into a DockerEnvInput. `input` is null
+ * whenever the report has errors.
+ */
+export function validateDockerEnvRequest(body: unknown): { input: DockerEnvInput | null; report: DockerEnvReport } {
+  const errors: DockerEnvIssue[] = [];
+  const warnings: DockerEnvIssue[] = [];
+  const data = (body ?? {}) as Record<string, unknown>;
+  const section = (name: string) => (data[name] && typeof data[name] === 'object' ? data[name] as Record<string, unknown> : null);
+
+  function text(value: unknown, field: string, { required = true } = {}): string {
+    if (typeof value !== 'string' || !value.trim()) {
+      if (required) errors.push({ field, message: 'is required' });
+      return '';
+    }
+    if (value !== value.trim()) errors.push({ field, message: 'must not have leading or trailing whitespace' });
+    else if (ENV_UNSAFE_CHARS.test(value)) errors.push({ field, message: 'must not contain control characters or line breaks' });
+    return value;
+  }
+
+  function positive(value: unknown, field: string, fallback: number, { integer = false } = {}): number {
+    if (value === undefined || value === null || value === 0) {
+      warnings.push({ field, message: `not set, using ${fallback}` });
+      return fallback;
+    }
+    if (typeof value !== 'number' || !Number.isFinite(value) || value <= 0 || (integer && !Number.isInteger(value))) {
+      errors.push({ field, message: `must be a positive ${integer ? 'integer' : 'number'}` });
+      return fallback;
+    }
+    return value;
+  }
+
+  const mode = data.mode === 'jd' || data.mode === 'no-jd' ? data.mode : null;
+  if (!mode) errors.push({ field: 'mode', message: 'must be jd or no-jd' });
+  let miningMode: MiningMode = 'pool';
+  if (data.miningMode === 'solo' || data.miningMode === 'pool') {
+    miningMode = data.miningMode;
+  } else if (data.miningMode == null) {
+    warnings.push({ field: 'miningMode', message: 'not set, using pool' });
+  } else {
+    errors.push({ field: 'miningMode', message: 'must be pool or solo' });
+  }
+
+  const bitcoin = section('bitcoin');
+  let network: BitcoinNetwork | null = null;
+  if (mode === 'jd') {
+    if (SUPPORTED_NETWORKS.includes(bitcoin?.network as BitcoinNetwork)) {
+      network = bitcoin!.network as BitcoinNetwork;
+    } else {
+      errors.push({ field: 'bitcoin.network', message: `must be one of ${SUPPORTED_NETWORKS.join(', ')}` });
+    }
+  }
+
+  // Solo mining through the JDC builds its own templates and has no pool.
+  const rawPool = section('pool');
+  let pool: DockerEnvInput['pool'] = null;
+  if (!(mode === 'jd' && miningMode === 'solo')) {
+    if (!rawPool) {
+      errors.push({ field: 'pool', message: 'is required' });
+    } else {
+      const address = text(rawPool.address, 'pool.address');
+      if (/\s/.test(address)) errors.push({ field: 'pool.address', message: 'must be a hostname or IP address' });
+      const port = rawPool.port;
+      if (typeof port !== 'number' || !Number.isInteger(port) || port < 1 || port > 65535) {
+        errors.push({ field: 'pool.port', message: 'must be an integer between 1 and 65535' });
+      }
+      const authorityPublicKey = text(rawPool.authority_public_key, 'pool.authority_public_key');
+      if (authorityPublicKey && !decodeBase58Check(authorityPublicKey)) {
+        errors.push({ field: 'pool.authority_public_key', message: 'is not a valid base58check authority key' });
+      }
+      pool = { name: typeof rawPool.name === 'string' ? rawPool.name : '', address, port: port as number, authorityPublicKey };
+    }
+  }
+
+  const rawTranslator = section('translator');
+  if (!rawTranslator) errors.push({ field: 'translator', message: 'is required' });
+  const enableVardiff = rawTranslator?.enable_vardiff;
+  if (enableVardiff !== undefined && typeof enableVardiff !== 'boolean') {
+    errors.push({ field: 'translator.enable_vardiff', message: 'must be true or false' });
+  }
+  const translator: DockerEnvInput['translator'] = {
+    userIdentity: rawTranslator ? text(rawTranslator.user_identity, 'translator.user_identity') : '',
+    enableVardiff: enableVardiff !== false,
+    minHashrate: rawTranslator ? positive(rawTranslator.min_hashrate, 'translator.min_hashrate', DEFAULT_MIN_HASHRATE) : DEFAULT_MIN_HASHRATE,
+    sharesPerMinute: rawTranslator
+      ? positive(rawTranslator.shares_per_minute, 'translator.shares_per_minute', DEFAULT_SHARES_PER_MINUTE)
+      : DEFAULT_SHARES_PER_MINUTE,
+    downstreamExtranonce2Size: rawTranslator
+      ? positive(rawTranslator.downstream_extranonce2_size, 'translator.downstream_extranonce2_size', DEFAULT_DOWNSTREAM_EXTRANONCE2_SIZE, { integer: true })
+      : DEFAULT_DOWNSTREAM_EXTRANONCE2_SIZE,
+    aggregateChannels: shouldAggregateTranslatorChannels(pool && { ...pool, authority_public_key: pool.authorityPublicKey }),
+  };
+
+  let jdc: DockerEnvInput['jdc'] = null;
+  if (mode === 'jd') {
+    const rawJdc = section('jdc');
+    if (!rawJdc) {
+      errors.push({ field: 'jdc', message: 'is required in JD mode' });
+    } else {
+      const coinbaseRewardAddress = text(rawJdc.coinbase_reward_address, 'jdc.coinbase_reward_address');
+      if (coinbaseRewardAddress && network && !ADDRESS_PREFIXES[network].some((prefix) => coinbaseRewardAddress.toLowerCase().startsWith(prefix))) {
+        warnings.push({ field: 'jdc.coinbase_reward_address', message: `doesn't look like a ${network} address` });
+      }
+      const userIdentity = text(rawJdc.user_identity, 'jdc.user_identity');
+      jdc = {
+        userIdentity,
+        // Solo mining signs the coinbase with the user identity when no signature is given.
+        signature: text(rawJdc.jdc_signature, 'jdc.jdc_signature', { required: false }) || (miningMode === 'solo' ? userIdentity : ''),
+        coinbaseRewardAddress,
+      };
+    }
+  }
+
+  const report = { valid: errors.length === 0, errors, warnings };
+  return { input: report.valid ? { mode: mode!, miningMode, network, pool, translator, jdc } : null, report };
+}
+
+export function renderDockerEnv(input: DockerEnvInput): string {
+  const lines = [
+    '# sv2-apps docker_env',
+    '# Generated by sv2-ui',
+    '',
+    `SV2_MODE=${input.mode}`,
+    `MINING_MODE=${input.miningMode}`,
+  ];
+  if (input.network) lines.push(`BITCOIN_NETWORK=${input.network}`);
+
+  if (input.pool) {
+    lines.push(
+      '',
+      `# Upstream pool${input.pool.name ? `: ${input.pool.name}` : ''}`,
+      `POOL_ADDRESS=${input.pool.address}`,
+      `POOL_PORT=${input.pool.port}`,
+      `POOL_AUTHORITY_PUBKEY=${input.pool.authorityPublicKey}`,
+    );
+  }
+
+  const { translator } = input;
+  lines.push(
+    '',
+    '# Translator Proxy',
+    `TPROXY_USER_IDENTITY=${translator.userIdentity}`,
+    `TPROXY_ENABLE_VARDIFF=${translator.enableVardiff}`,
+    `TPROXY_MIN_HASHRATE=${translator.minHashrate}`,
+    `TPROXY_SHARES_PER_MINUTE=${translator.sharesPerMinute}`,
+    `TPROXY_DOWNSTREAM_EXTRANONCE2_SIZE=${translator.downstreamExtranonce2Size}`,
+    `TPROXY_AGGREGATE_CHANNELS=${translator.aggregateChannels}`,
+  );
+
+  if (input.jdc) {
+    lines.push(
+      '',
+      '# JD Client',
+      `JDC_MODE=${input.miningMode === 'solo' ? 'SOLOMINING' : 'FULLTEMPLATE'}`,
+      `JDC_USER_IDENTITY=${input.jdc.userIdentity}`,
+      `JDC_SIGNATURE=${input.jdc.signature}`,
+      `JDC_COINBASE_REWARD_ADDRESS=${input.jdc.coinbaseRewardAddress}`,
+    );
+  }
+
+  return `${lines.join('\n')}\n`;
+}
@@ -6,6 +6,7 @@ import fs from 'fs';
 import path from 'path'
 import Docker from 'dockerode';
 import os from 'os';
+import { Writable } from 'stream';
 import type { BitcoinNetwork, HealthStatus } from '@sv2-ui/shared';
 import {
   CONTAINER_NAMES,
@@ -19,8 +20,10 @@ import type { ContainerLogLine, LogContainerRole, LogOutputStream } from './logs
 import { getImageSelectionForSetup } from '@sv2-ui/shared';
 import { bitcoinSocketValidatorScript } from './bitcoin-socket-validator.js';
 import { bitcoinSocketExistsScript } from './bitcoin-socket-exists.js';
-import { bitcoinRpcValidatorScript } from './bitcoin-rpc-validator.js';
+import { bitcoinChainStatsScript, bitcoinRecentBlocksScript, bitcoinRpcValidatorScript } from './bitcoin-rpc-validator.js';
+import { createLogger } from './logger.js';
 
+const log = createLogger('docker');
 
 /**
  * Expand ~ to home directory in a path.
@@ -347,26 +350,39 @@ export async function probeBitcoinRpcWithDocker(
   }
 }
 
-async function runBitcoinRpcProbeContainer({
+type BitcoinRpcScriptOptions = {
+  script: string;
+  dataDir: string;
+  network: BitcoinNetwork;
+  rpcPort: number;
+  containerDataDir: string;
+  transport: BitcoinRpcProbeTransport;
+};
+
+// `error` is the script's output, possibly empty.
+type BitcoinRpcScriptResult =
+  | { ok: true; output: string }
+  | { ok: false; error: string };
+
+/**
+ * Run one of the bitcoin-rpc-validator.ts scripts in a short-lived container
+ * with the data directory mounted (for cookie/bitcoin.conf credentials).
+ */
+async function runBitcoinRpcScript({
+  script,
   dataDir,
   network,
   rpcPort,
   containerDataDir,
   transport,
-}: {
-  dataDir: string;
-  network: 'mainnet' | 'testnet4';
-  rpcPort: number;
-  containerDataDir: string;
-  transport: BitcoinRpcProbeTransport;
-}): Promise<BitcoinRpcValidationResult> {
+}: BitcoinRpcScriptOptions): Promise<BitcoinRpcScriptResult> {
   let container: Docker.Container | null = null;
 
   try {
     container = await docker.createContainer({
       Image: 'node:20-bookworm-slim',
       Entrypoint: ['node'],
-      Cmd: ['-e', bitcoinRpcValidatorScript, containerDataDir, network, transport.host, String(rpcPort)],
+      Cmd: ['-e', script, containerDataDir, network, transport.host, String(rpcPort)],
       AttachStdout: true,
       AttachStderr: true,
       HostConfig: {
@@ -387,40 +403,15 @@ async function runBitcoinRpcProbeContainer({
     await container.start();
     const result = await container.wait();
 
-    if (result.StatusCode === 0) {
-      const rawLogs = await container.logs({ stdout: true, stderr: false });
-      const output = demuxDockerLogBuffer(Buffer.isBuffer(rawLogs) ? rawLogs : Buffer.from(rawLogs))
-        .map((chunk) => chunk.payload.trim())
-        .filter(Boolean)
-        .join('\n');
-
-      try {
-        const parsed = JSON.parse(output);
-        return {
-          valid: true,
-          chain: parsed.chain,
-          version: parsed.version,
-          initialBlockDownload: parsed.initialblockdownload,
-          logpath: parsed.logpath,
-        };
-      } catch {
-        return {
-          valid: false,
-          error: `Failed to parse RPC validation output: ${output}`,
-        };
-      }
-    }
-
-    const rawLogs = await container.logs({ stdout: true, stderr: true });
-    const message = demuxDockerLogBuffer(Buffer.isBuffer(rawLogs) ? rawLogs : Buffer.from(rawLogs))
+    const rawLogs = await container.logs({ stdout: true, stderr: result.StatusCode !== 0 });
+    const output = demuxDockerLogBuffer(Buffer.isBuffer(rawLogs) ? rawLogs : Buffer.from(rawLogs))
       .map((chunk) => chunk.payload.trim())
       .filter(Boolean)
       .join('\n');
 
-    return {
-      valid: false,
-      error: message || `RPC validation failed for port ${rpcPort}`,
-    };
+    return result.StatusCode === 0
+      ? { ok: true, output }
+      : { ok: false, error: output };
   } finally {
     if (container) {
       try {
@@ -432,6 +423,120 @@ async function runBitcoinRpcProbeContainer({
   }
 }
 
+async function runBitcoinRpcProbeContainer(
+  options: Omit<BitcoinRpcScriptOptions, 'script'>,
+): Promise<BitcoinRpcValidationResult> {
+  const result = await runBitcoinRpcScript({ ...options, script: bitcoinRpcValidatorScript });
+
+  if (!result.ok) {
+    return {
+      valid: false,
+      error: result.error || `RPC validation failed for port ${options.rpcPort}`,
+    };
+  }
+
+  try {
+    const parsed = JSON.parse(result.output);
+    return {
+      valid: true,
+      chain: parsed.chain,
+      version: parsed.version,
+      initialBlockDownload: parsed.initialblockdownload,
+      logpath: parsed.logpath,
+    };
+  } catch {
+    return {
+      valid: false,
+      error: `Failed to parse RPC validation output: ${result.output}`,
+    };
+  }
+}
+
+export type BitcoinChainStats = {
+  chain: string;
+  height: number;
+  difficulty: number;
+  tipTime: number;
+  epochStartHeight: number;
+  epochStartTime: number;
+};
+
+/**
+ * Read chain tip and difficulty epoch details from the configured node.
+ */
+export async function queryBitcoinChainStats(
+  dataDir: string,
+  network: BitcoinNetwork,
+): Promise<BitcoinChainStats> {
+  await pullImage("node:20-bookworm-slim");
+
+  const errors: string[] = [];
+  for (const transport of getBitcoinRpcProbeTransports()) {
+    try {
+      const result = await runBitcoinRpcScript({
+        script: bitcoinChainStatsScript,
+        dataDir,
+        network,
+        rpcPort: RPC_PORTS[network],
+        containerDataDir: "/tmp/bitcoin",
+        transport,
+      });
+
+      if (result.ok) {
+        return JSON.parse(result.output) as BitcoinChainStats;
+      }
+      errors.push(`${transport.name}: ${result.error || 'RPC call failed'}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      errors.push(`${transport.name}: ${message}`);
+    }
+  }
+
+  throw new Error(errors.join('\n') || 'Bitcoin Core RPC unreachable');
+}
+
+export type BitcoinRecentBlock = {
+  hash: string;
+  height: number;
+  time: number;
+  // scriptPubKey hex of each coinbase output.
+  coinbaseScripts: string[];
+};
+
+/**
+ * The last few blocks of the configured node's chain, newest first.
+ */
+export async function queryRecentBlocks(
+  dataDir: string,
+  network: BitcoinNetwork,
+): Promise<BitcoinRecentBlock[]> {
+  await pullImage("node:20-bookworm-slim");
+
+  const errors: string[] = [];
+  for (const transport of getBitcoinRpcProbeTransports()) {
+    try {
+      const result = await runBitcoinRpcScript({
+        script: bitcoinRecentBlocksScript,
+        dataDir,
+        network,
+        rpcPort: RPC_PORTS[network],
+        containerDataDir: "/tmp/bitcoin",
+        transport,
+      });
+
+      if (result.ok) {
+        return JSON.parse(result.output) as BitcoinRecentBlock[];
+      }
+      errors.push(`${transport.name}: ${result.error || 'RPC call failed'}`);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      errors.push(`${transport.name}: ${message}`);
+    }
+  }
+
+  throw new Error(errors.join('\n') || 'Bitcoin Core RPC unreachable');
+}
+
 async function checkSocketExists(
   socketPath: string,
   containerSocketPath: string,
@@ -568,6 +673,20 @@ function demuxDockerLogBuffer(buffer: Buffer): DockerLogChunk[] {
   return chunks;
 }
 
+function parseLogLine(container: LogContainerRole, stream: LogOutputStream, raw: string): ContainerLogLine {
+  // Dockerode prefixes lines with an RFC 3339 timestamp when
+  // `timestamps: true` is enabled, so we split it from the log message.
+  const match = raw.match(/^(\d{4}-\d{2}-\d{2}T\S+?)\s(.*)$/);
+
+  return {
+    container,
+    stream,
+    timestamp: match ? match[1] : null,
+    message: match ? match[2] : raw,
+    raw,
+  };
+}
+
 function splitLogLines(
   container: LogContainerRole,
   stream: LogOutputStream,
@@ -576,19 +695,7 @@ function splitLogLines(
   return payload
     .split(/\r?\n/)
     .filter((line) => line.length > 0)
-    .map((raw) => {
-      // Dockerode prefixes lines with an RFC 3339 timestamp when
-      // `timestamps: true` is enabled, so we split it from the log message.
-      const match = raw.match(/^(\d{4}-\d{2}-\d{2}T\S+?)\s(.*)$/);
-
-      return {
-        container,
-        stream,
-        timestamp: match ? match[1] : null,
-        message: match ? match[2] : raw,
-        raw,
-      };
-    });
+    .map((raw) => parseLogLine(container, stream, raw));
 }
 
 export async function readContainerLogs(
@@ -629,6 +736,71 @@ export async function readContainerLogs(
   }
 }
 
+/**
+ * Follow a container's logs, starting with the last `tail` lines. `onLine` is
+ * called per complete line and `onEnd` once the container stops or the stream
+ * fails. Resolves to a function that stops following.
+ */
+export async function followContainerLogs(
+  container: LogContainerRole,
+  { tail, onLine, onEnd }: { tail: number; onLine: (line: ContainerLogLine) => void; onEnd: () => void }
+): Promise<() => void> {
+  refreshDockerConnection();
+
+  const dockerContainer = docker.getContainer(LOG_CONTAINER_NAMES[container]);
+  let info;
+  let logStream: NodeJS.ReadableStream & { destroy?: () => void };
+  try {
+    info = await dockerContainer.inspect();
+    logStream = await dockerContainer.logs({
+      stdout: true,
+      stderr: true,
+      follow: true,
+      timestamps: true,
+      tail,
+    });
+  } catch (error) {
+    throw new Error(`Failed to follow logs for ${container} container`, {
+      cause: error instanceof Error ? error : new Error(String(error)),
+    });
+  }
+
+  // Chunks don't line up with lines, so hold back the unterminated tail.
+  const lineWriter = (stream: LogOutputStream) => {
+    let pending = '';
+    return new Writable({
+      write(chunk: Buffer, _encoding, callback) {
+        const lines = (pending + chunk.toString('utf-8')).split(/\r?\n/);
+        pending = lines.pop() ?? '';
+        for (const raw of lines) {
+          if (raw.length > 0) onLine(parseLogLine(container, stream, raw));
+        }
+        callback();
+      },
+    });
+  };
+
+  if (info.Config?.Tty) {
+    logStream.pipe(lineWriter('stdout'));
+  } else {
+    docker.modem.demuxStream(logStream, lineWriter('stdout'), lineWriter('stderr'));
+  }
+
+  let ended = false;
+  const end = () => {
+    if (ended) return;
+    ended = true;
+    onEnd();
+  };
+  logStream.on('end', end);
+  logStream.on('error', end);
+
+  return () => {
+    ended = true;
+    logStream.destroy?.();
+  };
+}
+
 /**
  * Ensure the sv2 network exists
  */
@@ -637,7 +809,7 @@ async function ensureNetwork(): Promise<void> {
     const network = docker.getNetwork(NETWORK_NAME);
     await network.inspect();
   } catch {
-    console.log(`Creating network ${NETWORK_NAME}...`);
+    log.info(`Creating network ${NETWORK_NAME}...`);
     await docker.createNetwork({
       Name: NETWORK_NAME,
       Driver: 'bridge',
@@ -670,12 +842,12 @@ async function connectSv2UiToNetwork(): Promise<void> {
       return;
     }
 
-    console.log('Connecting sv2-ui to sv2-network...');
+    log.info('Connecting sv2-ui to sv2-network...');
     await network.connect({ Container: sv2UiContainer.Id });
   } catch {
     // Non-fatal: sv2-ui stays on its default network (bridge).
     // The API proxy will still work via exposed ports on localhost.
-    console.log('Note: Could not connect to sv2-network');
+    log.info('Note: Could not connect to sv2-network');
   }
 }
 
@@ -691,13 +863,13 @@ async function pullImage(imageName: string): Promise<void> {
     // Image not found locally, proceed to pull
   }
 
-  console.log(`Pulling latest ${imageName}...`);
+  log.info(`Pulling latest ${imageName}...`);
   await new Promise<void>((resolve, reject) => {
     docker.pull(imageName, (err: Error | null, stream: NodeJS.ReadableStream) => {
       if (err) return reject(err);
       docker.modem.followProgress(stream, (err) => {
         if (err) return reject(err);
-        console.log(`Pulled ${imageName}`);
+        log.info(`Pulled ${imageName}`);
         resolve();
       });
     });
@@ -715,7 +887,7 @@ async function removeContainer(name: string): Promise<void> {
       await container.stop();
     }
     await container.remove();
-    console.log(`Removed container ${name}`);
+    log.info(`Removed container ${name}`);
   } catch {
     // Container doesn't exist, that's fine
   }
@@ -788,7 +960,7 @@ async function startTranslator(configPath: string, image: string): Promise<void>
   });
 
   await container.start();
-  console.log('Translator container started');
+  log.info('Translator container started');
 }
 
 /**
@@ -840,7 +1012,7 @@ async function startJdc(
   });
 
   await container.start();
-  console.log('JDC container started');
+  log.info('JDC container started');
 }
 
 /**
@@ -861,9 +1033,9 @@ export async function startStack(
   const imageSelection = getImageSelectionForSetup(data);
 
   if (imageSelection.mode === 'jd') {
-    console.log(`Using JDC image ${imageSelection.jdc} and Translator image ${imageSelection.translator}`);
+    log.info(`Using JDC image ${imageSelection.jdc} and Translator image ${imageSelection.translator}`);
   } else {
-    console.log(`Using Translator image ${imageSelection.translator} for no-JD mode`);
+    log.info(`Using Translator image ${imageSelection.translator} for no-JD mode`);
   }
 
   // Pull selected images from Docker Hub
@@ -876,7 +1048,7 @@ export async function startStack(
   if (imageSelection.mode === 'jd' && data.bitcoin) {
     const socketPath = expandHomePath(data.bitcoin.socket_path);
     await startJdc(`${configDir}/jdc.toml`, socketPath, data.bitcoin.network, imageSelection.jdc);
-    console.log('Waiting for JDC to initialize...');
+    log.info('Waiting for JDC to initialize...');
     await new Promise(resolve => setTimeout(resolve, 3000));
   }
 
@@ -898,6 +1070,89 @@ export async function stopStack(): Promise<void> {
   await removeContainer(TRANSLATOR_CONTAINER);
 }
 
+function isDockerStatus(error: unknown, statusCode: number): boolean {
+  return typeof error === 'object' && error !== null && (error as { statusCode?: number }).statusCode === statusCode;
+}
+
+/**
+ * Start one stopped mining container as it was created.
+ */
+export async function startContainer(role: 'translator' | 'jdc'): Promise<void> {
+  await ensureDockerAvailable();
+  const name = role === 'jdc' ? JDC_CONTAINER : TRANSLATOR_CONTAINER;
+  try {
+    await docker.getContainer(name).start();
+  } catch (error) {
+    // 304: already running.
+    if (!isDockerStatus(error, 304)) throw error;
+  }
+  log.info(`Started container ${name}`);
+}
+
+/**
+ * Stop one mining container without removing it.
+ */
+export async function stopContainer(role: 'translator' | 'jdc'): Promise<void> {
+  await ensureDockerAvailable();
+  const name = role === 'jdc' ? JDC_CONTAINER : TRANSLATOR_CONTAINER;
+  try {
+    await docker.getContainer(name).stop();
+  } catch (error) {
+    // 304: already stopped.
+    if (!isDockerStatus(error, 304)) throw error;
+  }
+  log.info(`Stopped container ${name}`);
+}
+
+/**
+ * Whether `error` says the container doesn't exist.
+ */
+export function isContainerNotFound(error: unknown): boolean {
+  return isDockerStatus(error, 404);
+}
+
+export type ContainerImage = {
+  // The reference the container was created from, e.g. stratumv2/translator_sv2:main.
+  image: string;
+  // `<repo>@sha256:…` digests of the image the container runs.
+  repoDigests: string[];
+};
+
+/**
+ * The image one mining container runs, or null when the container doesn't exist.
+ */
+export async function getContainerImage(role: 'translator' | 'jdc'): Promise<ContainerImage | null> {
+  await ensureDockerAvailable();
+  const name = role === 'jdc' ? JDC_CONTAINER : TRANSLATOR_CONTAINER;
+  try {
+    const container = await docker.getContainer(name).inspect();
+    const image = await docker.getImage(container.Image).inspect();
+    return { image: container.Config.Image, repoDigests: image.RepoDigests ?? [] };
+  } catch (error) {
+    if (isDockerStatus(error, 404)) return null;
+    throw error;
+  }
+}
+
+/**
+ * The digest the registry currently serves for `image`, without pulling it.
+ */
+export async function getRegistryDigest(image: string): Promise<string> {
+  await ensureDockerAvailable();
+  const info = await docker.getImage(image).distribution({});
+  return info.Descriptor.digest;
+}
+
+/**
+ * Restart one mining container in place, keeping its config and port bindings.
+ */
+export async function restartContainer(role: 'translator' | 'jdc'): Promise<void> {
+  await ensureDockerAvailable();
+  const name = role === 'jdc' ? JDC_CONTAINER : TRANSLATOR_CONTAINER;
+  await docker.getContainer(name).restart();
+  log.info(`Restarted container ${name}`);
+}
+
 /**
  * Get stack status
  */
@@ -932,3 +1187,54 @@ export async function ensureDockerAvailable(): Promise<void> {
     throw normalizeDockerError(error);
   }
 }
+
+export type RunningContainer = {
+  name: string;
+  image: string;
+  labels: Record<string, string>;
+  networks: string[];
+  ports: { privatePort: number; publicPort?: number; ip?: string }[];
+};
+
+/**
+ * Running containers, in the shape Docker auto-configuration needs.
+ */
+export async function listRunningContainers(): Promise<RunningContainer[]> {
+  await ensureDockerAvailable();
+  const containers = await docker.listContainers();
+  return containers.map((container) => ({
+    name: (container.Names[0] ?? container.Id).replace(/^\//, ''),
+    image: container.Image,
+    labels: container.Labels ?? {},
+    networks: Object.keys(container.NetworkSettings?.Networks ?? {}),
+    ports: container.Ports.map((port) => ({ privatePort: port.PrivatePort, publicPort: port.PublicPort, ip: port.IP })),
+  }));
+}
+
+/**
+ * Networks sv2-ui's own container is attached to; empty outside Docker.
+ */
+export async function getOwnNetworks(): Promise<string[]> {
+  if (!isRunningInDocker) return [];
+  try {
+    // Docker sets the container's hostname to its short ID.
+    const info = await docker.getContainer(os.hostname()).inspect();
+    return Object.keys(info.NetworkSettings.Networks ?? {});
+  } catch {
+    return [];
+  }
+}
+
+/**
+ * Call `onChange` whenever a container starts, stops or restarts. Resolves
+ * to a function that stops watching.
+ */
+export async function watchContainerEvents(onChange: () => void): Promise<() => void> {
+  await ensureDockerAvailable();
+  const stream = await docker.getEvents({
+    filters: { type: ['container'], event: ['start', 'die', 'restart'] },
+  }) as NodeJS.ReadableStream & { destroy?: () => void };
+  stream.on('data', onChange);
+  stream.on('error', (error) => log.error('Docker event stream error:', error));
+  return () => stream.destroy?.();
+}
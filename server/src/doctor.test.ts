@@ -0,0 +1,88 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { diagnoseClock, diagnoseDiskSpace, diagnoseMonitoring, diagnoseSv1 } from './doctor.js';
+import type { StackData } from './monitoring.js';
+import { getDefaultState } from './state.js';
+import type { Sv1ProbeResult } from './sv1-listener.js';
+
+const jdState = { ...getDefaultState(), configured: true, mode: 'jd' as const, miningMode: 'pool' as const };
+
+function stackData(errors: StackData['errors']): StackData {
+  return { mode: 'jd', upstream: 'jdc', global: null, serverChannels: null, sv1Clients: null, sv2Clients: null, errors };
+}
+
+test('monitoring findings tell an unreachable API from one whose responses no longer decode', () => {
+  assert.deepEqual(diagnoseMonitoring(getDefaultState(), null).map(({ status }) => status), ['warn']);
+
+  const findings = diagnoseMonitoring(jdState, stackData([
+    { section: 'sv1Clients', backend: 'translator', errorClass: 'unreachable', message: 'fetch failed' },
+    { section: 'global', backend: 'jdc', errorClass: 'invalid-response', message: 'uptime_secs: expected a number' },
+  ]));
+  assert.deepEqual(findings.map(({ name, status }) => [name, status]), [
+    ['Translator monitoring API', 'fail'],
+    ['JDC monitoring API', 'ok'],
+    ['JDC API schema', 'fail'],
+  ]);
+  assert.match(findings[0].detail, /fetch failed$/);
+  assert.equal(findings[2].detail, 'global: uptime_secs: expected a number');
+  assert.ok(findings.every(({ status, hint }) => status === 'ok' || hint));
+
+  assert.deepEqual(diagnoseMonitoring(jdState, stackData([])).map(({ status }) => status), ['ok', 'ok', 'ok', 'ok']);
+});
+
+test('the SV1 finding depends on the step the self-test stopped at', () => {
+  const probe = (steps: Sv1ProbeResult['steps']): Sv1ProbeResult => ({
+    ok: steps.every((step) => step.ok),
+    host: 'localhost',
+    port: 34255,
+    user: 'sv2-ui-probe',
+    steps,
+    extranonce1: null,
+    extranonce2Size: null,
+    checkedAt: new Date(0).toISOString(),
+  });
+  const ok = (step: 'connect' | 'subscribe' | 'authorize') => ({ step, ok: true, durationMs: 3, detail: '', error: null });
+  const failed = (step: 'connect' | 'subscribe' | 'authorize') => ({ step, ok: false, durationMs: 3, detail: null, error: 'No reply within 5000ms' });
+
+  assert.deepEqual(diagnoseSv1(probe([ok('connect'), ok('subscribe'), ok('authorize')])), {
+    name: 'SV1 port (localhost:34255)',
+    status: 'ok',
+    detail: 'subscribe and authorize answered in 9ms',
+  });
+  assert.equal(diagnoseSv1(probe([failed('connect')])).status, 'fail');
+  const subscribe = diagnoseSv1(probe([ok('connect'), failed('subscribe')]));
+  assert.equal(subscribe.detail, 'subscribe failed: No reply within 5000ms');
+  assert.match(subscribe.hint ?? '', /upstream connection/);
+  assert.equal(diagnoseSv1(probe([ok('connect'), ok('subscribe'), failed('authorize')])).status, 'warn');
+});
+
+test('clock skew warns past 5s and fails past a minute', () => {
+  assert.equal(diagnoseClock(1_200, 'registry-1.docker.io').status, 'ok');
+  assert.equal(diagnoseClock(-8_000, 'registry-1.docker.io').detail, '8s ahead of registry-1.docker.io');
+  assert.equal(diagnoseClock(-8_000, 'registry-1.docker.io').status, 'warn');
+  assert.equal(diagnoseClock(300_000, 'registry-1.docker.io').detail, '300s behind registry-1.docker.io');
+  assert.equal(diagnoseClock(300_000, 'registry-1.docker.io').status, 'fail');
+});
+
+test('disk space is measured where the history database lives, even before it exists', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-doctor-'));
+  t.after(() => fs.rm(dir, { recursive: true, force: true }));
+  await fs.writeFile(path.join(dir, 'history.db'), Buffer.alloc(2 * 1024 * 1024));
+
+  const statted: string[] = [];
+  const statfs = (free: number) => (at: string) => {
+    statted.push(at);
+    return { bavail: free / 4096, bsize: 4096 };
+  };
+  assert.deepEqual(diagnoseDiskSpace(path.join(dir, 'history.db'), statfs(8 * 1024 ** 3)), {
+    name: 'Disk space',
+    status: 'ok',
+    detail: `8.0 GiB free in ${dir}, history database 2 MiB`,
+  });
+  assert.equal(diagnoseDiskSpace(path.join(dir, 'history.db'), statfs(512 * 1024 ** 2)).status, 'warn');
+  assert.equal(diagnoseDiskSpace(path.join(dir, 'new', 'history.db'), statfs(50 * 1024 ** 2)).status, 'fail');
+  assert.equal(statted.at(-1), dir);
+});
@@ -0,0 +1,263 @@
+/**
+ * `doctor`: gather what a support thread usually asks for first.
+ *
+ * The command runs every `check` (see check.ts), then looks at the stack end
+ * to end:
+ *
+ * - whether the monitoring APIs answer, and whether their responses still
+ *   decode into sv2-ui's schema (see monitoring-schema.ts);
+ * - whether the SV1 port takes a miner's subscribe and authorize;
+ * - how far the local clock is off, from the Date header of the registry
+ *   the sv2-apps images come from;
+ * - how much disk is left for the history database;
+ * - whether the Docker daemon can be reached.
+ *
+ * Every finding that isn't ok carries a hint on what to do about it.
+ */
+
+import { existsSync, readFileSync, statfsSync, statSync } from 'fs';
+import path from 'path';
+import { CONTAINER_NAMES, TRANSLATOR_PORT } from '@sv2-ui/shared';
+import { runChecks } from './check.js';
+import type { CheckResult } from './check.js';
+import { getDockerConnectionInfo, isDockerAvailable } from './docker.js';
+import { parseKubernetesDiscovery } from './kubernetes-discovery.js';
+import { fetchStackData, getMonitoringBaseUrl, getUpstreamService, MONITORING_SERVICES } from './monitoring.js';
+import type { MonitoringService, StackData, StackSection } from './monitoring.js';
+import { getContainerHost } from './proxy.js';
+import { getDefaultState, normalizeSavedState } from './state.js';
+import type { SavedState } from './state.js';
+import { probeSv1Stratum } from './sv1-listener.js';
+import type { Sv1ProbeResult } from './sv1-listener.js';
+
+export interface DoctorOptions {
+  configDir: string;
+  historyDb: string;
+  timeoutMs: number;
+}
+
+// Answers with a Date header, even to anonymous requests.
+export const CLOCK_REFERENCE_URL = 'https://registry-1.docker.io/v2/';
+const CLOCK_SKEW_WARN_MS = 5_000;
+// Further off, pool jobs and TLS certificates are judged by the wrong time.
+const CLOCK_SKEW_FAIL_MS = 60_000;
+const MIB = 1024 * 1024;
+const DISK_FREE_WARN_BYTES = 1024 * MIB;
+const DISK_FREE_FAIL_BYTES = 100 * MIB;
+
+function formatBytes(bytes: number): string {
+  return bytes >= 1024 * MIB ? `${(bytes / 1024 / MIB).toFixed(1)} GiB` : `${Math.round(bytes / MIB)} MiB`;
+}
+
+function readSavedState(configDir: string): SavedState {
+  const file = path.join(configDir, 'state.json');
+  // A broken state.json is already reported by the Setup check.
+  try {
+    return existsSync(file) ? normalizeSavedState(JSON.parse(readFileSync(file, 'utf-8')) as Partial<SavedState>) : getDefaultState();
+  } catch {
+    return getDefaultState();
+  }
+}
+
+function getStackServices(state: SavedState): MonitoringService[] {
+  return state.mode === 'jd' ? ['translator', 'jdc'] : ['translator'];
+}
+
+/**
+ * Reachability and schema findings for each monitoring API, from one round
+ * of the requests the dashboard makes. `data` is null until a stack is
+ * configured.
+ */
+export function diagnoseMonitoring(state: SavedState, data: StackData | null): CheckResult[] {
+  if (!state.configured || !data) {
+    return [{
+      name: 'Monitoring APIs',
+      status: 'warn',
+      detail: 'No stack is configured yet',
+      hint: 'Finish the setup in the dashboard, then run doctor again.',
+    }];
+  }
+
+  const upstream = getUpstreamService(state.mode);
+  const sections: Record<StackSection, MonitoringService | null> = {
+    global: upstream,
+    serverChannels: upstream,
+    sv1Clients: 'translator',
+    sv2Clients: state.mode === 'jd' ? 'jdc' : null,
+  };
+  const errors = data.errors ?? [];
+
+  return getStackServices(state).flatMap((service): CheckResult[] => {
+    const { label } = MONITORING_SERVICES[service];
+    const url = getMonitoringBaseUrl(service);
+    const asked = Object.values(sections).filter((backend) => backend === service).length;
+    const failed = errors.filter((error) => error.backend === service);
+    const unreachable = failed.filter((error) => error.errorClass === 'unreachable' || error.errorClass === 'timeout');
+
+    if (unreachable.length === asked) {
+      return [{
+        name: `${label} monitoring API`,
+        status: 'fail',
+        detail: `Cannot reach ${url}: ${unreachable[0].message}`,
+        hint: `Start the stack from the dashboard, and make sure the ${label} has its monitoring API enabled on port ${MONITORING_SERVICES[service].port}.`,
+      }];
+    }
+
+    const rejected = failed.filter((error) => error.errorClass === 'http' || error.errorClass === 'invalid-response');
+    return [
+      { name: `${label} monitoring API`, status: 'ok', detail: `answering at ${url}` },
+      rejected.length === 0
+        ? { name: `${label} API schema`, status: 'ok', detail: `${asked} of ${asked} responses decoded` }
+        : {
+          name: `${label} API schema`,
+          status: 'fail',
+          detail: rejected.map((error) => `${error.section}: ${error.message}`).join('; '),
+          hint: `This ${label} release answers in a shape sv2-ui doesn't read. Run the image version this sv2-ui release ships with, or update sv2-ui.`,
+        },
+    ];
+  });
+}
+
+/**
+ * The SV1 port finding, from the stratum self-test.
+ */
+export function diagnoseSv1(probe: Sv1ProbeResult): CheckResult {
+  const name = `SV1 port (${probe.host}:${probe.port})`;
+  if (probe.ok) {
+    const ms = probe.steps.reduce((total, step) => total + step.durationMs, 0);
+    return { name, status: 'ok', detail: `subscribe and authorize answered in ${ms}ms` };
+  }
+
+  const failed = probe.steps.find((step) => !step.ok);
+  const detail = `${failed?.step ?? 'connect'} failed: ${failed?.error ?? 'no reply'}`;
+  switch (failed?.step) {
+    case 'subscribe':
+      return {
+        name,
+        status: 'fail',
+        detail,
+        hint: 'The Translator accepts connections but hands out no work. Check its upstream connection to the pool or JDC in its logs.',
+      };
+    case 'authorize':
+      // The probe's worker name may not fit the Translator's rules while real miners' do.
+      return {
+        name,
+        status: 'warn',
+        detail,
+        hint: 'The Translator rejected the probe worker. Compare the user identity your miners use with the one configured for the Translator.',
+      };
+    default:
+      return {
+        name,
+        status: 'fail',
+        detail,
+        hint: `Make sure the Translator is running and listens on port ${probe.port}; miners can't connect either.`,
+      };
+  }
+}
+
+/**
+ * The clock finding for a measured offset from the reference clock.
+ */
+export function diagnoseClock(skewMs: number, reference: string): CheckResult {
+  const seconds = Math.round(Math.abs(skewMs) / 1000);
+  const off = `${seconds}s ${skewMs > 0 ? 'behind' : 'ahead of'} ${reference}`;
+  if (Math.abs(skewMs) < CLOCK_SKEW_WARN_MS) return { name: 'Clock', status: 'ok', detail: `within ${CLOCK_SKEW_WARN_MS / 1000}s of ${reference}` };
+  return {
+    name: 'Clock',
+    status: Math.abs(skewMs) < CLOCK_SKEW_FAIL_MS ? 'warn' : 'fail',
+    detail: off,
+    hint: 'Turn on time synchronisation on the host, e.g. with `timedatectl set-ntp true`.',
+  };
+}
+
+/**
+ * How far the local clock is behind (positive) or ahead of the server
+ * answering `url`, taken at the midpoint of the request.
+ */
+export async function measureClockSkew(url: string, timeoutMs: number, now: () => number = Date.now): Promise<number> {
+  const sentAt = now();
+  const response = await fetch(url, { method: 'HEAD', signal: AbortSignal.timeout(timeoutMs) });
+  const receivedAt = now();
+  const date = Date.parse(response.headers.get('date') ?? '');
+  if (Number.isNaN(date)) throw new Error('The response has no Date header');
+  // The header has a resolution of one second.
+  return date + 500 - (sentAt + receivedAt) / 2;
+}
+
+/**
+ * The disk space finding for the history database, which SQLite can no
+ * longer write to once the disk is full.
+ */
+export function diagnoseDiskSpace(historyDb: string, statfs: (dir: string) => { bavail: number; bsize: number } = statfsSync): CheckResult {
+  let dir = path.dirname(path.resolve(historyDb));
+  while (!existsSync(dir) && path.dirname(dir) !== dir) dir = path.dirname(dir);
+  const { bavail, bsize } = statfs(dir);
+  const free = bavail * bsize;
+  const size = [historyDb, `${historyDb}-wal`].reduce((total, file) => total + (existsSync(file) ? statSync(file).size : 0), 0);
+
+  const detail = `${formatBytes(free)} free in ${dir}, history database ${formatBytes(size)}`;
+  if (free >= DISK_FREE_WARN_BYTES) return { name: 'Disk space', status: 'ok', detail };
+  return {
+    name: 'Disk space',
+    status: free < DISK_FREE_FAIL_BYTES ? 'fail' : 'warn',
+    detail,
+    hint: 'Free up space on that disk, or shorten HISTORY_RETENTION so fewer samples are kept.',
+  };
+}
+
+/**
+ * The Docker finding. Without the daemon sv2-ui can't start the stack,
+ * which only matters when it manages the stack itself.
+ */
+export function diagnoseDocker(available: boolean, connection: { endpoint: string; source: string }, managed: boolean): CheckResult {
+  if (available) return { name: 'Docker', status: 'ok', detail: `reachable at ${connection.endpoint} (${connection.source})` };
+  return {
+    name: 'Docker',
+    status: managed ? 'fail' : 'warn',
+    detail: `Cannot reach the Docker daemon at ${connection.endpoint} (${connection.source})`,
+    hint: 'Mount the Docker socket into the sv2-ui container with `-v /var/run/docker.sock:/var/run/docker.sock`, or set DOCKER_HOST or DOCKER_SOCKET_PATH.',
+  };
+}
+
+export async function runDoctor(env: NodeJS.ProcessEnv, { configDir, historyDb, timeoutMs }: DoctorOptions): Promise<CheckResult[]> {
+  const state = readSavedState(configDir);
+  const reference = new URL(CLOCK_REFERENCE_URL).hostname;
+  let managed = true;
+  try {
+    managed = !parseKubernetesDiscovery(env);
+  } catch {
+    // Reported by the Kubernetes discovery check.
+  }
+
+  const [checks, data, sv1, clock, docker] = await Promise.all([
+    runChecks(env, configDir, timeoutMs),
+    state.configured ? fetchStackData(state.mode) : Promise.resolve(null),
+    state.configured ? probeSv1Stratum(getContainerHost(CONTAINER_NAMES.translator), TRANSLATOR_PORT, { timeoutMs }) : Promise.resolve(null),
+    measureClockSkew(CLOCK_REFERENCE_URL, timeoutMs)
+      .then((skewMs) => diagnoseClock(skewMs, reference))
+      .catch((error): CheckResult => ({
+        name: 'Clock',
+        status: 'warn',
+        detail: `Cannot read the time from ${reference}: ${error instanceof Error ? error.message : String(error)}`,
+        hint: 'The clock check needs outbound HTTPS. Compare the host clock with a trusted one by hand.',
+      })),
+    isDockerAvailable(),
+  ]);
+
+  let disk: CheckResult;
+  try {
+    disk = diagnoseDiskSpace(historyDb);
+  } catch (error) {
+    disk = { name: 'Disk space', status: 'fail', detail: error instanceof Error ? error.message : String(error) };
+  }
+
+  return [
+    ...checks,
+    ...diagnoseMonitoring(state, data),
+    ...sv1 ? [diagnoseSv1(sv1)] : [],
+    clock,
+    disk,
+    diagnoseDocker(docker, getDockerConnectionInfo(), managed),
+  ];
+}
@@ -0,0 +1,141 @@
+import assert from 'node:assert/strict';
+import net from 'node:net';
+import { test } from 'node:test';
+import type { AlertTransition } from './alerts.js';
+import {
+  buildMimeMessage,
+  formatAlertEmail,
+  getNextDailyRun,
+  parseEmailConfig,
+  sendEmail,
+} from './email.js';
+import type { EmailConfig } from './email.js';
+
+const alert: AlertTransition = {
+  id: 'a1',
+  ruleId: 'unreachable',
+  kind: 'unreachable',
+  status: 'firing',
+  message: 'Monitoring API unreachable',
+  at: '2026-01-01T00:00:00.000Z',
+};
+
+const baseEnv = { SMTP_HOST: 'smtp.example', ALERT_EMAIL_FROM: 'miner@example.com', ALERT_EMAIL_TO: 'ops@example.com' };
+
+test('parses SMTP config with TLS defaults', () => {
+  assert.equal(parseEmailConfig({}), null);
+  assert.deepEqual(parseEmailConfig({ ...baseEnv, ALERT_EMAIL_TO: 'a@x.io, b@x.io', ALERT_EMAIL_DAILY_AT: '08:30' }), {
+    host: 'smtp.example',
+    port: 587,
+    tls: 'starttls',
+    user: null,
+    password: null,
+    from: 'miner@example.com',
+    to: ['a@x.io', 'b@x.io'],
+    dailyAt: 510,
+  });
+  assert.equal(parseEmailConfig({ ...baseEnv, SMTP_PORT: '465' })?.tls, 'tls');
+  assert.equal(parseEmailConfig({ ...baseEnv, SMTP_TLS: 'none' })?.port, 25);
+});
+
+test('rejects incomplete or unsafe SMTP config', () => {
+  assert.throws(() => parseEmailConfig({ ...baseEnv, ALERT_EMAIL_TO: '' }), /ALERT_EMAIL_TO is required/);
+  assert.throws(() => parseEmailConfig({ ...baseEnv, SMTP_TLS: 'ssl' }), /SMTP_TLS must be one of/);
+  assert.throws(() => parseEmailConfig({ ...baseEnv, SMTP_USER: 'u' }), /must be set together/);
+  assert.throws(
+    () => parseEmailConfig({ ...baseEnv, SMTP_TLS: 'none', SMTP_USER: 'u', SMTP_PASSWORD: 'p' }),
+    /requires SMTP_TLS/,
+  );
+  assert.throws(() => parseEmailConfig({ ...baseEnv, ALERT_EMAIL_DAILY_AT: '8am' }), /must be HH:MM/);
+});
+
+test('schedules the next daily run in UTC', () => {
+  const now = new Date('2026-01-01T09:00:00.000Z');
+  assert.equal(getNextDailyRun(600, now).toISOString(), '2026-01-01T10:00:00.000Z');
+  assert.equal(getNextDailyRun(540, now).toISOString(), '2026-01-02T09:00:00.000Z');
+});
+
+test('labels alert emails with the pool and test network', () => {
+  assert.equal(
+    formatAlertEmail(alert, { poolName: 'Demo Pool', network: 'signet' }).subject,
+    '[SV2 Mining · Demo Pool · Signet] Alert: Monitoring API unreachable',
+  );
+  assert.equal(
+    formatAlertEmail({ ...alert, status: 'resolved', message: 'Resolved: Monitoring API unreachable' }).subject,
+    '[SV2 Mining] Resolved: Monitoring API unreachable',
+  );
+});
+
+test('encodes non-ASCII subjects and base64 bodies', () => {
+  const config = parseEmailConfig(baseEnv) as EmailConfig;
+  const mime = buildMimeMessage(config, { subject: 'Pool · down', text: 'hi' }, new Date('2026-01-01T00:00:00Z'));
+  assert.match(mime, /^Subject: =\?UTF-8\?B\?UG9vbCDCtyBkb3du\?=\r$/m);
+  assert.match(mime, /\r\n\r\naGk=\r\n$/);
+});
+
+test('delivers a message through an SMTP session', async (t) => {
+  const commands: string[] = [];
+  const server = net.createServer((socket) => {
+    let buffer = '';
+    let inData = false;
+    socket.write('220 test ESMTP\r\n');
+    socket.on('data', (chunk) => {
+      buffer += chunk.toString();
+      let index;
+      while ((index = buffer.indexOf('\r\n')) !== -1) {
+        const line = buffer.slice(0, index);
+        buffer = buffer.slice(index + 2);
+        if (inData) {
+          if (line === '.') {
+            inData = false;
+            socket.write('250 queued\r\n');
+          }
+          continue;
+        }
+        commands.push(line);
+        if (line.startsWith('EHLO')) socket.write('250-test\r\n250 8BITMIME\r\n');
+        else if (line === 'DATA') {
+          inData = true;
+          socket.write('354 go ahead\r\n');
+        } else if (line === 'QUIT') socket.end('221 bye\r\n');
+        else socket.write('250 ok\r\n');
+      }
+    });
+  });
+  server.listen(0, '127.0.0.1');
+  await new Promise((resolve) => server.once('listening', resolve));
+  t.after(() => server.close());
+
+  const { port } = server.address() as net.AddressInfo;
+  const config = parseEmailConfig({
+    ...baseEnv,
+    SMTP_HOST: '127.0.0.1',
+    SMTP_PORT: String(port),
+    SMTP_TLS: 'none',
+    ALERT_EMAIL_TO: 'a@x.io,b@x.io',
+  }) as EmailConfig;
+
+  await sendEmail(config, formatAlertEmail(alert));
+  assert.deepEqual(commands, [
+    'EHLO example.com',
+    'MAIL FROM:<miner@example.com>',
+    'RCPT TO:<a@x.io>',
+    'RCPT TO:<b@x.io>',
+    'DATA',
+    'QUIT',
+  ]);
+});
+
+test('refuses to continue without STARTTLS when it is required', async (t) => {
+  const server = net.createServer((socket) => {
+    socket.write('220 test ESMTP\r\n');
+    socket.on('data', () => socket.write('250-test\r\n250 8BITMIME\r\n'));
+  });
+  server.listen(0, '127.0.0.1');
+  await new Promise((resolve) => server.once('listening', resolve));
+  t.after(() => server.close());
+
+  const { port } = server.address() as net.AddressInfo;
+  const config = parseEmailConfig({ ...baseEnv, SMTP_HOST: '127.0.0.1', SMTP_PORT: String(port) }) as EmailConfig;
+  await assert.rejects(sendEmail(config, formatAlertEmail(alert)), /does not offer STARTTLS/);
+});
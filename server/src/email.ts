@@ -0,0 +1,340 @@
+/**
+ * Email notifications over SMTP for alert transitions and a daily summary.
+ *
+ * Set SMTP_HOST, ALERT_EMAIL_FROM and ALERT_EMAIL_TO (comma-separated) to
+ * enable. SMTP_TLS picks how the connection is secured:
+ *
+ * - starttls (default, port 587): upgrade after EHLO; fails if the server
+ *   doesn't offer STARTTLS
+ * - tls (default on port 465): TLS from the first byte
+ * - none: plain text, only for local relays
+ *
+ * SMTP_USER and SMTP_PASSWORD enable AUTH PLAIN, which is refused without TLS.
+ * ALERT_EMAIL_DAILY_AT (HH:MM, UTC) also sends a summary once a day.
+ */
+
+import crypto from 'crypto';
+import net from 'net';
+import tls from 'tls';
+import { formatHashrate, formatUptime } from '@sv2-ui/shared';
+import type { AlertTransition } from './alerts.js';
+import type { StackEvent } from './events.js';
+import { DEFAULT_MESSAGES, formatAlertText, formatEventText } from './notification-messages.js';
+import type { Messages } from './notification-messages.js';
+import { getTestNetworkLabel } from './summary.js';
+import type { StackSummary } from './summary.js';
+import type { NotificationContext } from './webhooks.js';
+
+export type SmtpTls = 'starttls' | 'tls' | 'none';
+
+export interface EmailConfig {
+  host: string;
+  port: number;
+  tls: SmtpTls;
+  user: string | null;
+  password: string | null;
+  from: string;
+  to: string[];
+  // Minutes after midnight UTC; null disables the daily summary.
+  dailyAt: number | null;
+}
+
+export interface EmailMessage {
+  subject: string;
+  text: string;
+}
+
+const SMTP_TLS_MODES: SmtpTls[] = ['starttls', 'tls', 'none'];
+const SMTP_TIMEOUT_MS = 15_000;
+const ADDRESS_PATTERN = /^[^\s@<>]+@[^\s@<>]+$/;
+const DAILY_AT_PATTERN = /^([01]\d|2[0-3]):([0-5]\d)$/;
+
+export function parseEmailConfig(env: NodeJS.ProcessEnv = process.env): EmailConfig | null {
+  const host = env.SMTP_HOST?.trim();
+  if (!host) return null;
+
+  const tlsMode = (env.SMTP_TLS?.trim() || (env.SMTP_PORT?.trim() === '465' ? 'tls' : 'starttls')) as SmtpTls;
+  if (!SMTP_TLS_MODES.includes(tlsMode)) {
+    throw new Error(`SMTP_TLS must be one of ${SMTP_TLS_MODES.join(', ')}`);
+  }
+
+  const rawPort = env.SMTP_PORT?.trim();
+  const port = rawPort ? Number(rawPort) : tlsMode === 'tls' ? 465 : tlsMode === 'starttls' ? 587 : 25;
+  if (!Number.isInteger(port) || port < 1 || port > 65535) {
+    throw new Error(`Invalid SMTP_PORT "${rawPort}"`);
+  }
+
+  const user = env.SMTP_USER?.trim() || null;
+  const password = env.SMTP_PASSWORD || null;
+  if (Boolean(user) !== Boolean(password)) {
+    throw new Error('SMTP_USER and SMTP_PASSWORD must be set together');
+  }
+  if (user && tlsMode === 'none') {
+    throw new Error('SMTP_USER requires SMTP_TLS=starttls or tls');
+  }
+
+  const from = env.ALERT_EMAIL_FROM?.trim() ?? '';
+  if (!ADDRESS_PATTERN.test(from)) {
+    throw new Error('ALERT_EMAIL_FROM must be an email address');
+  }
+  const to = (env.ALERT_EMAIL_TO ?? '').split(',').map((address) => address.trim()).filter(Boolean);
+  if (to.length === 0) {
+    throw new Error('ALERT_EMAIL_TO is required with SMTP_HOST');
+  }
+  const invalid = to.find((address) => !ADDRESS_PATTERN.test(address));
+  if (invalid) {
+    throw new Error(`Invalid ALERT_EMAIL_TO address "${invalid}"`);
+  }
+
+  const rawDailyAt = env.ALERT_EMAIL_DAILY_AT?.trim();
+  let dailyAt: number | null = null;
+  if (rawDailyAt) {
+    const match = DAILY_AT_PATTERN.exec(rawDailyAt);
+    if (!match) {
+      throw new Error(`ALERT_EMAIL_DAILY_AT must be HH:MM, got "${rawDailyAt}"`);
+    }
+    dailyAt = Number(match[1]) * 60 + Number(match[2]);
+  }
+
+  return { host, port, tls: tlsMode, user, password, from, to, dailyAt };
+}
+
+/**
+ * Next time a daily job at `dailyAt` (minutes after midnight UTC) runs.
+ */
+export function getNextDailyRun(dailyAt: number, now = new Date()): Date {
+  const next = new Date(now);
+  next.setUTCHours(Math.floor(dailyAt / 60), dailyAt % 60, 0, 0);
+  if (next.getTime() <= now.getTime()) {
+    next.setUTCDate(next.getUTCDate() + 1);
+  }
+  return next;
+}
+
+function subjectPrefix({ poolName, network }: NotificationContext): string {
+  const networkLabel = getTestNetworkLabel(network);
+  return `[${['SV2 Mining', poolName, networkLabel].filter(Boolean).join(' · ')}]`;
+}
+
+export function formatAlertEmail(
+  alert: AlertTransition,
+  context: NotificationContext = {},
+  messages: Messages = DEFAULT_MESSAGES,
+): EmailMessage {
+  const status = messages.t(alert.status === 'firing' ? 'alert.firing' : 'alert.resolved');
+  const message = formatAlertText(alert, messages);
+  return {
+    subject: `${subjectPrefix(context)} ${status}: ${message}`,
+    text: `${status}: ${message}\n\n${messages.t('field.rule')}: ${alert.ruleId} (${alert.kind})\n${messages.t('field.at')}: ${alert.at}\n`,
+  };
+}
+
+export function formatEventEmail(
+  event: StackEvent,
+  context: NotificationContext = {},
+  messages: Messages = DEFAULT_MESSAGES,
+): EmailMessage {
+  const { title, detail } = formatEventText(event, messages);
+  return {
+    subject: `${subjectPrefix(context)} ${title}`,
+    text: `${title}\n\n${detail}\n${messages.t('field.at')}: ${event.at}\n`,
+  };
+}
+
+export function formatDailySummaryEmail(
+  summary: StackSummary,
+  events: StackEvent[],
+  messages: Messages = DEFAULT_MESSAGES,
+): EmailMessage {
+  const { t } = messages;
+  const lines = [
+    `${t('summary.status')}: ${t(summary.online ? 'summary.online' : 'summary.offline')}`,
+    `${t('summary.hashrate')}: ${formatHashrate(summary.hashrate)}`,
+    `${t('summary.workers')}: ${summary.workers}`,
+    `${t('summary.sharesAccepted')}: ${summary.sharesAccepted}`,
+    `${t('summary.sharesRejected')}: ${summary.sharesRejected}`,
+    `${t('summary.blocksFound')}: ${summary.blocksFound}`,
+    `${t('summary.uptime')}: ${formatUptime(summary.uptimeSecs)}`,
+  ];
+  const since = Date.parse(summary.generatedAt) - 86_400_000;
+  const recent = events.filter((event) => Date.parse(event.at) > since);
+
+  return {
+    subject: `${subjectPrefix(summary)} ${t('summary.title')}`,
+    text: [
+      ...lines,
+      '',
+      t(recent.length > 0 ? 'summary.recent' : 'summary.noRecent'),
+      ...recent.map((event) => `- ${event.at} ${formatEventText(event, messages).title}`),
+      '',
+    ].join('\n'),
+  };
+}
+
+function encodeHeader(value: string): string {
+  // RFC 2047 encoded-word for anything outside printable ASCII.
+  return /^[\x20-\x7e]*$/.test(value) ? value : `=?UTF-8?B?${Buffer.from(value).toString('base64')}?=`;
+}
+
+/**
+ * RFC 5322 message with CRLF line endings, ready for DATA (before dot-stuffing).
+ */
+export function buildMimeMessage(config: EmailConfig, message: EmailMessage, date = new Date()): string {
+  const domain = config.from.split('@')[1];
+  const headers = [
+    `From: ${config.from}`,
+    `To: ${config.to.join(', ')}`,
+    `Subject: ${encodeHeader(message.subject)}`,
+    `Date: ${date.toUTCString()}`,
+    `Message-ID: <${crypto.randomUUID()}@${domain}>`,
+    'MIME-Version: 1.0',
+    'Content-Type: text/plain; charset=utf-8',
+    'Content-Transfer-Encoding: base64',
+  ];
+  const body = Buffer.from(message.text).toString('base64').replace(/.{1,76}/g, '$&\r\n');
+  return `${headers.join('\r\n')}\r\n\r\n${body}`;
+}
+
+interface SmtpReply {
+  code: number;
+  lines: string[];
+}
+
+function createReplyReader(socket: net.Socket) {
+  let buffer = '';
+  const replies: SmtpReply[] = [];
+  const waiters: Array<{ resolve: (reply: SmtpReply) => void; reject: (error: Error) => void }> = [];
+  let lines: string[] = [];
+  let failure: Error | null = null;
+
+  const onData = (chunk: Buffer) => {
+    buffer += chunk.toString('utf-8');
+    let index;
+    while ((index = buffer.indexOf('\r\n')) !== -1) {
+      const line = buffer.slice(0, index);
+      buffer = buffer.slice(index + 2);
+      lines.push(line.slice(4));
+      // "250-" continues a multi-line reply, "250 " ends it.
+      if (line[3] !== '-') {
+        const reply = { code: Number(line.slice(0, 3)), lines };
+        lines = [];
+        const waiter = waiters.shift();
+        if (waiter) waiter.resolve(reply);
+        else replies.push(reply);
+      }
+    }
+  };
+  const onError = (error: Error) => {
+    failure = error;
+    for (const waiter of waiters.splice(0)) waiter.reject(error);
+  };
+  const onClose = () => onError(new Error('SMTP connection closed'));
+
+  socket.on('data', onData);
+  socket.on('error', onError);
+  socket.on('close', onClose);
+
+  return {
+    read(): Promise<SmtpReply> {
+      const reply = replies.shift();
+      if (reply) return Promise.resolve(reply);
+      if (failure) return Promise.reject(failure);
+      return new Promise((resolve, reject) => waiters.push({ resolve, reject }));
+    },
+    detach(): void {
+      socket.off('data', onData);
+      socket.off('error', onError);
+      socket.off('close', onClose);
+    },
+  };
+}
+
+function connect(config: EmailConfig): Promise<net.Socket> {
+  return new Promise((resolve, reject) => {
+    const socket = config.tls === 'tls'
+      ? tls.connect({ host: config.host, port: config.port, servername: config.host }, () => resolve(socket))
+      : net.connect({ host: config.host, port: config.port }, () => resolve(socket));
+    socket.once('error', reject);
+    socket.setTimeout(SMTP_TIMEOUT_MS, () => socket.destroy(new Error('SMTP connection timed out')));
+  });
+}
+
+function upgradeToTls(socket: net.Socket, host: string): Promise<tls.TLSSocket> {
+  return new Promise((resolve, reject) => {
+    const secure = tls.connect({ socket, servername: host }, () => resolve(secure));
+    secure.once('error', reject);
+  });
+}
+
+/**
+ * Deliver one message to every recipient in a single SMTP session.
+ */
+export async function sendEmail(config: EmailConfig, message: EmailMessage): Promise<void> {
+  let socket = await connect(config);
+  let reader = createReplyReader(socket);
+
+  async function expect(code: number, command?: string): Promise<SmtpReply> {
+    if (command !== undefined) socket.write(`${command}\r\n`);
+    const reply = await reader.read();
+    if (reply.code !== code) {
+      // Don't echo credentials back in errors.
+      const sent = command?.startsWith('AUTH') ? 'AUTH' : command ?? 'greeting';
+      throw new Error(`SMTP ${sent} failed: ${reply.code} ${reply.lines.join(' ')}`);
+    }
+    return reply;
+  }
+
+  try {
+    const hostname = config.from.split('@')[1];
+    await expect(220);
+    const ehlo = await expect(250, `EHLO ${hostname}`);
+
+    if (config.tls === 'starttls') {
+      if (!ehlo.lines.some((line) => /^STARTTLS\b/i.test(line))) {
+        throw new Error('SMTP server does not offer STARTTLS');
+      }
+      await expect(220, 'STARTTLS');
+      reader.detach();
+      socket = await upgradeToTls(socket, config.host);
+      reader = createReplyReader(socket);
+      await expect(250, `EHLO ${hostname}`);
+    }
+
+    if (config.user && config.password) {
+      const credentials = Buffer.from(`\0${config.user}\0${config.password}`).toString('base64');
+      await expect(235, `AUTH PLAIN ${credentials}`);
+    }
+
+    await expect(250, `MAIL FROM:<${config.from}>`);
+    for (const recipient of config.to) {
+      await expect(250, `RCPT TO:<${recipient}>`);
+    }
+    await expect(354, 'DATA');
+    // Lines starting with "." are escaped by doubling it.
+    const data = buildMimeMessage(config, message).replace(/^\./gm, '..');
+    await expect(250, `${data}\r\n.`);
+    await expect(221, 'QUIT').catch(() => undefined);
+  } finally {
+    reader.detach();
+    socket.destroy();
+  }
+}
+
+export interface EmailNotifierDeps {
+  send?: (config: EmailConfig, message: EmailMessage) => Promise<void>;
+  messages?: Messages;
+}
+
+export function createEmailNotifier(config: EmailConfig, deps: EmailNotifierDeps = {}) {
+  const send = deps.send ?? sendEmail;
+  const messages = deps.messages ?? DEFAULT_MESSAGES;
+
+  return {
+    send: (message: EmailMessage) => send(config, message),
+    notifyAlert: (alert: AlertTransition, context?: NotificationContext) => send(config, formatAlertEmail(alert, context, messages)),
+    notifyEvent: (event: StackEvent, context?: NotificationContext) => send(config, formatEventEmail(event, context, messages)),
+    sendDailySummary: (summary: StackSummary, events: StackEvent[]) => send(config, formatDailySummaryEmail(summary, events, messages)),
+  };
+}
+
+export type EmailNotifier = ReturnType<typeof createEmailNotifier>;
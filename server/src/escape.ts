@@ -0,0 +1,11 @@
+/**
+ * Escape text for HTML/XML element content and attribute values.
+ */
+export function escapeMarkup(value: string): string {
+  return value
+    .replace(/&/g, '&amp;')
+    .replace(/</g, '&lt;')
+    .replace(/>/g, '&gt;')
+    .replace(/"/g, '&quot;')
+    .replace(/'/g, '&#39;');
+}
@@ -0,0 +1,84 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { createEventLog, detectEvents } from './events.js';
+import type { StackSummary } from './summary.js';
+
+function summary(overrides: Partial<StackSummary> = {}): StackSummary {
+  return {
+    generatedAt: '2026-01-01T00:00:00.000Z',
+    miningMode: 'solo',
+    mode: 'jd',
+    network: null,
+    poolName: null,
+    online: true,
+    hashrate: 1e12,
+    workers: 1,
+    sharesAccepted: 0,
+    sharesSubmitted: 0,
+    sharesRejected: 0,
+    bestDifficulty: 1000,
+    blocksFound: 0,
+    uptimeSecs: 60,
+    ...overrides,
+  };
+}
+
+test('reports outages and recoveries on transitions only', () => {
+  assert.deepEqual(detectEvents(summary(), summary({ online: false }), 1000).map((event) => event.kind), ['incident']);
+  assert.deepEqual(detectEvents(summary({ online: false }), summary({ online: false }), 1000), []);
+  assert.deepEqual(detectEvents(summary({ online: false }), summary(), 1000).map((event) => event.kind), ['recovered']);
+  assert.deepEqual(detectEvents(null, summary({ online: false }), 1000), []);
+});
+
+test('counts new blocks but ignores counter resets', () => {
+  const [event] = detectEvents(summary({ blocksFound: 1 }), summary({ blocksFound: 3 }), 1000);
+  assert.equal(event.kind, 'block-found');
+  assert.equal(event.title, '2 blocks found');
+
+  assert.deepEqual(detectEvents(summary({ blocksFound: 3 }), summary({ blocksFound: 0 }), 1000), []);
+});
+
+test('only beats of the all-time best share are announced', () => {
+  assert.deepEqual(detectEvents(null, summary({ bestDifficulty: 5000 }), 0), []);
+  assert.deepEqual(detectEvents(null, summary({ bestDifficulty: 5000 }), 8000), []);
+
+  const [event] = detectEvents(null, summary({ bestDifficulty: 5000 }), 2000);
+  assert.equal(event.kind, 'best-share');
+  assert.equal(event.title, 'New best share: 5.00K');
+});
+
+test('persists events newest first and keeps the best share across restarts', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-events-'));
+  const filePath = path.join(dir, 'events.json');
+  const log = createEventLog(filePath, 2);
+
+  await log.record(summary());
+  await log.record(summary({ online: false }));
+  await log.record(summary({ blocksFound: 0 }));
+  await log.record(summary({ blocksFound: 1, bestDifficulty: 4000 }));
+
+  assert.deepEqual((await log.list()).map((event) => event.kind), ['best-share', 'block-found']);
+
+  const restarted = createEventLog(filePath, 2);
+  assert.deepEqual(await restarted.record(summary({ bestDifficulty: 3000 })), []);
+});
+
+test('paused logs record nothing and resume from a fresh baseline', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-events-'));
+  const log = createEventLog(path.join(dir, 'events.json'));
+
+  await log.record(summary());
+  const resume = log.pause();
+  assert.equal(log.isPaused(), true);
+  assert.deepEqual(await log.record(summary({ online: false })), []);
+
+  resume();
+  resume();
+  assert.equal(log.isPaused(), false);
+  // The outage happened while paused, so coming back isn't a recovery.
+  assert.deepEqual(await log.record(summary()), []);
+  assert.deepEqual(await log.list(), []);
+});
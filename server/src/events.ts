@@ -0,0 +1,170 @@
+/**
+ * Notable stack events (blocks found, new best shares, outages), derived by
+ * comparing consecutive stack summaries and kept in a capped log on disk.
+ */
+
+import crypto from 'crypto';
+import fs from 'fs/promises';
+import path from 'path';
+import { formatDifficulty } from '@sv2-ui/shared';
+import type { StackSummary } from './summary.js';
+
+export type StackEventKind = 'block-found' | 'best-share' | 'incident' | 'recovered';
+
+export interface StackEvent {
+  id: string;
+  kind: StackEventKind;
+  title: string;
+  detail: string;
+  at: string;
+  // Values for the localized title and detail (see notification-messages.ts).
+  params?: Record<string, string | number>;
+}
+
+export type NewStackEvent = Omit<StackEvent, 'id' | 'at'>;
+
+interface EventLogState {
+  events: StackEvent[];
+  // All-time best, so a channel reconnecting (which resets its stats) doesn't
+  // re-announce an old best share.
+  bestDifficulty: number;
+}
+
+export const DEFAULT_EVENT_LOG_LIMIT = 200;
+
+/**
+ * Compare two consecutive summaries and describe what changed.
+ */
+export function detectEvents(
+  previous: StackSummary | null,
+  current: StackSummary,
+  bestDifficulty: number,
+): NewStackEvent[] {
+  const events: NewStackEvent[] = [];
+
+  if (previous && previous.online !== current.online) {
+    events.push(current.online
+      ? { kind: 'recovered', title: 'Mining services recovered', detail: 'The monitoring API is reachable again.' }
+      : { kind: 'incident', title: 'Mining services unreachable', detail: 'The monitoring API stopped responding.' });
+  }
+
+  if (!current.online) {
+    return events;
+  }
+
+  // Counters restart from zero when the services restart, so only count growth.
+  const newBlocks = previous?.online ? current.blocksFound - previous.blocksFound : 0;
+  if (newBlocks > 0) {
+    events.push({
+      kind: 'block-found',
+      title: newBlocks === 1 ? 'Block found' : `${newBlocks} blocks found`,
+      detail: `${current.blocksFound} block(s) found since the services started.`,
+      params: { count: newBlocks, total: current.blocksFound },
+    });
+  }
+
+  if (bestDifficulty > 0 && current.bestDifficulty > bestDifficulty) {
+    events.push({
+      kind: 'best-share',
+      title: `New best share: ${formatDifficulty(current.bestDifficulty)}`,
+      detail: `Previous best was ${formatDifficulty(bestDifficulty)}.`,
+      params: { difficulty: formatDifficulty(current.bestDifficulty), previous: formatDifficulty(bestDifficulty) },
+    });
+  }
+
+  return events;
+}
+
+export function createEventLog(filePath: string, limit = DEFAULT_EVENT_LOG_LIMIT) {
+  let previous: StackSummary | null = null;
+  let pauses = 0;
+
+  async function read(): Promise<EventLogState> {
+    try {
+      const state = JSON.parse(await fs.readFile(filePath, 'utf-8')) as Partial<EventLogState>;
+      return {
+        events: Array.isArray(state.events) ? state.events : [],
+        bestDifficulty: state.bestDifficulty ?? 0,
+      };
+    } catch {
+      return { events: [], bestDifficulty: 0 };
+    }
+  }
+
+  async function write(state: EventLogState): Promise<void> {
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    await fs.writeFile(filePath, JSON.stringify(state, null, 2));
+  }
+
+  /**
+   * Most recent first.
+   */
+  async function list(): Promise<StackEvent[]> {
+    return (await read()).events;
+  }
+
+  /**
+   * Feed the next summary sample; returns any events it produced.
+   */
+  async function record(current: StackSummary): Promise<StackEvent[]> {
+    if (pauses > 0) {
+      previous = null;
+      return [];
+    }
+
+    const state = await read();
+    const detected = detectEvents(previous, current, state.bestDifficulty).map((event) => ({
+      id: crypto.randomUUID(),
+      at: current.generatedAt,
+      ...event,
+    }));
+    previous = current;
+
+    const bestDifficulty = current.online ? Math.max(state.bestDifficulty, current.bestDifficulty) : state.bestDifficulty;
+    if (detected.length > 0 || bestDifficulty !== state.bestDifficulty) {
+      await write({
+        events: [...detected.reverse(), ...state.events].slice(0, limit),
+        bestDifficulty,
+      });
+    }
+
+    return detected;
+  }
+
+  /**
+   * Replace a recorded event, e.g. once the node has said which block it was.
+   */
+  async function update(event: StackEvent): Promise<void> {
+    const state = await read();
+    if (!state.events.some((existing) => existing.id === event.id)) return;
+    await write({ ...state, events: state.events.map((existing) => (existing.id === event.id ? event : existing)) });
+  }
+
+  /**
+   * Forget the previous sample, e.g. when the stack is stopped on purpose.
+   */
+  function resetBaseline(): void {
+    previous = null;
+  }
+
+  /**
+   * Suppress events during planned disruption (e.g. a warm restart). Pauses
+   * nest; sampling resumes from a fresh baseline once all are released.
+   */
+  function pause(): () => void {
+    pauses += 1;
+    previous = null;
+
+    let released = false;
+    return () => {
+      if (released) return;
+      released = true;
+      pauses -= 1;
+      previous = null;
+    };
+  }
+
+  return { list, record, update, resetBaseline, pause, isPaused: () => pauses > 0 };
+}
+
+export type EventLog = ReturnType<typeof createEventLog>;
@@ -0,0 +1,154 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import { createFederation, parseFederationSite, parseFederationSites, readHistoryCsv, toPublicSite } from './federation.js';
+import type { FederationSite } from './federation.js';
+
+const summary = (hashrate: number, online = true) => ({
+  generatedAt: '2026-01-01T00:00:00.000Z',
+  miningMode: 'pool',
+  mode: 'jd',
+  network: 'mainnet',
+  poolName: null,
+  online,
+  hashrate,
+  workers: 10,
+  sharesAccepted: 100,
+  sharesSubmitted: 101,
+  sharesRejected: 1,
+  bestDifficulty: hashrate / 1e9,
+  blocksFound: 0,
+  uptimeSecs: 60,
+});
+
+test('parseFederationSites validates sites and keeps stored tokens unless replaced', () => {
+  const previous: FederationSite[] = [{ id: 'north', name: 'North', url: 'https://north.example', token: 'sv2k_north' }];
+  const sites = parseFederationSites({
+    sites: [
+      { id: 'north', name: 'North', url: 'https://north.example/sv2/' },
+      { id: 'south', name: ' South ', url: 'http://10.0.2.5:3001', token: 'sv2k_south' },
+    ],
+  }, previous);
+  assert.deepEqual(sites, [
+    { id: 'north', name: 'North', url: 'https://north.example/sv2', token: 'sv2k_north' },
+    { id: 'south', name: 'South', url: 'http://10.0.2.5:3001', token: 'sv2k_south' },
+  ]);
+  assert.equal(parseFederationSites({ sites: [{ id: 'north', name: 'North', url: 'https://north.example', token: null }] }, previous)[0].token, null);
+  assert.deepEqual(toPublicSite(sites[0]), { id: 'north', name: 'North', url: 'https://north.example/sv2', hasToken: true });
+
+  assert.throws(() => parseFederationSites({}), /sites must be an array/);
+  assert.throws(() => parseFederationSites({ sites: [{ id: 'North', name: 'x', url: 'https://x' }] }), /sites\[0\]\.id must be/);
+  assert.throws(() => parseFederationSites({ sites: [{ id: 'a', name: 'x', url: 'ftp://x' }] }), /sites\[0\]\.url must be http\(s\)/);
+  assert.throws(() => parseFederationSites({ sites: [{ id: 'a', name: 'x', url: 'https://x' }, { id: 'a', name: 'y', url: 'https://y' }] }), /used more than once/);
+  assert.throws(() => parseFederationSite({ id: 'a', name: ' ', url: 'https://x' }), /site\.name is required/);
+});
+
+test('the fleet adds up the sites that answer, and caches their summaries', async () => {
+  const sites: FederationSite[] = [
+    { id: 'north', name: 'North', url: 'https://north.example', token: 'sv2k_north' },
+    { id: 'south', name: 'South', url: 'https://south.example', token: null },
+    { id: 'east', name: 'East', url: 'https://east.example', token: null },
+  ];
+  const requests: string[] = [];
+  let now = Date.parse('2026-01-01T00:00:00.000Z');
+  const federation = createFederation({
+    getSites: async () => sites,
+    request: async (site, path) => {
+      requests.push(`${site.id} ${path}`);
+      if (site.id === 'east') throw new Error('fetch failed');
+      return Response.json(summary(site.id === 'north' ? 100e12 : 50e12));
+    },
+    now: () => new Date(now),
+  });
+
+  const fleet = await federation.fleet();
+  assert.deepEqual(fleet.total, {
+    sites: 3,
+    sitesOnline: 2,
+    hashrate: 150e12,
+    workers: 20,
+    sharesAccepted: 200,
+    sharesSubmitted: 202,
+    sharesRejected: 2,
+    bestDifficulty: 100e3,
+    blocksFound: 0,
+  });
+  assert.deepEqual(fleet.sites.map(({ id, ok, error, hasToken }) => [id, ok, error, hasToken]), [
+    ['north', true, null, true],
+    ['south', true, null, false],
+    ['east', false, 'fetch failed', false],
+  ]);
+
+  now += 5_000;
+  await federation.fleet();
+  assert.equal(requests.length, 3);
+  now += 10_000;
+  await federation.fleet();
+  assert.equal(requests.length, 6);
+});
+
+test('fleet history sums the sites per timestamp', async () => {
+  const csv = (rows: string[]) => ['timestamp,metric,key,value', ...rows, ''].join('\r\n');
+  const federation = createFederation({
+    getSites: async () => [
+      { id: 'north', name: 'North', url: 'https://north.example', token: null },
+      { id: 'south', name: 'South', url: 'https://south.example', token: null },
+    ],
+    request: async (site, path) => {
+      assert.equal(path, '/api/v1/history/export?metric=stack.hashrate&range=6h&resolution=1h');
+      return new Response(site.id === 'north'
+        ? csv(['2026-01-01T00:00:00.000Z,stack.hashrate,,100', '2026-01-01T01:00:00.000Z,stack.hashrate,,110'])
+        : csv(['2026-01-01T00:00:00.000Z,stack.hashrate,,50', '2026-01-01T01:00:00.000Z,stack.hashrate,,']));
+    },
+  });
+
+  const history = await federation.history({ range: '6h', resolution: '1h' });
+  assert.deepEqual(history.total, [
+    { at: '2026-01-01T00:00:00.000Z', value: 150 },
+    { at: '2026-01-01T01:00:00.000Z', value: 110 },
+  ]);
+  assert.deepEqual(history.sites[1], { id: 'south', error: null, samples: [{ at: '2026-01-01T00:00:00.000Z', value: 50 }] });
+  assert.deepEqual(readHistoryCsv(csv(['2026-01-01T00:00:00.000Z,stack.workers,,3']), 'stack.hashrate'), []);
+});
+
+test('site health checks read each site\'s own report and remember its last success', async () => {
+  let sites: FederationSite[] = [
+    { id: 'north', name: 'North', url: 'https://north.example', token: 'sv2k_north' },
+    { id: 'south', name: 'South', url: 'https://south.example', token: null },
+    { id: 'east', name: 'East', url: 'https://east.example', token: null },
+  ];
+  let now = Date.parse('2026-01-01T00:00:00.000Z');
+  let southUp = true;
+  const federation = createFederation({
+    getSites: async () => sites,
+    request: async (site, path) => {
+      assert.equal(path, '/api/v1/health');
+      if (site.id === 'north') return Response.json({ status: 'degraded' }, { status: 503 });
+      if (site.id === 'south' && southUp) return Response.json({ status: 'ok' });
+      if (site.id === 'south') throw new Error('fetch failed');
+      return Response.json({ error: 'Authentication required' }, { status: 401 });
+    },
+    now: () => new Date(now),
+  });
+
+  assert.deepEqual(await federation.health(), []);
+  const first = await federation.checkHealth();
+  assert.deepEqual(first.map(({ id, reachable, httpStatus, status, error }) => ({ id, reachable, httpStatus, status, error })), [
+    { id: 'north', reachable: true, httpStatus: 503, status: 'degraded', error: null },
+    { id: 'south', reachable: true, httpStatus: 200, status: 'ok', error: null },
+    { id: 'east', reachable: false, httpStatus: 401, status: null, error: 'HTTP 401: Authentication required' },
+  ]);
+
+  southUp = false;
+  now += 30_000;
+  await federation.checkHealth();
+  const south = (await federation.health()).find((site) => site.id === 'south');
+  assert.equal(south?.reachable, false);
+  assert.equal(south?.error, 'fetch failed');
+  assert.equal(south?.latencyMs, null);
+  assert.equal(south?.lastSuccessAt, '2026-01-01T00:00:00.000Z');
+
+  // A site edited since its last check isn't reported until it's checked again.
+  sites = [{ ...sites[0], url: 'https://north-2.example' }];
+  assert.deepEqual(await federation.health(), []);
+});
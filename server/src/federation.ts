@@ -0,0 +1,346 @@
+/**
+ * Federation: one sv2-ui instance showing a fleet of others, e.g. a stack at
+ * each of three sites.
+ *
+ * Sites live in CONFIG_DIR/federation.json and are registered one by one
+ * (POST and DELETE /api/v1/federation/sites) or replaced together (PUT).
+ * Each is another sv2-ui instance, reached by
+ * its URL (including any BASE_PATH) with an optional bearer token, ideally
+ * one of its API keys. The central instance pulls each site's
+ * /api/summary and hashrate history and adds them up; anything else is
+ * read through /api/v1/federation/sites/<id>/... (see routes/federation.ts).
+ * Every site's /api/v1/health is checked in the background, and the result
+ * is part of this instance's own health report.
+ *
+ * Tokens are stored so they can be sent, but never returned by the API.
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import type { HealthStatus } from './health.js';
+import { getRequestIdHeaders } from './request-context.js';
+import type { StackSummary } from './summary.js';
+
+export interface FederationSite {
+  id: string;
+  name: string;
+  // The instance's root, e.g. https://site-a.example/sv2; no trailing slash.
+  url: string;
+  token: string | null;
+}
+
+export type PublicFederationSite = Omit<FederationSite, 'token'> & { hasToken: boolean };
+
+export interface FleetSite extends PublicFederationSite {
+  ok: boolean;
+  error: string | null;
+  fetchedAt: string | null;
+  summary: StackSummary | null;
+}
+
+export interface FleetTotals {
+  sites: number;
+  sitesOnline: number;
+  hashrate: number;
+  workers: number;
+  sharesAccepted: number;
+  sharesSubmitted: number;
+  sharesRejected: number;
+  bestDifficulty: number;
+  blocksFound: number;
+}
+
+export interface FleetView {
+  generatedAt: string;
+  total: FleetTotals;
+  sites: FleetSite[];
+}
+
+export interface FleetHistoryPoint {
+  at: string;
+  value: number;
+}
+
+export interface FleetHistory {
+  metric: string;
+  range: string;
+  resolution: string;
+  // Summed per timestamp over the sites that have a sample there.
+  total: FleetHistoryPoint[];
+  sites: Array<{ id: string; error: string | null; samples: FleetHistoryPoint[] }>;
+}
+
+export interface SiteHealth {
+  id: string;
+  name: string;
+  reachable: boolean;
+  httpStatus: number | null;
+  latencyMs: number | null;
+  // The site's own health status; null when it couldn't be read.
+  status: HealthStatus | null;
+  lastSuccessAt: string | null;
+  checkedAt: string;
+  error: string | null;
+}
+
+export interface FederationDeps {
+  getSites: () => Promise<FederationSite[]>;
+  // GET `path` (starting with /) on a site.
+  request?: (site: FederationSite, path: string) => Promise<Response>;
+  now?: () => Date;
+}
+
+const SITE_ID_PATTERN = /^[a-z0-9][a-z0-9-]{0,31}$/;
+const REQUEST_TIMEOUT_MS = 10_000;
+// Dashboards refresh often; a site isn't asked for its summary more than this.
+const SUMMARY_CACHE_MS = 10_000;
+const DEFAULT_HISTORY_RANGE = '24h';
+// Rollups line the sites' samples up on the same timestamps.
+const DEFAULT_HISTORY_RESOLUTION = '1m';
+const HISTORY_METRIC = 'stack.hashrate';
+const HEALTH_STATUSES: readonly string[] = ['ok', 'degraded', 'down'];
+
+/**
+ * Validate one site. Without `token` it keeps the one it had in `previous`;
+ * `token: null` clears it.
+ */
+export function parseFederationSite(value: unknown, previous: FederationSite[] = [], field = 'site'): FederationSite {
+  const site = (value ?? {}) as Record<string, unknown>;
+  if (typeof site.id !== 'string' || !SITE_ID_PATTERN.test(site.id)) {
+    throw new Error(`${field}.id must be 1-32 lowercase letters, digits or dashes`);
+  }
+
+  if (typeof site.name !== 'string' || !site.name.trim()) {
+    throw new Error(`${field}.name is required`);
+  }
+
+  let url: URL;
+  try {
+    url = new URL(String(site.url));
+  } catch {
+    throw new Error(`${field}.url must be a URL`);
+  }
+  if (url.protocol !== 'http:' && url.protocol !== 'https:') {
+    throw new Error(`${field}.url must be http(s)`);
+  }
+
+  if (site.token !== undefined && site.token !== null && (typeof site.token !== 'string' || !site.token.trim())) {
+    throw new Error(`${field}.token must be a non-empty string or null`);
+  }
+  const token = site.token === undefined
+    ? previous.find((entry) => entry.id === site.id)?.token ?? null
+    : site.token === null ? null : site.token.trim();
+
+  return { id: site.id, name: site.name.trim(), url: url.toString().replace(/\/+$/, ''), token };
+}
+
+/**
+ * Validate a sites document (see parseFederationSite).
+ */
+export function parseFederationSites(body: unknown, previous: FederationSite[] = []): FederationSite[] {
+  const { sites } = (body ?? {}) as { sites?: unknown };
+  if (!Array.isArray(sites)) {
+    throw new Error('sites must be an array');
+  }
+
+  const ids = new Set<string>();
+  return sites.map((value, index) => {
+    const site = parseFederationSite(value, previous, `sites[${index}]`);
+    if (ids.has(site.id)) {
+      throw new Error(`sites[${index}].id "${site.id}" is used more than once`);
+    }
+    ids.add(site.id);
+    return site;
+  });
+}
+
+export function toPublicSite({ token, ...site }: FederationSite): PublicFederationSite {
+  return { ...site, hasToken: token !== null };
+}
+
+export function createFederationRegistry(filePath: string) {
+  async function get(): Promise<FederationSite[]> {
+    try {
+      return parseFederationSites(JSON.parse(await fs.readFile(filePath, 'utf-8')));
+    } catch {
+      return [];
+    }
+  }
+
+  async function save(sites: FederationSite[]): Promise<void> {
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    // The tokens grant access to the sites.
+    await fs.writeFile(filePath, JSON.stringify({ sites }, null, 2), { mode: 0o600 });
+  }
+
+  return { get, save };
+}
+
+export type FederationRegistry = ReturnType<typeof createFederationRegistry>;
+
+export function requestSite(site: FederationSite, requestPath: string): Promise<Response> {
+  return fetch(`${site.url}${requestPath}`, {
+    headers: { Accept: 'application/json', ...getRequestIdHeaders(), ...(site.token ? { Authorization: `Bearer ${site.token}` } : {}) },
+    signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+  });
+}
+
+export function sumFleet(sites: FleetSite[]): FleetTotals {
+  const online = sites.filter((site) => site.summary?.online);
+  const sum = (field: 'hashrate' | 'workers' | 'sharesAccepted' | 'sharesSubmitted' | 'sharesRejected' | 'blocksFound') =>
+    online.reduce((total, site) => total + (site.summary?.[field] ?? 0), 0);
+  return {
+    sites: sites.length,
+    sitesOnline: online.length,
+    hashrate: sum('hashrate'),
+    workers: sum('workers'),
+    sharesAccepted: sum('sharesAccepted'),
+    sharesSubmitted: sum('sharesSubmitted'),
+    sharesRejected: sum('sharesRejected'),
+    bestDifficulty: Math.max(0, ...online.map((site) => site.summary?.bestDifficulty ?? 0)),
+    blocksFound: sum('blocksFound'),
+  };
+}
+
+/**
+ * The samples of a history export (timestamp,metric,key,value), skipping
+ * gaps and other metrics' rows.
+ */
+export function readHistoryCsv(csv: string, metric: string): FleetHistoryPoint[] {
+  return csv.split(/\r?\n/).slice(1).flatMap((line) => {
+    const [at, rowMetric, , value] = line.split(',');
+    if (rowMetric !== metric || !value) return [];
+    const number = Number(value);
+    return Number.isFinite(number) ? [{ at, value: number }] : [];
+  });
+}
+
+async function readError(response: Response): Promise<string> {
+  const body = await response.json().catch(() => null) as { error?: unknown } | null;
+  return typeof body?.error === 'string' ? `HTTP ${response.status}: ${body.error}` : `HTTP ${response.status}`;
+}
+
+export function createFederation({ getSites, request = requestSite, now = () => new Date() }: FederationDeps) {
+  const summaries = new Map<string, { key: string; fetchedAt: number; result: Promise<FleetSite> }>();
+  const healthChecks = new Map<string, { key: string; health: SiteHealth }>();
+
+  async function fetchSummary(site: FederationSite): Promise<FleetSite> {
+    const base = { ...toPublicSite(site), fetchedAt: now().toISOString() };
+    try {
+      const response = await request(site, '/api/summary');
+      if (!response.ok) return { ...base, ok: false, error: await readError(response), summary: null };
+      return { ...base, ok: true, error: null, summary: await response.json() as StackSummary };
+    } catch (error) {
+      return { ...base, ok: false, error: error instanceof Error ? error.message : String(error), summary: null };
+    }
+  }
+
+  /**
+   * Every site's summary, and the fleet totals of the sites that are online.
+   */
+  async function fleet(): Promise<FleetView> {
+    const sites = await getSites();
+    for (const id of summaries.keys()) {
+      if (!sites.some((site) => site.id === id)) summaries.delete(id);
+    }
+
+    const results = await Promise.all(sites.map((site) => {
+      // A site edited in place is asked again.
+      const key = JSON.stringify(site);
+      const cached = summaries.get(site.id);
+      if (cached?.key === key && now().getTime() - cached.fetchedAt < SUMMARY_CACHE_MS) return cached.result;
+      const result = fetchSummary(site);
+      summaries.set(site.id, { key, fetchedAt: now().getTime(), result });
+      return result;
+    }));
+    return { generatedAt: now().toISOString(), total: sumFleet(results), sites: results };
+  }
+
+  /**
+   * The sites' hashrate over `range`, at `resolution`, and its sum.
+   */
+  async function history({ range = DEFAULT_HISTORY_RANGE, resolution = DEFAULT_HISTORY_RESOLUTION } = {}): Promise<FleetHistory> {
+    const query = new URLSearchParams({ metric: HISTORY_METRIC, range, resolution });
+    const sites = await Promise.all((await getSites()).map(async (site) => {
+      try {
+        const response = await request(site, `/api/v1/history/export?${query}`);
+        if (!response.ok) return { id: site.id, error: await readError(response), samples: [] };
+        return { id: site.id, error: null, samples: readHistoryCsv(await response.text(), HISTORY_METRIC) };
+      } catch (error) {
+        return { id: site.id, error: error instanceof Error ? error.message : String(error), samples: [] };
+      }
+    }));
+
+    const totals = new Map<string, number>();
+    for (const { samples } of sites) {
+      for (const { at, value } of samples) totals.set(at, (totals.get(at) ?? 0) + value);
+    }
+    return {
+      metric: HISTORY_METRIC,
+      range,
+      resolution,
+      total: [...totals].sort(([a], [b]) => a.localeCompare(b)).map(([at, value]) => ({ at, value })),
+      sites,
+    };
+  }
+
+  async function probeSite(site: FederationSite): Promise<SiteHealth> {
+    const startedAt = now().getTime();
+    let httpStatus: number | null = null;
+    let status: HealthStatus | null = null;
+    let error: string | null = null;
+    try {
+      const response = await request(site, '/api/v1/health');
+      httpStatus = response.status;
+      // A degraded site answers 503, still with its report.
+      const body = await response.json().catch(() => null) as { status?: unknown; error?: unknown } | null;
+      if (typeof body?.status === 'string' && HEALTH_STATUSES.includes(body.status)) {
+        status = body.status as HealthStatus;
+      } else {
+        error = typeof body?.error === 'string' ? `HTTP ${response.status}: ${body.error}` : `HTTP ${response.status}`;
+      }
+    } catch (cause) {
+      error = cause instanceof Error ? cause.message : String(cause);
+    }
+
+    const checkedAt = now().toISOString();
+    const reachable = status !== null;
+    return {
+      id: site.id,
+      name: site.name,
+      reachable,
+      httpStatus,
+      latencyMs: httpStatus === null ? null : now().getTime() - startedAt,
+      status,
+      lastSuccessAt: reachable ? checkedAt : healthChecks.get(site.id)?.health.lastSuccessAt ?? null,
+      checkedAt,
+      error,
+    };
+  }
+
+  /**
+   * Check every site's /api/v1/health now; run periodically and after the
+   * sites change.
+   */
+  async function checkHealth(): Promise<SiteHealth[]> {
+    const sites = await getSites();
+    const results = await Promise.all(sites.map(async (site) => ({ key: JSON.stringify(site), health: await probeSite(site) })));
+    healthChecks.clear();
+    for (const result of results) healthChecks.set(result.health.id, result);
+    return results.map(({ health }) => health);
+  }
+
+  /**
+   * The last check of each site, leaving out sites added or edited since.
+   */
+  async function health(): Promise<SiteHealth[]> {
+    return (await getSites()).flatMap((site) => {
+      const checked = healthChecks.get(site.id);
+      return checked?.key === JSON.stringify(site) ? [checked.health] : [];
+    });
+  }
+
+  return { fleet, history, checkHealth, health, size: () => summaries.size };
+}
+
+export type Federation = ReturnType<typeof createFederation>;
@@ -0,0 +1,28 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { renderAtomFeed } from './feed.js';
+
+const OPTIONS = {
+  title: 'SV2 Mining · <Pool>',
+  selfUrl: 'http://miner.local:8080/feed.xml',
+  dashboardUrl: 'http://miner.local:8080/',
+  now: new Date('2026-01-01T00:00:00.000Z'),
+};
+
+test('renders an Atom entry per event with escaped text', () => {
+  const xml = renderAtomFeed([
+    { id: 'b7c1', kind: 'block-found', title: 'Block found', detail: '1 block(s) found & counting.', at: '2026-01-02T00:00:00.000Z' },
+  ], OPTIONS);
+
+  assert.match(xml, /^<\?xml version="1.0" encoding="utf-8"\?>\n<feed xmlns="http:\/\/www\.w3\.org\/2005\/Atom">/);
+  assert.match(xml, /<title>SV2 Mining · &lt;Pool&gt;<\/title>/);
+  assert.match(xml, /<updated>2026-01-02T00:00:00.000Z<\/updated>\n {2}<link rel="self"/);
+  assert.match(xml, /<id>urn:uuid:b7c1<\/id>/);
+  assert.match(xml, /<summary>1 block\(s\) found &amp; counting\.<\/summary>/);
+});
+
+test('an empty feed is still valid and uses the current time', () => {
+  const xml = renderAtomFeed([], OPTIONS);
+  assert.match(xml, /<updated>2026-01-01T00:00:00.000Z<\/updated>/);
+  assert.doesNotMatch(xml, /<entry>/);
+});
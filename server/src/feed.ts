@@ -0,0 +1,37 @@
+/**
+ * Atom feed of stack events, so a farm can be followed from any feed reader.
+ */
+
+import type { StackEvent } from './events.js';
+import { escapeMarkup } from './escape.js';
+
+export interface FeedOptions {
+  title: string;
+  selfUrl: string;
+  dashboardUrl: string;
+  now?: Date;
+}
+
+export function renderAtomFeed(events: StackEvent[], { title, selfUrl, dashboardUrl, now = new Date() }: FeedOptions): string {
+  const updated = events[0]?.at ?? now.toISOString();
+  const entries = events.map((event) => `  <entry>
+    <id>urn:uuid:${escapeMarkup(event.id)}</id>
+    <title>${escapeMarkup(event.title)}</title>
+    <updated>${escapeMarkup(event.at)}</updated>
+    <category term="${escapeMarkup(event.kind)}" />
+    <link href="${escapeMarkup(dashboardUrl)}" />
+    <summary>${escapeMarkup(event.detail)}</summary>
+  </entry>`).join('\n');
+
+  return `<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>${escapeMarkup(selfUrl)}</id>
+  <title>${escapeMarkup(title)}</title>
+  <updated>${escapeMarkup(updated)}</updated>
+  <link rel="self" href="${escapeMarkup(selfUrl)}" />
+  <link href="${escapeMarkup(dashboardUrl)}" />
+  <generator>sv2-ui</generator>
+${entries}
+</feed>
+`;
+}
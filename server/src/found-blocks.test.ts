@@ -0,0 +1,41 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import type { StackEvent } from './events.js';
+import { findFoundBlock, getPayoutScript, withFoundBlock } from './found-blocks.js';
+
+const OURS = `0014${'ab'.repeat(20)}`;
+const block = (height: number, time: string, coinbaseScripts: string[]) => ({
+  hash: `${height}`.padStart(64, '0'),
+  height,
+  time: Date.parse(time) / 1000,
+  coinbaseScripts,
+});
+
+test('the found block is the newest recent one paying the reward address', () => {
+  assert.equal(getPayoutScript(`raw(${OURS})`, 'regtest'), OURS);
+  assert.equal(getPayoutScript('not an address', 'regtest'), null);
+
+  const blocks = [
+    block(101, '2026-01-01T00:10:00.000Z', ['6a24aa21a9ed', `0014${'cd'.repeat(20)}`]),
+    block(100, '2026-01-01T00:05:00.000Z', [OURS, '6a24aa21a9ed']),
+    block(99, '2025-12-31T12:00:00.000Z', [OURS]),
+  ];
+  assert.equal(findFoundBlock(blocks, OURS, '2026-01-01T00:06:00.000Z')?.height, 100);
+  // An older block of ours isn't the one just found.
+  assert.equal(findFoundBlock(blocks.slice(2), OURS, '2026-01-01T00:06:00.000Z'), null);
+});
+
+test('the block goes into the event detail and params', () => {
+  const event: StackEvent = {
+    id: '1',
+    kind: 'block-found',
+    title: 'Block found',
+    detail: '1 block(s) found since the services started.',
+    at: '2026-01-01T00:06:00.000Z',
+    params: { count: 1, total: 1 },
+  };
+  const found = withFoundBlock(event, block(100, '2026-01-01T00:05:00.000Z', [OURS]));
+  assert.equal(found.detail, `1 block(s) found since the services started. Block 100: ${'100'.padStart(64, '0')}.`);
+  assert.deepEqual(found.params, { count: 1, total: 1, height: 100, hash: '100'.padStart(64, '0') });
+});
@@ -0,0 +1,44 @@
+/**
+ * Which block a `block-found` event was about.
+ *
+ * The monitoring APIs only count found blocks. In JD mode the configured
+ * node can tell more: the stack's block is the recent one whose coinbase
+ * pays the JDC's coinbase_reward_address, and its hash and height go into
+ * the event and its notifications. Blocks found through a pool pay the
+ * pool, so they keep the bare count.
+ */
+
+import type { BitcoinNetwork } from '@sv2-ui/shared';
+import { validateCoinbaseScript } from './coinbase-script.js';
+import type { BitcoinRecentBlock } from './docker.js';
+import type { StackEvent } from './events.js';
+
+// Block timestamps may be up to two hours ahead of or behind the real time.
+const BLOCK_TIME_TOLERANCE_SECS = 2 * 3600;
+
+/**
+ * The scriptPubKey hex the JDC pays block rewards to, or null if the
+ * descriptor can't be decoded.
+ */
+export function getPayoutScript(coinbaseRewardAddress: string, network: BitcoinNetwork): string | null {
+  const report = validateCoinbaseScript(coinbaseRewardAddress.trim(), network);
+  return report.valid ? report.scriptPubKey : null;
+}
+
+/**
+ * The newest block paying `payoutScript` that isn't older than the event.
+ */
+export function findFoundBlock(blocks: BitcoinRecentBlock[], payoutScript: string, eventAt: string): BitcoinRecentBlock | null {
+  const since = Date.parse(eventAt) / 1000 - BLOCK_TIME_TOLERANCE_SECS;
+  return [...blocks]
+    .sort((a, b) => b.height - a.height)
+    .find((block) => block.time >= since && block.coinbaseScripts.includes(payoutScript)) ?? null;
+}
+
+export function withFoundBlock(event: StackEvent, block: BitcoinRecentBlock): StackEvent {
+  return {
+    ...event,
+    detail: `${event.detail} Block ${block.height}: ${block.hash}.`,
+    params: { ...event.params, height: block.height, hash: block.hash },
+  };
+}
@@ -0,0 +1,165 @@
+import assert from 'node:assert/strict';
+import http2 from 'node:http2';
+import type { AddressInfo } from 'node:net';
+import { test } from 'node:test';
+
+import { createGrpcService, encodeHealthReport, encodeSummary, parseGrpcListen } from './grpc.js';
+import { parseIpFilter } from './ip-filter.js';
+import type { StackSummary } from './summary.js';
+
+const SUMMARY: StackSummary = {
+  generatedAt: '2026-01-01T00:00:00.000Z',
+  miningMode: 'pool',
+  mode: 'no-jd',
+  network: null,
+  poolName: 'Pool',
+  online: true,
+  hashrate: 1.5e12,
+  workers: 3,
+  sharesAccepted: 300,
+  sharesSubmitted: 302,
+  sharesRejected: 2,
+  bestDifficulty: 12345.5,
+  blocksFound: 0,
+  uptimeSecs: 2 ** 40,
+};
+
+// Decode a message into its fields: varints as numbers, doubles as numbers,
+// and length-delimited fields as buffers.
+function decode(buffer: Buffer): Map<number, (number | Buffer)[]> {
+  const fields = new Map<number, (number | Buffer)[]>();
+  let offset = 0;
+  const readVarint = () => {
+    let value = 0;
+    for (let scale = 1; ; scale *= 0x80) {
+      const byte = buffer[offset++];
+      value += (byte & 0x7f) * scale;
+      if (byte < 0x80) return value;
+    }
+  };
+  while (offset < buffer.length) {
+    const key = readVarint();
+    let value: number | Buffer;
+    if ((key & 7) === 0) value = readVarint();
+    else if ((key & 7) === 1) {
+      value = buffer.readDoubleLE(offset);
+      offset += 8;
+    } else {
+      const length = readVarint();
+      value = buffer.subarray(offset, offset + length);
+      offset += length;
+    }
+    fields.set(key >> 3, [...(fields.get(key >> 3) ?? []), value]);
+  }
+  return fields;
+}
+
+function unframe(data: Buffer): Buffer[] {
+  const messages: Buffer[] = [];
+  for (let offset = 0; offset + 5 <= data.length; offset += 5 + data.readUInt32BE(offset + 1)) {
+    messages.push(data.subarray(offset + 5, offset + 5 + data.readUInt32BE(offset + 1)));
+  }
+  return messages;
+}
+
+test('parseGrpcListen stays off unless GRPC_LISTEN is set', () => {
+  assert.equal(parseGrpcListen({}), null);
+  assert.deepEqual(parseGrpcListen({ GRPC_LISTEN: '127.0.0.1' }), [{ host: '127.0.0.1', port: 50051 }]);
+  assert.throws(() => parseGrpcListen({ GRPC_LISTEN: ':99999' }), /Invalid port/);
+});
+
+test('messages encode every set field and leave unset ones out', () => {
+  const summary = decode(encodeSummary(SUMMARY));
+  assert.equal(String(summary.get(1)?.[0]), SUMMARY.generatedAt);
+  assert.equal(summary.has(4), false);
+  assert.equal(String(summary.get(5)?.[0]), 'Pool');
+  assert.equal(summary.get(6)?.[0], 1);
+  assert.equal(summary.get(7)?.[0], 1.5e12);
+  assert.equal(summary.get(12)?.[0], 12345.5);
+  assert.equal(summary.get(14)?.[0], 2 ** 40);
+
+  const report = decode(encodeHealthReport({
+    status: 'degraded',
+    checkedAt: SUMMARY.generatedAt,
+    docker: true,
+    services: ['translator', 'jdc'].map((id) => ({
+      id,
+      service: id as 'translator' | 'jdc',
+      label: id,
+      stack: null,
+      reachable: id === 'translator',
+      httpStatus: id === 'translator' ? 200 : null,
+      latencyMs: id === 'translator' ? 4 : null,
+      lastSuccessAt: null,
+      checkedAt: SUMMARY.generatedAt,
+      error: id === 'translator' ? null : 'fetch failed',
+    })),
+    sv1: null,
+  }));
+  assert.equal(report.get(4)?.length, 2);
+  assert.equal(report.has(5), false);
+  const jdc = decode(report.get(4)![1] as Buffer);
+  assert.equal(jdc.has(7), false);
+  assert.equal(String(jdc.get(11)?.[0]), 'fetch failed');
+});
+
+test('unary and streaming calls answer over HTTP/2', async (t) => {
+  const listeners = new Set<(summary: StackSummary) => void>();
+  const { handleStream } = createGrpcService({
+    getSummary: async () => SUMMARY,
+    getHealth: async () => { throw new Error('boom'); },
+    watchSummary: (listener) => {
+      listeners.add(listener);
+      return () => listeners.delete(listener);
+    },
+    ipFilter: parseIpFilter({}),
+  });
+  const server = http2.createServer().on('stream', handleStream);
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  const client = http2.connect(`http://127.0.0.1:${(server.address() as AddressInfo).port}`);
+  t.after(() => {
+    client.close();
+    server.close();
+  });
+
+  function call(method: string): Promise<{ status: string | undefined; messages: Buffer[] }> {
+    const stream = client.request({ ':method': 'POST', ':path': `/sv2ui.v1.Stack/${method}`, 'content-type': 'application/grpc' });
+    stream.end(Buffer.alloc(5));
+    const chunks: Buffer[] = [];
+    let status: string | undefined;
+    const readStatus = (headers: http2.IncomingHttpHeaders) => {
+      if (headers['grpc-status'] !== undefined) status = String(headers['grpc-status']);
+    };
+    stream.on('response', readStatus);
+    stream.on('trailers', readStatus);
+    stream.on('data', (chunk: Buffer) => chunks.push(chunk));
+    return new Promise((resolve) => stream.on('close', () => resolve({ status, messages: unframe(Buffer.concat(chunks)) })));
+  }
+
+  const summary = await call('GetSummary');
+  assert.equal(summary.status, '0');
+  assert.equal(summary.messages.length, 1);
+  assert.equal(String(decode(summary.messages[0]).get(5)?.[0]), 'Pool');
+  assert.equal((await call('GetHealth')).status, '13');
+  assert.equal((await call('Unknown')).status, '12');
+
+  const watch = client.request({ ':method': 'POST', ':path': '/sv2ui.v1.Stack/WatchSummary', 'content-type': 'application/grpc' });
+  watch.end(Buffer.alloc(5));
+  const chunks: Buffer[] = [];
+  await new Promise<void>((resolve) => {
+    watch.on('data', (chunk: Buffer) => {
+      chunks.push(chunk);
+      const messages = unframe(Buffer.concat(chunks));
+      // The first summary is out; push one as a poll would.
+      if (messages.length === 1 && chunks.length === 1) for (const listener of listeners) listener({ ...SUMMARY, workers: 4 });
+      if (messages.length === 2) {
+        assert.equal(decode(messages[1]).get(8)?.[0], 4);
+        resolve();
+      }
+    });
+  });
+  watch.close();
+  await new Promise((resolve) => watch.once('close', resolve));
+  await new Promise((resolve) => setTimeout(resolve, 20));
+  assert.equal(listeners.size, 0);
+});
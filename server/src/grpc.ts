@@ -0,0 +1,253 @@
+/**
+ * gRPC interface to the stack summary and health, for fleet tools watching
+ * many sv2-ui instances (see server/proto/sv2ui.proto).
+ *
+ * GRPC_LISTEN turns it on, with the same address syntax as LISTEN. It is
+ * served over plaintext HTTP/2 with node:http2, and the few messages are
+ * encoded by hand, so no gRPC runtime is needed. Like the dashboard's read
+ * routes it takes no token, and the IP filter applies.
+ *
+ * WatchSummary streams a summary straight away and another after every poll
+ * (see poller.ts), for as long as the client keeps the call open.
+ */
+
+import type { IncomingHttpHeaders, ServerHttp2Stream } from 'http2';
+import type { HealthReport } from './health.js';
+import { isIpAllowed } from './ip-filter.js';
+import type { IpFilter } from './ip-filter.js';
+import { parseListenAddresses } from './listen.js';
+import type { ListenAddress } from './listen.js';
+import { createLogger } from './logger.js';
+import { redactForPublic } from './redact.js';
+import type { StackSummary } from './summary.js';
+
+const log = createLogger('grpc');
+
+export const DEFAULT_GRPC_PORT = 50051;
+const SERVICE = 'sv2ui.v1.Stack';
+
+// https://grpc.github.io/grpc/core/md_doc_statuscodes.html
+const GRPC_STATUS = {
+  OK: 0,
+  PERMISSION_DENIED: 7,
+  UNIMPLEMENTED: 12,
+  INTERNAL: 13,
+} as const;
+
+export interface GrpcDeps {
+  getSummary: () => Promise<StackSummary>;
+  getHealth: () => Promise<HealthReport>;
+  // Call `listener` with a new summary after every poll; returns an unsubscribe.
+  watchSummary: (listener: (summary: StackSummary) => void) => () => void;
+  ipFilter: IpFilter;
+}
+
+/**
+ * The addresses to serve gRPC on, or null when GRPC_LISTEN is unset.
+ */
+export function parseGrpcListen(env: NodeJS.ProcessEnv = process.env): ListenAddress[] | null {
+  const value = env.GRPC_LISTEN?.trim();
+  return value ? parseListenAddresses(value, DEFAULT_GRPC_PORT) : null;
+}
+
+function varint(value: number): Buffer {
+  const bytes: number[] = [];
+  // Integers up to 2^53 stay exact with division; negatives encode as 0.
+  let rest = Math.max(0, Math.floor(value));
+  while (rest >= 0x80) {
+    bytes.push((rest % 0x80) | 0x80);
+    rest = Math.floor(rest / 0x80);
+  }
+  bytes.push(rest);
+  return Buffer.from(bytes);
+}
+
+/**
+ * A protobuf message writer. Fields given null or undefined are left out,
+ * which is how unset `optional` fields are encoded.
+ */
+function createMessageWriter() {
+  const chunks: Buffer[] = [];
+  const key = (field: number, wireType: number) => chunks.push(varint((field << 3) | wireType));
+  const bytes = (field: number, value: Buffer) => {
+    key(field, 2);
+    chunks.push(varint(value.length), value);
+  };
+
+  const writer = {
+    uint(field: number, value: unknown) {
+      if (typeof value !== 'number') return writer;
+      key(field, 0);
+      chunks.push(varint(value));
+      return writer;
+    },
+    bool(field: number, value: unknown) {
+      if (typeof value !== 'boolean') return writer;
+      key(field, 0);
+      chunks.push(varint(value ? 1 : 0));
+      return writer;
+    },
+    double(field: number, value: unknown) {
+      if (typeof value !== 'number') return writer;
+      key(field, 1);
+      const buffer = Buffer.alloc(8);
+      buffer.writeDoubleLE(value);
+      chunks.push(buffer);
+      return writer;
+    },
+    string(field: number, value: unknown) {
+      if (typeof value === 'string') bytes(field, Buffer.from(value, 'utf-8'));
+      return writer;
+    },
+    message(field: number, value: Buffer | null) {
+      if (value) bytes(field, value);
+      return writer;
+    },
+    finish: () => Buffer.concat(chunks),
+  };
+  return writer;
+}
+
+export function encodeSummary(summary: StackSummary): Buffer {
+  return createMessageWriter()
+    .string(1, summary.generatedAt)
+    .string(2, summary.miningMode)
+    .string(3, summary.mode)
+    .string(4, summary.network)
+    .string(5, summary.poolName)
+    .bool(6, summary.online)
+    .double(7, summary.hashrate)
+    .uint(8, summary.workers)
+    .uint(9, summary.sharesAccepted)
+    .uint(10, summary.sharesSubmitted)
+    .uint(11, summary.sharesRejected)
+    .double(12, summary.bestDifficulty)
+    .uint(13, summary.blocksFound)
+    .uint(14, summary.uptimeSecs)
+    .finish();
+}
+
+export function encodeHealthReport(report: HealthReport): Buffer {
+  const writer = createMessageWriter()
+    .string(1, report.status)
+    .string(2, report.checkedAt)
+    .bool(3, report.docker);
+  for (const service of report.services) {
+    writer.message(4, createMessageWriter()
+      .string(1, service.id)
+      .string(2, service.service)
+      .string(3, service.label)
+      .string(4, service.stack?.id)
+      .string(5, service.stack?.name)
+      .bool(6, service.reachable)
+      .uint(7, service.httpStatus)
+      .uint(8, service.latencyMs)
+      .string(9, service.lastSuccessAt)
+      .string(10, service.checkedAt)
+      .string(11, service.error)
+      .finish());
+  }
+  return writer
+    .message(5, report.sv1 && createMessageWriter()
+      .bool(1, report.sv1.ok)
+      .string(2, report.sv1.host)
+      .uint(3, report.sv1.port)
+      .string(4, report.sv1.checkedAt)
+      .finish())
+    .finish();
+}
+
+/**
+ * A length-prefixed gRPC message, uncompressed.
+ */
+export function frameMessage(message: Buffer): Buffer {
+  const header = Buffer.alloc(5);
+  header.writeUInt32BE(message.length, 1);
+  return Buffer.concat([header, message]);
+}
+
+function respond(stream: ServerHttp2Stream): void {
+  stream.respond({ ':status': 200, 'content-type': 'application/grpc' }, { waitForTrailers: true });
+}
+
+function endCall(stream: ServerHttp2Stream, status: number, message?: string): void {
+  if (stream.destroyed || stream.closed) return;
+  const trailers = { 'grpc-status': String(status), ...(message ? { 'grpc-message': encodeURIComponent(message) } : {}) };
+  if (!stream.headersSent) {
+    // A trailers-only response.
+    stream.respond({ ':status': 200, 'content-type': 'application/grpc', ...trailers }, { endStream: true });
+    return;
+  }
+  stream.once('wantTrailers', () => stream.sendTrailers(trailers));
+  stream.end();
+}
+
+async function unary(stream: ServerHttp2Stream, label: string, produce: () => Promise<Buffer>): Promise<void> {
+  try {
+    const message = await produce();
+    respond(stream);
+    stream.write(frameMessage(message));
+    endCall(stream, GRPC_STATUS.OK);
+  } catch (error) {
+    log.error(`gRPC ${label} error:`, error);
+    endCall(stream, GRPC_STATUS.INTERNAL, `Failed to get ${label.toLowerCase()}`);
+  }
+}
+
+/**
+ * The service, as a handler for the 'stream' event of one or more HTTP/2 servers.
+ */
+export function createGrpcService({ getSummary, getHealth, watchSummary, ipFilter }: GrpcDeps) {
+  const watchers = new Set<ServerHttp2Stream>();
+
+  function handleStream(stream: ServerHttp2Stream, headers: IncomingHttpHeaders): void {
+    // The request messages are all empty; read them so the call can finish.
+    stream.resume();
+    stream.on('error', () => {});
+
+    if (!isIpAllowed(ipFilter, stream.session?.socket.remoteAddress)) {
+      endCall(stream, GRPC_STATUS.PERMISSION_DENIED, 'Access from this address is not allowed');
+      return;
+    }
+
+    const contentType = String(headers['content-type'] ?? '');
+    if (headers[':method'] !== 'POST' || !contentType.startsWith('application/grpc')) {
+      stream.respond({ ':status': 415 }, { endStream: true });
+      return;
+    }
+
+    switch (headers[':path']) {
+      case `/${SERVICE}/GetSummary`:
+        void unary(stream, 'Summary', async () => encodeSummary(redactForPublic(await getSummary())));
+        return;
+      case `/${SERVICE}/GetHealth`:
+        void unary(stream, 'Health', async () => encodeHealthReport(await getHealth()));
+        return;
+      case `/${SERVICE}/WatchSummary`: {
+        watchers.add(stream);
+        // Polls finishing before the first summary is out wait for the next one.
+        const send = (summary: StackSummary) => {
+          if (stream.headersSent && !stream.closed) stream.write(frameMessage(encodeSummary(redactForPublic(summary))));
+        };
+        const unsubscribe = watchSummary(send);
+        stream.on('close', () => {
+          unsubscribe();
+          watchers.delete(stream);
+        });
+        getSummary().then((summary) => {
+          if (stream.closed) return;
+          respond(stream);
+          send(summary);
+        }).catch((error) => {
+          log.error('gRPC WatchSummary error:', error);
+          endCall(stream, GRPC_STATUS.INTERNAL, 'Failed to get summary');
+        });
+        return;
+      }
+      default:
+        endCall(stream, GRPC_STATUS.UNIMPLEMENTED, `Unknown method ${headers[':path']}`);
+    }
+  }
+
+  return { handleStream, streams: () => watchers.size };
+}
@@ -0,0 +1,85 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import { test } from 'node:test';
+import { createHealthMonitor, getHealthStatus, getHealthTargets } from './health.js';
+import type { RemoteStack } from './stacks.js';
+import type { Sv1ProbeResult } from './sv1-listener.js';
+
+const remoteStack: RemoteStack = {
+  id: 'rack-2',
+  name: 'Rack 2',
+  network: 'mainnet',
+  mode: 'no-jd',
+  miningMode: 'pool',
+  translatorUrl: 'http://10.0.0.2:9092/api/v1',
+  jdcUrl: null,
+};
+
+test('health targets follow the local mode and each remote stack', () => {
+  assert.deepEqual(getHealthTargets(null, []), []);
+  assert.deepEqual(getHealthTargets('jd', []).map((target) => target.id), ['translator', 'jdc']);
+  assert.deepEqual(getHealthTargets('no-jd', [remoteStack]), [
+    { id: 'translator', service: 'translator', stack: null },
+    {
+      id: 'rack-2/translator',
+      service: 'translator',
+      stack: { id: 'rack-2', name: 'Rack 2' },
+      endpoints: { translator: 'http://10.0.0.2:9092/api/v1' },
+    },
+  ]);
+});
+
+test('overall status is ok only when docker and every service are up', () => {
+  const up = { reachable: true } as Parameters<typeof getHealthStatus>[1][number];
+  const down = { reachable: false } as Parameters<typeof getHealthStatus>[1][number];
+
+  assert.equal(getHealthStatus(true, []), 'ok');
+  assert.equal(getHealthStatus(true, [up, up]), 'ok');
+  assert.equal(getHealthStatus(false, [up]), 'degraded');
+  assert.equal(getHealthStatus(true, [up, down]), 'degraded');
+  assert.equal(getHealthStatus(true, [down]), 'down');
+  // A failed SV1 self-test degrades an otherwise healthy stack.
+  assert.equal(getHealthStatus(true, [up], { ok: false } as Sv1ProbeResult), 'degraded');
+  assert.equal(getHealthStatus(true, [up], { ok: true } as Sv1ProbeResult), 'ok');
+  // So does a federated site that can't be reached, but not one that is degraded itself.
+  const site = (reachable: boolean) => ({ reachable, status: reachable ? 'degraded' : null }) as Parameters<typeof getHealthStatus>[3][number];
+  assert.equal(getHealthStatus(true, [up], null, [site(true)]), 'ok');
+  assert.equal(getHealthStatus(true, [up], null, [site(true), site(false)]), 'degraded');
+  assert.equal(getHealthStatus(true, [], null, [site(false)]), 'degraded');
+});
+
+test('probes report status, latency and the last success per service', async (t) => {
+  let healthy = true;
+  const server = http.createServer((_req, res) => {
+    res.writeHead(healthy ? 200 : 500, { 'Content-Type': 'application/json' }).end('{}');
+  }).listen(0, '127.0.0.1');
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+
+  let now = Date.parse('2026-04-17T18:30:00Z');
+  const monitor = createHealthMonitor(() => now);
+  const targets = [
+    { id: 'up/translator', service: 'translator' as const, stack: null, endpoints: { translator: `http://127.0.0.1:${port}/api/v1` } },
+    // Nothing listens on port 1.
+    { id: 'gone/jdc', service: 'jdc' as const, stack: null, endpoints: { jdc: 'http://127.0.0.1:1/api/v1' } },
+  ];
+
+  const first = await monitor.check(targets, true);
+  assert.equal(first.status, 'degraded');
+  assert.deepEqual(first.services.map(({ reachable, httpStatus, error }) => ({ reachable, httpStatus, error })), [
+    { reachable: true, httpStatus: 200, error: null },
+    { reachable: false, httpStatus: null, error: 'Connection failed' },
+  ]);
+  assert.equal(first.services[0].lastSuccessAt, '2026-04-17T18:30:00.000Z');
+  assert.equal(first.services[0].latencyMs, 0);
+  assert.equal(first.services[1].latencyMs, null);
+
+  healthy = false;
+  now += 60_000;
+  const second = await monitor.check(targets.slice(0, 1), true);
+  assert.equal(second.status, 'down');
+  assert.equal(second.services[0].httpStatus, 500);
+  assert.equal(second.services[0].error, 'HTTP 500');
+  assert.equal(second.services[0].lastSuccessAt, '2026-04-17T18:30:00.000Z');
+});
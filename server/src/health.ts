@@ -0,0 +1,152 @@
+/**
+ * Per-service health for GET /api/v1/health.
+ *
+ * Every monitoring API the server knows about, the local Translator and JDC
+ * plus those of registered remote stacks, is probed at its /health endpoint.
+ * The report carries reachability, HTTP status, round-trip latency and when
+ * each service last answered, so the UI and external monitors read the same
+ * picture. When the local Translator runs, the report also carries the last
+ * SV1 stratum self-test (see sv1-listener.ts), and a failed one degrades it.
+ * Federated sv2-ui instances are listed under `sites` with their last
+ * background check (see federation.ts); one that can't be reached degrades
+ * the report too.
+ */
+
+import type { SetupMode } from '@sv2-ui/shared';
+import type { SiteHealth } from './federation.js';
+import { getMonitoringBaseUrl, MONITORING_SERVICES } from './monitoring.js';
+import type { MonitoringEndpoints, MonitoringService } from './monitoring.js';
+import { getRequestIdHeaders } from './request-context.js';
+import { getStackEndpoints } from './stacks.js';
+import type { Sv1ProbeResult } from './sv1-listener.js';
+import type { RemoteStack } from './stacks.js';
+
+export interface HealthTarget {
+  // 'translator', or '<stack id>/translator' for a remote stack.
+  id: string;
+  service: MonitoringService;
+  stack: { id: string; name: string } | null;
+  endpoints?: MonitoringEndpoints;
+}
+
+export interface ServiceHealth {
+  id: string;
+  service: MonitoringService;
+  label: string;
+  stack: { id: string; name: string } | null;
+  reachable: boolean;
+  httpStatus: number | null;
+  latencyMs: number | null;
+  lastSuccessAt: string | null;
+  checkedAt: string;
+  error: string | null;
+}
+
+export type HealthStatus = 'ok' | 'degraded' | 'down';
+
+export interface HealthReport {
+  status: HealthStatus;
+  checkedAt: string;
+  docker: boolean;
+  services: ServiceHealth[];
+  sv1: Sv1ProbeResult | null;
+  sites: SiteHealth[];
+}
+
+const PROBE_TIMEOUT_MS = 2000;
+
+/**
+ * The services to probe: the local ones the configured mode runs (none until
+ * setup is done), then every service of each remote stack.
+ */
+export function getHealthTargets(mode: SetupMode | null, remoteStacks: RemoteStack[]): HealthTarget[] {
+  const local: MonitoringService[] = mode === 'jd' ? ['translator', 'jdc'] : mode ? ['translator'] : [];
+  return [
+    ...local.map((service) => ({ id: service, service, stack: null })),
+    ...remoteStacks.flatMap((stack) => {
+      const endpoints = getStackEndpoints(stack);
+      return (Object.keys(endpoints) as MonitoringService[]).map((service) => ({
+        id: `${stack.id}/${service}`,
+        service,
+        stack: { id: stack.id, name: stack.name },
+        endpoints,
+      }));
+    }),
+  ];
+}
+
+export function getHealthStatus(
+  docker: boolean,
+  services: ServiceHealth[],
+  sv1: Sv1ProbeResult | null = null,
+  sites: SiteHealth[] = [],
+): HealthStatus {
+  const sitesReachable = sites.every((site) => site.reachable);
+  if (services.length === 0) return !docker ? 'down' : sitesReachable ? 'ok' : 'degraded';
+  const reachable = services.filter((service) => service.reachable).length;
+  if (reachable === 0) return 'down';
+  return docker && reachable === services.length && sv1?.ok !== false && sitesReachable ? 'ok' : 'degraded';
+}
+
+export function createHealthMonitor(now: () => number = Date.now, timeoutMs = PROBE_TIMEOUT_MS) {
+  // Only successes are remembered, keyed by target id.
+  const lastSuccess = new Map<string, string>();
+
+  async function probe(target: HealthTarget): Promise<ServiceHealth> {
+    const startedAt = now();
+    let httpStatus: number | null = null;
+    let error: string | null = null;
+    try {
+      const response = await fetch(`${getMonitoringBaseUrl(target.service, target.endpoints)}/health`, {
+        headers: { Accept: 'application/json', ...getRequestIdHeaders() },
+        signal: AbortSignal.timeout(timeoutMs),
+      });
+      httpStatus = response.status;
+      // Drain the body so the connection can be reused.
+      await response.arrayBuffer().catch(() => undefined);
+      if (!response.ok) error = `HTTP ${response.status}`;
+    } catch (cause) {
+      error = cause instanceof Error && cause.name === 'TimeoutError'
+        ? `No response within ${timeoutMs}ms`
+        : 'Connection failed';
+    }
+
+    const checkedAt = new Date(now()).toISOString();
+    const reachable = error === null;
+    if (reachable) lastSuccess.set(target.id, checkedAt);
+
+    return {
+      id: target.id,
+      service: target.service,
+      label: MONITORING_SERVICES[target.service].label,
+      stack: target.stack,
+      reachable,
+      httpStatus,
+      latencyMs: httpStatus === null ? null : now() - startedAt,
+      lastSuccessAt: lastSuccess.get(target.id) ?? null,
+      checkedAt,
+      error,
+    };
+  }
+
+  async function check(
+    targets: HealthTarget[],
+    docker: boolean,
+    sv1: Sv1ProbeResult | null = null,
+    sites: SiteHealth[] = [],
+  ): Promise<HealthReport> {
+    const services = await Promise.all(targets.map(probe));
+    return {
+      status: getHealthStatus(docker, services, sv1, sites),
+      checkedAt: new Date(now()).toISOString(),
+      docker,
+      services,
+      sv1,
+      sites,
+    };
+  }
+
+  return { check, size: () => lastSuccess.size };
+}
+
+export type HealthMonitor = ReturnType<typeof createHealthMonitor>;
@@ -0,0 +1,109 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+
+import { createHistoryStore, parseHistoryRetention } from './history.js';
+import {
+  exportHistory,
+  HISTORY_METRICS,
+  parseExportArgs,
+  parseHistoryMetrics,
+  parseHistoryRange,
+  parseHistoryResolution,
+  renderHistoryCsv,
+} from './history-export.js';
+import { getStackSamples } from './stack-history.js';
+import type { StackSummary } from './summary.js';
+
+test('parseHistoryRange and parseHistoryMetrics read the export parameters', () => {
+  assert.equal(parseHistoryRange(), 24 * 3_600_000);
+  assert.equal(parseHistoryRange('30m'), 30 * 60_000);
+  assert.equal(parseHistoryRange('7d'), 7 * 86_400_000);
+  assert.equal(parseHistoryRange('all'), null);
+  assert.throws(() => parseHistoryRange('0h'), /range must be/);
+  assert.throws(() => parseHistoryRange('1w'), /range must be/);
+
+  assert.deepEqual(parseHistoryMetrics(undefined), HISTORY_METRICS);
+  assert.deepEqual(parseHistoryMetrics('stack.hashrate, stack.hashrate,latency.connect'), ['stack.hashrate', 'latency.connect']);
+  assert.throws(() => parseHistoryMetrics('hashrate'), /Unknown metric "hashrate"; available: stack\.hashrate/);
+
+  const retention = parseHistoryRetention({});
+  assert.equal(parseHistoryResolution(undefined, retention), undefined);
+  assert.equal(parseHistoryResolution('1h', retention), 3_600_000);
+  assert.throws(() => parseHistoryResolution('5m', retention), /resolution must be one of raw, 1m, 1h, got "5m"/);
+});
+
+test('stack samples are recorded per poll and exported as CSV rows', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-history-export-'));
+  const history = createHistoryStore(path.join(dir, 'history.db'));
+  const summary = (generatedAt: string, online: boolean): StackSummary => ({
+    generatedAt,
+    miningMode: null,
+    mode: 'no-jd',
+    network: null,
+    poolName: null,
+    online,
+    hashrate: 2e12,
+    workers: 2,
+    sharesAccepted: 10,
+    sharesSubmitted: 11,
+    sharesRejected: 1,
+    bestDifficulty: 500,
+    blocksFound: 0,
+    uptimeSecs: 60,
+  });
+  history.record(getStackSamples(summary('2026-01-01T00:00:00.000Z', true)));
+  history.record(getStackSamples(summary('2026-01-01T00:00:10.000Z', false)));
+  history.record([{ metric: 'latency.connect', key: 'pool, "eu"', at: '2026-01-01T00:00:05.000Z', value: 41 }]);
+
+  const csv = [...renderHistoryCsv(history, ['stack.hashrate', 'latency.connect'])].join('');
+  assert.equal(csv, [
+    'timestamp,metric,key,value',
+    '2026-01-01T00:00:00.000Z,stack.hashrate,,2000000000000',
+    '2026-01-01T00:00:10.000Z,stack.hashrate,,',
+    '2026-01-01T00:00:05.000Z,latency.connect,"pool, ""eu""",41',
+    '',
+  ].join('\r\n'));
+
+  const recent = [...renderHistoryCsv(history, ['stack.workers'], { since: new Date('2026-01-01T00:00:05.000Z') })];
+  assert.equal(recent.length, 2);
+  history.close();
+});
+
+test('the export subcommand writes one file per metric', async () => {
+  const retention = parseHistoryRetention({});
+  assert.deepEqual(parseExportArgs(['--format', 'parquet', '--metric=stack.hashrate', '--resolution', '1m', '--out', '/tmp/out'], retention), {
+    format: 'parquet',
+    metrics: ['stack.hashrate'],
+    rangeMs: null,
+    resolutionMs: 60_000,
+    outDir: '/tmp/out',
+  });
+  assert.equal(parseExportArgs([], retention).format, 'csv');
+  assert.throws(() => parseExportArgs(['--format', 'xlsx'], retention), /--format must be one of csv, parquet/);
+  assert.throws(() => parseExportArgs(['--range'], retention), /--range requires a value/);
+  assert.throws(() => parseExportArgs(['--since', '1d'], retention), /Unknown export option "--since"/);
+
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-history-export-'));
+  const history = createHistoryStore(path.join(dir, 'history.db'));
+  history.record([
+    { metric: 'stack.hashrate', key: '', at: '2026-01-01T00:00:00.000Z', value: 1e12 },
+    { metric: 'stack.hashrate', key: '', at: '2026-01-02T00:00:00.000Z', value: null },
+  ]);
+  const outDir = path.join(dir, 'export');
+  const files = await exportHistory(history, parseExportArgs(['--format', 'parquet', '--metric', 'stack.hashrate,stack.workers', '--out', outDir], retention));
+  assert.deepEqual(files, [path.join(outDir, 'stack.hashrate.parquet'), path.join(outDir, 'stack.workers.parquet')]);
+  const parquet = await fs.readFile(files[0]);
+  assert.equal(parquet.subarray(0, 4).toString(), 'PAR1');
+  assert.equal(parquet.subarray(-4).toString(), 'PAR1');
+
+  const [csv] = await exportHistory(
+    history,
+    { ...parseExportArgs(['--metric', 'stack.hashrate', '--out', outDir], retention), rangeMs: 3_600_000 },
+    () => Date.parse('2026-01-02T00:30:00.000Z'),
+  );
+  assert.equal(await fs.readFile(csv, 'utf8'), 'timestamp,metric,key,value\r\n2026-01-02T00:00:00.000Z,stack.hashrate,,\r\n');
+  history.close();
+});
@@ -0,0 +1,200 @@
+/**
+ * Export of the history database: as CSV, one row per sample, for
+ * spreadsheets and ad-hoc analysis, or as one Parquet file per metric for
+ * DuckDB and pandas.
+ *
+ * Rows are produced a sample at a time straight from SQLite, so exporting a
+ * long range doesn't build it up in memory first. The same export runs from
+ * GET /api/v1/history/export and from the `export` subcommand, which writes
+ * files next to a running server without going through it.
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { Readable } from 'stream';
+import { pipeline } from 'stream/promises';
+import type { HistoryRetention, HistorySample, HistoryStore } from './history.js';
+import { CONNECT_METRIC, HANDSHAKE_METRIC } from './latency.js';
+import { writeParquet } from './parquet.js';
+import type { ParquetColumn } from './parquet.js';
+import { MINER_HASHRATE_METRIC, STACK_METRICS } from './stack-history.js';
+import { VARDIFF_DIFFICULTY_METRIC, VARDIFF_SHARE_RATE_METRIC } from './vardiff.js';
+
+// Every metric the server records.
+export const HISTORY_METRICS = [
+  ...Object.keys(STACK_METRICS),
+  MINER_HASHRATE_METRIC,
+  VARDIFF_DIFFICULTY_METRIC,
+  VARDIFF_SHARE_RATE_METRIC,
+  CONNECT_METRIC,
+  HANDSHAKE_METRIC,
+];
+
+export const HISTORY_EXPORT_FORMATS = ['csv', 'parquet'] as const;
+export type HistoryExportFormat = typeof HISTORY_EXPORT_FORMATS[number];
+
+const RANGE_UNITS_MS: Record<string, number> = { m: 60_000, h: 3_600_000, d: 86_400_000 };
+export const DEFAULT_EXPORT_RANGE = '24h';
+const RESOLUTION_UNITS_MS: Record<string, number> = { s: 1000, ...RANGE_UNITS_MS };
+
+/**
+ * How far back a `range` like 30m, 24h or 7d reaches, in milliseconds;
+ * null for `all`.
+ */
+export function parseHistoryRange(value: string = DEFAULT_EXPORT_RANGE): number | null {
+  if (value === 'all') return null;
+  const match = /^(\d+)([mhd])$/.exec(value.trim());
+  const amount = match ? Number(match[1]) : 0;
+  if (!match || amount < 1) {
+    throw new Error(`range must be a number of minutes, hours or days (e.g. 30m, 24h, 7d) or all, got "${value}"`);
+  }
+  return amount * RANGE_UNITS_MS[match[2]];
+}
+
+/**
+ * The rollup resolution named by `resolution` (1m, 1h, ...), which has to be
+ * one of the HISTORY_RETENTION tiers; undefined for raw samples.
+ */
+export function parseHistoryResolution(value: string | undefined, retention: HistoryRetention): number | undefined {
+  if (value === undefined || value === 'raw') return undefined;
+  const match = /^(\d+)([smhd])$/.exec(value.trim());
+  const resolutionMs = match ? Number(match[1]) * RESOLUTION_UNITS_MS[match[2]] : NaN;
+  const tiers = retention.rollups.map((tier) => tier.resolutionMs);
+  if (!tiers.includes(resolutionMs)) {
+    const available = ['raw', ...tiers.map(formatResolution)].join(', ');
+    throw new Error(`resolution must be one of ${available}, got "${value}"`);
+  }
+  return resolutionMs;
+}
+
+function formatResolution(ms: number): string {
+  // Largest unit first, so 3600000 reads 1h rather than 60m.
+  const [unit, size] = Object.entries(RESOLUTION_UNITS_MS).reverse().find(([, size]) => ms % size === 0) ?? ['ms', 1];
+  return `${ms / size}${unit}`;
+}
+
+/**
+ * The metrics named in a comma-separated `metric` parameter; every metric
+ * when it's left out.
+ */
+export function parseHistoryMetrics(value: string | undefined): string[] {
+  if (value === undefined || value.trim() === '') return HISTORY_METRICS;
+  const metrics = value.split(',').map((metric) => metric.trim()).filter(Boolean);
+  const unknown = metrics.find((metric) => !HISTORY_METRICS.includes(metric));
+  if (unknown !== undefined) {
+    throw new Error(`Unknown metric "${unknown}"; available: ${HISTORY_METRICS.join(', ')}`);
+  }
+  return [...new Set(metrics)];
+}
+
+function csvField(value: string): string {
+  return /[",\r\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+}
+
+function csvRow(sample: HistorySample): string {
+  return `${[sample.at, sample.metric, sample.key, sample.value === null ? '' : String(sample.value)].map(csvField).join(',')}\r\n`;
+}
+
+export interface ExportRange {
+  since?: Date;
+  // Export rollups of this resolution (their averages) instead of raw samples.
+  resolutionMs?: number;
+}
+
+/**
+ * The CSV export as lines: a header, then each metric's samples oldest first.
+ * Missing samples have an empty value.
+ */
+export function* renderHistoryCsv(
+  history: Pick<HistoryStore, 'iterate'>,
+  metrics: string[],
+  range: ExportRange = {},
+): Generator<string> {
+  yield 'timestamp,metric,key,value\r\n';
+  for (const metric of metrics) {
+    for (const sample of history.iterate(metric, range)) yield csvRow(sample);
+  }
+}
+
+const PARQUET_COLUMNS: ParquetColumn[] = [
+  { name: 'timestamp', type: 'timestamp' },
+  { name: 'key', type: 'string' },
+  { name: 'value', type: 'double', optional: true },
+];
+
+/**
+ * One metric's samples as a Parquet file with timestamp, key and value
+ * columns; missing samples are null.
+ */
+export function renderHistoryParquet(
+  history: Pick<HistoryStore, 'iterate'>,
+  metric: string,
+  range: ExportRange = {},
+): Generator<Buffer> {
+  function* rows() {
+    for (const sample of history.iterate(metric, range)) yield [Date.parse(sample.at), sample.key, sample.value];
+  }
+  return writeParquet(PARQUET_COLUMNS, rows());
+}
+
+export interface ExportArgs {
+  format: HistoryExportFormat;
+  metrics: string[];
+  // Null exports everything.
+  rangeMs: number | null;
+  resolutionMs?: number;
+  outDir: string;
+}
+
+/**
+ * The options of `export [--format csv|parquet] [--metric a,b] [--range 7d]
+ * [--resolution 1h] [--out <dir>]`, each also accepted as --name=value.
+ * Unlike the endpoint, the range defaults to all.
+ */
+export function parseExportArgs(argv: string[], retention: HistoryRetention): ExportArgs {
+  const options: Record<string, string> = {};
+  for (let i = 0; i < argv.length; i++) {
+    const match = /^--(format|metric|range|resolution|out)(?:=(.*))?$/.exec(argv[i]);
+    if (!match) throw new Error(`Unknown export option "${argv[i]}"; use --format, --metric, --range, --resolution or --out`);
+    const value = match[2] ?? argv[++i];
+    if (value === undefined || value.trim() === '' || (match[2] === undefined && value.startsWith('--'))) {
+      throw new Error(`--${match[1]} requires a value`);
+    }
+    options[match[1]] = value.trim();
+  }
+
+  const format = (options.format ?? 'csv') as HistoryExportFormat;
+  if (!HISTORY_EXPORT_FORMATS.includes(format)) {
+    throw new Error(`--format must be one of ${HISTORY_EXPORT_FORMATS.join(', ')}, got "${options.format}"`);
+  }
+  return {
+    format,
+    metrics: parseHistoryMetrics(options.metric),
+    rangeMs: parseHistoryRange(options.range ?? 'all'),
+    resolutionMs: parseHistoryResolution(options.resolution, retention),
+    outDir: path.resolve(options.out ?? '.'),
+  };
+}
+
+/**
+ * Write one file per metric, <metric>.csv or <metric>.parquet, to outDir.
+ * Resolves to the paths written.
+ */
+export async function exportHistory(
+  history: Pick<HistoryStore, 'iterate'>,
+  { format, metrics, rangeMs, resolutionMs, outDir }: ExportArgs,
+  now: () => number = Date.now,
+): Promise<string[]> {
+  const since = rangeMs === null ? undefined : new Date(now() - rangeMs);
+  await fs.promises.mkdir(outDir, { recursive: true });
+  const files: string[] = [];
+  for (const metric of metrics) {
+    const file = path.join(outDir, `${metric}.${format}`);
+    const content = format === 'parquet'
+      ? renderHistoryParquet(history, metric, { since, resolutionMs })
+      : renderHistoryCsv(history, [metric], { since, resolutionMs });
+    await pipeline(Readable.from(content), fs.createWriteStream(file));
+    files.push(file);
+  }
+  return files;
+}
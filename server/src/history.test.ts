@@ -0,0 +1,84 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+
+import { createHistoryStore, parseHistoryRetention, selectResolution } from './history.js';
+
+test('history keeps samples per metric and key across reopening', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-history-'));
+  const filePath = path.join(dir, 'nested', 'history.db');
+  const history = createHistoryStore(filePath);
+  history.record([
+    { metric: 'latency.connect', key: 'pool', at: '2026-01-01T00:00:00.000Z', value: 40 },
+    { metric: 'latency.connect', key: 'jds', at: '2026-01-01T00:00:00.000Z', value: null },
+    { metric: 'latency.connect', key: 'pool', at: '2026-01-01T00:01:00.000Z', value: 42 },
+    { metric: 'latency.handshake', key: 'pool', at: '2026-01-01T00:01:00.000Z', value: 90 },
+  ]);
+  history.close();
+
+  const reopened = createHistoryStore(filePath);
+  assert.deepEqual(reopened.query('latency.connect', { key: 'pool' }).map(({ at, value }) => [at, value]), [
+    ['2026-01-01T00:00:00.000Z', 40],
+    ['2026-01-01T00:01:00.000Z', 42],
+  ]);
+  assert.equal(reopened.query('latency.connect', { key: 'jds' })[0].value, null);
+  assert.equal(reopened.query('latency.connect').length, 3);
+  assert.deepEqual(reopened.query('latency.connect', { key: 'pool', since: new Date('2026-01-01T00:00:30.000Z') }).map(({ value }) => value), [42]);
+  // The limit keeps the most recent samples.
+  assert.deepEqual(reopened.query('latency.connect', { key: 'pool', limit: 1 }).map(({ value }) => value), [42]);
+  reopened.close();
+});
+
+test('parseHistoryRetention reads tiers and rejects ones that can\'t be rolled up', () => {
+  assert.deepEqual(parseHistoryRetention({}), {
+    rawMs: 48 * 3_600_000,
+    rollups: [
+      { resolutionMs: 60_000, retentionMs: 30 * 86_400_000 },
+      { resolutionMs: 3_600_000, retentionMs: 365 * 86_400_000 },
+    ],
+  });
+  assert.deepEqual(parseHistoryRetention({ HISTORY_RETENTION: 'raw:all' }), { rawMs: null, rollups: [] });
+  assert.throws(() => parseHistoryRetention({ HISTORY_RETENTION: '1m:30d' }), /raw is missing/);
+  assert.throws(() => parseHistoryRetention({ HISTORY_RETENTION: 'raw:2d,1m:30d,90s:1y' }), /"90s:1y" isn't <resolution>:<retention>/);
+  assert.throws(() => parseHistoryRetention({ HISTORY_RETENTION: 'raw:2d,1m:30d,90s:60d' }), /90s isn't a multiple/);
+  assert.throws(() => parseHistoryRetention({ HISTORY_RETENTION: 'raw:1h,1h:30d' }), /at least twice 1h/);
+
+  const retention = parseHistoryRetention({});
+  const now = Date.parse('2026-02-01T00:00:00.000Z');
+  assert.equal(selectResolution(retention, new Date(now - 3_600_000), now), null);
+  assert.equal(selectResolution(retention, new Date(now - 7 * 86_400_000), now), 60_000);
+  assert.equal(selectResolution(retention, new Date(now - 2 * 365 * 86_400_000), now), 3_600_000);
+});
+
+test('compact rolls completed buckets up tier by tier and prunes what\'s past retention', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-history-'));
+  const history = createHistoryStore(path.join(dir, 'history.db'));
+  const retention = parseHistoryRetention({ HISTORY_RETENTION: 'raw:10m,1m:2h,1h:all' });
+  const start = Date.parse('2026-01-01T00:00:00.000Z');
+  const at = (offsetMs: number) => new Date(start + offsetMs).toISOString();
+  history.record([
+    { metric: 'stack.hashrate', key: '', at: at(0), value: 10 },
+    { metric: 'stack.hashrate', key: '', at: at(30_000), value: 20 },
+    { metric: 'stack.hashrate', key: '', at: at(60_000), value: null },
+    { metric: 'stack.hashrate', key: '', at: at(120_000), value: 60 },
+    // Still in an open bucket.
+    { metric: 'stack.hashrate', key: '', at: at(3_600_000 + 150_000), value: 5 },
+  ]);
+
+  assert.deepEqual(history.compact(retention, start + 3_600_000 + 170_000), { rolledUp: 4, deleted: 4 });
+  assert.deepEqual(history.query('stack.hashrate').map(({ value }) => value), [5]);
+  assert.deepEqual(history.query('stack.hashrate', { resolutionMs: 60_000 }).map(({ at: time, value }) => [time, value]), [
+    [at(0), 15],
+    [at(60_000), null],
+    [at(120_000), 60],
+  ]);
+  // Weighted by sample count, so the 1m average over two samples counts twice.
+  assert.deepEqual(history.query('stack.hashrate', { resolutionMs: 3_600_000 }).map(({ value }) => value), [30]);
+
+  // A second run only adds buckets completed since; the old minutes and the last raw sample age out.
+  assert.deepEqual(history.compact(retention, start + 4 * 3_600_000), { rolledUp: 2, deleted: 5 });
+  assert.deepEqual(history.query('stack.hashrate', { resolutionMs: 3_600_000 }).map(({ value }) => value), [30, 5]);
+  history.close();
+});
@@ -0,0 +1,270 @@
+/**
+ * The history database: time series the server samples itself, kept in
+ * SQLite at HISTORY_DB (CONFIG_DIR/history.db by default).
+ *
+ * Every series is a metric name plus a key (a target, a worker, or '' for
+ * an aggregate), and each sample a point in time with a value; null records
+ * a sample that couldn't be taken, so gaps show up as gaps.
+ *
+ * Raw samples are kept for a while and then survive only as rollups: the
+ * average, minimum and maximum per bucket of a coarser resolution, each
+ * tier kept for longer (see HISTORY_RETENTION below). compact() rolls up
+ * completed buckets and drops what's past its retention; the server runs it
+ * in the background so the file stays small on a Raspberry Pi while
+ * long-range charts still have something to show.
+ *
+ * The database is opened on first use, after startup has checked that the
+ * config directory is writable.
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { DatabaseSync } from 'node:sqlite';
+
+export interface HistorySample {
+  metric: string;
+  key: string;
+  at: string;
+  value: number | null;
+}
+
+export interface HistoryQuery {
+  key?: string;
+  since?: Date;
+  until?: Date;
+  limit?: number;
+  // Read the rollups of this resolution instead of raw samples.
+  resolutionMs?: number;
+}
+
+export interface RollupTier {
+  resolutionMs: number;
+  // Null keeps the tier forever.
+  retentionMs: number | null;
+}
+
+export interface HistoryRetention {
+  // How long raw samples are kept; null keeps them forever.
+  rawMs: number | null;
+  // Finest first; each resolution is a multiple of the one before.
+  rollups: RollupTier[];
+}
+
+export interface CompactionResult {
+  rolledUp: number;
+  deleted: number;
+}
+
+const DEFAULT_QUERY_LIMIT = 1000;
+export const DEFAULT_HISTORY_RETENTION = 'raw:48h,1m:30d,1h:365d';
+const DURATION_UNITS_MS: Record<string, number> = { s: 1000, m: 60_000, h: 3_600_000, d: 86_400_000 };
+
+const SCHEMA = `
+  CREATE TABLE IF NOT EXISTS samples (
+    metric TEXT NOT NULL,
+    key TEXT NOT NULL DEFAULT '',
+    at INTEGER NOT NULL,
+    value REAL
+  );
+  CREATE INDEX IF NOT EXISTS samples_by_series ON samples (metric, key, at);
+  CREATE INDEX IF NOT EXISTS samples_by_time ON samples (at);
+  CREATE TABLE IF NOT EXISTS rollups (
+    metric TEXT NOT NULL,
+    key TEXT NOT NULL DEFAULT '',
+    resolution INTEGER NOT NULL,
+    at INTEGER NOT NULL,
+    value REAL,
+    min REAL,
+    max REAL,
+    count INTEGER NOT NULL
+  );
+  CREATE UNIQUE INDEX IF NOT EXISTS rollups_by_series ON rollups (resolution, metric, key, at);
+`;
+
+interface SampleRow {
+  metric: string;
+  key: string;
+  at: number;
+  value: number | null;
+}
+
+function parseDuration(value: string): number | null {
+  const match = /^(\d+)([smhd])$/.exec(value);
+  return match && Number(match[1]) > 0 ? Number(match[1]) * DURATION_UNITS_MS[match[2]] : null;
+}
+
+/**
+ * HISTORY_RETENTION: comma-separated `<resolution>:<retention>` tiers, where
+ * the resolution is `raw` or a duration and the retention a duration or
+ * `all`, e.g. the default `raw:48h,1m:30d,1h:365d`. Durations are a number
+ * of s, m, h or d.
+ */
+export function parseHistoryRetention(env: NodeJS.ProcessEnv = process.env): HistoryRetention {
+  const raw = env.HISTORY_RETENTION?.trim() || DEFAULT_HISTORY_RETENTION;
+  const fail = (reason: string): never => {
+    throw new Error(`HISTORY_RETENTION must be tiers like ${DEFAULT_HISTORY_RETENTION} (${reason}), got "${raw}"`);
+  };
+
+  let rawMs: number | null | undefined;
+  const rollups: RollupTier[] = [];
+  for (const tier of raw.split(',').map((part) => part.trim())) {
+    const [resolution, retention, ...rest] = tier.split(':');
+    const retentionMs = retention === 'all' ? null : parseDuration(retention ?? '');
+    if (rest.length > 0 || (retentionMs === null && retention !== 'all')) fail(`"${tier}" isn't <resolution>:<retention>`);
+    if (resolution === 'raw') {
+      if (rawMs !== undefined || rollups.length > 0) fail('raw must come first, once');
+      rawMs = retentionMs;
+      continue;
+    }
+    const resolutionMs = parseDuration(resolution) ?? fail(`"${resolution}" isn't a duration`);
+    const previous = rollups.at(-1);
+    if (previous && resolutionMs % previous.resolutionMs !== 0) {
+      fail(`${resolution} isn't a multiple of the resolution before it`);
+    }
+    // Buckets are rolled up from the tier before, which has to keep them until then.
+    const sourceMs = previous ? previous.retentionMs : rawMs ?? null;
+    if (sourceMs !== null && sourceMs < resolutionMs * 2) fail(`the tier before ${resolution} must be kept for at least twice ${resolution}`);
+    rollups.push({ resolutionMs, retentionMs });
+  }
+  if (rawMs === undefined) fail('raw is missing');
+  return { rawMs: rawMs as number | null, rollups };
+}
+
+/**
+ * The finest data that still reaches back to `since`: raw samples (null) or
+ * a rollup resolution. Falls back to the coarsest tier.
+ */
+export function selectResolution(retention: HistoryRetention, since: Date, now = Date.now()): number | null {
+  const span = now - since.getTime();
+  if (retention.rawMs === null || span <= retention.rawMs) return null;
+  const tier = retention.rollups.find(({ retentionMs }) => retentionMs === null || span <= retentionMs);
+  return (tier ?? retention.rollups.at(-1))?.resolutionMs ?? null;
+}
+
+export function createHistoryStore(filePath: string) {
+  let db: DatabaseSync | null = null;
+
+  function open(): DatabaseSync {
+    if (!db) {
+      fs.mkdirSync(path.dirname(filePath), { recursive: true });
+      db = new DatabaseSync(filePath);
+      db.exec('PRAGMA journal_mode = WAL');
+      db.exec(SCHEMA);
+    }
+    return db;
+  }
+
+  function record(samples: HistorySample[]): void {
+    if (samples.length === 0) return;
+    const database = open();
+    const insert = database.prepare('INSERT INTO samples (metric, key, at, value) VALUES (?, ?, ?, ?)');
+    database.exec('BEGIN');
+    try {
+      for (const { metric, key, at, value } of samples) insert.run(metric, key, Date.parse(at), value);
+      database.exec('COMMIT');
+    } catch (error) {
+      database.exec('ROLLBACK');
+      throw error;
+    }
+  }
+
+  function getConditions(metric: string, { key, since, until, resolutionMs }: HistoryQuery) {
+    const conditions = ['metric = ?'];
+    const params: Array<string | number> = [metric];
+    if (resolutionMs !== undefined) {
+      conditions.push('resolution = ?');
+      params.push(resolutionMs);
+    }
+    if (key !== undefined) {
+      conditions.push('key = ?');
+      params.push(key);
+    }
+    if (since) {
+      conditions.push('at >= ?');
+      params.push(since.getTime());
+    }
+    if (until) {
+      conditions.push('at <= ?');
+      params.push(until.getTime());
+    }
+    return { table: resolutionMs === undefined ? 'samples' : 'rollups', where: conditions.join(' AND '), params };
+  }
+
+  const toSample = (row: SampleRow): HistorySample => ({ ...row, at: new Date(row.at).toISOString() });
+
+  /**
+   * Samples of one metric, oldest first; the most recent `limit` when there are more.
+   */
+  function query(metric: string, { limit = DEFAULT_QUERY_LIMIT, ...filter }: HistoryQuery = {}): HistorySample[] {
+    const { table, where, params } = getConditions(metric, filter);
+    const rows = open()
+      .prepare(`SELECT metric, key, at, value FROM ${table} WHERE ${where} ORDER BY at DESC LIMIT ?`)
+      .all(...params, limit) as unknown as SampleRow[];
+    return rows.reverse().map(toSample);
+  }
+
+  /**
+   * Every matching sample of one metric, oldest first, read a row at a time
+   * so long ranges can be streamed. `limit` is ignored.
+   */
+  function* iterate(metric: string, filter: HistoryQuery = {}): Generator<HistorySample> {
+    const { table, where, params } = getConditions(metric, filter);
+    const statement = open().prepare(`SELECT metric, key, at, value FROM ${table} WHERE ${where} ORDER BY at, key`);
+    for (const row of statement.iterate(...params)) yield toSample(row as unknown as SampleRow);
+  }
+
+  /**
+   * Roll up every bucket that has completed since the last run, then delete
+   * raw samples and rollups past their retention.
+   */
+  function compact({ rawMs, rollups }: HistoryRetention, now = Date.now()): CompactionResult {
+    const database = open();
+    let rolledUp = 0;
+    let deleted = 0;
+    database.exec('BEGIN');
+    try {
+      rollups.forEach(({ resolutionMs }, index) => {
+        const { at: last } = database
+          .prepare('SELECT MAX(at) AS at FROM rollups WHERE resolution = ?')
+          .get(resolutionMs) as { at: number | null };
+        const from = last === null ? 0 : last + resolutionMs;
+        const until = Math.floor(now / resolutionMs) * resolutionMs;
+        const bucket = 'CAST(at / ? AS INTEGER) * ?';
+        // The first tier averages raw samples; later ones weight the tier before by its sample counts.
+        const insert = index === 0
+          ? `INSERT OR REPLACE INTO rollups (metric, key, resolution, at, value, min, max, count)
+              SELECT metric, key, ?, ${bucket} AS bucket, AVG(value), MIN(value), MAX(value), COUNT(value)
+              FROM samples WHERE at >= ? AND at < ? GROUP BY metric, key, bucket`
+          : `INSERT OR REPLACE INTO rollups (metric, key, resolution, at, value, min, max, count)
+              SELECT metric, key, ?, ${bucket} AS bucket, SUM(value * count) / SUM(count), MIN(min), MAX(max), SUM(count)
+              FROM rollups WHERE resolution = ? AND at >= ? AND at < ? GROUP BY metric, key, bucket`;
+        const params = index === 0
+          ? [resolutionMs, resolutionMs, resolutionMs, from, until]
+          : [resolutionMs, resolutionMs, resolutionMs, rollups[index - 1].resolutionMs, from, until];
+        if (from < until) rolledUp += Number(database.prepare(insert).run(...params).changes);
+      });
+
+      if (rawMs !== null) deleted += Number(database.prepare('DELETE FROM samples WHERE at < ?').run(now - rawMs).changes);
+      for (const { resolutionMs, retentionMs } of rollups) {
+        if (retentionMs === null) continue;
+        deleted += Number(database.prepare('DELETE FROM rollups WHERE resolution = ? AND at < ?').run(resolutionMs, now - retentionMs).changes);
+      }
+      database.exec('COMMIT');
+    } catch (error) {
+      database.exec('ROLLBACK');
+      throw error;
+    }
+    // Truncate the WAL, which otherwise stays at its largest size on disk.
+    database.exec('PRAGMA wal_checkpoint(TRUNCATE)');
+    return { rolledUp, deleted };
+  }
+
+  function close(): void {
+    db?.close();
+    db = null;
+  }
+
+  return { record, query, iterate, compact, close };
+}
+
+export type HistoryStore = ReturnType<typeof createHistoryStore>;
@@ -5,20 +5,23 @@
  */
 
 import express from 'express';
-import cors from 'cors';
+import http from 'http';
+import http2 from 'http2';
 import path from 'path';
 import fs from 'fs/promises';
 import { fileURLToPath } from 'url';
 
-import type { SetupData, StatusResponse, SetupResponse } from './types.js';
-import { generateTranslatorConfig, generateJdcConfig, normalizeSetupData } from './config-generator.js';
+import type { SetupData, StatusResponse } from './types.js';
+import type { BitcoinNetwork } from '@sv2-ui/shared';
+import { getBitcoinCoreVersionError } from './config-generator.js';
 import {
-  isSupportedBitcoinCoreVersion,
-  normalizeBitcoinCoreVersion,
   TRANSLATOR_MONITORING_PORT,
   JDC_MONITORING_PORT,
+  TRANSLATOR_PORT,
+  CONTAINER_NAMES,
+  DEFAULT_BITCOIN_PATHS,
+  DEFAULT_SHARES_PER_MINUTE,
 } from '@sv2-ui/shared';
-import { BITCOIN_ERROR_MESSAGES } from './messages.js';
 import {
   startStack,
   stopStack,
@@ -29,110 +32,537 @@ import {
   expandHomePath,
   readContainerLogs,
   probeBitcoinSocketWithDocker,
-  autoDiscoverBitcoinRpc
+  autoDiscoverBitcoinRpc,
+  queryBitcoinChainStats,
+  queryRecentBlocks,
+  restartContainer,
+  listRunningContainers,
+  getOwnNetworks,
+  watchContainerEvents,
+  followContainerLogs,
+  startContainer,
+  stopContainer,
+  isContainerNotFound,
+  getContainerImage,
+  getRegistryDigest,
 } from './docker.js';
-import { getLogDiagnostics, getLogStreams, readCollatedLogLines } from './logs/diagnostics.js';
+import type { BitcoinChainStats } from './docker.js';
+import { parseJournalUnits } from './logs/tail.js';
+import { formatListenUrl, parseListenAddresses } from './listen.js';
+import { ensureWritableDir, resolveConfigDir } from './dirs.js';
+import { claimPidFile, daemonize, notifyDaemonParent, parseDaemonArgs } from './daemon.js';
+import { runDoctor } from './doctor.js';
+import { createLogger, parseLogLevelArg, setLogFilter } from './logger.js';
+import { installService, parseServiceArgs, runService, uninstallService } from './service.js';
+import { requireOperatorForWrites } from './auth.js';
+import { createAuthHook, createAuthHookMiddleware, parseAuthHookConfig } from './auth-hook.js';
+import { createHealthMonitor, getHealthTargets } from './health.js';
+import type { HealthReport, HealthTarget } from './health.js';
+import { createGrpcService, parseGrpcListen } from './grpc.js';
+import { parseIpFilter } from './ip-filter.js';
+import {
+  createDiscovery,
+  DISCOVERY_INTERVAL_MS,
+  getUnconfiguredServices,
+  groupDiscoveredStacks,
+  parseDiscoveryMode,
+} from './discovery.js';
+import type { DiscoveredService } from './discovery.js';
+import { createMdnsAdvertiser, parseAdvertiseConfig } from './advertise.js';
+import type { MdnsAdvertiser } from './advertise.js';
+import { createApiKeyQuotaMiddleware, createApiKeyStore, createApiKeyStreamMiddleware, createApiKeyUsageTracker } from './api-keys.js';
+import { registerRuntimeGauge } from './runtime.js';
+import { redactForRequest } from './redact.js';
+import { createMempoolProxy, createMonitoringProxy, getContainerHost, parseMempoolUrl, setContainerOrigins } from './proxy.js';
+import { createDockerAutoconfig, parseDockerAutoconfig } from './docker-autoconfig.js';
+import { createKubernetesDiscovery, parseKubernetesDiscovery } from './kubernetes-discovery.js';
+import { createAlertEngine, createAlertRuleStore } from './alerts.js';
+import type { AlertTransition } from './alerts.js';
+import { createWebhookNotifier, parseWebhookConfig } from './webhooks.js';
+import { createTelegramNotifier, parseTelegramConfig } from './telegram.js';
+import { createEmailNotifier, getNextDailyRun, parseEmailConfig } from './email.js';
+import { createPushNotifier, parsePushConfig } from './push.js';
+import { createDiscordNotifier, parseDiscordConfig } from './discord.js';
+import { createMessages, loadMessageCatalogs, parseNotificationLocales } from './notification-messages.js';
+import type { NotificationChannel } from './notification-messages.js';
+import { createAuditLog, createAuditMiddleware } from './audit.js';
+import { normalizeBasePath } from './base-path.js';
+import { createStaticAssetHandler, parseHeadlessMode } from './static-assets.js';
+import { createRateLimitMiddleware, createTokenBucketLimiter, LOGIN_RATE_LIMIT, parseRateLimitOptions } from './rate-limit.js';
+import { fetchMonitoringJson, fetchStackData, getSectionFailureCount } from './monitoring.js';
+import {
+  createStackPoller,
+  createStackSnapshot,
+  getCachedMonitoringResponse,
+  getJitteredDelayMs,
+  parseBitcoinPollIntervalMs,
+  parsePollIntervals,
+  parsePollJitter,
+} from './poller.js';
+import type { StackSnapshot } from './poller.js';
+import { evaluateReadiness, parseReadyGraceMs } from './probes.js';
+import type { StackData } from './monitoring.js';
+import { getTestNetworkLabel, summarizeStack } from './summary.js';
+import { fetchServerStatus, formatServerStatus, parseStatusArgs } from './status.js';
+import { parseTuiArgs, runTui } from './tui.js';
+import type { StackSummary } from './summary.js';
+import { createSummaryStream } from './summary-stream.js';
+import { createBestShareStore } from './best-shares.js';
+import { createLifetimeCounterStore } from './lifetime-counters.js';
+import { createEventLog } from './events.js';
+import { findFoundBlock, getPayoutScript, withFoundBlock } from './found-blocks.js';
+import type { StackEvent } from './events.js';
+import { createRealtimeHub, createRealtimeRedactor } from './realtime.js';
+import { createBlockNotifier, parseZmqEndpoints } from './zmq.js';
+import type { RefreshTarget } from './realtime.js';
+import { checkSv1Listener, probeSv1Stratum } from './sv1-listener.js';
+import type { Sv1ListenerStatus, Sv1ProbeResult } from './sv1-listener.js';
+import { createScheduleStore } from './schedule.js';
+import { createShareLinkStore } from './share-links.js';
+import { createStackMonitors, createStackRegistry, getStackEndpoints, parseRemoteStacks } from './stacks.js';
+import { createFederation, createFederationRegistry } from './federation.js';
+import { createFederationRoutes } from './routes/federation.js';
+import type { RemoteStack } from './stacks.js';
+import { createStateStore } from './state.js';
+import type { SavedState } from './state.js';
+import { createApp } from './app.js';
+import { createApiKeyRoutes } from './routes/api-keys.js';
+import { createUserRoutes } from './routes/users.js';
+import { createUserAuthMiddleware, createUserStore } from './users.js';
+import { createSessionMiddleware, createSessionStore, parseSessionConfig } from './sessions.js';
+import { createSessionRoutes } from './routes/sessions.js';
+import { createHealthRoutes } from './routes/health.js';
+import { createLogRoutes } from './routes/logs.js';
+import { createServiceRoutes } from './routes/services.js';
+import { createProbeRoutes } from './routes/probes.js';
+import { createShareLinkRoutes } from './routes/share-links.js';
+import { createUpdateRoutes } from './routes/updates.js';
+import { createPriceRoutes } from './routes/price.js';
+import { createDockerEnvRoutes } from './routes/docker-env.js';
+import { createKeygenRoutes } from './routes/keygen.js';
+import { createCoinbaseScriptRoutes } from './routes/coinbase-script.js';
+import { createQrRoutes } from './routes/qr.js';
+import { createSv2ProbeRoutes } from './routes/sv2-probe.js';
+import { createApiExplorerRoutes } from './routes/api-explorer.js';
+import { createMonitoringRoutes } from './routes/monitoring.js';
+import { getMonitoringSpecPath, readMonitoringSpec } from './api-explorer.js';
+import type { OpenApiDocument } from './api-explorer.js';
+import { formatKeypair, generateAuthorityKeypair } from './keygen.js';
+import { createUpdateChecker, parseUpdateCheckConfig } from './updates.js';
+import { createPriceFeed, parsePriceConfig } from './price.js';
+import { isServiceDeployed } from './service-control.js';
+import { createTelemetryReporter, DISABLED_TELEMETRY_STATUS, parseTelemetryConfig } from './telemetry.js';
+import { createHistoryStore, parseHistoryRetention } from './history.js';
+import { createMinerLabelStore } from './miner-labels.js';
+import { buildStateSnapshot } from './snapshot.js';
+import type { StateSnapshot } from './snapshot.js';
+import { createLatencyProber, getLatencyEndpoints, parseLatencyConfig } from './latency.js';
+import { createLatencyRoutes } from './routes/latency.js';
+import { createPluginHost, loadPlugins, resolvePluginsDir } from './plugins.js';
+import { createPluginRoutes } from './routes/plugins.js';
+import { createHistoryRoutes } from './routes/history.js';
+import { createMinerRoutes } from './routes/miners.js';
+import { exportHistory, parseExportArgs } from './history-export.js';
+import { backupHistory, parseBackupArgs, restoreHistory } from './backup.js';
+import { formatCheckReport, parseCheckArgs, runChecks } from './check.js';
+import { getMinerSamples, getStackSamples } from './stack-history.js';
+import { createShareWindows } from './share-windows.js';
+import { createVardiffTracker } from './vardiff.js';
+import { createVardiffRoutes } from './routes/vardiff.js';
+import { createStatusRoutes } from './routes/status.js';
+import { createRetargetRoutes } from './routes/retarget.js';
+import { createSv1Routes } from './routes/sv1.js';
+import { createEventRoutes } from './routes/events.js';
+import { createAlertRoutes } from './routes/alerts.js';
+import { createStackRoutes } from './routes/stacks.js';
+import { createAuditRoutes } from './routes/audit.js';
+import { createPollingRoutes } from './routes/polling.js';
+import { createScheduleRoutes } from './routes/schedule.js';
+import { createBitcoinRoutes } from './routes/bitcoin.js';
+import { createSetupRoutes } from './routes/setup.js';
+import { createDiagnosticRoutes } from './routes/diagnostics.js';
+import { createBadgeRoutes } from './routes/badges.js';
+import { createUiRoutes } from './routes/ui.js';
+import {
+  createDemoSimulation,
+  createDemoStateStore,
+  DEMO_SETUP,
+  getDemoStackStatus,
+  parseDemoMode,
+  rejectStackControl,
+  startDemoServers,
+} from './demo.js';
+import { getReplaySetup, loadRecording, parseTrafficArgs, startRecording, startReplay } from './traffic.js';
+
+// `keygen` prints a fresh authority keypair instead of starting the server.
+if (process.argv[2] === 'keygen') {
+  process.stdout.write(formatKeypair(generateAuthorityKeypair()));
+  process.exit(0);
+}
 
 const __dirname = path.dirname(fileURLToPath(import.meta.url));
-const app = express();
-const PORT = process.env.PORT || 3001;
 
 // Config storage
-const CONFIG_DIR = process.env.CONFIG_DIR || path.join(__dirname, '../../data/config');
-const STATE_FILE = path.join(CONFIG_DIR, 'state.json');
+const CONFIG_DIR = resolveConfigDir({ legacyDir: path.join(__dirname, '../../data/config') });
+const HISTORY_DB = process.env.HISTORY_DB || path.join(CONFIG_DIR, 'history.db');
+
+// `status [url]` prints the state of a running server instead of starting one (see status.ts).
+if (process.argv[2] === 'status') {
+  const args = parseStatusArgs(process.argv.slice(3));
+  try {
+    const status = await fetchServerStatus(args);
+    process.stdout.write(args.json ? `${JSON.stringify(status, null, 2)}\n` : formatServerStatus(status));
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : error);
+    process.exit(1);
+  }
+  process.exit(0);
+}
+
+// `tui [url]` draws a live dashboard of a running server in the terminal (see tui.ts).
+if (process.argv[2] === 'tui') {
+  await runTui(parseTuiArgs(process.argv.slice(3)));
+  process.exit(0);
+}
+
+// `check` validates the configuration and reaches every service it names,
+// instead of starting the server (see check.ts); `doctor` also diagnoses the
+// running stack (see doctor.ts). They run before the settings below are
+// parsed, so a bad value ends up in their report.
+if (process.argv[2] === 'check' || process.argv[2] === 'doctor') {
+  const command = process.argv[2];
+  const args = parseCheckArgs(process.argv.slice(3), command);
+  const results = command === 'doctor'
+    ? await runDoctor(process.env, { configDir: CONFIG_DIR, historyDb: HISTORY_DB, timeoutMs: args.timeoutMs })
+    : await runChecks(process.env, CONFIG_DIR, args.timeoutMs);
+  const ok = !results.some((result) => result.status === 'fail');
+  process.stdout.write(args.json ? `${JSON.stringify({ ok, results }, null, 2)}\n` : formatCheckReport(results));
+  process.exit(ok ? 0 : 1);
+}
+
+const PORT = Number(process.env.PORT || 3001);
+const LISTEN_ADDRESSES = parseListenAddresses(process.env.LISTEN, PORT);
+const BASE_PATH = normalizeBasePath(process.env.BASE_PATH);
+// Every route is registered on this router, which is mounted at BASE_PATH.
+const router = express.Router();
+
+// `export` writes the history database to files instead of starting the server.
+if (process.argv[2] === 'export') {
+  const store = createHistoryStore(HISTORY_DB);
+  for (const file of await exportHistory(store, parseExportArgs(process.argv.slice(3), parseHistoryRetention()))) console.log(file);
+  store.close();
+  process.exit(0);
+}
+
+// `backup <file>` and `restore <file>` copy the history database and CONFIG_DIR (see backup.ts).
+if (process.argv[2] === 'backup' || process.argv[2] === 'restore') {
+  const command = process.argv[2];
+  const args = parseBackupArgs(command, process.argv.slice(3));
+  const paths = { historyDb: HISTORY_DB, configDir: CONFIG_DIR };
+  const { file, configFiles } = command === 'backup' ? backupHistory(paths, args.file) : restoreHistory(paths, args);
+  console.log(command === 'backup'
+    ? `Backed up the history database and ${configFiles} config files to ${file}`
+    : `Restored the history database and ${configFiles} config files from ${file}`);
+  process.exit(0);
+}
+
+// `service install|uninstall` registers sv2-ui to start at boot on Windows,
+// and `service run` keeps the server running (see service.ts).
+if (process.argv[2] === 'service') {
+  const args = parseServiceArgs(process.argv.slice(3));
+  const paths = { configDir: CONFIG_DIR, script: process.argv[1], execArgv: process.execArgv };
+  try {
+    if (args.action === 'install') {
+      console.log(`Installed the ${await installService(args, paths)} boot task; it runs as SYSTEM with ${CONFIG_DIR}`);
+    } else if (args.action === 'uninstall') {
+      await uninstallService(args, paths);
+      console.log(`Removed the ${args.name} boot task`);
+    } else {
+      await runService(args, paths);
+    }
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : error);
+    process.exit(1);
+  }
+  process.exit(0);
+}
+
+// --log-level, or LOG_LEVEL, sets how much each module logs (see logger.ts).
+setLogFilter(parseLogLevelArg());
+const log = createLogger('server');
+
+// --daemon starts the server in the background instead, and --pid-file records
+// the server's PID (see daemon.ts).
+const DAEMON = parseDaemonArgs();
+if (DAEMON.daemon) {
+  const logFile = DAEMON.logFile ?? path.join(CONFIG_DIR, 'sv2-ui.log');
+  try {
+    const pid = await daemonize({ logFile, pidFile: DAEMON.pidFile });
+    console.log(`sv2-ui is running in the background with PID ${pid}, logging to ${logFile}`);
+  } catch (error) {
+    console.error(error instanceof Error ? error.message : error);
+    process.exit(1);
+  }
+  process.exit(0);
+}
+if (DAEMON.pidFile) {
+  process.on('exit', claimPidFile(DAEMON.pidFile));
+}
+
+// --demo serves a simulated stack instead of the containers (see demo.ts), and
+// --replay a recorded one (see traffic.ts).
+const DEMO_MODE = parseDemoMode();
+const TRAFFIC = parseTrafficArgs();
+if (DEMO_MODE && TRAFFIC) {
+  throw new Error(`--demo can't be combined with --${TRAFFIC.mode}`);
+}
+const replay = TRAFFIC?.mode === 'replay' ? await loadRecording(TRAFFIC.dir) : null;
+const SIMULATED_SETUP = DEMO_MODE ? DEMO_SETUP : replay ? getReplaySetup(replay.session) : null;
+const stateStore = SIMULATED_SETUP
+  ? createDemoStateStore(SIMULATED_SETUP, { onChange: () => stackPoller.invalidate() })
+  : createStateStore(path.join(CONFIG_DIR, 'state.json'), { onChange: () => stackPoller.invalidate() });
+const getServiceStatus = SIMULATED_SETUP ? async (mode: SavedState['mode']) => getDemoStackStatus(mode) : getStackStatus;
+const shareLinks = createShareLinkStore(path.join(CONFIG_DIR, 'share-links.json'));
+const apiKeys = createApiKeyStore(path.join(CONFIG_DIR, 'api-keys.json'));
+const users = createUserStore(path.join(CONFIG_DIR, 'users.db'));
+const SESSION_CONFIG = parseSessionConfig();
+// Logins, Basic credential checks and failed auth hook checks share one
+// budget per client.
+const loginLimiter = createTokenBucketLimiter(LOGIN_RATE_LIMIT);
+const sessions = createSessionStore(SESSION_CONFIG);
+registerRuntimeGauge('caches', 'sessions', sessions.size);
+const apiKeyUsage = createApiKeyUsageTracker();
+// On stream routes, so each one holds an API key's stream while it is open.
+const holdStream = createApiKeyStreamMiddleware(apiKeyUsage);
+const history = createHistoryStore(HISTORY_DB);
+const HISTORY_RETENTION = parseHistoryRetention();
+const minerLabels = createMinerLabelStore(HISTORY_DB);
+registerRuntimeGauge('caches', 'api-key-usage', apiKeyUsage.size);
+const AUTH_HOOK_CONFIG = parseAuthHookConfig();
+const authHook = AUTH_HOOK_CONFIG ? createAuthHook(AUTH_HOOK_CONFIG, { failures: loginLimiter }) : null;
+if (authHook) {
+  registerRuntimeGauge('caches', 'auth-hook', authHook.size);
+  registerRuntimeGauge('tasks', 'auth-hook-calls', authHook.inFlight);
+}
+const eventLog = createEventLog(path.join(CONFIG_DIR, 'events.json'));
+const bestShares = createBestShareStore(path.join(CONFIG_DIR, 'best-shares.json'));
+const lifetimeCounters = createLifetimeCounterStore(path.join(CONFIG_DIR, 'lifetime-counters.json'));
+const schedule = createScheduleStore(path.join(CONFIG_DIR, 'schedule.json'));
+const auditLog = createAuditLog(path.join(CONFIG_DIR, 'audit.log'));
+const alertRules = createAlertRuleStore(path.join(CONFIG_DIR, 'alerts.json'));
+const alerts = createAlertEngine();
+const healthMonitor = createHealthMonitor();
+registerRuntimeGauge('caches', 'health-last-success', healthMonitor.size);
+const stackRegistry = createStackRegistry(path.join(CONFIG_DIR, 'stacks.json'));
+const stackMonitors = createStackMonitors(path.join(CONFIG_DIR, 'stacks'));
+const DISCOVERY_MODE = parseDiscoveryMode();
+const discovery = DISCOVERY_MODE ? createDiscovery() : null;
+if (discovery) registerRuntimeGauge('caches', 'mdns-discovered', discovery.size);
+const ADVERTISE_CONFIG = parseAdvertiseConfig();
+const dockerAutoconfig = parseDockerAutoconfig() && !DEMO_MODE && !TRAFFIC
+  ? createDockerAutoconfig({
+    listContainers: listRunningContainers,
+    getOwnNetworks,
+    watch: watchContainerEvents,
+    apply: (origins) => {
+      setContainerOrigins(origins);
+      stackPoller.invalidate();
+    },
+  })
+  : null;
+const K8S_DISCOVERY_CONFIG = parseKubernetesDiscovery();
+if (K8S_DISCOVERY_CONFIG && dockerAutoconfig) {
+  throw new Error('DOCKER_AUTOCONFIG and K8S_DISCOVERY cannot both be on');
+}
+const kubernetesDiscovery = K8S_DISCOVERY_CONFIG && !DEMO_MODE && !TRAFFIC
+  ? createKubernetesDiscovery({
+    config: K8S_DISCOVERY_CONFIG,
+    apply: (origins) => {
+      setContainerOrigins(origins);
+      stackPoller.invalidate();
+    },
+  })
+  : null;
+let advertiser: MdnsAdvertiser | null = null;
+registerRuntimeGauge('caches', 'remote-stacks', () => stackMonitors.list().length);
+const WEBHOOK_CONFIG = parseWebhookConfig();
+const webhooks = WEBHOOK_CONFIG ? createWebhookNotifier(WEBHOOK_CONFIG) : null;
+if (webhooks) {
+  registerRuntimeGauge('tasks', 'webhook-deliveries', webhooks.inFlight);
+}
+const MESSAGE_CATALOGS = loadMessageCatalogs(process.env.MESSAGES_DIR || path.join(CONFIG_DIR, 'messages'));
+const NOTIFICATION_LOCALES = parseNotificationLocales(process.env, Object.keys(MESSAGE_CATALOGS));
+const getChannelMessages = (channel: NotificationChannel) => createMessages(NOTIFICATION_LOCALES[channel], MESSAGE_CATALOGS);
+const TELEGRAM_CONFIG = parseTelegramConfig();
+const telegram = TELEGRAM_CONFIG ? createTelegramNotifier(TELEGRAM_CONFIG, { messages: getChannelMessages('telegram') }) : null;
+const EMAIL_CONFIG = parseEmailConfig();
+const email = EMAIL_CONFIG ? createEmailNotifier(EMAIL_CONFIG, { messages: getChannelMessages('email') }) : null;
+const PUSH_CONFIG = parsePushConfig();
+const push = PUSH_CONFIG ? createPushNotifier(PUSH_CONFIG, { messages: getChannelMessages('push') }) : null;
+const DISCORD_CONFIG = parseDiscordConfig();
+const discord = DISCORD_CONFIG ? createDiscordNotifier(DISCORD_CONFIG, { messages: getChannelMessages('discord') }) : null;
+const realtime = createRealtimeHub({ redact: createRealtimeRedactor(redactForRequest) });
+registerRuntimeGauge('caches', 'realtime-clients', realtime.size);
+const ZMQ_ENDPOINTS = SIMULATED_SETUP ? [] : parseZmqEndpoints();
+const blockNotifier = ZMQ_ENDPOINTS.length > 0
+  ? createBlockNotifier({
+    endpoints: ZMQ_ENDPOINTS,
+    onBlock: (block) => {
+      log.info(`ZMQ: new block ${block.height === null ? '' : `${block.height} `}${block.hash}`);
+      // Chain stats only move with a block.
+      chainStatsCache = null;
+      realtime.broadcast({ type: 'block', hash: block.hash, height: block.height, at: new Date().toISOString() });
+      void pollNow('all').catch((error) => log.error('Polling after a new block failed:', error));
+    },
+    onError: (endpoint, error) => log.error(`ZMQ subscription to ${endpoint.host}:${endpoint.port} failed:`, error.message),
+  })
+  : null;
+if (blockNotifier) registerRuntimeGauge('tasks', 'zmq-connections', blockNotifier.connections);
+const READY_GRACE_MS = parseReadyGraceMs();
+const POLL_JITTER = parsePollJitter();
+const BITCOIN_POLL_INTERVAL_MS = parseBitcoinPollIntervalMs();
+const stackPoller = createStackPoller(async (services) => {
+  const state = await stateStore.load();
+  return state.configured ? fetchStackData(state.mode, {}, services) : null;
+}, { intervalMs: parsePollIntervals(), jitter: POLL_JITTER });
+registerRuntimeGauge('tasks', 'stack-poll', stackPoller.inFlight);
+const vardiff = createVardiffTracker();
+// Stack totals, per-miner hashrate and per-channel difficulty go to the
+// history database after every poll (see stack-history.ts and vardiff.ts).
+stackPoller.onSnapshot((snapshot) => {
+  try {
+    const summary = summarizeStack(snapshot.data, { miningMode: null, poolName: null });
+    history.record([
+      ...getStackSamples(summary),
+      ...getMinerSamples(snapshot.data, summary.generatedAt),
+      ...vardiff.observe(snapshot.data, summary.generatedAt),
+    ]);
+  } catch (error) {
+    log.error('Recording stack history failed:', error);
+  }
+});
+// Rolling accepted/rejected counts for the summary, from counter growth between polls.
+const shareWindows = createShareWindows();
+stackPoller.onSnapshot((snapshot) => shareWindows.observe(snapshot.data, Date.parse(snapshot.fetchedAt)));
+stackPoller.onSnapshot((snapshot) => {
+  bestShares.record(snapshot.data, snapshot.fetchedAt).catch((error) => log.error('Recording best shares failed:', error));
+  lifetimeCounters.record(snapshot.data, snapshot.fetchedAt).catch((error) => log.error('Recording lifetime counters failed:', error));
+});
+// /api/summary/stream sends what changed in the summary after every poll.
+const summaryStream = createSummaryStream({ redact: redactForRequest });
+registerRuntimeGauge('caches', 'summary-stream-clients', summaryStream.size);
+stackPoller.onSnapshot((snapshot) => {
+  if (summaryStream.size() === 0) return;
+  stateStore.load()
+    .then((state) => getSummaryResponse(state, snapshot))
+    .then((summary) => summaryStream.publish(summary))
+    .catch((error) => log.error('Summary stream error:', error));
+});
+registerRuntimeGauge('caches', 'stack-snapshot', () => (stackPoller.latest() ? 1 : 0));
+registerRuntimeGauge('failures', 'stack-section:translator', () => getSectionFailureCount('translator'));
+registerRuntimeGauge('failures', 'stack-section:jdc', () => getSectionFailureCount('jdc'));
 
 const AUTO_START_RETRY_INTERVAL_MS = 30_000;
+const EVENT_SAMPLE_INTERVAL_MS = 30_000;
+const SV1_LISTENER_CHECK_INTERVAL_MS = 15_000;
+const HISTORY_COMPACT_INTERVAL_MS = 5 * 60_000;
+const FEDERATION_HEALTH_INTERVAL_MS = 30_000;
+const SV1_PROBE_MAX_AGE_MS = 60_000;
 
 type StackBusyReason = 'auto-start' | 'manual';
 
 let stackBusyReason: StackBusyReason | null = null;
 
-type SavedState = {
-  configured: boolean;
-  miningMode: 'solo' | 'pool' | null;
-  mode: 'jd' | 'no-jd' | null;
-  data: SetupData | null;
-  shouldBeRunning: boolean;
-};
-
-// Middleware
-app.use(cors());
-app.use(express.json());
-
 // Serve static files from the built UI
 // In Docker (NODE_ENV=production): /app/public
 // In development: ../../dist (relative to server/dist/)
 const UI_DIR = process.env.NODE_ENV === 'production'
   ? path.join(__dirname, '../public')
   : path.join(__dirname, '../../dist');
-app.use(express.static(UI_DIR));
+// --headless serves the APIs only, for a dashboard hosted elsewhere.
+const HEADLESS = parseHeadlessMode();
+if (!HEADLESS) {
+  // Precompressed, ETagged build output first; express.static covers anything
+  // the build manifest doesn't list. index.html is rendered by the SPA fallback
+  // so it can carry the base href.
+  router.use(createStaticAssetHandler(UI_DIR));
+  router.use(express.static(UI_DIR, { index: false }));
+}
+// Requests carrying an API key count against its quota before reaching any route.
+router.use(createApiKeyQuotaMiddleware(apiKeys, apiKeyUsage));
+router.use(createSessionMiddleware(sessions, users));
+router.use(createUserAuthMiddleware(users, { limiter: loginLimiter }));
+if (authHook) router.use(createAuthHookMiddleware(authHook));
+if (SIMULATED_SETUP) router.use(rejectStackControl(DEMO_MODE ? 'demo mode' : 'replay mode'));
+
+function getPoolName(state: SavedState): string | null {
+  return state.data?.miningMode === 'solo' && state.data?.mode === 'jd'
+    ? 'Sovereign Solo Mining'
+    : (state.data?.pool?.name ?? null);
+}
 
 /**
- * Load saved state
+ * The Bitcoin network, known from the node configured in JD mode.
  */
-function getDefaultState(): SavedState {
-  return { configured: false, miningMode: null, mode: null, data: null, shouldBeRunning: false };
+function getNetwork(state: SavedState): BitcoinNetwork | null {
+  return state.mode === 'jd' ? state.data?.bitcoin?.network ?? null : null;
 }
 
-function normalizeSavedState(state: Partial<SavedState>): SavedState {
-  const configured = state.configured ?? false;
-  return {
-    configured,
-    miningMode: state.miningMode ?? null,
-    mode: state.mode ?? null,
-    data: normalizeSetupBitcoinCoreVersion(state.data ?? null),
-    shouldBeRunning: state.shouldBeRunning ?? configured,
-  };
+/**
+ * Title for feeds and calendars, flagging stacks on a test network.
+ */
+function getStackTitle(state: SavedState): string {
+  const poolName = getPoolName(state);
+  const networkLabel = getTestNetworkLabel(getNetwork(state));
+  return [poolName ? `SV2 Mining · ${poolName}` : 'SV2 Mining', networkLabel && `(${networkLabel})`]
+    .filter(Boolean)
+    .join(' ');
 }
 
-function normalizeSetupBitcoinCoreVersion(data: SetupData | null): SetupData | null {
-  if (!data?.bitcoin) {
-    return data;
-  }
+function summarizeStackData(state: SavedState, data: StackData): StackSummary {
+  return summarizeStack(data, {
+    miningMode: state.miningMode,
+    poolName: getPoolName(state),
+    network: getNetwork(state),
+  });
+}
 
-  return {
-    ...data,
-    bitcoin: {
-      ...data.bitcoin,
-      core_version: normalizeBitcoinCoreVersion(data.bitcoin.core_version),
-    },
-  };
+function summarizeRemoteStack(stack: RemoteStack, data: StackData): StackSummary {
+  return summarizeStack(data, { miningMode: stack.miningMode, poolName: stack.name, network: stack.network });
 }
 
-async function loadState(): Promise<SavedState> {
-  try {
-    const content = await fs.readFile(STATE_FILE, 'utf-8');
-    return normalizeSavedState(JSON.parse(content) as Partial<SavedState>);
-  } catch {
-    return getDefaultState();
-  }
+/**
+ * The /api/summary document: the summary and where its numbers come from,
+ * with the counters kept across restarts (see lifetime-counters.ts).
+ */
+async function getSummaryResponse(state: SavedState, snapshot: StackSnapshot) {
+  const [lifetime, best] = await Promise.all([lifetimeCounters.get(), bestShares.list()]);
+  return {
+    ...summarizeStackData(state, snapshot.data),
+    freshness: stackPoller.freshness(snapshot),
+    errors: stackPoller.errors(snapshot),
+    shareWindows: shareWindows.get(),
+    lifetime: { ...lifetime, bestDifficulty: best.overall?.difficulty ?? 0 },
+  };
 }
 
 /**
- * Save state
+ * The background poller's snapshot, fetched directly only when it has
+ * nothing fresh for the current mode.
  */
-async function saveState(data: SetupData, shouldBeRunning = true): Promise<void> {
-  const normalizedData = normalizeSetupBitcoinCoreVersion(data) ?? data;
-  await fs.mkdir(CONFIG_DIR, { recursive: true });
-  await fs.writeFile(STATE_FILE, JSON.stringify({
-    configured: true,
-    miningMode: normalizedData.miningMode,
-    mode: normalizedData.mode,
-    data: normalizedData,
-    shouldBeRunning,
-  }, null, 2));
-}
-
-function getBitcoinCoreVersionError(data: SetupData): string | null {
-  if (data.mode !== 'jd') {
-    return null;
-  }
+async function getStackSnapshot(state: SavedState): Promise<StackSnapshot> {
+  const snapshot = await stackPoller.get(state.mode);
+  if (snapshot) return snapshot;
+  return createStackSnapshot(await fetchStackData(state.mode), new Date().toISOString());
+}
 
-  if (!isSupportedBitcoinCoreVersion(data.bitcoin?.core_version)) {
-    return BITCOIN_ERROR_MESSAGES.selectVersion;
-  }
+async function getStackData(state: SavedState): Promise<StackData> {
+  return (await getStackSnapshot(state)).data;
+}
 
-  return null;
+async function getStackSummary(state?: SavedState): Promise<StackSummary> {
+  const current = state ?? await stateStore.load();
+  return summarizeStackData(current, await getStackData(current));
 }
 
 function isStackRunning(
@@ -159,99 +589,298 @@ function finishStackOperation(reason: StackBusyReason): void {
   }
 }
 
-function stackBusyResponse() {
-  return {
-    success: false,
-    error: stackBusyReason === 'auto-start'
-      ? 'Mining services are already starting. Please wait.'
-      : 'Mining services are busy. Please wait.',
-  };
-}
+registerRuntimeGauge('tasks', 'stack-operation', () => (stackBusyReason ? 1 : 0));
 
-/**
- * GET /api/health - Health check
- */
-app.get('/api/health', async (_req, res) => {
-  const dockerOk = await isDockerAvailable();
-  res.json({
-    status: 'ok',
-    docker: dockerOk,
-  });
-});
+function getStackBusyError(): string {
+  return stackBusyReason === 'auto-start'
+    ? 'Mining services are already starting. Please wait.'
+    : 'Mining services are busy. Please wait.';
+}
 
 /**
- * GET /api/status - Get current stack status
+ * Claim the stack for a request, or null while another operation holds it.
  */
-app.get('/api/status', async (_req, res) => {
-  try {
-    const state = await loadState();
-    const containers = await getStackStatus(state.mode);
-
-    const response: StatusResponse = {
-      configured: state.configured,
-      running: isStackRunning(state.mode, containers),
-      autoStarting: stackBusyReason === 'auto-start',
-      shouldBeRunning: state.shouldBeRunning,
-      miningMode: state.miningMode,
-      mode: state.mode,
-      poolName: state.data?.miningMode === 'solo' && state.data?.mode === 'jd'
-        ? 'Sovereign Solo Mining'
-        : (state.data?.pool?.name ?? null),
-      containers,
-    };
+function claimStack(): (() => void) | null {
+  return beginStackOperation('manual') ? () => finishStackOperation('manual') : null;
+}
 
-    res.json(response);
-  } catch (error) {
-    console.error('Status error:', error);
-    res.status(500).json({ error: 'Failed to get status' });
-  }
-});
+async function getCurrentHealthTargets(): Promise<HealthTarget[]> {
+  const [state, remoteStacks] = await Promise.all([stateStore.load(), stackRegistry.get()]);
+  return getHealthTargets(state.configured ? state.mode : null, remoteStacks);
+}
 
-/**
- * GET /api/config - Get current configuration
- */
-app.get('/api/config', async (_req, res) => {
-  try {
-    const state = await loadState();
-    res.json({
-      configured: state.configured,
-      config: state.data,
-    });
-  } catch (error) {
-    console.error('Config error:', error);
-    res.status(500).json({ error: 'Failed to get config' });
-  }
-});
+async function getHealthReport(): Promise<HealthReport> {
+  const [targets, docker, sv1, sites] = await Promise.all([
+    getCurrentHealthTargets(),
+    isDockerAvailable(),
+    getSv1Probe(),
+    federation.health(),
+  ]);
+  return healthMonitor.check(targets, docker, sv1, sites);
+}
 
+router.use(createHealthRoutes({
+  healthMonitor,
+  isDockerAvailable,
+  getTargets: getCurrentHealthTargets,
+  getSv1Probe,
+  getSiteHealth: () => federation.health(),
+}));
 
-/**
- * GET /api/env - Host environment variables relevant to the UI
- */
-app.get('/api/env', (_req, res) => {
-  res.json({ HOST_OS: process.env.HOST_OS || null, STRATUM_HOST: process.env.STRATUM_HOST || null });
-});
+async function getStatusResponse(state: SavedState): Promise<StatusResponse> {
+  const containers = await getServiceStatus(state.mode);
+  return {
+    configured: state.configured,
+    running: isStackRunning(state.mode, containers),
+    autoStarting: stackBusyReason === 'auto-start',
+    shouldBeRunning: state.shouldBeRunning,
+    miningMode: state.miningMode,
+    mode: state.mode,
+    poolName: getPoolName(state),
+    network: getNetwork(state),
+    containers,
+  };
+}
 
-/**
- * POST /api/validate/bitcoin-socket - Check if a Bitcoin Core IPC socket is listening
- */
-app.post('/api/validate/bitcoin-socket', async (req, res) => {
-  const { socket_path } = req.body;
-  if (!socket_path || typeof socket_path !== 'string') {
-    return res.status(400).json({ valid: false, error: 'socket_path is required' });
-  }
+async function getStateSnapshot(): Promise<StateSnapshot> {
+  const state = await stateStore.load();
+  const [status, health, snapshot, rules, remoteStacks] = await Promise.all([
+    getStatusResponse(state),
+    getHealthReport(),
+    getStackSnapshot(state),
+    alertRules.get(),
+    stackRegistry.get(),
+  ]);
+  return buildStateSnapshot({
+    status,
+    health,
+    summary: await getSummaryResponse(state, snapshot),
+    alerts: alerts.list(rules),
+    anomalies: alerts.anomalies(),
+    data: snapshot.data,
+    config: {
+      configured: state.configured,
+      setup: state.data,
+      integrations: {
+        authHook: authHook !== null,
+        users: users.count() > 0,
+        webhooks: webhooks !== null,
+        telegram: telegram !== null,
+        email: email !== null,
+        push: push !== null,
+        discord: discord !== null,
+        telemetry: telemetry !== null,
+        latencyProbe: latencyProber !== null,
+        grpc: grpc !== null,
+      },
+      remoteStacks: remoteStacks.map(({ id, name }) => ({ id, name })),
+    },
+  });
+}
 
-  const resolved = expandHomePath(socket_path);
-  const result = await probeBitcoinSocketWithDocker(resolved);
-  return res.json(result);
+router.use(createStatusRoutes({
+  loadState: stateStore.load,
+  getStatus: getStatusResponse,
+  getSummary: async (state) => getSummaryResponse(state, await getStackSnapshot(state)),
+  getSnapshot: getStateSnapshot,
+  getTelemetryStatus: () => telemetry?.status() ?? DISABLED_TELEMETRY_STATUS,
+  summaryStream: summaryStream.handler,
+  holdStream,
+}));
+
+router.use(createShareLinkRoutes({ shareLinks, getStackSummary: () => getStackSummary(), basePath: BASE_PATH }));
+router.use(createApiKeyRoutes({ apiKeys, usage: apiKeyUsage }));
+router.use(createUserRoutes({ users, sessions }));
+router.use(createSessionRoutes({ sessions, users, config: SESSION_CONFIG, cookiePath: BASE_PATH, loginLimiter }));
+
+router.use(createRetargetRoutes({
+  getChainStats: async () => getChainStats(await stateStore.load()),
+  getHashrate: async () => {
+    const state = await stateStore.load();
+    const summary = state.shouldBeRunning ? await getStackSummary(state) : null;
+    return summary?.online ? summary.hashrate : 0;
+  },
+}));
+router.use(createSv1Routes({
+  getListenerStatus: () => sv1ListenerStatus,
+  refreshListenerStatus: refreshSv1ListenerStatus,
+  listenerMaxAgeMs: SV1_LISTENER_CHECK_INTERVAL_MS,
+  isConfigured: async () => (await stateStore.load()).configured,
+  probe: (user) => (user
+    ? probeSv1Stratum(getContainerHost(CONTAINER_NAMES.translator), TRANSLATOR_PORT, { user })
+    : getSv1Probe(0)),
+}));
+
+router.use(createEventRoutes({
+  eventLog,
+  bestShares,
+  getTitle: async () => getStackTitle(await stateStore.load()),
+  basePath: BASE_PATH,
+}));
+router.use(createAlertRoutes({ alerts, alertRules }));
+
+router.use(createStackRoutes({
+  registry: stackRegistry,
+  monitors: stackMonitors,
+  alertRules,
+  discovery,
+  getSummary: async (stack) => {
+    const data = await fetchStackData(stack.mode, getStackEndpoints(stack));
+    const freshness = stackPoller.freshness(createStackSnapshot(data, new Date().toISOString()));
+    // Remote stacks are fetched on every request, so the next one retries.
+    const errors = (data.errors ?? []).map((error) => ({ ...error, retryInSecs: 0 }));
+    return { ...summarizeRemoteStack(stack, data), freshness, errors };
+  },
+}));
+
+router.use(createAuditRoutes({ auditLog }));
+router.use(createPollingRoutes({
+  getSchedule: () => stackPoller.schedule(),
+  realtime,
+  pollNow,
+  loadState: stateStore.load,
+  warmRestart: {
+    pauseAlerts: () => eventLog.pause(),
+    restart: restartContainer,
+    isHealthy: async (service) => {
+      try {
+        await fetchMonitoringJson(service, '/health', 2000);
+        return true;
+      } catch {
+        return false;
+      }
+    },
+    pollNow: () => pollNow('all'),
+  },
+  beginOperation: claimStack,
+  getBusyError: getStackBusyError,
+  holdStream,
+}));
+
+router.use(createServiceRoutes({
+  auditLog,
+  getMode: async () => (await stateStore.load()).mode,
+  control: async (service, action) => {
+    if (action === 'start') await startContainer(service);
+    else if (action === 'stop') await stopContainer(service);
+    else await restartContainer(service);
+
+    // A stop should stick instead of being undone by auto-start; a start resumes it.
+    const state = await stateStore.load();
+    if (action !== 'restart' && state.configured && state.data) {
+      await stateStore.save(state.data, action === 'start');
+    } else {
+      stackPoller.invalidate();
+    }
+  },
+  beginOperation: claimStack,
+  isNotFound: isContainerNotFound,
+}));
+
+const UPDATE_CHECK_CONFIG = parseUpdateCheckConfig();
+const updateChecker = createUpdateChecker({
+  getServices: async () => {
+    const state = await stateStore.load();
+    if (!state.configured) return [];
+    return (['translator', 'jdc'] as const).filter((service) => isServiceDeployed(service, state.mode));
+  },
+  getImage: getContainerImage,
+  getLatestDigest: getRegistryDigest,
+  onTransitions: (transitions) => {
+    for (const update of transitions.filter((transition) => transition.status === 'firing')) {
+      log.info(`Update: ${update.message}`);
+    }
+    if (!UPDATE_CHECK_CONFIG.notify) return;
+    void getStackSummary().then((summary) => publishAlerts(transitions, summary), (error) => {
+      log.error('Update notification failed:', error);
+    });
+  },
 });
-
-/**
- * POST /api/validate/bitcoin-rpc - Auto-discover Bitcoin Core RPC nodes
- */
-app.get('/api/validate/bitcoin-rpc', async (_req, res) => {
-  const results = await autoDiscoverBitcoinRpc();
-  return res.json(results);
+registerRuntimeGauge('tasks', 'update-check', updateChecker.inFlight);
+router.use(createUpdateRoutes({ updates: updateChecker, checkOnRequest: UPDATE_CHECK_CONFIG.intervalMs === 0 }));
+
+const PRICE_CONFIG = parsePriceConfig();
+const priceFeed = PRICE_CONFIG ? createPriceFeed(PRICE_CONFIG) : null;
+router.use(createPriceRoutes({ price: priceFeed }));
+
+const TELEMETRY_CONFIG = parseTelemetryConfig();
+const telemetry = TELEMETRY_CONFIG
+  ? createTelemetryReporter(TELEMETRY_CONFIG, {
+    collect: async () => {
+      const state = await stateStore.load();
+      if (!state.configured) return null;
+      const summary = await getStackSummary(state);
+      const images: Partial<Record<'translator' | 'jdc', string>> = {};
+      for (const service of (['translator', 'jdc'] as const).filter((name) => isServiceDeployed(name, state.mode))) {
+        const image = await getContainerImage(service).catch(() => null);
+        if (image) images[service] = image.image;
+      }
+      return {
+        mode: summary.mode,
+        miningMode: summary.miningMode,
+        network: summary.network,
+        hashrate: summary.hashrate,
+        workers: summary.workers,
+        images,
+      };
+    },
+  })
+  : null;
+
+const LATENCY_CONFIG = parseLatencyConfig();
+const latencyProber = LATENCY_CONFIG
+  ? createLatencyProber(LATENCY_CONFIG, {
+    getEndpoints: async () => {
+      const state = await stateStore.load();
+      return state.configured ? getLatencyEndpoints(state.data) : [];
+    },
+    history,
+  })
+  : null;
+if (latencyProber) registerRuntimeGauge('tasks', 'latency-probe', latencyProber.inFlight);
+router.use(createLatencyRoutes({ config: LATENCY_CONFIG, prober: latencyProber, history, retention: HISTORY_RETENTION }));
+
+const PLUGINS_DIR = resolvePluginsDir(CONFIG_DIR);
+const loadedPlugins = await loadPlugins(PLUGINS_DIR);
+for (const { file, error } of loadedPlugins.errors) log.warn(`Plugin ${file} not loaded: ${error}`);
+const pluginHost = createPluginHost(loadedPlugins.plugins, {
+  history,
+  onLog: (plugin, line) => log.info(`[plugin ${plugin.id}] ${line}`),
 });
+registerRuntimeGauge('tasks', 'plugins', pluginHost.inFlight);
+router.use(createPluginRoutes({ host: pluginHost, history, retention: HISTORY_RETENTION, loadErrors: loadedPlugins.errors }));
+router.use(createHistoryRoutes({ history, retention: HISTORY_RETENTION }));
+router.use(createMinerRoutes({ history, retention: HISTORY_RETENTION, labels: minerLabels }));
+router.use(createVardiffRoutes({
+  tracker: vardiff,
+  history,
+  retention: HISTORY_RETENTION,
+  getConfig: async () => {
+    const translator = (await stateStore.load()).data?.translator;
+    if (!translator) return { enabled: null, sharesPerMinute: null };
+    return {
+      enabled: translator.enable_vardiff !== false,
+      sharesPerMinute: translator.shares_per_minute > 0 ? translator.shares_per_minute : DEFAULT_SHARES_PER_MINUTE,
+    };
+  },
+}));
+
+router.use(createScheduleRoutes({
+  schedule,
+  getTitle: async () => getStackTitle(await stateStore.load()),
+}));
+router.use(createBitcoinRoutes({
+  probeSocket: (socketPath) => probeBitcoinSocketWithDocker(expandHomePath(socketPath)),
+  discoverRpc: autoDiscoverBitcoinRpc,
+}));
+router.use(createDockerEnvRoutes());
+router.use(createKeygenRoutes());
+router.use(createCoinbaseScriptRoutes({ getNetwork: async () => getNetwork(await stateStore.load()) }));
+router.use(createQrRoutes({
+  stratumHost: process.env.STRATUM_HOST || null,
+  getWorker: async () => (await stateStore.load()).data?.translator?.user_identity || null,
+}));
+router.use(createSv2ProbeRoutes({ getPool: async () => (await stateStore.load()).data?.pool ?? null }));
 
 async function getBitcoinSocketStartupError(data: SetupData): Promise<string | null> {
   if (data.mode !== 'jd' || !data.bitcoin) {
@@ -263,477 +892,633 @@ async function getBitcoinSocketStartupError(data: SetupData): Promise<string | n
   return result.valid ? null : result.error;
 }
 
+router.use(createSetupRoutes({
+  stateStore,
+  configDir: CONFIG_DIR,
+  startStack,
+  stopStack,
+  ensureDockerAvailable,
+  getBitcoinSocketError: getBitcoinSocketStartupError,
+  beginOperation: claimStack,
+  getBusyError: getStackBusyError,
+}));
+router.use(createDiagnosticRoutes({
+  loadState: stateStore.load,
+  read: readContainerLogs,
+  getStackData,
+}));
+router.use(createLogRoutes({
+  getMode: async () => (await stateStore.load()).mode,
+  follow: followContainerLogs,
+  read: readContainerLogs,
+  journalUnits: parseJournalUnits(),
+  holdStream,
+}));
+
+// One limiter for both proxies and their decoded reads, so the budget is per
+// client rather than per service.
+const RATE_LIMIT = parseRateLimitOptions();
+const proxyLimiter = RATE_LIMIT ? createTokenBucketLimiter(RATE_LIMIT) : null;
+const proxyRateLimit: express.RequestHandler = proxyLimiter
+  ? createRateLimitMiddleware(proxyLimiter)
+  : (_req, _res, next) => next();
+if (proxyLimiter) {
+  registerRuntimeGauge('caches', 'rate-limit-buckets', proxyLimiter.size);
+}
+
 /**
- * PUT /api/config - Update configuration and restart with new values
+ * Proxy requests to Translator monitoring API
+ * /translator-api/v1/global -> http://sv2-translator:9092/api/v1/global
  */
-app.put('/api/config', async (req, res) => {
-  if (!beginStackOperation('manual')) {
-    return res.status(409).json(stackBusyResponse());
-  }
-
-  try {
-    const state = await loadState();
-
-    if (!state.configured || !state.data) {
-      return res.status(400).json({ success: false, error: 'No configuration to update' });
-    }
+router.use('/translator-api', proxyRateLimit, requireOperatorForWrites, createAuditMiddleware(auditLog, 'translator'), createMonitoringProxy({
+  label: 'Translator',
+  containerName: 'sv2-translator',
+  port: TRANSLATOR_MONITORING_PORT,
+  getCached: (path) => {
+    const snapshot = stackPoller.latest();
+    return snapshot ? getCachedMonitoringResponse(snapshot.data, 'translator', path) : undefined;
+  },
+}));
 
-    const updates = req.body as Partial<SetupData>;
-    const currentData = state.data;
-    const newData: SetupData = normalizeSetupData({
-      ...currentData,
-      ...updates,
-      mode: updates.mode ?? currentData.mode,
-      miningMode: updates.miningMode ?? currentData.miningMode,
-      pool: updates.pool ?? currentData.pool,
-      bitcoin: updates.bitcoin ?? currentData.bitcoin,
-      jdc: updates.jdc ?? currentData.jdc,
-      translator: updates.translator ?? currentData.translator,
-    });
+/**
+ * Proxy requests to JDC monitoring API
+ * /jdc-api/v1/global -> http://sv2-jdc:9091/api/v1/global
+ */
+router.use('/jdc-api', proxyRateLimit, requireOperatorForWrites, createAuditMiddleware(auditLog, 'jdc'), createMonitoringProxy({
+  label: 'JDC',
+  containerName: 'sv2-jdc',
+  port: JDC_MONITORING_PORT,
+  getCached: (path) => {
+    const snapshot = stackPoller.latest();
+    return snapshot ? getCachedMonitoringResponse(snapshot.data, 'jdc', path) : undefined;
+  },
+}));
 
-    const requiresPool = !(newData.miningMode === 'solo' && newData.mode === 'jd');
+/**
+ * Proxy requests to a mempool instance, so browsers never call it directly
+ * /mempool-api/v1/fees/recommended -> http://umbrel.local:3006/api/v1/fees/recommended
+ */
+const MEMPOOL_URL = parseMempoolUrl();
+if (MEMPOOL_URL) {
+  router.use('/mempool-api', proxyRateLimit, createMempoolProxy({ baseUrl: MEMPOOL_URL }));
+}
 
-    if (!newData.mode || !newData.translator || (requiresPool && !newData.pool)) {
-      return res.status(400).json({ success: false, error: BITCOIN_ERROR_MESSAGES.missingConfig });
-    }
+// Fleet view over other sv2-ui instances; drill-down reads count against the proxy limit.
+const federationRegistry = createFederationRegistry(path.join(CONFIG_DIR, 'federation.json'));
+const federation = createFederation({ getSites: federationRegistry.get });
+registerRuntimeGauge('caches', 'federation-summaries', federation.size);
+router.use('/api/v1/federation/sites/:id', proxyRateLimit);
+router.use(createFederationRoutes({ federation, registry: federationRegistry }));
+
+router.use('/api/v1/monitoring', proxyRateLimit);
+router.use(createMonitoringRoutes({
+  getCached: (service, path) => {
+    const snapshot = stackPoller.latest();
+    return snapshot ? getCachedMonitoringResponse(snapshot.data, service, path) : undefined;
+  },
+  getLabels: minerLabels.list,
+}));
+
+router.use(createBadgeRoutes({ getStackSummary: () => getStackSummary() }));
+
+let monitoringSpec: Promise<OpenApiDocument | null> | null = null;
+router.use(createApiExplorerRoutes({
+  router,
+  // The monitoring API's document ships with the shared package.
+  loadMonitoringSpec: () => (monitoringSpec ??= readMonitoringSpec(getMonitoringSpecPath(__dirname)).catch((error) => {
+    log.error('Failed to load shared/openapi.json:', error);
+    return null;
+  })),
+  basePath: BASE_PATH,
+}));
+
+router.use(createUiRoutes({ uiDir: UI_DIR, basePath: BASE_PATH, headless: HEADLESS }));
+
+const app = createApp({
+  router,
+  basePath: BASE_PATH,
+  probes: createProbeRoutes({
+    getReadiness: async () => {
+      const [assetsLoaded, state] = await Promise.all([
+        HEADLESS ? null : fs.access(path.join(UI_DIR, 'index.html')).then(() => true, () => false),
+        stateStore.load(),
+      ]);
+      const data = stackPoller.latest()?.data;
+      return evaluateReadiness({
+        assetsLoaded,
+        stackConfigured: state.configured,
+        upstreamReachable: Boolean(data && (data.global || data.serverChannels || data.sv1Clients || data.sv2Clients)),
+        uptimeMs: process.uptime() * 1000,
+        graceMs: READY_GRACE_MS,
+      });
+    },
+  }),
+});
 
-    if (newData.mode === 'jd' && (!newData.jdc || !newData.bitcoin)) {
-      return res.status(400).json({ success: false, error: BITCOIN_ERROR_MESSAGES.jdConfig });
-    }
+const GRPC_ADDRESSES = parseGrpcListen();
+const grpc = GRPC_ADDRESSES
+  ? createGrpcService({
+    getSummary: () => getStackSummary(),
+    getHealth: getHealthReport,
+    watchSummary: (listener) => stackPoller.onSnapshot((snapshot) => {
+      stateStore.load()
+        .then((state) => listener(summarizeStackData(state, snapshot.data)))
+        .catch((error) => log.error('gRPC summary error:', error));
+    }),
+    ipFilter: parseIpFilter(),
+  })
+  : null;
+if (grpc) registerRuntimeGauge('caches', 'grpc-streams', grpc.streams);
 
-    const bitcoinCoreVersionError = getBitcoinCoreVersionError(newData);
-    if (bitcoinCoreVersionError) {
-      return res.status(400).json({ success: false, error: bitcoinCoreVersionError });
-    }
+/**
+ * Add stacks for newly discovered hosts to the registry (MDNS_DISCOVERY=register).
+ * Hosts whose id is already taken are left for the admin to sort out.
+ */
+async function registerDiscoveredStacks(services: DiscoveredService[]): Promise<void> {
+  const stacks = await stackRegistry.get();
+  const ids = new Set(stacks.map((stack) => stack.id));
+  const added = groupDiscoveredStacks(getUnconfiguredServices(services, stacks)).filter((stack) => !ids.has(stack.id));
+  if (added.length === 0) return;
+
+  const next = parseRemoteStacks({ stacks: [...stacks, ...added] });
+  await stackRegistry.save(next);
+  stackMonitors.sync(next);
+  log.info(`mDNS discovery: added ${added.map((stack) => stack.id).join(', ')} to the remote stacks`);
+}
 
-    await ensureDockerAvailable();
+async function reconcileShouldBeRunning(): Promise<void> {
+  if (!beginStackOperation('auto-start')) return;
 
-    const bitcoinSocketError = await getBitcoinSocketStartupError(newData);
-    if (bitcoinSocketError) {
-      return res.status(400).json({ success: false, error: bitcoinSocketError });
-    }
+  try {
+    const state = await stateStore.load();
+    if (!state.configured || !state.data || !state.shouldBeRunning) return;
 
-    await fs.mkdir(CONFIG_DIR, { recursive: true });
+    const containers = await getServiceStatus(state.mode);
+    if (isStackRunning(state.mode, containers)) return;
 
-    const translatorPath = path.join(CONFIG_DIR, 'translator.toml');
-    const jdcPath = path.join(CONFIG_DIR, 'jdc.toml');
+    log.info('Auto-start: shouldBeRunning=true and stack is stopped. Starting containers...');
 
-    try {
-      const translatorStat = await fs.stat(translatorPath);
-      if (translatorStat.isDirectory()) {
-        await fs.rm(translatorPath, { recursive: true });
-      }
-    } catch {
-      // translatorPath doesn't exist or isn't a directory, ignore
-    }
-
-    try {
-      const jdcStat = await fs.stat(jdcPath);
-      if (jdcStat.isDirectory()) {
-        await fs.rm(jdcPath, { recursive: true });
-      }
-    } catch {
-      // jdcPath doesn't exist or isn't a directory, ignore
+    const versionError = getBitcoinCoreVersionError(state.data);
+    if (versionError) {
+      log.error('Auto-start blocked:', versionError);
+      return;
     }
 
-    const translatorConfig = generateTranslatorConfig(newData);
-    await fs.writeFile(translatorPath, translatorConfig);
-    console.log('Updated translator.toml');
-
-    if (newData.mode === 'jd') {
-      const jdcConfig = generateJdcConfig(newData);
-      if (jdcConfig) {
-        await fs.writeFile(jdcPath, jdcConfig);
-        console.log('Updated jdc.toml');
+    if (state.data.mode === 'jd') {
+      const socketError = await getBitcoinSocketStartupError(state.data);
+      if (socketError) {
+        log.error('Auto-start blocked:', socketError);
+        return;
       }
     }
 
-    await saveState(newData);
-
-    await stopStack();
-
-    await startStack(newData, CONFIG_DIR);
-
-    const response: SetupResponse = { success: true };
-    res.json(response);
+    await startStack(state.data, CONFIG_DIR);
+    log.info('Auto-start: containers started successfully');
   } catch (error) {
-    console.error('Config update error:', error);
-    const response: SetupResponse = {
-      success: false,
-      error: error instanceof Error ? error.message : 'Failed to update config',
-    };
-    res.status(500).json(response);
+    log.error('Auto-start failed:', error);
   } finally {
-    finishStackOperation('manual');
+    finishStackOperation('auto-start');
   }
-});
+}
 
-/**
- * GET /api/logs/diagnostics - Get collated log diagnostics for the deployed stack
- */
-app.get('/api/logs/diagnostics', async (_req, res) => {
-  try {
-    const state = await loadState();
-    const response = await getLogDiagnostics(state.mode, state.configured);
-    res.json(response);
-  } catch (error) {
-    console.error('Log diagnostics error:', error);
-    res.status(500).json({ error: 'Failed to get log diagnostics' });
+let sv1ListenerStatus: Sv1ListenerStatus | null = null;
+
+async function refreshSv1ListenerStatus(): Promise<void> {
+  const previous = sv1ListenerStatus?.state;
+  sv1ListenerStatus = await checkSv1Listener({
+    host: getContainerHost(CONTAINER_NAMES.translator),
+    port: TRANSLATOR_PORT,
+    countConnections: async () =>
+      (await fetchMonitoringJson<{ total: number }>('translator', '/sv1/clients?offset=0&limit=1')).total,
+  });
+
+  if (previous && previous !== sv1ListenerStatus.state) {
+    log.info(`SV1 listener: ${previous} -> ${sv1ListenerStatus.state}`);
   }
-});
+}
+
+let sv1Probe: Sv1ProbeResult | null = null;
+let sv1ProbePending: Promise<Sv1ProbeResult> | null = null;
 
 /**
- * GET /api/logs/raw - Get raw collated log lines for the deployed stack
- * Query params:
- *   ?tail=N  max lines per container (default 200, capped at 500)
+ * The latest SV1 self-test, rerun once it's older than maxAgeMs. Health
+ * checks reuse it so monitors polling /api/v1/health don't each open a
+ * stratum session. Null when no Translator is configured.
  */
-app.get('/api/logs/raw', async (req, res) => {
-  try {
-    const state = await loadState();
-    const tailStr = req.query.tail as string;
-    let lines: Awaited<ReturnType<typeof readCollatedLogLines>>;
-
-    if (tailStr === 'all') {
-      // Pull full history since container start by ignoring the per-container
-      // tail cap applied inside readCollatedLogLines.
-      lines = await readCollatedLogLines(state.mode, (container) =>
-        readContainerLogs(container)
-      );
-    } else {
-      const tailParam = parseInt(tailStr, 10);
-      const tail = Number.isFinite(tailParam) ? Math.min(Math.max(tailParam, 1), 500) : 200;
-      lines = await readCollatedLogLines(state.mode, (container, opts) =>
-        readContainerLogs(container, { ...opts, tail })
-      );
-    }
-
-    res.json({
-      configured: state.configured,
-      mode: state.mode,
-      generatedAt: new Date().toISOString(),
-      streams: getLogStreams(state.mode),
-      lines,
+async function getSv1Probe(maxAgeMs = SV1_PROBE_MAX_AGE_MS): Promise<Sv1ProbeResult | null> {
+  const state = await stateStore.load();
+  if (!state.configured) return null;
+  if (sv1Probe && Date.now() - Date.parse(sv1Probe.checkedAt) < maxAgeMs) return sv1Probe;
+  sv1ProbePending ??= probeSv1Stratum(getContainerHost(CONTAINER_NAMES.translator), TRANSLATOR_PORT)
+    .finally(() => {
+      sv1ProbePending = null;
     });
-  } catch (error) {
-    console.error('Raw logs error:', error);
-    res.status(500).json({ error: 'Failed to get container logs' });
-  }
-});
+  sv1Probe = await sv1ProbePending;
+  return sv1Probe;
+}
+
+let chainStatsCache: { key: string; fetchedAt: number; stats: BitcoinChainStats } | null = null;
+registerRuntimeGauge('caches', 'chain-stats', () => (chainStatsCache ? 1 : 0));
 
 /**
- * POST /api/setup - Configure and start the stack
+ * Chain stats from the node configured for JD mode, or null without one.
  */
-app.post('/api/setup', async (req, res) => {
-  if (!beginStackOperation('manual')) {
-    return res.status(409).json(stackBusyResponse());
+async function getChainStats(state: SavedState, { refresh = false } = {}): Promise<BitcoinChainStats | null> {
+  const bitcoin = state.data?.bitcoin;
+  if (state.mode !== 'jd' || !bitcoin) return null;
+
+  const dataDir = expandHomePath(bitcoin.customDataDir.trim() || DEFAULT_BITCOIN_PATHS[bitcoin.os]);
+  const key = `${dataDir}:${bitcoin.network}`;
+  // Stats the background poll has kept up to date, allowing for its jitter.
+  const maxAgeMs = BITCOIN_POLL_INTERVAL_MS * (1 + POLL_JITTER);
+  if (!refresh && chainStatsCache?.key === key && Date.now() - chainStatsCache.fetchedAt < maxAgeMs) {
+    return chainStatsCache.stats;
   }
 
-  try {
-    const data = normalizeSetupData(req.body as SetupData);
-    const requiresPool = !(data.miningMode === 'solo' && data.mode === 'jd');
+  const stats = await queryBitcoinChainStats(dataDir, bitcoin.network);
+  chainStatsCache = { key, fetchedAt: Date.now(), stats };
+  return stats;
+}
 
-    // Validate required fields
-    if (!data.mode || !data.translator || (requiresPool && !data.pool)) {
-      return res.status(400).json({ success: false, error: BITCOIN_ERROR_MESSAGES.missingConfig });
-    }
+/**
+ * Read the chain stats every BITCOIN_POLL_INTERVAL_SECS, give or take
+ * POLL_JITTER_PERCENT, so /api/retarget rarely waits on bitcoind.
+ */
+function scheduleChainStatsPoll(): void {
+  setTimeout(() => {
+    void stateStore.load()
+      .then((state) => getChainStats(state, { refresh: true }))
+      .catch((error) => log.warn('Polling chain stats failed:', error))
+      .finally(scheduleChainStatsPoll);
+  }, getJitteredDelayMs(BITCOIN_POLL_INTERVAL_MS, POLL_JITTER)).unref();
+}
 
-    if (data.mode === 'jd' && (!data.jdc || !data.bitcoin)) {
-      return res.status(400).json({ success: false, error: BITCOIN_ERROR_MESSAGES.jdConfig });
-    }
+/**
+ * Sample right away and tell open dashboards to refetch.
+ */
+async function pollNow(backend: RefreshTarget): Promise<void> {
+  await stackPoller.poll(backend === 'all' ? undefined : [backend]);
+  await sampleStackEvents();
+  realtime.broadcast({ type: 'refresh', backend, at: new Date().toISOString() });
+}
 
-    const bitcoinCoreVersionError = getBitcoinCoreVersionError(data);
-    if (bitcoinCoreVersionError) {
-      return res.status(400).json({ success: false, error: bitcoinCoreVersionError });
+/**
+ * Log and push events; `stack` is the remote stack id, if any.
+ */
+function publishEvents(events: StackEvent[], summary: StackSummary, stack?: string): void {
+  for (const event of events) {
+    log.info(`Event${stack ? ` [${stack}]` : ''}: ${event.title}`);
+    realtime.broadcast({ type: 'event', event, ...(stack ? { stack } : {}) });
+    if (event.kind === 'block-found') {
+      // The local node may know which block it was; notify without it if not.
+      const identified = stack ? Promise.resolve(event) : identifyFoundBlock(event).catch((error) => {
+        log.error('Identifying the found block failed:', error);
+        return event;
+      });
+      void identified.then((found) => notifyBlockFound(found, summary));
     }
+  }
+}
 
-    await ensureDockerAvailable();
+/**
+ * The event with the found block's hash and height, recorded in the event
+ * log, when the JD-mode node has a recent block paying the JDC's reward address.
+ */
+async function identifyFoundBlock(event: StackEvent): Promise<StackEvent> {
+  const state = await stateStore.load();
+  const bitcoin = state.data?.bitcoin;
+  const rewardAddress = state.data?.jdc?.coinbase_reward_address;
+  if (state.mode !== 'jd' || !bitcoin || !rewardAddress) return event;
+
+  const payoutScript = getPayoutScript(rewardAddress, bitcoin.network);
+  if (!payoutScript) return event;
+  const dataDir = expandHomePath(bitcoin.customDataDir.trim() || DEFAULT_BITCOIN_PATHS[bitcoin.os]);
+  const block = findFoundBlock(await queryRecentBlocks(dataDir, bitcoin.network), payoutScript, event.at);
+  if (!block) return event;
+
+  const found = withFoundBlock(event, block);
+  log.info(`Found block ${block.height}: ${block.hash}`);
+  await eventLog.update(found);
+  return found;
+}
 
-    const bitcoinSocketError = await getBitcoinSocketStartupError(data);
-    if (bitcoinSocketError) {
-      return res.status(400).json({ success: false, error: bitcoinSocketError });
+function notifyBlockFound(event: StackEvent, summary: StackSummary): void {
+  telegram?.notifyEvent(event, summary).catch((error) => {
+    log.error('Telegram notification failed:', error);
+  });
+  email?.notifyEvent(event, summary).catch((error) => {
+    log.error('Email notification failed:', error);
+  });
+  push?.notifyEvent(event, summary).catch((error) => {
+    log.error('Push notification failed:', error);
+  });
+  discord?.notifyEvent(event, summary).catch((error) => {
+    log.error('Discord notification failed:', error);
+  });
+  void webhooks?.notifyEvent(event, summary).then((results) => {
+    for (const result of results.filter((delivery) => !delivery.ok)) {
+      log.error(`Event webhook to ${new URL(result.url).origin} failed after ${result.attempts} attempt(s): ${result.error ?? `HTTP ${result.status}`}`);
     }
+  });
+}
 
-    // Generate config files
-    await fs.mkdir(CONFIG_DIR, { recursive: true });
-
-    const translatorPath = path.join(CONFIG_DIR, 'translator.toml');
-    const jdcPath = path.join(CONFIG_DIR, 'jdc.toml');
-
-    // Remove if exists as directory (can happen from Docker volume mounts)
-    try {
-      const translatorStat = await fs.stat(translatorPath);
-      if (translatorStat.isDirectory()) {
-        await fs.rm(translatorPath, { recursive: true });
-      }
-    } catch {
-      // Doesn't exist, fine
-    }
-    try {
-      const jdcStat = await fs.stat(jdcPath);
-      if (jdcStat.isDirectory()) {
-        await fs.rm(jdcPath, { recursive: true });
+function publishAlerts(transitions: AlertTransition[], summary: StackSummary, stack?: string): void {
+  for (const alert of transitions) {
+    log.info(`Alert ${alert.status}${stack ? ` [${stack}]` : ''}: ${alert.message}`);
+    realtime.broadcast({ type: 'alert', alert, ...(stack ? { stack } : {}) });
+    telegram?.notifyAlert(alert, summary).catch((error) => {
+      log.error('Telegram notification failed:', error);
+    });
+    email?.notifyAlert(alert, summary).catch((error) => {
+      log.error('Email notification failed:', error);
+    });
+    push?.notifyAlert(alert, summary).catch((error) => {
+      log.error('Push notification failed:', error);
+    });
+    discord?.notifyAlert(alert, summary).catch((error) => {
+      log.error('Discord notification failed:', error);
+    });
+    void webhooks?.notify(alert, summary).then((results) => {
+      for (const result of results.filter((delivery) => !delivery.ok)) {
+        // Webhook URLs often embed a secret, so only log the origin.
+        log.error(`Alert webhook to ${new URL(result.url).origin} failed after ${result.attempts} attempt(s): ${result.error ?? `HTTP ${result.status}`}`);
       }
-    } catch {
-      // Doesn't exist, fine
-    }
-
-    const translatorConfig = generateTranslatorConfig(data);
-    await fs.writeFile(translatorPath, translatorConfig);
-    console.log('Generated translator.toml');
+    });
+  }
+}
 
-    if (data.mode === 'jd') {
-      const jdcConfig = generateJdcConfig(data);
-      if (jdcConfig) {
-        await fs.writeFile(jdcPath, jdcConfig);
-        console.log('Generated jdc.toml');
-      }
+/**
+ * Sample the stack and record notable events. Only runs while the stack is
+ * supposed to be up, so deliberate stops and restarts aren't reported as outages.
+ */
+async function sampleStackEvents(): Promise<void> {
+  try {
+    const state = await stateStore.load();
+    if (!state.configured || !state.shouldBeRunning || stackBusyReason) {
+      eventLog.resetBaseline();
+      alerts.reset();
+      return;
     }
 
-    // Save state
-    await saveState(data);
-
-    // Stop any running containers first (graceful shutdown order matters:
-    // JDC must be stopped before Translator to avoid crashing Bitcoin Core).
-    // This is critical when switching from JD mode to solo mining — without
-    // this, the old JDC container would be left running and crash when the
-    // Translator is replaced underneath it.
-    await stopStack();
-
-    // Start the stack
-    await startStack(data, CONFIG_DIR);
+    const data = await getStackData(state);
+    const summary = summarizeStackData(state, data);
+    publishEvents(await eventLog.record(summary), summary);
 
-    const response: SetupResponse = { success: true };
-    res.json(response);
+    // Warm restarts pause alerts through the event log.
+    if (eventLog.isPaused()) {
+      alerts.reset();
+      return;
+    }
+    publishAlerts([
+      ...alerts.evaluate(summary, await alertRules.get()),
+      ...alerts.evaluateAnomalies(data, summary.generatedAt),
+    ], summary);
   } catch (error) {
-    console.error('Setup error:', error);
-    const response: SetupResponse = {
-      success: false,
-      error: error instanceof Error ? error.message : 'Unknown error'
-    };
-    res.status(500).json(response);
-  } finally {
-    finishStackOperation('manual');
+    log.error('Event sampling failed:', error);
   }
-});
+}
 
 /**
- * POST /api/stop - Stop the stack
+ * Sample every remote stack into its own event log and alert engine.
  */
-app.post('/api/stop', async (_req, res) => {
-  if (!beginStackOperation('manual')) {
-    return res.status(409).json(stackBusyResponse());
-  }
-
+async function sampleRemoteStacks(): Promise<void> {
   try {
-    const state = await loadState();
-    if (state.configured && state.data) await saveState(state.data, false);
-
-    await stopStack();
-    res.json({ success: true });
+    const monitors = stackMonitors.sync(await stackRegistry.get());
+    if (monitors.length === 0) return;
+
+    const rules = await alertRules.get();
+    await Promise.all(monitors.map(async (monitor) => {
+      const { stack } = monitor;
+      try {
+        const data = await fetchStackData(stack.mode, getStackEndpoints(stack));
+        const summary = summarizeRemoteStack(stack, data);
+        monitor.latest = summary;
+        publishEvents(await monitor.eventLog.record(summary), summary, stack.id);
+        publishAlerts([
+          ...monitor.alerts.evaluate(summary, rules),
+          ...monitor.alerts.evaluateAnomalies(data, summary.generatedAt),
+        ], summary, stack.id);
+      } catch (error) {
+        log.error(`Sampling stack ${stack.id} failed:`, error);
+      }
+    }));
   } catch (error) {
-    console.error('Stop error:', error);
-    res.status(500).json({ success: false, error: 'Failed to stop stack' });
-  } finally {
-    finishStackOperation('manual');
+    log.error('Remote stack sampling failed:', error);
   }
-});
+}
 
 /**
- * POST /api/restart - Restart the stack
+ * Email the local stack's summary and last day of events, then schedule the
+ * next run.
  */
-app.post('/api/restart', async (_req, res) => {
-  if (!beginStackOperation('manual')) {
-    return res.status(409).json(stackBusyResponse());
-  }
-
-  try {
-    const state = await loadState();
-    if (!state.configured || !state.data) {
-      return res.status(400).json({ success: false, error: 'Not configured' });
+function scheduleDailySummaryEmail(dailyAt: number): void {
+  const delay = getNextDailyRun(dailyAt).getTime() - Date.now();
+  setTimeout(async () => {
+    try {
+      const state = await stateStore.load();
+      if (state.configured) {
+        await email?.sendDailySummary(await getStackSummary(state), await eventLog.list());
+      }
+    } catch (error) {
+      log.error('Daily summary email failed:', error);
     }
+    scheduleDailySummaryEmail(dailyAt);
+  }, delay);
+}
 
-    await saveState(state.data, true);
-
-    const bitcoinCoreVersionError = getBitcoinCoreVersionError(state.data);
-    if (bitcoinCoreVersionError) {
-      return res.status(400).json({ success: false, error: bitcoinCoreVersionError });
-    }
+function onListening() {
+  const dockerConnection = getDockerConnectionInfo();
+  const uiPort = LISTEN_ADDRESSES[0].port;
 
-    await ensureDockerAvailable();
+  log.info(`Config directory: ${CONFIG_DIR}`);
+  log.info(`Docker: ${dockerConnection.endpoint} (${dockerConnection.source})`);
 
-    const bitcoinSocketError = await getBitcoinSocketStartupError(state.data);
-    if (bitcoinSocketError) {
-      return res.status(400).json({ success: false, error: bitcoinSocketError });
-    }
+  if (process.env.NODE_ENV === 'production') {
+    log.info('');
+    log.info('┌─────────────────────────────────────────────────────┐');
+    log.info('│                                                     │');
+    log.info('│   ⛏️  SV2 UI is ready!                               │');
+    log.info('│                                                     │');
+    log.info(`│   Open in browser: http://localhost:${uiPort}             │`);
+    log.info('│                                                     │');
+    log.info('└─────────────────────────────────────────────────────┘');
+    log.info('');
+  }
 
-    await stopStack();
-    await startStack(state.data, CONFIG_DIR);
+  // Keep configured mining services running across app/system restarts.
+  void reconcileShouldBeRunning();
+  setInterval(() => {
+    void reconcileShouldBeRunning();
+  }, AUTO_START_RETRY_INTERVAL_MS);
+  registerRuntimeGauge('tasks', 'auto-start', () => 1);
 
-    res.json({ success: true });
-  } catch (error) {
-    console.error('Restart error:', error);
-    res.status(500).json({ success: false, error: 'Failed to restart stack' });
-  } finally {
-    finishStackOperation('manual');
+  stackPoller.start();
+  if (!SIMULATED_SETUP) {
+    scheduleChainStatsPoll();
+    registerRuntimeGauge('tasks', 'chain-stats-poll', () => 1);
   }
-});
 
-/**
- * POST /api/reset - Reset configuration (stop containers and delete config)
- */
-app.post('/api/reset', async (_req, res) => {
-  if (!beginStackOperation('manual')) {
-    return res.status(409).json(stackBusyResponse());
+  if (dockerAutoconfig) {
+    void dockerAutoconfig.start();
+    registerRuntimeGauge('tasks', 'docker-autoconfig', () => 1);
   }
 
-  try {
-    // Stop containers first
-    await stopStack();
+  if (kubernetesDiscovery) {
+    void kubernetesDiscovery.start();
+    registerRuntimeGauge('tasks', 'k8s-discovery', () => 1);
+  }
 
-    // Delete state file
-    try {
-      await fs.unlink(STATE_FILE);
-    } catch {
-      // File might not exist, that's fine
+  if (discovery) {
+    if (DISCOVERY_MODE === 'register') {
+      discovery.onDiscovered((services) => {
+        void registerDiscoveredStacks(services).catch((error) => log.error('mDNS registration failed:', error));
+      });
     }
+    void discovery.refresh();
+    setInterval(() => void discovery.refresh(), DISCOVERY_INTERVAL_MS);
+    registerRuntimeGauge('tasks', 'mdns-discovery', () => 1);
+  }
 
-    // Delete config files
-    try {
-      await fs.unlink(path.join(CONFIG_DIR, 'translator.toml'));
-      await fs.unlink(path.join(CONFIG_DIR, 'jdc.toml'));
-    } catch {
-      // Files might not exist
-    }
+  if (blockNotifier) {
+    blockNotifier.start();
+    log.info(`ZMQ: subscribing to new blocks at ${ZMQ_ENDPOINTS.map(({ host, port }) => `${host}:${port}`).join(', ')}`);
+  }
 
-    res.json({ success: true });
-  } catch (error) {
-    console.error('Reset error:', error);
-    res.status(500).json({ success: false, error: 'Failed to reset configuration' });
-  } finally {
-    finishStackOperation('manual');
+  if (ADVERTISE_CONFIG) {
+    advertiser = createMdnsAdvertiser(ADVERTISE_CONFIG, { port: uiPort, basePath: BASE_PATH });
+    advertiser.start().then(
+      () => log.info(`mDNS: advertising "${ADVERTISE_CONFIG.instance}" at http://${ADVERTISE_CONFIG.hostname}.local:${uiPort}${BASE_PATH}/`),
+      (error) => log.error('mDNS advertisement failed to start:', error),
+    );
+    registerRuntimeGauge('tasks', 'mdns-advertise', () => 1);
   }
-});
 
-/**
- * Get the URL for connecting to a container's API.
- * Uses container name on sv2-network (Docker) or localhost (development).
- */
-function getContainerUrl(containerName: string, port: number): string {
-  // In Docker, containers are on sv2-network and can be reached by name
-  // In development, containers expose ports on localhost
-  // Try container name first (works when sv2-ui is on sv2-network)
-  // The container name is the hostname on the Docker network
-  return process.env.NODE_ENV === 'production'
-    ? `http://${containerName}:${port}`
-    : `http://localhost:${port}`;
-}
+  setInterval(() => {
+    void sampleStackEvents();
+    void sampleRemoteStacks();
+  }, EVENT_SAMPLE_INTERVAL_MS);
+  registerRuntimeGauge('tasks', 'event-sampler', () => 1);
+
+  void federation.checkHealth();
+  setInterval(() => void federation.checkHealth(), FEDERATION_HEALTH_INTERVAL_MS);
+  registerRuntimeGauge('tasks', 'federation-health', () => 1);
+
+  if (UPDATE_CHECK_CONFIG.intervalMs > 0) {
+    const checkForUpdates = () => void updateChecker.check().catch((error) => log.error('Update check failed:', error));
+    checkForUpdates();
+    setInterval(checkForUpdates, UPDATE_CHECK_CONFIG.intervalMs);
+  }
 
-/**
- * Proxy requests to Translator monitoring API
- * This avoids CORS issues when the frontend is served from a different port
- * /translator-api/v1/global -> http://sv2-translator:9092/api/v1/global
- */
-app.use('/translator-api', async (req, res) => {
-  const targetUrl = `${getContainerUrl('sv2-translator', TRANSLATOR_MONITORING_PORT)}/api${req.url}`;
-  try {
-    const response = await fetch(targetUrl, {
-      method: req.method,
-      headers: { 'Content-Type': 'application/json' },
-      signal: AbortSignal.timeout(5000),
-    });
-    const data = await response.text();
-    res.status(response.status).set('Content-Type', response.headers.get('Content-Type') || 'application/json').send(data);
-  } catch {
-    res.status(502).json({ error: 'Cannot connect to Translator monitoring API' });
+  if (telemetry && TELEMETRY_CONFIG) {
+    const submitTelemetry = () => void telemetry.submit().catch((error) => log.error('Telemetry submission failed:', error));
+    // Start at a random point in the interval so reports don't line up with restarts.
+    setTimeout(() => {
+      submitTelemetry();
+      setInterval(submitTelemetry, TELEMETRY_CONFIG.intervalMs);
+    }, Math.random() * TELEMETRY_CONFIG.intervalMs);
+    registerRuntimeGauge('tasks', 'telemetry', () => 1);
+    log.info(`Telemetry: sending anonymous stats to ${new URL(TELEMETRY_CONFIG.url).origin}`);
   }
-});
 
-/**
- * Proxy requests to JDC monitoring API
- * /jdc-api/v1/global -> http://sv2-jdc:9091/api/v1/global
- */
-app.use('/jdc-api', async (req, res) => {
-  const targetUrl = `${getContainerUrl('sv2-jdc', JDC_MONITORING_PORT)}/api${req.url}`;
-  try {
-    const response = await fetch(targetUrl, {
-      method: req.method,
-      headers: { 'Content-Type': 'application/json' },
-      signal: AbortSignal.timeout(5000),
-    });
-    const data = await response.text();
-    res.status(response.status).set('Content-Type', response.headers.get('Content-Type') || 'application/json').send(data);
-  } catch {
-    res.status(502).json({ error: 'Cannot connect to JDC monitoring API' });
+  if (latencyProber && LATENCY_CONFIG) {
+    const probeLatency = () => void latencyProber.run().catch((error) => log.error('Latency probe failed:', error));
+    probeLatency();
+    setInterval(probeLatency, LATENCY_CONFIG.intervalMs);
   }
-});
 
-/**
- * SPA fallback - serve index.html for client-side routing
- */
-app.get('*', (_req, res) => {
-  res.sendFile(path.join(UI_DIR, 'index.html'));
-});
+  if (loadedPlugins.plugins.length > 0) {
+    pluginHost.start();
+    log.info(`Plugins: ${loadedPlugins.plugins.map(({ manifest }) => manifest.id).join(', ')} from ${PLUGINS_DIR}`);
+  }
 
-async function reconcileShouldBeRunning(): Promise<void> {
-  if (!beginStackOperation('auto-start')) return;
+  // Roll up and prune the history database (see HISTORY_RETENTION in history.ts).
+  const compactHistory = () => {
+    try {
+      history.compact(HISTORY_RETENTION);
+    } catch (error) {
+      log.error('History compaction failed:', error);
+    }
+  };
+  compactHistory();
+  setInterval(compactHistory, HISTORY_COMPACT_INTERVAL_MS);
+  registerRuntimeGauge('tasks', 'history-compaction', () => 1);
 
-  try {
-    const state = await loadState();
-    if (!state.configured || !state.data || !state.shouldBeRunning) return;
+  setInterval(() => {
+    void refreshSv1ListenerStatus().catch((error) => log.error('SV1 listener check failed:', error));
+  }, SV1_LISTENER_CHECK_INTERVAL_MS);
+  registerRuntimeGauge('tasks', 'sv1-listener', () => 1);
 
-    const containers = await getStackStatus(state.mode);
-    if (isStackRunning(state.mode, containers)) return;
+  if (EMAIL_CONFIG?.dailyAt != null) {
+    scheduleDailySummaryEmail(EMAIL_CONFIG.dailyAt);
+    registerRuntimeGauge('tasks', 'daily-summary-email', () => 1);
+  }
+}
 
-    console.log('Auto-start: shouldBeRunning=true and stack is stopped. Starting containers...');
+try {
+  await ensureWritableDir(CONFIG_DIR);
+} catch (error) {
+  console.error(error instanceof Error ? error.message : error);
+  process.exit(1);
+}
 
-    const versionError = getBitcoinCoreVersionError(state.data);
-    if (versionError) {
-      console.error('Auto-start blocked:', versionError);
-      return;
-    }
+if (DEMO_MODE) {
+  const demo = await startDemoServers(createDemoSimulation());
+  setContainerOrigins(demo.origins);
+  log.info('Demo mode: serving a simulated mining stack; Docker is not used');
+}
 
-    if (state.data.mode === 'jd') {
-      const socketError = await getBitcoinSocketStartupError(state.data);
-      if (socketError) {
-        console.error('Auto-start blocked:', socketError);
-        return;
-      }
-    }
+if (replay) {
+  const servers = await startReplay(replay);
+  setContainerOrigins(servers.origins);
+  log.info(`Replay: serving the session recorded at ${replay.session.recordedAt} from ${TRAFFIC!.dir}`);
+}
 
-    await startStack(state.data, CONFIG_DIR);
-    console.log('Auto-start: containers started successfully');
-  } catch (error) {
-    console.error('Auto-start failed:', error);
-  } finally {
-    finishStackOperation('auto-start');
-  }
+if (TRAFFIC?.mode === 'record') {
+  const state = await stateStore.load();
+  const recorders = await startRecording(TRAFFIC.dir, {
+    miningMode: state.miningMode,
+    mode: state.mode,
+    poolName: getPoolName(state),
+    network: getNetwork(state),
+  });
+  setContainerOrigins(recorders.origins);
+  log.info(`Recording: monitoring API traffic goes to ${TRAFFIC.dir}`);
 }
 
-app.listen(PORT, () => {
-  const dockerConnection = getDockerConnectionInfo();
+// One app is served across every configured listener; startup work runs once
+// all of them are bound.
+let pendingListeners = LISTEN_ADDRESSES.length;
 
-  console.log(`sv2-ui server running on http://localhost:${PORT}`);
-  console.log(`Config directory: ${CONFIG_DIR}`);
-  console.log(`Docker: ${dockerConnection.endpoint} (${dockerConnection.source})`);
+for (const address of LISTEN_ADDRESSES) {
+  const server = http.createServer(app);
 
-  if (process.env.NODE_ENV === 'production') {
-    console.log('');
-    console.log('┌─────────────────────────────────────────────────────┐');
-    console.log('│                                                     │');
-    console.log('│   ⛏️  SV2 UI is ready!                               │');
-    console.log('│                                                     │');
-    console.log(`│   Open in browser: http://localhost:${PORT}             │`);
-    console.log('│                                                     │');
-    console.log('└─────────────────────────────────────────────────────┘');
-    console.log('');
-  }
+  server.on('error', (error) => {
+    log.error(`Failed to listen on ${formatListenUrl(address)}:`, error);
+    process.exit(1);
+  });
 
-  // Keep configured mining services running across app/system restarts.
-  void reconcileShouldBeRunning();
-  setInterval(() => {
-    void reconcileShouldBeRunning();
-  }, AUTO_START_RETRY_INTERVAL_MS);
-});
+  server.listen({ port: address.port, host: address.host }, () => {
+    log.info(`sv2-ui ${HEADLESS ? 'headless API' : 'server'} running on ${formatListenUrl(address)}`);
+    pendingListeners -= 1;
+    if (pendingListeners === 0) {
+      onListening();
+      notifyDaemonParent();
+    }
+  });
+}
+
+for (const address of GRPC_ADDRESSES ?? []) {
+  const server = http2.createServer().on('stream', grpc!.handleStream);
+
+  server.on('error', (error) => {
+    log.error(`Failed to serve gRPC on ${formatListenUrl(address)}:`, error);
+    process.exit(1);
+  });
+
+  server.listen({ port: address.port, host: address.host }, () => {
+    log.info(`gRPC API running on ${formatListenUrl(address)}`);
+  });
+}
 
 // Graceful shutdown: stop mining containers when sv2-ui exits
 let isShuttingDown = false;
@@ -742,10 +1527,16 @@ async function shutdown(signal: string) {
   if (isShuttingDown) return;
   isShuttingDown = true;
 
-  console.log(`\n${signal} received. Stopping mining containers...`);
+  // Tell LAN caches the dashboard is going away before anything else.
+  await advertiser?.stop().catch(() => {});
+  await lifetimeCounters.flush().catch((error) => log.error('Saving lifetime counters failed:', error));
+
+  if (SIMULATED_SETUP) process.exit(0);
+
+  log.info(`\n${signal} received. Stopping mining containers...`);
   try {
     await stopStack();
-    console.log('Mining containers stopped.');
+    log.info('Mining containers stopped.');
   } catch {
     // Docker may not be available, that's fine
   }
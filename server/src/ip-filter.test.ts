@@ -0,0 +1,33 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { isIpAllowed, parseIpFilter } from './ip-filter.js';
+
+test('an empty filter allows everyone', () => {
+  const filter = parseIpFilter({});
+  assert.equal(isIpAllowed(filter, '203.0.113.5'), true);
+  assert.equal(isIpAllowed(filter, undefined), true);
+});
+
+test('allowlists admit only matching subnets, including IPv4-mapped addresses', () => {
+  const filter = parseIpFilter({ ALLOW_CIDR: '192.168.10.0/24, 10.20.0.0/16, fd00::/8' });
+
+  assert.equal(isIpAllowed(filter, '192.168.10.42'), true);
+  assert.equal(isIpAllowed(filter, '::ffff:10.20.3.4'), true);
+  assert.equal(isIpAllowed(filter, 'fd12::1'), true);
+  assert.equal(isIpAllowed(filter, '192.168.11.1'), false);
+  assert.equal(isIpAllowed(filter, undefined), false);
+});
+
+test('deny entries win over allow entries', () => {
+  const filter = parseIpFilter({ ALLOW_CIDR: '192.168.10.0/24', DENY_CIDR: '192.168.10.13' });
+
+  assert.equal(isIpAllowed(filter, '192.168.10.12'), true);
+  assert.equal(isIpAllowed(filter, '192.168.10.13'), false);
+  assert.equal(isIpAllowed(parseIpFilter({ DENY_CIDR: '0.0.0.0/0' }), '198.51.100.1'), false);
+});
+
+test('rejects malformed CIDRs', () => {
+  assert.throws(() => parseIpFilter({ ALLOW_CIDR: '192.168.10.0/33' }), /Invalid ALLOW_CIDR entry/);
+  assert.throws(() => parseIpFilter({ DENY_CIDR: 'lan' }), /Invalid DENY_CIDR entry/);
+  assert.throws(() => parseIpFilter({ ALLOW_CIDR: '10.0.0.0/8/1' }), /Invalid ALLOW_CIDR entry/);
+});
@@ -0,0 +1,74 @@
+/**
+ * Client IP allowlist/denylist, enforced before auth and proxying.
+ *
+ * The client IP is req.ip, which is the socket address unless TRUST_PROXY
+ * tells Express to take it from X-Forwarded-For.
+ */
+
+import net from 'net';
+import type { RequestHandler } from 'express';
+
+export interface IpFilter {
+  allow: net.BlockList | null;
+  deny: net.BlockList | null;
+}
+
+function addCidr(list: net.BlockList, cidr: string, variable: string): void {
+  const [address, prefix, ...rest] = cidr.split('/');
+  const family = net.isIP(address);
+  const maxPrefix = family === 4 ? 32 : 128;
+  const prefixLength = prefix === undefined ? maxPrefix : Number(prefix);
+
+  if (!family || rest.length > 0 || !Number.isInteger(prefixLength) || prefixLength < 0 || prefixLength > maxPrefix) {
+    throw new Error(`Invalid ${variable} entry "${cidr}"`);
+  }
+
+  list.addSubnet(address, prefixLength, family === 4 ? 'ipv4' : 'ipv6');
+}
+
+/**
+ * Parse a comma-separated list of CIDRs (bare addresses mean a single host).
+ */
+export function parseCidrList(value: string | undefined, variable: string): net.BlockList | null {
+  const entries = (value ?? '').split(',').map((entry) => entry.trim()).filter(Boolean);
+  if (entries.length === 0) return null;
+
+  const list = new net.BlockList();
+  for (const entry of entries) {
+    addCidr(list, entry, variable);
+  }
+  return list;
+}
+
+export function parseIpFilter(env: NodeJS.ProcessEnv = process.env): IpFilter {
+  return {
+    allow: parseCidrList(env.ALLOW_CIDR, 'ALLOW_CIDR'),
+    deny: parseCidrList(env.DENY_CIDR, 'DENY_CIDR'),
+  };
+}
+
+function matches(list: net.BlockList, ip: string): boolean {
+  // Dual-stack sockets report IPv4 clients as ::ffff:a.b.c.d.
+  const mapped = ip.match(/^::ffff:(\d+\.\d+\.\d+\.\d+)$/i);
+  const address = mapped ? mapped[1] : ip;
+  const family = net.isIP(address);
+  if (!family) return false;
+  return list.check(address, family === 4 ? 'ipv4' : 'ipv6');
+}
+
+/**
+ * Deny entries win over allow entries. With an allowlist configured, clients
+ * that don't match it (or whose IP is unknown) are rejected.
+ */
+export function isIpAllowed(filter: IpFilter, ip: string | undefined): boolean {
+  if (ip && filter.deny && matches(filter.deny, ip)) return false;
+  if (filter.allow) return ip !== undefined && matches(filter.allow, ip);
+  return true;
+}
+
+export function createIpFilterMiddleware(filter: IpFilter): RequestHandler {
+  return (req, res, next) => {
+    if (isIpAllowed(filter, req.ip)) return next();
+    res.status(403).json({ error: 'Access from this address is not allowed' });
+  };
+}
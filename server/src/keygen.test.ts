@@ -0,0 +1,28 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { JDC_AUTHORITY_PUBLIC_KEY } from '@sv2-ui/shared';
+
+import { decodeBase58Check, encodeBase58Check } from './base58.js';
+import { generateAuthorityKeypair, getAuthorityPublicKey } from './keygen.js';
+
+// The keypair config-generator.ts gives the JDC.
+const JDC_AUTHORITY_SECRET_KEY = 'mkDLTBBRxdBv998612qipDYoTK3YUrqLe8uWw7gu3iXbSrn2n';
+
+test('base58check round-trips payloads, leading zeros included', () => {
+  const payload = Buffer.from([0, 0, 1, 2, 3]);
+  assert.deepEqual(decodeBase58Check(encodeBase58Check(payload)), payload);
+  assert.equal(decodeBase58Check(`${JDC_AUTHORITY_PUBLIC_KEY.slice(0, -1)}1`), null);
+  assert.equal(decodeBase58Check('0OIl'), null);
+});
+
+test('getAuthorityPublicKey derives the SV2 public key of a secret key', () => {
+  assert.equal(getAuthorityPublicKey(JDC_AUTHORITY_SECRET_KEY), JDC_AUTHORITY_PUBLIC_KEY);
+  assert.throws(() => getAuthorityPublicKey(JDC_AUTHORITY_PUBLIC_KEY), /32 base58check-encoded bytes/);
+});
+
+test('generateAuthorityKeypair returns a matching versioned public key', () => {
+  const { publicKey, secretKey } = generateAuthorityKeypair();
+  assert.equal(getAuthorityPublicKey(secretKey), publicKey);
+  assert.deepEqual([...decodeBase58Check(publicKey)!.subarray(0, 2)], [0x01, 0x00]);
+  assert.notEqual(generateAuthorityKeypair().secretKey, secretKey);
+});
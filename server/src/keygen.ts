@@ -0,0 +1,51 @@
+/**
+ * SV2 authority keypairs, as the `authority_pubkey` / `authority_secret_key`
+ * settings of the Translator, JDC and pool expect them.
+ *
+ * The secret key is the 32-byte secp256k1 scalar and the public key its
+ * x-only point behind a 2-byte version prefix, both base58check-encoded.
+ * `keygen` on the command line and POST /api/v1/keygen both use this.
+ */
+
+import crypto from 'crypto';
+import { decodeBase58Check, encodeBase58Check } from './base58.js';
+
+const PUBLIC_KEY_VERSION = Buffer.from([0x01, 0x00]);
+
+export interface AuthorityKeypair {
+  publicKey: string;
+  secretKey: string;
+}
+
+function toPublicKey(ecdh: crypto.ECDH): string {
+  // The compressed point without its parity byte is the x-only key.
+  const xOnly = ecdh.getPublicKey(null, 'compressed').subarray(1);
+  return encodeBase58Check(Buffer.concat([PUBLIC_KEY_VERSION, xOnly]));
+}
+
+export function generateAuthorityKeypair(): AuthorityKeypair {
+  const ecdh = crypto.createECDH('secp256k1');
+  ecdh.generateKeys();
+  const secret = ecdh.getPrivateKey();
+  return {
+    publicKey: toPublicKey(ecdh),
+    secretKey: encodeBase58Check(Buffer.concat([Buffer.alloc(32 - secret.length), secret])),
+  };
+}
+
+/**
+ * The public key of a base58check authority secret key.
+ */
+export function getAuthorityPublicKey(secretKey: string): string {
+  const secret = decodeBase58Check(secretKey);
+  if (!secret || secret.length !== 32) {
+    throw new Error('Authority secret key must be 32 base58check-encoded bytes');
+  }
+  const ecdh = crypto.createECDH('secp256k1');
+  ecdh.setPrivateKey(secret);
+  return toPublicKey(ecdh);
+}
+
+export function formatKeypair({ publicKey, secretKey }: AuthorityKeypair): string {
+  return `Public key: ${publicKey}\nSecret key: ${secretKey}\n`;
+}
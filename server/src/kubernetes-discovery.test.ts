@@ -0,0 +1,84 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { MONITORING_PORT_LABEL } from './docker-autoconfig.js';
+import {
+  createKubernetesDiscovery,
+  getPodOrigin,
+  parseKubernetesDiscovery,
+  pickPod,
+  readPod,
+} from './kubernetes-discovery.js';
+import type { KubernetesPod } from './kubernetes-discovery.js';
+
+function pod(overrides: Partial<KubernetesPod>): KubernetesPod {
+  return { name: 'p', ip: '10.1.0.5', ready: true, terminating: false, labels: {}, ports: [], ...overrides };
+}
+
+test('parseKubernetesDiscovery is off unless set, and defaults to the role label', () => {
+  assert.equal(parseKubernetesDiscovery({}), null);
+  assert.deepEqual(parseKubernetesDiscovery({ K8S_DISCOVERY: 'true' }, () => 'mining'), {
+    namespace: 'mining',
+    selectors: { translator: 'org.stratumv2.role=translator', jdc: 'org.stratumv2.role=jdc' },
+    intervalMs: 15_000,
+  });
+  const config = parseKubernetesDiscovery({
+    K8S_DISCOVERY: 'on',
+    K8S_NAMESPACE: 'sv2',
+    K8S_TRANSLATOR_SELECTOR: 'app.kubernetes.io/name=translator',
+    K8S_DISCOVERY_INTERVAL_SECS: '60',
+  }, () => null);
+  assert.equal(config?.namespace, 'sv2');
+  assert.equal(config?.selectors.translator, 'app.kubernetes.io/name=translator');
+  assert.equal(config?.intervalMs, 60_000);
+
+  assert.throws(() => parseKubernetesDiscovery({ K8S_DISCOVERY: 'maybe' }), /K8S_DISCOVERY must be/);
+  assert.throws(() => parseKubernetesDiscovery({ K8S_DISCOVERY: 'true', K8S_NAMESPACE: 'Mining' }), /K8S_NAMESPACE must be/);
+  assert.throws(() => parseKubernetesDiscovery({ K8S_DISCOVERY: 'true', K8S_DISCOVERY_INTERVAL_SECS: '1' }, () => null), /K8S_DISCOVERY_INTERVAL_SECS must be between/);
+});
+
+test('pods are read from the API and reached on their monitoring port', () => {
+  const read = readPod({
+    metadata: { name: 'translator-7d9f', labels: { app: 'translator' } },
+    spec: { containers: [{ ports: [{ name: 'sv1', containerPort: 34255 }, { name: 'monitoring', containerPort: 9000 }] }] },
+    status: { phase: 'Running', podIP: '10.1.0.7', conditions: [{ type: 'Ready', status: 'True' }] },
+  });
+  assert.equal(read.ready, true);
+  assert.equal(read.terminating, false);
+  assert.equal(getPodOrigin(read, 'translator'), 'http://10.1.0.7:9000');
+
+  assert.equal(getPodOrigin(pod({}), 'jdc'), 'http://10.1.0.5:9091');
+  assert.equal(getPodOrigin(pod({ labels: { [MONITORING_PORT_LABEL]: '9999' } }), 'translator'), 'http://10.1.0.5:9999');
+  assert.equal(getPodOrigin(pod({ ip: 'fd00::7' }), 'translator'), 'http://[fd00::7]:9092');
+  assert.equal(getPodOrigin(pod({ ready: false }), 'translator'), null);
+  assert.equal(getPodOrigin(pod({ terminating: true }), 'translator'), null);
+  assert.equal(readPod({ status: { phase: 'Pending' } }).ready, false);
+});
+
+test('the pod in use is kept until it goes away', async () => {
+  const pods: Record<string, KubernetesPod[]> = {
+    'org.stratumv2.role=translator': [pod({ name: 'translator-b', ip: '10.1.0.2' })],
+    'org.stratumv2.role=jdc': [],
+  };
+  const applied: Array<Record<string, string>> = [];
+  const discovery = createKubernetesDiscovery({
+    config: parseKubernetesDiscovery({ K8S_DISCOVERY: 'true' }, () => 'sv2')!,
+    listPods: async (namespace, selector) => {
+      assert.equal(namespace, 'sv2');
+      return pods[selector];
+    },
+    apply: (origins) => applied.push(origins),
+  });
+
+  await discovery.refresh();
+  assert.deepEqual(applied.at(-1), { 'sv2-translator': 'http://10.1.0.2:9092' });
+
+  // A rollout adds a pod that sorts first; the ready one in use stays.
+  pods['org.stratumv2.role=translator'] = [pod({ name: 'translator-a', ip: '10.1.0.3' }), pod({ name: 'translator-b', ip: '10.1.0.2' })];
+  await discovery.refresh();
+  assert.deepEqual(applied.at(-1), { 'sv2-translator': 'http://10.1.0.2:9092' });
+
+  pods['org.stratumv2.role=translator'] = [pod({ name: 'translator-a', ip: '10.1.0.3' }), pod({ name: 'translator-b', ip: '10.1.0.2', terminating: true })];
+  await discovery.refresh();
+  assert.deepEqual(applied.at(-1), { 'sv2-translator': 'http://10.1.0.3:9092' });
+  assert.equal(pickPod([], 'jdc'), null);
+});
@@ -0,0 +1,234 @@
+/**
+ * Kubernetes-based discovery of the local monitoring API URLs.
+ *
+ * With K8S_DISCOVERY set, the server lists pods through the Kubernetes API
+ * using its service account, on startup and every K8S_DISCOVERY_INTERVAL_SECS.
+ * The Translator and JDC are the pods matching K8S_TRANSLATOR_SELECTOR and
+ * K8S_JDC_SELECTOR, by default the same `org.stratumv2.role` label Docker
+ * auto-configuration reads. Of the pods that are ready, the one already in
+ * use is kept, so a rollout only moves the proxies once that pod goes away.
+ *
+ * The URL is the pod IP and its monitoring port: the
+ * `org.stratumv2.monitoring-port` label, else a container port named
+ * `monitoring`, else the well-known port. The proxies, poller and health
+ * checks then follow these URLs instead of static ones.
+ */
+
+import fs from 'fs';
+import https from 'https';
+import { MONITORING_PORT_LABEL, ROLE_LABEL } from './docker-autoconfig.js';
+import { createLogger } from './logger.js';
+import { MONITORING_SERVICES } from './monitoring.js';
+import type { MonitoringService } from './monitoring.js';
+
+const log = createLogger('kubernetes-discovery');
+
+export interface KubernetesDiscoveryConfig {
+  namespace: string;
+  selectors: Record<MonitoringService, string>;
+  intervalMs: number;
+}
+
+export interface KubernetesPod {
+  name: string;
+  ip: string | null;
+  ready: boolean;
+  // Set once the pod is being deleted, while it may still report ready.
+  terminating: boolean;
+  labels: Record<string, string>;
+  ports: Array<{ name: string | null; containerPort: number }>;
+}
+
+export type KubernetesOrigins = Partial<Record<MonitoringService, { pod: string; origin: string }>>;
+
+export const SERVICE_ACCOUNT_DIR = '/var/run/secrets/kubernetes.io/serviceaccount';
+const DEFAULT_INTERVAL_SECS = 15;
+const MIN_INTERVAL_SECS = 5;
+const MAX_INTERVAL_SECS = 3600;
+const MONITORING_PORT_NAME = 'monitoring';
+const REQUEST_TIMEOUT_MS = 10_000;
+
+function readServiceAccountNamespace(): string | null {
+  try {
+    return fs.readFileSync(`${SERVICE_ACCOUNT_DIR}/namespace`, 'utf-8').trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+export function parseKubernetesDiscovery(
+  env: NodeJS.ProcessEnv = process.env,
+  readNamespace: () => string | null = readServiceAccountNamespace,
+): KubernetesDiscoveryConfig | null {
+  const enabled = env.K8S_DISCOVERY?.trim().toLowerCase();
+  if (!enabled || enabled === 'false' || enabled === 'off') return null;
+  if (enabled !== 'true' && enabled !== 'on') {
+    throw new Error(`K8S_DISCOVERY must be true or false, got "${env.K8S_DISCOVERY}"`);
+  }
+
+  const namespace = env.K8S_NAMESPACE?.trim() || readNamespace() || 'default';
+  if (!/^[a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?$/.test(namespace)) {
+    throw new Error(`K8S_NAMESPACE must be a Kubernetes namespace name, got "${namespace}"`);
+  }
+
+  const selectors = {
+    translator: env.K8S_TRANSLATOR_SELECTOR?.trim() || `${ROLE_LABEL}=translator`,
+    jdc: env.K8S_JDC_SELECTOR?.trim() || `${ROLE_LABEL}=jdc`,
+  };
+
+  const rawSecs = env.K8S_DISCOVERY_INTERVAL_SECS?.trim();
+  const secs = rawSecs ? Number(rawSecs) : DEFAULT_INTERVAL_SECS;
+  if (!Number.isFinite(secs) || secs < MIN_INTERVAL_SECS || secs > MAX_INTERVAL_SECS) {
+    throw new Error(`K8S_DISCOVERY_INTERVAL_SECS must be between ${MIN_INTERVAL_SECS} and ${MAX_INTERVAL_SECS}, got "${rawSecs}"`);
+  }
+
+  return { namespace, selectors, intervalMs: secs * 1000 };
+}
+
+/**
+ * The fields discovery needs from a Pod object of the Kubernetes API.
+ */
+export function readPod(item: unknown): KubernetesPod {
+  const pod = (item ?? {}) as {
+    metadata?: { name?: string; labels?: Record<string, string>; deletionTimestamp?: string };
+    spec?: { containers?: Array<{ ports?: Array<{ name?: string; containerPort?: number }> }> };
+    status?: { phase?: string; podIP?: string; conditions?: Array<{ type?: string; status?: string }> };
+  };
+  return {
+    name: pod.metadata?.name ?? '',
+    ip: pod.status?.podIP || null,
+    ready: pod.status?.phase === 'Running'
+      && (pod.status.conditions ?? []).some((condition) => condition.type === 'Ready' && condition.status === 'True'),
+    terminating: Boolean(pod.metadata?.deletionTimestamp),
+    labels: pod.metadata?.labels ?? {},
+    ports: (pod.spec?.containers ?? []).flatMap((container) => (container.ports ?? [])
+      .filter((port) => typeof port.containerPort === 'number')
+      .map((port) => ({ name: port.name ?? null, containerPort: port.containerPort as number }))),
+  };
+}
+
+/**
+ * Where sv2-ui can reach the pod's monitoring API, or null if it can't.
+ */
+export function getPodOrigin(pod: KubernetesPod, role: MonitoringService): string | null {
+  if (!pod.ready || pod.terminating || !pod.ip) return null;
+  const labelled = Number(pod.labels[MONITORING_PORT_LABEL]);
+  const named = pod.ports.find((port) => port.name === MONITORING_PORT_NAME)?.containerPort;
+  const port = Number.isInteger(labelled) && labelled > 0 && labelled < 65536
+    ? labelled
+    : named ?? MONITORING_SERVICES[role].port;
+  return `http://${pod.ip.includes(':') ? `[${pod.ip}]` : pod.ip}:${port}`;
+}
+
+/**
+ * The pod to use for `role`: the one in use while it stays reachable, else
+ * the first reachable one by name.
+ */
+export function pickPod(
+  pods: KubernetesPod[],
+  role: MonitoringService,
+  current: string | null = null,
+): { pod: string; origin: string } | null {
+  const reachable = pods
+    .map((pod) => ({ pod: pod.name, origin: getPodOrigin(pod, role) }))
+    .filter((entry): entry is { pod: string; origin: string } => entry.origin !== null)
+    .sort((a, b) => Number(b.pod === current) - Number(a.pod === current) || a.pod.localeCompare(b.pod));
+  return reachable[0] ?? null;
+}
+
+/**
+ * List the pods matching `selector` with the in-cluster service account.
+ * The token is read on every request, since the kubelet rotates it.
+ */
+export function listClusterPods(namespace: string, selector: string, env: NodeJS.ProcessEnv = process.env): Promise<KubernetesPod[]> {
+  const host = env.KUBERNETES_SERVICE_HOST;
+  if (!host) return Promise.reject(new Error('Not running in a Kubernetes pod (KUBERNETES_SERVICE_HOST is unset)'));
+
+  return new Promise((resolve, reject) => {
+    const request = https.request({
+      host,
+      port: Number(env.KUBERNETES_SERVICE_PORT) || 443,
+      path: `/api/v1/namespaces/${encodeURIComponent(namespace)}/pods?labelSelector=${encodeURIComponent(selector)}`,
+      headers: {
+        Accept: 'application/json',
+        Authorization: `Bearer ${fs.readFileSync(`${SERVICE_ACCOUNT_DIR}/token`, 'utf-8').trim()}`,
+      },
+      ca: fs.readFileSync(`${SERVICE_ACCOUNT_DIR}/ca.crt`),
+      timeout: REQUEST_TIMEOUT_MS,
+    }, (response) => {
+      const chunks: Buffer[] = [];
+      response.on('data', (chunk: Buffer) => chunks.push(chunk));
+      response.on('end', () => {
+        const body = Buffer.concat(chunks).toString('utf-8');
+        if (response.statusCode !== 200) {
+          let message: string | undefined;
+          try {
+            message = (JSON.parse(body) as { message?: string }).message;
+          } catch {
+            // Not a Status object.
+          }
+          reject(new Error(`Kubernetes API answered ${response.statusCode}${message ? `: ${message}` : ''}`));
+          return;
+        }
+        try {
+          resolve(((JSON.parse(body) as { items?: unknown[] }).items ?? []).map(readPod));
+        } catch (error) {
+          reject(error);
+        }
+      });
+    });
+    request.on('timeout', () => request.destroy(new Error(`No answer from the Kubernetes API within ${REQUEST_TIMEOUT_MS}ms`)));
+    request.on('error', reject);
+    request.end();
+  });
+}
+
+export interface KubernetesDiscoveryDeps {
+  config: KubernetesDiscoveryConfig;
+  listPods?: (namespace: string, selector: string) => Promise<KubernetesPod[]>;
+  // Receives the origins keyed by the container name they stand in for.
+  apply: (origins: Record<string, string>) => void;
+}
+
+export function createKubernetesDiscovery({ config, listPods = listClusterPods, apply }: KubernetesDiscoveryDeps) {
+  let origins: KubernetesOrigins = {};
+  let timer: NodeJS.Timeout | null = null;
+
+  async function refresh(): Promise<KubernetesOrigins> {
+    try {
+      const services = Object.keys(MONITORING_SERVICES) as MonitoringService[];
+      const pods = await Promise.all(services.map((service) => listPods(config.namespace, config.selectors[service])));
+      const next: KubernetesOrigins = {};
+      services.forEach((service, index) => {
+        const picked = pickPod(pods[index], service, origins[service]?.pod ?? null);
+        if (picked) next[service] = picked;
+        if (picked?.origin !== origins[service]?.origin) {
+          log.info(`Kubernetes discovery: ${MONITORING_SERVICES[service].label} ${picked ? `at ${picked.origin} (pod ${picked.pod})` : 'not found'}`);
+        }
+      });
+      origins = next;
+      apply(Object.fromEntries(Object.entries(origins).map(([service, entry]) => [
+        MONITORING_SERVICES[service as MonitoringService].containerName,
+        entry.origin,
+      ])));
+    } catch (error) {
+      // Keep the last known origins; the API server may be briefly unavailable.
+      log.error('Kubernetes discovery failed:', error);
+    }
+    return origins;
+  }
+
+  async function start(): Promise<void> {
+    await refresh();
+    timer = setInterval(() => void refresh(), config.intervalMs);
+  }
+
+  function stop(): void {
+    if (timer) clearInterval(timer);
+    timer = null;
+  }
+
+  return { start, stop, refresh, get: () => origins };
+}
+
+export type KubernetesDiscovery = ReturnType<typeof createKubernetesDiscovery>;
@@ -0,0 +1,60 @@
+import assert from 'node:assert/strict';
+import net from 'node:net';
+import { test } from 'node:test';
+
+import type { HistorySample } from './history.js';
+import { createLatencyProber, getLatencyEndpoints, measureLatency, parseLatencyConfig } from './latency.js';
+import type { SetupData } from './types.js';
+
+const pool = { name: 'Pool', address: 'pool.example.com', port: 3333, authority_public_key: '9auqWEzQDVyd2oe1JVGFLMLHZtCo2FFqZwtKA5gd9xbuEu7PH72' };
+
+test('parseLatencyConfig defaults to a minute without handshakes and turns off at 0', () => {
+  assert.deepEqual(parseLatencyConfig({}), { intervalMs: 60_000, handshake: false });
+  assert.deepEqual(parseLatencyConfig({ LATENCY_PROBE_INTERVAL_SECS: '15', LATENCY_PROBE_HANDSHAKE: 'true' }), { intervalMs: 15_000, handshake: true });
+  assert.equal(parseLatencyConfig({ LATENCY_PROBE_INTERVAL_SECS: '0' }), null);
+  assert.throws(() => parseLatencyConfig({ LATENCY_PROBE_INTERVAL_SECS: '1.5' }), /LATENCY_PROBE_INTERVAL_SECS must be an integer/);
+  assert.throws(() => parseLatencyConfig({ LATENCY_PROBE_HANDSHAKE: 'yes' }), /LATENCY_PROBE_HANDSHAKE must be true or false/);
+});
+
+test('getLatencyEndpoints probes the JDS only in JD mode, and nothing for Sovereign Solo', () => {
+  const data = { miningMode: 'pool', mode: 'no-jd', pool } as SetupData;
+  assert.deepEqual(getLatencyEndpoints(data).map(({ target, port }) => [target, port]), [['pool', 3333]]);
+  assert.deepEqual(getLatencyEndpoints({ ...data, mode: 'jd' }).map(({ target, port }) => [target, port]), [['pool', 3333], ['jds', 3334]]);
+  assert.deepEqual(getLatencyEndpoints({ ...data, mode: 'jd', miningMode: 'solo' }), []);
+  assert.deepEqual(getLatencyEndpoints(null), []);
+});
+
+test('the prober stores connect times, and handshake times when enabled', async () => {
+  const recorded: HistorySample[] = [];
+  const prober = createLatencyProber({ intervalMs: 60_000, handshake: true }, {
+    getEndpoints: async () => getLatencyEndpoints({ miningMode: 'pool', mode: 'jd', pool } as SetupData),
+    history: { record: (samples) => recorded.push(...samples) },
+    measure: async ({ target }) => (target === 'pool'
+      ? { connectMs: 12, handshakeMs: 30, error: null }
+      : { connectMs: null, handshakeMs: null, error: 'connect: ECONNREFUSED' }),
+    now: () => new Date('2026-01-01T00:00:00.000Z'),
+  });
+
+  await prober.run();
+  assert.deepEqual(recorded.map(({ metric, key, value }) => [metric, key, value]), [
+    ['latency.connect', 'pool', 12],
+    ['latency.handshake', 'pool', 30],
+    ['latency.connect', 'jds', null],
+    ['latency.handshake', 'jds', null],
+  ]);
+  assert.equal(prober.latest()[1].error, 'connect: ECONNREFUSED');
+});
+
+test('measureLatency times the TCP connect without a handshake', async () => {
+  const server = net.createServer((socket) => socket.destroy());
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  try {
+    const { port } = server.address() as net.AddressInfo;
+    const sample = await measureLatency({ target: 'pool', host: '127.0.0.1', port, authorityPubkey: pool.authority_public_key }, false);
+    assert.equal(sample.error, null);
+    assert.equal(typeof sample.connectMs, 'number');
+    assert.equal(sample.handshakeMs, null);
+  } finally {
+    server.close();
+  }
+});
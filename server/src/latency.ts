@@ -0,0 +1,128 @@
+/**
+ * Round-trip times to the upstream pool and JDS, sampled in the background.
+ *
+ * Every LATENCY_PROBE_INTERVAL_SECS (60 by default, 0 turns it off) the
+ * server opens a TCP connection to each configured endpoint and times it;
+ * with LATENCY_PROBE_HANDSHAKE=true it also times the Noise handshake. The
+ * samples go to the history database, so latency spikes can be lined up
+ * with rejected shares; a failed probe is stored as a gap.
+ */
+
+import { POOL_JDS_PORT } from '@sv2-ui/shared';
+import type { HistorySample, HistoryStore } from './history.js';
+import { probeSv2 } from './sv2-probe.js';
+import type { SetupData } from './types.js';
+
+export type LatencyTarget = 'pool' | 'jds';
+
+export interface LatencyConfig {
+  intervalMs: number;
+  handshake: boolean;
+}
+
+export interface LatencyEndpoint {
+  target: LatencyTarget;
+  host: string;
+  port: number;
+  authorityPubkey: string;
+}
+
+export interface LatencySample {
+  target: LatencyTarget;
+  at: string;
+  connectMs: number | null;
+  // Null unless handshake probes are on.
+  handshakeMs: number | null;
+  error: string | null;
+}
+
+export interface LatencyProberDeps {
+  getEndpoints: () => Promise<LatencyEndpoint[]>;
+  history: Pick<HistoryStore, 'record'>;
+  measure?: (endpoint: LatencyEndpoint, handshake: boolean) => Promise<Omit<LatencySample, 'target' | 'at'>>;
+  now?: () => Date;
+}
+
+export const LATENCY_TARGETS: LatencyTarget[] = ['pool', 'jds'];
+export const CONNECT_METRIC = 'latency.connect';
+export const HANDSHAKE_METRIC = 'latency.handshake';
+const DEFAULT_INTERVAL_SECS = 60;
+const MAX_INTERVAL_SECS = 3600;
+
+export function parseLatencyConfig(env: NodeJS.ProcessEnv = process.env): LatencyConfig | null {
+  const rawInterval = env.LATENCY_PROBE_INTERVAL_SECS?.trim();
+  const secs = rawInterval ? Number(rawInterval) : DEFAULT_INTERVAL_SECS;
+  if (!Number.isInteger(secs) || secs < 0 || secs > MAX_INTERVAL_SECS) {
+    throw new Error(`LATENCY_PROBE_INTERVAL_SECS must be an integer between 0 and ${MAX_INTERVAL_SECS}, got "${rawInterval}"`);
+  }
+
+  const handshake = env.LATENCY_PROBE_HANDSHAKE?.trim().toLowerCase();
+  if (handshake && handshake !== 'true' && handshake !== 'false') {
+    throw new Error(`LATENCY_PROBE_HANDSHAKE must be true or false, got "${env.LATENCY_PROBE_HANDSHAKE}"`);
+  }
+
+  return secs === 0 ? null : { intervalMs: secs * 1000, handshake: handshake === 'true' };
+}
+
+/**
+ * The pool, and in JD mode its JDS, the services connect to; none for Sovereign Solo.
+ */
+export function getLatencyEndpoints(data: SetupData | null): LatencyEndpoint[] {
+  const pool = data?.pool;
+  if (!pool || (data.mode === 'jd' && data.miningMode === 'solo')) return [];
+  const endpoint = { host: pool.address, authorityPubkey: pool.authority_public_key };
+  return data.mode === 'jd'
+    ? [{ target: 'pool', port: pool.port, ...endpoint }, { target: 'jds', port: POOL_JDS_PORT, ...endpoint }]
+    : [{ target: 'pool', port: pool.port, ...endpoint }];
+}
+
+export async function measureLatency(endpoint: LatencyEndpoint, handshake: boolean): Promise<Omit<LatencySample, 'target' | 'at'>> {
+  const result = await probeSv2(
+    { ...endpoint, protocol: endpoint.target === 'jds' ? 'job-declaration' : 'mining' },
+    { stopAfter: handshake ? 'handshake' : 'connect' },
+  );
+  const step = (name: 'connect' | 'handshake') => result.steps.find((candidate) => candidate.step === name);
+  const failed = result.steps.find((candidate) => !candidate.ok);
+  return {
+    connectMs: step('connect')?.ok ? step('connect')!.durationMs : null,
+    handshakeMs: step('handshake')?.ok ? step('handshake')!.durationMs : null,
+    error: failed ? `${failed.step}: ${failed.error}` : null,
+  };
+}
+
+export function createLatencyProber(config: LatencyConfig, deps: LatencyProberDeps) {
+  const measure = deps.measure ?? measureLatency;
+  const now = deps.now ?? (() => new Date());
+  let latest: LatencySample[] = [];
+  let inFlight = 0;
+
+  /**
+   * Probe every endpoint once and store the samples.
+   */
+  async function run(): Promise<LatencySample[]> {
+    inFlight += 1;
+    try {
+      const endpoints = await deps.getEndpoints();
+      const at = now().toISOString();
+      const samples = await Promise.all(endpoints.map(async (endpoint): Promise<LatencySample> => ({
+        target: endpoint.target,
+        at,
+        ...await measure(endpoint, config.handshake),
+      })));
+
+      const history: HistorySample[] = samples.flatMap((sample) => [
+        { metric: CONNECT_METRIC, key: sample.target, at, value: sample.connectMs },
+        ...(config.handshake ? [{ metric: HANDSHAKE_METRIC, key: sample.target, at, value: sample.handshakeMs }] : []),
+      ]);
+      deps.history.record(history);
+      latest = samples;
+      return samples;
+    } finally {
+      inFlight -= 1;
+    }
+  }
+
+  return { run, latest: () => latest, inFlight: () => inFlight };
+}
+
+export type LatencyProber = ReturnType<typeof createLatencyProber>;
@@ -0,0 +1,83 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+
+import { createLifetimeCounterStore } from './lifetime-counters.js';
+import type { ServerChannelInfo, StackData } from './monitoring.js';
+
+const channel = (channel_id: number, shares_acknowledged: number, blocks_found = 0): ServerChannelInfo => ({
+  channel_id,
+  user_identity: `miner-${channel_id}`,
+  target_hex: '00',
+  best_diff: 0,
+  blocks_found,
+  shares_acknowledged,
+  shares_submitted: shares_acknowledged + 1,
+  shares_rejected: 1,
+  shares_rejected_by_reason: {},
+});
+
+const stack = (uptime_secs: number | null, channels: ServerChannelInfo[] | null): StackData => ({
+  mode: 'no-jd',
+  upstream: 'translator',
+  global: uptime_secs === null ? null : { uptime_secs },
+  serverChannels: channels && { extended_channels: channels, standard_channels: [], total_extended: channels.length, total_standard: 0 },
+  sv1Clients: null,
+  sv2Clients: null,
+});
+
+test('counters add up growth between polls across channel resets and upstream restarts', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-lifetime-'));
+  const store = createLifetimeCounterStore(path.join(dir, 'lifetime-counters.json'));
+
+  // The first poll starts from what upstream has counted so far.
+  await store.record(stack(600, [channel(1, 100), channel(2, 50)]), '2026-01-01T00:00:00.000Z');
+  // Channel 2 reopened with its counters reset.
+  await store.record(stack(610, [channel(1, 120), channel(2, 5, 1)]), '2026-01-01T00:00:10.000Z');
+  // The channels failed to load: nothing is counted, and nothing is forgotten.
+  await store.record(stack(620, null), '2026-01-01T00:00:20.000Z');
+  // The Translator restarted; channel 1 has already passed its old count.
+  await store.record(stack(30, [channel(1, 200), channel(2, 10)]), '2026-01-01T00:01:00.000Z');
+
+  assert.deepEqual(await store.get(), {
+    since: '2026-01-01T00:00:00.000Z',
+    sharesAccepted: 150 + 20 + 5 + 200 + 10,
+    sharesSubmitted: 152 + 20 + 6 + 201 + 11,
+    // Every channel has one rejection since it opened.
+    sharesRejected: 2 + 1 + 2,
+    blocksFound: 1,
+    uptimeSecs: 650,
+    sessions: 2,
+  });
+});
+
+test('counters are written every so often and pick up where they left off', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-lifetime-'));
+  const filePath = path.join(dir, 'lifetime-counters.json');
+  let clock = 0;
+  const store = createLifetimeCounterStore(filePath, { persistIntervalMs: 60_000, now: () => clock });
+
+  await store.record(stack(600, [channel(1, 100)]), '2026-01-01T00:00:00.000Z');
+  clock = 10_000;
+  await store.record(stack(610, [channel(1, 110)]), '2026-01-01T00:00:10.000Z');
+  assert.equal((await createLifetimeCounterStore(filePath).get()).sharesAccepted, 100);
+  await store.flush();
+  assert.equal((await createLifetimeCounterStore(filePath).get()).sharesAccepted, 110);
+  clock = 20_000;
+  await store.record(stack(620, [channel(1, 115)]), '2026-01-01T00:00:20.000Z');
+
+  // Growth since the last write isn't lost: it's still counted upstream.
+  const restarted = createLifetimeCounterStore(filePath);
+  await restarted.record(stack(700, [channel(1, 130)]), '2026-01-01T00:01:40.000Z');
+  assert.deepEqual(await restarted.get(), {
+    since: '2026-01-01T00:00:00.000Z',
+    sharesAccepted: 130,
+    sharesSubmitted: 131,
+    sharesRejected: 1,
+    blocksFound: 0,
+    uptimeSecs: 700,
+    sessions: 1,
+  });
+});
@@ -0,0 +1,194 @@
+/**
+ * Stack counters kept across restarts, in CONFIG_DIR/lifetime-counters.json:
+ * shares accepted, submitted and rejected, blocks found, uptime, and how
+ * many sessions of the Translator (or JDC in JD mode) they span.
+ *
+ * The monitoring APIs count shares since a channel opened and uptime since
+ * the process started, so restarting anything resets them. Instead, each
+ * poll's growth is added to stored totals, as in share-windows.ts. A channel
+ * counter lower than its last reading counts from zero, and so does every
+ * channel once uptime went down, since that means the service restarted.
+ * Totals start from the upstream counters on the first poll.
+ *
+ * The last readings are stored along with the totals, so growth while
+ * sv2-ui was down is counted on its first poll back. That also means the
+ * file only needs writing every so often.
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import type { StackData } from './monitoring.js';
+
+export interface LifetimeCounters {
+  // When the first poll was counted; null before that.
+  since: string | null;
+  sharesAccepted: number;
+  sharesSubmitted: number;
+  sharesRejected: number;
+  blocksFound: number;
+  // Uptime added up across restarts.
+  uptimeSecs: number;
+  // Times the service was seen starting, the first poll included.
+  sessions: number;
+}
+
+type ChannelCounter = 'sharesAccepted' | 'sharesSubmitted' | 'sharesRejected' | 'blocksFound';
+
+// Channel key -> last reading, per counter.
+type ChannelReadings = Partial<Record<ChannelCounter, Record<string, number>>>;
+
+interface StoredCounters {
+  totals: LifetimeCounters;
+  // The last uptime polled, for noticing restarts.
+  uptimeSecs: number | null;
+  channels: ChannelReadings;
+}
+
+export const DEFAULT_COUNTER_PERSIST_INTERVAL_MS = 60_000;
+
+const EMPTY_COUNTERS: LifetimeCounters = {
+  since: null,
+  sharesAccepted: 0,
+  sharesSubmitted: 0,
+  sharesRejected: 0,
+  blocksFound: 0,
+  uptimeSecs: 0,
+  sessions: 0,
+};
+
+/**
+ * Each channel's counters in a poll, leaving out those of sections that
+ * couldn't be fetched. Shares come from the upstream channels and blocks
+ * from the miners' channels, as in the stack summary.
+ */
+export function readChannelCounters(data: StackData): ChannelReadings {
+  const readings: ChannelReadings = {};
+  if (data.serverChannels) {
+    const channels = [...data.serverChannels.extended_channels, ...data.serverChannels.standard_channels];
+    const byChannel = (read: (channel: (typeof channels)[number]) => number) =>
+      Object.fromEntries(channels.map((channel) => [`server:${channel.channel_id}`, read(channel)]));
+    readings.sharesAccepted = byChannel((channel) => channel.shares_acknowledged);
+    readings.sharesSubmitted = byChannel((channel) => channel.shares_submitted);
+    readings.sharesRejected = byChannel((channel) => channel.shares_rejected);
+    if (data.mode !== 'jd') readings.blocksFound = byChannel((channel) => channel.blocks_found);
+  }
+  if (data.mode === 'jd' && data.sv2Clients) {
+    readings.blocksFound = Object.fromEntries(data.sv2Clients.flatMap((client) =>
+      [...client.extended_channels, ...client.standard_channels].map((channel) => [
+        `jdc:client:${client.client_id}:${channel.channel_id}`,
+        channel.blocks_found,
+      ])));
+  }
+  return readings;
+}
+
+/**
+ * Fold one poll into `stored`.
+ */
+export function addPoll(stored: StoredCounters, data: StackData, at: string): StoredCounters {
+  const totals = { ...stored.totals, since: stored.totals.since ?? at };
+  const uptime = data.global?.uptime_secs ?? null;
+  const restarted = uptime !== null && stored.uptimeSecs !== null && uptime < stored.uptimeSecs;
+
+  if (uptime !== null) {
+    if (stored.uptimeSecs === null || restarted) {
+      totals.uptimeSecs += uptime;
+      totals.sessions += 1;
+    } else {
+      totals.uptimeSecs += uptime - stored.uptimeSecs;
+    }
+  }
+
+  const polled = Object.entries(readChannelCounters(data)) as [ChannelCounter, Record<string, number>][];
+  // A channel with any counter lower than its last reading was reopened.
+  const reopened = new Set<string>();
+  for (const [counter, readings] of polled) {
+    for (const [key, value] of Object.entries(readings)) {
+      if (value < (stored.channels[counter]?.[key] ?? 0)) reopened.add(key);
+    }
+  }
+
+  // Sections missing from this poll keep their last readings.
+  const channels: ChannelReadings = { ...stored.channels };
+  for (const [counter, readings] of polled) {
+    for (const [key, value] of Object.entries(readings)) {
+      const prior = stored.channels[counter]?.[key];
+      totals[counter] += restarted || reopened.has(key) || prior === undefined ? value : value - prior;
+    }
+    channels[counter] = readings;
+  }
+
+  return { totals, uptimeSecs: uptime ?? stored.uptimeSecs, channels };
+}
+
+export interface LifetimeCounterStoreOptions {
+  persistIntervalMs?: number;
+  now?: () => number;
+}
+
+export function createLifetimeCounterStore(
+  filePath: string,
+  { persistIntervalMs = DEFAULT_COUNTER_PERSIST_INTERVAL_MS, now = Date.now }: LifetimeCounterStoreOptions = {},
+) {
+  let loaded: Promise<StoredCounters> | null = null;
+  // Serialize writes so an older state never lands after a newer one.
+  let pending: Promise<void> = Promise.resolve();
+  let writtenAt: number | null = null;
+  let dirty = false;
+
+  async function read(): Promise<StoredCounters> {
+    try {
+      const stored = JSON.parse(await fs.readFile(filePath, 'utf-8')) as Partial<StoredCounters>;
+      return {
+        totals: { ...EMPTY_COUNTERS, ...stored.totals },
+        uptimeSecs: stored.uptimeSecs ?? null,
+        channels: stored.channels ?? {},
+      };
+    } catch {
+      return { totals: { ...EMPTY_COUNTERS }, uptimeSecs: null, channels: {} };
+    }
+  }
+
+  function load(): Promise<StoredCounters> {
+    loaded ??= read();
+    return loaded;
+  }
+
+  async function persist(): Promise<void> {
+    const snapshot = JSON.stringify(await load(), null, 2);
+    writtenAt = now();
+    dirty = false;
+    const next = pending.then(async () => {
+      await fs.mkdir(path.dirname(filePath), { recursive: true });
+      await fs.writeFile(filePath, snapshot);
+    });
+    pending = next.catch(() => {});
+    return next;
+  }
+
+  async function get(): Promise<LifetimeCounters> {
+    return { ...(await load()).totals };
+  }
+
+  /**
+   * Fold in a poll, writing the file if it's been persistIntervalMs since
+   * the last write.
+   */
+  async function record(data: StackData, at: string): Promise<void> {
+    const stored = addPoll(await load(), data, at);
+    loaded = Promise.resolve(stored);
+    dirty = true;
+    if (writtenAt === null || now() - writtenAt >= persistIntervalMs) await persist();
+  }
+
+  /**
+   * Write what hasn't been written yet, e.g. on shutdown.
+   */
+  async function flush(): Promise<void> {
+    if (dirty) await persist();
+  }
+
+  return { get, record, flush };
+}
+
+export type LifetimeCounterStore = ReturnType<typeof createLifetimeCounterStore>;
@@ -0,0 +1,41 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { formatListenUrl, parseListenAddress, parseListenAddresses } from './listen.js';
+
+test('unset LISTEN binds every interface on the default port', () => {
+  assert.deepEqual(parseListenAddresses(undefined, 3001), [{ host: undefined, port: 3001 }]);
+  assert.deepEqual(parseListenAddresses('  ', 3001), [{ host: undefined, port: 3001 }]);
+});
+
+test('parses IPv4, bracketed IPv6, and port-only addresses', () => {
+  assert.deepEqual(parseListenAddress('127.0.0.1:3000', 3001), { host: '127.0.0.1', port: 3000 });
+  assert.deepEqual(parseListenAddress('[::1]:3000', 3001), { host: '::1', port: 3000 });
+  assert.deepEqual(parseListenAddress(':8080', 3001), { host: undefined, port: 8080 });
+  assert.deepEqual(parseListenAddress('8080', 3001), { host: undefined, port: 8080 });
+});
+
+test('falls back to the default port when only a host is given', () => {
+  assert.deepEqual(parseListenAddress('0.0.0.0', 3001), { host: '0.0.0.0', port: 3001 });
+  assert.deepEqual(parseListenAddress('[::]', 3001), { host: '::', port: 3001 });
+  assert.deepEqual(parseListenAddress('::1', 3001), { host: '::1', port: 3001 });
+});
+
+test('splits on commas and whitespace and drops duplicates', () => {
+  assert.deepEqual(parseListenAddresses('127.0.0.1:3000, [::1]:3000 127.0.0.1:3000', 3001), [
+    { host: '127.0.0.1', port: 3000 },
+    { host: '::1', port: 3000 },
+  ]);
+});
+
+test('rejects invalid ports and malformed IPv6 addresses', () => {
+  assert.throws(() => parseListenAddress('127.0.0.1:http', 3001), /Invalid port/);
+  assert.throws(() => parseListenAddress('127.0.0.1:70000', 3001), /Invalid port/);
+  assert.throws(() => parseListenAddress('[::1:3000', 3001), /Unterminated IPv6/);
+  assert.throws(() => parseListenAddress('[::1]3000', 3001), /Invalid listen address/);
+});
+
+test('formats listener URLs with brackets for IPv6 hosts', () => {
+  assert.equal(formatListenUrl({ host: undefined, port: 8080 }), 'http://localhost:8080');
+  assert.equal(formatListenUrl({ host: '::1', port: 3000 }), 'http://[::1]:3000');
+  assert.equal(formatListenUrl({ host: '127.0.0.1', port: 3000 }), 'http://127.0.0.1:3000');
+});
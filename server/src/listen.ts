@@ -0,0 +1,86 @@
+/**
+ * Listen address parsing for the HTTP server.
+ *
+ * `LISTEN` accepts a comma or whitespace separated list of addresses so the
+ * same app can be served on several sockets, e.g. `127.0.0.1:3000,[::1]:3000`.
+ */
+
+export interface ListenAddress {
+  // undefined binds every interface (Node's dual-stack default)
+  host: string | undefined;
+  port: number;
+}
+
+function parsePort(value: string, input: string): number {
+  const port = Number(value);
+  if (!/^\d+$/.test(value) || !Number.isInteger(port) || port < 1 || port > 65535) {
+    throw new Error(`Invalid port in listen address "${input}"`);
+  }
+  return port;
+}
+
+export function parseListenAddress(input: string, defaultPort: number): ListenAddress {
+  const value = input.trim();
+
+  if (value.startsWith('[')) {
+    const end = value.indexOf(']');
+    if (end === -1) {
+      throw new Error(`Unterminated IPv6 address in listen address "${input}"`);
+    }
+
+    const host = value.slice(1, end);
+    const rest = value.slice(end + 1);
+    if (rest === '') {
+      return { host, port: defaultPort };
+    }
+    if (!rest.startsWith(':')) {
+      throw new Error(`Invalid listen address "${input}"`);
+    }
+    return { host, port: parsePort(rest.slice(1), input) };
+  }
+
+  const colonCount = value.split(':').length - 1;
+
+  // A bare IPv6 address (`::1`) has no room for a port without brackets.
+  if (colonCount > 1) {
+    return { host: value, port: defaultPort };
+  }
+
+  if (colonCount === 1) {
+    const [host, port] = value.split(':');
+    return { host: host || undefined, port: parsePort(port, input) };
+  }
+
+  if (/^\d+$/.test(value)) {
+    return { host: undefined, port: parsePort(value, input) };
+  }
+
+  return { host: value, port: defaultPort };
+}
+
+export function parseListenAddresses(value: string | undefined, defaultPort: number): ListenAddress[] {
+  const entries = (value ?? '')
+    .split(/[\s,]+/)
+    .filter((entry) => entry.length > 0);
+
+  if (entries.length === 0) {
+    return [{ host: undefined, port: defaultPort }];
+  }
+
+  const addresses: ListenAddress[] = [];
+  for (const entry of entries) {
+    const address = parseListenAddress(entry, defaultPort);
+    if (!addresses.some((existing) => existing.host === address.host && existing.port === address.port)) {
+      addresses.push(address);
+    }
+  }
+
+  return addresses;
+}
+
+export function formatListenUrl(address: ListenAddress): string {
+  const host = address.host ?? 'localhost';
+  return host.includes(':')
+    ? `http://[${host}]:${address.port}`
+    : `http://${host}:${address.port}`;
+}
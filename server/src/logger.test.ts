@@ -0,0 +1,51 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { createLogger, DEFAULT_LOG_FILTER, getTargetLevel, isLogEnabled, parseLogFilter, parseLogLevelArg, setLogFilter } from './logger.js';
+import { runWithRequestId } from './request-context.js';
+
+test('parseLogFilter takes a default level and per-module levels', () => {
+  assert.deepEqual(parseLogFilter('warn,poller=debug, routes/history=TRACE'), {
+    level: 'warn',
+    targets: { poller: 'debug', 'routes/history': 'trace' },
+  });
+  assert.deepEqual(parseLogFilter('proxy.ts=off'), { level: 'info', targets: { proxy: 'off' } });
+  assert.throws(() => parseLogFilter('verbose'), /Unknown log level "verbose" in "verbose"; use off, error, warn, info, debug, trace/);
+  assert.throws(() => parseLogFilter('../poller=debug'), /Invalid log target/);
+
+  assert.deepEqual(parseLogLevelArg(['--headless', '--log-level', 'debug'], { LOG_LEVEL: 'error' }), { level: 'debug', targets: {} });
+  assert.deepEqual(parseLogLevelArg(['--log-level=poller=trace'], {}), { level: 'info', targets: { poller: 'trace' } });
+  assert.deepEqual(parseLogLevelArg([], { LOG_LEVEL: 'error' }), { level: 'error', targets: {} });
+  assert.equal(parseLogLevelArg([], {}), DEFAULT_LOG_FILTER);
+  assert.throws(() => parseLogLevelArg(['--log-level', '--headless'], {}), /--log-level requires a filter/);
+});
+
+test('a module logs at the level of its longest matching name', () => {
+  const filter = parseLogFilter('warn,routes=debug,routes/history=off,poller=trace');
+  assert.equal(getTargetLevel('routes/miners', filter), 'debug');
+  assert.equal(getTargetLevel('routes/history', filter), 'off');
+  assert.equal(getTargetLevel('proxy', filter), 'warn');
+  // A name only matches whole segments.
+  assert.equal(getTargetLevel('poller-extra', filter), 'warn');
+
+  assert.equal(isLogEnabled('proxy', 'warn', filter), true);
+  assert.equal(isLogEnabled('proxy', 'info', filter), false);
+  assert.equal(isLogEnabled('routes/history', 'error', filter), false);
+  assert.equal(isLogEnabled('poller', 'trace', filter), true);
+});
+
+test('loggers follow the filter set after they were created, and tag debug lines and requests', (t) => {
+  const logged: unknown[][] = [];
+  t.mock.method(console, 'log', (...args: unknown[]) => logged.push(args));
+  t.after(() => setLogFilter(DEFAULT_LOG_FILTER));
+  const log = createLogger('poller');
+
+  log.info('Polled');
+  log.debug('Polled in 12ms');
+  setLogFilter(parseLogFilter('off,poller=debug'));
+  log.debug('Polled in 9ms');
+  log.trace('No stack to poll');
+  createLogger('proxy').info('hidden');
+  runWithRequestId('panel-42', () => log.debug('Polled in 7ms'));
+
+  assert.deepEqual(logged, [['Polled'], ['[poller]', 'Polled in 9ms'], ['[poller]', '[request panel-42]', 'Polled in 7ms']]);
+});
@@ -0,0 +1,106 @@
+/**
+ * LOG_LEVEL and --log-level: how much each module logs.
+ *
+ * The filter is a comma-separated list of a default level and per-module
+ * levels, e.g. `warn,poller=debug,proxy=trace`. A module is named after its
+ * file, so `routes=debug` covers every route module, and `routes/history`
+ * one of them. The default is `info`, which logs what the server always has.
+ *
+ * Debug and trace lines start with their module's name in brackets, so a
+ * noisy module is easy to find and turn down again. Lines logged while a
+ * request is handled start with its ID (see request-context.ts).
+ */
+
+import { getRequestId } from './request-context.js';
+
+export const LOG_LEVELS = ['off', 'error', 'warn', 'info', 'debug', 'trace'] as const;
+export type LogLevel = (typeof LOG_LEVELS)[number];
+
+export interface LogFilter {
+  level: LogLevel;
+  targets: Record<string, LogLevel>;
+}
+
+export type Logger = Record<Exclude<LogLevel, 'off'>, (...args: unknown[]) => void>;
+
+export const DEFAULT_LOG_FILTER: LogFilter = { level: 'info', targets: {} };
+
+let activeFilter = DEFAULT_LOG_FILTER;
+
+function parseLevel(value: string, spec: string): LogLevel {
+  const level = value.trim().toLowerCase();
+  if (!(LOG_LEVELS as readonly string[]).includes(level)) {
+    throw new Error(`Unknown log level "${value}" in "${spec}"; use ${LOG_LEVELS.join(', ')}`);
+  }
+  return level as LogLevel;
+}
+
+export function parseLogFilter(spec: string): LogFilter {
+  const filter: LogFilter = { level: DEFAULT_LOG_FILTER.level, targets: {} };
+  for (const directive of spec.split(',').map((part) => part.trim()).filter(Boolean)) {
+    const separator = directive.indexOf('=');
+    if (separator === -1) {
+      filter.level = parseLevel(directive, spec);
+      continue;
+    }
+    const target = directive.slice(0, separator).trim().replace(/\.ts$/, '');
+    if (!/^[\w-]+(\/[\w-]+)*$/.test(target)) throw new Error(`Invalid log target "${target}" in "${spec}"`);
+    filter.targets[target] = parseLevel(directive.slice(separator + 1), spec);
+  }
+  return filter;
+}
+
+/**
+ * The filter from `--log-level`, or else LOG_LEVEL.
+ */
+export function parseLogLevelArg(argv: string[] = process.argv.slice(2), env: NodeJS.ProcessEnv = process.env): LogFilter {
+  const index = argv.findIndex((arg) => arg === '--log-level' || arg.startsWith('--log-level='));
+  if (index !== -1) {
+    const value = argv[index].startsWith('--log-level=') ? argv[index].slice('--log-level='.length) : argv[index + 1];
+    if (!value?.trim() || value.startsWith('--')) throw new Error('--log-level requires a filter, e.g. "info,poller=debug"');
+    return parseLogFilter(value);
+  }
+  return env.LOG_LEVEL?.trim() ? parseLogFilter(env.LOG_LEVEL) : DEFAULT_LOG_FILTER;
+}
+
+export function setLogFilter(filter: LogFilter): void {
+  activeFilter = filter;
+}
+
+/**
+ * The level `target` logs at: that of the longest matching module name.
+ */
+export function getTargetLevel(target: string, filter: LogFilter = activeFilter): LogLevel {
+  for (let name = target; name; name = name.slice(0, Math.max(0, name.lastIndexOf('/')))) {
+    const level = filter.targets[name];
+    if (level) return level;
+  }
+  return filter.level;
+}
+
+export function isLogEnabled(target: string, level: Exclude<LogLevel, 'off'>, filter: LogFilter = activeFilter): boolean {
+  return LOG_LEVELS.indexOf(level) <= LOG_LEVELS.indexOf(getTargetLevel(target, filter));
+}
+
+/**
+ * The logger of the module `target`. It checks the filter on every call, so
+ * modules can create theirs before the filter is parsed.
+ */
+export function createLogger(target: string): Logger {
+  const write = (level: Exclude<LogLevel, 'off'>, method: 'error' | 'warn' | 'log') => (...args: unknown[]) => {
+    if (!isLogEnabled(target, level)) return;
+    const requestId = getRequestId();
+    const prefix = [
+      ...(level === 'debug' || level === 'trace' ? [`[${target}]`] : []),
+      ...(requestId ? [`[request ${requestId}]`] : []),
+    ];
+    console[method](...prefix, ...args);
+  };
+  return {
+    error: write('error', 'error'),
+    warn: write('warn', 'warn'),
+    info: write('info', 'log'),
+    debug: write('debug', 'log'),
+    trace: write('trace', 'log'),
+  };
+}
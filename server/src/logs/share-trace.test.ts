@@ -0,0 +1,96 @@
+import assert from 'node:assert/strict';
+import test from 'node:test';
+
+import type { StackData } from '../monitoring.js';
+import { parseShareTraceQuery, traceShare } from './share-trace.js';
+import type { ContainerLogLine } from './types.js';
+
+function createLogLine(timestamp: string, message: string): ContainerLogLine {
+  return {
+    container: 'translator',
+    stream: 'stdout',
+    timestamp,
+    message,
+    raw: `${timestamp} INFO ${message}`,
+  };
+}
+
+const data: StackData = {
+  mode: 'no-jd',
+  upstream: 'translator',
+  global: null,
+  serverChannels: {
+    extended_channels: [{
+      channel_id: 7,
+      user_identity: 'acct.rig1',
+      target_hex: '00',
+      best_diff: 0,
+      blocks_found: 0,
+      shares_acknowledged: 41,
+      shares_submitted: 42,
+      shares_rejected: 1,
+      shares_rejected_by_reason: { 'stale-share': 1 },
+    }],
+    standard_channels: [],
+    total_extended: 1,
+    total_standard: 0,
+  },
+  sv1Clients: {
+    items: [{ client_id: 3, channel_id: 7, authorized_worker_name: 'acct.rig1', user_identity: 'acct.rig1', target_hex: '00' }],
+    total: 1,
+  },
+  sv2Clients: null,
+};
+
+const lines = [
+  createLogLine('2026-04-17T18:29:00.000Z', 'Received mining.submit from acct.rig1 job_id: 12'),
+  createLogLine('2026-04-17T18:30:00.100Z', 'Received mining.submit from acct.rig1 job_id: 15'),
+  createLogLine('2026-04-17T18:30:00.200Z', 'Sending SubmitSharesExtended { channel_id: 7, job_id: 15 }'),
+  createLogLine('2026-04-17T18:30:00.400Z', 'Received SubmitSharesSuccess { channel_id: 7, new_submits_accepted_count: 1 }'),
+  createLogLine('2026-04-17T18:30:00.500Z', 'Received mining.submit from acct.rig10 job_id: 15'),
+];
+
+test('parseShareTraceQuery requires a worker with a time, or a job id', () => {
+  assert.throws(() => parseShareTraceQuery({}), /worker \(with at\) or jobId is required/);
+  assert.throws(() => parseShareTraceQuery({ worker: 'acct.rig1' }), /at is required/);
+  assert.throws(() => parseShareTraceQuery({ jobId: '1', window: '9999' }), /window must be between/);
+  assert.deepEqual(parseShareTraceQuery({ worker: 'acct.rig1', at: '2026-04-17T18:30:00Z' }), {
+    worker: 'acct.rig1',
+    at: '2026-04-17T18:30:00.000Z',
+    jobId: null,
+    windowSecs: 60,
+  });
+});
+
+test('traceShare follows a worker share to its upstream acknowledgment', () => {
+  const query = parseShareTraceQuery({ worker: 'acct.rig1', at: '2026-04-17T18:30:00Z', window: '5' });
+  const trace = traceShare(lines, data, query, new Date('2026-04-17T18:31:00Z'));
+
+  assert.equal(trace.verdict, 'accepted');
+  assert.deepEqual(trace.steps.map((step) => step.stage), ['submitted', 'forwarded', 'accepted']);
+  assert.deepEqual(trace.client, { clientId: 3, channelId: 7, userIdentity: 'acct.rig1' });
+  assert.equal(trace.upstreamChannel?.sharesAcknowledged, 41);
+});
+
+test('traceShare matches by job id and reports rejections', () => {
+  const rejected = [
+    ...lines.slice(0, 3),
+    createLogLine('2026-04-17T18:30:00.400Z', 'Received SubmitSharesError { channel_id: 7, job_id: 15, error_code: stale-share }'),
+  ];
+  const trace = traceShare(rejected, null, parseShareTraceQuery({ jobId: '15' }));
+
+  assert.equal(trace.verdict, 'rejected');
+  assert.match(trace.summary, /stale-share/);
+  assert.equal(trace.steps.length, 3);
+  assert.equal(trace.client, null);
+});
+
+test('traceShare reports shares missing from the logs', () => {
+  const query = parseShareTraceQuery({ worker: 'acct.rig2', at: '2026-04-17T18:30:00Z' });
+  assert.equal(traceShare(lines, data, query).verdict, 'not-found');
+
+  const upstreamMissing = parseShareTraceQuery({ worker: 'acct.rig1', at: '2026-04-17T18:29:00Z', window: '5' });
+  const trace = traceShare(lines, data, upstreamMissing);
+  assert.equal(trace.verdict, 'unacknowledged');
+  assert.match(trace.summary, /no upstream submit/);
+});
@@ -0,0 +1,191 @@
+/**
+ * Follow one share through the service logs and upstream counters, to settle
+ * "my shares aren't being counted" reports with evidence.
+ *
+ * A share is identified by worker name plus approximate submit time, or by
+ * job id. Log lines are matched on those, and on the worker's upstream
+ * channel id so the Sv2 submit and its acknowledgment are picked up too.
+ * Only lines still held by the container log driver can be traced.
+ */
+
+import type { StackData } from '../monitoring.js';
+import type { ContainerLogLine, DiagnosticEvidence } from './types.js';
+
+export interface ShareTraceQuery {
+  worker: string | null;
+  // ISO timestamp of the submit, required with `worker`.
+  at: string | null;
+  jobId: string | null;
+  windowSecs: number;
+}
+
+export type ShareTraceStage = 'submitted' | 'forwarded' | 'accepted' | 'rejected' | 'other';
+
+export interface ShareTraceStep extends DiagnosticEvidence {
+  stage: ShareTraceStage;
+}
+
+export interface ShareTraceChannel {
+  channelId: number;
+  userIdentity: string;
+  sharesSubmitted: number;
+  sharesAcknowledged: number;
+  sharesRejected: number;
+  sharesRejectedByReason: Record<string, number>;
+}
+
+export type ShareTraceVerdict = 'accepted' | 'rejected' | 'unacknowledged' | 'not-found';
+
+export interface ShareTrace {
+  query: ShareTraceQuery;
+  generatedAt: string;
+  verdict: ShareTraceVerdict;
+  summary: string;
+  // The Sv1 connection behind `worker`, when it's still connected.
+  client: { clientId: number; channelId: number | null; userIdentity: string } | null;
+  upstreamChannel: ShareTraceChannel | null;
+  steps: ShareTraceStep[];
+}
+
+const DEFAULT_WINDOW_SECS = 60;
+const MAX_WINDOW_SECS = 600;
+
+// Checked in order; the first match decides a line's stage.
+const STAGE_PATTERNS: Array<[ShareTraceStage, RegExp]> = [
+  ['rejected', /SubmitSharesError|share rejected|invalid share|duplicate share|stale share/i],
+  ['accepted', /SubmitSharesSuccess|share accepted|shares accepted/i],
+  ['forwarded', /SubmitShares(Extended|Standard)\b/],
+  ['submitted', /mining\.submit\b/],
+];
+
+function getQueryString(value: unknown): string | null {
+  return typeof value === 'string' && value.trim() ? value.trim() : null;
+}
+
+export function parseShareTraceQuery(query: Record<string, unknown>): ShareTraceQuery {
+  const worker = getQueryString(query.worker);
+  const jobId = getQueryString(query.jobId);
+  const rawAt = getQueryString(query.at);
+  const rawWindow = getQueryString(query.window);
+
+  if (!worker && !jobId) {
+    throw new Error('worker (with at) or jobId is required');
+  }
+  if (worker && !rawAt && !jobId) {
+    throw new Error('at is required with worker');
+  }
+  if (rawAt && Number.isNaN(Date.parse(rawAt))) {
+    throw new Error('at must be an ISO 8601 timestamp');
+  }
+  if (jobId && !/^[\w-]{1,64}$/.test(jobId)) {
+    throw new Error('jobId must be a job id as logged by the services');
+  }
+
+  const windowSecs = rawWindow ? Number(rawWindow) : DEFAULT_WINDOW_SECS;
+  if (!Number.isInteger(windowSecs) || windowSecs < 1 || windowSecs > MAX_WINDOW_SECS) {
+    throw new Error(`window must be between 1 and ${MAX_WINDOW_SECS} seconds`);
+  }
+
+  return { worker, at: rawAt && new Date(rawAt).toISOString(), jobId, windowSecs };
+}
+
+function escapeRegex(value: string): string {
+  return value.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+}
+
+function getStage(message: string): ShareTraceStage {
+  return STAGE_PATTERNS.find(([, pattern]) => pattern.test(message))?.[0] ?? 'other';
+}
+
+function findClient(data: StackData | null, worker: string | null): ShareTrace['client'] {
+  if (!worker) return null;
+  const client = data?.sv1Clients?.items.find(
+    ({ authorized_worker_name, user_identity }) => authorized_worker_name === worker || user_identity === worker
+  );
+  return client
+    ? { clientId: client.client_id, channelId: client.channel_id ?? null, userIdentity: client.user_identity }
+    : null;
+}
+
+function findUpstreamChannel(data: StackData | null, channelId: number | null): ShareTraceChannel | null {
+  if (channelId === null || !data?.serverChannels) return null;
+  const channel = [...data.serverChannels.extended_channels, ...data.serverChannels.standard_channels]
+    .find(({ channel_id }) => channel_id === channelId);
+  return channel
+    ? {
+        channelId: channel.channel_id,
+        userIdentity: channel.user_identity,
+        sharesSubmitted: channel.shares_submitted,
+        sharesAcknowledged: channel.shares_acknowledged,
+        sharesRejected: channel.shares_rejected,
+        sharesRejectedByReason: channel.shares_rejected_by_reason,
+      }
+    : null;
+}
+
+function describe(verdict: ShareTraceVerdict, steps: ShareTraceStep[]): string {
+  switch (verdict) {
+    case 'accepted':
+      return 'The share was forwarded upstream and acknowledged.';
+    case 'rejected':
+      return `The share was rejected: ${steps.find((step) => step.stage === 'rejected')?.line ?? 'no reason logged'}`;
+    case 'unacknowledged':
+      return steps.some((step) => step.stage === 'forwarded')
+        ? 'The share was forwarded upstream, but no acknowledgment was logged in the window.'
+        : 'The share reached the Translator, but no upstream submit or acknowledgment was logged in the window.';
+    case 'not-found':
+      return 'No matching log lines. The share may predate the retained logs, or never reached the Translator.';
+  }
+}
+
+export function traceShare(
+  lines: ContainerLogLine[],
+  data: StackData | null,
+  query: ShareTraceQuery,
+  now = new Date()
+): ShareTrace {
+  const client = findClient(data, query.worker);
+  const upstreamChannel = findUpstreamChannel(data, client?.channelId ?? null);
+
+  const center = query.at ? Date.parse(query.at) : null;
+  const inWindow = (timestamp: string | null) => {
+    if (center === null) return true;
+    const time = timestamp ? Date.parse(timestamp) : Number.NaN;
+    return Number.isFinite(time) && Math.abs(time - center) <= query.windowSecs * 1000;
+  };
+
+  const patterns = [
+    query.worker && new RegExp(`(^|[^\\w.-])${escapeRegex(query.worker)}($|[^\\w.-])`),
+    query.jobId && new RegExp(`job[ _]?id\\W{0,3}${escapeRegex(query.jobId)}\\b`, 'i'),
+    client?.channelId != null && new RegExp(`channel[ _]?id\\W{0,3}${client.channelId}\\b`, 'i'),
+  ].filter((pattern): pattern is RegExp => Boolean(pattern));
+
+  const steps: ShareTraceStep[] = lines
+    .filter(({ message, timestamp }) => inWindow(timestamp) && patterns.some((pattern) => pattern.test(message)))
+    .map(({ container, stream, timestamp, raw, message }) => ({
+      stage: getStage(message),
+      container,
+      stream,
+      timestamp,
+      line: raw,
+    }));
+
+  const stages = new Set(steps.map((step) => step.stage));
+  const verdict: ShareTraceVerdict = stages.has('rejected')
+    ? 'rejected'
+    : stages.has('accepted')
+      ? 'accepted'
+      : steps.length > 0
+        ? 'unacknowledged'
+        : 'not-found';
+
+  return {
+    query,
+    generatedAt: now.toISOString(),
+    verdict,
+    summary: describe(verdict, steps),
+    client,
+    upstreamChannel,
+    steps,
+  };
+}
@@ -0,0 +1,41 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { formatLogStreamLine, parseLogStreamQuery, parseServiceLogQuery } from './stream.js';
+
+const LINE = {
+  container: 'translator' as const,
+  stream: 'stderr' as const,
+  timestamp: '2026-04-17T12:00:00.000000000Z',
+  message: 'Failed to connect to pool',
+  raw: '2026-04-17T12:00:00.000000000Z Failed to connect to pool',
+};
+
+test('parses the service, tail and format, preferring ?format over Accept', () => {
+  assert.deepEqual(parseLogStreamQuery('translator', {}, undefined), { service: 'translator', tail: 100, format: 'ndjson' });
+  assert.deepEqual(parseLogStreamQuery('jdc', { tail: '0' }, 'text/event-stream'), { service: 'jdc', tail: 0, format: 'sse' });
+  assert.equal(parseLogStreamQuery('jdc', { format: 'ndjson' }, 'text/event-stream').format, 'ndjson');
+
+  assert.throws(() => parseLogStreamQuery('jds', {}, undefined), /does not run a JDS container/);
+  assert.throws(() => parseLogStreamQuery('bitcoind', {}, undefined), /service must be/);
+  assert.throws(() => parseLogStreamQuery('translator', { tail: '5000' }, undefined), /tail must be between/);
+  assert.throws(() => parseLogStreamQuery('translator', { format: 'xml' }, undefined), /format must be/);
+});
+
+test('service log queries also take a minimum level and whether to follow', () => {
+  assert.deepEqual(parseServiceLogQuery('translator', {}, undefined), { service: 'translator', tail: 100, format: 'ndjson', level: null, follow: true });
+  assert.deepEqual(parseServiceLogQuery('jdc', { level: 'WARN', follow: 'false', tail: '20' }, undefined), {
+    service: 'jdc',
+    tail: 20,
+    format: 'ndjson',
+    level: 'warn',
+    follow: false,
+  });
+  assert.throws(() => parseServiceLogQuery('translator', { level: 'off' }, undefined), /level must be one of error, warn, info, debug, trace/);
+  assert.throws(() => parseServiceLogQuery('translator', { follow: 'yes' }, undefined), /follow must be true or false/);
+});
+
+test('formats lines as NDJSON or SSE', () => {
+  const body = '{"container":"translator","stream":"stderr","timestamp":"2026-04-17T12:00:00.000000000Z","message":"Failed to connect to pool"}';
+  assert.equal(formatLogStreamLine(LINE, 'ndjson'), `${body}\n`);
+  assert.equal(formatLogStreamLine(LINE, 'sse'), `event: log\ndata: ${body}\n\n`);
+});
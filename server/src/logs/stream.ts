@@ -0,0 +1,92 @@
+/**
+ * Live container logs for GET /api/v1/logs/:service.
+ *
+ * The container's log is followed through the Docker API, starting with the
+ * last `tail` lines, and each line is written as it arrives: one JSON object
+ * per line (NDJSON) by default, or Server-Sent Events with `?format=sse` or
+ * `Accept: text/event-stream`. The stream ends when the container stops.
+ */
+
+import { LOG_LEVELS } from '../logger.js';
+import type { LogLineLevel } from './tail.js';
+import type { ContainerLogLine, LogContainerRole } from './types.js';
+
+export type LogStreamFormat = 'ndjson' | 'sse';
+
+export interface LogStreamQuery {
+  service: LogContainerRole;
+  tail: number;
+  format: LogStreamFormat;
+}
+
+export const DEFAULT_LOG_STREAM_TAIL = 100;
+const MAX_LOG_STREAM_TAIL = 1000;
+
+function getQueryString(value: unknown): string | null {
+  return typeof value === 'string' && value.trim() ? value.trim() : null;
+}
+
+export function parseLogStreamQuery(
+  service: string,
+  query: Record<string, unknown>,
+  accept: string | undefined,
+): LogStreamQuery {
+  if (service === 'jds') {
+    throw new Error('JDS logs are not available: sv2-ui does not run a JDS container');
+  }
+  if (service !== 'translator' && service !== 'jdc') {
+    throw new Error('service must be translator or jdc');
+  }
+
+  const rawTail = getQueryString(query.tail);
+  const tail = rawTail ? Number(rawTail) : DEFAULT_LOG_STREAM_TAIL;
+  if (!Number.isInteger(tail) || tail < 0 || tail > MAX_LOG_STREAM_TAIL) {
+    throw new Error(`tail must be between 0 and ${MAX_LOG_STREAM_TAIL}`);
+  }
+
+  const rawFormat = getQueryString(query.format);
+  if (rawFormat && rawFormat !== 'ndjson' && rawFormat !== 'sse') {
+    throw new Error('format must be ndjson or sse');
+  }
+  const format = (rawFormat as LogStreamFormat | null)
+    ?? (accept?.includes('text/event-stream') ? 'sse' : 'ndjson');
+
+  return { service, tail, format };
+}
+
+export interface ServiceLogQuery extends LogStreamQuery {
+  level: LogLineLevel | null;
+  // false: just the tail, then the response ends.
+  follow: boolean;
+}
+
+/**
+ * The query of GET /api/v1/services/:service/logs: that of /api/v1/logs,
+ * plus `level` and `follow`.
+ */
+export function parseServiceLogQuery(
+  service: string,
+  query: Record<string, unknown>,
+  accept: string | undefined,
+): ServiceLogQuery {
+  const parsed = parseLogStreamQuery(service, query, accept);
+
+  const rawLevel = getQueryString(query.level)?.toLowerCase() ?? null;
+  const levels = LOG_LEVELS.filter((level) => level !== 'off');
+  if (rawLevel && !(levels as string[]).includes(rawLevel)) {
+    throw new Error(`level must be one of ${levels.join(', ')}`);
+  }
+
+  const rawFollow = getQueryString(query.follow);
+  if (rawFollow && rawFollow !== 'true' && rawFollow !== 'false') {
+    throw new Error('follow must be true or false');
+  }
+
+  return { ...parsed, level: rawLevel as LogLineLevel | null, follow: rawFollow !== 'false' };
+}
+
+export function formatLogStreamLine(line: ContainerLogLine, format: LogStreamFormat): string {
+  const { container, stream, timestamp, message } = line;
+  const body = JSON.stringify({ container, stream, timestamp, message });
+  return format === 'sse' ? `event: log\ndata: ${body}\n\n` : `${body}\n`;
+}
@@ -0,0 +1,76 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { createLevelFilter, followJournal, getLogLineLevel, parseJournalEntry, parseJournalUnits } from './tail.js';
+import type { ContainerLogLine } from './types.js';
+
+function line(message: string): ContainerLogLine {
+  return { container: 'jdc', stream: 'stdout', timestamp: null, message, raw: message };
+}
+
+test('reads the tracing level, and keeps unleveled lines with the line before them', () => {
+  assert.equal(getLogLineLevel('2026-04-17T18:30:43.174887Z ERROR jd_client_sv2: Disconnected'), 'error');
+  assert.equal(getLogLineLevel('\x1b[2m2026-04-17T18:30:43Z\x1b[0m \x1b[33m WARN\x1b[0m jd_client_sv2: Slow template'), 'warn');
+  assert.equal(getLogLineLevel(' INFO jd_client_sv2: Started'), 'info');
+  assert.equal(getLogLineLevel('thread main panicked at ERROR'), null);
+
+  const keep = createLevelFilter('warn');
+  const kept = [
+    'INFO Connecting to JDS',
+    '  at 10.0.0.2:34264',
+    'WARN Connection to JDS is slow',
+    'ERROR Failed to connect to JDS',
+    '  caused by: timeout',
+    'DEBUG Retrying in 5s',
+    '  attempt 2',
+  ].filter((message) => keep(line(message)));
+  assert.deepEqual(kept, ['WARN Connection to JDS is slow', 'ERROR Failed to connect to JDS', '  caused by: timeout']);
+  assert.equal(createLevelFilter(null)(line('TRACE everything')), true);
+});
+
+test('parses journal entries and the units to read them from', () => {
+  assert.deepEqual(parseJournalEntry('translator', JSON.stringify({ MESSAGE: 'ERROR Pool rejected share', PRIORITY: '3', __REALTIME_TIMESTAMP: '1776450643174887' })), {
+    container: 'translator',
+    stream: 'stderr',
+    timestamp: '2026-04-17T18:30:43.174Z',
+    message: 'ERROR Pool rejected share',
+    raw: 'ERROR Pool rejected share',
+  });
+  assert.equal(parseJournalEntry('translator', JSON.stringify({ MESSAGE: [104, 105], PRIORITY: '6' }))?.message, 'hi');
+  assert.equal(parseJournalEntry('translator', JSON.stringify({ MESSAGE: 'Started', PRIORITY: '6' }))?.stream, 'stdout');
+  assert.equal(parseJournalEntry('translator', '{'), null);
+  assert.equal(parseJournalEntry('translator', '{}'), null);
+
+  assert.deepEqual(parseJournalUnits({ TRANSLATOR_JOURNAL_UNIT: 'sv2-translator.service', JDC_JOURNAL_UNIT: ' ' }), { translator: 'sv2-translator.service' });
+  assert.throws(() => parseJournalUnits({ JDC_JOURNAL_UNIT: 'jdc; rm -rf /' }), /Invalid JDC_JOURNAL_UNIT/);
+});
+
+test('followJournal streams what journalctl prints, and why it failed', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-journal-'));
+  t.after(() => fs.rm(dir, { recursive: true, force: true }));
+  const command = path.join(dir, 'journalctl');
+  await fs.writeFile(command, [
+    '#!/bin/sh',
+    'echo "{\\"MESSAGE\\":\\"args: $*\\"}"',
+    'echo "{\\"MESSAGE\\":\\"INFO Started\\"}"',
+    'echo "No journal files were found." >&2',
+    'exit 1',
+  ].join('\n'), { mode: 0o755 });
+
+  const lines: string[] = [];
+  await new Promise<void>((resolve, reject) => {
+    followJournal('jdc', 'sv2-jdc.service', { tail: 20, follow: false, onLine: (entry) => lines.push(entry.message), onEnd: resolve }, command).catch(reject);
+  });
+  assert.deepEqual(lines, [
+    'args: --unit sv2-jdc.service --output json --lines 20 --no-pager',
+    'INFO Started',
+    `${command} exited with code 1: No journal files were found.`,
+  ]);
+
+  await assert.rejects(
+    followJournal('jdc', 'sv2-jdc.service', { tail: 20, follow: true, onLine: () => {}, onEnd: () => {} }, path.join(dir, 'missing')),
+    /Cannot run .*missing/,
+  );
+});
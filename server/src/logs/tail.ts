@@ -0,0 +1,129 @@
+/**
+ * Service log tails for GET /api/v1/services/:service/logs.
+ *
+ * The Translator and JDC monitoring APIs don't serve their logs, so the tail
+ * comes from the container through the Docker API, like /api/v1/logs. For a
+ * Translator or JDC installed as a systemd unit instead, set
+ * TRANSLATOR_JOURNAL_UNIT or JDC_JOURNAL_UNIT and the tail comes from
+ * journald when there's no container.
+ *
+ * `level` keeps the lines at that tracing level or more severe. Lines
+ * without a level, like the rest of a multi-line error, belong with the line
+ * before them.
+ */
+
+import { spawn } from 'child_process';
+import { LOG_LEVELS } from '../logger.js';
+import type { LogLevel } from '../logger.js';
+import type { ContainerLogLine, LogContainerRole } from './types.js';
+
+export type LogLineLevel = Exclude<LogLevel, 'off'>;
+
+export type JournalUnits = Partial<Record<LogContainerRole, string>>;
+
+const ANSI_ESCAPE = /\x1b\[[0-9;]*m/g;
+// tracing's format: an optional timestamp, then the level.
+const TRACING_LEVEL = /^(?:\S+\s+)?(ERROR|WARN|INFO|DEBUG|TRACE)\b/;
+// syslog priorities 0-7, from emerg to debug.
+const JOURNAL_PRIORITY_LEVELS: LogLineLevel[] = ['error', 'error', 'error', 'error', 'warn', 'info', 'info', 'debug'];
+
+export function getLogLineLevel(message: string): LogLineLevel | null {
+  const match = message.replace(ANSI_ESCAPE, '').trimStart().match(TRACING_LEVEL);
+  return match ? (match[1].toLowerCase() as LogLineLevel) : null;
+}
+
+/**
+ * A filter for one stream of lines, keeping `level` and more severe.
+ */
+export function createLevelFilter(level: LogLineLevel | null): (line: ContainerLogLine) => boolean {
+  if (!level) return () => true;
+  let keepUnleveled = true;
+  return (line) => {
+    const lineLevel = getLogLineLevel(line.message);
+    if (lineLevel) keepUnleveled = LOG_LEVELS.indexOf(lineLevel) <= LOG_LEVELS.indexOf(level);
+    return keepUnleveled;
+  };
+}
+
+export function parseJournalUnits(env: NodeJS.ProcessEnv = process.env): JournalUnits {
+  const units: JournalUnits = {};
+  for (const [container, variable] of [['translator', 'TRANSLATOR_JOURNAL_UNIT'], ['jdc', 'JDC_JOURNAL_UNIT']] as const) {
+    const unit = env[variable]?.trim();
+    if (!unit) continue;
+    if (!/^[\w@.:-]+$/.test(unit)) throw new Error(`Invalid ${variable} "${unit}"`);
+    units[container] = unit;
+  }
+  return units;
+}
+
+/**
+ * One line of `journalctl -o json`; null for entries without a message.
+ */
+export function parseJournalEntry(container: LogContainerRole, entry: string): ContainerLogLine | null {
+  let fields: Record<string, unknown>;
+  try {
+    fields = JSON.parse(entry) as Record<string, unknown>;
+  } catch {
+    return null;
+  }
+  // journald stores messages that aren't valid UTF-8 as byte arrays.
+  const message = Array.isArray(fields.MESSAGE) ? Buffer.from(fields.MESSAGE as number[]).toString('utf-8') : fields.MESSAGE;
+  if (typeof message !== 'string') return null;
+  const micros = Number(fields.__REALTIME_TIMESTAMP);
+  const priority = Number(fields.PRIORITY);
+  return {
+    container,
+    stream: Number.isInteger(priority) && JOURNAL_PRIORITY_LEVELS[priority] === 'error' ? 'stderr' : 'stdout',
+    timestamp: Number.isFinite(micros) ? new Date(micros / 1000).toISOString() : null,
+    message,
+    raw: message,
+  };
+}
+
+export interface JournalTailOptions {
+  tail: number;
+  follow: boolean;
+  onLine: (line: ContainerLogLine) => void;
+  onEnd: () => void;
+}
+
+/**
+ * Read the last `tail` lines of a systemd unit's journal, then follow it if
+ * asked. Resolves to a function that stops, once journalctl started.
+ */
+export function followJournal(
+  container: LogContainerRole,
+  unit: string,
+  { tail, follow, onLine, onEnd }: JournalTailOptions,
+  command = 'journalctl',
+): Promise<() => void> {
+  const child = spawn(command, ['--unit', unit, '--output', 'json', '--lines', String(tail), '--no-pager', ...(follow ? ['--follow'] : [])], {
+    stdio: ['ignore', 'pipe', 'pipe'],
+  });
+
+  return new Promise((resolve, reject) => {
+    let pending = '';
+    let stderr = '';
+    child.stdout.setEncoding('utf-8').on('data', (chunk: string) => {
+      const entries = (pending + chunk).split('\n');
+      pending = entries.pop() ?? '';
+      for (const entry of entries) {
+        const line = parseJournalEntry(container, entry);
+        if (line) onLine(line);
+      }
+    });
+    child.stderr.setEncoding('utf-8').on('data', (chunk: string) => {
+      stderr += chunk;
+    });
+    child.once('error', (error) => reject(new Error(`Cannot run ${command}: ${error.message}`)));
+    child.once('spawn', () => resolve(() => child.kill()));
+    child.once('close', (code) => {
+      // The response has started by now, so the reason goes in the stream.
+      if (code !== 0 && code !== null) {
+        const message = `${command} exited with code ${code}${stderr.trim() ? `: ${stderr.trim()}` : ''}`;
+        onLine({ container, stream: 'stderr', timestamp: null, message, raw: message });
+      }
+      onEnd();
+    });
+  });
+}
@@ -0,0 +1,43 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+
+import { createMinerLabelStore, parseMinerLabel } from './miner-labels.js';
+
+test('parseMinerLabel trims fields and rejects malformed tags', () => {
+  assert.deepEqual(parseMinerLabel({ name: ' Rack 2, top ', tags: ['s19', 'garage', 's19'] }), {
+    name: 'Rack 2, top',
+    location: null,
+    tags: ['s19', 'garage'],
+  });
+  assert.deepEqual(parseMinerLabel({ name: '   ' }), { name: null, location: null, tags: [] });
+  assert.throws(() => parseMinerLabel({ tags: 's19' }), /tags must be a list/);
+  assert.throws(() => parseMinerLabel({ tags: [''] }), /Each tag must be a non-empty string/);
+  assert.throws(() => parseMinerLabel({ location: 'x'.repeat(65) }), /location must be a string of at most 64/);
+});
+
+test('labels are stored per worker name and survive reopening', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-miner-labels-'));
+  const filePath = path.join(dir, 'history.db');
+  const labels = createMinerLabelStore(filePath);
+  assert.equal(labels.get('antminer.01'), null);
+  labels.set('antminer.01', { name: 'S19 top', location: 'garage', tags: ['s19'] }, Date.parse('2026-01-01T00:00:00.000Z'));
+  labels.set('antminer.01', { name: 'S19 top shelf', location: 'garage', tags: ['s19', 'hot'] }, Date.parse('2026-01-02T00:00:00.000Z'));
+  labels.set('antminer.02', { name: null, location: null, tags: [] });
+  labels.close();
+
+  const reopened = createMinerLabelStore(filePath);
+  assert.deepEqual(reopened.get('antminer.01'), {
+    id: 'antminer.01',
+    name: 'S19 top shelf',
+    location: 'garage',
+    tags: ['s19', 'hot'],
+    updatedAt: '2026-01-02T00:00:00.000Z',
+  });
+  assert.deepEqual(reopened.list().map((label) => label.id), ['antminer.01', 'antminer.02']);
+  assert.equal(reopened.remove('antminer.02'), true);
+  assert.equal(reopened.remove('antminer.02'), false);
+  reopened.close();
+});
@@ -0,0 +1,119 @@
+/**
+ * Labels for miners: a friendly name, a location and tags attached to a
+ * worker name (see getMinerId in stack-history.ts), since the names ASIC
+ * firmware reports are cryptic.
+ *
+ * Labels live in the history database next to the miners' hashrate, so
+ * backups carry both. Like the history, the table is opened on first use.
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { DatabaseSync } from 'node:sqlite';
+
+export interface MinerLabel {
+  id: string;
+  name: string | null;
+  location: string | null;
+  tags: string[];
+  updatedAt: string;
+}
+
+export type MinerLabelInput = Pick<MinerLabel, 'name' | 'location' | 'tags'>;
+
+export const MAX_LABEL_LENGTH = 64;
+export const MAX_TAGS = 16;
+const MAX_TAG_LENGTH = 32;
+
+const SCHEMA = `
+  CREATE TABLE IF NOT EXISTS miner_labels (
+    id TEXT PRIMARY KEY,
+    name TEXT,
+    location TEXT,
+    tags TEXT NOT NULL DEFAULT '[]',
+    updated_at INTEGER NOT NULL
+  );
+`;
+
+interface LabelRow {
+  id: string;
+  name: string | null;
+  location: string | null;
+  tags: string;
+  updated_at: number;
+}
+
+function parseText(value: unknown, field: string): string | null {
+  if (value === undefined || value === null) return null;
+  if (typeof value !== 'string' || value.trim().length > MAX_LABEL_LENGTH) {
+    throw new Error(`${field} must be a string of at most ${MAX_LABEL_LENGTH} characters`);
+  }
+  return value.trim() || null;
+}
+
+export function parseMinerLabel(body: unknown): MinerLabelInput {
+  const { name, location, tags = [] } = (body ?? {}) as { name?: unknown; location?: unknown; tags?: unknown };
+  if (!Array.isArray(tags) || tags.length > MAX_TAGS) {
+    throw new Error(`tags must be a list of at most ${MAX_TAGS} tags`);
+  }
+  const parsedTags = tags.map((tag) => {
+    if (typeof tag !== 'string' || tag.trim() === '' || tag.trim().length > MAX_TAG_LENGTH) {
+      throw new Error(`Each tag must be a non-empty string of at most ${MAX_TAG_LENGTH} characters`);
+    }
+    return tag.trim();
+  });
+  return { name: parseText(name, 'name'), location: parseText(location, 'location'), tags: [...new Set(parsedTags)] };
+}
+
+export function createMinerLabelStore(filePath: string) {
+  let db: DatabaseSync | null = null;
+
+  function open(): DatabaseSync {
+    if (!db) {
+      fs.mkdirSync(path.dirname(filePath), { recursive: true });
+      db = new DatabaseSync(filePath);
+      db.exec('PRAGMA journal_mode = WAL');
+      db.exec(SCHEMA);
+    }
+    return db;
+  }
+
+  const toLabel = (row: LabelRow): MinerLabel => ({
+    id: row.id,
+    name: row.name,
+    location: row.location,
+    tags: JSON.parse(row.tags) as string[],
+    updatedAt: new Date(row.updated_at).toISOString(),
+  });
+
+  function list(): MinerLabel[] {
+    const rows = open().prepare('SELECT * FROM miner_labels ORDER BY id').all() as unknown as LabelRow[];
+    return rows.map(toLabel);
+  }
+
+  function get(id: string): MinerLabel | null {
+    const row = open().prepare('SELECT * FROM miner_labels WHERE id = ?').get(id) as unknown as LabelRow | undefined;
+    return row ? toLabel(row) : null;
+  }
+
+  function set(id: string, { name, location, tags }: MinerLabelInput, now = Date.now()): MinerLabel {
+    open()
+      .prepare(`INSERT INTO miner_labels (id, name, location, tags, updated_at) VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT (id) DO UPDATE SET name = excluded.name, location = excluded.location, tags = excluded.tags, updated_at = excluded.updated_at`)
+      .run(id, name, location, JSON.stringify(tags), now);
+    return { id, name, location, tags, updatedAt: new Date(now).toISOString() };
+  }
+
+  function remove(id: string): boolean {
+    return Number(open().prepare('DELETE FROM miner_labels WHERE id = ?').run(id).changes) > 0;
+  }
+
+  function close(): void {
+    db?.close();
+    db = null;
+  }
+
+  return { list, get, set, remove, close };
+}
+
+export type MinerLabelStore = ReturnType<typeof createMinerLabelStore>;
@@ -0,0 +1,69 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import { decodeGlobal, decodeServerChannels, decodeSv1Clients, decodeSv2ClientChannels, SchemaError } from './monitoring-schema.js';
+
+test('decoding fills in dropped optional fields and drops unknown ones', () => {
+  assert.deepEqual(decodeGlobal({ uptime_secs: '120', server: { total_channels: 1, total_hashrate: 5e12 }, added_later: true }), {
+    server: { extended_channels: 0, standard_channels: 0, total_channels: 1, total_hashrate: 5e12 },
+    sv1_clients: null,
+    sv2_clients: null,
+    uptime_secs: 120,
+  });
+
+  const clients = decodeSv1Clients({
+    items: [{ client_id: 3, authorized_worker_name: 'rig.1', user_identity: 'user', nominal_hashrate: 1e12, target_hex: 'ff' }],
+    total: 1,
+  });
+  assert.deepEqual(clients.items[0], {
+    client_id: 3,
+    channel_id: null,
+    authorized_worker_name: 'rig.1',
+    user_identity: 'user',
+    hashrate: 1e12,
+    stable_hashrate: false,
+    target_hex: 'ff',
+    extranonce1_hex: '',
+    extranonce2_len: 0,
+    version_rolling_mask: null,
+    version_rolling_min_bit: null,
+  });
+});
+
+test('accepted shares are read under either name', () => {
+  const channel = {
+    channel_id: 1,
+    user_identity: 'user',
+    target_hex: 'ff',
+    best_diff: 10,
+    blocks_found: 0,
+    shares_rejected: 1,
+    shares_rejected_by_reason: { 'stale-share': 1 },
+  };
+
+  const server = decodeServerChannels({
+    extended_channels: [{ ...channel, shares_submitted: 6, shares_accepted: 5 }],
+    total_extended: 1,
+    total_standard: 0,
+  });
+  assert.equal(server.extended_channels[0].shares_acknowledged, 5);
+  assert.deepEqual(server.standard_channels, []);
+
+  const client = decodeSv2ClientChannels({
+    client_id: 1,
+    standard_channels: [{ ...channel, nominal_hashrate: 1e12, expected_shares_per_minute: 6, shares_acknowledged: 5 }],
+    total_extended: 0,
+    total_standard: 1,
+  });
+  assert.equal(client.standard_channels[0].shares_accepted, 5);
+});
+
+test('a missing required field is rejected with its path', () => {
+  assert.throws(() => decodeGlobal({ server: { total_hashrate: 1 }, uptime_secs: 1 }), (error: unknown) => {
+    assert.ok(error instanceof SchemaError);
+    assert.equal(error.message, 'server.total_channels: expected a number');
+    return true;
+  });
+  assert.throws(() => decodeSv1Clients({ items: [{ client_id: 'x' }], total: 1 }), /items\[0\]\.client_id: expected a number/);
+  assert.throws(() => decodeSv1Clients([]), /response: expected an object/);
+});
@@ -0,0 +1,359 @@
+/**
+ * sv2-ui's own, versioned schema for the monitoring API responses.
+ *
+ * The Translator and JDC monitoring APIs still change between sv2-apps
+ * releases. Every response sv2-ui reads is decoded here into the v1 shapes
+ * below, which match shared/openapi.json, so that churn stops at the server:
+ *
+ * - Fields sv2-ui relies on are required; a response without them is
+ *   rejected with a SchemaError naming the field.
+ * - Other fields fall back to a neutral value when upstream drops them.
+ * - Fields known under another name are read under either.
+ * - Numbers sent as strings are accepted, and unknown fields are dropped.
+ *
+ * The dashboard reads these shapes from /api/v1/monitoring/:service/...
+ * (see routes/monitoring.ts), and the poller's StackData holds them too.
+ */
+
+export const MONITORING_SCHEMA_VERSION = 1;
+
+export class SchemaError extends Error {
+  constructor(readonly at: string, expected: string) {
+    super(`${at || 'response'}: expected ${expected}`);
+    this.name = 'SchemaError';
+  }
+}
+
+export type Decoder<T> = (value: unknown, at?: string) => T;
+
+const number: Decoder<number> = (value, at = '') => {
+  const parsed = typeof value === 'string' && value.trim() !== '' ? Number(value) : value;
+  if (typeof parsed !== 'number' || !Number.isFinite(parsed)) throw new SchemaError(at, 'a number');
+  return parsed;
+};
+
+const string: Decoder<string> = (value, at = '') => {
+  if (typeof value !== 'string') throw new SchemaError(at, 'a string');
+  return value;
+};
+
+const boolean: Decoder<boolean> = (value, at = '') => {
+  if (typeof value !== 'boolean') throw new SchemaError(at, 'a boolean');
+  return value;
+};
+
+function isObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+function nullable<T>(decode: Decoder<T>): Decoder<T | null> {
+  return (value, at) => (value === null || value === undefined ? null : decode(value, at));
+}
+
+function fallback<T>(decode: Decoder<T>, missing: T): Decoder<T> {
+  return (value, at) => (value === null || value === undefined ? missing : decode(value, at));
+}
+
+function array<T>(decode: Decoder<T>): Decoder<T[]> {
+  return (value, at = '') => {
+    if (!Array.isArray(value)) throw new SchemaError(at, 'an array');
+    return value.map((item, index) => decode(item, `${at}[${index}]`));
+  };
+}
+
+// Counts keyed by name, e.g. rejected shares by reason.
+const counts: Decoder<Record<string, number>> = (value, at = '') => {
+  if (!isObject(value)) throw new SchemaError(at, 'an object of counts');
+  return Object.fromEntries(Object.entries(value).map(([key, count]) => [key, number(count, `${at}.${key}`)]));
+};
+
+/**
+ * Decode an object field by field. A field listed in `aliases` is also read
+ * under each of its other names when its own is missing.
+ */
+function object<T>(
+  fields: { [K in keyof T]-?: Decoder<T[K]> },
+  aliases: { [K in keyof T]?: string[] } = {},
+): Decoder<T> {
+  return (value, at = '') => {
+    if (!isObject(value)) throw new SchemaError(at, 'an object');
+    const decoded = {} as T;
+    for (const key of Object.keys(fields) as (keyof T & string)[]) {
+      const name = [key, ...(aliases[key] ?? [])].find((candidate) => value[candidate] !== undefined) ?? key;
+      decoded[key] = fields[key](value[name], at ? `${at}.${key}` : key);
+    }
+    return decoded;
+  };
+}
+
+export interface HealthResponse {
+  status: string;
+  timestamp: number;
+}
+
+export interface ServerSummary {
+  extended_channels: number;
+  standard_channels: number;
+  total_channels: number;
+  total_hashrate: number;
+}
+
+export interface Sv1ClientsSummary {
+  total_clients: number;
+  total_hashrate: number;
+}
+
+export interface Sv2ClientsSummary {
+  extended_channels: number;
+  standard_channels: number;
+  total_channels: number;
+  total_clients: number;
+  total_hashrate: number;
+}
+
+export interface GlobalInfo {
+  server: ServerSummary | null;
+  sv1_clients: Sv1ClientsSummary | null;
+  sv2_clients: Sv2ClientsSummary | null;
+  uptime_secs: number;
+}
+
+export interface ServerStandardChannelInfo {
+  channel_id: number;
+  user_identity: string;
+  nominal_hashrate: number | null;
+  target_hex: string;
+  extranonce_prefix_hex: string;
+  best_diff: number;
+  blocks_found: number;
+  shares_submitted: number;
+  shares_acknowledged: number;
+  shares_rejected: number;
+  shares_rejected_by_reason: Record<string, number>;
+  acknowledged_work_sum: number;
+  validated_work_sum: number;
+}
+
+export interface ServerExtendedChannelInfo extends ServerStandardChannelInfo {
+  full_extranonce_size: number;
+  rollable_extranonce_size: number;
+  version_rolling: boolean;
+}
+
+export interface ServerChannelsResponse {
+  extended_channels: ServerExtendedChannelInfo[];
+  standard_channels: ServerStandardChannelInfo[];
+  offset: number;
+  limit: number;
+  total_extended: number;
+  total_standard: number;
+}
+
+export interface Sv1ClientInfo {
+  client_id: number;
+  channel_id: number | null;
+  authorized_worker_name: string;
+  user_identity: string;
+  hashrate: number | null;
+  stable_hashrate: boolean;
+  target_hex: string;
+  extranonce1_hex: string;
+  extranonce2_len: number;
+  version_rolling_mask: string | null;
+  version_rolling_min_bit: string | null;
+}
+
+export interface Sv1ClientsResponse {
+  items: Sv1ClientInfo[];
+  offset: number;
+  limit: number;
+  total: number;
+}
+
+export interface StandardChannelInfo {
+  channel_id: number;
+  user_identity: string;
+  nominal_hashrate: number;
+  stable_hashrate: boolean;
+  target_hex: string;
+  requested_max_target_hex: string;
+  expected_shares_per_minute: number;
+  extranonce_prefix_hex: string;
+  best_diff: number;
+  blocks_found: number;
+  shares_accepted: number;
+  shares_rejected: number;
+  shares_rejected_by_reason: Record<string, number>;
+  share_work_sum: number;
+  share_batch_size: number;
+  last_batch_accepted: number;
+  last_batch_work_sum: number;
+  last_share_sequence_number: number;
+}
+
+export interface ExtendedChannelInfo extends StandardChannelInfo {
+  full_extranonce_size: number;
+  rollable_extranonce_size: number;
+}
+
+export interface Sv2ClientMetadata {
+  client_id: number;
+  extended_channels_count: number;
+  standard_channels_count: number;
+  total_hashrate: number;
+}
+
+export interface Sv2ClientsResponse {
+  items: Sv2ClientMetadata[];
+  offset: number;
+  limit: number;
+  total: number;
+}
+
+export interface Sv2ClientChannelsResponse {
+  client_id: number;
+  extended_channels: ExtendedChannelInfo[];
+  standard_channels: StandardChannelInfo[];
+  offset: number;
+  limit: number;
+  total_extended: number;
+  total_standard: number;
+}
+
+// Any answer from the health endpoint means the API is up.
+export const decodeHealth = object<HealthResponse>({
+  status: fallback(string, 'ok'),
+  timestamp: fallback(number, 0),
+});
+
+export const decodeGlobal = object<GlobalInfo>({
+  server: nullable(object<ServerSummary>({
+    extended_channels: fallback(number, 0),
+    standard_channels: fallback(number, 0),
+    total_channels: number,
+    total_hashrate: number,
+  })),
+  sv1_clients: nullable(object<Sv1ClientsSummary>({
+    total_clients: number,
+    total_hashrate: number,
+  })),
+  sv2_clients: nullable(object<Sv2ClientsSummary>({
+    extended_channels: fallback(number, 0),
+    standard_channels: fallback(number, 0),
+    total_channels: number,
+    total_clients: number,
+    total_hashrate: number,
+  })),
+  uptime_secs: number,
+});
+
+const serverStandardChannelFields = {
+  channel_id: number,
+  user_identity: string,
+  nominal_hashrate: nullable(number),
+  target_hex: string,
+  extranonce_prefix_hex: fallback(string, ''),
+  best_diff: number,
+  blocks_found: number,
+  shares_submitted: number,
+  shares_acknowledged: number,
+  shares_rejected: number,
+  shares_rejected_by_reason: fallback(counts, {}),
+  acknowledged_work_sum: fallback(number, 0),
+  validated_work_sum: fallback(number, 0),
+};
+
+// Upstream channels count accepted shares as acknowledged, client channels as
+// accepted; either name is read for both.
+const serverChannelAliases = { shares_acknowledged: ['shares_accepted'] };
+
+const decodeServerStandardChannel = object<ServerStandardChannelInfo>(serverStandardChannelFields, serverChannelAliases);
+
+const decodeServerExtendedChannel = object<ServerExtendedChannelInfo>({
+  ...serverStandardChannelFields,
+  full_extranonce_size: fallback(number, 0),
+  rollable_extranonce_size: fallback(number, 0),
+  version_rolling: fallback(boolean, false),
+}, serverChannelAliases);
+
+export const decodeServerChannels = object<ServerChannelsResponse>({
+  extended_channels: fallback(array(decodeServerExtendedChannel), []),
+  standard_channels: fallback(array(decodeServerStandardChannel), []),
+  offset: fallback(number, 0),
+  limit: fallback(number, 0),
+  total_extended: number,
+  total_standard: number,
+});
+
+const decodeSv1Client = object<Sv1ClientInfo>({
+  client_id: number,
+  channel_id: nullable(number),
+  authorized_worker_name: string,
+  user_identity: string,
+  hashrate: nullable(number),
+  stable_hashrate: fallback(boolean, false),
+  target_hex: string,
+  extranonce1_hex: fallback(string, ''),
+  extranonce2_len: fallback(number, 0),
+  version_rolling_mask: nullable(string),
+  version_rolling_min_bit: nullable(string),
+}, { hashrate: ['nominal_hashrate'] });
+
+export const decodeSv1Clients = object<Sv1ClientsResponse>({
+  items: array(decodeSv1Client),
+  offset: fallback(number, 0),
+  limit: fallback(number, 0),
+  total: number,
+});
+
+const standardChannelFields = {
+  channel_id: number,
+  user_identity: string,
+  nominal_hashrate: number,
+  stable_hashrate: fallback(boolean, false),
+  target_hex: string,
+  requested_max_target_hex: fallback(string, ''),
+  expected_shares_per_minute: number,
+  extranonce_prefix_hex: fallback(string, ''),
+  best_diff: number,
+  blocks_found: number,
+  shares_accepted: number,
+  shares_rejected: number,
+  shares_rejected_by_reason: fallback(counts, {}),
+  share_work_sum: fallback(number, 0),
+  share_batch_size: fallback(number, 0),
+  last_batch_accepted: fallback(number, 0),
+  last_batch_work_sum: fallback(number, 0),
+  last_share_sequence_number: fallback(number, 0),
+};
+const channelAliases = { shares_accepted: ['shares_acknowledged'] };
+
+const decodeStandardChannel = object<StandardChannelInfo>(standardChannelFields, channelAliases);
+
+const decodeExtendedChannel = object<ExtendedChannelInfo>({
+  ...standardChannelFields,
+  full_extranonce_size: fallback(number, 0),
+  rollable_extranonce_size: fallback(number, 0),
+}, channelAliases);
+
+export const decodeSv2Clients = object<Sv2ClientsResponse>({
+  items: array(object<Sv2ClientMetadata>({
+    client_id: number,
+    extended_channels_count: fallback(number, 0),
+    standard_channels_count: fallback(number, 0),
+    total_hashrate: fallback(number, 0),
+  })),
+  offset: fallback(number, 0),
+  limit: fallback(number, 0),
+  total: number,
+});
+
+export const decodeSv2ClientChannels = object<Sv2ClientChannelsResponse>({
+  client_id: number,
+  extended_channels: fallback(array(decodeExtendedChannel), []),
+  standard_channels: fallback(array(decodeStandardChannel), []),
+  offset: fallback(number, 0),
+  limit: fallback(number, 0),
+  total_extended: number,
+  total_standard: number,
+});
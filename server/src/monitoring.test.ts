@@ -0,0 +1,39 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import { test } from 'node:test';
+
+import { classifyMonitoringError, fetchStackData, getSectionFailureCount, MonitoringHttpError } from './monitoring.js';
+import { SchemaError } from './monitoring-schema.js';
+
+test('classifyMonitoringError tells timeouts, HTTP errors and bad bodies apart', () => {
+  assert.equal(classifyMonitoringError(new MonitoringHttpError('Translator /global returned HTTP 502', 502)), 'http');
+  assert.equal(classifyMonitoringError(new DOMException('The operation timed out.', 'TimeoutError')), 'timeout');
+  assert.equal(classifyMonitoringError(new SyntaxError('Unexpected token')), 'invalid-response');
+  assert.equal(classifyMonitoringError(new SchemaError('uptime_secs', 'a number')), 'invalid-response');
+  assert.equal(classifyMonitoringError(new TypeError('fetch failed')), 'unreachable');
+});
+
+test('fetchStackData keeps the sections that came back and records why the others failed', async (t) => {
+  const server = http.createServer((req, res) => {
+    if (req.url === '/api/v1/global') return res.end(JSON.stringify({ uptime_secs: 60 }));
+    if (req.url?.startsWith('/api/v1/sv1/clients')) return res.end('not json');
+    res.statusCode = 503;
+    res.end();
+  }).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const failuresBefore = getSectionFailureCount('translator');
+
+  const data = await fetchStackData('no-jd', { translator: `http://127.0.0.1:${port}/api/v1` });
+
+  assert.deepEqual(data.global, { server: null, sv1_clients: null, sv2_clients: null, uptime_secs: 60 });
+  assert.equal(data.serverChannels, null);
+  // Sections fail in whatever order the requests finish.
+  const errors = [...(data.errors ?? [])].sort((a, b) => a.section.localeCompare(b.section));
+  assert.deepEqual(errors.map((error) => [error.section, error.backend, error.errorClass]), [
+    ['serverChannels', 'translator', 'http'],
+    ['sv1Clients', 'translator', 'invalid-response'],
+  ]);
+  assert.equal(getSectionFailureCount('translator'), failuresBefore + 2);
+});
@@ -0,0 +1,283 @@
+/**
+ * Server-side client for the Translator and JDC monitoring APIs.
+ *
+ * Only the response fields sv2-ui consumes are typed here, which keeps the
+ * integration contract explicit (see docs/monitoring-api-compatibility.md).
+ * Responses are decoded into sv2-ui's v1 schema (see monitoring-schema.ts)
+ * before anything reads them.
+ */
+
+import {
+  CONTAINER_NAMES,
+  JDC_MONITORING_PORT,
+  TRANSLATOR_MONITORING_PORT,
+} from '@sv2-ui/shared';
+import type { SetupMode } from '@sv2-ui/shared';
+import {
+  decodeGlobal,
+  decodeServerChannels,
+  decodeSv1Clients,
+  decodeSv2ClientChannels,
+  decodeSv2Clients,
+  SchemaError,
+} from './monitoring-schema.js';
+import type { Decoder } from './monitoring-schema.js';
+import { getContainerUrl } from './proxy.js';
+import { getRequestIdHeaders } from './request-context.js';
+
+export type MonitoringService = 'translator' | 'jdc';
+
+export const MONITORING_SERVICES: Record<MonitoringService, {
+  label: string;
+  containerName: string;
+  port: number;
+}> = {
+  translator: {
+    label: 'Translator',
+    containerName: CONTAINER_NAMES.translator,
+    port: TRANSLATOR_MONITORING_PORT,
+  },
+  jdc: {
+    label: 'JDC',
+    containerName: CONTAINER_NAMES.jdc,
+    port: JDC_MONITORING_PORT,
+  },
+};
+
+const MONITORING_TIMEOUT_MS = 5000;
+const PAGE_LIMIT = 100;
+
+export interface GlobalInfo {
+  server?: { total_channels: number; total_hashrate: number } | null;
+  sv1_clients?: { total_clients: number; total_hashrate: number } | null;
+  sv2_clients?: { total_clients: number; total_channels: number; total_hashrate: number } | null;
+  uptime_secs: number;
+}
+
+export interface ServerChannelInfo {
+  channel_id: number;
+  user_identity: string;
+  nominal_hashrate?: number | null;
+  target_hex: string;
+  best_diff: number;
+  blocks_found: number;
+  shares_acknowledged: number;
+  shares_submitted: number;
+  shares_rejected: number;
+  shares_rejected_by_reason: Record<string, number>;
+}
+
+export interface ServerChannelsResponse {
+  extended_channels: ServerChannelInfo[];
+  standard_channels: ServerChannelInfo[];
+  total_extended: number;
+  total_standard: number;
+}
+
+export interface Sv1ClientInfo {
+  client_id: number;
+  channel_id?: number | null;
+  authorized_worker_name: string;
+  user_identity: string;
+  hashrate?: number | null;
+  target_hex: string;
+  extranonce1_hex?: string;
+}
+
+export interface Sv1ClientsResponse {
+  items: Sv1ClientInfo[];
+  total: number;
+}
+
+export interface ClientChannelInfo {
+  channel_id: number;
+  user_identity: string;
+  nominal_hashrate: number;
+  target_hex: string;
+  expected_shares_per_minute: number;
+  extranonce_prefix_hex?: string;
+  best_diff: number;
+  blocks_found: number;
+  shares_accepted: number;
+  shares_rejected: number;
+  shares_rejected_by_reason: Record<string, number>;
+}
+
+export interface Sv2ClientInfo {
+  client_id: number;
+  extended_channels: ClientChannelInfo[];
+  standard_channels: ClientChannelInfo[];
+}
+
+/**
+ * Monitoring API base URLs (ending in /api/v1) of a stack run elsewhere.
+ * Services left out resolve to the local containers.
+ */
+export type MonitoringEndpoints = Partial<Record<MonitoringService, string>>;
+
+export function getMonitoringBaseUrl(service: MonitoringService, endpoints: MonitoringEndpoints = {}): string {
+  const endpoint = endpoints[service];
+  if (endpoint) return endpoint.replace(/\/+$/, '');
+
+  const { containerName, port } = MONITORING_SERVICES[service];
+  return `${getContainerUrl(containerName, port)}/api/v1`;
+}
+
+export class MonitoringHttpError extends Error {
+  constructor(message: string, readonly status: number) {
+    super(message);
+    this.name = 'MonitoringHttpError';
+  }
+}
+
+export async function fetchMonitoringJson<T>(
+  service: MonitoringService,
+  path: string,
+  timeoutMs = MONITORING_TIMEOUT_MS,
+  endpoints: MonitoringEndpoints = {},
+): Promise<T> {
+  const response = await fetch(`${getMonitoringBaseUrl(service, endpoints)}${path}`, {
+    headers: { Accept: 'application/json', ...getRequestIdHeaders() },
+    signal: AbortSignal.timeout(timeoutMs),
+  });
+
+  if (!response.ok) {
+    throw new MonitoringHttpError(`${MONITORING_SERVICES[service].label} ${path} returned HTTP ${response.status}`, response.status);
+  }
+
+  return response.json() as Promise<T>;
+}
+
+/**
+ * Fetch a monitoring response and decode it into the v1 schema.
+ */
+export async function fetchMonitoringResponse<T>(
+  service: MonitoringService,
+  path: string,
+  decode: Decoder<T>,
+  endpoints: MonitoringEndpoints = {},
+): Promise<T> {
+  return decode(await fetchMonitoringJson<unknown>(service, path, MONITORING_TIMEOUT_MS, endpoints));
+}
+
+/**
+ * Fetch all Sv2 clients plus their channels, mirroring the dashboard.
+ */
+export async function fetchSv2Clients(
+  service: MonitoringService,
+  endpoints: MonitoringEndpoints = {},
+): Promise<Sv2ClientInfo[]> {
+  const clients = await fetchMonitoringResponse(service, `/clients?offset=0&limit=${PAGE_LIMIT}`, decodeSv2Clients, endpoints);
+
+  return Promise.all(clients.items.map(async ({ client_id }) => {
+    try {
+      const channels = await fetchMonitoringResponse(
+        service,
+        `/clients/${client_id}/channels?offset=0&limit=${PAGE_LIMIT}`,
+        decodeSv2ClientChannels,
+        endpoints,
+      );
+      return {
+        client_id,
+        extended_channels: channels.extended_channels,
+        standard_channels: channels.standard_channels,
+      };
+    } catch {
+      return { client_id, extended_channels: [], standard_channels: [] };
+    }
+  }));
+}
+
+export type StackSection = 'global' | 'serverChannels' | 'sv1Clients' | 'sv2Clients';
+
+export type StackErrorClass = 'timeout' | 'unreachable' | 'http' | 'invalid-response';
+
+export interface StackSectionError {
+  section: StackSection;
+  backend: MonitoringService;
+  errorClass: StackErrorClass;
+  message: string;
+}
+
+/**
+ * Everything the dashboard reads from the monitoring APIs for one stack.
+ * Sections are null when the service couldn't be reached.
+ */
+export interface StackData {
+  mode: SetupMode | null;
+  // JDC owns the pool connection in JD mode, the Translator otherwise.
+  upstream: MonitoringService;
+  global: GlobalInfo | null;
+  serverChannels: ServerChannelsResponse | null;
+  sv1Clients: Sv1ClientsResponse | null;
+  sv2Clients: Sv2ClientInfo[] | null;
+  // Why each null section failed; fetchStackData always fills it in.
+  errors?: StackSectionError[];
+}
+
+// The exact requests behind StackData; the dashboard issues the same ones.
+export const STACK_DATA_PATHS = {
+  global: '/global',
+  serverChannels: `/server/channels?offset=0&limit=${PAGE_LIMIT}`,
+  sv1Clients: `/sv1/clients?offset=0&limit=${PAGE_LIMIT}`,
+} as const;
+
+export function getUpstreamService(mode: SetupMode | null): MonitoringService {
+  return mode === 'jd' ? 'jdc' : 'translator';
+}
+
+// Failed sections per backend since startup, for /api/debug/runtime.
+const sectionFailures: Record<MonitoringService, number> = { translator: 0, jdc: 0 };
+
+export function getSectionFailureCount(service: MonitoringService): number {
+  return sectionFailures[service];
+}
+
+export function classifyMonitoringError(error: unknown): StackErrorClass {
+  if (error instanceof MonitoringHttpError) return 'http';
+  if (error instanceof SyntaxError || error instanceof SchemaError) return 'invalid-response';
+  const name = error instanceof Error ? error.name : '';
+  return name === 'TimeoutError' || name === 'AbortError' ? 'timeout' : 'unreachable';
+}
+
+/**
+ * Fetch every section `mode` serves. With `services`, sections from other
+ * services are left null without trying them, and without an error.
+ */
+export async function fetchStackData(
+  mode: SetupMode | null,
+  endpoints: MonitoringEndpoints = {},
+  services?: MonitoringService[],
+): Promise<StackData> {
+  const upstream = getUpstreamService(mode);
+  const errors: StackSectionError[] = [];
+
+  async function orNull<T>(section: StackSection, backend: MonitoringService, promise: Promise<T>): Promise<T | null> {
+    try {
+      return await promise;
+    } catch (error) {
+      sectionFailures[backend]++;
+      errors.push({
+        section,
+        backend,
+        errorClass: classifyMonitoringError(error),
+        message: error instanceof Error ? error.message : String(error),
+      });
+      return null;
+    }
+  }
+  const wants = (service: MonitoringService) => !services || services.includes(service);
+  const get = <T>(section: keyof typeof STACK_DATA_PATHS, service: MonitoringService, decode: Decoder<T>) =>
+    wants(service)
+      ? orNull(section, service, fetchMonitoringResponse(service, STACK_DATA_PATHS[section], decode, endpoints))
+      : Promise.resolve(null);
+
+  const [global, serverChannels, sv1Clients, sv2Clients] = await Promise.all([
+    get('global', upstream, decodeGlobal),
+    get('serverChannels', upstream, decodeServerChannels),
+    get('sv1Clients', 'translator', decodeSv1Clients),
+    mode === 'jd' && wants('jdc') ? orNull('sv2Clients', 'jdc', fetchSv2Clients('jdc', endpoints)) : Promise.resolve(null),
+  ]);
+
+  return { mode, upstream, global, serverChannels, sv1Clients, sv2Clients, errors };
+}
@@ -0,0 +1,89 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+
+import type { AlertTransition } from './alerts.js';
+import { formatAlertEmbed } from './discord.js';
+import type { StackEvent } from './events.js';
+import {
+  createMessages,
+  formatAlertText,
+  formatEventText,
+  loadMessageCatalogs,
+  parseNotificationLocales,
+} from './notification-messages.js';
+
+const alert: AlertTransition = {
+  id: 'a1',
+  ruleId: 'low',
+  kind: 'hashrate-below',
+  status: 'firing',
+  message: 'Hashrate 50.00 TH/s below 100.00 TH/s',
+  at: '2026-01-01T00:00:00.000Z',
+  threshold: 100e12,
+  value: 50e12,
+};
+
+const block: StackEvent = {
+  id: 'e1',
+  kind: 'block-found',
+  title: 'Block found',
+  detail: '1 block(s) found since the services started.',
+  at: '2026-01-01T00:00:00.000Z',
+  params: { count: 1, total: 1 },
+};
+
+test('parseNotificationLocales picks NOTIFY_LOCALE unless a channel overrides it', () => {
+  assert.deepEqual(parseNotificationLocales({}), { telegram: 'en', email: 'en', push: 'en', discord: 'en' });
+  assert.deepEqual(parseNotificationLocales({ NOTIFY_LOCALE: 'es', TELEGRAM_LOCALE: 'zh' }), {
+    telegram: 'zh',
+    email: 'es',
+    push: 'es',
+    discord: 'es',
+  });
+  assert.throws(() => parseNotificationLocales({ DISCORD_LOCALE: 'fr' }), /DISCORD_LOCALE must be one of en, es, zh, got "fr"/);
+  assert.equal(parseNotificationLocales({ PUSH_LOCALE: 'fr' }, ['en', 'fr']).push, 'fr');
+});
+
+test('messages use the locale plural rules and fall back to English per key', () => {
+  assert.equal(formatEventText(block, createMessages('es')).title, 'Bloque encontrado');
+  assert.equal(formatEventText({ ...block, params: { count: 2, total: 3 } }, createMessages('es')).title, '2 bloques encontrados');
+  assert.equal(formatEventText(block, createMessages('zh')).title, '发现 1 个区块');
+  assert.equal(
+    formatEventText({ ...block, params: { count: 1, total: 1, height: 100, hash: 'ab12' } }, createMessages('es')).detail.endsWith('Bloque 100: ab12.'),
+    true,
+  );
+  assert.equal(createMessages('fr', { en: { greeting: 'Hello {name}' }, fr: {} }).t('greeting', { name: 'Ana' }), 'Hello Ana');
+});
+
+test('alerts are localized from their values, keeping the English message when they are missing', () => {
+  assert.equal(formatAlertText(alert, createMessages('zh')), '算力 50.00 TH/s 低于 100.00 TH/s');
+  assert.equal(formatAlertText({ ...alert, value: null }, createMessages('zh')), 'Hashrate 50.00 TH/s below 100.00 TH/s');
+  assert.equal(formatAlertText({ ...alert, status: 'resolved', message: `Resolved: ${alert.message}` }), alert.message);
+
+  const update = { ...alert, kind: 'image-update' as const, threshold: undefined, value: undefined, params: { service: 'jdc', image: 'jdc_sv2:main' } };
+  assert.equal(formatAlertText(update, createMessages('es')), 'Hay una imagen más reciente de jdc disponible (jdc_sv2:main)');
+  assert.equal(formatAlertText({ ...update, status: 'resolved' }, createMessages('es')), 'jdc ejecuta la última versión de jdc_sv2:main');
+
+  const embed = formatAlertEmbed(alert, {}, createMessages('es'));
+  assert.equal(embed.title, '🚨 Alerta');
+  assert.deepEqual(embed.fields.map(({ name }) => name), ['Servicio', 'Métrica', 'Umbral', 'Valor actual']);
+});
+
+test('loadMessageCatalogs adds locales and overrides built-in strings from the directory', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-messages-'));
+  await fs.writeFile(path.join(dir, 'es.json'), JSON.stringify({ 'alert.firing': 'Alarma' }));
+  await fs.writeFile(path.join(dir, 'pt-BR.json'), JSON.stringify({ 'alert.firing': 'Alerta' }));
+
+  const catalogs = loadMessageCatalogs(dir);
+  assert.deepEqual(Object.keys(catalogs).sort(), ['en', 'es', 'pt-BR', 'zh']);
+  assert.equal(createMessages('es', catalogs).t('alert.firing'), 'Alarma');
+  assert.equal(createMessages('es', catalogs).t('alert.resolved'), 'Resuelta');
+  assert.equal(createMessages('pt-BR', catalogs).t('alert.resolved'), 'Resolved');
+  assert.deepEqual(Object.keys(loadMessageCatalogs(path.join(dir, 'missing'))), ['en', 'es', 'zh']);
+
+  await fs.writeFile(path.join(dir, 'de.json'), JSON.stringify({ 'alert.firing': 1 }));
+  assert.throws(() => loadMessageCatalogs(dir), /Invalid message catalog de\.json: "alert\.firing" must be a string/);
+});
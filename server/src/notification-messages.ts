@@ -0,0 +1,327 @@
+/**
+ * Message catalogs for notification and report text.
+ *
+ * Telegram, email, push and Discord each pick a locale: <CHANNEL>_LOCALE,
+ * else NOTIFY_LOCALE, else English. English, Spanish and Chinese are built
+ * in. `<locale>.json` files in MESSAGES_DIR (CONFIG_DIR/messages by default)
+ * add locales or replace single strings of a built-in one; anything a
+ * catalog leaves out falls back to English.
+ *
+ * Catalogs map keys to templates with `{name}` placeholders. A template for
+ * a message with a `count` may have plural variants, e.g.
+ * `event.block-found.title.one`, picked with the locale's plural rules.
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { formatHashrate } from '@sv2-ui/shared';
+import type { AlertTransition } from './alerts.js';
+import type { StackEvent } from './events.js';
+
+export type MessageCatalog = Record<string, string>;
+export type MessageParams = Record<string, string | number>;
+
+export type NotificationChannel = 'telegram' | 'email' | 'push' | 'discord';
+
+export interface Messages {
+  locale: string;
+  // The filled-in template, or null if there is none or a placeholder has no value.
+  format(key: string, params?: MessageParams): string | null;
+  t(key: string, params?: MessageParams): string;
+}
+
+export const DEFAULT_LOCALE = 'en';
+export const NOTIFICATION_CHANNELS: NotificationChannel[] = ['telegram', 'email', 'push', 'discord'];
+
+const en: MessageCatalog = {
+  'alert.firing': 'Alert',
+  'alert.resolved': 'Resolved',
+  'field.service': 'Service',
+  'field.metric': 'Metric',
+  'field.threshold': 'Threshold',
+  'field.currentValue': 'Current value',
+  'field.rule': 'Rule',
+  'field.at': 'At',
+  'metric.unreachable': 'Monitoring API',
+  'metric.hashrate-below': 'Hashrate',
+  'metric.reject-ratio-above': 'Rejected shares',
+  'metric.expression': 'Condition',
+  'metric.duplicate-shares': 'Duplicate shares',
+  'metric.shared-extranonce': 'Shared extranonce',
+  'metric.excess-share-rate': 'Share rate',
+  'metric.image-update': 'Image',
+  'value.unreachable': 'Unreachable',
+  'value.reachable': 'Reachable',
+  'alert.unreachable': 'Monitoring API unreachable',
+  'alert.hashrate-below': 'Hashrate {value} below {threshold}',
+  'alert.reject-ratio-above': 'Rejected shares {value} above {threshold}',
+  'alert.expression': 'Alert condition: {expression}',
+  'alert.duplicate-shares': '{worker}: {count} duplicate share(s) rejected. Check for two miners using the same worker.',
+  'alert.excess-share-rate': '{worker}: {rate} shares/min, expected about {expected}. More hashrate than announced is behind this channel.',
+  'alert.shared-extranonce': '{workers} share extranonce {extranonce} and will submit identical work.',
+  'alert.image-update': 'A newer {service} image is available ({image})',
+  'alert.image-update.resolved': '{service} runs the latest {image}',
+  'event.incident.title': 'Mining services unreachable',
+  'event.incident.detail': 'The monitoring API stopped responding.',
+  'event.recovered.title': 'Mining services recovered',
+  'event.recovered.detail': 'The monitoring API is reachable again.',
+  'event.block-found.title.one': 'Block found',
+  'event.block-found.title': '{count} blocks found',
+  'event.block-found.detail': '{total} block(s) found since the services started.',
+  'event.block': 'Block {height}: {hash}.',
+  'event.best-share.title': 'New best share: {difficulty}',
+  'event.best-share.detail': 'Previous best was {previous}.',
+  'summary.title': 'Daily summary',
+  'summary.status': 'Status',
+  'summary.online': 'online',
+  'summary.offline': 'offline',
+  'summary.hashrate': 'Hashrate',
+  'summary.workers': 'Workers',
+  'summary.sharesAccepted': 'Shares accepted',
+  'summary.sharesRejected': 'Shares rejected',
+  'summary.blocksFound': 'Blocks found',
+  'summary.uptime': 'Uptime',
+  'summary.recent': 'Last 24 hours:',
+  'summary.noRecent': 'No events in the last 24 hours.',
+};
+
+const es: MessageCatalog = {
+  'alert.firing': 'Alerta',
+  'alert.resolved': 'Resuelta',
+  'field.service': 'Servicio',
+  'field.metric': 'Métrica',
+  'field.threshold': 'Umbral',
+  'field.currentValue': 'Valor actual',
+  'field.rule': 'Regla',
+  'field.at': 'Hora',
+  'metric.unreachable': 'API de monitoreo',
+  'metric.hashrate-below': 'Hashrate',
+  'metric.reject-ratio-above': 'Shares rechazados',
+  'metric.expression': 'Condición',
+  'metric.duplicate-shares': 'Shares duplicados',
+  'metric.shared-extranonce': 'Extranonce compartido',
+  'metric.excess-share-rate': 'Tasa de shares',
+  'metric.image-update': 'Imagen',
+  'value.unreachable': 'Inalcanzable',
+  'value.reachable': 'Accesible',
+  'alert.unreachable': 'API de monitoreo inalcanzable',
+  'alert.hashrate-below': 'Hashrate {value} por debajo de {threshold}',
+  'alert.reject-ratio-above': 'Shares rechazados {value} por encima de {threshold}',
+  'alert.expression': 'Condición de alerta: {expression}',
+  'alert.duplicate-shares': '{worker}: {count} share(s) duplicado(s) rechazado(s). Revise si dos mineros usan el mismo worker.',
+  'alert.excess-share-rate': '{worker}: {rate} shares/min, se esperaban unos {expected}. Hay más hashrate del anunciado detrás de este canal.',
+  'alert.shared-extranonce': '{workers} comparten el extranonce {extranonce} y enviarán trabajo idéntico.',
+  'alert.image-update': 'Hay una imagen más reciente de {service} disponible ({image})',
+  'alert.image-update.resolved': '{service} ejecuta la última versión de {image}',
+  'event.incident.title': 'Servicios de minería inalcanzables',
+  'event.incident.detail': 'La API de monitoreo dejó de responder.',
+  'event.recovered.title': 'Servicios de minería recuperados',
+  'event.recovered.detail': 'La API de monitoreo vuelve a responder.',
+  'event.block-found.title.one': 'Bloque encontrado',
+  'event.block-found.title': '{count} bloques encontrados',
+  'event.block-found.detail': '{total} bloque(s) encontrado(s) desde que se iniciaron los servicios.',
+  'event.block': 'Bloque {height}: {hash}.',
+  'event.best-share.title': 'Nuevo mejor share: {difficulty}',
+  'event.best-share.detail': 'El mejor anterior era {previous}.',
+  'summary.title': 'Resumen diario',
+  'summary.status': 'Estado',
+  'summary.online': 'en línea',
+  'summary.offline': 'fuera de línea',
+  'summary.hashrate': 'Hashrate',
+  'summary.workers': 'Workers',
+  'summary.sharesAccepted': 'Shares aceptados',
+  'summary.sharesRejected': 'Shares rechazados',
+  'summary.blocksFound': 'Bloques encontrados',
+  'summary.uptime': 'Tiempo activo',
+  'summary.recent': 'Últimas 24 horas:',
+  'summary.noRecent': 'Sin eventos en las últimas 24 horas.',
+};
+
+const zh: MessageCatalog = {
+  'alert.firing': '告警',
+  'alert.resolved': '已恢复',
+  'field.service': '服务',
+  'field.metric': '指标',
+  'field.threshold': '阈值',
+  'field.currentValue': '当前值',
+  'field.rule': '规则',
+  'field.at': '时间',
+  'metric.unreachable': '监控 API',
+  'metric.hashrate-below': '算力',
+  'metric.reject-ratio-above': '拒绝份额',
+  'metric.expression': '条件',
+  'metric.duplicate-shares': '重复份额',
+  'metric.shared-extranonce': '共享 extranonce',
+  'metric.excess-share-rate': '份额速率',
+  'metric.image-update': '镜像',
+  'value.unreachable': '不可达',
+  'value.reachable': '可达',
+  'alert.unreachable': '监控 API 不可达',
+  'alert.hashrate-below': '算力 {value} 低于 {threshold}',
+  'alert.reject-ratio-above': '拒绝份额 {value} 高于 {threshold}',
+  'alert.expression': '告警条件：{expression}',
+  'alert.duplicate-shares': '{worker}：{count} 个重复份额被拒绝。请检查是否有两台矿机使用同一个矿工名。',
+  'alert.excess-share-rate': '{worker}：每分钟 {rate} 个份额，预期约 {expected} 个。该通道背后的算力高于其声明值。',
+  'alert.shared-extranonce': '{workers} 共用 extranonce {extranonce}，将提交相同的工作。',
+  'alert.image-update': '{service} 有可用的新镜像（{image}）',
+  'alert.image-update.resolved': '{service} 已运行最新的 {image}',
+  'event.incident.title': '挖矿服务不可达',
+  'event.incident.detail': '监控 API 已停止响应。',
+  'event.recovered.title': '挖矿服务已恢复',
+  'event.recovered.detail': '监控 API 已恢复响应。',
+  'event.block-found.title': '发现 {count} 个区块',
+  'event.block-found.detail': '服务启动以来共发现 {total} 个区块。',
+  'event.block': '区块 {height}：{hash}。',
+  'event.best-share.title': '新的最佳份额：{difficulty}',
+  'event.best-share.detail': '之前的最佳为 {previous}。',
+  'summary.title': '每日摘要',
+  'summary.status': '状态',
+  'summary.online': '在线',
+  'summary.offline': '离线',
+  'summary.hashrate': '算力',
+  'summary.workers': '矿工',
+  'summary.sharesAccepted': '已接受份额',
+  'summary.sharesRejected': '已拒绝份额',
+  'summary.blocksFound': '已发现区块',
+  'summary.uptime': '运行时间',
+  'summary.recent': '过去 24 小时：',
+  'summary.noRecent': '过去 24 小时内没有事件。',
+};
+
+export const BUILTIN_CATALOGS: Record<string, MessageCatalog> = { en, es, zh };
+
+const LOCALE_PATTERN = /^[a-z]{2,3}(-[A-Za-z0-9]{2,8})*$/;
+
+function getPluralCategory(locale: string, count: number): string {
+  try {
+    return new Intl.PluralRules(locale).select(count);
+  } catch {
+    return 'other';
+  }
+}
+
+function interpolate(template: string, params: MessageParams): string | null {
+  let missing = false;
+  const text = template.replace(/\{(\w+)\}/g, (_, name: string) => {
+    if (params[name] === undefined) missing = true;
+    return String(params[name]);
+  });
+  return missing ? null : text;
+}
+
+export function createMessages(locale: string = DEFAULT_LOCALE, catalogs: Record<string, MessageCatalog> = BUILTIN_CATALOGS): Messages {
+  const lookups = [locale, DEFAULT_LOCALE].map((name) => ({ name, catalog: catalogs[name] ?? {} }));
+
+  function format(key: string, params: MessageParams = {}): string | null {
+    for (const { name, catalog } of lookups) {
+      const plural = typeof params.count === 'number' ? catalog[`${key}.${getPluralCategory(name, params.count)}`] : undefined;
+      const template = plural ?? catalog[key];
+      if (template !== undefined) return interpolate(template, params);
+    }
+    return null;
+  }
+
+  return { locale, format, t: (key, params) => format(key, params) ?? key };
+}
+
+export const DEFAULT_MESSAGES = createMessages();
+
+/**
+ * The built-in catalogs plus the override files in `dir`. A missing
+ * directory just means no overrides.
+ */
+export function loadMessageCatalogs(dir: string): Record<string, MessageCatalog> {
+  const catalogs = { ...BUILTIN_CATALOGS };
+  let files: string[];
+  try {
+    files = fs.readdirSync(dir).filter((file) => file.endsWith('.json')).sort();
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === 'ENOENT') return catalogs;
+    throw error;
+  }
+
+  for (const file of files) {
+    const locale = file.slice(0, -'.json'.length);
+    if (!LOCALE_PATTERN.test(locale)) {
+      throw new Error(`Message catalog ${file} must be named after a locale, e.g. es.json or pt-BR.json`);
+    }
+    let parsed: unknown;
+    try {
+      parsed = JSON.parse(fs.readFileSync(path.join(dir, file), 'utf-8'));
+    } catch (error) {
+      throw new Error(`Invalid message catalog ${file}: ${error instanceof Error ? error.message : String(error)}`);
+    }
+    if (!parsed || typeof parsed !== 'object' || Array.isArray(parsed)) {
+      throw new Error(`Invalid message catalog ${file}: expected an object of strings`);
+    }
+    const invalid = Object.entries(parsed).find(([, value]) => typeof value !== 'string');
+    if (invalid) {
+      throw new Error(`Invalid message catalog ${file}: "${invalid[0]}" must be a string`);
+    }
+    catalogs[locale] = { ...catalogs[locale], ...(parsed as MessageCatalog) };
+  }
+  return catalogs;
+}
+
+/**
+ * The locale each notification channel uses.
+ */
+export function parseNotificationLocales(
+  env: NodeJS.ProcessEnv = process.env,
+  available: string[] = Object.keys(BUILTIN_CATALOGS),
+): Record<NotificationChannel, string> {
+  function parse(name: string, fallback: string): string {
+    const value = env[name]?.trim();
+    if (!value) return fallback;
+    if (!available.includes(value)) {
+      throw new Error(`${name} must be one of ${available.join(', ')}, got "${value}"`);
+    }
+    return value;
+  }
+
+  const fallback = parse('NOTIFY_LOCALE', DEFAULT_LOCALE);
+  return Object.fromEntries(NOTIFICATION_CHANNELS.map((channel) => [
+    channel,
+    parse(`${channel.toUpperCase()}_LOCALE`, fallback),
+  ])) as Record<NotificationChannel, string>;
+}
+
+export function formatAlertValue(kind: AlertTransition['kind'], value: number, messages: Messages = DEFAULT_MESSAGES): string {
+  switch (kind) {
+    case 'hashrate-below':
+      return formatHashrate(value);
+    case 'reject-ratio-above':
+      return `${(value * 100).toFixed(1)}%`;
+    case 'unreachable':
+      return messages.t(value === 1 ? 'value.unreachable' : 'value.reachable');
+    case 'expression':
+      return value === 1 ? 'true' : 'false';
+    default:
+      return String(value);
+  }
+}
+
+/**
+ * What an alert is about, in the catalog's language. Transitions recorded
+ * without the values a template needs keep their original English message.
+ */
+export function formatAlertText(alert: AlertTransition, messages: Messages = DEFAULT_MESSAGES): string {
+  const params: MessageParams = { ...alert.params };
+  if (alert.threshold != null) params.threshold = formatAlertValue(alert.kind, alert.threshold, messages);
+  if (alert.value != null) params.value = formatAlertValue(alert.kind, alert.value, messages);
+
+  const key = `alert.${alert.kind}`;
+  const text = (alert.status === 'resolved' ? messages.format(`${key}.resolved`, params) : null) ?? messages.format(key, params);
+  return text ?? alert.message.replace(/^Resolved: /, '');
+}
+
+export function formatEventText(event: StackEvent, messages: Messages = DEFAULT_MESSAGES): { title: string; detail: string } {
+  const params = event.params ?? {};
+  const detail = messages.format(`event.${event.kind}.detail`, params);
+  // The found block, when the node identified it (see found-blocks.ts).
+  const block = params.hash === undefined ? null : messages.format('event.block', params);
+  return {
+    title: messages.format(`event.${event.kind}.title`, params) ?? event.title,
+    detail: detail === null ? event.detail : [detail, block].filter(Boolean).join(' '),
+  };
+}
@@ -0,0 +1,99 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import { writeParquet } from './parquet.js';
+import type { ParquetColumn } from './parquet.js';
+
+type Thrift = number | Buffer | Thrift[] | Map<number, Thrift>;
+
+// Just enough of a Thrift compact protocol reader to check the metadata.
+function readThrift(buffer: Buffer, start: number) {
+  let offset = start;
+  const varint = () => {
+    let value = 0;
+    for (let scale = 1; ; scale *= 0x80) {
+      const byte = buffer[offset++];
+      value += (byte & 0x7f) * scale;
+      if (byte < 0x80) return value;
+    }
+  };
+  const unzigzag = (value: number) => (value % 2 === 0 ? value / 2 : -(value + 1) / 2);
+  const read = (type: number): Thrift => {
+    if (type === 5 || type === 6) return unzigzag(varint());
+    if (type === 8) {
+      const length = varint();
+      offset += length;
+      return buffer.subarray(offset - length, offset);
+    }
+    if (type === 9) {
+      const header = buffer[offset++];
+      const size = header >> 4 === 15 ? varint() : header >> 4;
+      return Array.from({ length: size }, () => read(header & 15));
+    }
+    const fields = new Map<number, Thrift>();
+    for (let id = 0; ;) {
+      const header = buffer[offset++];
+      if (header === 0) return fields;
+      id = header >> 4 ? id + (header >> 4) : unzigzag(varint());
+      fields.set(id, read(header & 15));
+    }
+  };
+  return { value: read(12) as Map<number, Thrift>, end: () => offset };
+}
+
+const COLUMNS: ParquetColumn[] = [
+  { name: 'timestamp', type: 'timestamp' },
+  { name: 'key', type: 'string' },
+  { name: 'value', type: 'double', optional: true },
+];
+
+test('writeParquet writes row groups and a footer describing them', () => {
+  const rows = [
+    [1_767_225_600_000, 'pool', 41.5],
+    [1_767_225_610_000, 'pool', null],
+    [1_767_225_620_000, 'jds', 12],
+  ];
+  const file = Buffer.concat([...writeParquet(COLUMNS, rows, { rowGroupSize: 2 })]);
+  assert.equal(file.subarray(0, 4).toString(), 'PAR1');
+  assert.equal(file.subarray(-4).toString(), 'PAR1');
+
+  const footerLength = file.readUInt32LE(file.length - 8);
+  const metadata = readThrift(file, file.length - 8 - footerLength).value;
+  assert.equal(metadata.get(1), 1);
+  assert.equal(metadata.get(3), 3);
+  const schema = metadata.get(2) as Map<number, Thrift>[];
+  assert.deepEqual(schema.map((element) => String(element.get(4))), ['schema', 'timestamp', 'key', 'value']);
+  assert.equal(schema[0].get(5), 3);
+  assert.equal(schema[1].get(6), 9);
+  assert.equal(schema[3].get(3), 1);
+
+  const rowGroups = metadata.get(4) as Map<number, Thrift>[];
+  assert.deepEqual(rowGroups.map((group) => group.get(3)), [2, 1]);
+
+  // The value column of the first row group: definition levels, then the one present value.
+  const chunk = ((rowGroups[0].get(1) as Map<number, Thrift>[])[2]).get(3) as Map<number, Thrift>;
+  assert.equal(chunk.get(5), 2);
+  const page = readThrift(file, chunk.get(9) as number);
+  const pageHeader = page.value;
+  assert.equal((pageHeader.get(5) as Map<number, Thrift>).get(1), 2);
+  const data = file.subarray(page.end(), page.end() + (pageHeader.get(3) as number));
+  assert.equal(data.readUInt32LE(0), 2);
+  assert.deepEqual([...data.subarray(4, 6)], [0b11, 0b01]);
+  assert.equal(data.readDoubleLE(6), 41.5);
+  assert.equal(data.length, 14);
+
+  // The key column is plain length-prefixed strings.
+  const keys = ((rowGroups[1].get(1) as Map<number, Thrift>[])[1]).get(3) as Map<number, Thrift>;
+  const keyPage = readThrift(file, keys.get(9) as number);
+  const keyData = file.subarray(keyPage.end(), keyPage.end() + (keyPage.value.get(3) as number));
+  assert.equal(keyData.readUInt32LE(0), 3);
+  assert.equal(keyData.subarray(4).toString(), 'jds');
+});
+
+test('writeParquet rejects nulls in required columns and writes empty files', () => {
+  assert.throws(() => [...writeParquet(COLUMNS, [[null, 'pool', 1]])], /"timestamp" is required/);
+  const file = Buffer.concat([...writeParquet(COLUMNS, [])]);
+  const metadata = readThrift(file, file.length - 8 - file.readUInt32LE(file.length - 8)).value;
+  assert.equal(metadata.get(3), 0);
+  assert.deepEqual(metadata.get(4), []);
+});
@@ -0,0 +1,250 @@
+/**
+ * A minimal Parquet writer for flat tables, enough for the history export
+ * (see history-export.ts) to be read by DuckDB, pandas or pyarrow.
+ *
+ * Every column chunk is a single uncompressed v1 data page in PLAIN
+ * encoding; optional columns carry RLE/bit-packed definition levels. The
+ * file metadata is Thrift compact protocol, written by hand like the
+ * protobuf in grpc.ts.
+ *
+ * Rows are written a row group at a time, so a file never has to be held in
+ * memory whole.
+ */
+
+const MAGIC = Buffer.from('PAR1');
+const DEFAULT_ROW_GROUP_SIZE = 65_536;
+
+// parquet.thrift enums.
+const PHYSICAL_TYPE = { INT64: 2, DOUBLE: 5, BYTE_ARRAY: 6 } as const;
+const CONVERTED_TYPE = { UTF8: 0, TIMESTAMP_MILLIS: 9 } as const;
+const REPETITION = { REQUIRED: 0, OPTIONAL: 1 } as const;
+const ENCODING = { PLAIN: 0, RLE: 3 } as const;
+const PAGE_TYPE_DATA = 0;
+const CODEC_UNCOMPRESSED = 0;
+
+export type ParquetColumnType = 'timestamp' | 'string' | 'double';
+
+export interface ParquetColumn {
+  name: string;
+  // timestamp values are milliseconds since the epoch (UTC).
+  type: ParquetColumnType;
+  optional?: boolean;
+}
+
+export type ParquetValue = number | string | null;
+
+// Thrift compact protocol.
+
+type ThriftValue =
+  | { kind: 'i32' | 'i64'; value: number }
+  | { kind: 'binary'; value: string }
+  | { kind: 'list'; element: 'i32' | 'binary' | 'struct'; items: ThriftValue[] }
+  | { kind: 'struct'; fields: Record<number, ThriftValue | undefined> };
+
+const COMPACT_TYPE = { i32: 5, i64: 6, binary: 8, list: 9, struct: 12 } as const;
+
+const i32 = (value: number): ThriftValue => ({ kind: 'i32', value });
+const i64 = (value: number): ThriftValue => ({ kind: 'i64', value });
+const binary = (value: string): ThriftValue => ({ kind: 'binary', value });
+const list = (element: 'i32' | 'binary' | 'struct', items: ThriftValue[]): ThriftValue => ({ kind: 'list', element, items });
+const struct = (fields: Record<number, ThriftValue | undefined>): ThriftValue => ({ kind: 'struct', fields });
+
+function writeVarint(out: number[], value: number): void {
+  // Arithmetic rather than bit operations, so values past 2^31 survive.
+  while (value >= 0x80) {
+    out.push((value % 0x80) | 0x80);
+    value = Math.floor(value / 0x80);
+  }
+  out.push(value);
+}
+
+const zigzag = (value: number) => (value >= 0 ? value * 2 : -value * 2 - 1);
+
+function writeThrift(out: number[], value: ThriftValue): void {
+  switch (value.kind) {
+    case 'i32':
+    case 'i64':
+      writeVarint(out, zigzag(value.value));
+      return;
+    case 'binary': {
+      const bytes = Buffer.from(value.value, 'utf8');
+      writeVarint(out, bytes.length);
+      out.push(...bytes);
+      return;
+    }
+    case 'list': {
+      const size = value.items.length;
+      const element = COMPACT_TYPE[value.element];
+      if (size < 15) out.push((size << 4) | element);
+      else {
+        out.push(0xf0 | element);
+        writeVarint(out, size);
+      }
+      for (const item of value.items) writeThrift(out, item);
+      return;
+    }
+    case 'struct': {
+      let lastId = 0;
+      for (const [key, field] of Object.entries(value.fields)) {
+        if (field === undefined) continue;
+        const id = Number(key);
+        const type = COMPACT_TYPE[field.kind];
+        if (id > lastId && id - lastId <= 15) out.push(((id - lastId) << 4) | type);
+        else {
+          out.push(type);
+          writeVarint(out, zigzag(id));
+        }
+        writeThrift(out, field);
+        lastId = id;
+      }
+      out.push(0);
+    }
+  }
+}
+
+function encodeThrift(value: ThriftValue): Buffer {
+  const out: number[] = [];
+  writeThrift(out, value);
+  return Buffer.from(out);
+}
+
+// Column data.
+
+function physicalType(type: ParquetColumnType): number {
+  if (type === 'timestamp') return PHYSICAL_TYPE.INT64;
+  return type === 'string' ? PHYSICAL_TYPE.BYTE_ARRAY : PHYSICAL_TYPE.DOUBLE;
+}
+
+function encodePlain(type: ParquetColumnType, values: Array<number | string>): Buffer {
+  if (type === 'string') {
+    return Buffer.concat(values.flatMap((value) => {
+      const bytes = Buffer.from(String(value), 'utf8');
+      const length = Buffer.alloc(4);
+      length.writeUInt32LE(bytes.length);
+      return [length, bytes];
+    }));
+  }
+  const buffer = Buffer.alloc(values.length * 8);
+  values.forEach((value, index) => {
+    if (type === 'timestamp') buffer.writeBigInt64LE(BigInt(Math.trunc(Number(value))), index * 8);
+    else buffer.writeDoubleLE(Number(value), index * 8);
+  });
+  return buffer;
+}
+
+/**
+ * Definition levels (1 for a value, 0 for null) as a single bit-packed run,
+ * prefixed with its length as a v1 data page expects.
+ */
+function encodeDefinitionLevels(values: ParquetValue[]): Buffer {
+  const groups = Math.ceil(values.length / 8);
+  const header: number[] = [];
+  writeVarint(header, groups * 2 + 1);
+  const bits = Buffer.alloc(groups);
+  values.forEach((value, index) => {
+    if (value !== null) bits[index >> 3] |= 1 << (index & 7);
+  });
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(header.length + groups);
+  return Buffer.concat([length, Buffer.from(header), bits]);
+}
+
+function encodeColumnChunk(column: ParquetColumn, values: ParquetValue[], offset: number) {
+  const present = values.filter((value): value is number | string => value !== null);
+  if (!column.optional && present.length !== values.length) {
+    throw new Error(`Parquet column "${column.name}" is required but has null values`);
+  }
+  const page = Buffer.concat([
+    ...(column.optional ? [encodeDefinitionLevels(values)] : []),
+    encodePlain(column.type, present),
+  ]);
+  const header = encodeThrift(struct({
+    1: i32(PAGE_TYPE_DATA),
+    2: i32(page.length),
+    3: i32(page.length),
+    5: struct({
+      1: i32(values.length),
+      2: i32(ENCODING.PLAIN),
+      3: i32(ENCODING.RLE),
+      4: i32(ENCODING.RLE),
+    }),
+  }));
+  const size = header.length + page.length;
+  const metadata = struct({
+    2: i64(offset),
+    3: struct({
+      1: i32(physicalType(column.type)),
+      2: list('i32', [i32(ENCODING.PLAIN), i32(ENCODING.RLE)]),
+      3: list('binary', [binary(column.name)]),
+      4: i32(CODEC_UNCOMPRESSED),
+      5: i64(values.length),
+      6: i64(size),
+      7: i64(size),
+      9: i64(offset),
+    }),
+  });
+  return { bytes: Buffer.concat([header, page]), metadata };
+}
+
+function schemaElement(column: ParquetColumn): ThriftValue {
+  const convertedType = column.type === 'timestamp'
+    ? CONVERTED_TYPE.TIMESTAMP_MILLIS
+    : column.type === 'string' ? CONVERTED_TYPE.UTF8 : undefined;
+  return struct({
+    1: i32(physicalType(column.type)),
+    3: i32(column.optional ? REPETITION.OPTIONAL : REPETITION.REQUIRED),
+    4: binary(column.name),
+    6: convertedType === undefined ? undefined : i32(convertedType),
+  });
+}
+
+/**
+ * A Parquet file as a sequence of buffers: the magic number, one buffer per
+ * row group and the footer. Each row has one value per column, in order.
+ */
+export function* writeParquet(
+  columns: ParquetColumn[],
+  rows: Iterable<ParquetValue[]>,
+  { rowGroupSize = DEFAULT_ROW_GROUP_SIZE }: { rowGroupSize?: number } = {},
+): Generator<Buffer> {
+  let offset = MAGIC.length;
+  let numRows = 0;
+  const rowGroups: ThriftValue[] = [];
+  yield MAGIC;
+
+  let pending: ParquetValue[][] = [];
+  function* flush(): Generator<Buffer> {
+    if (pending.length === 0) return;
+    const chunks: ThriftValue[] = [];
+    const buffers: Buffer[] = [];
+    let byteSize = 0;
+    columns.forEach((column, index) => {
+      const chunk = encodeColumnChunk(column, pending.map((row) => row[index] ?? null), offset + byteSize);
+      chunks.push(chunk.metadata);
+      buffers.push(chunk.bytes);
+      byteSize += chunk.bytes.length;
+    });
+    rowGroups.push(struct({ 1: list('struct', chunks), 2: i64(byteSize), 3: i64(pending.length) }));
+    numRows += pending.length;
+    offset += byteSize;
+    pending = [];
+    yield Buffer.concat(buffers);
+  }
+
+  for (const row of rows) {
+    pending.push(row);
+    if (pending.length >= rowGroupSize) yield* flush();
+  }
+  yield* flush();
+
+  const footer = encodeThrift(struct({
+    1: i32(1),
+    2: list('struct', [struct({ 4: binary('schema'), 5: i32(columns.length) }), ...columns.map(schemaElement)]),
+    3: i64(numRows),
+    4: list('struct', rowGroups),
+    6: binary('sv2-ui'),
+  }));
+  const length = Buffer.alloc(4);
+  length.writeUInt32LE(footer.length);
+  yield Buffer.concat([footer, length, MAGIC]);
+}
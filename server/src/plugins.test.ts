@@ -0,0 +1,131 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import type { HistorySample } from './history.js';
+import { compilePlugin, createPluginHost, MAX_MEMORY_PAGES, parsePluginManifest, parsePluginOutput, runPlugin } from './plugins.js';
+import type { LoadedPlugin } from './plugins.js';
+
+const uleb = (value: number): number[] => {
+  const bytes: number[] = [];
+  do {
+    const byte = value & 0x7f;
+    value >>>= 7;
+    bytes.push(value ? byte | 0x80 : byte);
+  } while (value);
+  return bytes;
+};
+
+const sleb = (value: bigint): number[] => {
+  const bytes: number[] = [];
+  for (;;) {
+    const byte = Number(value & 0x7fn);
+    value >>= 7n;
+    if ((value === 0n && !(byte & 0x40)) || (value === -1n && byte & 0x40)) return [...bytes, byte];
+    bytes.push(byte | 0x80);
+  }
+};
+
+const section = (id: number, ...items: number[][]) => {
+  const body = [...uleb(items.length), ...items.flat()];
+  return [id, ...uleb(body.length), ...body];
+};
+const name = (value: string) => [...uleb(value.length), ...Buffer.from(value)];
+const body = (...code: number[]) => [...uleb(code.length + 1), 0x00, ...code];
+
+/**
+ * A plugin whose normalize returns `output`, whatever its input, or loops
+ * forever when `output` is null. Its memory can grow to `maxPages`, with no
+ * maximum when that is null.
+ */
+function buildPlugin(output: string | null, maxPages: number | null = 1): Uint8Array {
+  const data = Buffer.from(output ?? '');
+  const offset = 16n;
+  const normalize = output === null
+    // loop br 0 end, i64.const 0
+    ? body(0x03, 0x40, 0x0c, 0x00, 0x0b, 0x42, 0x00, 0x0b)
+    : body(0x42, ...sleb((offset << 32n) | BigInt(data.length)), 0x0b);
+  return new Uint8Array([
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    ...section(1, [0x60, 0x01, 0x7f, 0x01, 0x7f], [0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7e]),
+    ...section(3, [0x00], [0x01]),
+    ...section(5, maxPages === null ? [0x00, 0x01] : [0x01, 0x01, ...uleb(maxPages)]),
+    ...section(7, [...name('memory'), 0x02, 0x00], [...name('alloc'), 0x00, 0x00], [...name('normalize'), 0x00, 0x01]),
+    // alloc always hands out 1024, past the output.
+    ...section(10, body(0x41, 0x80, 0x08, 0x0b), normalize),
+    ...section(11, [0x00, 0x41, ...sleb(offset), 0x0b, ...uleb(data.length), ...data]),
+  ]);
+}
+
+test('validates manifests and module output', () => {
+  const manifest = { name: 'Cooling', module: 'weather.wasm', source: 'https://example.com/weather' };
+  assert.deepEqual(parsePluginManifest('weather', manifest, '/plugins'), {
+    id: 'weather',
+    name: 'Cooling',
+    module: '/plugins/weather.wasm',
+    source: 'https://example.com/weather',
+    intervalMs: 60_000,
+    headers: {},
+  });
+  assert.throws(() => parsePluginManifest('Weather!', manifest, '/plugins'), /plugin id/);
+  assert.throws(() => parsePluginManifest('weather', { ...manifest, module: '../weather.wasm' }, '/plugins'), /inside the plugins directory/);
+  assert.throws(() => parsePluginManifest('weather', { ...manifest, source: 'file:///etc/passwd' }, '/plugins'), /http or https/);
+  assert.throws(() => parsePluginManifest('weather', { ...manifest, intervalSecs: 1 }, '/plugins'), /between 5 and 86400/);
+
+  assert.deepEqual(parsePluginOutput('{"metrics":[{"name":"outside_temp","value":31.5,"unit":"°C"},{"name":"humidity","value":null}]}'), [
+    { name: 'outside_temp', value: 31.5, unit: '°C' },
+    { name: 'humidity', value: null, unit: null },
+  ]);
+  assert.throws(() => parsePluginOutput('not json'), /not JSON/);
+  assert.throws(() => parsePluginOutput('{"metrics":[{"name":"a b","value":1}]}'), /metrics\[0\]\.name/);
+  assert.throws(() => parsePluginOutput('{"metrics":[{"name":"a","value":"1"}]}'), /metrics\[0\]\.value/);
+});
+
+test('runs normalize in a worker and gives up on one that never returns', async () => {
+  const output = '{"metrics":[{"name":"outside_temp","value":31.5}]}';
+  const module = await compilePlugin(buildPlugin(output));
+  assert.deepEqual(await runPlugin(module, Buffer.from('{"temp":31.5}')), { output, logs: [] });
+
+  const stuck = await compilePlugin(buildPlugin(null));
+  await assert.rejects(runPlugin(stuck, Buffer.from('{}'), 200), /longer than 200ms/);
+});
+
+test('rejects modules whose memory can grow past the page limit', async () => {
+  const output = '{"metrics":[]}';
+  const capped = await compilePlugin(buildPlugin(output, MAX_MEMORY_PAGES));
+  assert.deepEqual(await runPlugin(capped, Buffer.from('{}')), { output, logs: [] });
+
+  for (const maxPages of [null, MAX_MEMORY_PAGES + 1]) {
+    const module = await compilePlugin(buildPlugin(output, maxPages));
+    await assert.rejects(runPlugin(module, Buffer.from('{}')), /memory must declare a maximum of at most 256 pages/);
+  }
+});
+
+test('records metrics as history samples and keeps the last ones when a run fails', async () => {
+  const recorded: HistorySample[] = [];
+  let source = '';
+  const plugin: LoadedPlugin = {
+    manifest: parsePluginManifest('weather', { name: 'Cooling', module: 'weather.wasm', source: 'https://example.com/weather' }, '/plugins'),
+    module: {} as WebAssembly.Module,
+  };
+  const host = createPluginHost([plugin], {
+    history: { record: (samples) => void recorded.push(...samples) },
+    fetchSource: async () => Buffer.from(source),
+    run: async (_module, input) => ({ output: Buffer.from(input).toString(), logs: [] }),
+    now: () => new Date('2026-01-01T00:00:00.000Z'),
+  });
+
+  source = '{"metrics":[{"name":"outside_temp","value":31.5,"unit":"°C"}]}';
+  await host.run('weather');
+  source = '{"metrics":"none"}';
+  const failed = await host.run('weather');
+
+  assert.deepEqual(recorded, [{ metric: 'plugin.weather', key: 'outside_temp', at: '2026-01-01T00:00:00.000Z', value: 31.5 }]);
+  assert.deepEqual(failed, {
+    id: 'weather',
+    name: 'Cooling',
+    intervalSecs: 60,
+    metrics: [{ name: 'outside_temp', value: 31.5, unit: '°C' }],
+    updatedAt: '2026-01-01T00:00:00.000Z',
+    error: 'output must have a metrics array',
+  });
+  assert.equal(await host.run('missing'), null);
+});
@@ -0,0 +1,333 @@
+/**
+ * WebAssembly plugins for data sources sv2-ui doesn't know about, such as
+ * a custom firmware API or the weather where the miners are cooled.
+ *
+ * Each plugin is a manifest in PLUGINS_DIR (CONFIG_DIR/plugins by default),
+ * <id>.json, next to its module:
+ *
+ *   { "name": "Cooling", "module": "weather.wasm", "source": "https://…", "intervalSecs": 300 }
+ *
+ * Every intervalSecs the server fetches `source` and hands the response body
+ * to the module, which turns it into metrics. The module never gets network
+ * or file access itself. It exports `memory`, `alloc(len: i32) -> i32` for
+ * the server to copy the body into, and
+ *
+ *   normalize(ptr: i32, len: i32) -> i64
+ *
+ * returning where its output is in memory, as (ptr << 32) | len. The output
+ * is UTF-8 JSON:
+ *
+ *   { "metrics": [{ "name": "outside_temp", "value": 31.5, "unit": "°C" }] }
+ *
+ * Modules run in a worker thread, which is terminated when a run takes
+ * longer than 5 seconds, so a plugin stuck in a loop can't stall the
+ * server. The module's memory must declare a maximum of at most
+ * MAX_MEMORY_PAGES (16 MiB), since the worker's heap limit doesn't cover
+ * linear memory. Metrics go to the history database as plugin.<id>, keyed by
+ * metric name.
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import { Worker } from 'worker_threads';
+import type { HistorySample, HistoryStore } from './history.js';
+
+export interface PluginManifest {
+  id: string;
+  name: string;
+  // Absolute path of the .wasm file.
+  module: string;
+  source: string;
+  intervalMs: number;
+  headers: Record<string, string>;
+}
+
+export interface LoadedPlugin {
+  manifest: PluginManifest;
+  module: WebAssembly.Module;
+}
+
+export interface PluginMetric {
+  name: string;
+  value: number | null;
+  unit: string | null;
+}
+
+export interface PluginStatus {
+  id: string;
+  name: string;
+  intervalSecs: number;
+  metrics: PluginMetric[];
+  // When the metrics were last normalized; null before the first run.
+  updatedAt: string | null;
+  // Why the last run failed; its metrics are from the run before.
+  error: string | null;
+}
+
+export interface PluginLoadError {
+  file: string;
+  error: string;
+}
+
+export const PLUGIN_TIMEOUT_MS = 5_000;
+const PLUGIN_ID = /^[a-z0-9][a-z0-9_-]{0,31}$/;
+const METRIC_NAME = /^[A-Za-z0-9_.:-]{1,64}$/;
+const DEFAULT_INTERVAL_SECS = 60;
+const MIN_INTERVAL_SECS = 5;
+const MAX_INTERVAL_SECS = 86_400;
+const MAX_METRICS = 100;
+const MAX_SOURCE_BYTES = 1024 * 1024;
+// 16 MiB of linear memory, which the worker's heap limit doesn't cover.
+export const MAX_MEMORY_PAGES = 256;
+const SOURCE_TIMEOUT_MS = 10_000;
+
+export function getPluginMetric(id: string): string {
+  return `plugin.${id}`;
+}
+
+export function resolvePluginsDir(configDir: string, env: NodeJS.ProcessEnv = process.env): string {
+  const dir = env.PLUGINS_DIR?.trim();
+  return dir ? path.resolve(dir) : path.join(configDir, 'plugins');
+}
+
+/**
+ * Validate the manifest in `dir`/<id>.json.
+ */
+export function parsePluginManifest(id: string, value: unknown, dir: string): PluginManifest {
+  if (!PLUGIN_ID.test(id)) throw new Error(`plugin id "${id}" must be lowercase letters, digits, - and _`);
+  if (typeof value !== 'object' || value === null || Array.isArray(value)) throw new Error('manifest must be a JSON object');
+  const { name, module, source, intervalSecs = DEFAULT_INTERVAL_SECS, headers = {} } = value as Record<string, unknown>;
+
+  if (typeof name !== 'string' || !name.trim()) throw new Error('name is required');
+  if (typeof module !== 'string' || !module.endsWith('.wasm')) throw new Error('module must be the path of a .wasm file');
+  const modulePath = path.resolve(dir, module);
+  if (path.relative(dir, modulePath).startsWith('..')) throw new Error('module must be inside the plugins directory');
+
+  let url: URL;
+  try {
+    url = new URL(String(source));
+  } catch {
+    throw new Error('source must be a URL');
+  }
+  if (url.protocol !== 'http:' && url.protocol !== 'https:') throw new Error('source must be an http or https URL');
+
+  if (typeof intervalSecs !== 'number' || !Number.isInteger(intervalSecs) || intervalSecs < MIN_INTERVAL_SECS || intervalSecs > MAX_INTERVAL_SECS) {
+    throw new Error(`intervalSecs must be an integer between ${MIN_INTERVAL_SECS} and ${MAX_INTERVAL_SECS}`);
+  }
+  if (typeof headers !== 'object' || headers === null || Object.values(headers).some((header) => typeof header !== 'string')) {
+    throw new Error('headers must map header names to strings');
+  }
+
+  return { id, name: name.trim(), module: modulePath, source: url.toString(), intervalMs: intervalSecs * 1000, headers: headers as Record<string, string> };
+}
+
+/**
+ * Compile the module and check it exports what a plugin must.
+ */
+export async function compilePlugin(bytes: Uint8Array): Promise<WebAssembly.Module> {
+  const module = await WebAssembly.compile(bytes);
+  const exports = new Map(WebAssembly.Module.exports(module).map((entry) => [entry.name, entry.kind]));
+  for (const [name, kind] of [['memory', 'memory'], ['alloc', 'function'], ['normalize', 'function']] as const) {
+    if (exports.get(name) !== kind) throw new Error(`module must export ${kind} "${name}"`);
+  }
+  const imports = WebAssembly.Module.imports(module).filter((entry) => !(entry.module === 'env' && entry.name === 'log'));
+  if (imports.length > 0) {
+    throw new Error(`module imports ${imports[0].module}.${imports[0].name}; plugins may only import env.log`);
+  }
+  return module;
+}
+
+/**
+ * Every plugin in `dir`. One that doesn't load is reported and skipped,
+ * so it can't keep the server from starting.
+ */
+export async function loadPlugins(dir: string): Promise<{ plugins: LoadedPlugin[]; errors: PluginLoadError[] }> {
+  let files: string[];
+  try {
+    files = (await fs.readdir(dir)).filter((file) => file.endsWith('.json')).sort();
+  } catch {
+    return { plugins: [], errors: [] };
+  }
+
+  const plugins: LoadedPlugin[] = [];
+  const errors: PluginLoadError[] = [];
+  for (const file of files) {
+    try {
+      const manifest = parsePluginManifest(path.basename(file, '.json'), JSON.parse(await fs.readFile(path.join(dir, file), 'utf-8')), dir);
+      plugins.push({ manifest, module: await compilePlugin(await fs.readFile(manifest.module)) });
+    } catch (error) {
+      errors.push({ file, error: error instanceof Error ? error.message : String(error) });
+    }
+  }
+  return { plugins, errors };
+}
+
+export function parsePluginOutput(output: string): PluginMetric[] {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(output);
+  } catch {
+    throw new Error('output is not JSON');
+  }
+  const metrics = (parsed as { metrics?: unknown } | null)?.metrics;
+  if (!Array.isArray(metrics)) throw new Error('output must have a metrics array');
+  if (metrics.length > MAX_METRICS) throw new Error(`output has more than ${MAX_METRICS} metrics`);
+
+  return metrics.map((metric, index) => {
+    const { name, value, unit = null } = (metric ?? {}) as Record<string, unknown>;
+    if (typeof name !== 'string' || !METRIC_NAME.test(name)) throw new Error(`metrics[${index}].name must match ${METRIC_NAME}`);
+    if (value !== null && (typeof value !== 'number' || !Number.isFinite(value))) throw new Error(`metrics[${index}].value must be a number or null`);
+    if (unit !== null && (typeof unit !== 'string' || unit.length > 16)) throw new Error(`metrics[${index}].unit must be a short string`);
+    return { name, value, unit };
+  });
+}
+
+// Runs in the worker: instantiate, check the memory's maximum, copy the input
+// in, normalize, copy the output out.
+const WORKER_SOURCE = `
+const { parentPort, workerData } = require('node:worker_threads');
+const { module, input } = workerData;
+const MAX_PAGES = ${MAX_MEMORY_PAGES};
+const logs = [];
+let memory;
+const read = (ptr, len) => new TextDecoder().decode(new Uint8Array(memory.buffer, ptr, len));
+const checkSize = () => {
+  if (memory.buffer.byteLength > MAX_PAGES * 65536) throw new Error('memory grew past ' + MAX_PAGES + ' pages');
+};
+const instance = new WebAssembly.Instance(module, { env: { log: (ptr, len) => logs.push(read(ptr, len)) } });
+memory = instance.exports.memory;
+checkSize();
+// A Memory doesn't tell its maximum, but can't grow past it, so one that
+// grows to MAX_PAGES + 1 has no maximum or too high a one.
+let capped = false;
+try {
+  memory.grow(MAX_PAGES + 1 - memory.buffer.byteLength / 65536);
+} catch {
+  capped = true;
+}
+if (!capped) throw new Error('memory must declare a maximum of at most ' + MAX_PAGES + ' pages');
+const ptr = instance.exports.alloc(input.length);
+checkSize();
+new Uint8Array(memory.buffer, ptr, input.length).set(input);
+const packed = BigInt.asUintN(64, BigInt(instance.exports.normalize(ptr, input.length)));
+checkSize();
+parentPort.postMessage({ output: read(Number(packed >> 32n), Number(packed & 0xffffffffn)), logs });
+`;
+
+/**
+ * Run a module's normalize on `input` in a worker thread. Resolves to the
+ * output and whatever the module logged.
+ */
+export function runPlugin(
+  module: WebAssembly.Module,
+  input: Uint8Array,
+  timeoutMs = PLUGIN_TIMEOUT_MS,
+): Promise<{ output: string; logs: string[] }> {
+  return new Promise((resolve, reject) => {
+    const worker = new Worker(WORKER_SOURCE, {
+      eval: true,
+      workerData: { module, input },
+      resourceLimits: { maxOldGenerationSizeMb: 32 },
+    });
+    const timer = setTimeout(() => {
+      reject(new Error(`normalize took longer than ${timeoutMs}ms`));
+      void worker.terminate();
+    }, timeoutMs);
+    worker.once('message', (result: { output: string; logs: string[] }) => {
+      clearTimeout(timer);
+      resolve(result);
+      void worker.terminate();
+    });
+    worker.once('error', (error) => {
+      clearTimeout(timer);
+      reject(error);
+    });
+    worker.once('exit', () => {
+      clearTimeout(timer);
+      reject(new Error('plugin worker exited without output'));
+    });
+  });
+}
+
+export async function fetchPluginSource(manifest: PluginManifest): Promise<Uint8Array> {
+  const response = await fetch(manifest.source, { headers: manifest.headers, signal: AbortSignal.timeout(SOURCE_TIMEOUT_MS) });
+  if (!response.ok) throw new Error(`source returned HTTP ${response.status}`);
+  const body = new Uint8Array(await response.arrayBuffer());
+  if (body.length > MAX_SOURCE_BYTES) throw new Error(`source returned more than ${MAX_SOURCE_BYTES} bytes`);
+  return body;
+}
+
+export interface PluginHostDeps {
+  history: Pick<HistoryStore, 'record'>;
+  fetchSource?: (manifest: PluginManifest) => Promise<Uint8Array>;
+  run?: (module: WebAssembly.Module, input: Uint8Array) => Promise<{ output: string; logs: string[] }>;
+  onLog?: (plugin: PluginManifest, line: string) => void;
+  now?: () => Date;
+}
+
+export function createPluginHost(plugins: LoadedPlugin[], deps: PluginHostDeps) {
+  const fetchSource = deps.fetchSource ?? fetchPluginSource;
+  const run = deps.run ?? runPlugin;
+  const now = deps.now ?? (() => new Date());
+  const statuses = new Map<string, PluginStatus>(plugins.map(({ manifest }) => [manifest.id, {
+    id: manifest.id,
+    name: manifest.name,
+    intervalSecs: manifest.intervalMs / 1000,
+    metrics: [],
+    updatedAt: null,
+    error: null,
+  }]));
+  const timers: NodeJS.Timeout[] = [];
+  let inFlight = 0;
+
+  /**
+   * Fetch one plugin's source, normalize it and store the metrics.
+   */
+  async function runOnce({ manifest, module }: LoadedPlugin): Promise<PluginStatus> {
+    const status = statuses.get(manifest.id)!;
+    inFlight += 1;
+    try {
+      const { output, logs } = await run(module, await fetchSource(manifest));
+      for (const line of logs) deps.onLog?.(manifest, line);
+      const metrics = parsePluginOutput(output);
+      const at = now().toISOString();
+      const samples: HistorySample[] = metrics.map((metric) => ({ metric: getPluginMetric(manifest.id), key: metric.name, at, value: metric.value }));
+      deps.history.record(samples);
+      Object.assign(status, { metrics, updatedAt: at, error: null });
+    } catch (error) {
+      status.error = error instanceof Error ? error.message : String(error);
+    } finally {
+      inFlight -= 1;
+    }
+    return { ...status };
+  }
+
+  function start(): void {
+    for (const plugin of plugins) {
+      void runOnce(plugin);
+      timers.push(setInterval(() => void runOnce(plugin), plugin.manifest.intervalMs));
+    }
+  }
+
+  function stop(): void {
+    for (const timer of timers.splice(0)) clearInterval(timer);
+  }
+
+  function list(): PluginStatus[] {
+    return [...statuses.values()].map((status) => ({ ...status }));
+  }
+
+  return {
+    run: (id: string) => {
+      const plugin = plugins.find(({ manifest }) => manifest.id === id);
+      return plugin ? runOnce(plugin) : Promise.resolve(null);
+    },
+    start,
+    stop,
+    list,
+    get: (id: string) => (statuses.has(id) ? { ...statuses.get(id)! } : null),
+    inFlight: () => inFlight,
+  };
+}
+
+export type PluginHost = ReturnType<typeof createPluginHost>;
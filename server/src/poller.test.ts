@@ -0,0 +1,211 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import type { StackData } from './monitoring.js';
+import {
+  createStackPoller,
+  createStackSnapshot,
+  getCachedMonitoringResponse,
+  getJitteredDelayMs,
+  getStackFreshness,
+  parseBitcoinPollIntervalMs,
+  parsePollIntervalMs,
+  parsePollIntervals,
+  parsePollJitter,
+} from './poller.js';
+
+function createStackData(mode: StackData['mode'] = 'no-jd'): StackData {
+  return {
+    mode,
+    upstream: mode === 'jd' ? 'jdc' : 'translator',
+    global: { uptime_secs: 60 } as StackData['global'],
+    serverChannels: null,
+    sv1Clients: { items: [], total: 0 },
+    sv2Clients: null,
+  };
+}
+
+test('parsePollIntervalMs defaults to 10 seconds and rejects out-of-range values', () => {
+  assert.equal(parsePollIntervalMs(undefined), 10_000);
+  assert.equal(parsePollIntervalMs('2'), 2_000);
+  assert.throws(() => parsePollIntervalMs('0'), /POLL_INTERVAL_SECS must be between/);
+  assert.throws(() => parsePollIntervalMs('soon'), /POLL_INTERVAL_SECS must be between/);
+});
+
+test('each service polls at its own interval, POLL_INTERVAL_SECS by default, with jitter', () => {
+  assert.deepEqual(parsePollIntervals({}), { translator: 10_000, jdc: 10_000 });
+  assert.deepEqual(parsePollIntervals({ POLL_INTERVAL_SECS: '5', TRANSLATOR_POLL_INTERVAL_SECS: '1' }), { translator: 1_000, jdc: 5_000 });
+  assert.throws(() => parsePollIntervals({ JDC_POLL_INTERVAL_SECS: '600' }), /JDC_POLL_INTERVAL_SECS must be between 1 and 300/);
+
+  assert.equal(parsePollJitter(undefined), 0.1);
+  assert.equal(parsePollJitter('0'), 0);
+  assert.throws(() => parsePollJitter('80'), /POLL_JITTER_PERCENT must be between 0 and 50/);
+});
+
+test('bitcoind is polled every 5 minutes by default, with the same jitter', () => {
+  assert.equal(parseBitcoinPollIntervalMs(undefined), 300_000);
+  assert.equal(parseBitcoinPollIntervalMs('60'), 60_000);
+  assert.throws(() => parseBitcoinPollIntervalMs('900'), /BITCOIN_POLL_INTERVAL_SECS must be between 1 and 300/);
+
+  assert.equal(getJitteredDelayMs(300_000, 0.1, () => 0), 270_000);
+  assert.equal(getJitteredDelayMs(300_000, 0.1, () => 0.5), 300_000);
+  assert.equal(getJitteredDelayMs(300_000, 0, () => 1), 300_000);
+});
+
+test('polling one service refreshes its sections and carries over the others', async (t) => {
+  t.mock.timers.enable({ apis: ['setTimeout', 'Date'] });
+  const polls: string[][] = [];
+  let hashrate = 1;
+  const poller = createStackPoller(async (services) => {
+    polls.push(services);
+    const data = createStackData('jd');
+    return {
+      ...data,
+      global: services.includes('jdc') ? { uptime_secs: 60, sv2_clients: { total_clients: 1, total_channels: 1, total_hashrate: hashrate } } : null,
+      sv1Clients: services.includes('translator') ? data.sv1Clients : null,
+      sv2Clients: services.includes('jdc') ? [] : null,
+      errors: [],
+    };
+  }, { intervalMs: { translator: 1_000, jdc: 5_000 }, jitter: 0.1, random: () => 1 });
+
+  poller.start();
+  t.after(() => poller.stop());
+  await poller.get();
+  assert.deepEqual(polls, [['translator', 'jdc']]);
+  const [first] = Object.values(poller.latest()!.sections);
+
+  hashrate = 2;
+  t.mock.timers.tick(1_100);
+  // The timer's poll is in flight; this one shares it.
+  const snapshot = await poller.poll(['translator']);
+  assert.deepEqual(polls.slice(1), [['translator']]);
+  // The JDC's sections are carried over, with the time they were fetched.
+  assert.equal(snapshot!.data.global?.sv2_clients?.total_hashrate, 1);
+  assert.equal(snapshot!.sections.global, first);
+  assert.notEqual(snapshot!.sections.sv1Clients, first);
+
+  const [translator, jdc] = poller.schedule();
+  assert.deepEqual(translator, {
+    service: 'translator',
+    intervalMs: 1_000,
+    jitterMs: 100,
+    sections: ['sv1Clients'],
+    lastPolledAt: new Date(1_100).toISOString(),
+    nextPollAt: new Date(2_200).toISOString(),
+  });
+  assert.deepEqual(jdc.sections, ['global', 'serverChannels', 'sv2Clients']);
+  assert.equal(jdc.nextPollAt, new Date(5_500).toISOString());
+  // The JDC's sections are fresh against its own, longer interval.
+  t.mock.timers.tick(3_000);
+  assert.equal(poller.freshness(poller.latest()!).global?.stale, false);
+});
+
+test('stack poller shares one in-flight fetch between concurrent callers', async () => {
+  let calls = 0;
+  const poller = createStackPoller(async () => {
+    calls++;
+    return createStackData();
+  }, { intervalMs: 1_000 });
+
+  const [first, second] = await Promise.all([poller.poll(), poller.get('no-jd')]);
+  assert.equal(calls, 1);
+  assert.equal(first, second);
+  assert.equal(poller.inFlight(), 0);
+});
+
+test('stack poller serves fresh snapshots and refetches stale or other-mode ones', async () => {
+  let now = 0;
+  let mode: StackData['mode'] = 'no-jd';
+  let calls = 0;
+  const poller = createStackPoller(async () => {
+    calls++;
+    return createStackData(mode);
+  }, { intervalMs: 1_000, now: () => now });
+
+  await poller.poll();
+  now = 1_500;
+  assert.ok(poller.latest('no-jd'));
+  assert.equal(poller.latest('jd'), null);
+
+  await poller.get('no-jd');
+  assert.equal(calls, 1);
+
+  now = 5_000;
+  assert.equal(poller.latest(), null);
+  await poller.get('no-jd');
+  assert.equal(calls, 2);
+
+  // The stack now reports another mode than the caller asked for.
+  mode = 'jd';
+  poller.invalidate();
+  assert.equal(poller.latest(), null);
+  assert.equal(await poller.get('no-jd'), null);
+  assert.equal(calls, 3);
+});
+
+test('stack poller keeps the previous snapshot when a poll fails', async () => {
+  let fail = false;
+  const seen: string[] = [];
+  const poller = createStackPoller(async () => {
+    if (fail) throw new Error('connection refused');
+    return createStackData();
+  }, { intervalMs: 1_000 });
+  poller.onSnapshot((snapshot) => seen.push(snapshot.fetchedAt));
+
+  const first = await poller.poll();
+  fail = true;
+  assert.equal(await poller.poll(), first);
+  assert.equal(seen.length, 1);
+});
+
+test('getCachedMonitoringResponse only answers paths the snapshot holds', () => {
+  const data = createStackData('no-jd');
+
+  assert.deepEqual(getCachedMonitoringResponse(data, 'translator', '/global'), data.global);
+  assert.deepEqual(getCachedMonitoringResponse(data, 'translator', '/sv1/clients?offset=0&limit=100'), data.sv1Clients);
+  assert.equal(getCachedMonitoringResponse(data, 'jdc', '/global'), undefined);
+  // Failed sections go upstream so the proxy reports the real error.
+  assert.equal(getCachedMonitoringResponse(data, 'translator', '/server/channels?offset=0&limit=100'), undefined);
+  assert.equal(getCachedMonitoringResponse(data, 'translator', '/sv1/clients?offset=100&limit=100'), undefined);
+});
+
+test('createStackSnapshot keeps when a failing section last came back', () => {
+  const first = createStackSnapshot({ ...createStackData('jd'), sv2Clients: { items: [], total: 0 } }, '2026-01-01T00:00:00.000Z');
+  const second = createStackSnapshot(createStackData('jd'), '2026-01-01T00:00:10.000Z', { previous: first });
+
+  assert.deepEqual(second.sections, {
+    global: '2026-01-01T00:00:10.000Z',
+    serverChannels: null,
+    sv1Clients: '2026-01-01T00:00:10.000Z',
+    sv2Clients: '2026-01-01T00:00:00.000Z',
+  });
+  // A snapshot for another mode starts over.
+  assert.equal(createStackSnapshot(createStackData('no-jd'), '2026-01-01T00:00:20.000Z', { previous: first }).sections.serverChannels, null);
+});
+
+test('getStackFreshness marks failed and old sections stale', () => {
+  const snapshot = createStackSnapshot(createStackData('no-jd'), '2026-01-01T00:00:00.000Z');
+  const fetchedAt = Date.parse(snapshot.fetchedAt);
+
+  assert.deepEqual(getStackFreshness(snapshot, 20_000, fetchedAt + 5_000), {
+    global: { fetchedAt: snapshot.fetchedAt, source: 'translator', stale: false },
+    serverChannels: { fetchedAt: null, source: 'translator', stale: true },
+    sv1Clients: { fetchedAt: snapshot.fetchedAt, source: 'translator', stale: false },
+  });
+  assert.equal(getStackFreshness(snapshot, 20_000, fetchedAt + 30_000).global?.stale, true);
+  assert.equal(getStackFreshness(createStackSnapshot(createStackData('jd'), snapshot.fetchedAt), 20_000).sv2Clients?.source, 'jdc');
+});
+
+test('stack poller reports missing sections with the time until the next poll', async () => {
+  let now = 0;
+  const poller = createStackPoller(async () => ({
+    ...createStackData(),
+    errors: [{ section: 'serverChannels', backend: 'translator', errorClass: 'timeout', message: 'timed out' }],
+  }), { intervalMs: 10_000, now: () => now });
+
+  const snapshot = await poller.poll();
+  now = 2_500;
+  assert.deepEqual(poller.errors(snapshot!), [
+    { section: 'serverChannels', backend: 'translator', errorClass: 'timeout', message: 'timed out', retryInSecs: 8 },
+  ]);
+});
@@ -0,0 +1,414 @@
+/**
+ * Background polling of the local stack's monitoring APIs.
+ *
+ * The poller fetches StackData and keeps the latest snapshot in memory.
+ * Summaries, badges, the event sampler and the proxied dashboard reads it
+ * covers are served from that snapshot, so they no longer wait on the
+ * Translator or JDC, and a busy dashboard doesn't multiply upstream load.
+ *
+ * Each service is polled on its own schedule (TRANSLATOR_POLL_INTERVAL_SECS
+ * and JDC_POLL_INTERVAL_SECS, both POLL_INTERVAL_SECS by default), give or
+ * take POLL_JITTER_PERCENT so services with the same interval don't stay
+ * in lockstep. A poll of one service fetches only the sections it serves
+ * (see getSectionSource) and carries the others over from the snapshot.
+ * bitcoind's chain stats are read on a schedule of their own,
+ * BITCOIN_POLL_INTERVAL_SECS with the same jitter (see getJitteredDelayMs).
+ *
+ * Each snapshot also records when every section last came back, so
+ * aggregate responses can say how old each part of them is (see
+ * getStackFreshness).
+ */
+
+import type { SetupMode } from '@sv2-ui/shared';
+import { createLogger } from './logger.js';
+import { MONITORING_SERVICES, STACK_DATA_PATHS } from './monitoring.js';
+import type { MonitoringService, StackData, StackSection, StackSectionError } from './monitoring.js';
+import { runOutsideRequest } from './request-context.js';
+
+const log = createLogger('poller');
+
+export interface StackSnapshot {
+  data: StackData;
+  fetchedAt: string;
+  durationMs: number;
+  // When each section last came back, carried over from earlier snapshots
+  // while a section keeps failing; null if it never has.
+  sections: Partial<Record<StackSection, string | null>>;
+}
+
+export interface SectionFreshness {
+  fetchedAt: string | null;
+  source: MonitoringService;
+  // Failed in the latest fetch, or older than two of its service's poll intervals.
+  stale: boolean;
+}
+
+export type StackFreshness = Partial<Record<StackSection, SectionFreshness>>;
+
+export interface StackErrorReport extends StackSectionError {
+  // Until the next poll fetches the section again.
+  retryInSecs: number;
+}
+
+export type PollIntervals = Record<MonitoringService, number>;
+
+export interface ServicePollSchedule {
+  service: MonitoringService;
+  intervalMs: number;
+  // Each poll lands up to this much before or after its interval.
+  jitterMs: number;
+  // What a poll of this service fetches, in the latest snapshot's mode.
+  sections: StackSection[];
+  lastPolledAt: string | null;
+  nextPollAt: string | null;
+}
+
+export const DEFAULT_POLL_INTERVAL_SECS = 10;
+const MAX_POLL_INTERVAL_SECS = 300;
+export const DEFAULT_BITCOIN_POLL_INTERVAL_SECS = 300;
+export const DEFAULT_POLL_JITTER_PERCENT = 10;
+const MAX_POLL_JITTER_PERCENT = 50;
+const POLLED_SERVICES = Object.keys(MONITORING_SERVICES) as MonitoringService[];
+
+export function parsePollIntervalMs(
+  value: string | undefined = process.env.POLL_INTERVAL_SECS,
+  name = 'POLL_INTERVAL_SECS',
+  defaultMs = DEFAULT_POLL_INTERVAL_SECS * 1000,
+): number {
+  const raw = value?.trim();
+  if (!raw) return defaultMs;
+  const secs = Number(raw);
+  if (!Number.isFinite(secs) || secs < 1 || secs > MAX_POLL_INTERVAL_SECS) {
+    throw new Error(`${name} must be between 1 and ${MAX_POLL_INTERVAL_SECS}, got "${raw}"`);
+  }
+  return secs * 1000;
+}
+
+/**
+ * The poll interval of each service, falling back to POLL_INTERVAL_SECS.
+ */
+export function parsePollIntervals(env: NodeJS.ProcessEnv = process.env): PollIntervals {
+  const fallback = parsePollIntervalMs(env.POLL_INTERVAL_SECS);
+  return {
+    translator: parsePollIntervalMs(env.TRANSLATOR_POLL_INTERVAL_SECS, 'TRANSLATOR_POLL_INTERVAL_SECS', fallback),
+    jdc: parsePollIntervalMs(env.JDC_POLL_INTERVAL_SECS, 'JDC_POLL_INTERVAL_SECS', fallback),
+  };
+}
+
+/**
+ * How often bitcoind's chain stats are read. Each read runs a helper
+ * container, and difficulty only moves once per block, so this defaults to
+ * the longest interval.
+ */
+export function parseBitcoinPollIntervalMs(value: string | undefined = process.env.BITCOIN_POLL_INTERVAL_SECS): number {
+  return parsePollIntervalMs(value, 'BITCOIN_POLL_INTERVAL_SECS', DEFAULT_BITCOIN_POLL_INTERVAL_SECS * 1000);
+}
+
+/**
+ * POLL_JITTER_PERCENT as a fraction of the interval.
+ */
+export function parsePollJitter(value: string | undefined = process.env.POLL_JITTER_PERCENT): number {
+  const raw = value?.trim();
+  const percent = raw ? Number(raw) : DEFAULT_POLL_JITTER_PERCENT;
+  if (!Number.isFinite(percent) || percent < 0 || percent > MAX_POLL_JITTER_PERCENT) {
+    throw new Error(`POLL_JITTER_PERCENT must be between 0 and ${MAX_POLL_JITTER_PERCENT}, got "${raw}"`);
+  }
+  return percent / 100;
+}
+
+/**
+ * The delay before the next poll: `intervalMs`, give or take `jitter` of it.
+ */
+export function getJitteredDelayMs(intervalMs: number, jitter: number, random: () => number = Math.random): number {
+  return Math.round(intervalMs * (1 + jitter * (2 * random() - 1)));
+}
+
+/**
+ * The sections a stack in `mode` serves; only JD mode has SV2 clients.
+ */
+export function getStackSections(mode: SetupMode | null): StackSection[] {
+  return mode === 'jd' ? ['global', 'serverChannels', 'sv1Clients', 'sv2Clients'] : ['global', 'serverChannels', 'sv1Clients'];
+}
+
+export function getSectionSource(data: StackData, section: StackSection): MonitoringService {
+  if (section === 'sv1Clients') return 'translator';
+  if (section === 'sv2Clients') return 'jdc';
+  return data.upstream;
+}
+
+/**
+ * The sections of `data` a poll of `services` fetches.
+ */
+export function getPolledSections(data: StackData, services: MonitoringService[]): StackSection[] {
+  return getStackSections(data.mode).filter((section) => services.includes(getSectionSource(data, section)));
+}
+
+/**
+ * `data` from a poll of only `services`, with the other sections and their
+ * errors carried over from `previous`; null when that was for another mode.
+ */
+export function mergeStackData(previous: StackData, data: StackData, services: MonitoringService[]): StackData | null {
+  if (previous.mode !== data.mode) return null;
+  const polled = getPolledSections(data, services);
+  const merged: StackData = { ...data };
+  for (const section of getStackSections(data.mode)) {
+    if (!polled.includes(section)) Object.assign(merged, { [section]: previous[section] });
+  }
+  merged.errors = [
+    ...(previous.errors ?? []).filter((error) => !polled.includes(error.section)),
+    ...(data.errors ?? []).filter((error) => polled.includes(error.section)),
+  ];
+  return merged;
+}
+
+/**
+ * A snapshot of `data` fetched at `fetchedAt`. Sections that failed, or
+ * that weren't among `polled`, keep their time from `previous`, when it was
+ * for the same mode.
+ */
+export function createStackSnapshot(
+  data: StackData,
+  fetchedAt: string,
+  { durationMs = 0, previous = null, polled }: { durationMs?: number; previous?: StackSnapshot | null; polled?: StackSection[] } = {},
+): StackSnapshot {
+  const carried = previous?.data.mode === data.mode ? previous.sections : {};
+  const sections = Object.fromEntries(getStackSections(data.mode).map((section) => [
+    section,
+    data[section] !== null && (!polled || polled.includes(section)) ? fetchedAt : carried[section] ?? null,
+  ]));
+  return { data, fetchedAt, durationMs, sections };
+}
+
+/**
+ * `maxAgeMs` is for every section, or for the sections of each service.
+ */
+export function getStackFreshness(
+  snapshot: StackSnapshot,
+  maxAgeMs: number | ((source: MonitoringService) => number),
+  now = Date.now(),
+): StackFreshness {
+  return Object.fromEntries(getStackSections(snapshot.data.mode).map((section) => {
+    const fetchedAt = snapshot.sections[section] ?? null;
+    const source = getSectionSource(snapshot.data, section);
+    const maxAge = typeof maxAgeMs === 'number' ? maxAgeMs : maxAgeMs(source);
+    const stale = snapshot.data[section] === null || fetchedAt === null || now - Date.parse(fetchedAt) > maxAge;
+    return [section, { fetchedAt, source, stale }];
+  }));
+}
+
+export interface StackPollerOptions {
+  // One interval for every service, or one each.
+  intervalMs: number | PollIntervals;
+  // Up to this fraction of an interval earlier or later, e.g. 0.1.
+  jitter?: number;
+  now?: () => number;
+  random?: () => number;
+}
+
+/**
+ * `fetchData` fetches the sections of `services`, leaving the others null
+ * (see fetchStackData). It resolves to null while there's no stack to poll,
+ * which clears the snapshot.
+ */
+export function createStackPoller(
+  fetchData: (services: MonitoringService[]) => Promise<StackData | null>,
+  { intervalMs, jitter = 0, now = Date.now, random = Math.random }: StackPollerOptions,
+) {
+  const intervals: PollIntervals = typeof intervalMs === 'number' ? { translator: intervalMs, jdc: intervalMs } : intervalMs;
+  const longestIntervalMs = Math.max(...POLLED_SERVICES.map((service) => intervals[service]));
+  let snapshot: StackSnapshot | null = null;
+  let inFlight: { services: MonitoringService[]; promise: Promise<StackSnapshot | null> } | null = null;
+  const timers = new Map<MonitoringService, NodeJS.Timeout>();
+  const lastPolledAt = new Map<MonitoringService, number>();
+  const nextPollAt = new Map<MonitoringService, number>();
+  const listeners = new Set<(snapshot: StackSnapshot) => void>();
+
+  async function fetchSnapshot(services: MonitoringService[]): Promise<StackSnapshot | null> {
+    const previous = snapshot;
+    const startedAt = now();
+    // Without a snapshot to carry the rest from, every section is fetched.
+    let polledServices = previous ? services : POLLED_SERVICES;
+    let data = await fetchData(polledServices);
+    if (data && previous && polledServices !== POLLED_SERVICES) {
+      const merged = mergeStackData(previous.data, data, polledServices);
+      if (!merged) {
+        // The mode changed, so nothing can be carried over.
+        polledServices = POLLED_SERVICES;
+        data = await fetchData(polledServices);
+      } else {
+        data = merged;
+      }
+    }
+    for (const service of polledServices) lastPolledAt.set(service, now());
+    return data
+      ? createStackSnapshot(data, new Date(now()).toISOString(), {
+        durationMs: now() - startedAt,
+        previous,
+        polled: getPolledSections(data, polledServices),
+      })
+      : null;
+  }
+
+  /**
+   * Fetch the sections of `services` (all of them by default) right away.
+   * Concurrent callers share one upstream round trip, and polls run one at
+   * a time so they don't overwrite each other's sections.
+   */
+  function poll(services: MonitoringService[] = POLLED_SERVICES): Promise<StackSnapshot | null> {
+    if (inFlight) {
+      if (services.every((service) => inFlight!.services.includes(service))) return inFlight.promise;
+      return inFlight.promise.then(() => poll(services));
+    }
+    // Nothing in the stack comes from these services, e.g. the JDC outside JD mode.
+    if (snapshot && getPolledSections(snapshot.data, services).length === 0) return Promise.resolve(snapshot);
+
+    // Shared by every caller and feeding the listeners, so it belongs to no
+    // one request.
+    const promise = runOutsideRequest(async () => {
+      try {
+        snapshot = await fetchSnapshot(services);
+      } catch (error) {
+        // Keep serving the previous snapshot until it goes stale.
+        log.error('Stack poll failed:', error);
+        return snapshot;
+      }
+      if (snapshot) {
+        log.debug(`Polled the ${snapshot.data.mode ?? 'unconfigured'} stack in ${snapshot.durationMs}ms`);
+        for (const error of snapshot.data.errors ?? []) {
+          log.debug(`${error.section} from the ${error.backend} failed (${error.errorClass}): ${error.message}`);
+        }
+        for (const listener of listeners) listener(snapshot);
+      } else {
+        log.trace('No stack to poll');
+      }
+      return snapshot;
+    }).finally(() => {
+      inFlight = null;
+    });
+    inFlight = { services, promise };
+    return promise;
+  }
+
+  function isFresh(current: StackSnapshot | null, mode?: SetupMode | null): current is StackSnapshot {
+    return current !== null
+      && now() - Date.parse(current.fetchedAt) <= longestIntervalMs * 2
+      && (mode === undefined || current.data.mode === mode);
+  }
+
+  /**
+   * The latest snapshot if it's fresh (and for `mode`, when given), without
+   * fetching.
+   */
+  function latest(mode?: SetupMode | null): StackSnapshot | null {
+    return isFresh(snapshot, mode) ? snapshot : null;
+  }
+
+  /**
+   * Like `latest`, but polls when the snapshot is stale or from another mode.
+   */
+  async function get(mode?: SetupMode | null): Promise<StackSnapshot | null> {
+    const current = latest(mode);
+    if (current) return current;
+    const polled = await poll();
+    return isFresh(polled, mode) ? polled : null;
+  }
+
+  function scheduleNext(service: MonitoringService): void {
+    const delayMs = getJitteredDelayMs(intervals[service], jitter, random);
+    nextPollAt.set(service, now() + delayMs);
+    timers.set(service, setTimeout(() => {
+      scheduleNext(service);
+      void poll([service]);
+    }, delayMs));
+  }
+
+  function start(): void {
+    if (timers.size > 0) return;
+    void poll();
+    for (const service of POLLED_SERVICES) scheduleNext(service);
+  }
+
+  function stop(): void {
+    for (const timer of timers.values()) clearTimeout(timer);
+    timers.clear();
+    nextPollAt.clear();
+  }
+
+  /**
+   * Drop the snapshot, e.g. after the stack was reconfigured.
+   */
+  function invalidate(): void {
+    snapshot = null;
+  }
+
+  function onSnapshot(listener: (snapshot: StackSnapshot) => void): () => void {
+    listeners.add(listener);
+    return () => listeners.delete(listener);
+  }
+
+  /**
+   * How old each section of `snapshot` is, judged against its service's interval.
+   */
+  function freshness(current: StackSnapshot): StackFreshness {
+    return getStackFreshness(current, (source) => intervals[source] * 2, now());
+  }
+
+  /**
+   * The sections `snapshot` is missing, and when the next poll retries them.
+   */
+  function errors(current: StackSnapshot): StackErrorReport[] {
+    return (current.data.errors ?? []).map((error) => {
+      const retryAt = nextPollAt.get(error.backend) ?? Date.parse(current.fetchedAt) + intervals[error.backend];
+      return { ...error, retryInSecs: Math.max(0, Math.ceil((retryAt - now()) / 1000)) };
+    });
+  }
+
+  /**
+   * When each service is polled, and what that fetches.
+   */
+  function schedule(): ServicePollSchedule[] {
+    const toIso = (at: number | undefined) => (at === undefined ? null : new Date(at).toISOString());
+    return POLLED_SERVICES.map((service) => ({
+      service,
+      intervalMs: intervals[service],
+      jitterMs: Math.round(intervals[service] * jitter),
+      sections: snapshot ? getPolledSections(snapshot.data, [service]) : [],
+      lastPolledAt: toIso(lastPolledAt.get(service)),
+      nextPollAt: toIso(nextPollAt.get(service)),
+    }));
+  }
+
+  return {
+    poll,
+    latest,
+    get,
+    start,
+    stop,
+    invalidate,
+    onSnapshot,
+    freshness,
+    errors,
+    schedule,
+    inFlight: () => (inFlight ? 1 : 0),
+  };
+}
+
+export type StackPoller = ReturnType<typeof createStackPoller>;
+
+/**
+ * The cached body for a proxied GET of `path` (relative to /api/v1), or
+ * undefined when the snapshot doesn't hold it and the request should go
+ * upstream.
+ */
+export function getCachedMonitoringResponse(
+  data: StackData,
+  service: MonitoringService,
+  path: string,
+): unknown {
+  let body: unknown = null;
+  if (path === STACK_DATA_PATHS.global && service === data.upstream) body = data.global;
+  if (path === STACK_DATA_PATHS.serverChannels && service === data.upstream) body = data.serverChannels;
+  if (path === STACK_DATA_PATHS.sv1Clients && service === 'translator') body = data.sv1Clients;
+  // Null sections failed upstream; let the proxy report the real error.
+  return body ?? undefined;
+}
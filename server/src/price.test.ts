@@ -0,0 +1,79 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import { createPriceFeed, parsePriceConfig, readProviderPrice } from './price.js';
+
+test('parsePriceConfig is off without a provider and checks the rest', () => {
+  assert.equal(parsePriceConfig({}), null);
+  assert.equal(parsePriceConfig({ PRICE_PROVIDER: 'off' }), null);
+  assert.deepEqual(parsePriceConfig({ PRICE_PROVIDER: 'mempool' }), {
+    provider: 'mempool',
+    url: 'https://mempool.space',
+    currency: 'USD',
+    cacheMs: 300_000,
+  });
+  assert.deepEqual(parsePriceConfig({ PRICE_PROVIDER: 'mempool', PRICE_URL: 'http://umbrel.local:3006/', PRICE_CURRENCY: 'eur' }), {
+    provider: 'mempool',
+    url: 'http://umbrel.local:3006',
+    currency: 'EUR',
+    cacheMs: 300_000,
+  });
+  assert.throws(() => parsePriceConfig({ PRICE_PROVIDER: 'kraken' }), /PRICE_PROVIDER must be one of mempool, coingecko or off/);
+  assert.throws(() => parsePriceConfig({ PRICE_PROVIDER: 'coingecko', PRICE_CURRENCY: 'euro' }), /PRICE_CURRENCY must be a three-letter/);
+  assert.throws(() => parsePriceConfig({ PRICE_PROVIDER: 'coingecko', PRICE_CACHE_SECS: '1' }), /PRICE_CACHE_SECS must be between 10 and 86400/);
+});
+
+test('provider responses are read in the configured currency', () => {
+  assert.equal(readProviderPrice('mempool', 'EUR', { time: 1767225600, USD: 100000, EUR: 92000 }), 92000);
+  assert.equal(readProviderPrice('coingecko', 'USD', { bitcoin: { usd: 100000 } }), 100000);
+  assert.throws(() => readProviderPrice('mempool', 'SEK', { USD: 100000 }), /mempool has no SEK price/);
+});
+
+test('the price is cached and served stale while the provider is unreachable', async () => {
+  let now = Date.parse('2026-01-01T00:00:00.000Z');
+  let next: number | Error = 100000;
+  let fetches = 0;
+  const config = parsePriceConfig({ PRICE_PROVIDER: 'mempool' })!;
+  const feed = createPriceFeed(config, {
+    fetchPrice: async () => {
+      fetches += 1;
+      if (next instanceof Error) throw next;
+      return next;
+    },
+    now: () => new Date(now),
+  });
+
+  assert.equal((await feed.get()).price, 100000);
+  now += 60_000;
+  next = 101000;
+  assert.equal((await feed.get()).price, 100000);
+  assert.equal(fetches, 1);
+
+  now += 300_000;
+  next = new Error('fetch failed');
+  assert.deepEqual(await feed.get(), {
+    enabled: true,
+    provider: 'mempool',
+    currency: 'USD',
+    price: 100000,
+    fetchedAt: '2026-01-01T00:00:00.000Z',
+    stale: true,
+    error: 'fetch failed',
+  });
+  // Failures are retried once a minute, not on every request.
+  now += 10_000;
+  await feed.get();
+  assert.equal(fetches, 2);
+
+  now += 60_000;
+  next = 102000;
+  assert.deepEqual(await feed.get(), {
+    enabled: true,
+    provider: 'mempool',
+    currency: 'USD',
+    price: 102000,
+    fetchedAt: '2026-01-01T00:07:10.000Z',
+    stale: false,
+    error: null,
+  });
+});
@@ -0,0 +1,157 @@
+/**
+ * An optional BTC price, fetched by the backend so the dashboard never calls
+ * third-party APIs from the browser.
+ *
+ * With PRICE_PROVIDER set, GET /api/v1/price returns the price in
+ * PRICE_CURRENCY, cached for PRICE_CACHE_SECS. Providers are a mempool
+ * instance (mempool.space, or a self-hosted one at PRICE_URL) and CoinGecko.
+ * When the provider can't be reached, the last price is served as stale
+ * with the error, and the provider is retried at most once a minute; on an
+ * air-gapped LAN the price is simply null.
+ */
+
+export const PRICE_PROVIDERS = ['mempool', 'coingecko'] as const;
+export type PriceProvider = typeof PRICE_PROVIDERS[number];
+
+export interface PriceConfig {
+  provider: PriceProvider;
+  url: string;
+  // Upper-case ISO 4217 code, e.g. USD.
+  currency: string;
+  cacheMs: number;
+}
+
+export interface PriceStatus {
+  enabled: boolean;
+  provider: PriceProvider | null;
+  currency: string | null;
+  price: number | null;
+  fetchedAt: string | null;
+  // The last fetch failed, so `price` is older than the cache period.
+  stale: boolean;
+  error: string | null;
+}
+
+export interface PriceFeedDeps {
+  fetchPrice?: (config: PriceConfig) => Promise<number>;
+  now?: () => Date;
+}
+
+const DEFAULT_URLS: Record<PriceProvider, string> = {
+  mempool: 'https://mempool.space',
+  coingecko: 'https://api.coingecko.com',
+};
+const DEFAULT_CURRENCY = 'USD';
+const DEFAULT_CACHE_SECS = 300;
+const MIN_CACHE_SECS = 10;
+const MAX_CACHE_SECS = 86_400;
+const RETRY_MS = 60_000;
+const FETCH_TIMEOUT_MS = 5_000;
+
+export const DISABLED_PRICE_STATUS: PriceStatus = {
+  enabled: false,
+  provider: null,
+  currency: null,
+  price: null,
+  fetchedAt: null,
+  stale: false,
+  error: null,
+};
+
+export function parsePriceConfig(env: NodeJS.ProcessEnv = process.env): PriceConfig | null {
+  const provider = env.PRICE_PROVIDER?.trim().toLowerCase();
+  if (!provider || provider === 'off') return null;
+  if (!(PRICE_PROVIDERS as readonly string[]).includes(provider)) {
+    throw new Error(`PRICE_PROVIDER must be one of ${PRICE_PROVIDERS.join(', ')} or off, got "${env.PRICE_PROVIDER}"`);
+  }
+
+  const rawUrl = env.PRICE_URL?.trim() || DEFAULT_URLS[provider as PriceProvider];
+  let url: URL;
+  try {
+    url = new URL(rawUrl);
+  } catch {
+    throw new Error(`Invalid PRICE_URL "${rawUrl}"`);
+  }
+  if (url.protocol !== 'https:' && url.protocol !== 'http:') {
+    throw new Error('PRICE_URL must be http(s)');
+  }
+
+  const currency = (env.PRICE_CURRENCY?.trim() || DEFAULT_CURRENCY).toUpperCase();
+  if (!/^[A-Z]{3}$/.test(currency)) {
+    throw new Error(`PRICE_CURRENCY must be a three-letter currency code, got "${env.PRICE_CURRENCY}"`);
+  }
+
+  const rawSecs = env.PRICE_CACHE_SECS?.trim();
+  const secs = rawSecs ? Number(rawSecs) : DEFAULT_CACHE_SECS;
+  if (!Number.isFinite(secs) || secs < MIN_CACHE_SECS || secs > MAX_CACHE_SECS) {
+    throw new Error(`PRICE_CACHE_SECS must be between ${MIN_CACHE_SECS} and ${MAX_CACHE_SECS}, got "${rawSecs}"`);
+  }
+
+  return { provider: provider as PriceProvider, url: url.toString().replace(/\/$/, ''), currency, cacheMs: secs * 1000 };
+}
+
+/**
+ * The price in a provider's response, or an error saying why there isn't one.
+ */
+export function readProviderPrice(provider: PriceProvider, currency: string, body: unknown): number {
+  const prices = provider === 'mempool'
+    ? body as Record<string, unknown> | null
+    : (body as { bitcoin?: Record<string, unknown> } | null)?.bitcoin;
+  const price = prices?.[provider === 'mempool' ? currency : currency.toLowerCase()];
+  if (typeof price !== 'number' || !Number.isFinite(price) || price <= 0) {
+    throw new Error(`${provider} has no ${currency} price`);
+  }
+  return price;
+}
+
+async function fetchProviderPrice(config: PriceConfig): Promise<number> {
+  const url = config.provider === 'mempool'
+    ? `${config.url}/api/v1/prices`
+    : `${config.url}/api/v3/simple/price?ids=bitcoin&vs_currencies=${config.currency.toLowerCase()}`;
+  const response = await fetch(url, { headers: { Accept: 'application/json' }, signal: AbortSignal.timeout(FETCH_TIMEOUT_MS) });
+  if (!response.ok) {
+    throw new Error(`Price request to ${new URL(url).origin} failed (HTTP ${response.status})`);
+  }
+  return readProviderPrice(config.provider, config.currency, await response.json());
+}
+
+export function createPriceFeed(config: PriceConfig, deps: PriceFeedDeps = {}) {
+  const fetchPrice = deps.fetchPrice ?? fetchProviderPrice;
+  const now = deps.now ?? (() => new Date());
+  let status: PriceStatus = { ...DISABLED_PRICE_STATUS, enabled: true, provider: config.provider, currency: config.currency };
+  let attemptedAt: number | null = null;
+  let inFlight: Promise<PriceStatus> | null = null;
+
+  async function refresh(): Promise<PriceStatus> {
+    const at = now();
+    attemptedAt = at.getTime();
+    try {
+      const price = await fetchPrice(config);
+      status = { ...status, price, fetchedAt: at.toISOString(), stale: false, error: null };
+    } catch (error) {
+      status = { ...status, stale: status.price !== null, error: error instanceof Error ? error.message : String(error) };
+    }
+    return status;
+  }
+
+  /**
+   * The cached price, refreshed first once it's older than the cache period;
+   * concurrent callers share one fetch.
+   */
+  function get(): Promise<PriceStatus> {
+    const fetchedAt = status.fetchedAt === null ? null : Date.parse(status.fetchedAt);
+    const current = fetchedAt !== null && now().getTime() - fetchedAt < config.cacheMs;
+    const retrying = attemptedAt !== null && now().getTime() - attemptedAt < Math.min(RETRY_MS, config.cacheMs);
+    if (current || retrying) return Promise.resolve(status);
+    if (!inFlight) {
+      inFlight = refresh().finally(() => {
+        inFlight = null;
+      });
+    }
+    return inFlight;
+  }
+
+  return { get };
+}
+
+export type PriceFeed = ReturnType<typeof createPriceFeed>;
@@ -0,0 +1,38 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { evaluateReadiness, parseReadyGraceMs } from './probes.js';
+
+const booting = { assetsLoaded: true, stackConfigured: true, upstreamReachable: false, uptimeMs: 5_000, graceMs: 60_000 };
+
+test('parseReadyGraceMs defaults to a minute', () => {
+  assert.equal(parseReadyGraceMs(undefined), 60_000);
+  assert.equal(parseReadyGraceMs('0'), 0);
+  assert.throws(() => parseReadyGraceMs('-1'), /READY_GRACE_SECS must be between/);
+});
+
+test('a configured stack is not ready until a monitoring API answers', () => {
+  const readiness = evaluateReadiness(booting);
+  assert.equal(readiness.ready, false);
+  assert.deepEqual(readiness.checks[1], {
+    name: 'upstream',
+    ok: false,
+    detail: 'Waiting for a monitoring API (55s of grace left)',
+  });
+
+  assert.equal(evaluateReadiness({ ...booting, upstreamReachable: true }).ready, true);
+  assert.equal(evaluateReadiness({ ...booting, stackConfigured: false }).ready, true);
+});
+
+test('the grace period ends the wait, but never covers a missing UI build', () => {
+  assert.equal(evaluateReadiness({ ...booting, uptimeMs: 60_000 }).ready, true);
+
+  const noAssets = evaluateReadiness({ ...booting, assetsLoaded: false, upstreamReachable: true });
+  assert.equal(noAssets.ready, false);
+  assert.deepEqual(noAssets.checks[0], { name: 'assets', ok: false, detail: 'UI build not found' });
+});
+
+test('a headless instance needs no UI build', () => {
+  const headless = evaluateReadiness({ ...booting, assetsLoaded: null, upstreamReachable: true });
+  assert.equal(headless.ready, true);
+  assert.deepEqual(headless.checks[0], { name: 'assets', ok: true, detail: 'Headless; no UI served' });
+});
@@ -0,0 +1,67 @@
+/**
+ * Liveness and readiness probes for orchestrators (Kubernetes, Umbrel).
+ *
+ * /livez only says the process is up and serving requests. /readyz also
+ * requires the UI build to be in place and, when a stack is configured, at
+ * least one of its monitoring APIs to have answered. A stack that stays
+ * unreachable doesn't hold readiness back forever: once READY_GRACE_SECS
+ * have passed since startup the server reports ready anyway, so the UI can
+ * show what's wrong.
+ */
+
+export interface ReadinessCheck {
+  name: 'assets' | 'upstream';
+  ok: boolean;
+  detail: string;
+}
+
+export interface Readiness {
+  ready: boolean;
+  checks: ReadinessCheck[];
+}
+
+export interface ReadinessInput {
+  // Null in headless mode, which serves no UI.
+  assetsLoaded: boolean | null;
+  stackConfigured: boolean;
+  upstreamReachable: boolean;
+  uptimeMs: number;
+  graceMs: number;
+}
+
+export const DEFAULT_READY_GRACE_SECS = 60;
+const MAX_READY_GRACE_SECS = 3600;
+
+export function parseReadyGraceMs(value: string | undefined = process.env.READY_GRACE_SECS): number {
+  const raw = value?.trim();
+  const secs = raw ? Number(raw) : DEFAULT_READY_GRACE_SECS;
+  if (!Number.isFinite(secs) || secs < 0 || secs > MAX_READY_GRACE_SECS) {
+    throw new Error(`READY_GRACE_SECS must be between 0 and ${MAX_READY_GRACE_SECS}, got "${raw}"`);
+  }
+  return secs * 1000;
+}
+
+function getUpstreamCheck({ stackConfigured, upstreamReachable, uptimeMs, graceMs }: ReadinessInput): ReadinessCheck {
+  if (!stackConfigured) return { name: 'upstream', ok: true, detail: 'No stack configured' };
+  if (upstreamReachable) return { name: 'upstream', ok: true, detail: 'Monitoring API reachable' };
+  if (uptimeMs >= graceMs) {
+    return { name: 'upstream', ok: true, detail: 'No monitoring API reachable; grace period elapsed' };
+  }
+  return {
+    name: 'upstream',
+    ok: false,
+    detail: `Waiting for a monitoring API (${Math.ceil((graceMs - uptimeMs) / 1000)}s of grace left)`,
+  };
+}
+
+export function evaluateReadiness(input: ReadinessInput): Readiness {
+  const checks: ReadinessCheck[] = [
+    {
+      name: 'assets',
+      ok: input.assetsLoaded !== false,
+      detail: input.assetsLoaded === null ? 'Headless; no UI served' : input.assetsLoaded ? 'UI build found' : 'UI build not found',
+    },
+    getUpstreamCheck(input),
+  ];
+  return { ready: checks.every((check) => check.ok), checks };
+}
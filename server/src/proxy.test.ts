@@ -0,0 +1,65 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { buildForwardedHeaders, buildMempoolHeaders, parseMempoolUrl } from './proxy.js';
+import { resolveRequestId } from './request-context.js';
+
+test('injects forwarding headers and drops hop-by-hop and credential headers', () => {
+  const headers = buildForwardedHeaders({
+    headers: {
+      host: 'dashboard.local:8080',
+      accept: 'application/json',
+      connection: 'keep-alive',
+      authorization: 'Bearer admin-token',
+      cookie: 'session=abc',
+    },
+    clientIp: '192.168.1.20',
+    protocol: 'http',
+    requestId: 'req-1',
+  });
+
+  assert.deepEqual(headers, {
+    accept: 'application/json',
+    'x-forwarded-for': '192.168.1.20',
+    'x-forwarded-proto': 'http',
+    'x-forwarded-host': 'dashboard.local:8080',
+    'x-request-id': 'req-1',
+    'content-type': 'application/json',
+  });
+});
+
+test('appends the client to an existing X-Forwarded-For chain', () => {
+  const headers = buildForwardedHeaders({
+    headers: { 'x-forwarded-for': '203.0.113.7', 'content-type': 'text/plain' },
+    clientIp: '10.0.0.2',
+    protocol: 'https',
+    requestId: 'req-2',
+  });
+
+  assert.equal(headers['x-forwarded-for'], '203.0.113.7, 10.0.0.2');
+  assert.equal(headers['content-type'], 'text/plain');
+});
+
+test('reuses well-formed request IDs and replaces anything else', () => {
+  assert.equal(resolveRequestId('abc-123'), 'abc-123');
+  assert.match(resolveRequestId(undefined), /^[0-9a-f-]{36}$/);
+  assert.match(resolveRequestId('bad id with spaces'), /^[0-9a-f-]{36}$/);
+  assert.equal(resolveRequestId(['first', 'second']), 'first');
+});
+
+test('mempool requests carry nothing about the client', () => {
+  assert.deepEqual(buildMempoolHeaders({
+    host: 'dashboard.local:8080',
+    accept: 'application/json',
+    'if-none-match': '"abc"',
+    'x-forwarded-for': '203.0.113.7',
+    'user-agent': 'Mozilla/5.0',
+    cookie: 'session=abc',
+  }), { accept: 'application/json', 'if-none-match': '"abc"' });
+});
+
+test('parseMempoolUrl defaults to a self-hosted instance and can be turned off', () => {
+  assert.equal(parseMempoolUrl({}), 'http://umbrel.local:3006');
+  assert.equal(parseMempoolUrl({ MEMPOOL_URL: 'https://mempool.space/' }), 'https://mempool.space');
+  assert.equal(parseMempoolUrl({ MEMPOOL_URL: 'off' }), null);
+  assert.throws(() => parseMempoolUrl({ MEMPOOL_URL: 'ftp://mempool.lan' }), /MEMPOOL_URL must be http\(s\)/);
+});
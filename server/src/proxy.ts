@@ -0,0 +1,242 @@
+/**
+ * Reverse proxy for the Translator and JDC monitoring APIs, and for a
+ * mempool instance.
+ *
+ * Proxying through the backend avoids CORS issues when the frontend is served
+ * from a different port, and lets us inject standard forwarding headers so the
+ * upstream access logs show the real client. The mempool proxy does the
+ * opposite: the instance may be a public one, so it only sees the backend.
+ */
+
+import type { IncomingHttpHeaders } from 'http';
+import type { Request, RequestHandler } from 'express';
+import { createLogger } from './logger.js';
+import { redactForRequest, shouldRedact } from './redact.js';
+import { getRequestId, resolveRequestId } from './request-context.js';
+
+const log = createLogger('proxy');
+
+const PROXY_TIMEOUT_MS = 5000;
+
+// Hop-by-hop headers, plus headers that only make sense for sv2-ui itself
+// (our own credentials must never leak to the monitoring APIs).
+const NON_FORWARDED_HEADERS = new Set([
+  'connection',
+  'keep-alive',
+  'proxy-authenticate',
+  'proxy-authorization',
+  'te',
+  'trailer',
+  'transfer-encoding',
+  'upgrade',
+  'host',
+  'content-length',
+  'accept-encoding',
+  'authorization',
+  'cookie',
+]);
+
+// Umbrel's mempool app; set MEMPOOL_URL for another instance, or off.
+const DEFAULT_MEMPOOL_URL = 'http://umbrel.local:3006';
+// The only request headers sent on to a mempool instance.
+const MEMPOOL_FORWARDED_HEADERS = ['accept', 'if-none-match', 'if-modified-since'];
+const MEMPOOL_RESPONSE_HEADERS = ['content-type', 'cache-control', 'etag', 'last-modified'];
+
+/**
+ * Get the hostname for reaching a container.
+ * Uses container name on sv2-network (Docker) or localhost (development).
+ */
+export function getContainerHost(containerName: string): string {
+  // In Docker, containers are on sv2-network and can be reached by name
+  // In development, containers expose ports on localhost
+  // Try container name first (works when sv2-ui is on sv2-network)
+  // The container name is the hostname on the Docker network
+  return process.env.NODE_ENV === 'production' ? containerName : 'localhost';
+}
+
+// Origins found by Docker auto-configuration (see docker-autoconfig.ts),
+// keyed by the container name they stand in for.
+let containerOrigins: Record<string, string> = {};
+
+export function setContainerOrigins(origins: Record<string, string>): void {
+  containerOrigins = { ...origins };
+}
+
+/**
+ * Get the URL for connecting to a container's API.
+ */
+export function getContainerUrl(containerName: string, port: number): string {
+  return containerOrigins[containerName] ?? `http://${getContainerHost(containerName)}:${port}`;
+}
+
+export interface ForwardedRequestInfo {
+  headers: IncomingHttpHeaders;
+  clientIp: string | undefined;
+  protocol: string;
+  requestId: string;
+}
+
+export function buildForwardedHeaders({
+  headers,
+  clientIp,
+  protocol,
+  requestId,
+}: ForwardedRequestInfo): Record<string, string> {
+  const forwarded: Record<string, string> = {};
+
+  for (const [name, value] of Object.entries(headers)) {
+    if (value === undefined || NON_FORWARDED_HEADERS.has(name)) continue;
+    forwarded[name] = Array.isArray(value) ? value.join(', ') : value;
+  }
+
+  const priorForwardedFor = forwarded['x-forwarded-for'];
+  if (clientIp) {
+    forwarded['x-forwarded-for'] = priorForwardedFor ? `${priorForwardedFor}, ${clientIp}` : clientIp;
+  }
+
+  forwarded['x-forwarded-proto'] = protocol;
+  if (headers.host) {
+    forwarded['x-forwarded-host'] = headers.host;
+  }
+  forwarded['x-request-id'] = requestId;
+  forwarded['content-type'] = forwarded['content-type'] ?? 'application/json';
+
+  return forwarded;
+}
+
+function getRequestBody(req: Request): string | undefined {
+  if (req.method === 'GET' || req.method === 'HEAD') {
+    return undefined;
+  }
+
+  // express.json() has already consumed the stream, so re-serialize it.
+  if (req.body === undefined || (typeof req.body === 'object' && Object.keys(req.body).length === 0)) {
+    return undefined;
+  }
+
+  return typeof req.body === 'string' ? req.body : JSON.stringify(req.body);
+}
+
+/**
+ * Redact proxied JSON for viewers. Bodies that aren't JSON pass through untouched.
+ */
+function redactProxyBody(req: Request, body: string): string {
+  if (!shouldRedact(req)) return body;
+
+  try {
+    return JSON.stringify(redactForRequest(req, JSON.parse(body)));
+  } catch {
+    return body;
+  }
+}
+
+export interface MonitoringProxyOptions {
+  label: string;
+  containerName: string;
+  port: number;
+  // Cached body for a GET of `path` (relative to /api/v1), if there is one.
+  getCached?: (path: string) => unknown;
+}
+
+/**
+ * Proxy /<prefix>/v1/... to http://<container>:<port>/api/v1/...
+ */
+export function createMonitoringProxy({ label, containerName, port, getCached }: MonitoringProxyOptions): RequestHandler {
+  return async (req, res) => {
+    const targetUrl = `${getContainerUrl(containerName, port)}/api${req.url}`;
+    const requestId = getRequestId() ?? resolveRequestId(req.headers['x-request-id']);
+
+    res.set('X-Request-Id', requestId);
+
+    if (req.method === 'GET' && getCached && req.url.startsWith('/v1/')) {
+      const cached = getCached(req.url.slice('/v1'.length));
+      if (cached !== undefined) {
+        log.trace(`${req.method} ${req.url} served from the ${label} snapshot`);
+        res.set('X-Cache', 'HIT').json(redactForRequest(req, cached));
+        return;
+      }
+    }
+
+    const startedAt = Date.now();
+    try {
+      const response = await fetch(targetUrl, {
+        method: req.method,
+        headers: buildForwardedHeaders({
+          headers: req.headers,
+          clientIp: req.ip,
+          protocol: req.protocol,
+          requestId,
+        }),
+        body: getRequestBody(req),
+        signal: AbortSignal.timeout(PROXY_TIMEOUT_MS),
+      });
+      const data = await response.text();
+      log.debug(`${req.method} ${targetUrl} -> ${response.status} in ${Date.now() - startedAt}ms`);
+      res.status(response.status).set('Content-Type', response.headers.get('Content-Type') || 'application/json').send(redactProxyBody(req, data));
+    } catch (error) {
+      log.debug(`${req.method} ${targetUrl} failed after ${Date.now() - startedAt}ms:`, error instanceof Error ? error.message : error);
+      res.status(502).json({ error: `Cannot connect to ${label} monitoring API` });
+    }
+  };
+}
+
+/**
+ * The mempool instance /mempool-api proxies to, or null with MEMPOOL_URL=off.
+ */
+export function parseMempoolUrl(env: NodeJS.ProcessEnv = process.env): string | null {
+  const raw = env.MEMPOOL_URL?.trim() || DEFAULT_MEMPOOL_URL;
+  if (raw.toLowerCase() === 'off') return null;
+  let url: URL;
+  try {
+    url = new URL(raw);
+  } catch {
+    throw new Error(`Invalid MEMPOOL_URL "${raw}"`);
+  }
+  if (url.protocol !== 'https:' && url.protocol !== 'http:') {
+    throw new Error('MEMPOOL_URL must be http(s)');
+  }
+  return url.toString().replace(/\/$/, '');
+}
+
+/**
+ * The headers a mempool request carries: nothing about the client, its
+ * address or its credentials.
+ */
+export function buildMempoolHeaders(headers: IncomingHttpHeaders): Record<string, string> {
+  const forwarded: Record<string, string> = {};
+  for (const name of MEMPOOL_FORWARDED_HEADERS) {
+    const value = headers[name];
+    if (value !== undefined) forwarded[name] = Array.isArray(value) ? value.join(', ') : value;
+  }
+  return forwarded;
+}
+
+/**
+ * Proxy GET /mempool-api/... to <baseUrl>/api/..., e.g.
+ * /mempool-api/v1/fees/recommended or /mempool-api/block/<hash>.
+ */
+export function createMempoolProxy({ baseUrl }: { baseUrl: string }): RequestHandler {
+  return async (req, res) => {
+    if (req.method !== 'GET' && req.method !== 'HEAD') {
+      res.status(405).set('Allow', 'GET, HEAD').json({ error: 'The mempool API is read-only' });
+      return;
+    }
+
+    try {
+      const response = await fetch(`${baseUrl}/api${req.url}`, {
+        method: req.method,
+        headers: buildMempoolHeaders(req.headers),
+        signal: AbortSignal.timeout(PROXY_TIMEOUT_MS),
+      });
+      for (const name of MEMPOOL_RESPONSE_HEADERS) {
+        const value = response.headers.get(name);
+        if (value) res.set(name, value);
+      }
+      log.debug(`${req.method} ${baseUrl}/api${req.url} -> ${response.status}`);
+      res.status(response.status).send(Buffer.from(await response.arrayBuffer()));
+    } catch (error) {
+      log.debug(`${req.method} ${baseUrl}/api${req.url} failed:`, error instanceof Error ? error.message : error);
+      res.status(502).json({ error: 'Cannot connect to the mempool API' });
+    }
+  };
+}
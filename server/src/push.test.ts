@@ -0,0 +1,84 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import { test } from 'node:test';
+import type { AlertTransition } from './alerts.js';
+import { createPushNotifier, formatAlertPush, parsePushConfig } from './push.js';
+
+const alert: AlertTransition = {
+  id: 'a1',
+  ruleId: 'unreachable',
+  kind: 'unreachable',
+  status: 'firing',
+  message: 'Monitoring API unreachable',
+  at: '2026-01-01T00:00:00.000Z',
+};
+
+test('parses ntfy and Gotify configuration', () => {
+  assert.equal(parsePushConfig({}), null);
+  assert.deepEqual(parsePushConfig({ NTFY_URL: 'https://ntfy.example/sv2/', GOTIFY_URL: 'http://gotify.local', GOTIFY_TOKEN: 'tok' }), {
+    ntfy: { url: 'https://ntfy.example/sv2', token: null },
+    gotify: { url: 'http://gotify.local', token: 'tok' },
+  });
+  assert.throws(() => parsePushConfig({ NTFY_URL: 'https://ntfy.example' }), /must include the topic/);
+  assert.throws(() => parsePushConfig({ GOTIFY_URL: 'http://gotify.local' }), /GOTIFY_TOKEN is required/);
+  assert.throws(() => parsePushConfig({ NTFY_URL: 'ftp://ntfy.example/sv2' }), /must be http\(s\)/);
+});
+
+test('formats alert pushes with pool and test network', () => {
+  assert.deepEqual(formatAlertPush(alert, { poolName: 'Demo', network: 'signet' }), {
+    title: 'Alert · Demo · Signet',
+    message: 'Monitoring API unreachable',
+    urgent: true,
+    tag: 'rotating_light',
+  });
+  assert.equal(formatAlertPush({ ...alert, status: 'resolved', message: 'Resolved: Monitoring API unreachable' }).urgent, false);
+});
+
+test('pushes to ntfy and Gotify', async (t) => {
+  const requests: Array<{ url: string; headers: http.IncomingHttpHeaders; body: string }> = [];
+  const server = http.createServer((req, res) => {
+    let body = '';
+    req.on('data', (chunk) => { body += chunk; });
+    req.on('end', () => {
+      requests.push({ url: req.url ?? '', headers: req.headers, body });
+      res.end('{}');
+    });
+  });
+  server.listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+
+  const notifier = createPushNotifier({
+    ntfy: { url: `http://127.0.0.1:${port}/sv2`, token: 'secret' },
+    gotify: { url: `http://127.0.0.1:${port}`, token: 'app' },
+  });
+  await notifier.notifyAlert(alert);
+
+  const ntfy = requests.find((request) => request.url === '/sv2');
+  assert.equal(ntfy?.headers.title, 'Alert');
+  assert.equal(ntfy?.headers.priority, 'high');
+  assert.equal(ntfy?.headers.authorization, 'Bearer secret');
+  assert.equal(ntfy?.body, 'Monitoring API unreachable');
+
+  const gotify = requests.find((request) => request.url === '/message');
+  assert.equal(gotify?.headers['x-gotify-key'], 'app');
+  assert.deepEqual(JSON.parse(gotify?.body ?? '{}'), { title: 'Alert', message: 'Monitoring API unreachable', priority: 8 });
+});
+
+test('reports failed pushes by origin only', async (t) => {
+  const server = http.createServer((_req, res) => {
+    res.statusCode = 403;
+    res.end();
+  });
+  server.listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+
+  const notifier = createPushNotifier({ ntfy: { url: `http://127.0.0.1:${port}/private-topic`, token: null }, gotify: null });
+  await assert.rejects(notifier.notifyAlert(alert), (error: Error) => {
+    assert.equal(error.message, `Push to http://127.0.0.1:${port} failed (HTTP 403)`);
+    return true;
+  });
+});
@@ -0,0 +1,152 @@
+/**
+ * Push notifications through self-hosted ntfy or Gotify servers, for alert
+ * transitions and blocks found.
+ *
+ * - ntfy: NTFY_URL is the topic URL (e.g. https://ntfy.example/sv2), with
+ *   NTFY_TOKEN for protected topics
+ * - Gotify: GOTIFY_URL is the server URL and GOTIFY_TOKEN an application token
+ *
+ * Either or both can be configured.
+ */
+
+import type { AlertTransition } from './alerts.js';
+import type { StackEvent } from './events.js';
+import { DEFAULT_MESSAGES, formatAlertText, formatEventText } from './notification-messages.js';
+import type { Messages } from './notification-messages.js';
+import { getTestNetworkLabel } from './summary.js';
+import type { NotificationContext } from './webhooks.js';
+
+export interface PushConfig {
+  ntfy: { url: string; token: string | null } | null;
+  gotify: { url: string; token: string } | null;
+}
+
+export interface PushMessage {
+  title: string;
+  message: string;
+  // Urgent pushes may bypass do-not-disturb on the receiving phone.
+  urgent: boolean;
+  tag: string;
+}
+
+export interface PushNotifierDeps {
+  messages?: Messages;
+}
+
+const PUSH_TIMEOUT_MS = 10_000;
+
+function parseUrl(value: string, name: string): string {
+  let url: URL;
+  try {
+    url = new URL(value);
+  } catch {
+    throw new Error(`Invalid ${name} "${value}"`);
+  }
+  if (url.protocol !== 'http:' && url.protocol !== 'https:') {
+    throw new Error(`${name} must be http(s)`);
+  }
+  return url.toString().replace(/\/+$/, '');
+}
+
+export function parsePushConfig(env: NodeJS.ProcessEnv = process.env): PushConfig | null {
+  const ntfyUrl = env.NTFY_URL?.trim();
+  const gotifyUrl = env.GOTIFY_URL?.trim();
+  const gotifyToken = env.GOTIFY_TOKEN?.trim();
+  if (!ntfyUrl && !gotifyUrl) return null;
+
+  const ntfy = ntfyUrl ? parseUrl(ntfyUrl, 'NTFY_URL') : null;
+  if (ntfy && new URL(ntfy).pathname === '/') {
+    throw new Error('NTFY_URL must include the topic, e.g. https://ntfy.example/sv2');
+  }
+  if (gotifyUrl && !gotifyToken) {
+    throw new Error('GOTIFY_TOKEN is required with GOTIFY_URL');
+  }
+
+  return {
+    ntfy: ntfy ? { url: ntfy, token: env.NTFY_TOKEN?.trim() || null } : null,
+    gotify: gotifyUrl && gotifyToken ? { url: parseUrl(gotifyUrl, 'GOTIFY_URL'), token: gotifyToken } : null,
+  };
+}
+
+function withContext(title: string, { poolName, network }: NotificationContext): string {
+  return [title, poolName, getTestNetworkLabel(network)].filter(Boolean).join(' · ');
+}
+
+export function formatAlertPush(
+  alert: AlertTransition,
+  context: NotificationContext = {},
+  messages: Messages = DEFAULT_MESSAGES,
+): PushMessage {
+  const firing = alert.status === 'firing';
+  return {
+    title: withContext(messages.t(firing ? 'alert.firing' : 'alert.resolved'), context),
+    message: formatAlertText(alert, messages),
+    urgent: firing,
+    tag: firing ? 'rotating_light' : 'white_check_mark',
+  };
+}
+
+export function formatEventPush(
+  event: StackEvent,
+  context: NotificationContext = {},
+  messages: Messages = DEFAULT_MESSAGES,
+): PushMessage {
+  const { title, detail } = formatEventText(event, messages);
+  return { title: withContext(title, context), message: detail, urgent: false, tag: 'tada' };
+}
+
+async function post(url: string, init: RequestInit): Promise<void> {
+  const response = await fetch(url, { method: 'POST', ...init, signal: AbortSignal.timeout(PUSH_TIMEOUT_MS) });
+  if (!response.ok) {
+    // Only the origin; Gotify and ntfy URLs can identify private topics.
+    throw new Error(`Push to ${new URL(url).origin} failed (HTTP ${response.status})`);
+  }
+}
+
+export function createPushNotifier(config: PushConfig, deps: PushNotifierDeps = {}) {
+  const messages = deps.messages ?? DEFAULT_MESSAGES;
+
+  function sendNtfy({ url, token }: NonNullable<PushConfig['ntfy']>, push: PushMessage): Promise<void> {
+    return post(url, {
+      headers: {
+        'Content-Type': 'text/plain; charset=utf-8',
+        // Headers must be ASCII; ntfy decodes RFC 2047 encoded-words.
+        Title: /^[\x20-\x7e]*$/.test(push.title)
+          ? push.title
+          : `=?UTF-8?B?${Buffer.from(push.title).toString('base64')}?=`,
+        Priority: push.urgent ? 'high' : 'default',
+        Tags: push.tag,
+        ...(token ? { Authorization: `Bearer ${token}` } : {}),
+      },
+      body: push.message,
+    });
+  }
+
+  function sendGotify({ url, token }: NonNullable<PushConfig['gotify']>, push: PushMessage): Promise<void> {
+    return post(`${url}/message`, {
+      headers: { 'Content-Type': 'application/json', 'X-Gotify-Key': token },
+      body: JSON.stringify({ title: push.title, message: push.message, priority: push.urgent ? 8 : 4 }),
+    });
+  }
+
+  /**
+   * Send to every configured server. Rejects with the first failure after
+   * attempting all of them.
+   */
+  async function send(push: PushMessage): Promise<void> {
+    const results = await Promise.allSettled([
+      ...(config.ntfy ? [sendNtfy(config.ntfy, push)] : []),
+      ...(config.gotify ? [sendGotify(config.gotify, push)] : []),
+    ]);
+    const failure = results.find((result): result is PromiseRejectedResult => result.status === 'rejected');
+    if (failure) throw failure.reason;
+  }
+
+  return {
+    send,
+    notifyAlert: (alert: AlertTransition, context?: NotificationContext) => send(formatAlertPush(alert, context, messages)),
+    notifyEvent: (event: StackEvent, context?: NotificationContext) => send(formatEventPush(event, context, messages)),
+  };
+}
+
+export type PushNotifier = ReturnType<typeof createPushNotifier>;
@@ -0,0 +1,41 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import { encodeQr, getErrorCorrection, renderQrPng, renderQrSvg } from './qr.js';
+
+test('getErrorCorrection computes Reed-Solomon codewords', () => {
+  // "HELLO WORLD" as 1-M, from the standard's worked example.
+  const data = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17];
+  assert.deepEqual(getErrorCorrection(data, 10), [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]);
+});
+
+test('encodeQr picks the smallest version that fits', () => {
+  assert.equal(encodeQr('x').version, 1);
+  assert.equal(encodeQr('x').size, 21);
+  assert.equal(encodeQr('stratum+tcp://192.168.1.100:34255\nworker.1').version, 3);
+  assert.equal(encodeQr('a'.repeat(150)).version, 8);
+  assert.equal(encodeQr('a'.repeat(150), 'L').version, 7);
+  assert.throws(() => encodeQr('a'.repeat(3000)), /too long/);
+});
+
+test('encodeQr draws finder and timing patterns', () => {
+  const { size, modules } = encodeQr('stratum+tcp://miner.local:34255\nrig');
+  const finderRow = [true, true, true, true, true, true, true, false];
+  assert.deepEqual(modules[0].slice(0, 8), finderRow);
+  assert.deepEqual(modules[0].slice(size - 8).reverse(), finderRow);
+  assert.deepEqual(modules[size - 1].slice(0, 8), finderRow);
+  assert.deepEqual(modules[6].slice(8, size - 8), Array.from({ length: size - 16 }, (_, i) => i % 2 === 0));
+  // The dark module next to the bottom-left finder is always set.
+  assert.equal(modules[size - 8][8], true);
+});
+
+test('renders SVG and PNG with a quiet zone', () => {
+  const qr = encodeQr('hello');
+  assert.match(renderQrSvg(qr, { scale: 4 }), /^<svg [^>]*width="116" height="116" viewBox="0 0 29 29"/);
+
+  const png = renderQrPng(qr, { scale: 2 });
+  assert.deepEqual([...png.subarray(0, 8)], [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]);
+  assert.equal(png.subarray(12, 16).toString('ascii'), 'IHDR');
+  assert.equal(png.readUInt32BE(16), 58);
+  assert.equal(png.readUInt32BE(20), 58);
+});
@@ -0,0 +1,377 @@
+/**
+ * A small QR code encoder (ISO/IEC 18004, byte mode) with SVG and PNG output,
+ * for the miner connection codes at /api/v1/qr/miner-config.
+ *
+ * The version (size) is the smallest that fits the text at the given error
+ * correction level, and the mask is the one with the lowest penalty score,
+ * as the standard prescribes.
+ */
+
+import zlib from 'zlib';
+
+export type ErrorCorrectionLevel = 'L' | 'M' | 'Q' | 'H';
+
+export interface QrCode {
+  version: number;
+  size: number;
+  // modules[y][x], true for dark.
+  modules: boolean[][];
+}
+
+export interface QrRenderOptions {
+  // Pixels (PNG) or user units (SVG) per module.
+  scale?: number;
+  // Quiet zone in modules; the standard asks for 4.
+  margin?: number;
+}
+
+const ECL_ORDER: ErrorCorrectionLevel[] = ['L', 'M', 'Q', 'H'];
+const FORMAT_BITS: Record<ErrorCorrectionLevel, number> = { L: 1, M: 0, Q: 3, H: 2 };
+
+// Indexed by level (L, M, Q, H), then version; index 0 is unused.
+const ECC_CODEWORDS_PER_BLOCK = [
+  [-1, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+  [-1, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28],
+  [-1, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+  [-1, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+];
+const NUM_ERROR_CORRECTION_BLOCKS = [
+  [-1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25],
+  [-1, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49],
+  [-1, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68],
+  [-1, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81],
+];
+
+const MASKS: Array<(x: number, y: number) => boolean> = [
+  (x, y) => (x + y) % 2 === 0,
+  (_x, y) => y % 2 === 0,
+  (x) => x % 3 === 0,
+  (x, y) => (x + y) % 3 === 0,
+  (x, y) => (Math.floor(x / 3) + Math.floor(y / 2)) % 2 === 0,
+  (x, y) => ((x * y) % 2) + ((x * y) % 3) === 0,
+  (x, y) => (((x * y) % 2) + ((x * y) % 3)) % 2 === 0,
+  (x, y) => (((x + y) % 2) + ((x * y) % 3)) % 2 === 0,
+];
+
+const getBit = (value: number, index: number) => ((value >>> index) & 1) !== 0;
+
+// Modules left for data and error correction once the function patterns are drawn.
+function getRawDataModules(version: number): number {
+  let result = (16 * version + 128) * version + 64;
+  if (version >= 2) {
+    const alignments = Math.floor(version / 7) + 2;
+    result -= (25 * alignments - 10) * alignments - 55;
+    if (version >= 7) result -= 36;
+  }
+  return result;
+}
+
+function getDataCodewords(version: number, level: number): number {
+  return Math.floor(getRawDataModules(version) / 8)
+    - ECC_CODEWORDS_PER_BLOCK[level][version] * NUM_ERROR_CORRECTION_BLOCKS[level][version];
+}
+
+function getAlignmentPositions(version: number): number[] {
+  if (version === 1) return [];
+  const count = Math.floor(version / 7) + 2;
+  const step = Math.floor((version * 8 + count * 3 + 5) / (count * 4 - 4)) * 2;
+  const positions = [6];
+  for (let position = version * 4 + 10; positions.length < count; position -= step) {
+    positions.splice(1, 0, position);
+  }
+  return positions;
+}
+
+// Reed-Solomon over GF(2^8) with the QR polynomial x^8 + x^4 + x^3 + x^2 + 1.
+function gfMultiply(x: number, y: number): number {
+  let z = 0;
+  for (let i = 7; i >= 0; i--) {
+    z = (z << 1) ^ ((z >>> 7) * 0x11d);
+    z ^= ((y >>> i) & 1) * x;
+  }
+  return z;
+}
+
+function getGenerator(degree: number): number[] {
+  const result = new Array<number>(degree).fill(0);
+  result[degree - 1] = 1;
+  let root = 1;
+  for (let i = 0; i < degree; i++) {
+    for (let j = 0; j < result.length; j++) {
+      result[j] = gfMultiply(result[j], root);
+      if (j + 1 < result.length) result[j] ^= result[j + 1];
+    }
+    root = gfMultiply(root, 0x02);
+  }
+  return result;
+}
+
+export function getErrorCorrection(data: number[], degree: number): number[] {
+  const generator = getGenerator(degree);
+  const result = new Array<number>(degree).fill(0);
+  for (const byte of data) {
+    const factor = byte ^ (result.shift() as number);
+    result.push(0);
+    generator.forEach((coefficient, i) => {
+      result[i] ^= gfMultiply(coefficient, factor);
+    });
+  }
+  return result;
+}
+
+function getCodewords(bytes: Buffer, version: number, level: number): number[] {
+  const capacity = getDataCodewords(version, level) * 8;
+  const bits: number[] = [];
+  const append = (value: number, length: number) => {
+    for (let i = length - 1; i >= 0; i--) bits.push((value >>> i) & 1);
+  };
+  append(0b0100, 4);
+  append(bytes.length, version <= 9 ? 8 : 16);
+  for (const byte of bytes) append(byte, 8);
+  append(0, Math.min(4, capacity - bits.length));
+  append(0, (8 - (bits.length % 8)) % 8);
+
+  const data: number[] = [];
+  for (let i = 0; i < bits.length; i += 8) {
+    data.push(bits.slice(i, i + 8).reduce((byte, bit) => (byte << 1) | bit, 0));
+  }
+  for (let pad = 0xec; data.length < capacity / 8; pad ^= 0xec ^ 0x11) data.push(pad);
+
+  // Split into blocks, add error correction to each and interleave them.
+  const blockCount = NUM_ERROR_CORRECTION_BLOCKS[level][version];
+  const eccLength = ECC_CODEWORDS_PER_BLOCK[level][version];
+  const rawCodewords = Math.floor(getRawDataModules(version) / 8);
+  const shortBlocks = blockCount - (rawCodewords % blockCount);
+  const shortBlockLength = Math.floor(rawCodewords / blockCount);
+  const blocks: number[][] = [];
+  for (let i = 0, offset = 0; i < blockCount; i++) {
+    const block = data.slice(offset, offset + shortBlockLength - eccLength + (i < shortBlocks ? 0 : 1));
+    offset += block.length;
+    const ecc = getErrorCorrection(block, eccLength);
+    // Short blocks get a placeholder so every block has the same length.
+    if (i < shortBlocks) block.push(0);
+    blocks.push([...block, ...ecc]);
+  }
+
+  const result: number[] = [];
+  for (let i = 0; i < blocks[0].length; i++) {
+    blocks.forEach((block, j) => {
+      if (i !== shortBlockLength - eccLength || j >= shortBlocks) result.push(block[i]);
+    });
+  }
+  return result;
+}
+
+function getPenalty(modules: boolean[][]): number {
+  const size = modules.length;
+  const lines = [
+    ...modules.map((row) => row.map(Number).join('')),
+    ...modules.map((_, x) => modules.map((row) => Number(row[x])).join('')),
+  ];
+  let penalty = 0;
+  for (const line of lines) {
+    // Runs of five or more modules of one color.
+    for (const run of line.match(/0{5,}|1{5,}/g) ?? []) penalty += run.length - 2;
+    // Finder-like 1:1:3:1:1 patterns with four light modules on one side.
+    for (const pattern of ['10111010000', '00001011101']) {
+      for (let index = line.indexOf(pattern); index !== -1; index = line.indexOf(pattern, index + 1)) penalty += 40;
+    }
+  }
+  // 2×2 blocks of one color.
+  for (let y = 0; y < size - 1; y++) {
+    for (let x = 0; x < size - 1; x++) {
+      const color = modules[y][x];
+      if (color === modules[y][x + 1] && color === modules[y + 1][x] && color === modules[y + 1][x + 1]) penalty += 3;
+    }
+  }
+  // Deviation from 50% dark, in 5% steps.
+  const dark = modules.reduce((count, row) => count + row.filter(Boolean).length, 0);
+  penalty += (Math.ceil(Math.abs(dark * 20 - size * size * 10) / (size * size)) - 1) * 10;
+  return penalty;
+}
+
+/**
+ * Encode `text` as UTF-8 bytes. Throws if it doesn't fit in a version 40 code.
+ */
+export function encodeQr(text: string, errorCorrection: ErrorCorrectionLevel = 'M'): QrCode {
+  const bytes = Buffer.from(text, 'utf-8');
+  const level = ECL_ORDER.indexOf(errorCorrection);
+  let version = 1;
+  while (4 + (version <= 9 ? 8 : 16) + bytes.length * 8 > getDataCodewords(version, level) * 8) {
+    if (++version > 40) throw new Error(`Text is too long for a QR code (${bytes.length} bytes)`);
+  }
+
+  const size = version * 4 + 17;
+  const modules = Array.from({ length: size }, () => new Array<boolean>(size).fill(false));
+  const reserved = Array.from({ length: size }, () => new Array<boolean>(size).fill(false));
+  const set = (x: number, y: number, dark: boolean) => {
+    modules[y][x] = dark;
+    reserved[y][x] = true;
+  };
+
+  // Timing patterns, then finders and alignment patterns over them.
+  for (let i = 0; i < size; i++) {
+    set(6, i, i % 2 === 0);
+    set(i, 6, i % 2 === 0);
+  }
+  for (const [cx, cy] of [[3, 3], [size - 4, 3], [3, size - 4]]) {
+    for (let dy = -4; dy <= 4; dy++) {
+      for (let dx = -4; dx <= 4; dx++) {
+        const distance = Math.max(Math.abs(dx), Math.abs(dy));
+        const x = cx + dx;
+        const y = cy + dy;
+        if (x >= 0 && x < size && y >= 0 && y < size) set(x, y, distance !== 2 && distance !== 4);
+      }
+    }
+  }
+  const alignments = getAlignmentPositions(version);
+  alignments.forEach((cy, i) => alignments.forEach((cx, j) => {
+    // Skip the three corners taken by finders.
+    const last = alignments.length - 1;
+    if ((i === 0 && j === 0) || (i === 0 && j === last) || (i === last && j === 0)) return;
+    for (let dy = -2; dy <= 2; dy++) {
+      for (let dx = -2; dx <= 2; dx++) set(cx + dx, cy + dy, Math.max(Math.abs(dx), Math.abs(dy)) !== 1);
+    }
+  }));
+
+  function drawFormatBits(mask: number): void {
+    const data = (FORMAT_BITS[errorCorrection] << 3) | mask;
+    let remainder = data;
+    for (let i = 0; i < 10; i++) remainder = (remainder << 1) ^ ((remainder >>> 9) * 0x537);
+    const bits = ((data << 10) | remainder) ^ 0x5412;
+    for (let i = 0; i <= 5; i++) set(8, i, getBit(bits, i));
+    set(8, 7, getBit(bits, 6));
+    set(8, 8, getBit(bits, 7));
+    set(7, 8, getBit(bits, 8));
+    for (let i = 9; i < 15; i++) set(14 - i, 8, getBit(bits, i));
+    for (let i = 0; i < 8; i++) set(size - 1 - i, 8, getBit(bits, i));
+    for (let i = 8; i < 15; i++) set(8, size - 15 + i, getBit(bits, i));
+    set(8, size - 8, true);
+  }
+  drawFormatBits(0);
+
+  if (version >= 7) {
+    let remainder = version;
+    for (let i = 0; i < 12; i++) remainder = (remainder << 1) ^ ((remainder >>> 11) * 0x1f25);
+    const bits = (version << 12) | remainder;
+    for (let i = 0; i < 18; i++) {
+      const a = size - 11 + (i % 3);
+      const b = Math.floor(i / 3);
+      set(a, b, getBit(bits, i));
+      set(b, a, getBit(bits, i));
+    }
+  }
+
+  // Data goes in two-module columns, zigzagging up and down from the bottom right.
+  const codewords = getCodewords(bytes, version, level);
+  let bit = 0;
+  for (let right = size - 1; right >= 1; right -= 2) {
+    if (right === 6) right = 5;
+    for (let vertical = 0; vertical < size; vertical++) {
+      for (let j = 0; j < 2; j++) {
+        const x = right - j;
+        const y = ((right + 1) & 2) === 0 ? size - 1 - vertical : vertical;
+        if (!reserved[y][x] && bit < codewords.length * 8) {
+          modules[y][x] = getBit(codewords[bit >>> 3], 7 - (bit & 7));
+          bit++;
+        }
+      }
+    }
+  }
+
+  const applyMask = (mask: number) => {
+    for (let y = 0; y < size; y++) {
+      for (let x = 0; x < size; x++) {
+        if (!reserved[y][x] && MASKS[mask](x, y)) modules[y][x] = !modules[y][x];
+      }
+    }
+  };
+  let best = 0;
+  let bestPenalty = Infinity;
+  for (let mask = 0; mask < MASKS.length; mask++) {
+    applyMask(mask);
+    drawFormatBits(mask);
+    const penalty = getPenalty(modules);
+    if (penalty < bestPenalty) {
+      best = mask;
+      bestPenalty = penalty;
+    }
+    // Masking is an XOR, so applying it again undoes it.
+    applyMask(mask);
+  }
+  applyMask(best);
+  drawFormatBits(best);
+
+  return { version, size, modules };
+}
+
+export function renderQrSvg(qr: QrCode, { scale = 8, margin = 4 }: QrRenderOptions = {}): string {
+  const extent = qr.size + margin * 2;
+  const path = qr.modules
+    .flatMap((row, y) => row.map((dark, x) => (dark ? `M${x + margin},${y + margin}h1v1h-1z` : '')))
+    .join('');
+  return `<svg xmlns="http://www.w3.org/2000/svg" width="${extent * scale}" height="${extent * scale}" viewBox="0 0 ${extent} ${extent}" shape-rendering="crispEdges">`
+    + `<rect width="${extent}" height="${extent}" fill="#fff"/><path d="${path}" fill="#000"/></svg>\n`;
+}
+
+const CRC_TABLE = Array.from({ length: 256 }, (_, n) => {
+  let c = n;
+  for (let k = 0; k < 8; k++) c = c & 1 ? 0xedb88320 ^ (c >>> 1) : c >>> 1;
+  return c >>> 0;
+});
+
+function crc32(data: Buffer): number {
+  let crc = 0xffffffff;
+  for (const byte of data) crc = CRC_TABLE[(crc ^ byte) & 0xff] ^ (crc >>> 8);
+  return (crc ^ 0xffffffff) >>> 0;
+}
+
+function pngChunk(type: string, data: Buffer): Buffer {
+  const length = Buffer.alloc(4);
+  length.writeUInt32BE(data.length);
+  const body = Buffer.concat([Buffer.from(type, 'ascii'), data]);
+  const crc = Buffer.alloc(4);
+  crc.writeUInt32BE(crc32(body));
+  return Buffer.concat([length, body, crc]);
+}
+
+/**
+ * Encodes 8-bit grayscale (1 channel) or RGB (3 channel) pixels, stored row by
+ * row without padding, as a PNG.
+ */
+export function encodePng(width: number, height: number, channels: 1 | 3, pixels: Buffer): Buffer {
+  const stride = width * channels;
+  // Each scanline starts with filter type 0 (none).
+  const raw = Buffer.alloc((stride + 1) * height);
+  for (let y = 0; y < height; y++) {
+    pixels.copy(raw, y * (stride + 1) + 1, y * stride, (y + 1) * stride);
+  }
+
+  const header = Buffer.alloc(13);
+  header.writeUInt32BE(width, 0);
+  header.writeUInt32BE(height, 4);
+  header[8] = 8;
+  header[9] = channels === 1 ? 0 : 2;
+  return Buffer.concat([
+    Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]),
+    pngChunk('IHDR', header),
+    pngChunk('IDAT', zlib.deflateSync(raw)),
+    pngChunk('IEND', Buffer.alloc(0)),
+  ]);
+}
+
+/**
+ * An 8-bit grayscale PNG of the code.
+ */
+export function renderQrPng(qr: QrCode, { scale = 8, margin = 4 }: QrRenderOptions = {}): Buffer {
+  const width = (qr.size + margin * 2) * scale;
+  const pixels = Buffer.alloc(width * width, 0xff);
+  for (let py = 0; py < width; py++) {
+    const y = Math.floor(py / scale) - margin;
+    for (let px = 0; px < width; px++) {
+      const x = Math.floor(px / scale) - margin;
+      if (qr.modules[y]?.[x]) pixels[py * width + px] = 0;
+    }
+  }
+  return encodePng(width, width, 1, pixels);
+}
@@ -0,0 +1,42 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { createTokenBucketLimiter, DEFAULT_RATE_LIMIT, parseRateLimitOptions } from './rate-limit.js';
+
+test('parses rate limit settings from the environment', () => {
+  assert.deepEqual(parseRateLimitOptions({}), DEFAULT_RATE_LIMIT);
+  assert.deepEqual(parseRateLimitOptions({ RATE_LIMIT_RPS: '5', RATE_LIMIT_BURST: '10' }), { ratePerSecond: 5, burst: 10 });
+  assert.deepEqual(parseRateLimitOptions({ RATE_LIMIT_RPS: '500' }), { ratePerSecond: 500, burst: 500 });
+  assert.equal(parseRateLimitOptions({ RATE_LIMIT_RPS: '0' }), null);
+  assert.throws(() => parseRateLimitOptions({ RATE_LIMIT_RPS: 'fast' }), /Invalid RATE_LIMIT_RPS/);
+});
+
+test('allows a burst, then refills at the configured rate', () => {
+  const limiter = createTokenBucketLimiter({ ratePerSecond: 2, burst: 3 });
+  const start = 1_000_000;
+
+  assert.equal(limiter.take('10.0.0.1', start), 0);
+  assert.equal(limiter.take('10.0.0.1', start), 0);
+  assert.equal(limiter.take('10.0.0.1', start), 0);
+  assert.equal(limiter.take('10.0.0.1', start), 500);
+  assert.equal(limiter.peek('10.0.0.1', start + 250), 250);
+  assert.equal(limiter.peek('10.0.0.3', start), 0);
+
+  // Other clients have their own bucket.
+  assert.equal(limiter.take('10.0.0.2', start), 0);
+
+  assert.equal(limiter.take('10.0.0.1', start + 500), 0);
+  assert.equal(limiter.take('10.0.0.1', start + 500), 500);
+});
+
+test('sweeping drops buckets that have fully refilled', () => {
+  const limiter = createTokenBucketLimiter({ ratePerSecond: 1, burst: 2 });
+  limiter.take('10.0.0.1', 0);
+  limiter.take('10.0.0.2', 0);
+  limiter.take('10.0.0.2', 0);
+
+  limiter.sweep(1000);
+  assert.equal(limiter.size(), 1);
+
+  limiter.sweep(2000);
+  assert.equal(limiter.size(), 0);
+});
@@ -0,0 +1,125 @@
+/**
+ * Per-client token bucket rate limiting for the monitoring proxy routes.
+ *
+ * Each client IP gets a bucket that holds up to `burst` tokens and refills at
+ * `ratePerSecond`. The dashboard's polling stays well inside the defaults;
+ * the limit is there so a runaway script or an exposed deployment can't
+ * flood the Translator/JDC monitoring APIs through the proxy.
+ */
+
+import type { RequestHandler, Response } from 'express';
+
+export interface RateLimitOptions {
+  ratePerSecond: number;
+  burst: number;
+}
+
+export const DEFAULT_RATE_LIMIT: RateLimitOptions = { ratePerSecond: 50, burst: 200 };
+// Password checks (logins, Basic credentials, failed auth hook checks): ten
+// tries, then one every six seconds per client.
+export const LOGIN_RATE_LIMIT: RateLimitOptions = { ratePerSecond: 1 / 6, burst: 10 };
+
+// Buckets that have refilled completely carry no state worth keeping.
+const SWEEP_INTERVAL_MS = 60_000;
+
+interface Bucket {
+  tokens: number;
+  updatedAt: number;
+}
+
+/**
+ * Read RATE_LIMIT_RPS / RATE_LIMIT_BURST. A rate of 0 disables limiting.
+ */
+export function parseRateLimitOptions(env: NodeJS.ProcessEnv = process.env): RateLimitOptions | null {
+  const parse = (name: string, fallback: number) => {
+    const raw = env[name]?.trim();
+    if (!raw) return fallback;
+    const value = Number(raw);
+    if (!Number.isFinite(value) || value < 0) {
+      throw new Error(`Invalid ${name} "${raw}"`);
+    }
+    return value;
+  };
+
+  const ratePerSecond = parse('RATE_LIMIT_RPS', DEFAULT_RATE_LIMIT.ratePerSecond);
+  if (ratePerSecond === 0) return null;
+
+  const burst = parse('RATE_LIMIT_BURST', Math.max(DEFAULT_RATE_LIMIT.burst, ratePerSecond));
+  return { ratePerSecond, burst: Math.max(burst, 1) };
+}
+
+export function createTokenBucketLimiter({ ratePerSecond, burst }: RateLimitOptions) {
+  const buckets = new Map<string, Bucket>();
+
+  function refill(bucket: Bucket, now: number): void {
+    const elapsedSecs = Math.max(0, now - bucket.updatedAt) / 1000;
+    bucket.tokens = Math.min(burst, bucket.tokens + elapsedSecs * ratePerSecond);
+    bucket.updatedAt = now;
+  }
+
+  /**
+   * Take one token for `key`. Returns 0 when allowed, otherwise the number of
+   * milliseconds until a token is available.
+   */
+  function take(key: string, now = Date.now()): number {
+    let bucket = buckets.get(key);
+    if (!bucket) {
+      bucket = { tokens: burst, updatedAt: now };
+      buckets.set(key, bucket);
+    }
+
+    refill(bucket, now);
+    if (bucket.tokens >= 1) {
+      bucket.tokens -= 1;
+      return 0;
+    }
+
+    return Math.ceil(((1 - bucket.tokens) / ratePerSecond) * 1000);
+  }
+
+  /**
+   * Like take, without taking the token: for limits charged only once the
+   * outcome is known, such as failed auth hook checks.
+   */
+  function peek(key: string, now = Date.now()): number {
+    const bucket = buckets.get(key);
+    if (!bucket) return 0;
+    refill(bucket, now);
+    return bucket.tokens >= 1 ? 0 : Math.ceil(((1 - bucket.tokens) / ratePerSecond) * 1000);
+  }
+
+  function sweep(now = Date.now()): void {
+    for (const [key, bucket] of buckets) {
+      refill(bucket, now);
+      if (bucket.tokens >= burst) buckets.delete(key);
+    }
+  }
+
+  return { take, peek, sweep, size: () => buckets.size };
+}
+
+export type TokenBucketLimiter = ReturnType<typeof createTokenBucketLimiter>;
+
+/**
+ * Answer an over-limit request with 429 and Retry-After.
+ */
+export function sendRateLimited(res: Response, retryAfterMs: number): void {
+  const retryAfterSecs = Math.max(1, Math.ceil(retryAfterMs / 1000));
+  res.set('Retry-After', String(retryAfterSecs)).status(429).json({
+    error: 'Too many requests',
+    retryAfterSecs,
+  });
+}
+
+/**
+ * Express middleware answering over-limit requests with 429 and Retry-After.
+ */
+export function createRateLimitMiddleware(limiter: TokenBucketLimiter): RequestHandler {
+  setInterval(() => limiter.sweep(), SWEEP_INTERVAL_MS).unref();
+
+  return (req, res, next) => {
+    const retryAfterMs = limiter.take(req.ip ?? 'unknown');
+    if (retryAfterMs === 0) return next();
+    sendRateLimited(res, retryAfterMs);
+  };
+}
@@ -0,0 +1,125 @@
+import assert from 'node:assert/strict';
+import type { AddressInfo } from 'node:net';
+import { test } from 'node:test';
+import express from 'express';
+import { createRedactor, parseRedactionPaths, REDACTED } from './redact.js';
+import { createRealtimeHub, createRealtimeRedactor, formatSseMessage, parseRefreshTarget } from './realtime.js';
+
+test('parses the poll-now backend parameter', () => {
+  assert.equal(parseRefreshTarget(undefined), 'all');
+  assert.equal(parseRefreshTarget('translator'), 'translator');
+  assert.equal(parseRefreshTarget('jdc'), 'jdc');
+  assert.throws(() => parseRefreshTarget('pool'), /backend must be/);
+});
+
+test('formats named SSE messages', () => {
+  assert.equal(
+    formatSseMessage({ type: 'refresh', backend: 'jdc', at: '2026-01-01T00:00:00.000Z' }),
+    'event: refresh\ndata: {"type":"refresh","backend":"jdc","at":"2026-01-01T00:00:00.000Z"}\n\n',
+  );
+});
+
+test('broadcasts to connected clients and forgets them on disconnect', async (t) => {
+  const hub = createRealtimeHub();
+  const app = express();
+  app.get('/stream', hub.handler);
+  const server = app.listen(0);
+  t.after(() => server.close());
+
+  const controller = new AbortController();
+  const response = await fetch(`http://127.0.0.1:${(server.address() as AddressInfo).port}/stream`, {
+    signal: controller.signal,
+  });
+  assert.match(response.headers.get('content-type') ?? '', /^text\/event-stream/);
+  assert.equal(hub.size(), 1);
+
+  const reader = response.body!.getReader();
+  const decoder = new TextDecoder();
+  let received = '';
+  hub.broadcast({ type: 'refresh', backend: 'all', at: '2026-01-01T00:00:00.000Z' });
+  while (!received.includes('event: refresh')) {
+    const { value } = await reader.read();
+    received += decoder.decode(value);
+  }
+  assert.match(received, /"backend":"all"/);
+
+  controller.abort();
+  await new Promise((resolve) => setTimeout(resolve, 50));
+  assert.equal(hub.size(), 0);
+});
+
+async function readUntil(response: Response, pattern: RegExp): Promise<string> {
+  const reader = response.body!.getReader();
+  const decoder = new TextDecoder();
+  let received = '';
+  while (!pattern.test(received)) {
+    const { value } = await reader.read();
+    received += decoder.decode(value);
+  }
+  return received;
+}
+
+test('replays missed messages to clients resuming from a cursor', async (t) => {
+  const hub = createRealtimeHub({ replayLimit: 2 });
+  const app = express();
+  app.get('/stream', hub.handler);
+  const server = app.listen(0);
+  t.after(() => server.close());
+  const url = `http://127.0.0.1:${(server.address() as AddressInfo).port}/stream`;
+
+  const controller = new AbortController();
+  t.after(() => controller.abort());
+  const first = await fetch(url, { signal: controller.signal });
+  hub.broadcast({ type: 'refresh', backend: 'jdc', at: '2026-01-01T00:00:00.000Z' });
+  const [, cursor] = /id: (\S+)/.exec(await readUntil(first, /event: refresh/))!;
+
+  hub.broadcast({ type: 'refresh', backend: 'translator', at: '2026-01-01T00:00:01.000Z' });
+  hub.broadcast({ type: 'refresh', backend: 'all', at: '2026-01-01T00:00:02.000Z' });
+
+  const resumed = await fetch(url, { headers: { 'Last-Event-ID': cursor }, signal: controller.signal });
+  const replayed = await readUntil(resumed, /"backend":"all"/);
+  assert.match(replayed, /"backend":"translator"/);
+  assert.doesNotMatch(replayed, /"backend":"jdc"/);
+
+  // The first message has fallen out of the two-message buffer.
+  hub.broadcast({ type: 'refresh', backend: 'jdc', at: '2026-01-01T00:00:03.000Z' });
+  const stale = await fetch(`${url}?cursor=${cursor}`, { signal: controller.signal });
+  assert.match(await readUntil(stale, /event: reset/), /"type":"reset"/);
+
+  const foreign = await fetch(`${url}?cursor=deadbeef-1`, { signal: controller.signal });
+  assert.match(await readUntil(foreign, /event: reset/), /"type":"reset"/);
+});
+
+test('redacts events and alerts for viewers as /api/events and /api/alerts do', async (t) => {
+  const hub = createRealtimeHub({
+    redact: createRealtimeRedactor(createRedactor(parseRedactionPaths('events[].detail,transitions[].message'))),
+  });
+  const app = express();
+  app.use((req, res, next) => {
+    res.locals.role = req.get('X-Role');
+    next();
+  });
+  app.get('/stream', hub.handler);
+  const server = app.listen(0);
+  t.after(() => server.close());
+  const url = `http://127.0.0.1:${(server.address() as AddressInfo).port}/stream`;
+
+  const controller = new AbortController();
+  t.after(() => controller.abort());
+  const viewer = await fetch(url, { headers: { 'X-Role': 'viewer' }, signal: controller.signal });
+  const admin = await fetch(url, { headers: { 'X-Role': 'admin' }, signal: controller.signal });
+  hub.broadcast({
+    type: 'event',
+    event: { id: 'e1', kind: 'incident', title: 'JDC down', detail: 'bc1qexample.rig1 lost', at: '2026-01-01T00:00:00.000Z' },
+  });
+  hub.broadcast({
+    type: 'alert',
+    alert: { id: 'a1', ruleId: 'r1', kind: 'hashrate-below', status: 'firing', message: 'bc1qexample.rig1 is slow', at: '2026-01-01T00:00:00.000Z' },
+  });
+
+  const redacted = await readUntil(viewer, /event: alert/);
+  assert.match(redacted, /"title":"JDC down"/);
+  assert.doesNotMatch(redacted, /bc1qexample/);
+  assert.equal(redacted.split(`"${REDACTED}"`).length - 1, 2);
+  assert.match(await readUntil(admin, /event: alert/), /"detail":"bc1qexample\.rig1 lost"/);
+});
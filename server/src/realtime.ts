@@ -0,0 +1,145 @@
+/**
+ * Server-sent events hub pushing refresh hints, stack events and new blocks
+ * to open dashboards, so they update without waiting for their next poll.
+ *
+ * Every message carries an SSE id of the form <session>-<seq>. A client that
+ * reconnects with it (the Last-Event-ID header, which EventSource sends on its
+ * own, or ?cursor=) is replayed what it missed from a bounded buffer. When the
+ * gap can't be filled, because the buffer moved on or the server restarted,
+ * it gets a `reset` message and should refetch everything instead.
+ *
+ * Messages are redacted per client as they are written, so viewers don't get
+ * over the stream what REDACT_PATHS removes from /api/events and /api/alerts
+ * (see createRealtimeRedactor).
+ */
+
+import crypto from 'crypto';
+import type { Request, RequestHandler, Response } from 'express';
+import type { AlertTransition } from './alerts.js';
+import type { StackEvent } from './events.js';
+import type { MonitoringService } from './monitoring.js';
+import type { Redactor } from './redact.js';
+
+export type RefreshTarget = MonitoringService | 'all';
+
+export type RealtimeMessage =
+  | { type: 'refresh'; backend: RefreshTarget; at: string }
+  // `stack` is set for remote stacks (see stacks.ts), absent for the local one.
+  | { type: 'event'; event: StackEvent; stack?: string }
+  | { type: 'alert'; alert: AlertTransition; stack?: string }
+  // A new block on the local node, from ZMQ (see zmq.ts).
+  | { type: 'block'; hash: string; height: number | null; at: string }
+  | { type: 'reset'; at: string };
+
+// Comment lines keep idle connections from being dropped by proxies.
+const HEARTBEAT_INTERVAL_MS = 25_000;
+export const DEFAULT_REPLAY_LIMIT = 200;
+
+export function formatSseMessage(message: RealtimeMessage, id?: string): string {
+  return `${id ? `id: ${id}\n` : ''}event: ${message.type}\ndata: ${JSON.stringify(message)}\n\n`;
+}
+
+export interface RealtimeHubOptions {
+  // Messages kept for replay to reconnecting clients.
+  replayLimit?: number;
+  // What a given client may see of a message, e.g. createRealtimeRedactor(redactForRequest).
+  redact?: (req: Request, message: RealtimeMessage) => RealtimeMessage;
+}
+
+/**
+ * Redact event and alert messages as if they were entries of /api/events
+ * and /api/alerts, so paths like `events[].detail` or `transitions[].message`
+ * cover them too.
+ */
+export function createRealtimeRedactor(redact: Redactor) {
+  return (req: Request, message: RealtimeMessage): RealtimeMessage => {
+    if (message.type === 'event') return { ...message, event: redact(req, { events: [message.event] }).events[0] };
+    if (message.type === 'alert') return { ...message, alert: redact(req, { transitions: [message.alert] }).transitions[0] };
+    return message;
+  };
+}
+
+export function parseRefreshTarget(value: unknown): RefreshTarget {
+  if (value === undefined || value === '' || value === 'all') return 'all';
+  if (value === 'translator' || value === 'jdc') return value;
+  throw new Error('backend must be "translator", "jdc", or "all"');
+}
+
+export function createRealtimeHub({
+  replayLimit = DEFAULT_REPLAY_LIMIT,
+  redact = (_req, message) => message,
+}: RealtimeHubOptions = {}) {
+  const clients = new Map<Response, Request>();
+  // Distinguishes cursors handed out by an earlier server process.
+  const session = crypto.randomBytes(4).toString('hex');
+  const buffer: Array<{ seq: number; message: RealtimeMessage }> = [];
+  let seq = 0;
+
+  const format = (req: Request, entry: { seq: number; message: RealtimeMessage }) =>
+    formatSseMessage(redact(req, entry.message), `${session}-${entry.seq}`);
+
+  function broadcast(message: RealtimeMessage): void {
+    seq += 1;
+    const entry = { seq, message };
+    buffer.push(entry);
+    if (buffer.length > replayLimit) buffer.shift();
+
+    for (const [res, req] of clients) {
+      res.write(format(req, entry));
+    }
+  }
+
+  /**
+   * Messages a client holding `cursor` missed, or null if they're no longer
+   * all buffered.
+   */
+  function replay(cursor: string): Array<{ seq: number; message: RealtimeMessage }> | null {
+    const match = /^([0-9a-f]+)-(\d+)$/.exec(cursor);
+    if (!match || match[1] !== session) return null;
+
+    const last = Number(match[2]);
+    if (last > seq) return null;
+    // Anything after `last` must still be buffered.
+    const oldest = buffer[0]?.seq ?? seq + 1;
+    if (last + 1 < oldest) return null;
+    return buffer.filter((entry) => entry.seq > last);
+  }
+
+  /**
+   * GET handler that keeps the response open as an event stream.
+   */
+  const handler: RequestHandler = (req, res) => {
+    res.set({
+      'Content-Type': 'text/event-stream',
+      'Cache-Control': 'no-cache',
+      Connection: 'keep-alive',
+      // Stop nginx and friends from buffering the stream.
+      'X-Accel-Buffering': 'no',
+    });
+    res.flushHeaders();
+    res.write(': connected\n\n');
+
+    const cursor = req.get('Last-Event-ID') ?? (typeof req.query.cursor === 'string' ? req.query.cursor : '');
+    if (cursor) {
+      const missed = replay(cursor);
+      if (missed) {
+        for (const entry of missed) res.write(format(req, entry));
+      } else {
+        // Carries the current cursor so the next reconnect resumes from here.
+        res.write(formatSseMessage({ type: 'reset', at: new Date().toISOString() }, `${session}-${seq}`));
+      }
+    }
+
+    clients.set(res, req);
+    const heartbeat = setInterval(() => res.write(': heartbeat\n\n'), HEARTBEAT_INTERVAL_MS);
+
+    req.on('close', () => {
+      clearInterval(heartbeat);
+      clients.delete(res);
+    });
+  };
+
+  return { broadcast, handler, size: () => clients.size };
+}
+
+export type RealtimeHub = ReturnType<typeof createRealtimeHub>;
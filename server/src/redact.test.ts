@@ -0,0 +1,68 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { createRedactor, parseRedactionPaths, redactJson, REDACTED } from './redact.js';
+
+const SV1_CLIENTS = {
+  total: 2,
+  items: [
+    { client_id: 1, user_identity: 'bc1qexample.rig1', hashrate: 10 },
+    { client_id: 2, user_identity: 'bc1qexample.rig2', hashrate: 20 },
+  ],
+};
+
+test('parses dotted paths with array and wildcard segments', () => {
+  assert.deepEqual(parseRedactionPaths('items[].user_identity, config.*.user_identity,,'), [
+    ['items', '[]', 'user_identity'],
+    ['config', '*', 'user_identity'],
+  ]);
+  assert.deepEqual(parseRedactionPaths(undefined), []);
+});
+
+test('redacts fields inside arrays without mutating the input', () => {
+  const redacted = redactJson(SV1_CLIENTS, parseRedactionPaths('items[].user_identity'));
+
+  assert.deepEqual(redacted.items.map((item) => item.user_identity), [REDACTED, REDACTED]);
+  assert.deepEqual(redacted.items.map((item) => item.hashrate), [10, 20]);
+  assert.equal(SV1_CLIENTS.items[0].user_identity, 'bc1qexample.rig1');
+});
+
+test('wildcards match every key and null values are left alone', () => {
+  const config = {
+    config: {
+      jdc: { user_identity: 'jdc-user', coinbase_reward_address: 'bc1qpayout' },
+      translator: { user_identity: 'tproxy-user', min_hashrate: 1 },
+      pool: null,
+    },
+  };
+
+  const redacted = redactJson(config, parseRedactionPaths('config.*.user_identity,config.pool'));
+
+  assert.equal(redacted.config.jdc.user_identity, REDACTED);
+  assert.equal(redacted.config.translator.user_identity, REDACTED);
+  assert.equal(redacted.config.jdc.coinbase_reward_address, 'bc1qpayout');
+  assert.equal(redacted.config.pool, null);
+});
+
+test('paths that do not match the response shape are ignored', () => {
+  const redacted = redactJson(SV1_CLIENTS, parseRedactionPaths('extended_channels[].user_identity,total.value'));
+  assert.deepEqual(redacted, SV1_CLIENTS);
+});
+
+test('redactors only redact for viewers, e.g. worker names in events, alerts and raw logs', () => {
+  const redact = createRedactor(parseRedactionPaths('events[].detail,transitions[].message,lines[].message'));
+  const request = (role: string) => ({ headers: {}, res: { locals: { role } } }) as never;
+  const body = {
+    events: [{ id: '1', kind: 'best-share', title: 'New best share', detail: 'bc1qexample.rig1', at: '2026-01-01T00:00:00.000Z' }],
+    transitions: [{ rule: 'offline', message: 'bc1qexample.rig1 stopped submitting shares' }],
+    lines: [{ container: 'sv2-translator', message: 'bc1qexample.rig1 connected' }],
+  };
+
+  const viewer = redact(request('viewer'), body);
+  assert.equal(viewer.events[0].detail, REDACTED);
+  assert.equal(viewer.events[0].title, 'New best share');
+  assert.equal(viewer.transitions[0].message, REDACTED);
+  assert.equal(viewer.lines[0].message, REDACTED);
+  assert.equal(redact(request('operator'), body), body);
+  assert.equal(redact(request('admin'), body), body);
+  assert.equal(createRedactor([])(request('viewer'), body), body);
+});
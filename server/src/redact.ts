@@ -0,0 +1,122 @@
+/**
+ * Redaction of sensitive fields for viewer-role requests.
+ *
+ * REDACT_PATHS lists JSON paths separated by commas. Path segments are
+ * separated by dots, `[]` walks every element of an array and `*` matches
+ * every key of an object, e.g.
+ *
+ *   REDACT_PATHS=items[].user_identity,config.jdc.coinbase_reward_address
+ *
+ * Each path is tried against every redactable response, so paths that don't
+ * match a given response shape are simply ignored.
+ *
+ * Redactable responses are the summaries, proxied and decoded monitoring
+ * reads, config, best shares, events, alerts, miner labels and history,
+ * vardiff, federation, share links, the summary and realtime streams and
+ * gRPC. Log lines and history export samples are redacted one at a time, so
+ * a path like `message` or `key` applies to each of them. Realtime events
+ * and alerts are redacted as entries of /api/events and /api/alerts, so
+ * `events[].detail` covers both. Badges and the Atom feed only carry stack
+ * totals.
+ */
+
+import type { Request } from 'express';
+import { getRequestRole } from './auth.js';
+
+export const REDACTED = '[redacted]';
+
+export type RedactionPath = string[];
+
+export function parseRedactionPath(path: string): RedactionPath {
+  return path
+    .trim()
+    .replace(/\[\]/g, '.[]')
+    .split('.')
+    .filter((segment) => segment.length > 0);
+}
+
+export function parseRedactionPaths(value: string | undefined): RedactionPath[] {
+  return (value ?? '')
+    .split(',')
+    .map(parseRedactionPath)
+    .filter((path) => path.length > 0);
+}
+
+function redactInPlace(node: unknown, segments: RedactionPath): void {
+  if (segments.length === 0 || node === null || typeof node !== 'object') {
+    return;
+  }
+
+  const [head, ...rest] = segments;
+
+  if (head === '[]') {
+    if (!Array.isArray(node)) return;
+    for (let index = 0; index < node.length; index += 1) {
+      if (rest.length === 0) {
+        node[index] = REDACTED;
+      } else {
+        redactInPlace(node[index], rest);
+      }
+    }
+    return;
+  }
+
+  if (Array.isArray(node)) return;
+
+  const record = node as Record<string, unknown>;
+  const keys = head === '*' ? Object.keys(record) : (head in record ? [head] : []);
+
+  for (const key of keys) {
+    if (rest.length === 0) {
+      if (record[key] !== null && record[key] !== undefined) {
+        record[key] = REDACTED;
+      }
+    } else {
+      redactInPlace(record[key], rest);
+    }
+  }
+}
+
+/**
+ * Return a copy of `value` with every field matched by `paths` replaced.
+ */
+export function redactJson<T>(value: T, paths: RedactionPath[]): T {
+  if (paths.length === 0) {
+    return value;
+  }
+
+  const copy = structuredClone(value);
+  for (const path of paths) {
+    redactInPlace(copy, path);
+  }
+  return copy;
+}
+
+const REDACTION_PATHS = parseRedactionPaths(process.env.REDACT_PATHS);
+
+export function shouldRedact(req: Request): boolean {
+  return REDACTION_PATHS.length > 0 && getRequestRole(req) === 'viewer';
+}
+
+export type Redactor = <T>(req: Request, body: T) => T;
+
+/**
+ * Redact response bodies of viewer requests with `paths`; routes take one of
+ * these so tests can pick the paths.
+ */
+export function createRedactor(paths: RedactionPath[]): Redactor {
+  return (req, body) => (paths.length > 0 && getRequestRole(req) === 'viewer' ? redactJson(body, paths) : body);
+}
+
+/**
+ * Redact a response body when the request comes from a viewer.
+ */
+export const redactForRequest: Redactor = createRedactor(REDACTION_PATHS);
+
+/**
+ * Redact a body served without any credentials (e.g. share links), which is
+ * always treated like a viewer request.
+ */
+export function redactForPublic<T>(body: T): T {
+  return redactJson(body, REDACTION_PATHS);
+}
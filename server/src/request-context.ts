@@ -0,0 +1,68 @@
+/**
+ * Request IDs, so a blank dashboard panel can be traced to the upstream call
+ * that failed behind it.
+ *
+ * Every request gets an ID, reusing a well-formed incoming X-Request-Id so
+ * callers can correlate their own logs. It's returned in the X-Request-Id
+ * response header, starts every line the server logs while handling the
+ * request (see logger.ts), and is sent on as X-Request-Id to the monitoring
+ * APIs and federated sites the request reads.
+ */
+
+import { AsyncLocalStorage } from 'async_hooks';
+import crypto from 'crypto';
+import type { RequestHandler } from 'express';
+
+export const REQUEST_ID_HEADER = 'X-Request-Id';
+
+const REQUEST_ID_PATTERN = /^[\w.:-]{1,128}$/;
+
+const requestContext = new AsyncLocalStorage<string>();
+
+/**
+ * Reuse a well-formed incoming X-Request-Id, otherwise mint a fresh one.
+ */
+export function resolveRequestId(incoming: string | string[] | undefined): string {
+  const value = Array.isArray(incoming) ? incoming[0] : incoming;
+  return value && REQUEST_ID_PATTERN.test(value) ? value : crypto.randomUUID();
+}
+
+/**
+ * The ID of the request being handled, if any.
+ */
+export function getRequestId(): string | undefined {
+  return requestContext.getStore();
+}
+
+/**
+ * The header that carries the current request's ID to an upstream.
+ */
+export function getRequestIdHeaders(): Record<string, string> {
+  const requestId = getRequestId();
+  return requestId ? { [REQUEST_ID_HEADER]: requestId } : {};
+}
+
+export function runWithRequestId<T>(requestId: string, fn: () => T): T {
+  return requestContext.run(requestId, fn);
+}
+
+/**
+ * Run `fn` as background work, even when a request started it.
+ */
+export function runOutsideRequest<T>(fn: () => T): T {
+  return requestContext.exit(fn);
+}
+
+/**
+ * Give the request its ID. Reading the body loses the async context, so the
+ * app mounts this again after express.json(), and the second pass keeps the
+ * ID of the first.
+ */
+export function createRequestIdMiddleware(): RequestHandler {
+  return (req, res, next) => {
+    const requestId = (res.locals.requestId as string | undefined) ?? resolveRequestId(req.headers['x-request-id']);
+    res.locals.requestId = requestId;
+    res.set(REQUEST_ID_HEADER, requestId);
+    requestContext.run(requestId, next);
+  };
+}
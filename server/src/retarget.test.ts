@@ -0,0 +1,59 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import type { BitcoinChainStats } from './docker.js';
+import { estimateRetarget, expectedSecondsToBlock, getBlockSubsidySats } from './retarget.js';
+
+const EPOCH_START_TIME = 1_700_000_000;
+
+function stats(overrides: Partial<BitcoinChainStats> = {}): BitcoinChainStats {
+  // 1008 blocks into the epoch at 9 minutes each.
+  return {
+    chain: 'main',
+    height: 866_880 + 1008,
+    difficulty: 100e12,
+    tipTime: EPOCH_START_TIME + 1008 * 540,
+    epochStartHeight: 866_880,
+    epochStartTime: EPOCH_START_TIME,
+    ...overrides,
+  };
+}
+
+test('projects the retarget from the epoch average block time', () => {
+  const now = new Date((EPOCH_START_TIME + 1008 * 540) * 1000);
+  const estimate = estimateRetarget(stats(), 0, now);
+
+  assert.equal(estimate.nextRetargetHeight, 866_880 + 2016);
+  assert.equal(estimate.blocksRemaining, 1008);
+  assert.equal(estimate.averageBlockSecs, 540);
+  assert.equal(estimate.secondsRemaining, 1008 * 540);
+  assert.ok(Math.abs(estimate.projectedChangePercent - 11.11) < 0.01);
+  assert.equal(estimate.impact, null);
+});
+
+test('clamps the adjustment to a factor of four', () => {
+  const estimate = estimateRetarget(stats({ tipTime: EPOCH_START_TIME + 1008 * 60 }), 0);
+  assert.equal(estimate.projectedDifficulty, 400e12);
+});
+
+test('treats a fresh epoch as on target', () => {
+  const estimate = estimateRetarget(stats({ height: 866_880, tipTime: EPOCH_START_TIME }), 0);
+  assert.equal(estimate.projectedChangePercent, 0);
+  assert.equal(estimate.blocksRemaining, 2016);
+});
+
+test('estimates the impact on time to block and subsidy earnings', () => {
+  const hashrate = 1e15;
+  const { impact } = estimateRetarget(stats(), hashrate);
+  assert.ok(impact);
+  assert.equal(impact.expectedBlockSecs, expectedSecondsToBlock(100e12, hashrate));
+  assert.ok(impact.projectedExpectedBlockSecs > impact.expectedBlockSecs);
+  assert.equal(impact.subsidySats, 312_500_000);
+  assert.ok(Math.abs(impact.projectedSubsidySatsPerDay / impact.subsidySatsPerDay - 0.9) < 1e-9);
+});
+
+test('halves the subsidy every 210,000 blocks', () => {
+  assert.equal(getBlockSubsidySats(0), 5_000_000_000);
+  assert.equal(getBlockSubsidySats(839_999), 625_000_000);
+  assert.equal(getBlockSubsidySats(840_000), 312_500_000);
+  assert.equal(getBlockSubsidySats(64 * 210_000), 0);
+});
@@ -0,0 +1,106 @@
+/**
+ * Difficulty retarget countdown and its effect on the miner's expectations.
+ *
+ * The projection assumes the rest of the epoch keeps the average block time
+ * seen so far, the same estimate block explorers show. Expected earnings count
+ * the block subsidy only; fees vary too much to project.
+ */
+
+import type { BitcoinChainStats } from './docker.js';
+
+export const RETARGET_INTERVAL_BLOCKS = 2016;
+const TARGET_BLOCK_SECS = 600;
+// Consensus limits a single adjustment to a factor of 4 either way.
+const MAX_ADJUSTMENT_FACTOR = 4;
+const HALVING_INTERVAL_BLOCKS = 210_000;
+const INITIAL_SUBSIDY_SATS = 50 * 100_000_000;
+// Hashes per unit of difficulty.
+const TWO_POW_32 = 2 ** 32;
+
+export interface RetargetImpact {
+  hashrate: number;
+  expectedBlockSecs: number;
+  projectedExpectedBlockSecs: number;
+  blocksPerDay: number;
+  projectedBlocksPerDay: number;
+  subsidySats: number;
+  subsidySatsPerDay: number;
+  projectedSubsidySatsPerDay: number;
+}
+
+export interface RetargetEstimate {
+  chain: string;
+  height: number;
+  difficulty: number;
+  nextRetargetHeight: number;
+  blocksRemaining: number;
+  averageBlockSecs: number;
+  secondsRemaining: number;
+  estimatedRetargetAt: string;
+  projectedDifficulty: number;
+  projectedChangePercent: number;
+  // null without hashrate to project from.
+  impact: RetargetImpact | null;
+}
+
+export function getBlockSubsidySats(height: number): number {
+  const halvings = Math.floor(height / HALVING_INTERVAL_BLOCKS);
+  return halvings >= 64 ? 0 : Math.floor(INITIAL_SUBSIDY_SATS / 2 ** halvings);
+}
+
+/**
+ * Expected seconds between blocks for `hashrate` (H/s) at `difficulty`.
+ */
+export function expectedSecondsToBlock(difficulty: number, hashrate: number): number {
+  return (difficulty * TWO_POW_32) / hashrate;
+}
+
+export function estimateRetarget(stats: BitcoinChainStats, hashrate: number, now = new Date()): RetargetEstimate {
+  const nextRetargetHeight = stats.epochStartHeight + RETARGET_INTERVAL_BLOCKS;
+  const blocksRemaining = nextRetargetHeight - stats.height;
+  const blocksMined = stats.height - stats.epochStartHeight;
+  const averageBlockSecs = blocksMined > 0
+    ? Math.max(1, (stats.tipTime - stats.epochStartTime) / blocksMined)
+    : TARGET_BLOCK_SECS;
+
+  const factor = Math.min(MAX_ADJUSTMENT_FACTOR, Math.max(1 / MAX_ADJUSTMENT_FACTOR, TARGET_BLOCK_SECS / averageBlockSecs));
+  const projectedDifficulty = stats.difficulty * factor;
+
+  // The next retarget follows the last block of the epoch, counted from the tip.
+  const retargetAtMs = stats.tipTime * 1000 + blocksRemaining * averageBlockSecs * 1000;
+  const secondsRemaining = Math.max(0, Math.round((retargetAtMs - now.getTime()) / 1000));
+
+  let impact: RetargetImpact | null = null;
+  if (hashrate > 0) {
+    const expectedBlockSecs = expectedSecondsToBlock(stats.difficulty, hashrate);
+    const projectedExpectedBlockSecs = expectedSecondsToBlock(projectedDifficulty, hashrate);
+    const subsidySats = getBlockSubsidySats(nextRetargetHeight);
+    const blocksPerDay = 86_400 / expectedBlockSecs;
+    const projectedBlocksPerDay = 86_400 / projectedExpectedBlockSecs;
+
+    impact = {
+      hashrate,
+      expectedBlockSecs,
+      projectedExpectedBlockSecs,
+      blocksPerDay,
+      projectedBlocksPerDay,
+      subsidySats,
+      subsidySatsPerDay: blocksPerDay * subsidySats,
+      projectedSubsidySatsPerDay: projectedBlocksPerDay * subsidySats,
+    };
+  }
+
+  return {
+    chain: stats.chain,
+    height: stats.height,
+    difficulty: stats.difficulty,
+    nextRetargetHeight,
+    blocksRemaining,
+    averageBlockSecs,
+    secondsRemaining,
+    estimatedRetargetAt: new Date(Math.max(retargetAtMs, now.getTime())).toISOString(),
+    projectedDifficulty,
+    projectedChangePercent: (factor - 1) * 100,
+    impact,
+  };
+}
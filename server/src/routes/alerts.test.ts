@@ -0,0 +1,44 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import http from 'node:http';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import express from 'express';
+import { createAlertEngine, createAlertRuleStore, DEFAULT_ALERT_RULES } from '../alerts.js';
+import { createApp } from '../app.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { createAlertRoutes } from './alerts.js';
+
+test('lists alert states and replaces the rules', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-alert-routes-'));
+  const router = express.Router();
+  router.use(createAlertRoutes({
+    alerts: createAlertEngine(),
+    alertRules: createAlertRuleStore(path.join(dir, 'alerts.json')),
+  }));
+  const app = createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) });
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = `http://127.0.0.1:${port}/api/alerts`;
+
+  const initial = await (await fetch(url)).json();
+  assert.deepEqual(initial.alerts.map((alert: { rule: { id: string } }) => alert.rule.id), DEFAULT_ALERT_RULES.map((rule) => rule.id));
+  assert.deepEqual(initial.transitions, []);
+
+  const put = (body: unknown) => fetch(`${url}/rules`, {
+    method: 'PUT',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(body),
+  });
+  assert.equal((await put({ rules: [{ kind: 'hashrate-below', threshold: -1 }] })).status, 400);
+
+  const saved = await put({ rules: [{ id: 'low', kind: 'hashrate-below', threshold: 1e12, forMinutes: 5 }] });
+  assert.equal(saved.status, 200);
+  assert.deepEqual((await (await fetch(`${url}/rules`)).json()).rules, [
+    { id: 'low', kind: 'hashrate-below', threshold: 1e12, forMinutes: 5 },
+  ]);
+  assert.deepEqual((await (await fetch(url)).json()).alerts.map((alert: { status: string }) => alert.status), ['ok']);
+});
@@ -0,0 +1,75 @@
+/**
+ * Alert routes: the status of each rule on the local stack and the rules
+ * themselves (see alerts.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { parseAlertRules } from '../alerts.js';
+import type { AlertEngine, AlertRuleStore } from '../alerts.js';
+import { requireAdmin } from '../auth.js';
+import { createLogger } from '../logger.js';
+import { redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+
+const log = createLogger('routes/alerts');
+
+export interface AlertRouteDeps {
+  alerts: Pick<AlertEngine, 'list' | 'anomalies' | 'transitions'>;
+  alertRules: Pick<AlertRuleStore, 'get' | 'save'>;
+  redact?: Redactor;
+}
+
+export function createAlertRoutes({ alerts, alertRules, redact = redactForRequest }: AlertRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/alerts - Current status of each alert rule, active share anomalies, and recent transitions
+   */
+  router.get('/api/alerts', async (req, res) => {
+    try {
+      res.json(redact(req, {
+        alerts: alerts.list(await alertRules.get()),
+        anomalies: alerts.anomalies(),
+        transitions: alerts.transitions(),
+      }));
+    } catch (error) {
+      log.error('Alerts error:', error);
+      res.status(500).json({ error: 'Failed to get alerts' });
+    }
+  });
+
+  /**
+   * GET /api/alerts/rules - Configured alert rules
+   */
+  router.get('/api/alerts/rules', async (_req, res) => {
+    try {
+      res.json({ rules: await alertRules.get() });
+    } catch (error) {
+      log.error('Alert rules error:', error);
+      res.status(500).json({ error: 'Failed to get alert rules' });
+    }
+  });
+
+  /**
+   * PUT /api/alerts/rules - Replace the alert rules (admin only)
+   */
+  router.put('/api/alerts/rules', requireAdmin, async (req, res) => {
+    let rules;
+    try {
+      rules = parseAlertRules(req.body);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid alert rules' });
+    }
+
+    try {
+      await alertRules.save(rules);
+      res.json({ rules });
+    } catch (error) {
+      log.error('Alert rules error:', error);
+      res.status(500).json({ error: 'Failed to save alert rules' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,61 @@
+/**
+ * The API explorer and the OpenAPI document behind it (see api-explorer.ts).
+ */
+
+import express from 'express';
+import type { RequestHandler, Router } from 'express';
+import { buildApiDocument, renderApiExplorer } from '../api-explorer.js';
+import type { OpenApiDocument } from '../api-explorer.js';
+import { createLogger } from '../logger.js';
+import { addCspNonce, createCspNonce } from '../security-headers.js';
+
+const log = createLogger('routes/api-explorer');
+
+export interface ApiExplorerRouteDeps {
+  // The router whose routes are documented; read on every request.
+  router: Router;
+  loadMonitoringSpec: () => Promise<OpenApiDocument | null>;
+  basePath: string;
+}
+
+export function createApiExplorerRoutes({ router: documented, loadMonitoringSpec, basePath }: ApiExplorerRouteDeps): Router {
+  const router = express.Router();
+
+  const getDocument = async () => buildApiDocument(documented, await loadMonitoringSpec());
+
+  /**
+   * GET /api/openapi.json - OpenAPI document for the server's routes and the proxied monitoring APIs
+   */
+  router.get('/api/openapi.json', async (_req, res) => {
+    try {
+      res.json(await getDocument());
+    } catch (error) {
+      log.error('OpenAPI error:', error);
+      res.status(500).json({ error: 'Failed to build OpenAPI document' });
+    }
+  });
+
+  const renderExplorer: RequestHandler = async (_req, res) => {
+    try {
+      const nonce = createCspNonce();
+      const csp = res.get('Content-Security-Policy');
+      if (csp) res.set('Content-Security-Policy', addCspNonce(csp, nonce));
+      res.set('Cache-Control', 'no-cache').type('html').send(renderApiExplorer(await getDocument(), { basePath, nonce }));
+    } catch (error) {
+      log.error('API explorer error:', error);
+      res.status(500).json({ error: 'Failed to render API explorer' });
+    }
+  };
+
+  /**
+   * GET /api - API explorer with a try-it form per endpoint
+   */
+  router.get('/api', renderExplorer);
+
+  /**
+   * GET /docs - The API explorer, at the path integrators look for first
+   */
+  router.get('/docs', renderExplorer);
+
+  return router;
+}
@@ -0,0 +1,73 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import http from 'node:http';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import express from 'express';
+import { createApiKeyQuotaMiddleware, createApiKeyStore, createApiKeyUsageTracker } from '../api-keys.js';
+import { createApp } from '../app.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { createApiKeyRoutes } from './api-keys.js';
+
+async function createKeysApp(t: { after: (fn: () => void) => void }): Promise<string> {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-api-keys-routes-'));
+  const apiKeys = createApiKeyStore(path.join(dir, 'api-keys.json'));
+  const usage = createApiKeyUsageTracker();
+
+  const router = express.Router();
+  router.use(createApiKeyQuotaMiddleware(apiKeys, usage));
+  router.use(createApiKeyRoutes({ apiKeys, usage }));
+  const app = createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) });
+
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  return `http://127.0.0.1:${port}/api/admin/keys`;
+}
+
+test('creates keys, reports their usage and revokes them', async (t) => {
+  const url = await createKeysApp(t);
+
+  const invalid = await fetch(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify({}),
+  });
+  assert.equal(invalid.status, 400);
+  assert.match((await invalid.json()).error, /name is required/);
+
+  const created = await fetch(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify({ name: 'Grafana', requestsPerDay: 100 }),
+  });
+  assert.equal(created.status, 201);
+  const { key, token } = await created.json();
+  assert.match(token, /^sv2k_/);
+  assert.equal(key.tokenHash, undefined);
+
+  const listed = await (await fetch(url)).json();
+  assert.deepEqual(listed.keys.map((entry: { id: string }) => entry.id), [key.id]);
+
+  const usage = await fetch(`${url}/${key.id}/usage`);
+  assert.equal(usage.status, 200);
+  assert.equal((await usage.json()).requestsPerDay, 100);
+
+  assert.equal((await fetch(`${url}/${key.id}`, { method: 'DELETE' })).status, 200);
+  assert.equal((await fetch(`${url}/${key.id}`, { method: 'DELETE' })).status, 404);
+  assert.equal((await fetch(`${url}/${key.id}/usage`)).status, 404);
+});
+
+test('API keys are viewers and cannot manage keys', async (t) => {
+  const url = await createKeysApp(t);
+  const { token } = await (await fetch(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify({ name: 'Grafana' }),
+  })).json();
+
+  const response = await fetch(url, { headers: { Authorization: `Bearer ${token}` } });
+  assert.equal(response.status, 403);
+});
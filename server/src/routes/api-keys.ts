@@ -0,0 +1,88 @@
+/**
+ * Admin routes for API keys (see api-keys.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { describeApiKey, parseApiKeyRequest } from '../api-keys.js';
+import type { ApiKeyStore, ApiKeyUsageTracker } from '../api-keys.js';
+import { requireAdmin } from '../auth.js';
+import { createLogger } from '../logger.js';
+
+const log = createLogger('routes/api-keys');
+
+export interface ApiKeyRouteDeps {
+  apiKeys: ApiKeyStore;
+  usage: ApiKeyUsageTracker;
+}
+
+export function createApiKeyRoutes({ apiKeys, usage }: ApiKeyRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/admin/keys - List API keys and their quotas (admin only)
+   */
+  router.get('/api/admin/keys', requireAdmin, async (_req, res) => {
+    try {
+      res.json({ keys: (await apiKeys.list()).map(describeApiKey) });
+    } catch (error) {
+      log.error('API keys error:', error);
+      res.status(500).json({ error: 'Failed to list API keys' });
+    }
+  });
+
+  /**
+   * POST /api/admin/keys - Create a viewer API key with request and stream quotas (admin only)
+   */
+  router.post('/api/admin/keys', requireAdmin, async (req, res) => {
+    let request;
+    try {
+      request = parseApiKeyRequest(req.body);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid API key request' });
+    }
+
+    try {
+      const { key, token } = await apiKeys.create(request);
+      // The token can't be recovered later; only its hash is stored.
+      res.status(201).json({ key: describeApiKey(key), token });
+    } catch (error) {
+      log.error('API key error:', error);
+      res.status(500).json({ error: 'Failed to create API key' });
+    }
+  });
+
+  /**
+   * GET /api/admin/keys/:id/usage - Requests today and open streams for an API key (admin only)
+   */
+  router.get('/api/admin/keys/:id/usage', requireAdmin, async (req, res) => {
+    try {
+      const key = await apiKeys.get(req.params.id);
+      if (!key) {
+        return res.status(404).json({ error: 'API key not found' });
+      }
+      res.json(usage.getUsage(key));
+    } catch (error) {
+      log.error('API key usage error:', error);
+      res.status(500).json({ error: 'Failed to load API key usage' });
+    }
+  });
+
+  /**
+   * DELETE /api/admin/keys/:id - Revoke an API key (admin only)
+   */
+  router.delete('/api/admin/keys/:id', requireAdmin, async (req, res) => {
+    try {
+      if (!(await apiKeys.revoke(req.params.id))) {
+        return res.status(404).json({ error: 'API key not found' });
+      }
+      usage.forget(req.params.id);
+      res.json({ success: true });
+    } catch (error) {
+      log.error('API key error:', error);
+      res.status(500).json({ error: 'Failed to revoke API key' });
+    }
+  });
+
+  return router;
+}
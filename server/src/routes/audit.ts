@@ -0,0 +1,42 @@
+/**
+ * The audit log of mutating requests proxied to the monitoring APIs
+ * (see audit.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { parseAuditQuery } from '../audit.js';
+import type { AuditLog } from '../audit.js';
+import { requireAdmin } from '../auth.js';
+import { createLogger } from '../logger.js';
+
+const log = createLogger('routes/audit');
+
+export interface AuditRouteDeps {
+  auditLog: Pick<AuditLog, 'query'>;
+}
+
+export function createAuditRoutes({ auditLog }: AuditRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/audit-log?service=translator|jdc&since=<iso>&limit=<n> - Mutating proxied requests, newest first (admin only)
+   */
+  router.get('/api/audit-log', requireAdmin, async (req, res) => {
+    let query;
+    try {
+      query = parseAuditQuery(req.query);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid query' });
+    }
+
+    try {
+      res.json({ entries: await auditLog.query(query) });
+    } catch (error) {
+      log.error('Audit log error:', error);
+      res.status(500).json({ error: 'Failed to get audit log' });
+    }
+  });
+
+  return router;
+}
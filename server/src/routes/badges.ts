@@ -0,0 +1,72 @@
+/**
+ * Embeddable badges and summary cards (see badges.ts), for forum
+ * signatures, READMEs, and status pages.
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { BADGE_METRICS, isBadgeMetric, renderBadgeSvg, renderSummaryCardPng, renderSummaryCardSvg } from '../badges.js';
+import { createLogger } from '../logger.js';
+import { redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+import type { StackSummary } from '../summary.js';
+
+const log = createLogger('routes/badges');
+
+// Badges are meant to be hot-linked, so let caches hold them briefly.
+const BADGE_CACHE_CONTROL = 'public, max-age=60';
+
+export interface BadgeRouteDeps {
+  getStackSummary: () => Promise<StackSummary>;
+  redact?: Redactor;
+}
+
+export function createBadgeRoutes({ getStackSummary, redact = redactForRequest }: BadgeRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /badge/:metric.svg - Embeddable badge for a single stat (status, hashrate, workers, ...)
+   */
+  router.get('/badge/:metric.svg', async (req, res) => {
+    const { metric } = req.params;
+    if (!isBadgeMetric(metric)) {
+      return res.status(404).json({ error: `Unknown badge "${metric}"`, available: Object.keys(BADGE_METRICS) });
+    }
+
+    try {
+      const summary = redact(req, await getStackSummary());
+      res.set('Cache-Control', BADGE_CACHE_CONTROL).type('image/svg+xml').send(renderBadgeSvg(BADGE_METRICS[metric](summary)));
+    } catch (error) {
+      log.error('Badge error:', error);
+      res.status(500).json({ error: 'Failed to render badge' });
+    }
+  });
+
+  /**
+   * GET /card/summary.svg - Embeddable summary card with the headline dashboard stats
+   */
+  router.get('/card/summary.svg', async (req, res) => {
+    try {
+      const summary = redact(req, await getStackSummary());
+      res.set('Cache-Control', BADGE_CACHE_CONTROL).type('image/svg+xml').send(renderSummaryCardSvg(summary));
+    } catch (error) {
+      log.error('Card error:', error);
+      res.status(500).json({ error: 'Failed to render card' });
+    }
+  });
+
+  /**
+   * GET /card/summary.png - Summary card as a PNG, for sites that strip SVG
+   */
+  router.get('/card/summary.png', async (req, res) => {
+    try {
+      const summary = redact(req, await getStackSummary());
+      res.set('Cache-Control', BADGE_CACHE_CONTROL).type('png').send(renderSummaryCardPng(summary));
+    } catch (error) {
+      log.error('Card error:', error);
+      res.status(500).json({ error: 'Failed to render card' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,40 @@
+/**
+ * Setup wizard checks against the local Bitcoin Core node: its IPC socket,
+ * and RPC nodes found in the default data directories.
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { requireOperator } from '../auth.js';
+import type { BitcoinRpcDiscoveryResult, BitcoinSocketValidationResult } from '../docker.js';
+
+export interface BitcoinRouteDeps {
+  // Probes a socket path on the host, where ~ is the host user's home.
+  probeSocket: (socketPath: string) => Promise<BitcoinSocketValidationResult>;
+  discoverRpc: () => Promise<BitcoinRpcDiscoveryResult[]>;
+}
+
+export function createBitcoinRoutes({ probeSocket, discoverRpc }: BitcoinRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * POST /api/validate/bitcoin-socket - Check if a Bitcoin Core IPC socket is listening (operator or admin)
+   */
+  router.post('/api/validate/bitcoin-socket', requireOperator, async (req, res) => {
+    const { socket_path } = req.body;
+    if (!socket_path || typeof socket_path !== 'string') {
+      return res.status(400).json({ valid: false, error: 'socket_path is required' });
+    }
+
+    return res.json(await probeSocket(socket_path));
+  });
+
+  /**
+   * GET /api/validate/bitcoin-rpc - Auto-discover Bitcoin Core RPC nodes
+   */
+  router.get('/api/validate/bitcoin-rpc', async (_req, res) => {
+    return res.json(await discoverRpc());
+  });
+
+  return router;
+}
@@ -0,0 +1,47 @@
+/**
+ * Pre-deployment checks of the JDC's coinbase reward script (see coinbase-script.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { SUPPORTED_NETWORKS } from '@sv2-ui/shared';
+import type { BitcoinNetwork } from '@sv2-ui/shared';
+import { validateCoinbaseScript } from '../coinbase-script.js';
+import { createLogger } from '../logger.js';
+
+const log = createLogger('routes/coinbase-script');
+
+export interface CoinbaseScriptRouteDeps {
+  // The configured stack's network, used when the request doesn't name one.
+  getNetwork: () => Promise<BitcoinNetwork | null>;
+}
+
+export function createCoinbaseScriptRoutes({ getNetwork }: CoinbaseScriptRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * POST /api/v1/validate/coinbase-script - Parse an addr()/raw() descriptor or address into its scriptPubKey and address type
+   */
+  router.post('/api/v1/validate/coinbase-script', async (req, res) => {
+    try {
+      const { script, network: requested } = (req.body ?? {}) as { script?: unknown; network?: unknown };
+      if (typeof script !== 'string') {
+        return res.status(400).json({ error: 'script must be a string' });
+      }
+      if (requested !== undefined && !SUPPORTED_NETWORKS.includes(requested as BitcoinNetwork)) {
+        return res.status(400).json({ error: `network must be one of ${SUPPORTED_NETWORKS.join(', ')}` });
+      }
+
+      const network = (requested as BitcoinNetwork | undefined) ?? await getNetwork();
+      if (!network) {
+        return res.status(400).json({ error: 'network is required until a JD stack is configured' });
+      }
+      res.json({ network, ...validateCoinbaseScript(script, network) });
+    } catch (error) {
+      log.error('Coinbase script validation error:', error);
+      res.status(500).json({ error: 'Failed to validate coinbase script' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,110 @@
+/**
+ * Diagnostics over the collated translator and JDC logs: known problems
+ * found in them (see logs/diagnostics.ts), the raw lines, and the trace of
+ * one share (see logs/share-trace.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { requireAdmin } from '../auth.js';
+import { createLogger } from '../logger.js';
+import { getLogDiagnostics, getLogStreams, readCollatedLogLines } from '../logs/diagnostics.js';
+import type { LogProvider } from '../logs/diagnostics.js';
+import { parseShareTraceQuery, traceShare } from '../logs/share-trace.js';
+import type { StackData } from '../monitoring.js';
+import { redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+import type { SavedState } from '../state.js';
+
+const log = createLogger('routes/diagnostics');
+
+const DEFAULT_RAW_TAIL = 200;
+const MAX_RAW_TAIL = 500;
+
+export interface DiagnosticRouteDeps {
+  loadState: () => Promise<SavedState>;
+  // Reads one container's log; the whole of it when no tail is given.
+  read: LogProvider;
+  getStackData: (state: SavedState) => Promise<StackData>;
+  redact?: Redactor;
+}
+
+export function createDiagnosticRoutes({ loadState, read, getStackData, redact = redactForRequest }: DiagnosticRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/logs/diagnostics - Get collated log diagnostics for the deployed stack
+   */
+  router.get('/api/logs/diagnostics', async (req, res) => {
+    try {
+      const state = await loadState();
+      const response = await getLogDiagnostics(state.mode, state.configured, read);
+      res.json(redact(req, response));
+    } catch (error) {
+      log.error('Log diagnostics error:', error);
+      res.status(500).json({ error: 'Failed to get log diagnostics' });
+    }
+  });
+
+  /**
+   * GET /api/logs/raw - Get raw collated log lines for the deployed stack
+   * Query params:
+   *   ?tail=N  max lines per container (default 200, capped at 500)
+   */
+  router.get('/api/logs/raw', async (req, res) => {
+    try {
+      const state = await loadState();
+      const tailStr = req.query.tail as string;
+      let lines: Awaited<ReturnType<typeof readCollatedLogLines>>;
+
+      if (tailStr === 'all') {
+        // Pull full history since container start by ignoring the per-container
+        // tail cap applied inside readCollatedLogLines.
+        lines = await readCollatedLogLines(state.mode, (container) => read(container));
+      } else {
+        const tailParam = parseInt(tailStr, 10);
+        const tail = Number.isFinite(tailParam) ? Math.min(Math.max(tailParam, 1), MAX_RAW_TAIL) : DEFAULT_RAW_TAIL;
+        lines = await readCollatedLogLines(state.mode, (container, opts) => read(container, { ...opts, tail }));
+      }
+
+      res.json(redact(req, {
+        configured: state.configured,
+        mode: state.mode,
+        generatedAt: new Date().toISOString(),
+        streams: getLogStreams(state.mode),
+        lines,
+      }));
+    } catch (error) {
+      log.error('Raw logs error:', error);
+      res.status(500).json({ error: 'Failed to get container logs' });
+    }
+  });
+
+  /**
+   * GET /api/share-trace?worker=<name>&at=<iso>|jobId=<id>[&window=<secs>] - Trace one
+   * share through the service logs and upstream counters (admin only)
+   */
+  router.get('/api/share-trace', requireAdmin, async (req, res) => {
+    let query;
+    try {
+      query = parseShareTraceQuery(req.query);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid share trace query' });
+    }
+
+    try {
+      const state = await loadState();
+      // Full history, since the share may be older than the diagnostics window.
+      const [lines, data] = await Promise.all([
+        readCollatedLogLines(state.mode, (container) => read(container)),
+        getStackData(state),
+      ]);
+      res.json(traceShare(lines, data, query));
+    } catch (error) {
+      log.error('Share trace error:', error);
+      res.status(500).json({ error: 'Failed to trace share' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,38 @@
+/**
+ * Rendering of the sv2-apps `docker_env` file (see docker-env.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { renderDockerEnv, validateDockerEnvRequest } from '../docker-env.js';
+import { createLogger } from '../logger.js';
+
+const log = createLogger('routes/docker-env');
+
+export function createDockerEnvRoutes(): Router {
+  const router = express.Router();
+
+  /**
+   * POST /api/v1/docker-env - Validate setup wizard data and render the docker_env file; 400 with the report when invalid
+   */
+  router.post('/api/v1/docker-env', (req, res) => {
+    try {
+      const { input, report } = validateDockerEnvRequest(req.body);
+      if (!input) {
+        const [first] = report.errors;
+        return res.status(400).json({ error: `${first.field} ${first.message}`, report });
+      }
+
+      const env = renderDockerEnv(input);
+      res.format({
+        json: () => res.json({ env, report }),
+        text: () => res.attachment('docker_env').type('text/plain').send(env),
+      });
+    } catch (error) {
+      log.error('docker_env error:', error);
+      res.status(500).json({ error: 'Failed to render docker_env' });
+    }
+  });
+
+  return router;
+}
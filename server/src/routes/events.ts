@@ -0,0 +1,93 @@
+/**
+ * Event routes: recent notable events (see events.ts) as JSON and as an Atom
+ * feed, and the best shares kept across restarts (see best-shares.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { requireAdmin } from '../auth.js';
+import type { BestShareStore } from '../best-shares.js';
+import type { EventLog } from '../events.js';
+import { renderAtomFeed } from '../feed.js';
+import { createLogger } from '../logger.js';
+import { redactForPublic, redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+
+const log = createLogger('routes/events');
+
+export interface EventRouteDeps {
+  eventLog: Pick<EventLog, 'list'>;
+  bestShares: Pick<BestShareStore, 'list' | 'reset'>;
+  // The feed title, naming the pool and any test network.
+  getTitle: () => Promise<string>;
+  basePath?: string;
+  redact?: Redactor;
+}
+
+export function createEventRoutes({
+  eventLog,
+  bestShares,
+  getTitle,
+  basePath = '',
+  redact = redactForRequest,
+}: EventRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/events - Recent notable events (blocks found, best shares, outages)
+   */
+  router.get('/api/events', async (req, res) => {
+    try {
+      res.json(redact(req, { events: await eventLog.list() }));
+    } catch (error) {
+      log.error('Events error:', error);
+      res.status(500).json({ error: 'Failed to get events' });
+    }
+  });
+
+  /**
+   * GET /api/v1/best-shares - The best share difficulty per miner and overall, kept across restarts
+   */
+  router.get('/api/v1/best-shares', async (req, res) => {
+    try {
+      res.json(redact(req, await bestShares.list()));
+    } catch (error) {
+      log.error('Best shares error:', error);
+      res.status(500).json({ error: 'Failed to get best shares' });
+    }
+  });
+
+  /**
+   * DELETE /api/v1/best-shares - Forget every best share (admin only)
+   */
+  router.delete('/api/v1/best-shares', requireAdmin, async (_req, res) => {
+    try {
+      await bestShares.reset();
+      res.json({ success: true });
+    } catch (error) {
+      log.error('Best shares error:', error);
+      res.status(500).json({ error: 'Failed to reset best shares' });
+    }
+  });
+
+  /**
+   * GET /feed.xml - Atom feed of recent events
+   */
+  router.get('/feed.xml', async (req, res) => {
+    try {
+      const origin = `${req.protocol}://${req.get('host')}${basePath}`;
+      // Public, so redacted like a viewer's /api/events.
+      const { events } = redactForPublic({ events: await eventLog.list() });
+      res.type('application/atom+xml').send(renderAtomFeed(events, {
+        title: await getTitle(),
+        selfUrl: `${origin}/feed.xml`,
+        dashboardUrl: `${origin}/`,
+      }));
+    } catch (error) {
+      log.error('Feed error:', error);
+      res.status(500).json({ error: 'Failed to render feed' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,185 @@
+/**
+ * The fleet view over remote sv2-ui instances, and per-site drill-down
+ * (see federation.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { requireAdmin } from '../auth.js';
+import { parseFederationSite, parseFederationSites, requestSite, toPublicSite } from '../federation.js';
+import type { Federation, FederationRegistry, FederationSite } from '../federation.js';
+import { parseHistoryRange } from '../history-export.js';
+import { createLogger } from '../logger.js';
+import { redactForRequest } from '../redact.js';
+
+const log = createLogger('routes/federation');
+
+export interface FederationRouteDeps {
+  federation: Pick<Federation, 'fleet' | 'history' | 'checkHealth'>;
+  registry: FederationRegistry;
+  request?: (site: FederationSite, path: string) => Promise<Response>;
+}
+
+// Drill-down reaches the site's own API and its monitoring proxies, nothing else.
+const DRILL_DOWN_PREFIXES = ['api/', 'translator-api/', 'jdc-api/'];
+const RESOLUTION_PATTERN = /^(raw|\d+[smhd])$/;
+const FORWARDED_RESPONSE_HEADERS = ['content-type', 'content-disposition', 'x-cache'];
+
+export function createFederationRoutes({ federation, registry, request = requestSite }: FederationRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/federation - Every site's summary and the fleet totals
+   */
+  router.get('/api/v1/federation', async (req, res) => {
+    try {
+      res.json(redactForRequest(req, await federation.fleet()));
+    } catch (error) {
+      log.error('Federation error:', error);
+      res.status(500).json({ error: 'Failed to get the fleet' });
+    }
+  });
+
+  /**
+   * GET /api/v1/federation/history - Each site's hashrate history and the fleet total
+   */
+  router.get('/api/v1/federation/history', async (req, res) => {
+    const { range, resolution } = req.query;
+    if ([range, resolution].some((value) => value !== undefined && typeof value !== 'string')) {
+      return res.status(400).json({ error: 'range and resolution can only be given once' });
+    }
+    try {
+      parseHistoryRange(range as string | undefined);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : String(error) });
+    }
+    if (resolution !== undefined && !RESOLUTION_PATTERN.test(resolution as string)) {
+      return res.status(400).json({ error: 'resolution must be raw or a rollup resolution such as 1m or 1h' });
+    }
+
+    try {
+      res.json(await federation.history({ range: range as string | undefined, resolution: resolution as string | undefined }));
+    } catch (error) {
+      log.error('Federation history error:', error);
+      res.status(500).json({ error: 'Failed to get the fleet history' });
+    }
+  });
+
+  /**
+   * GET /api/v1/federation/sites - The federated sv2-ui instances, without their tokens
+   */
+  router.get('/api/v1/federation/sites', async (_req, res) => {
+    try {
+      res.json({ sites: (await registry.get()).map(toPublicSite) });
+    } catch (error) {
+      log.error('Federation sites error:', error);
+      res.status(500).json({ error: 'Failed to list federation sites' });
+    }
+  });
+
+  /**
+   * PUT /api/v1/federation/sites - Replace the federated sv2-ui instances (admin only)
+   */
+  router.put('/api/v1/federation/sites', requireAdmin, async (req, res) => {
+    let sites: FederationSite[];
+    try {
+      sites = parseFederationSites(req.body, await registry.get());
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid sites' });
+    }
+
+    try {
+      await registry.save(sites);
+      void federation.checkHealth();
+      res.json({ sites: sites.map(toPublicSite) });
+    } catch (error) {
+      log.error('Federation sites save error:', error);
+      res.status(500).json({ error: 'Failed to save federation sites' });
+    }
+  });
+
+  /**
+   * POST /api/v1/federation/sites - Register one sv2-ui instance (admin only)
+   */
+  router.post('/api/v1/federation/sites', requireAdmin, async (req, res) => {
+    let sites: FederationSite[];
+    let site: FederationSite;
+    try {
+      sites = await registry.get();
+      site = parseFederationSite(req.body);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid site' });
+    }
+    if (sites.some((entry) => entry.id === site.id)) {
+      return res.status(409).json({ error: `Federation site "${site.id}" already exists` });
+    }
+
+    try {
+      await registry.save([...sites, site]);
+      void federation.checkHealth();
+      res.status(201).json(toPublicSite(site));
+    } catch (error) {
+      log.error('Federation sites save error:', error);
+      res.status(500).json({ error: 'Failed to save federation sites' });
+    }
+  });
+
+  /**
+   * DELETE /api/v1/federation/sites/:id - Deregister one sv2-ui instance (admin only)
+   */
+  router.delete('/api/v1/federation/sites/:id', requireAdmin, async (req, res) => {
+    try {
+      const sites = await registry.get();
+      if (!sites.some((entry) => entry.id === req.params.id)) {
+        return res.status(404).json({ error: 'Federation site not found' });
+      }
+      await registry.save(sites.filter((entry) => entry.id !== req.params.id));
+      void federation.checkHealth();
+      res.json({ success: true });
+    } catch (error) {
+      log.error('Federation sites save error:', error);
+      res.status(500).json({ error: 'Failed to save federation sites' });
+    }
+  });
+
+  /**
+   * GET /api/v1/federation/sites/:id/* - Read one site's API or monitoring proxies through this instance
+   */
+  router.get('/api/v1/federation/sites/:id/*', async (req, res) => {
+    const rest = (req.params as Record<string, string>)[0] ?? '';
+    if (!DRILL_DOWN_PREFIXES.some((prefix) => rest.startsWith(prefix))) {
+      return res.status(404).json({ error: `Only ${DRILL_DOWN_PREFIXES.map((prefix) => `/${prefix}`).join(', ')} can be read through a site` });
+    }
+
+    let site: FederationSite | undefined;
+    try {
+      site = (await registry.get()).find((entry) => entry.id === req.params.id);
+    } catch (error) {
+      log.error('Federation sites error:', error);
+      return res.status(500).json({ error: 'Failed to list federation sites' });
+    }
+    if (!site) {
+      return res.status(404).json({ error: 'Federation site not found' });
+    }
+
+    const search = req.originalUrl.includes('?') ? req.originalUrl.slice(req.originalUrl.indexOf('?')) : '';
+    try {
+      const response = await request(site, `/${rest}${search}`);
+      for (const name of FORWARDED_RESPONSE_HEADERS) {
+        const value = response.headers.get(name);
+        if (value) res.set(name, value);
+      }
+      res.status(response.status);
+      // The site redacted for its token's role; redact again for this request's.
+      if (response.headers.get('content-type')?.includes('application/json')) {
+        res.json(redactForRequest(req, await response.json()));
+      } else {
+        res.send(Buffer.from(await response.arrayBuffer()));
+      }
+    } catch {
+      res.status(502).json({ error: `Cannot connect to site ${site.name}` });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,59 @@
+/**
+ * Health routes: the plain liveness check the UI has always used, and the
+ * per-service report (see health.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import type { SiteHealth } from '../federation.js';
+import type { HealthMonitor, HealthTarget } from '../health.js';
+import { createLogger } from '../logger.js';
+import type { Sv1ProbeResult } from '../sv1-listener.js';
+
+const log = createLogger('routes/health');
+
+export interface HealthRouteDeps {
+  healthMonitor: HealthMonitor;
+  getTargets: () => Promise<HealthTarget[]>;
+  isDockerAvailable: () => Promise<boolean>;
+  // The latest SV1 self-test; null when no local Translator runs.
+  getSv1Probe?: () => Promise<Sv1ProbeResult | null>;
+  // The last check of each federated sv2-ui instance.
+  getSiteHealth?: () => Promise<SiteHealth[]>;
+}
+
+export function createHealthRoutes({ healthMonitor, getTargets, isDockerAvailable, getSv1Probe, getSiteHealth }: HealthRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/health - Health check
+   */
+  router.get('/api/health', async (_req, res) => {
+    const dockerOk = await isDockerAvailable();
+    res.json({
+      status: 'ok',
+      docker: dockerOk,
+    });
+  });
+
+  /**
+   * GET /api/v1/health - Reachability, HTTP status and latency of every monitoring API, plus the SV1 self-test and federated sites; 503 unless all are healthy
+   */
+  router.get('/api/v1/health', async (_req, res) => {
+    try {
+      const [targets, docker, sv1, sites] = await Promise.all([
+        getTargets(),
+        isDockerAvailable(),
+        getSv1Probe?.() ?? null,
+        getSiteHealth?.() ?? [],
+      ]);
+      const report = await healthMonitor.check(targets, docker, sv1, sites);
+      res.status(report.status === 'ok' ? 200 : 503).set('Cache-Control', 'no-store').json(report);
+    } catch (error) {
+      log.error('Health error:', error);
+      res.status(500).json({ error: 'Failed to check health' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,36 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp } from '../app.js';
+import { parseHistoryRetention } from '../history.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { createRedactor, parseRedactionPaths, REDACTED } from '../redact.js';
+import { MINER_HASHRATE_METRIC } from '../stack-history.js';
+import { createHistoryRoutes } from './history.js';
+
+test('viewers get exported sample keys redacted by REDACT_PATHS', async (t) => {
+  const router = express.Router();
+  router.use((req, res, next) => {
+    res.locals.role = req.get('X-Role');
+    next();
+  });
+  router.use(createHistoryRoutes({
+    history: {
+      *iterate(metric) {
+        yield { metric, key: 'bc1qexample.rig1', at: '2026-01-01T00:00:00.000Z', value: 95e12 };
+      },
+    },
+    retention: parseHistoryRetention({}),
+    redact: createRedactor(parseRedactionPaths('key')),
+  }));
+  const server = http.createServer(createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) }));
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  const url = `http://127.0.0.1:${(server.address() as AddressInfo).port}/api/v1/history/export?metric=${MINER_HASHRATE_METRIC}&range=all`;
+  t.after(() => server.close());
+
+  const row = async (role: string) => (await (await fetch(url, { headers: { 'X-Role': role } })).text()).split('\r\n')[1];
+  assert.equal(await row('viewer'), `2026-01-01T00:00:00.000Z,${MINER_HASHRATE_METRIC},${REDACTED},95000000000000`);
+  assert.equal(await row('operator'), `2026-01-01T00:00:00.000Z,${MINER_HASHRATE_METRIC},bc1qexample.rig1,95000000000000`);
+});
@@ -0,0 +1,88 @@
+/**
+ * Export of the history database (see history-export.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { Readable, pipeline } from 'stream';
+import type { HistoryRetention, HistoryStore } from '../history.js';
+import {
+  HISTORY_EXPORT_FORMATS,
+  parseHistoryMetrics,
+  parseHistoryRange,
+  parseHistoryResolution,
+  renderHistoryCsv,
+  renderHistoryParquet,
+} from '../history-export.js';
+import type { HistoryExportFormat } from '../history-export.js';
+import { createLogger } from '../logger.js';
+import { redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+
+const log = createLogger('routes/history');
+
+export interface HistoryRouteDeps {
+  history: Pick<HistoryStore, 'iterate'>;
+  retention: HistoryRetention;
+  now?: () => number;
+  // Applied to each sample, whose key is a worker name for per-miner metrics.
+  redact?: Redactor;
+}
+
+export function createHistoryRoutes({ history, retention, now = Date.now, redact = redactForRequest }: HistoryRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/history/export - Stream stored time series (hashrate, shares, latency) as CSV or Parquet
+   */
+  router.get('/api/v1/history/export', (req, res) => {
+    const { format = 'csv', metric, range, resolution } = req.query;
+    if (!HISTORY_EXPORT_FORMATS.includes(format as HistoryExportFormat)) {
+      return res.status(400).json({ error: `format must be one of ${HISTORY_EXPORT_FORMATS.join(', ')}` });
+    }
+    if ([metric, range, resolution].some((value) => value !== undefined && typeof value !== 'string')) {
+      return res.status(400).json({ error: 'metric, range and resolution can only be given once' });
+    }
+
+    let metrics: string[];
+    let rangeMs: number | null;
+    let resolutionMs: number | undefined;
+    try {
+      metrics = parseHistoryMetrics(metric as string | undefined);
+      rangeMs = parseHistoryRange(range as string | undefined);
+      resolutionMs = parseHistoryResolution(resolution as string | undefined, retention);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : String(error) });
+    }
+
+    if (format === 'parquet' && metrics.length !== 1) {
+      return res.status(400).json({ error: 'format=parquet exports one metric per file; pick one with metric' });
+    }
+
+    const since = rangeMs === null ? undefined : new Date(now() - rangeMs);
+    const samples: Pick<HistoryStore, 'iterate'> = {
+      *iterate(...args) {
+        for (const sample of history.iterate(...args)) yield redact(req, sample);
+      },
+    };
+    const date = new Date(now()).toISOString().slice(0, 10);
+    const content = format === 'parquet'
+      ? renderHistoryParquet(samples, metrics[0], { since, resolutionMs })
+      : renderHistoryCsv(samples, metrics, { since, resolutionMs });
+    res.set(format === 'parquet'
+      ? {
+        'Content-Type': 'application/vnd.apache.parquet',
+        'Content-Disposition': `attachment; filename="sv2-ui-${metrics[0]}-${date}.parquet"`,
+      }
+      : {
+        'Content-Type': 'text/csv; charset=utf-8',
+        'Content-Disposition': `attachment; filename="sv2-ui-history-${date}.csv"`,
+      });
+    pipeline(Readable.from(content), res, (error) => {
+      // A client leaving mid-export isn't an error.
+      if (error && (error as NodeJS.ErrnoException).code !== 'ERR_STREAM_PREMATURE_CLOSE') log.error('History export error:', error);
+    });
+  });
+
+  return router;
+}
@@ -0,0 +1,30 @@
+/**
+ * Authority keypair generation (see keygen.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { requireAdmin } from '../auth.js';
+import { generateAuthorityKeypair } from '../keygen.js';
+import { createLogger } from '../logger.js';
+
+const log = createLogger('routes/keygen');
+
+export function createKeygenRoutes(): Router {
+  const router = express.Router();
+
+  /**
+   * POST /api/v1/keygen - Generate a secp256k1 authority keypair in SV2's base58check format (admin only)
+   */
+  router.post('/api/v1/keygen', requireAdmin, (_req, res) => {
+    try {
+      // The secret key is never stored, so it must not be cached either.
+      res.set('Cache-Control', 'no-store').json(generateAuthorityKeypair());
+    } catch (error) {
+      log.error('Keygen error:', error);
+      res.status(500).json({ error: 'Failed to generate keypair' });
+    }
+  });
+
+  return router;
+}
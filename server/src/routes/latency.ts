@@ -0,0 +1,84 @@
+/**
+ * Pool and JDS round-trip times from the background prober (see latency.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { selectResolution } from '../history.js';
+import type { HistoryRetention, HistoryStore } from '../history.js';
+import { CONNECT_METRIC, HANDSHAKE_METRIC, LATENCY_TARGETS } from '../latency.js';
+import type { LatencyConfig, LatencyProber, LatencyTarget } from '../latency.js';
+import { createLogger } from '../logger.js';
+
+const log = createLogger('routes/latency');
+
+export interface LatencyRouteDeps {
+  // Null when LATENCY_PROBE_INTERVAL_SECS=0.
+  config: LatencyConfig | null;
+  prober: LatencyProber | null;
+  history: Pick<HistoryStore, 'query'>;
+  // Older series are read from rollups once raw samples are gone.
+  retention?: HistoryRetention;
+}
+
+export interface LatencyPoint {
+  at: string;
+  connectMs: number | null;
+  handshakeMs: number | null;
+}
+
+const DEFAULT_WINDOW_MS = 24 * 3_600_000;
+const MAX_LIMIT = 10_000;
+
+export function createLatencyRoutes({ config, prober, history, retention }: LatencyRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/latency - Latest and historical TCP connect/handshake times to the pool and JDS
+   */
+  router.get('/api/v1/latency', (req, res) => {
+    try {
+      const { target, since, limit } = req.query;
+      if (target !== undefined && !LATENCY_TARGETS.includes(target as LatencyTarget)) {
+        return res.status(400).json({ error: `target must be one of ${LATENCY_TARGETS.join(', ')}` });
+      }
+      if (since !== undefined && (typeof since !== 'string' || Number.isNaN(Date.parse(since)))) {
+        return res.status(400).json({ error: 'since must be an ISO 8601 timestamp' });
+      }
+      const max = limit === undefined ? 1000 : Number(limit);
+      if (!Number.isInteger(max) || max < 1 || max > MAX_LIMIT) {
+        return res.status(400).json({ error: `limit must be between 1 and ${MAX_LIMIT}` });
+      }
+
+      const from = since ? new Date(since as string) : new Date(Date.now() - DEFAULT_WINDOW_MS);
+      const resolutionMs = retention ? selectResolution(retention, from) ?? undefined : undefined;
+      const targets = target ? [target as LatencyTarget] : LATENCY_TARGETS;
+      const series = Object.fromEntries(targets.map((key) => {
+        const points = new Map<string, LatencyPoint>();
+        for (const sample of history.query(CONNECT_METRIC, { key, since: from, limit: max, resolutionMs })) {
+          points.set(sample.at, { at: sample.at, connectMs: sample.value, handshakeMs: null });
+        }
+        for (const sample of history.query(HANDSHAKE_METRIC, { key, since: from, limit: max, resolutionMs })) {
+          const point = points.get(sample.at);
+          if (point) point.handshakeMs = sample.value;
+        }
+        return [key, [...points.values()]];
+      }));
+
+      res.json({
+        enabled: config !== null,
+        intervalSecs: config ? config.intervalMs / 1000 : null,
+        handshake: config?.handshake ?? false,
+        // Null for raw samples, otherwise the rollup buckets' size; their values are averages.
+        resolutionSecs: resolutionMs === undefined ? null : resolutionMs / 1000,
+        latest: (prober?.latest() ?? []).filter((sample) => !target || sample.target === target),
+        series,
+      });
+    } catch (error) {
+      log.error('Latency error:', error);
+      res.status(500).json({ error: 'Failed to get latency' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,106 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp } from '../app.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { createRedactor, parseRedactionPaths, REDACTED } from '../redact.js';
+import type { ContainerLogLine } from '../logs/types.js';
+import { createLogRoutes } from './logs.js';
+
+function line(message: string): ContainerLogLine {
+  return { container: 'translator', stream: 'stdout', timestamp: null, message, raw: message };
+}
+
+test('streams log lines as NDJSON until the container stops', async (t) => {
+  const router = express.Router();
+  router.use(createLogRoutes({
+    getMode: async () => 'no-jd',
+    follow: async (_service, { tail, onLine, onEnd }) => {
+      // The tail is replayed straight away, before the headers go out.
+      onLine(line(`tail=${tail}`));
+      setTimeout(() => {
+        onLine(line('Connected to pool'));
+        onEnd();
+      }, 10);
+      return () => {};
+    },
+  }));
+  const app = createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) });
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = `http://127.0.0.1:${port}/api/v1/logs`;
+
+  const response = await fetch(`${url}/translator?tail=5`);
+  assert.equal(response.status, 200);
+  assert.match(response.headers.get('content-type') ?? '', /^application\/x-ndjson/);
+  const lines = (await response.text()).trim().split('\n').map((entry) => JSON.parse(entry).message);
+  assert.deepEqual(lines, ['tail=5', 'Connected to pool']);
+
+  assert.equal((await fetch(`${url}/jdc`)).status, 404);
+  assert.equal((await fetch(`${url}/jds`)).status, 400);
+});
+
+test('service logs are filtered by level, and come from journald when there is no container', async (t) => {
+  const router = express.Router();
+  const journalCalls: unknown[] = [];
+  router.use(createLogRoutes({
+    getMode: async () => null,
+    follow: async () => {
+      throw new Error('Failed to follow logs for translator container');
+    },
+    journalUnits: { translator: 'sv2-translator.service' },
+    followJournal: async (service, unit, { tail, follow, onLine, onEnd }) => {
+      journalCalls.push({ service, unit, tail, follow });
+      onLine(line('2026-04-17T12:00:00.000000Z  INFO translator_sv2: Listening for SV1 miners'));
+      onLine(line('2026-04-17T12:00:01.000000Z ERROR translator_sv2: Failed to connect to pool'));
+      onLine(line('    caused by: connection refused'));
+      onLine(line('2026-04-17T12:00:02.000000Z DEBUG translator_sv2: Retrying'));
+      onEnd();
+      return () => {};
+    },
+  }));
+  const app = createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) });
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = `http://127.0.0.1:${port}/api/v1/services`;
+
+  const response = await fetch(`${url}/translator/logs?level=warn&follow=false&tail=10`);
+  assert.equal(response.status, 200);
+  assert.equal(response.headers.get('x-log-source'), 'journald');
+  const lines = (await response.text()).trim().split('\n').map((entry) => JSON.parse(entry).message);
+  assert.deepEqual(lines, ['2026-04-17T12:00:01.000000Z ERROR translator_sv2: Failed to connect to pool', '    caused by: connection refused']);
+  assert.deepEqual(journalCalls, [{ service: 'translator', unit: 'sv2-translator.service', tail: 10, follow: false }]);
+
+  // Without a unit, the JDC has to be part of the deployed stack.
+  assert.equal((await fetch(`${url}/jdc/logs`)).status, 404);
+  assert.equal((await fetch(`${url}/translator/logs?level=loud`)).status, 400);
+});
+
+test('viewers get log lines redacted by REDACT_PATHS', async (t) => {
+  const router = express.Router();
+  router.use((req, res, next) => {
+    res.locals.role = req.get('X-Role');
+    next();
+  });
+  router.use(createLogRoutes({
+    getMode: async () => 'no-jd',
+    follow: async () => () => {},
+    read: async () => [line('Miner bc1qexample.rig1 connected')],
+    redact: createRedactor(parseRedactionPaths('message')),
+  }));
+  const app = createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) });
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = `http://127.0.0.1:${port}/api/v1/services/translator/logs?follow=false`;
+
+  const read = async (role: string) => JSON.parse((await (await fetch(url, { headers: { 'X-Role': role } })).text()));
+  assert.deepEqual(await read('viewer'), { container: 'translator', stream: 'stdout', timestamp: null, message: REDACTED });
+  assert.equal((await read('admin')).message, 'Miner bc1qexample.rig1 connected');
+});
@@ -0,0 +1,180 @@
+/**
+ * Live container log streaming (see logs/stream.ts), and the service log
+ * tails with their journald fallback (see logs/tail.ts).
+ */
+
+import express from 'express';
+import type { Request, RequestHandler, Response, Router } from 'express';
+import type { SetupMode } from '@sv2-ui/shared';
+import { createLogger } from '../logger.js';
+import { redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+import { formatLogStreamLine, parseLogStreamQuery, parseServiceLogQuery } from '../logs/stream.js';
+import type { LogStreamFormat } from '../logs/stream.js';
+import { createLevelFilter, followJournal } from '../logs/tail.js';
+import type { JournalTailOptions, JournalUnits } from '../logs/tail.js';
+import { isServiceDeployed } from '../service-control.js';
+import type { ContainerLogLine, LogContainerRole } from '../logs/types.js';
+
+const log = createLogger('routes/logs');
+
+const HEARTBEAT_INTERVAL_MS = 15_000;
+
+type LineHandlers = { onLine: (line: ContainerLogLine) => void; onEnd: () => void };
+
+export interface LogRouteDeps {
+  getMode: () => Promise<SetupMode | null>;
+  follow: (
+    service: LogContainerRole,
+    options: { tail: number } & LineHandlers,
+  ) => Promise<() => void>;
+  // The last lines, without following; for ?follow=false.
+  read?: (service: LogContainerRole, options: { tail: number }) => Promise<ContainerLogLine[]>;
+  // systemd units to read the journal of when Docker has no container.
+  journalUnits?: JournalUnits;
+  followJournal?: (service: LogContainerRole, unit: string, options: JournalTailOptions) => Promise<() => void>;
+  // Applied to each line.
+  redact?: Redactor;
+  // In front of both routes, so a follow holds an API key's stream (see api-keys.ts).
+  holdStream?: RequestHandler;
+}
+
+/**
+ * Write the lines `start` produces to `res` until they end or the client
+ * goes away. Resolves to false, with nothing sent, when `start` fails.
+ */
+async function streamLines(
+  req: Request,
+  res: Response,
+  format: LogStreamFormat,
+  redact: Redactor,
+  start: (handlers: LineHandlers) => Promise<() => void>,
+): Promise<boolean> {
+  // Lines can arrive before the headers below are sent; hold them until then.
+  let pending: string[] | null = [];
+  let ended = false;
+  let stop: () => void;
+  try {
+    stop = await start({
+      onLine: (line) => {
+        const chunk = formatLogStreamLine(redact(req, line), format);
+        if (pending) pending.push(chunk);
+        else res.write(chunk);
+      },
+      onEnd: () => {
+        if (pending) ended = true;
+        else res.end();
+      },
+    });
+  } catch (error) {
+    log.error('Log stream error:', error);
+    return false;
+  }
+
+  res.status(200).set({
+    'Content-Type': format === 'sse' ? 'text/event-stream' : 'application/x-ndjson',
+    'Cache-Control': 'no-cache',
+    // Stop nginx and friends from buffering the stream.
+    'X-Accel-Buffering': 'no',
+  });
+  res.flushHeaders();
+  for (const chunk of pending) res.write(chunk);
+  pending = null;
+  if (ended) {
+    res.end();
+    return true;
+  }
+
+  const heartbeat = format === 'sse' ? setInterval(() => res.write(': heartbeat\n\n'), HEARTBEAT_INTERVAL_MS) : null;
+
+  res.on('close', () => {
+    if (heartbeat) clearInterval(heartbeat);
+    stop();
+  });
+  return true;
+}
+
+export function createLogRoutes({
+  getMode,
+  follow,
+  read,
+  journalUnits = {},
+  followJournal: followUnit = followJournal,
+  redact = redactForRequest,
+  holdStream = (_req, _res, next) => next(),
+}: LogRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/logs/:service?tail=N&format=ndjson|sse - Follow the translator or JDC container log
+   */
+  router.get('/api/v1/logs/:service', holdStream, async (req, res) => {
+    let query;
+    try {
+      query = parseLogStreamQuery(req.params.service, req.query, req.get('Accept'));
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid log stream request' });
+    }
+
+    const { service, tail, format } = query;
+    try {
+      if (!isServiceDeployed(service, await getMode())) {
+        return res.status(404).json({ error: `${service} is not part of the deployed stack` });
+      }
+    } catch (error) {
+      log.error('Log stream error:', error);
+      return res.status(502).json({ error: `Failed to follow ${service} logs` });
+    }
+    if (!await streamLines(req, res, format, redact, (handlers) => follow(service, { tail, ...handlers }))) {
+      res.status(502).json({ error: `Failed to follow ${service} logs` });
+    }
+  });
+
+  /**
+   * GET /api/v1/services/:service/logs?tail=N&level=warn&follow=true|false&format=ndjson|sse - Tail the translator or JDC log, from Docker or journald
+   */
+  router.get('/api/v1/services/:service/logs', holdStream, async (req, res) => {
+    let query;
+    try {
+      query = parseServiceLogQuery(req.params.service, req.query, req.get('Accept'));
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid log request' });
+    }
+
+    const { service, tail, format, level, follow: following } = query;
+    const unit = journalUnits[service];
+    try {
+      if (!unit && !isServiceDeployed(service, await getMode())) {
+        return res.status(404).json({ error: `${service} is not part of the deployed stack` });
+      }
+    } catch (error) {
+      log.error('Log tail error:', error);
+      return res.status(502).json({ error: `Failed to read ${service} logs` });
+    }
+
+    const keep = createLevelFilter(level);
+    const started = await streamLines(req, res, format, redact, async ({ onLine, onEnd }) => {
+      const handlers = { onLine: (line: ContainerLogLine) => keep(line) && onLine(line), onEnd };
+      try {
+        if (!following && read) {
+          for (const line of await read(service, { tail })) handlers.onLine(line);
+          onEnd();
+          res.set('X-Log-Source', 'docker');
+          return () => {};
+        }
+        const stop = await follow(service, { tail, ...handlers });
+        res.set('X-Log-Source', 'docker');
+        return stop;
+      } catch (error) {
+        if (!unit) throw error;
+        log.debug(`No ${service} container to read, reading the ${unit} journal instead:`, error);
+        const stop = await followUnit(service, unit, { tail, follow: following, ...handlers });
+        res.set('X-Log-Source', 'journald');
+        return stop;
+      }
+    });
+    if (!started) res.status(502).json({ error: `Failed to read ${service} logs` });
+  });
+
+  return router;
+}
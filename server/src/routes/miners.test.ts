@@ -0,0 +1,148 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp } from '../app.js';
+import type { HistoryQuery, HistorySample } from '../history.js';
+import type { MinerLabel } from '../miner-labels.js';
+import { parseHistoryRetention } from '../history.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { createRedactor, parseRedactionPaths, REDACTED } from '../redact.js';
+import type { StackData } from '../monitoring.js';
+import { getMinerSamples } from '../stack-history.js';
+import { createMinerRoutes } from './miners.js';
+
+const client = (authorized_worker_name: string, hashrate: number | null) => ({
+  client_id: 1,
+  channel_id: null,
+  authorized_worker_name,
+  user_identity: 'pool-user',
+  hashrate,
+  stable_hashrate: true,
+  target_hex: '00',
+  extranonce1_hex: '00',
+  extranonce2_len: 4,
+  version_rolling_mask: null,
+  version_rolling_min_bit: null,
+});
+
+test('miner samples are keyed by worker name and connections of one worker add up', () => {
+  const data: StackData = {
+    mode: 'no-jd',
+    upstream: 'translator',
+    global: null,
+    serverChannels: null,
+    sv1Clients: {
+      items: [client('s19-a', 100e12), client('s19-a', 10e12), client('s19-b', null), client('', 5e12)],
+      offset: 0,
+      limit: 100,
+      total: 4,
+    },
+    sv2Clients: null,
+  };
+  assert.deepEqual(getMinerSamples(data, '2026-01-01T00:00:00.000Z').map(({ key, value }) => [key, value]), [
+    ['s19-a', 110e12],
+    ['s19-b', null],
+    ['pool-user', 5e12],
+  ]);
+  assert.deepEqual(getMinerSamples({ ...data, sv1Clients: null }, '2026-01-01T00:00:00.000Z'), []);
+});
+
+test('GET /api/v1/miners/:id/history returns one worker\'s samples', async (t) => {
+  const since = new Date(Date.now() - 3_600_000).toISOString();
+  const queries: HistoryQuery[] = [];
+  const router = express.Router();
+  router.use(createMinerRoutes({
+    retention: parseHistoryRetention({}),
+    labels: { list: () => [], get: () => null, set: () => { throw new Error('unused'); }, remove: () => false },
+    history: {
+      query: (_metric, filter = {}): HistorySample[] => {
+        queries.push(filter);
+        return filter.key === 's19.a' && filter.resolutionMs === undefined
+          ? [{ metric: 'miner.hashrate', key: 's19.a', at: since, value: 95e12 }]
+          : [];
+      },
+    },
+  }));
+  const server = http.createServer(createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) }));
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  const url = `http://127.0.0.1:${(server.address() as AddressInfo).port}/api/v1/miners`;
+  t.after(() => server.close());
+
+  const response = await fetch(`${url}/s19.a/history?since=${since}&limit=10`);
+  assert.deepEqual(await response.json(), { id: 's19.a', resolutionSecs: null, samples: [{ at: since, hashrate: 95e12 }] });
+  assert.equal(queries.at(-1)?.limit, 10);
+
+  assert.equal((await fetch(`${url}/s19-c/history`)).status, 404);
+  assert.equal((await fetch(`${url}/s19.a/history?since=yesterday`)).status, 400);
+  assert.equal((await fetch(`${url}/s19.a/history?limit=0`)).status, 400);
+});
+
+test('miner labels can be set, read and removed', async (t) => {
+  const stored = new Map<string, MinerLabel>();
+  const router = express.Router();
+  router.use(createMinerRoutes({
+    retention: parseHistoryRetention({}),
+    history: { query: () => [] },
+    labels: {
+      list: () => [...stored.values()],
+      get: (id) => stored.get(id) ?? null,
+      set: (id, input) => {
+        const label = { id, ...input, updatedAt: '2026-01-01T00:00:00.000Z' };
+        stored.set(id, label);
+        return label;
+      },
+      remove: (id) => stored.delete(id),
+    },
+  }));
+  const server = http.createServer(createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) }));
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  const url = `http://127.0.0.1:${(server.address() as AddressInfo).port}/api/v1/miners`;
+  t.after(() => server.close());
+
+  const put = (id: string, body: unknown) => fetch(`${url}/${id}/label`, {
+    method: 'PUT',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(body),
+  });
+  assert.equal((await put('s19.a', { tags: 'hot' })).status, 400);
+  assert.equal((await put('s19.a', { name: 'Top shelf', tags: ['hot'] })).status, 200);
+  assert.deepEqual(await (await fetch(`${url}/s19.a/label`)).json(), {
+    id: 's19.a',
+    name: 'Top shelf',
+    location: null,
+    tags: ['hot'],
+    updatedAt: '2026-01-01T00:00:00.000Z',
+  });
+  assert.equal((await (await fetch(`${url}/labels`)).json()).labels.length, 1);
+  assert.equal((await fetch(`${url}/s19.a/label`, { method: 'DELETE' })).status, 200);
+  assert.equal((await fetch(`${url}/s19.a/label`)).status, 404);
+  assert.equal((await fetch(`${url}/s19.a/label`, { method: 'DELETE' })).status, 404);
+});
+
+test('viewers get miner ids redacted by REDACT_PATHS', async (t) => {
+  const since = new Date(Date.now() - 3_600_000).toISOString();
+  const label = { id: 's19.a', name: 'Top shelf', location: null, tags: [], updatedAt: since };
+  const router = express.Router();
+  router.use((req, res, next) => {
+    res.locals.role = req.get('X-Role');
+    next();
+  });
+  router.use(createMinerRoutes({
+    retention: parseHistoryRetention({}),
+    history: { query: () => [{ metric: 'miner.hashrate', key: 's19.a', at: since, value: 95e12 }] },
+    labels: { list: () => [label], get: () => label, set: () => label, remove: () => false },
+    redact: createRedactor(parseRedactionPaths('id,labels[].id')),
+  }));
+  const server = http.createServer(createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) }));
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  const url = `http://127.0.0.1:${(server.address() as AddressInfo).port}/api/v1/miners`;
+  t.after(() => server.close());
+
+  const viewer = { headers: { 'X-Role': 'viewer' } };
+  assert.equal((await (await fetch(`${url}/labels`, viewer)).json()).labels[0].id, REDACTED);
+  assert.equal((await (await fetch(`${url}/s19.a/label`, viewer)).json()).id, REDACTED);
+  assert.equal((await (await fetch(`${url}/s19.a/history`, viewer)).json()).id, REDACTED);
+  assert.equal((await (await fetch(`${url}/s19.a/history`, { headers: { 'X-Role': 'operator' } })).json()).id, 's19.a');
+});
@@ -0,0 +1,138 @@
+/**
+ * Per-miner hashrate from the history database (see stack-history.ts), and
+ * the labels attached to miners (see miner-labels.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { requireAdmin } from '../auth.js';
+import { selectResolution } from '../history.js';
+import type { HistoryRetention, HistoryStore } from '../history.js';
+import { createLogger } from '../logger.js';
+import { parseMinerLabel } from '../miner-labels.js';
+import type { MinerLabelStore } from '../miner-labels.js';
+import { redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+import { MINER_HASHRATE_METRIC } from '../stack-history.js';
+
+const log = createLogger('routes/miners');
+
+export interface MinerRouteDeps {
+  history: Pick<HistoryStore, 'query'>;
+  retention: HistoryRetention;
+  labels: Pick<MinerLabelStore, 'list' | 'get' | 'set' | 'remove'>;
+  // Worker names are miner ids, so viewers see them through REDACT_PATHS.
+  redact?: Redactor;
+}
+
+const DEFAULT_WINDOW_MS = 24 * 3_600_000;
+const MAX_LIMIT = 10_000;
+const MAX_ID_LENGTH = 128;
+
+export function createMinerRoutes({ history, retention, labels, redact = redactForRequest }: MinerRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/miners/labels - Names, locations and tags attached to miners
+   */
+  router.get('/api/v1/miners/labels', (req, res) => {
+    try {
+      res.json(redact(req, { labels: labels.list() }));
+    } catch (error) {
+      log.error('Miner labels error:', error);
+      res.status(500).json({ error: 'Failed to list miner labels' });
+    }
+  });
+
+  /**
+   * GET /api/v1/miners/:id/label - The label of one miner, by worker name
+   */
+  router.get('/api/v1/miners/:id/label', (req, res) => {
+    try {
+      const label = labels.get(req.params.id);
+      if (!label) {
+        return res.status(404).json({ error: 'Miner label not found' });
+      }
+      res.json(redact(req, label));
+    } catch (error) {
+      log.error('Miner label error:', error);
+      res.status(500).json({ error: 'Failed to get miner label' });
+    }
+  });
+
+  /**
+   * PUT /api/v1/miners/:id/label - Set a miner's name, location and tags (admin only)
+   */
+  router.put('/api/v1/miners/:id/label', requireAdmin, (req, res) => {
+    const { id } = req.params;
+    if (id.length > MAX_ID_LENGTH) {
+      return res.status(400).json({ error: `id must be at most ${MAX_ID_LENGTH} characters` });
+    }
+    let input;
+    try {
+      input = parseMinerLabel(req.body);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid miner label' });
+    }
+
+    try {
+      res.json(labels.set(id, input));
+    } catch (error) {
+      log.error('Miner label error:', error);
+      res.status(500).json({ error: 'Failed to save miner label' });
+    }
+  });
+
+  /**
+   * DELETE /api/v1/miners/:id/label - Remove a miner's label (admin only)
+   */
+  router.delete('/api/v1/miners/:id/label', requireAdmin, (req, res) => {
+    try {
+      if (!labels.remove(req.params.id)) {
+        return res.status(404).json({ error: 'Miner label not found' });
+      }
+      res.json({ success: true });
+    } catch (error) {
+      log.error('Miner label error:', error);
+      res.status(500).json({ error: 'Failed to remove miner label' });
+    }
+  });
+
+  /**
+   * GET /api/v1/miners/:id/history - One miner's hashrate over time, by worker name
+   */
+  router.get('/api/v1/miners/:id/history', (req, res) => {
+    try {
+      const { id } = req.params;
+      const { since, limit } = req.query;
+      if (since !== undefined && (typeof since !== 'string' || Number.isNaN(Date.parse(since)))) {
+        return res.status(400).json({ error: 'since must be an ISO 8601 timestamp' });
+      }
+      const max = limit === undefined ? 1000 : Number(limit);
+      if (!Number.isInteger(max) || max < 1 || max > MAX_LIMIT) {
+        return res.status(400).json({ error: `limit must be between 1 and ${MAX_LIMIT}` });
+      }
+
+      // A worker that was never seen is more likely a typo than an idle miner.
+      const tiers = [undefined, ...retention.rollups.map((tier) => tier.resolutionMs)];
+      if (!tiers.some((resolutionMs) => history.query(MINER_HASHRATE_METRIC, { key: id, limit: 1, resolutionMs }).length > 0)) {
+        return res.status(404).json({ error: `No hashrate history for miner "${id}"` });
+      }
+
+      const from = since ? new Date(since as string) : new Date(Date.now() - DEFAULT_WINDOW_MS);
+      const resolutionMs = selectResolution(retention, from) ?? undefined;
+      const samples = history.query(MINER_HASHRATE_METRIC, { key: id, since: from, limit: max, resolutionMs });
+      res.json(redact(req, {
+        id,
+        // Null for raw samples, otherwise the rollup buckets' size; their values are averages.
+        resolutionSecs: resolutionMs === undefined ? null : resolutionMs / 1000,
+        samples: samples.map(({ at, value }) => ({ at, hashrate: value })),
+      }));
+    } catch (error) {
+      log.error('Miner history error:', error);
+      res.status(500).json({ error: 'Failed to get miner history' });
+    }
+  });
+
+  return router;
+}
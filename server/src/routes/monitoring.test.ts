@@ -0,0 +1,55 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp } from '../app.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { setContainerOrigins } from '../proxy.js';
+import { createMonitoringRoutes } from './monitoring.js';
+
+async function listen(server: http.Server): Promise<string> {
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  return `http://127.0.0.1:${(server.address() as AddressInfo).port}`;
+}
+
+test('monitoring reads are decoded, cached reads served, and bad responses reported', async (t) => {
+  const upstream = http.createServer((req, res) => {
+    res.setHeader('Content-Type', 'application/json');
+    if (req.url === '/api/v1/global') return res.end(JSON.stringify({ uptime_secs: 30, renamed_field: 1 }));
+    if (req.url?.startsWith('/api/v1/sv1/clients')) return res.end(JSON.stringify({ items: [{ client_id: 1 }], total: 1 }));
+    res.statusCode = 404;
+    res.end(JSON.stringify({ error: 'Not found' }));
+  });
+  setContainerOrigins({ 'sv2-translator': await listen(upstream) });
+  t.after(() => {
+    setContainerOrigins({});
+    upstream.close();
+  });
+
+  const router = express.Router();
+  router.use(createMonitoringRoutes({
+    getCached: (service, path) => (service === 'translator' && path === '/server/channels?offset=0&limit=100'
+      ? { extended_channels: [], standard_channels: [], offset: 0, limit: 100, total_extended: 0, total_standard: 0 }
+      : undefined),
+  }));
+  const app = createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) });
+  const server = http.createServer(app);
+  const url = `${await listen(server)}/api/v1/monitoring`;
+  t.after(() => server.close());
+
+  const global = await fetch(`${url}/translator/global`);
+  assert.equal(global.headers.get('X-Schema-Version'), '1');
+  assert.deepEqual(await global.json(), { server: null, sv1_clients: null, sv2_clients: null, uptime_secs: 30 });
+
+  const cached = await fetch(`${url}/translator/server/channels?offset=0&limit=100`);
+  assert.equal(cached.headers.get('X-Cache'), 'HIT');
+
+  const invalid = await fetch(`${url}/translator/sv1/clients?offset=0&limit=25`);
+  assert.equal(invalid.status, 502);
+  assert.match((await invalid.json()).error, /Translator \/sv1\/clients returned an unexpected response \(items\[0\]\.authorized_worker_name: expected a string\)/);
+
+  assert.equal((await fetch(`${url}/translator/clients`)).status, 404);
+  assert.equal((await fetch(`${url}/translator/clients/abc/channels`)).status, 400);
+  assert.equal((await fetch(`${url}/pool/global`)).status, 404);
+});
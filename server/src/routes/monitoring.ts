@@ -0,0 +1,120 @@
+/**
+ * The Translator and JDC monitoring reads the dashboard makes, decoded into
+ * sv2-ui's v1 schema (see monitoring-schema.ts) instead of passed through.
+ *
+ * Paths mirror the upstream ones: /api/v1/monitoring/jdc/server/channels reads
+ * the JDC's /api/v1/server/channels.
+ */
+
+import express from 'express';
+import type { RequestHandler, Router } from 'express';
+import { createLogger } from '../logger.js';
+import {
+  fetchMonitoringResponse,
+  MONITORING_SERVICES,
+  MonitoringHttpError,
+} from '../monitoring.js';
+import type { MonitoringService } from '../monitoring.js';
+import {
+  decodeGlobal,
+  decodeHealth,
+  decodeServerChannels,
+  decodeSv1Clients,
+  decodeSv2ClientChannels,
+  decodeSv2Clients,
+  MONITORING_SCHEMA_VERSION,
+  SchemaError,
+} from '../monitoring-schema.js';
+import type { Decoder, Sv1ClientsResponse } from '../monitoring-schema.js';
+import type { MinerLabel } from '../miner-labels.js';
+import { redactForRequest } from '../redact.js';
+import { getMinerId } from '../stack-history.js';
+
+const log = createLogger('routes/monitoring');
+
+export interface MonitoringRouteDeps {
+  // The poller's decoded copy of a response, if it has one for the path.
+  getCached: (service: MonitoringService, path: string) => unknown;
+  // Labels merged into the SV1 client listings, by worker name.
+  getLabels?: () => MinerLabel[];
+}
+
+function isMonitoringService(value: string): value is MonitoringService {
+  return Object.hasOwn(MONITORING_SERVICES, value);
+}
+
+export function createMonitoringRoutes({ getCached, getLabels }: MonitoringRouteDeps): Router {
+  const router = express.Router();
+
+  function withLabels(clients: Sv1ClientsResponse) {
+    const labels = new Map((getLabels?.() ?? []).map((label) => [label.id, label]));
+    return { ...clients, items: clients.items.map((client) => ({ ...client, label: labels.get(getMinerId(client)) ?? null })) };
+  }
+
+  // `annotate` adds sv2-ui's own fields to the decoded (or cached) body.
+  function serve<T>(decode: Decoder<T>, annotate: (body: T) => unknown = (body) => body): RequestHandler {
+    return async (req, res) => {
+      const { service, id } = req.params;
+      if (!isMonitoringService(service)) {
+        return res.status(404).json({ error: 'Unknown service' });
+      }
+      if (id !== undefined && !/^\d+$/.test(id)) {
+        return res.status(400).json({ error: 'id must be a client id' });
+      }
+      const { label } = MONITORING_SERVICES[service];
+      // The upstream path and query, after /api/v1/monitoring/<service>.
+      const path = req.url.slice(`/api/v1/monitoring/${service}`.length);
+
+      res.set('X-Schema-Version', String(MONITORING_SCHEMA_VERSION));
+      const cached = getCached(service, path);
+      if (cached !== undefined) {
+        return res.set('X-Cache', 'HIT').json(redactForRequest(req, annotate(cached as T)));
+      }
+
+      try {
+        res.json(redactForRequest(req, annotate(await fetchMonitoringResponse(service, path, decode))));
+      } catch (error) {
+        if (error instanceof MonitoringHttpError) {
+          return res.status(error.status).json({ error: error.message });
+        }
+        if (error instanceof SchemaError) {
+          log.error(`${label} ${path} response error:`, error.message);
+          return res.status(502).json({ error: `${label} ${path.split('?')[0]} returned an unexpected response (${error.message})` });
+        }
+        res.status(502).json({ error: `Cannot connect to ${label} monitoring API` });
+      }
+    };
+  }
+
+  /**
+   * GET /api/v1/monitoring/:service/health - Monitoring API health of the Translator or JDC
+   */
+  router.get('/api/v1/monitoring/:service/health', serve(decodeHealth));
+
+  /**
+   * GET /api/v1/monitoring/:service/global - Global statistics of the Translator or JDC, normalized
+   */
+  router.get('/api/v1/monitoring/:service/global', serve(decodeGlobal));
+
+  /**
+   * GET /api/v1/monitoring/:service/server/channels - Upstream channels of the Translator or JDC, normalized
+   */
+  router.get('/api/v1/monitoring/:service/server/channels', serve(decodeServerChannels));
+
+  /**
+   * GET /api/v1/monitoring/:service/sv1/clients - SV1 miners connected to the Translator, normalized, with their labels
+   */
+  router.get('/api/v1/monitoring/:service/sv1/clients', serve(decodeSv1Clients, withLabels));
+
+  /**
+   * GET /api/v1/monitoring/:service/clients - SV2 clients connected to the JDC, normalized
+   */
+  router.get('/api/v1/monitoring/:service/clients', serve(decodeSv2Clients));
+
+  /**
+   * GET /api/v1/monitoring/:service/clients/:id/channels - Channels of one SV2 client, normalized
+   */
+  router.get('/api/v1/monitoring/:service/clients/:id/channels', serve(decodeSv2ClientChannels));
+
+  return router;
+}
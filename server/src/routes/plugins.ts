@@ -0,0 +1,90 @@
+/**
+ * WebAssembly plugins' status and metric history (see plugins.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { requireOperator } from '../auth.js';
+import { selectResolution } from '../history.js';
+import type { HistoryRetention, HistoryStore } from '../history.js';
+import { createLogger } from '../logger.js';
+import { getPluginMetric } from '../plugins.js';
+import type { PluginHost, PluginLoadError } from '../plugins.js';
+
+const log = createLogger('routes/plugins');
+
+export interface PluginRouteDeps {
+  host: PluginHost;
+  history: Pick<HistoryStore, 'query'>;
+  // Older series are read from rollups once raw samples are gone.
+  retention?: HistoryRetention;
+  // Manifests and modules that didn't load at startup.
+  loadErrors: PluginLoadError[];
+}
+
+const DEFAULT_WINDOW_MS = 24 * 3_600_000;
+const MAX_LIMIT = 10_000;
+
+export function createPluginRoutes({ host, history, retention, loadErrors }: PluginRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/plugins - Loaded plugins with their latest metrics, and those that failed to load
+   */
+  router.get('/api/v1/plugins', (_req, res) => {
+    res.json({ plugins: host.list(), errors: loadErrors });
+  });
+
+  /**
+   * GET /api/v1/plugins/:id - One plugin's latest and historical metrics
+   */
+  router.get('/api/v1/plugins/:id', (req, res) => {
+    try {
+      const status = host.get(req.params.id);
+      if (!status) return res.status(404).json({ error: 'Plugin not found' });
+
+      const { since, limit } = req.query;
+      if (since !== undefined && (typeof since !== 'string' || Number.isNaN(Date.parse(since)))) {
+        return res.status(400).json({ error: 'since must be an ISO 8601 timestamp' });
+      }
+      const max = limit === undefined ? 1000 : Number(limit);
+      if (!Number.isInteger(max) || max < 1 || max > MAX_LIMIT) {
+        return res.status(400).json({ error: `limit must be between 1 and ${MAX_LIMIT}` });
+      }
+
+      const from = since ? new Date(since as string) : new Date(Date.now() - DEFAULT_WINDOW_MS);
+      const resolutionMs = retention ? selectResolution(retention, from) ?? undefined : undefined;
+      const series = Object.fromEntries(status.metrics.map(({ name }) => [
+        name,
+        history.query(getPluginMetric(status.id), { key: name, since: from, limit: max, resolutionMs })
+          .map(({ at, value }) => ({ at, value })),
+      ]));
+
+      res.json({
+        ...status,
+        // Null for raw samples, otherwise the rollup buckets' size; their values are averages.
+        resolutionSecs: resolutionMs === undefined ? null : resolutionMs / 1000,
+        series,
+      });
+    } catch (error) {
+      log.error('Plugin error:', error);
+      res.status(500).json({ error: 'Failed to get plugin' });
+    }
+  });
+
+  /**
+   * POST /api/v1/plugins/:id/run - Fetch and normalize a plugin's source now (operator or admin)
+   */
+  router.post('/api/v1/plugins/:id/run', requireOperator, async (req, res) => {
+    try {
+      const status = await host.run(req.params.id);
+      if (!status) return res.status(404).json({ error: 'Plugin not found' });
+      res.json(status);
+    } catch (error) {
+      log.error('Plugin run error:', error);
+      res.status(500).json({ error: 'Failed to run plugin' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,110 @@
+/**
+ * Polling routes: the poll schedule, the realtime event stream that tells
+ * dashboards when to refresh (see realtime.ts), and forcing a refresh, with
+ * or without a warm restart first (see warm-restart.ts).
+ */
+
+import express from 'express';
+import type { RequestHandler, Router } from 'express';
+import { requireOperator } from '../auth.js';
+import type { ServicePollSchedule } from '../poller.js';
+import { parseRefreshTarget } from '../realtime.js';
+import type { RealtimeHub, RefreshTarget } from '../realtime.js';
+import type { SavedState } from '../state.js';
+import { getRestartOrder, runWarmRestart } from '../warm-restart.js';
+import type { WarmRestartDeps } from '../warm-restart.js';
+
+export interface PollingRouteDeps {
+  getSchedule: () => ServicePollSchedule[];
+  realtime: Pick<RealtimeHub, 'handler'>;
+  pollNow: (backend: RefreshTarget) => Promise<void>;
+  loadState: () => Promise<SavedState>;
+  warmRestart: WarmRestartDeps;
+  // Claims the stack for the duration of the restart; null when it's busy.
+  beginOperation: () => (() => void) | null;
+  getBusyError?: () => string;
+  // In front of /api/realtime, so it holds an API key's stream (see api-keys.ts).
+  holdStream?: RequestHandler;
+}
+
+export function createPollingRoutes({
+  getSchedule,
+  realtime,
+  pollNow,
+  loadState,
+  warmRestart,
+  beginOperation,
+  getBusyError = () => 'Mining services are busy. Please wait.',
+  holdStream = (_req, _res, next) => next(),
+}: PollingRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/polling - Each service's poll interval and jitter, when it was last polled and when it's next due
+   */
+  router.get('/api/v1/polling', (_req, res) => {
+    res.json({ services: getSchedule() });
+  });
+
+  /**
+   * GET /api/realtime - Server-sent events with refresh hints, stack events and new blocks
+   */
+  router.get('/api/realtime', holdStream, realtime.handler);
+
+  /**
+   * POST /api/poll-now?backend=translator|jdc|all - Force an immediate refresh (operator or admin)
+   *
+   * Meant for automation, e.g. right after restarting the translator.
+   */
+  router.post('/api/poll-now', requireOperator, async (req, res) => {
+    let backend;
+    try {
+      backend = parseRefreshTarget(req.query.backend);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid backend' });
+    }
+
+    await pollNow(backend);
+    res.json({ success: true, backend });
+  });
+
+  /**
+   * POST /api/warm-restart?backend=translator|jdc|all - Restart services without
+   * recreating them, streaming progress as NDJSON (operator or admin)
+   *
+   * Pauses alerts, restarts, waits for the monitoring API to answer, polls, and
+   * resumes alerts.
+   */
+  router.post('/api/warm-restart', requireOperator, async (req, res) => {
+    let services;
+    try {
+      const state = await loadState();
+      if (!state.configured) {
+        return res.status(400).json({ success: false, error: 'Not configured' });
+      }
+      services = getRestartOrder(parseRefreshTarget(req.query.backend), state.mode);
+    } catch (error) {
+      return res.status(400).json({ success: false, error: error instanceof Error ? error.message : 'Invalid backend' });
+    }
+
+    const finish = beginOperation();
+    if (!finish) {
+      return res.status(409).json({ success: false, error: getBusyError() });
+    }
+
+    res.status(200).type('application/x-ndjson');
+    res.flushHeaders();
+
+    try {
+      const success = await runWarmRestart(services, warmRestart, (progress) => {
+        res.write(`${JSON.stringify(progress)}\n`);
+      });
+
+      res.end(`${JSON.stringify({ success })}\n`);
+    } finally {
+      finish();
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,34 @@
+/**
+ * The optional BTC price feed (see price.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { createLogger } from '../logger.js';
+import { DISABLED_PRICE_STATUS } from '../price.js';
+import type { PriceFeed } from '../price.js';
+
+const log = createLogger('routes/price');
+
+export interface PriceRouteDeps {
+  // Null without PRICE_PROVIDER.
+  price: PriceFeed | null;
+}
+
+export function createPriceRoutes({ price }: PriceRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/price - The cached BTC price in the configured currency
+   */
+  router.get('/api/v1/price', async (_req, res) => {
+    try {
+      res.json(price ? await price.get() : DISABLED_PRICE_STATUS);
+    } catch (error) {
+      log.error('Price error:', error);
+      res.status(500).json({ error: 'Failed to get the BTC price' });
+    }
+  });
+
+  return router;
+}
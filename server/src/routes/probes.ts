@@ -0,0 +1,41 @@
+/**
+ * Orchestrator probes, served at the root ahead of the IP filter (see
+ * probes.ts for what readiness means).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { createLogger } from '../logger.js';
+import type { Readiness } from '../probes.js';
+
+const log = createLogger('routes/probes');
+
+export interface ProbeRouteDeps {
+  getReadiness: () => Promise<Readiness>;
+}
+
+export function createProbeRoutes({ getReadiness }: ProbeRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /livez - Process liveness; answers as long as the server is serving requests
+   */
+  router.get('/livez', (_req, res) => {
+    res.set('Cache-Control', 'no-store').json({ status: 'ok' });
+  });
+
+  /**
+   * GET /readyz - 200 once the UI build is in place and the stack is reachable (or the grace period is over), 503 before
+   */
+  router.get('/readyz', async (_req, res) => {
+    try {
+      const readiness = await getReadiness();
+      res.status(readiness.ready ? 200 : 503).set('Cache-Control', 'no-store').json(readiness);
+    } catch (error) {
+      log.error('Readiness error:', error);
+      res.status(503).json({ ready: false, error: 'Failed to check readiness' });
+    }
+  });
+
+  return router;
+}
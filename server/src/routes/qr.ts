@@ -0,0 +1,73 @@
+/**
+ * QR codes with the miner connection details (see qr.ts), for pointing
+ * phone-configured ASICs and field setups at the Translator.
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { TRANSLATOR_PORT } from '@sv2-ui/shared';
+import { createLogger } from '../logger.js';
+import { encodeQr, renderQrPng, renderQrSvg } from '../qr.js';
+
+const log = createLogger('routes/qr');
+
+export interface QrRouteDeps {
+  // STRATUM_HOST, the address miners reach this machine at.
+  stratumHost: string | null;
+  // The Translator's configured user identity.
+  getWorker: () => Promise<string | null>;
+}
+
+const HOST_PATTERN = /^([A-Za-z0-9-]+(\.[A-Za-z0-9-]+)*|\[[0-9A-Fa-f:.]+\])$/;
+const FORMATS = ['svg', 'png', 'json'];
+const MAX_SCALE = 32;
+
+export function createQrRoutes({ stratumHost, getWorker }: QrRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/qr/miner-config - QR code (SVG, PNG or JSON) with the Translator's SV1 stratum URL and worker name
+   */
+  router.get('/api/v1/qr/miner-config', async (req, res) => {
+    try {
+      const query = req.query as Record<string, unknown>;
+      const format = query.format ?? 'svg';
+      if (typeof format !== 'string' || !FORMATS.includes(format)) {
+        return res.status(400).json({ error: `format must be one of ${FORMATS.join(', ')}` });
+      }
+      const scale = query.scale === undefined ? 8 : Number(query.scale);
+      if (!Number.isInteger(scale) || scale < 1 || scale > MAX_SCALE) {
+        return res.status(400).json({ error: `scale must be an integer between 1 and ${MAX_SCALE}` });
+      }
+
+      // Without STRATUM_HOST, the host the dashboard was opened at is usually the one miners can reach.
+      const host = typeof query.host === 'string' && query.host ? query.host : stratumHost ?? req.hostname;
+      if (!HOST_PATTERN.test(host)) {
+        return res.status(400).json({ error: 'host must be a hostname or IP address' });
+      }
+      const worker = typeof query.worker === 'string' && query.worker ? query.worker : await getWorker();
+      if (!worker) {
+        return res.status(400).json({ error: 'worker is required until the Translator is configured' });
+      }
+      if (/[\r\n]/.test(worker)) {
+        return res.status(400).json({ error: 'worker must be a single line' });
+      }
+
+      const url = `stratum+tcp://${host}:${TRANSLATOR_PORT}`;
+      const text = `${url}\n${worker}`;
+      if (format === 'json') {
+        return res.json({ url, worker, text });
+      }
+      const qr = encodeQr(text);
+      if (format === 'png') {
+        return res.type('png').send(renderQrPng(qr, { scale }));
+      }
+      res.type('svg').send(renderQrSvg(qr, { scale }));
+    } catch (error) {
+      log.error('Miner config QR error:', error);
+      res.status(500).json({ error: 'Failed to render QR code' });
+    }
+  });
+
+  return router;
+}
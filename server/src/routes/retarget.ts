@@ -0,0 +1,41 @@
+/**
+ * The difficulty retarget countdown for the node configured in JD mode
+ * (see retarget.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import type { BitcoinChainStats } from '../docker.js';
+import { createLogger } from '../logger.js';
+import { estimateRetarget } from '../retarget.js';
+
+const log = createLogger('routes/retarget');
+
+export interface RetargetRouteDeps {
+  // Null without a Bitcoin Core node configured.
+  getChainStats: () => Promise<BitcoinChainStats | null>;
+  // The stack's hashrate, 0 while it's stopped or offline.
+  getHashrate: () => Promise<number>;
+}
+
+export function createRetargetRoutes({ getChainStats, getHashrate }: RetargetRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/retarget - Blocks and time until the next difficulty retarget, and its effect on expected time to block
+   */
+  router.get('/api/retarget', async (_req, res) => {
+    try {
+      const stats = await getChainStats();
+      if (!stats) {
+        return res.status(404).json({ error: 'Retarget estimates need the Bitcoin Core node configured in JD mode' });
+      }
+      res.json(estimateRetarget(stats, await getHashrate()));
+    } catch (error) {
+      log.error('Retarget error:', error);
+      res.status(502).json({ error: 'Failed to read chain stats from Bitcoin Core' });
+    }
+  });
+
+  return router;
+}
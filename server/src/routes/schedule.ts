@@ -0,0 +1,71 @@
+/**
+ * Maintenance and report schedule routes, as JSON for the UI and as an
+ * iCalendar feed for calendar apps (see schedule.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { requireAdmin } from '../auth.js';
+import { createLogger } from '../logger.js';
+import { parseSchedule, renderIcalendar } from '../schedule.js';
+import type { ScheduleStore } from '../schedule.js';
+
+const log = createLogger('routes/schedule');
+
+export interface ScheduleRouteDeps {
+  schedule: Pick<ScheduleStore, 'get' | 'save'>;
+  // The calendar name, naming the pool and any test network.
+  getTitle: () => Promise<string>;
+}
+
+export function createScheduleRoutes({ schedule, getTitle }: ScheduleRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/schedule - Planned maintenance windows and report runs
+   */
+  router.get('/api/schedule', async (_req, res) => {
+    try {
+      res.json(await schedule.get());
+    } catch (error) {
+      log.error('Schedule error:', error);
+      res.status(500).json({ error: 'Failed to get schedule' });
+    }
+  });
+
+  /**
+   * PUT /api/schedule - Replace the maintenance and report schedule (admin only)
+   */
+  router.put('/api/schedule', requireAdmin, async (req, res) => {
+    let parsed;
+    try {
+      parsed = parseSchedule(req.body);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid schedule' });
+    }
+
+    try {
+      await schedule.save(parsed);
+      res.json(parsed);
+    } catch (error) {
+      log.error('Schedule error:', error);
+      res.status(500).json({ error: 'Failed to save schedule' });
+    }
+  });
+
+  /**
+   * GET /calendar.ics - iCalendar feed of the maintenance and report schedule
+   */
+  router.get('/calendar.ics', async (_req, res) => {
+    try {
+      res.type('text/calendar').send(renderIcalendar(await schedule.get(), {
+        calendarName: await getTitle(),
+      }));
+    } catch (error) {
+      log.error('Calendar error:', error);
+      res.status(500).json({ error: 'Failed to render calendar' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,51 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import http from 'node:http';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp } from '../app.js';
+import { createAuditLog } from '../audit.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { createServiceRoutes } from './services.js';
+
+test('controls deployed services, records each action and refuses while busy', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-services-'));
+  const auditLog = createAuditLog(path.join(dir, 'audit.log'));
+  const actions: string[] = [];
+  let busy = false;
+
+  const router = express.Router();
+  router.use(createServiceRoutes({
+    auditLog,
+    getMode: async () => 'no-jd',
+    control: async (service, action) => {
+      actions.push(`${action} ${service}`);
+    },
+    beginOperation: () => (busy ? null : () => {}),
+  }));
+  const app = createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) });
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = `http://127.0.0.1:${port}/api/v1/services`;
+
+  const restarted = await fetch(`${url}/translator/restart`, { method: 'POST' });
+  assert.equal(restarted.status, 200);
+  assert.deepEqual(await restarted.json(), { success: true, service: 'translator', action: 'restart' });
+
+  assert.equal((await fetch(`${url}/translator/kill`, { method: 'POST' })).status, 400);
+  assert.equal((await fetch(`${url}/jdc/stop`, { method: 'POST' })).status, 404);
+  busy = true;
+  assert.equal((await fetch(`${url}/translator/stop`, { method: 'POST' })).status, 409);
+
+  assert.deepEqual(actions, ['restart translator']);
+  const entries = await auditLog.query();
+  assert.deepEqual(entries.map((entry) => [entry.service, entry.path, entry.status]), [
+    ['translator', '/api/v1/services/translator/stop', 409],
+    ['jdc', '/api/v1/services/jdc/stop', 404],
+    ['translator', '/api/v1/services/translator/restart', 200],
+  ]);
+});
@@ -0,0 +1,74 @@
+/**
+ * Lifecycle control of the mining containers (see service-control.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import type { SetupMode } from '@sv2-ui/shared';
+import { createAuditMiddleware } from '../audit.js';
+import type { AuditLog } from '../audit.js';
+import { requireOperator } from '../auth.js';
+import { createLogger } from '../logger.js';
+import type { MonitoringService } from '../monitoring.js';
+import { isServiceDeployed, parseServiceAction } from '../service-control.js';
+import type { ServiceAction } from '../service-control.js';
+
+const log = createLogger('routes/services');
+
+export interface ServiceRouteDeps {
+  auditLog: AuditLog;
+  getMode: () => Promise<SetupMode | null>;
+  control: (service: MonitoringService, action: ServiceAction) => Promise<void>;
+  // Claims the stack for the duration of the action; null when it's busy.
+  beginOperation: () => (() => void) | null;
+  isNotFound?: (error: unknown) => boolean;
+}
+
+export function createServiceRoutes({
+  auditLog,
+  getMode,
+  control,
+  beginOperation,
+  isNotFound = () => false,
+}: ServiceRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * POST /api/v1/services/:service/start|stop|restart - Control the translator or JDC container (operator or admin)
+   */
+  router.post('/api/v1/services/:service/:action', requireOperator, (req, res, next) => {
+    let parsed;
+    try {
+      parsed = parseServiceAction(req.params.service, req.params.action);
+    } catch (error) {
+      return res.status(400).json({ success: false, error: error instanceof Error ? error.message : 'Invalid service action' });
+    }
+    res.locals.serviceAction = parsed;
+    createAuditMiddleware(auditLog, parsed.service)(req, res, next);
+  }, async (_req, res) => {
+    const { service, action } = res.locals.serviceAction as ReturnType<typeof parseServiceAction>;
+
+    let finish;
+    try {
+      if (!isServiceDeployed(service, await getMode())) {
+        return res.status(404).json({ success: false, error: `${service} is not part of the deployed stack` });
+      }
+      finish = beginOperation();
+      if (!finish) {
+        return res.status(409).json({ success: false, error: 'Mining services are busy. Please wait.' });
+      }
+      await control(service, action);
+      res.json({ success: true, service, action });
+    } catch (error) {
+      if (isNotFound(error)) {
+        return res.status(404).json({ success: false, error: `No ${service} container; run setup to create it` });
+      }
+      log.error('Service control error:', error);
+      res.status(500).json({ success: false, error: `Failed to ${action} ${service}` });
+    } finally {
+      finish?.();
+    }
+  });
+
+  return router;
+}
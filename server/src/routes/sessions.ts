@@ -0,0 +1,81 @@
+/**
+ * Login and logout for cookie sessions (see sessions.ts).
+ */
+
+import express from 'express';
+import type { CookieOptions, Request, Router } from 'express';
+import { createLogger } from '../logger.js';
+import { createRateLimitMiddleware, createTokenBucketLimiter, LOGIN_RATE_LIMIT } from '../rate-limit.js';
+import type { TokenBucketLimiter } from '../rate-limit.js';
+import { parseCookies, SESSION_COOKIE } from '../sessions.js';
+import type { SessionConfig, SessionStore } from '../sessions.js';
+import type { UserStore } from '../users.js';
+
+const log = createLogger('routes/sessions');
+
+export interface SessionRouteDeps {
+  sessions: SessionStore;
+  users: Pick<UserStore, 'count' | 'authenticate'>;
+  config: SessionConfig;
+  // BASE_PATH, so the cookie is only sent to this server's routes.
+  cookiePath?: string;
+  // Shared with Basic credential checks (see users.ts), so both count
+  // against the same per-client budget.
+  loginLimiter?: TokenBucketLimiter;
+}
+
+export function createSessionRoutes({
+  sessions,
+  users,
+  config,
+  cookiePath = '',
+  loginLimiter = createTokenBucketLimiter(LOGIN_RATE_LIMIT),
+}: SessionRouteDeps): Router {
+  const router = express.Router();
+  const loginRateLimit = createRateLimitMiddleware(loginLimiter);
+
+  const cookieOptions = (req: Request): CookieOptions => ({
+    httpOnly: true,
+    sameSite: 'strict',
+    // Behind a TLS-terminating proxy this needs TRUST_PROXY.
+    secure: req.secure,
+    path: cookiePath || '/',
+  });
+
+  /**
+   * POST /api/v1/auth/login - Sign in with a username and password, setting the session cookie
+   */
+  router.post('/api/v1/auth/login', loginRateLimit, async (req, res) => {
+    try {
+      const { username, password } = (req.body ?? {}) as { username?: unknown; password?: unknown };
+      if (typeof username !== 'string' || typeof password !== 'string') {
+        return res.status(400).json({ error: 'username and password are required' });
+      }
+      if (users.count() === 0) {
+        return res.status(409).json({ error: 'There are no user accounts yet' });
+      }
+
+      const user = await users.authenticate(username, password);
+      if (!user) return res.status(401).json({ error: 'Invalid username or password' });
+
+      const { token, expiresAt } = sessions.create(user.id);
+      res.cookie(SESSION_COOKIE, token, { ...cookieOptions(req), maxAge: config.lifetimeMs });
+      res.json({ user, expiresAt, idleTimeoutSecs: config.idleMs / 1000 });
+    } catch (error) {
+      log.error('Login error:', error);
+      res.status(500).json({ error: 'Failed to sign in' });
+    }
+  });
+
+  /**
+   * POST /api/v1/auth/logout - End the current session and clear its cookie
+   */
+  router.post('/api/v1/auth/logout', (req, res) => {
+    const token = parseCookies(req.headers.cookie)[SESSION_COOKIE];
+    if (token) sessions.destroy(token);
+    res.clearCookie(SESSION_COOKIE, cookieOptions(req));
+    res.json({ success: true });
+  });
+
+  return router;
+}
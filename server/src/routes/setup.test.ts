@@ -0,0 +1,89 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import http from 'node:http';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp } from '../app.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { BITCOIN_ERROR_MESSAGES } from '../messages.js';
+import { createStateStore } from '../state.js';
+import type { SetupData } from '../types.js';
+import { createSetupRoutes } from './setup.js';
+
+const NO_JD_DATA: SetupData = {
+  miningMode: 'pool',
+  mode: 'no-jd',
+  pool: {
+    name: 'Remote Pool',
+    address: 'remote.pool.com',
+    port: 3333,
+    authority_public_key: 'remote-pool-key',
+  },
+  bitcoin: null,
+  jdc: null,
+  translator: {
+    user_identity: 'miner.solo',
+    enable_vardiff: true,
+    aggregate_channels: false,
+    min_hashrate: 100_000_000_000_000,
+    shares_per_minute: 6,
+    downstream_extranonce2_size: 4,
+  },
+};
+
+test('sets up, restarts and resets the stack, refusing while busy', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-setup-routes-'));
+  const configDir = path.join(dir, 'config');
+  const stateStore = createStateStore(path.join(dir, 'state.json'));
+  const actions: string[] = [];
+  let busy = false;
+
+  const router = express.Router();
+  router.use(createSetupRoutes({
+    stateStore,
+    configDir,
+    startStack: async (data) => {
+      actions.push(`start ${data.mode}`);
+    },
+    stopStack: async () => {
+      actions.push('stop');
+    },
+    ensureDockerAvailable: async () => {},
+    getBitcoinSocketError: async () => null,
+    beginOperation: () => (busy ? null : () => {}),
+  }));
+  const app = createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) });
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = `http://127.0.0.1:${port}/api`;
+  const post = (route: string, body?: unknown) => fetch(`${url}/${route}`, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(body ?? {}),
+  });
+
+  assert.equal((await post('restart')).status, 400);
+
+  const incomplete = await post('setup', { ...NO_JD_DATA, pool: null });
+  assert.equal(incomplete.status, 400);
+  assert.equal((await incomplete.json()).error, BITCOIN_ERROR_MESSAGES.missingConfig);
+
+  assert.equal((await post('setup', NO_JD_DATA)).status, 200);
+  assert.match(await fs.readFile(path.join(configDir, 'translator.toml'), 'utf-8'), /user_identity = "miner\.solo"/);
+  assert.equal((await stateStore.load()).configured, true);
+
+  busy = true;
+  assert.equal((await post('restart')).status, 409);
+  busy = false;
+  assert.equal((await post('restart')).status, 200);
+
+  assert.equal((await post('reset')).status, 200);
+  assert.equal((await stateStore.load()).configured, false);
+  await assert.rejects(fs.access(path.join(configDir, 'translator.toml')));
+
+  assert.deepEqual(actions, ['stop', 'start no-jd', 'stop', 'start no-jd', 'stop']);
+});
@@ -0,0 +1,295 @@
+/**
+ * Stack lifecycle routes: the setup wizard's submit, config updates, and
+ * stopping, restarting and resetting the whole stack.
+ *
+ * Each one writes translator.toml and jdc.toml to the config dir (see
+ * config-generator.ts) before recreating the containers, and holds the stack
+ * while it runs so auto-start and other requests don't interleave.
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import express from 'express';
+import type { Router } from 'express';
+import { requireAdmin, requireOperator } from '../auth.js';
+import {
+  generateJdcConfig,
+  generateTranslatorConfig,
+  getBitcoinCoreVersionError,
+  getSetupDataError,
+  normalizeSetupData,
+} from '../config-generator.js';
+import { createLogger } from '../logger.js';
+import type { StateStore } from '../state.js';
+import type { SetupData, SetupResponse } from '../types.js';
+
+const log = createLogger('routes/setup');
+
+export interface SetupRouteDeps {
+  stateStore: Pick<StateStore, 'load' | 'save' | 'clear'>;
+  configDir: string;
+  startStack: (data: SetupData, configDir: string) => Promise<void>;
+  stopStack: () => Promise<void>;
+  ensureDockerAvailable: () => Promise<void>;
+  // Why the JD-mode node's IPC socket can't be used, or null when it can.
+  getBitcoinSocketError: (data: SetupData) => Promise<string | null>;
+  // Claims the stack for the duration of the request; null when it's busy.
+  beginOperation: () => (() => void) | null;
+  getBusyError?: () => string;
+}
+
+export function createSetupRoutes({
+  stateStore,
+  configDir,
+  startStack,
+  stopStack,
+  ensureDockerAvailable,
+  getBitcoinSocketError,
+  beginOperation,
+  getBusyError = () => 'Mining services are busy. Please wait.',
+}: SetupRouteDeps): Router {
+  const router = express.Router();
+  const translatorPath = path.join(configDir, 'translator.toml');
+  const jdcPath = path.join(configDir, 'jdc.toml');
+
+  async function writeServiceConfigs(data: SetupData, verb: 'Generated' | 'Updated'): Promise<void> {
+    await fs.mkdir(configDir, { recursive: true });
+
+    // Remove if exists as directory (can happen from Docker volume mounts)
+    for (const file of [translatorPath, jdcPath]) {
+      try {
+        if ((await fs.stat(file)).isDirectory()) {
+          await fs.rm(file, { recursive: true });
+        }
+      } catch {
+        // Doesn't exist, fine
+      }
+    }
+
+    await fs.writeFile(translatorPath, generateTranslatorConfig(data));
+    log.info(`${verb} translator.toml`);
+
+    if (data.mode === 'jd') {
+      const jdcConfig = generateJdcConfig(data);
+      if (jdcConfig) {
+        await fs.writeFile(jdcPath, jdcConfig);
+        log.info(`${verb} jdc.toml`);
+      }
+    }
+  }
+
+  /**
+   * PUT /api/config - Update configuration and restart with new values (admin only)
+   */
+  router.put('/api/config', requireAdmin, async (req, res) => {
+    const finish = beginOperation();
+    if (!finish) {
+      return res.status(409).json({ success: false, error: getBusyError() });
+    }
+
+    try {
+      const state = await stateStore.load();
+
+      if (!state.configured || !state.data) {
+        return res.status(400).json({ success: false, error: 'No configuration to update' });
+      }
+
+      const updates = req.body as Partial<SetupData>;
+      const currentData = state.data;
+      const newData: SetupData = normalizeSetupData({
+        ...currentData,
+        ...updates,
+        mode: updates.mode ?? currentData.mode,
+        miningMode: updates.miningMode ?? currentData.miningMode,
+        pool: updates.pool ?? currentData.pool,
+        bitcoin: updates.bitcoin ?? currentData.bitcoin,
+        jdc: updates.jdc ?? currentData.jdc,
+        translator: updates.translator ?? currentData.translator,
+      });
+
+      const dataError = getSetupDataError(newData);
+      if (dataError) {
+        return res.status(400).json({ success: false, error: dataError });
+      }
+
+      await ensureDockerAvailable();
+
+      const bitcoinSocketError = await getBitcoinSocketError(newData);
+      if (bitcoinSocketError) {
+        return res.status(400).json({ success: false, error: bitcoinSocketError });
+      }
+
+      await writeServiceConfigs(newData, 'Updated');
+      await stateStore.save(newData);
+
+      await stopStack();
+
+      await startStack(newData, configDir);
+
+      const response: SetupResponse = { success: true };
+      res.json(response);
+    } catch (error) {
+      log.error('Config update error:', error);
+      const response: SetupResponse = {
+        success: false,
+        error: error instanceof Error ? error.message : 'Failed to update config',
+      };
+      res.status(500).json(response);
+    } finally {
+      finish();
+    }
+  });
+
+  /**
+   * POST /api/setup - Configure and start the stack (admin only)
+   */
+  router.post('/api/setup', requireAdmin, async (req, res) => {
+    const finish = beginOperation();
+    if (!finish) {
+      return res.status(409).json({ success: false, error: getBusyError() });
+    }
+
+    try {
+      const data = normalizeSetupData(req.body as SetupData);
+
+      // Validate required fields
+      const dataError = getSetupDataError(data);
+      if (dataError) {
+        return res.status(400).json({ success: false, error: dataError });
+      }
+
+      await ensureDockerAvailable();
+
+      const bitcoinSocketError = await getBitcoinSocketError(data);
+      if (bitcoinSocketError) {
+        return res.status(400).json({ success: false, error: bitcoinSocketError });
+      }
+
+      await writeServiceConfigs(data, 'Generated');
+
+      // Save state
+      await stateStore.save(data);
+
+      // Stop any running containers first (graceful shutdown order matters:
+      // JDC must be stopped before Translator to avoid crashing Bitcoin Core).
+      // This is critical when switching from JD mode to solo mining — without
+      // this, the old JDC container would be left running and crash when the
+      // Translator is replaced underneath it.
+      await stopStack();
+
+      // Start the stack
+      await startStack(data, configDir);
+
+      const response: SetupResponse = { success: true };
+      res.json(response);
+    } catch (error) {
+      log.error('Setup error:', error);
+      const response: SetupResponse = {
+        success: false,
+        error: error instanceof Error ? error.message : 'Unknown error'
+      };
+      res.status(500).json(response);
+    } finally {
+      finish();
+    }
+  });
+
+  /**
+   * POST /api/stop - Stop the stack (operator or admin)
+   */
+  router.post('/api/stop', requireOperator, async (_req, res) => {
+    const finish = beginOperation();
+    if (!finish) {
+      return res.status(409).json({ success: false, error: getBusyError() });
+    }
+
+    try {
+      const state = await stateStore.load();
+      if (state.configured && state.data) await stateStore.save(state.data, false);
+
+      await stopStack();
+      res.json({ success: true });
+    } catch (error) {
+      log.error('Stop error:', error);
+      res.status(500).json({ success: false, error: 'Failed to stop stack' });
+    } finally {
+      finish();
+    }
+  });
+
+  /**
+   * POST /api/restart - Restart the stack (operator or admin)
+   */
+  router.post('/api/restart', requireOperator, async (_req, res) => {
+    const finish = beginOperation();
+    if (!finish) {
+      return res.status(409).json({ success: false, error: getBusyError() });
+    }
+
+    try {
+      const state = await stateStore.load();
+      if (!state.configured || !state.data) {
+        return res.status(400).json({ success: false, error: 'Not configured' });
+      }
+
+      await stateStore.save(state.data, true);
+
+      const bitcoinCoreVersionError = getBitcoinCoreVersionError(state.data);
+      if (bitcoinCoreVersionError) {
+        return res.status(400).json({ success: false, error: bitcoinCoreVersionError });
+      }
+
+      await ensureDockerAvailable();
+
+      const bitcoinSocketError = await getBitcoinSocketError(state.data);
+      if (bitcoinSocketError) {
+        return res.status(400).json({ success: false, error: bitcoinSocketError });
+      }
+
+      await stopStack();
+      await startStack(state.data, configDir);
+
+      res.json({ success: true });
+    } catch (error) {
+      log.error('Restart error:', error);
+      res.status(500).json({ success: false, error: 'Failed to restart stack' });
+    } finally {
+      finish();
+    }
+  });
+
+  /**
+   * POST /api/reset - Reset configuration (stop containers and delete config) (admin only)
+   */
+  router.post('/api/reset', requireAdmin, async (_req, res) => {
+    const finish = beginOperation();
+    if (!finish) {
+      return res.status(409).json({ success: false, error: getBusyError() });
+    }
+
+    try {
+      // Stop containers first
+      await stopStack();
+
+      // Delete state file
+      await stateStore.clear();
+
+      // Delete config files
+      try {
+        await fs.unlink(translatorPath);
+        await fs.unlink(jdcPath);
+      } catch {
+        // Files might not exist
+      }
+
+      res.json({ success: true });
+    } catch (error) {
+      log.error('Reset error:', error);
+      res.status(500).json({ success: false, error: 'Failed to reset configuration' });
+    } finally {
+      finish();
+    }
+  });
+
+  return router;
+}
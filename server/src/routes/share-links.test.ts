@@ -0,0 +1,72 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import http from 'node:http';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp } from '../app.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { createShareLinkStore } from '../share-links.js';
+import type { StackSummary } from '../summary.js';
+import { createShareLinkRoutes } from './share-links.js';
+
+const SUMMARY: StackSummary = {
+  generatedAt: '2026-04-17T12:00:00.000Z',
+  miningMode: 'pool',
+  mode: 'no-jd',
+  network: null,
+  poolName: null,
+  online: true,
+  hashrate: 1e12,
+  workers: 2,
+  sharesAccepted: 10,
+  sharesSubmitted: 11,
+  sharesRejected: 1,
+  bestDifficulty: 5000,
+  blocksFound: 0,
+  uptimeSecs: 3600,
+};
+
+test('mints share links under BASE_PATH and serves them publicly', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-share-routes-'));
+  let summary = SUMMARY;
+  const router = express.Router();
+  router.use(createShareLinkRoutes({
+    shareLinks: createShareLinkStore(path.join(dir, 'share-links.json')),
+    getStackSummary: async () => summary,
+    basePath: '/sv2',
+  }));
+  const app = createApp({ router, basePath: '/sv2', trustProxy: undefined, ipFilter: parseIpFilter({}) });
+
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = `http://127.0.0.1:${port}`;
+
+  const invalid = await fetch(`${url}/sv2/api/share-links`, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify({ view: 'forever' }),
+  });
+  assert.equal(invalid.status, 400);
+
+  const created = await fetch(`${url}/sv2/api/share-links`, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify({ view: 'snapshot' }),
+  });
+  assert.equal(created.status, 201);
+  const { link, path: sharePath } = await created.json();
+  assert.equal(sharePath, `/sv2/share/${link.token}`);
+
+  // Snapshots keep the summary from when the link was minted.
+  summary = { ...SUMMARY, hashrate: 0 };
+  const shared = await fetch(`${url}${sharePath}`, { headers: { Accept: 'application/json' } });
+  assert.equal(shared.status, 200);
+  assert.equal((await shared.json()).summary.hashrate, 1e12);
+
+  assert.equal((await fetch(`${url}/sv2/api/share-links/${link.token}`, { method: 'DELETE' })).status, 200);
+  assert.equal((await fetch(`${url}${sharePath}`)).status, 404);
+});
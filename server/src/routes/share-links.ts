@@ -0,0 +1,102 @@
+/**
+ * Share link routes: the admin endpoints that mint and revoke links, and the
+ * public page behind each one (see share-links.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { formatDifficulty, formatHashrate, formatUptime } from '@sv2-ui/shared';
+import { requireAdmin } from '../auth.js';
+import { createLogger } from '../logger.js';
+import { redactForPublic } from '../redact.js';
+import { parseShareLinkRequest, renderSharePage } from '../share-links.js';
+import type { ShareLinkStore } from '../share-links.js';
+import type { StackSummary } from '../summary.js';
+
+const log = createLogger('routes/share-links');
+
+export interface ShareLinkRouteDeps {
+  shareLinks: ShareLinkStore;
+  getStackSummary: () => Promise<StackSummary>;
+  basePath?: string;
+}
+
+export function createShareLinkRoutes({ shareLinks, getStackSummary, basePath = '' }: ShareLinkRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/share-links - List active share links (admin only)
+   */
+  router.get('/api/share-links', requireAdmin, async (_req, res) => {
+    try {
+      res.json({ links: await shareLinks.list() });
+    } catch (error) {
+      log.error('Share links error:', error);
+      res.status(500).json({ error: 'Failed to list share links' });
+    }
+  });
+
+  /**
+   * POST /api/share-links - Mint an expiring public link to a snapshot or live summary (admin only)
+   */
+  router.post('/api/share-links', requireAdmin, async (req, res) => {
+    let request;
+    try {
+      request = parseShareLinkRequest(req.body);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid share link request' });
+    }
+
+    try {
+      const snapshot = request.view === 'snapshot' ? await getStackSummary() : null;
+      const link = await shareLinks.create({ ...request, snapshot });
+      res.status(201).json({ link, path: `${basePath}/share/${link.token}` });
+    } catch (error) {
+      log.error('Share link error:', error);
+      res.status(500).json({ error: 'Failed to create share link' });
+    }
+  });
+
+  /**
+   * DELETE /api/share-links/:token - Revoke a share link (admin only)
+   */
+  router.delete('/api/share-links/:token', requireAdmin, async (req, res) => {
+    try {
+      if (!(await shareLinks.revoke(req.params.token))) {
+        return res.status(404).json({ error: 'Share link not found' });
+      }
+      res.json({ success: true });
+    } catch (error) {
+      log.error('Share link error:', error);
+      res.status(500).json({ error: 'Failed to revoke share link' });
+    }
+  });
+
+  /**
+   * GET /share/:token - Public, read-only summary behind an expiring share link
+   */
+  router.get('/share/:token', async (req, res) => {
+    try {
+      const link = await shareLinks.get(req.params.token);
+      if (!link) {
+        return res.status(404).json({ error: 'Share link not found or expired' });
+      }
+
+      const summary = redactForPublic(link.snapshot ?? await getStackSummary());
+      res.set('Cache-Control', 'no-store');
+      res.format({
+        html: () => res.send(renderSharePage(summary, link, {
+          hashrate: formatHashrate,
+          difficulty: formatDifficulty,
+          uptime: formatUptime,
+        })),
+        json: () => res.json({ view: link.view, expiresAt: link.expiresAt, summary }),
+      });
+    } catch (error) {
+      log.error('Share link error:', error);
+      res.status(500).json({ error: 'Failed to load share link' });
+    }
+  });
+
+  return router;
+}
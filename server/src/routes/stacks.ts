@@ -0,0 +1,155 @@
+/**
+ * Remote stack routes: the registry of other Translator/JDC pairs this
+ * instance watches, their live summaries, events and alerts (see stacks.ts),
+ * and what mDNS discovery found that isn't registered yet.
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import type { AlertRuleStore } from '../alerts.js';
+import { requireAdmin } from '../auth.js';
+import { getUnconfiguredServices, groupDiscoveredStacks } from '../discovery.js';
+import type { Discovery } from '../discovery.js';
+import { createLogger } from '../logger.js';
+import { redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+import { parseRemoteStacks } from '../stacks.js';
+import type { RemoteStack, StackMonitors, StackRegistry } from '../stacks.js';
+import type { StackSummary } from '../summary.js';
+
+const log = createLogger('routes/stacks');
+
+export interface StackRouteDeps {
+  registry: Pick<StackRegistry, 'get' | 'save'>;
+  monitors: Pick<StackMonitors, 'sync' | 'get'>;
+  alertRules: Pick<AlertRuleStore, 'get'>;
+  // Fetched on every request, with the freshness and errors of the fetch.
+  getSummary: (stack: RemoteStack) => Promise<StackSummary & Record<string, unknown>>;
+  // Null when MDNS_DISCOVERY is off.
+  discovery?: Pick<Discovery, 'get'> | null;
+  redact?: Redactor;
+}
+
+export function createStackRoutes({
+  registry,
+  monitors,
+  alertRules,
+  getSummary,
+  discovery = null,
+  redact = redactForRequest,
+}: StackRouteDeps): Router {
+  const router = express.Router();
+
+  async function getMonitor(id: string) {
+    monitors.sync(await registry.get());
+    return monitors.get(id);
+  }
+
+  /**
+   * GET /api/stacks - Remote stacks with their latest sampled summary
+   */
+  router.get('/api/stacks', async (req, res) => {
+    try {
+      const synced = monitors.sync(await registry.get());
+      res.json(redact(req, {
+        stacks: synced.map(({ stack, latest }) => ({ ...stack, summary: latest })),
+      }));
+    } catch (error) {
+      log.error('Stacks error:', error);
+      res.status(500).json({ error: 'Failed to get stacks' });
+    }
+  });
+
+  /**
+   * PUT /api/stacks - Replace the remote stacks (admin only)
+   */
+  router.put('/api/stacks', requireAdmin, async (req, res) => {
+    let stacks;
+    try {
+      stacks = parseRemoteStacks(req.body);
+    } catch (error) {
+      return res.status(400).json({ error: error instanceof Error ? error.message : 'Invalid stacks' });
+    }
+
+    try {
+      await registry.save(stacks);
+      monitors.sync(stacks);
+      res.json({ stacks });
+    } catch (error) {
+      log.error('Stacks error:', error);
+      res.status(500).json({ error: 'Failed to save stacks' });
+    }
+  });
+
+  /**
+   * GET /api/v1/discovered - Translator and JDC services found over mDNS that no stack uses yet
+   */
+  router.get('/api/v1/discovered', async (_req, res) => {
+    if (!discovery) {
+      return res.status(404).json({ error: 'mDNS discovery is disabled (set MDNS_DISCOVERY)' });
+    }
+
+    try {
+      const { browsedAt, services } = discovery.get();
+      const unconfigured = getUnconfiguredServices(services, await registry.get());
+      res.json({ browsedAt, services: unconfigured, stacks: groupDiscoveredStacks(unconfigured) });
+    } catch (error) {
+      log.error('Discovery error:', error);
+      res.status(500).json({ error: 'Failed to list discovered services' });
+    }
+  });
+
+  /**
+   * GET /api/stacks/:id/summary - Live summary of one remote stack
+   */
+  router.get('/api/stacks/:id/summary', async (req, res) => {
+    try {
+      const monitor = await getMonitor(req.params.id);
+      if (!monitor) {
+        return res.status(404).json({ error: 'Unknown stack' });
+      }
+      res.json(redact(req, await getSummary(monitor.stack)));
+    } catch (error) {
+      log.error('Stack summary error:', error);
+      res.status(500).json({ error: 'Failed to get stack summary' });
+    }
+  });
+
+  /**
+   * GET /api/stacks/:id/events - Recent notable events of one remote stack
+   */
+  router.get('/api/stacks/:id/events', async (req, res) => {
+    try {
+      const monitor = await getMonitor(req.params.id);
+      if (!monitor) {
+        return res.status(404).json({ error: 'Unknown stack' });
+      }
+      res.json(redact(req, { events: await monitor.eventLog.list() }));
+    } catch (error) {
+      log.error('Stack events error:', error);
+      res.status(500).json({ error: 'Failed to get stack events' });
+    }
+  });
+
+  /**
+   * GET /api/stacks/:id/alerts - Alert status of one remote stack, using the shared rules
+   */
+  router.get('/api/stacks/:id/alerts', async (req, res) => {
+    try {
+      const monitor = await getMonitor(req.params.id);
+      if (!monitor) {
+        return res.status(404).json({ error: 'Unknown stack' });
+      }
+      res.json(redact(req, {
+        alerts: monitor.alerts.list(await alertRules.get()),
+        anomalies: monitor.alerts.anomalies(),
+        transitions: monitor.alerts.transitions(),
+      }));
+    } catch (error) {
+      log.error('Stack alerts error:', error);
+      res.status(500).json({ error: 'Failed to get stack alerts' });
+    }
+  });
+
+  return router;
+}
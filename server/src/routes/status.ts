@@ -0,0 +1,128 @@
+/**
+ * Status routes: whether the stack is configured and running, its saved
+ * config, the summary the dashboard is built from (also as a stream), and
+ * the whole-server snapshot for bug reports (see snapshot.ts).
+ */
+
+import express from 'express';
+import type { RequestHandler, Router } from 'express';
+import { requireAdmin } from '../auth.js';
+import { createLogger } from '../logger.js';
+import { redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+import { getRuntimeSnapshot } from '../runtime.js';
+import type { StateSnapshot } from '../snapshot.js';
+import type { SavedState } from '../state.js';
+import type { TelemetryStatus } from '../telemetry.js';
+import type { StatusResponse } from '../types.js';
+
+const log = createLogger('routes/status');
+
+export interface StatusRouteDeps {
+  loadState: () => Promise<SavedState>;
+  getStatus: (state: SavedState) => Promise<StatusResponse>;
+  // The summary with its freshness and errors, from the poller's latest snapshot.
+  getSummary: (state: SavedState) => Promise<StateSnapshot['summary']>;
+  getSnapshot: () => Promise<StateSnapshot>;
+  getTelemetryStatus: () => TelemetryStatus;
+  // The handler behind /api/summary/stream (see summary-stream.ts).
+  summaryStream: RequestHandler;
+  // In front of /api/summary/stream, so it holds an API key's stream (see api-keys.ts).
+  holdStream?: RequestHandler;
+  env?: NodeJS.ProcessEnv;
+  redact?: Redactor;
+}
+
+export function createStatusRoutes({
+  loadState,
+  getStatus,
+  getSummary,
+  getSnapshot,
+  getTelemetryStatus,
+  summaryStream,
+  holdStream = (_req, _res, next) => next(),
+  env = process.env,
+  redact = redactForRequest,
+}: StatusRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/debug/runtime - Process memory, task, and cache introspection (admin only)
+   */
+  router.get('/api/debug/runtime', requireAdmin, (_req, res) => {
+    res.json(getRuntimeSnapshot());
+  });
+
+  /**
+   * GET /api/status - Get current stack status
+   */
+  router.get('/api/status', async (_req, res) => {
+    try {
+      res.json(await getStatus(await loadState()));
+    } catch (error) {
+      log.error('Status error:', error);
+      res.status(500).json({ error: 'Failed to get status' });
+    }
+  });
+
+  /**
+   * GET /api/config - Get current configuration
+   */
+  router.get('/api/config', async (req, res) => {
+    try {
+      const state = await loadState();
+      res.json(redact(req, {
+        configured: state.configured,
+        config: state.data,
+        telemetry: getTelemetryStatus(),
+      }));
+    } catch (error) {
+      log.error('Config error:', error);
+      res.status(500).json({ error: 'Failed to get config' });
+    }
+  });
+
+  /**
+   * GET /api/summary - Aggregate hashrate, shares, and best difficulty for the stack, with per-section freshness
+   */
+  router.get('/api/summary', async (req, res) => {
+    try {
+      res.json(redact(req, await getSummary(await loadState())));
+    } catch (error) {
+      log.error('Summary error:', error);
+      res.status(500).json({ error: 'Failed to get summary' });
+    }
+  });
+
+  /**
+   * GET /api/summary/stream - Server-sent events with the summary, then only what changed after each poll
+   */
+  router.get('/api/summary/stream', holdStream, summaryStream);
+
+  /**
+   * GET /api/v1/snapshot - Everything the server currently knows in one JSON document, for bug reports and backups
+   */
+  router.get('/api/v1/snapshot', async (req, res) => {
+    try {
+      const body = await getSnapshot();
+      res
+        .set({
+          'Cache-Control': 'no-store',
+          'Content-Disposition': `attachment; filename="sv2-ui-snapshot-${body.generatedAt.slice(0, 10)}.json"`,
+        })
+        .json(redact(req, body));
+    } catch (error) {
+      log.error('Snapshot error:', error);
+      res.status(500).json({ error: 'Failed to build snapshot' });
+    }
+  });
+
+  /**
+   * GET /api/env - Host environment variables relevant to the UI
+   */
+  router.get('/api/env', (_req, res) => {
+    res.json({ HOST_OS: env.HOST_OS || null, STRATUM_HOST: env.STRATUM_HOST || null });
+  });
+
+  return router;
+}
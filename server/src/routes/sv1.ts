@@ -0,0 +1,71 @@
+/**
+ * Checks of the Translator's SV1 port: whether it accepts miners, and a
+ * scripted subscribe/authorize the way a miner would (see sv1-listener.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { createLogger } from '../logger.js';
+import type { Sv1ListenerStatus, Sv1ProbeResult } from '../sv1-listener.js';
+
+const log = createLogger('routes/sv1');
+
+export interface Sv1RouteDeps {
+  // The last background check, refreshed here once it's older than listenerMaxAgeMs.
+  getListenerStatus: () => Sv1ListenerStatus | null;
+  refreshListenerStatus: () => Promise<void>;
+  listenerMaxAgeMs: number;
+  isConfigured: () => Promise<boolean>;
+  // A fresh probe, as `user` when given.
+  probe: (user?: string) => Promise<Sv1ProbeResult | null>;
+  now?: () => number;
+}
+
+export function createSv1Routes({
+  getListenerStatus,
+  refreshListenerStatus,
+  listenerMaxAgeMs,
+  isConfigured,
+  probe,
+  now = Date.now,
+}: Sv1RouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/sv1-listener - Whether the Translator's SV1 port accepts miners, and how many are attached
+   */
+  router.get('/api/sv1-listener', async (_req, res) => {
+    try {
+      const status = getListenerStatus();
+      const checkedAt = status ? Date.parse(status.checkedAt) : 0;
+      if (now() - checkedAt > listenerMaxAgeMs) {
+        await refreshListenerStatus();
+      }
+      res.json(getListenerStatus());
+    } catch (error) {
+      log.error('SV1 listener error:', error);
+      res.status(500).json({ error: 'Failed to check SV1 listener' });
+    }
+  });
+
+  /**
+   * GET /api/v1/probe/sv1 - Subscribe and authorize on the Translator's SV1 port the way a miner would
+   */
+  router.get('/api/v1/probe/sv1', async (req, res) => {
+    try {
+      const { user } = req.query;
+      if (user !== undefined && (typeof user !== 'string' || !/^[\x21-\x7e]{1,128}$/.test(user))) {
+        return res.status(400).json({ error: 'user must be 1-128 printable characters without spaces' });
+      }
+      if (!await isConfigured()) {
+        return res.status(404).json({ error: 'No Translator is configured' });
+      }
+      res.set('Cache-Control', 'no-store').json(await probe(user));
+    } catch (error) {
+      log.error('SV1 probe error:', error);
+      res.status(500).json({ error: 'Failed to probe the SV1 port' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,64 @@
+/**
+ * On-demand SV2 connection tests against the pool or JDS (see sv2-probe.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { POOL_JDS_PORT } from '@sv2-ui/shared';
+import type { PoolConfig } from '@sv2-ui/shared';
+import { requireOperator } from '../auth.js';
+import { createLogger } from '../logger.js';
+import { parseAuthorityPubkey, probeSv2 } from '../sv2-probe.js';
+import type { Sv2Protocol } from '../sv2-probe.js';
+
+const log = createLogger('routes/sv2-probe');
+
+export interface Sv2ProbeRouteDeps {
+  // The configured upstream pool, used for whatever the request leaves out.
+  getPool: () => Promise<PoolConfig | null>;
+}
+
+const PROTOCOLS: Sv2Protocol[] = ['mining', 'job-declaration'];
+const HOST_PATTERN = /^([A-Za-z0-9-]+(\.[A-Za-z0-9-]+)*|[0-9A-Fa-f:]+)$/;
+
+export function createSv2ProbeRoutes({ getPool }: Sv2ProbeRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * POST /api/v1/probe/sv2 - Connect, run the Noise handshake, check the certificate and send SetupConnection, reporting each step
+   */
+  router.post('/api/v1/probe/sv2', requireOperator, async (req, res) => {
+    try {
+      const body = (req.body ?? {}) as { host?: unknown; port?: unknown; authorityPubkey?: unknown; protocol?: unknown; flags?: unknown };
+      const protocol = body.protocol ?? 'mining';
+      if (!PROTOCOLS.includes(protocol as Sv2Protocol)) {
+        return res.status(400).json({ error: `protocol must be one of ${PROTOCOLS.join(', ')}` });
+      }
+
+      const pool = await getPool();
+      const host = body.host ?? pool?.address;
+      const port = body.port ?? (protocol === 'mining' ? pool?.port : POOL_JDS_PORT);
+      const authorityPubkey = body.authorityPubkey ?? pool?.authority_public_key;
+      if (typeof host !== 'string' || !HOST_PATTERN.test(host)) {
+        return res.status(400).json({ error: 'host must be a hostname or IP address' });
+      }
+      if (typeof port !== 'number' || !Number.isInteger(port) || port < 1 || port > 65535) {
+        return res.status(400).json({ error: 'port must be an integer between 1 and 65535' });
+      }
+      if (typeof authorityPubkey !== 'string' || !parseAuthorityPubkey(authorityPubkey)) {
+        return res.status(400).json({ error: 'authorityPubkey must be a base58check authority public key' });
+      }
+      if (body.flags !== undefined && (typeof body.flags !== 'number' || !Number.isInteger(body.flags) || body.flags < 0 || body.flags > 0xffffffff)) {
+        return res.status(400).json({ error: 'flags must be a 32-bit unsigned integer' });
+      }
+
+      const result = await probeSv2({ host, port, authorityPubkey, protocol: protocol as Sv2Protocol, flags: body.flags as number | undefined });
+      res.set('Cache-Control', 'no-store').json(result);
+    } catch (error) {
+      log.error('SV2 probe error:', error);
+      res.status(500).json({ error: 'Failed to probe the SV2 endpoint' });
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,46 @@
+/**
+ * The SPA fallback: index.html for any path no other route claimed, so the
+ * dashboard's client-side routes survive a reload. Mounted last.
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import express from 'express';
+import type { Router } from 'express';
+import { renderIndexHtml } from '../base-path.js';
+import { addCspNonce, createCspNonce } from '../security-headers.js';
+
+export interface UiRouteDeps {
+  // The built UI, holding index.html.
+  uiDir: string;
+  basePath?: string;
+  // --headless: no UI is served, so unknown paths are plain 404s.
+  headless?: boolean;
+}
+
+export function createUiRoutes({ uiDir, basePath = '', headless = false }: UiRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * SPA fallback - serve index.html for client-side routing
+   */
+  router.get('*', async (_req, res) => {
+    if (headless) {
+      return res.status(404).json({ error: 'Not found; this instance is headless and serves no UI' });
+    }
+    try {
+      const html = await fs.readFile(path.join(uiDir, 'index.html'), 'utf-8');
+      const nonce = createCspNonce();
+      const csp = res.get('Content-Security-Policy');
+      if (csp) res.set('Content-Security-Policy', addCspNonce(csp, nonce));
+      res.set('Cache-Control', 'no-cache').type('html').send(renderIndexHtml(html, basePath, {
+        nonce,
+        runtimeConfig: { basePath },
+      }));
+    } catch {
+      res.status(404).send('UI build not found');
+    }
+  });
+
+  return router;
+}
@@ -0,0 +1,39 @@
+/**
+ * Newer sv2-apps images for the deployed services (see updates.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { createLogger } from '../logger.js';
+import type { UpdateChecker } from '../updates.js';
+
+const log = createLogger('routes/updates');
+
+export interface UpdateRouteDeps {
+  updates: UpdateChecker;
+  // Without the background check, every request checks the registry.
+  checkOnRequest: boolean;
+}
+
+export function createUpdateRoutes({ updates, checkOnRequest }: UpdateRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/updates - Running image digests against the registry, and the services with a newer image
+   */
+  router.get('/api/v1/updates', async (_req, res) => {
+    try {
+      const latest = updates.latest();
+      const report = checkOnRequest || latest.checkedAt === null ? await updates.check() : latest;
+      res.json({
+        ...report,
+        available: report.services.filter((status) => status.updateAvailable).map((status) => status.service),
+      });
+    } catch (error) {
+      log.error('Update check error:', error);
+      res.status(500).json({ error: 'Failed to check for image updates' });
+    }
+  });
+
+  return router;
+}
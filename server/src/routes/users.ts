@@ -0,0 +1,88 @@
+/**
+ * User account routes (see users.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { getRequestRole, requireAdmin } from '../auth.js';
+import { createLogger } from '../logger.js';
+import { parseUserRequest, UserError } from '../users.js';
+import type { UserRequest, UserStore } from '../users.js';
+import type { SessionStore } from '../sessions.js';
+
+const log = createLogger('routes/users');
+
+export interface UserRouteDeps {
+  users: UserStore;
+  // Sessions of removed users and changed passwords end at once.
+  sessions?: Pick<SessionStore, 'revokeUser'>;
+}
+
+function sendError(res: express.Response, error: unknown, fallback: string) {
+  if (error instanceof UserError) return res.status(error.status).json({ error: error.message });
+  log.error(`${fallback}:`, error);
+  return res.status(500).json({ error: fallback });
+}
+
+export function createUserRoutes({ users, sessions }: UserRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/me - The role of the current request, and the user it signed in as
+   */
+  router.get('/api/me', (req, res) => {
+    res.json({ role: getRequestRole(req), user: res.locals.user ?? res.locals.tunnelUser ?? null });
+  });
+
+  /**
+   * GET /api/admin/users - List user accounts and their roles (admin only)
+   */
+  router.get('/api/admin/users', requireAdmin, (_req, res) => {
+    try {
+      res.json({ users: users.list() });
+    } catch (error) {
+      sendError(res, error, 'Failed to list users');
+    }
+  });
+
+  /**
+   * POST /api/admin/users - Create a user with a username, password and role (admin only)
+   */
+  router.post('/api/admin/users', requireAdmin, async (req, res) => {
+    try {
+      res.status(201).json(await users.create(parseUserRequest(req.body) as Required<UserRequest>));
+    } catch (error) {
+      sendError(res, error, 'Failed to create user');
+    }
+  });
+
+  /**
+   * PATCH /api/admin/users/:id - Change a user's name, password or role (admin only)
+   */
+  router.patch('/api/admin/users/:id', requireAdmin, async (req, res) => {
+    try {
+      const request = parseUserRequest(req.body, { partial: true });
+      const user = await users.update(req.params.id, request);
+      if (!user) return res.status(404).json({ error: 'User not found' });
+      if (request.password !== undefined) sessions?.revokeUser(user.id);
+      res.json(user);
+    } catch (error) {
+      sendError(res, error, 'Failed to update user');
+    }
+  });
+
+  /**
+   * DELETE /api/admin/users/:id - Remove a user (admin only)
+   */
+  router.delete('/api/admin/users/:id', requireAdmin, (req, res) => {
+    try {
+      if (!users.remove(req.params.id)) return res.status(404).json({ error: 'User not found' });
+      sessions?.revokeUser(req.params.id);
+      res.json({ success: true });
+    } catch (error) {
+      sendError(res, error, 'Failed to remove user');
+    }
+  });
+
+  return router;
+}
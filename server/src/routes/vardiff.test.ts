@@ -0,0 +1,55 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp } from '../app.js';
+import { parseHistoryRetention } from '../history.js';
+import { parseIpFilter } from '../ip-filter.js';
+import { createRedactor, parseRedactionPaths, REDACTED } from '../redact.js';
+import { createVardiffRoutes } from './vardiff.js';
+
+test('viewers get channel workers redacted by REDACT_PATHS', async (t) => {
+  const router = express.Router();
+  router.use((req, res, next) => {
+    res.locals.role = req.get('X-Role');
+    next();
+  });
+  router.use(createVardiffRoutes({
+    tracker: {
+      list: () => [{
+        key: 'translator:sv1:1',
+        service: 'translator',
+        worker: 'bc1qexample.rig1',
+        targetHex: '00',
+        difficulty: 1024,
+        configuredSharesPerMinute: 6,
+        achievedSharesPerMinute: 5.5,
+        retargets: 1,
+        lastRetargetAt: '2026-01-01T00:00:00.000Z',
+      }],
+      recentRetargets: () => [{
+        at: '2026-01-01T00:00:00.000Z',
+        key: 'translator:sv1:1',
+        worker: 'bc1qexample.rig1',
+        fromDifficulty: 512,
+        toDifficulty: 1024,
+      }],
+    },
+    history: { query: () => [] },
+    retention: parseHistoryRetention({}),
+    getConfig: async () => ({ enabled: true, sharesPerMinute: 6 }),
+    redact: createRedactor(parseRedactionPaths('channels[].worker,retargets[].worker')),
+  }));
+  const server = http.createServer(createApp({ router, trustProxy: undefined, ipFilter: parseIpFilter({}) }));
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  const url = `http://127.0.0.1:${(server.address() as AddressInfo).port}/api/v1/vardiff`;
+  t.after(() => server.close());
+
+  const viewer = await (await fetch(url, { headers: { 'X-Role': 'viewer' } })).json();
+  assert.equal(viewer.channels[0].worker, REDACTED);
+  assert.equal(viewer.channels[0].difficulty, 1024);
+  assert.equal(viewer.retargets[0].worker, REDACTED);
+  const admin = await (await fetch(url, { headers: { 'X-Role': 'admin' } })).json();
+  assert.equal(admin.channels[0].worker, 'bc1qexample.rig1');
+});
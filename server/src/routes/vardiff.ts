@@ -0,0 +1,109 @@
+/**
+ * Per-channel variable difficulty from the vardiff tracker (see vardiff.ts).
+ */
+
+import express from 'express';
+import type { Router } from 'express';
+import { selectResolution } from '../history.js';
+import type { HistoryRetention, HistoryStore } from '../history.js';
+import { createLogger } from '../logger.js';
+import { redactForRequest } from '../redact.js';
+import type { Redactor } from '../redact.js';
+import { VARDIFF_DIFFICULTY_METRIC, VARDIFF_SHARE_RATE_METRIC } from '../vardiff.js';
+import type { VardiffTracker } from '../vardiff.js';
+
+const log = createLogger('routes/vardiff');
+
+export interface VardiffConfig {
+  // Null until the stack is configured.
+  enabled: boolean | null;
+  sharesPerMinute: number | null;
+}
+
+export interface VardiffRouteDeps {
+  tracker: Pick<VardiffTracker, 'list' | 'recentRetargets'>;
+  history: Pick<HistoryStore, 'query'>;
+  retention: HistoryRetention;
+  // The Translator's vardiff settings from the setup.
+  getConfig: () => Promise<VardiffConfig>;
+  redact?: Redactor;
+}
+
+export interface VardiffPoint {
+  at: string;
+  difficulty: number | null;
+  sharesPerMinute: number | null;
+}
+
+const DEFAULT_WINDOW_MS = 24 * 3_600_000;
+const MAX_LIMIT = 10_000;
+const MAX_RETARGETS = 500;
+
+export function createVardiffRoutes({ tracker, history, retention, getConfig, redact = redactForRequest }: VardiffRouteDeps): Router {
+  const router = express.Router();
+
+  /**
+   * GET /api/v1/vardiff - Current difficulty, retargets and achieved share rate per channel
+   */
+  router.get('/api/v1/vardiff', async (req, res) => {
+    try {
+      const { limit } = req.query;
+      const max = limit === undefined ? 100 : Number(limit);
+      if (!Number.isInteger(max) || max < 1 || max > MAX_RETARGETS) {
+        return res.status(400).json({ error: `limit must be between 1 and ${MAX_RETARGETS}` });
+      }
+
+      const config = await getConfig();
+      res.json(redact(req, {
+        ...config,
+        channels: tracker.list(config.sharesPerMinute),
+        retargets: tracker.recentRetargets(max),
+      }));
+    } catch (error) {
+      log.error('Vardiff error:', error);
+      res.status(500).json({ error: 'Failed to get vardiff statistics' });
+    }
+  });
+
+  /**
+   * GET /api/v1/vardiff/history?channel=<key> - One channel's difficulty and achieved share rate over time
+   */
+  router.get('/api/v1/vardiff/history', (req, res) => {
+    try {
+      const { channel, since, limit } = req.query;
+      if (typeof channel !== 'string' || channel === '') {
+        return res.status(400).json({ error: 'channel is required, e.g. translator:sv1:1' });
+      }
+      if (since !== undefined && (typeof since !== 'string' || Number.isNaN(Date.parse(since)))) {
+        return res.status(400).json({ error: 'since must be an ISO 8601 timestamp' });
+      }
+      const max = limit === undefined ? 1000 : Number(limit);
+      if (!Number.isInteger(max) || max < 1 || max > MAX_LIMIT) {
+        return res.status(400).json({ error: `limit must be between 1 and ${MAX_LIMIT}` });
+      }
+
+      const from = since ? new Date(since as string) : new Date(Date.now() - DEFAULT_WINDOW_MS);
+      const resolutionMs = selectResolution(retention, from) ?? undefined;
+      const points = new Map<string, VardiffPoint>();
+      for (const sample of history.query(VARDIFF_DIFFICULTY_METRIC, { key: channel, since: from, limit: max, resolutionMs })) {
+        points.set(sample.at, { at: sample.at, difficulty: sample.value, sharesPerMinute: null });
+      }
+      for (const sample of history.query(VARDIFF_SHARE_RATE_METRIC, { key: channel, since: from, limit: max, resolutionMs })) {
+        const point = points.get(sample.at);
+        if (point) point.sharesPerMinute = sample.value;
+        else points.set(sample.at, { at: sample.at, difficulty: null, sharesPerMinute: sample.value });
+      }
+
+      res.json(redact(req, {
+        channel,
+        resolutionSecs: resolutionMs === undefined ? null : resolutionMs / 1000,
+        samples: [...points.values()].sort((a, b) => a.at.localeCompare(b.at)),
+      }));
+    } catch (error) {
+      log.error('Vardiff history error:', error);
+      res.status(500).json({ error: 'Failed to get vardiff history' });
+    }
+  });
+
+  return router;
+}
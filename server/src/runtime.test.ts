@@ -0,0 +1,20 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { getRuntimeSnapshot, registerRuntimeGauge } from './runtime.js';
+
+test('runtime snapshot reports registered task and cache gauges', () => {
+  registerRuntimeGauge('tasks', 'test-poller', () => 2);
+  registerRuntimeGauge('caches', 'test-cache', () => 17);
+  registerRuntimeGauge('failures', 'test-failures', () => 3);
+  registerRuntimeGauge('caches', 'broken-cache', () => {
+    throw new Error('unavailable');
+  });
+
+  const snapshot = getRuntimeSnapshot();
+
+  assert.equal(snapshot.tasks['test-poller'], 2);
+  assert.equal(snapshot.caches['test-cache'], 17);
+  assert.equal(snapshot.caches['broken-cache'], -1);
+  assert.equal(snapshot.failures['test-failures'], 3);
+  assert.ok(snapshot.memory.rss > 0);
+});
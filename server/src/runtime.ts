@@ -0,0 +1,92 @@
+/**
+ * Process introspection for /api/debug/runtime.
+ *
+ * Subsystems register gauges for their background tasks and in-memory caches
+ * so memory reports come with enough detail to tell what is growing.
+ * Failure counters only ever grow; alert on their rate to catch chronic
+ * flakiness.
+ */
+
+import v8 from 'v8';
+import { monitorEventLoopDelay } from 'perf_hooks';
+
+export type RuntimeGaugeKind = 'tasks' | 'caches' | 'failures';
+
+type Gauge = () => number;
+
+const gauges: Record<RuntimeGaugeKind, Map<string, Gauge>> = {
+  tasks: new Map(),
+  caches: new Map(),
+  failures: new Map(),
+};
+
+const eventLoopDelay = monitorEventLoopDelay({ resolution: 20 });
+eventLoopDelay.enable();
+
+export function registerRuntimeGauge(kind: RuntimeGaugeKind, name: string, read: Gauge): void {
+  gauges[kind].set(name, read);
+}
+
+function readGauges(kind: RuntimeGaugeKind): Record<string, number> {
+  const values: Record<string, number> = {};
+  for (const [name, read] of gauges[kind]) {
+    try {
+      values[name] = read();
+    } catch {
+      values[name] = -1;
+    }
+  }
+  return values;
+}
+
+function countActiveResources(): Record<string, number> {
+  const counts: Record<string, number> = {};
+  for (const resource of process.getActiveResourcesInfo()) {
+    counts[resource] = (counts[resource] ?? 0) + 1;
+  }
+  return counts;
+}
+
+const NS_PER_MS = 1e6;
+
+export function getRuntimeSnapshot() {
+  const memory = process.memoryUsage();
+  const heap = v8.getHeapStatistics();
+
+  return {
+    generatedAt: new Date().toISOString(),
+    pid: process.pid,
+    nodeVersion: process.version,
+    uptimeSecs: Math.round(process.uptime()),
+    memory: {
+      rss: memory.rss,
+      heapTotal: memory.heapTotal,
+      heapUsed: memory.heapUsed,
+      external: memory.external,
+      arrayBuffers: memory.arrayBuffers,
+    },
+    heap: {
+      totalHeapSize: heap.total_heap_size,
+      usedHeapSize: heap.used_heap_size,
+      heapSizeLimit: heap.heap_size_limit,
+      mallocedMemory: heap.malloced_memory,
+      peakMallocedMemory: heap.peak_malloced_memory,
+      nativeContexts: heap.number_of_native_contexts,
+      detachedContexts: heap.number_of_detached_contexts,
+      spaces: v8.getHeapSpaceStatistics().map((space) => ({
+        name: space.space_name,
+        size: space.space_size,
+        used: space.space_used_size,
+      })),
+    },
+    eventLoopDelayMs: {
+      mean: Number.isFinite(eventLoopDelay.mean) ? eventLoopDelay.mean / NS_PER_MS : 0,
+      p99: eventLoopDelay.percentile(99) / NS_PER_MS,
+      max: eventLoopDelay.max / NS_PER_MS,
+    },
+    activeResources: countActiveResources(),
+    tasks: readGauges('tasks'),
+    caches: readGauges('caches'),
+    failures: readGauges('failures'),
+  };
+}
@@ -0,0 +1,73 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { parseSchedule, renderIcalendar } from './schedule.js';
+
+const NOW = new Date('2026-03-01T12:00:00.000Z');
+
+test('validates entries and assigns ids to new ones', () => {
+  const schedule = parseSchedule({
+    maintenance: [{ title: ' Firmware update ', start: '2026-03-02T08:00:00Z', end: '2026-03-02T10:00:00Z' }],
+    reports: [{ id: 'weekly', title: 'Weekly summary', start: '2026-03-02T09:00:00+01:00', repeat: 'weekly' }],
+  });
+
+  assert.equal(schedule.maintenance[0].title, 'Firmware update');
+  assert.equal(schedule.maintenance[0].repeat, 'none');
+  assert.match(schedule.maintenance[0].id, /^[0-9a-f-]{36}$/);
+  assert.equal(schedule.reports[0].id, 'weekly');
+  assert.equal(schedule.reports[0].start, '2026-03-02T08:00:00.000Z');
+  assert.deepEqual(parseSchedule(undefined), { maintenance: [], reports: [] });
+});
+
+test('rejects malformed entries', () => {
+  assert.throws(() => parseSchedule({ maintenance: {} }), /must be arrays/);
+  assert.throws(() => parseSchedule({ reports: [{ start: '2026-03-02T08:00:00Z' }] }), /title is required/);
+  assert.throws(() => parseSchedule({ reports: [{ title: 'x', start: 'tomorrow' }] }), /ISO 8601/);
+  assert.throws(() => parseSchedule({ reports: [{ title: 'x', start: '2026-03-02T08:00:00Z', repeat: 'hourly' }] }), /repeat must be/);
+  assert.throws(
+    () => parseSchedule({ maintenance: [{ title: 'x', start: '2026-03-02T10:00:00Z', end: '2026-03-02T08:00:00Z' }] }),
+    /end must be after start/,
+  );
+});
+
+test('renders RFC 5545 events with recurrence and escaped text', () => {
+  const ics = renderIcalendar({
+    maintenance: [{
+      id: 'psu',
+      title: 'Swap PSUs; rack 2, row 1',
+      start: '2026-03-02T08:00:00.000Z',
+      end: '2026-03-02T10:00:00.000Z',
+      repeat: 'none',
+    }],
+    reports: [{ id: 'daily', title: 'Daily report', start: '2026-03-02T07:00:00.000Z', repeat: 'daily' }],
+  }, { calendarName: 'SV2 Mining', now: NOW });
+
+  const lines = ics.split('\r\n');
+  assert.equal(lines[0], 'BEGIN:VCALENDAR');
+  assert.ok(ics.endsWith('END:VCALENDAR\r\n'));
+  assert.ok(lines.includes('UID:maintenance-psu@sv2-ui'));
+  assert.ok(lines.includes('DTSTAMP:20260301T120000Z'));
+  assert.ok(lines.includes('DTSTART:20260302T080000Z'));
+  assert.ok(lines.includes('DTEND:20260302T100000Z'));
+  assert.ok(lines.includes('SUMMARY:Maintenance: Swap PSUs\\; rack 2\\, row 1'));
+  assert.ok(lines.includes('RRULE:FREQ=DAILY'));
+  assert.ok(lines.includes('DURATION:PT15M'));
+  assert.equal(lines.filter((line) => line === 'BEGIN:VEVENT').length, 2);
+});
+
+test('folds long lines at 75 octets', () => {
+  const ics = renderIcalendar({
+    maintenance: [{
+      id: 'long',
+      title: 'x'.repeat(200),
+      start: '2026-03-02T08:00:00.000Z',
+      end: '2026-03-02T10:00:00.000Z',
+      repeat: 'none',
+    }],
+    reports: [],
+  }, { calendarName: 'SV2 Mining', now: NOW });
+
+  for (const line of ics.split('\r\n')) {
+    assert.ok(Buffer.byteLength(line) <= 75, line);
+  }
+  assert.match(ics, /\r\n x/);
+});
@@ -0,0 +1,215 @@
+/**
+ * Planned maintenance windows and scheduled report runs, published as an
+ * iCalendar feed so team calendars reflect planned curtailment.
+ *
+ * The schedule lives in CONFIG_DIR/schedule.json and is edited through
+ * PUT /api/schedule. All times are ISO 8601 timestamps.
+ */
+
+import crypto from 'crypto';
+import fs from 'fs/promises';
+import path from 'path';
+
+export type ScheduleRepeat = 'none' | 'daily' | 'weekly' | 'monthly';
+
+export interface MaintenanceWindow {
+  id: string;
+  title: string;
+  description?: string;
+  start: string;
+  end: string;
+  repeat: ScheduleRepeat;
+}
+
+export interface ReportSchedule {
+  id: string;
+  title: string;
+  description?: string;
+  // First run; later runs follow `repeat`.
+  start: string;
+  repeat: ScheduleRepeat;
+}
+
+export interface Schedule {
+  maintenance: MaintenanceWindow[];
+  reports: ReportSchedule[];
+}
+
+const REPEATS: ScheduleRepeat[] = ['none', 'daily', 'weekly', 'monthly'];
+// Report runs show up as short blocks rather than zero-length events.
+const REPORT_DURATION = 'PT15M';
+
+function parseTimestamp(value: unknown, field: string): string {
+  if (typeof value !== 'string' || Number.isNaN(Date.parse(value))) {
+    throw new Error(`${field} must be an ISO 8601 timestamp`);
+  }
+  return new Date(value).toISOString();
+}
+
+function parseCommon(value: unknown, field: string) {
+  const entry = (value ?? {}) as Record<string, unknown>;
+  if (typeof entry.title !== 'string' || !entry.title.trim()) {
+    throw new Error(`${field}.title is required`);
+  }
+
+  const repeat = entry.repeat ?? 'none';
+  if (!REPEATS.includes(repeat as ScheduleRepeat)) {
+    throw new Error(`${field}.repeat must be one of ${REPEATS.join(', ')}`);
+  }
+
+  return {
+    entry,
+    id: typeof entry.id === 'string' && entry.id ? entry.id : crypto.randomUUID(),
+    title: entry.title.trim(),
+    ...(typeof entry.description === 'string' && entry.description ? { description: entry.description } : {}),
+    start: parseTimestamp(entry.start, `${field}.start`),
+    repeat: repeat as ScheduleRepeat,
+  };
+}
+
+/**
+ * Validate a schedule document, filling in ids for new entries.
+ */
+export function parseSchedule(body: unknown): Schedule {
+  const { maintenance = [], reports = [] } = (body ?? {}) as { maintenance?: unknown; reports?: unknown };
+  if (!Array.isArray(maintenance) || !Array.isArray(reports)) {
+    throw new Error('maintenance and reports must be arrays');
+  }
+
+  return {
+    maintenance: maintenance.map((value, index) => {
+      const { entry, ...common } = parseCommon(value, `maintenance[${index}]`);
+      const end = parseTimestamp(entry.end, `maintenance[${index}].end`);
+      if (Date.parse(end) <= Date.parse(common.start)) {
+        throw new Error(`maintenance[${index}].end must be after start`);
+      }
+      return { ...common, end };
+    }),
+    reports: reports.map((value, index) => {
+      const { entry: _entry, ...common } = parseCommon(value, `reports[${index}]`);
+      return common;
+    }),
+  };
+}
+
+export function createScheduleStore(filePath: string) {
+  async function get(): Promise<Schedule> {
+    try {
+      return parseSchedule(JSON.parse(await fs.readFile(filePath, 'utf-8')));
+    } catch {
+      return { maintenance: [], reports: [] };
+    }
+  }
+
+  async function save(schedule: Schedule): Promise<void> {
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    await fs.writeFile(filePath, JSON.stringify(schedule, null, 2));
+  }
+
+  return { get, save };
+}
+
+export type ScheduleStore = ReturnType<typeof createScheduleStore>;
+
+/**
+ * Escape TEXT values per RFC 5545 section 3.3.11.
+ */
+function escapeIcalText(value: string): string {
+  return value
+    .replace(/\\/g, '\\\\')
+    .replace(/;/g, '\\;')
+    .replace(/,/g, '\\,')
+    .replace(/\r?\n/g, '\\n');
+}
+
+/**
+ * Fold content lines longer than 75 octets (RFC 5545 section 3.1).
+ */
+function foldLine(line: string): string {
+  const bytes = Buffer.from(line, 'utf-8');
+  if (bytes.length <= 75) return line;
+
+  const parts: string[] = [];
+  let current = '';
+  let currentBytes = 0;
+  for (const char of line) {
+    const charBytes = Buffer.byteLength(char, 'utf-8');
+    // Continuation lines start with a space, which counts against the limit.
+    const limit = parts.length === 0 ? 75 : 74;
+    if (currentBytes + charBytes > limit) {
+      parts.push(current);
+      current = '';
+      currentBytes = 0;
+    }
+    current += char;
+    currentBytes += charBytes;
+  }
+  parts.push(current);
+
+  return parts.join('\r\n ');
+}
+
+function formatIcalDate(iso: string): string {
+  return iso.replace(/[-:]/g, '').replace(/\.\d{3}Z$/, 'Z');
+}
+
+const RRULES: Record<Exclude<ScheduleRepeat, 'none'>, string> = {
+  daily: 'FREQ=DAILY',
+  weekly: 'FREQ=WEEKLY',
+  monthly: 'FREQ=MONTHLY',
+};
+
+export function renderIcalendar(schedule: Schedule, { calendarName, now = new Date() }: { calendarName: string; now?: Date }): string {
+  const stamp = formatIcalDate(now.toISOString());
+
+  const event = (fields: {
+    uid: string;
+    summary: string;
+    description?: string;
+    start: string;
+    end?: string;
+    duration?: string;
+    repeat: ScheduleRepeat;
+    category: string;
+  }) => [
+    'BEGIN:VEVENT',
+    `UID:${fields.uid}`,
+    `DTSTAMP:${stamp}`,
+    `DTSTART:${formatIcalDate(fields.start)}`,
+    fields.end ? `DTEND:${formatIcalDate(fields.end)}` : `DURATION:${fields.duration}`,
+    ...(fields.repeat !== 'none' ? [`RRULE:${RRULES[fields.repeat]}`] : []),
+    `SUMMARY:${escapeIcalText(fields.summary)}`,
+    ...(fields.description ? [`DESCRIPTION:${escapeIcalText(fields.description)}`] : []),
+    `CATEGORIES:${fields.category}`,
+    'END:VEVENT',
+  ];
+
+  const lines = [
+    'BEGIN:VCALENDAR',
+    'VERSION:2.0',
+    'PRODID:-//stratum-mining//sv2-ui//EN',
+    'CALSCALE:GREGORIAN',
+    `X-WR-CALNAME:${escapeIcalText(calendarName)}`,
+    ...schedule.maintenance.flatMap((window) => event({
+      uid: `maintenance-${window.id}@sv2-ui`,
+      summary: `Maintenance: ${window.title}`,
+      description: window.description,
+      start: window.start,
+      end: window.end,
+      repeat: window.repeat,
+      category: 'MAINTENANCE',
+    })),
+    ...schedule.reports.flatMap((report) => event({
+      uid: `report-${report.id}@sv2-ui`,
+      summary: `Report: ${report.title}`,
+      description: report.description,
+      start: report.start,
+      duration: REPORT_DURATION,
+      repeat: report.repeat,
+      category: 'REPORT',
+    })),
+    'END:VCALENDAR',
+  ];
+
+  return `${lines.map(foldLine).join('\r\n')}\r\n`;
+}
@@ -0,0 +1,45 @@
+import assert from 'node:assert/strict';
+import crypto from 'node:crypto';
+import { test } from 'node:test';
+
+import {
+  createEllSwiftKeypair,
+  decodeEllSwift,
+  ellSwiftEcdh,
+  encodeEllSwift,
+  getXOnlyPublicKey,
+  signSchnorr,
+  verifySchnorr,
+} from './secp256k1.js';
+
+test('Schnorr signatures match BIP 340 test vector 0', () => {
+  const secretKey = Buffer.alloc(32);
+  secretKey[31] = 3;
+  const publicKey = getXOnlyPublicKey(secretKey);
+  assert.equal(publicKey.toString('hex'), 'f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9');
+
+  const signature = signSchnorr(Buffer.alloc(32), secretKey, Buffer.alloc(32));
+  assert.equal(
+    signature.toString('hex'),
+    'e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0',
+  );
+  assert.equal(verifySchnorr(Buffer.alloc(32), publicKey, signature), true);
+  assert.equal(verifySchnorr(Buffer.alloc(32, 1), publicKey, signature), false);
+});
+
+test('ElligatorSwift encodings decode to their x coordinate', () => {
+  // BIP 324 decoding vector: u = t = 0.
+  assert.equal(decodeEllSwift(Buffer.alloc(64)).toString('hex'), 'edd1fd3e327ce90cc7a3542614289aee9682003e9cf7dcc9cf2ca9743be5aa0c');
+
+  const x = getXOnlyPublicKey(crypto.randomBytes(32));
+  assert.deepEqual(decodeEllSwift(encodeEllSwift(x)), x);
+});
+
+test('both sides of an ElligatorSwift ECDH agree', () => {
+  const initiator = createEllSwiftKeypair();
+  const responder = createEllSwiftKeypair();
+  assert.deepEqual(
+    ellSwiftEcdh(initiator.publicKey, responder.publicKey, initiator.secretKey, true),
+    ellSwiftEcdh(initiator.publicKey, responder.publicKey, responder.secretKey, false),
+  );
+});
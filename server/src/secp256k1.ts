@@ -0,0 +1,226 @@
+/**
+ * The secp256k1 pieces the SV2 Noise handshake needs beyond what
+ * node:crypto offers: BIP 340 Schnorr signatures, which authority keys sign
+ * pool certificates with, and ElligatorSwift (BIP 324), the 64-byte public
+ * key encoding the handshake sends.
+ *
+ * Plain BigInt arithmetic: slow next to libsecp256k1, but the probe only
+ * does a handful of operations per run. The ECDH itself goes through
+ * node:crypto.
+ */
+
+import crypto from 'crypto';
+
+type Point = [bigint, bigint] | null;
+
+const P = 2n ** 256n - 2n ** 32n - 977n;
+const N = 0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141n;
+const G: Point = [
+  0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798n,
+  0x483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8n,
+];
+
+function mod(value: bigint, modulus = P): bigint {
+  const result = value % modulus;
+  return result < 0n ? result + modulus : result;
+}
+
+function pow(base: bigint, exponent: bigint): bigint {
+  let result = 1n;
+  let square = mod(base);
+  for (let e = exponent; e > 0n; e >>= 1n) {
+    if (e & 1n) result = (result * square) % P;
+    square = (square * square) % P;
+  }
+  return result;
+}
+
+function invert(value: bigint): bigint {
+  return pow(value, P - 2n);
+}
+
+// p = 3 mod 4, so a square root is a single exponentiation.
+function sqrt(value: bigint): bigint | null {
+  const root = pow(value, (P + 1n) / 4n);
+  return (root * root) % P === mod(value) ? root : null;
+}
+
+function isValidX(x: bigint): boolean {
+  return sqrt(x ** 3n + 7n) !== null;
+}
+
+const MINUS_3_SQRT = sqrt(P - 3n)!;
+
+function add(a: Point, b: Point): Point {
+  if (!a) return b;
+  if (!b) return a;
+  let slope: bigint;
+  if (a[0] === b[0]) {
+    if (mod(a[1] + b[1]) === 0n) return null;
+    slope = mod(3n * a[0] * a[0] * invert(2n * a[1]));
+  } else {
+    slope = mod((b[1] - a[1]) * invert(b[0] - a[0]));
+  }
+  const x = mod(slope * slope - a[0] - b[0]);
+  return [x, mod(slope * (a[0] - x) - a[1])];
+}
+
+function multiply(scalar: bigint, point: Point): Point {
+  let result: Point = null;
+  for (let bit = 255n; bit >= 0n; bit--) {
+    result = add(result, result);
+    if ((scalar >> bit) & 1n) result = add(result, point);
+  }
+  return result;
+}
+
+// The point with this x and an even y (BIP 340 lift_x).
+function liftX(x: bigint): Point {
+  if (x >= P) return null;
+  const y = sqrt(x ** 3n + 7n);
+  if (y === null) return null;
+  return [x, y & 1n ? P - y : y];
+}
+
+function toBigInt(bytes: Uint8Array): bigint {
+  return BigInt(`0x${Buffer.from(bytes).toString('hex') || '0'}`);
+}
+
+function toBytes(value: bigint): Buffer {
+  return Buffer.from(value.toString(16).padStart(64, '0'), 'hex');
+}
+
+export function taggedHash(tag: string, ...data: Uint8Array[]): Buffer {
+  const tagHash = crypto.createHash('sha256').update(tag).digest();
+  const hash = crypto.createHash('sha256').update(tagHash).update(tagHash);
+  for (const chunk of data) hash.update(chunk);
+  return hash.digest();
+}
+
+/**
+ * The x-only public key of a 32-byte secret key.
+ */
+export function getXOnlyPublicKey(secretKey: Uint8Array): Buffer {
+  const ecdh = crypto.createECDH('secp256k1');
+  ecdh.setPrivateKey(Buffer.from(secretKey));
+  return ecdh.getPublicKey(null, 'compressed').subarray(1);
+}
+
+/**
+ * Verify a BIP 340 signature over a 32-byte message.
+ */
+export function verifySchnorr(message: Uint8Array, publicKey: Uint8Array, signature: Uint8Array): boolean {
+  if (publicKey.length !== 32 || signature.length !== 64) return false;
+  const point = liftX(toBigInt(publicKey));
+  const r = toBigInt(signature.subarray(0, 32));
+  const s = toBigInt(signature.subarray(32));
+  if (!point || r >= P || s >= N) return false;
+
+  const e = mod(toBigInt(taggedHash('BIP0340/challenge', signature.subarray(0, 32), publicKey, message)), N);
+  const R = add(multiply(s, G), multiply(N - e, point));
+  return R !== null && (R[1] & 1n) === 0n && R[0] === r;
+}
+
+/**
+ * Sign a 32-byte message (BIP 340).
+ */
+export function signSchnorr(message: Uint8Array, secretKey: Uint8Array, auxRand: Uint8Array = crypto.randomBytes(32)): Buffer {
+  const secret = toBigInt(secretKey);
+  if (secret <= 0n || secret >= N) throw new Error('Secret key out of range');
+  const publicPoint = multiply(secret, G)!;
+  const d = publicPoint[1] & 1n ? N - secret : secret;
+  const publicKey = toBytes(publicPoint[0]);
+
+  const t = toBytes(d ^ toBigInt(taggedHash('BIP0340/aux', auxRand)));
+  const k0 = mod(toBigInt(taggedHash('BIP0340/nonce', t, publicKey, message)), N);
+  if (k0 === 0n) throw new Error('Nonce is zero');
+  const R = multiply(k0, G)!;
+  const k = R[1] & 1n ? N - k0 : k0;
+  const e = mod(toBigInt(taggedHash('BIP0340/challenge', toBytes(R[0]), publicKey, message)), N);
+  return Buffer.concat([toBytes(R[0]), toBytes(mod(k + e * d, N))]);
+}
+
+/**
+ * The x coordinate a 64-byte ElligatorSwift encoding stands for (BIP 324 XSwiftEC).
+ */
+export function decodeEllSwift(encoded: Uint8Array): Buffer {
+  if (encoded.length !== 64) throw new Error('ElligatorSwift encodings are 64 bytes');
+  let u = mod(toBigInt(encoded.subarray(0, 32)));
+  let t = mod(toBigInt(encoded.subarray(32)));
+  if (u === 0n) u = 1n;
+  if (t === 0n) t = 1n;
+  if (mod(u ** 3n + t * t + 7n) === 0n) t = mod(2n * t);
+
+  const X = mod((u ** 3n + 7n - t * t) * invert(2n * t));
+  const Y = mod((X + t) * invert(MINUS_3_SQRT * u));
+  const candidates = [
+    mod(u + 4n * Y * Y),
+    mod((-X * invert(Y) - u) * invert(2n)),
+    mod((X * invert(Y) - u) * invert(2n)),
+  ];
+  return toBytes(candidates.find(isValidX)!);
+}
+
+// A t with XSwiftEC(u, t) = x, if this case of the inverse has one (BIP 324 XSwiftECInv).
+function invertEllSwift(x: bigint, u: bigint, branch: number): bigint | null {
+  let s: bigint;
+  let v: bigint;
+  if ((branch & 2) === 0) {
+    if (isValidX(mod(-x - u))) return null;
+    v = x;
+    s = mod(-(u ** 3n + 7n) * invert(u * u + u * v + v * v));
+  } else {
+    s = mod(x - u);
+    if (s === 0n) return null;
+    const r = sqrt(mod(-s * (4n * (u ** 3n + 7n) + 3n * s * u * u)));
+    if (r === null || ((branch & 1) && r === 0n)) return null;
+    v = mod(((branch & 1 ? -r : r) * invert(s) - u) * invert(2n));
+  }
+  const w = sqrt(s);
+  if (w === null) return null;
+  return mod((((branch & 5) === 4) ? -w : w) * (u * (MINUS_3_SQRT - 1n) * invert(2n) - v));
+}
+
+/**
+ * A random ElligatorSwift encoding of an x coordinate.
+ */
+export function encodeEllSwift(xOnly: Uint8Array): Buffer {
+  const x = toBigInt(xOnly);
+  for (;;) {
+    const u = mod(toBigInt(crypto.randomBytes(32)));
+    if (u === 0n) continue;
+    const t = invertEllSwift(x, u, crypto.randomInt(8));
+    if (t !== null) return Buffer.concat([toBytes(u), toBytes(t)]);
+  }
+}
+
+/**
+ * A fresh keypair with its public key ElligatorSwift-encoded.
+ */
+export function createEllSwiftKeypair(): { secretKey: Buffer; publicKey: Buffer } {
+  const ecdh = crypto.createECDH('secp256k1');
+  ecdh.generateKeys();
+  const secret = ecdh.getPrivateKey();
+  return {
+    secretKey: Buffer.concat([Buffer.alloc(32 - secret.length), secret]),
+    publicKey: encodeEllSwift(ecdh.getPublicKey(null, 'compressed').subarray(1)),
+  };
+}
+
+/**
+ * The BIP 324 x-only ECDH secret between an initiator's and a responder's
+ * ElligatorSwift keys, computed with either side's secret key.
+ */
+export function ellSwiftEcdh(
+  initiatorKey: Uint8Array,
+  responderKey: Uint8Array,
+  secretKey: Uint8Array,
+  isInitiator: boolean,
+): Buffer {
+  const theirs = decodeEllSwift(isInitiator ? responderKey : initiatorKey);
+  const ecdh = crypto.createECDH('secp256k1');
+  ecdh.setPrivateKey(Buffer.from(secretKey));
+  // Either y gives the same shared x.
+  const shared = ecdh.computeSecret(Buffer.concat([Buffer.from([0x02]), theirs]));
+  return taggedHash('bip324_ellswift_xonly_ecdh', initiatorKey, responderKey, shared);
+}
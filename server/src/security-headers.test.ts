@@ -0,0 +1,55 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import {
+  addCspNonce,
+  buildContentSecurityPolicy,
+  createCspNonce,
+  parseCspDirectives,
+  parseSecurityHeadersConfig,
+} from './security-headers.js';
+
+test('defaults cover the bundled UI and omit HSTS', () => {
+  const config = parseSecurityHeadersConfig({});
+
+  assert.match(config.contentSecurityPolicy ?? '', /default-src 'self'/);
+  assert.match(config.contentSecurityPolicy ?? '', /script-src 'self' 'wasm-unsafe-eval'/);
+  assert.match(config.contentSecurityPolicy ?? '', /font-src 'self' data: https:\/\/fonts\.gstatic\.com/);
+  assert.equal(config.strictTransportSecurity, null);
+  assert.equal(config.referrerPolicy, 'same-origin');
+});
+
+test('extra directives extend the defaults', () => {
+  const extra = parseCspDirectives("img-src https://cdn.example.com;  frame-src https://grafana.lan ; frame-ancestors 'none'");
+  assert.deepEqual(extra, {
+    'img-src': ['https://cdn.example.com'],
+    'frame-src': ['https://grafana.lan'],
+    'frame-ancestors': ["'none'"],
+  });
+
+  const csp = buildContentSecurityPolicy(extra);
+  assert.match(csp, /img-src 'self' data: https:\/\/cdn\.example\.com/);
+  assert.match(csp, /frame-src https:\/\/grafana\.lan/);
+  assert.match(csp, /frame-ancestors 'none'/);
+  assert.match(csp, /object-src 'none'/);
+});
+
+test('CSP can be disabled and HSTS enabled', () => {
+  const config = parseSecurityHeadersConfig({ CSP_DIRECTIVES: 'off', HSTS_MAX_AGE: '31536000', REFERRER_POLICY: 'no-referrer' });
+
+  assert.equal(config.contentSecurityPolicy, null);
+  assert.equal(config.strictTransportSecurity, 'max-age=31536000; includeSubDomains');
+  assert.equal(config.referrerPolicy, 'no-referrer');
+  assert.throws(() => parseSecurityHeadersConfig({ HSTS_MAX_AGE: '1y' }), /Invalid HSTS_MAX_AGE/);
+  assert.throws(() => parseCspDirectives('IMG_SRC x'), /Invalid CSP_DIRECTIVES directive/);
+});
+
+test('nonces are added to script-src, or to a copy of default-src', () => {
+  const nonce = createCspNonce();
+  assert.match(nonce, /^[A-Za-z0-9+/]{22}==$/);
+  assert.notEqual(createCspNonce(), nonce);
+
+  const csp = addCspNonce(buildContentSecurityPolicy({}), 'abc');
+  assert.match(csp, /script-src 'self' 'wasm-unsafe-eval' 'nonce-abc'; style-src/);
+  assert.equal(addCspNonce("default-src 'self'; img-src data:", 'abc'), "default-src 'self'; img-src data:; script-src 'self' 'nonce-abc'");
+  assert.equal(addCspNonce("script-src 'none'", 'abc'), "script-src 'none'");
+});
@@ -0,0 +1,130 @@
+/**
+ * Security headers applied to every response.
+ *
+ * The default Content-Security-Policy allows exactly what the bundled UI
+ * needs: same-origin scripts and API calls, WebAssembly (address
+ * validation), inline styles, and Google Fonts. Deployments that embed extra
+ * resources can add sources per directive with CSP_DIRECTIVES.
+ *
+ * index.html is served with a per-response script nonce, so inline scripts
+ * the backend injects run without `'unsafe-inline'`.
+ */
+
+import { randomBytes } from 'node:crypto';
+import type { RequestHandler } from 'express';
+
+export type CspDirectives = Record<string, string[]>;
+
+export const DEFAULT_CSP_DIRECTIVES: CspDirectives = {
+  'default-src': ["'self'"],
+  'script-src': ["'self'", "'wasm-unsafe-eval'"],
+  'style-src': ["'self'", "'unsafe-inline'", 'https://fonts.googleapis.com'],
+  'font-src': ["'self'", 'data:', 'https://fonts.gstatic.com'],
+  'img-src': ["'self'", 'data:'],
+  'connect-src': ["'self'"],
+  'object-src': ["'none'"],
+  'base-uri': ["'self'"],
+  'form-action': ["'self'"],
+  'frame-ancestors': ["'self'"],
+};
+
+export interface SecurityHeadersConfig {
+  contentSecurityPolicy: string | null;
+  strictTransportSecurity: string | null;
+  referrerPolicy: string;
+}
+
+/**
+ * Parse `directive source source; directive source` into directives.
+ */
+export function parseCspDirectives(value: string | undefined): CspDirectives {
+  const directives: CspDirectives = {};
+  for (const part of (value ?? '').split(';')) {
+    const [name, ...sources] = part.trim().split(/\s+/).filter(Boolean);
+    if (!name) continue;
+    if (!/^[a-z-]+$/.test(name)) {
+      throw new Error(`Invalid CSP_DIRECTIVES directive "${name}"`);
+    }
+    directives[name] = [...(directives[name] ?? []), ...sources];
+  }
+  return directives;
+}
+
+/**
+ * Extra sources are added to the defaults. A directive listed with `'none'`
+ * only (e.g. "frame-ancestors 'none'") replaces the default instead.
+ */
+export function buildContentSecurityPolicy(extra: CspDirectives, defaults = DEFAULT_CSP_DIRECTIVES): string {
+  const merged: CspDirectives = { ...defaults };
+  for (const [name, sources] of Object.entries(extra)) {
+    merged[name] = sources.length === 1 && sources[0] === "'none'"
+      ? sources
+      : [...new Set([...(merged[name] ?? []).filter((source) => source !== "'none'"), ...sources])];
+  }
+
+  return Object.entries(merged)
+    .map(([name, sources]) => [name, ...sources].join(' '))
+    .join('; ');
+}
+
+export function createCspNonce(): string {
+  return randomBytes(16).toString('base64');
+}
+
+/**
+ * Allow scripts carrying `nonce`. Without a script-src directive the nonce is
+ * added to a copy of default-src, which script-src would otherwise fall back to.
+ */
+export function addCspNonce(policy: string, nonce: string): string {
+  const source = `'nonce-${nonce}'`;
+  const directives = policy.split(';').map((part) => part.trim()).filter(Boolean);
+  const scriptIndex = directives.findIndex((directive) => /^script-src(\s|$)/.test(directive));
+  if (scriptIndex !== -1) {
+    // A nonce next to 'none' would be ignored anyway; keep the policy as is.
+    if (/\s'none'(\s|$)/.test(directives[scriptIndex])) return policy;
+    directives[scriptIndex] = `${directives[scriptIndex]} ${source}`;
+  } else {
+    const fallback = directives.find((directive) => /^default-src(\s|$)/.test(directive));
+    const sources = fallback?.split(/\s+/).slice(1).filter((value) => value !== "'none'") ?? [];
+    directives.push(['script-src', ...sources, source].join(' '));
+  }
+  return directives.join('; ');
+}
+
+export function parseSecurityHeadersConfig(env: NodeJS.ProcessEnv = process.env): SecurityHeadersConfig {
+  const cspDisabled = env.CSP_DIRECTIVES?.trim().toLowerCase() === 'off';
+
+  let strictTransportSecurity: string | null = null;
+  const hstsMaxAge = env.HSTS_MAX_AGE?.trim();
+  if (hstsMaxAge) {
+    if (!/^\d+$/.test(hstsMaxAge)) {
+      throw new Error(`Invalid HSTS_MAX_AGE "${hstsMaxAge}"`);
+    }
+    strictTransportSecurity = `max-age=${hstsMaxAge}; includeSubDomains`;
+  }
+
+  return {
+    contentSecurityPolicy: cspDisabled ? null : buildContentSecurityPolicy(parseCspDirectives(env.CSP_DIRECTIVES)),
+    strictTransportSecurity,
+    // Keeps share-link tokens and internal hostnames out of other sites' logs.
+    referrerPolicy: env.REFERRER_POLICY?.trim() || 'same-origin',
+  };
+}
+
+export function createSecurityHeadersMiddleware(config: SecurityHeadersConfig): RequestHandler {
+  const headers: Record<string, string> = {
+    'X-Content-Type-Options': 'nosniff',
+    'Referrer-Policy': config.referrerPolicy,
+  };
+  if (config.contentSecurityPolicy) {
+    headers['Content-Security-Policy'] = config.contentSecurityPolicy;
+  }
+  if (config.strictTransportSecurity) {
+    headers['Strict-Transport-Security'] = config.strictTransportSecurity;
+  }
+
+  return (_req, res, next) => {
+    res.set(headers);
+    next();
+  };
+}
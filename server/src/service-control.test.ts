@@ -0,0 +1,17 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { isServiceDeployed, parseServiceAction } from './service-control.js';
+
+test('parses service actions', () => {
+  assert.deepEqual(parseServiceAction('translator', 'restart'), { service: 'translator', action: 'restart' });
+  assert.deepEqual(parseServiceAction('jdc', 'stop'), { service: 'jdc', action: 'stop' });
+  assert.throws(() => parseServiceAction('jds', 'start'), /does not run a JDS container/);
+  assert.throws(() => parseServiceAction('bitcoind', 'start'), /service must be/);
+  assert.throws(() => parseServiceAction('translator', 'kill'), /action must be one of start, stop, restart/);
+});
+
+test('the JDC is only deployed in JD mode', () => {
+  assert.equal(isServiceDeployed('translator', 'no-jd'), true);
+  assert.equal(isServiceDeployed('jdc', 'no-jd'), false);
+  assert.equal(isServiceDeployed('jdc', 'jd'), true);
+});
@@ -0,0 +1,33 @@
+/**
+ * Start, stop and restart individual mining containers from the dashboard.
+ *
+ * Only the containers sv2-ui runs can be controlled: the Translator, and the
+ * JDC in JD mode. Every action is recorded in the audit log.
+ */
+
+import type { SetupMode } from '@sv2-ui/shared';
+import type { MonitoringService } from './monitoring.js';
+
+export type ServiceAction = 'start' | 'stop' | 'restart';
+
+export const SERVICE_ACTIONS: ServiceAction[] = ['start', 'stop', 'restart'];
+
+export function parseServiceAction(service: string, action: string): { service: MonitoringService; action: ServiceAction } {
+  if (service === 'jds') {
+    throw new Error('sv2-ui does not run a JDS container');
+  }
+  if (service !== 'translator' && service !== 'jdc') {
+    throw new Error('service must be translator or jdc');
+  }
+  if (!SERVICE_ACTIONS.includes(action as ServiceAction)) {
+    throw new Error(`action must be one of ${SERVICE_ACTIONS.join(', ')}`);
+  }
+  return { service, action: action as ServiceAction };
+}
+
+/**
+ * Whether `service` runs in the deployed stack; the JDC only does in JD mode.
+ */
+export function isServiceDeployed(service: MonitoringService, mode: SetupMode | null): boolean {
+  return service === 'translator' || mode === 'jd';
+}
@@ -0,0 +1,63 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { parseServiceArgs, quoteWindowsArg, renderTaskXml, superviseServer } from './service.js';
+
+test('parseServiceArgs takes the action and the task name, and passes the rest to the server', () => {
+  assert.deepEqual(parseServiceArgs(['install', '--name', 'sv2-ui-testnet', '--headless', '--data-dir', 'data', '--env-file', 'sv2-ui.env']), {
+    action: 'install',
+    name: 'sv2-ui-testnet',
+    envFile: path.resolve('sv2-ui.env'),
+    serverArgs: ['--headless'],
+  });
+  assert.deepEqual(parseServiceArgs(['run']), { action: 'run', name: 'sv2-ui', envFile: null, serverArgs: [] });
+  assert.throws(() => parseServiceArgs(['start']), /one of install, uninstall, run, got "start"/);
+  assert.throws(() => parseServiceArgs(['install', '--name', 'sv2 ui']), /--name may only contain/);
+  assert.throws(() => parseServiceArgs(['install', '--daemon']), /--daemon is managed by the service/);
+});
+
+test('the boot task runs the supervisor as SYSTEM with its arguments quoted and escaped', () => {
+  assert.equal(quoteWindowsArg('--headless'), '--headless');
+  assert.equal(quoteWindowsArg('C:\\Program Files\\sv2-ui\\'), '"C:\\Program Files\\sv2-ui\\\\"');
+  assert.equal(quoteWindowsArg('say "hi"'), '"say \\"hi\\""');
+  assert.equal(quoteWindowsArg(''), '""');
+
+  const xml = renderTaskXml({
+    name: 'sv2-ui',
+    command: 'C:\\Program Files\\nodejs\\node.exe',
+    args: ['C:\\sv2-ui\\dist\\index.js', 'service', 'run', '--data-dir', 'C:\\Users\\R&D\\AppData\\Roaming\\sv2-ui'],
+    workingDirectory: 'C:\\sv2-ui\\dist',
+  });
+  assert.match(xml, /<BootTrigger>/);
+  assert.match(xml, /<UserId>S-1-5-18<\/UserId>/);
+  assert.match(xml, /<RestartOnFailure>\s*<Interval>PT1M<\/Interval>/);
+  assert.match(xml, /<Command>C:\\Program Files\\nodejs\\node.exe<\/Command>/);
+  assert.match(xml, /<Arguments>C:\\sv2-ui\\dist\\index.js service run --data-dir C:\\Users\\R&amp;D\\AppData\\Roaming\\sv2-ui<\/Arguments>/);
+});
+
+test('superviseServer restarts the server when it exits, until stopped', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-service-'));
+  t.after(() => fs.rm(dir, { recursive: true, force: true }));
+  const script = path.join(dir, 'server.mjs');
+  const runs = path.join(dir, 'runs');
+  // It exits at once twice, then stays up.
+  await fs.writeFile(script, [
+    "import fs from 'node:fs';",
+    `fs.appendFileSync(${JSON.stringify(runs)}, 'x');`,
+    `if (fs.readFileSync(${JSON.stringify(runs)}, 'utf-8').length < 3) process.exit(2);`,
+    "console.log('listening');",
+    'setInterval(() => undefined, 1000);',
+  ].join('\n'));
+  const logFile = path.join(dir, 'logs', 'sv2-ui.log');
+
+  const supervisor = superviseServer({ command: process.execPath, args: [script], logFile, minDelayMs: 10 });
+  while (!(await fs.readFile(logFile, 'utf-8')).includes('listening')) await new Promise((resolve) => setTimeout(resolve, 20));
+  assert.equal(supervisor.restarts(), 2);
+  await supervisor.stop();
+
+  const log = await fs.readFile(logFile, 'utf-8');
+  assert.equal(log.match(/sv2-ui exited \(code 2\); restarting/g)?.length, 2);
+  assert.equal(await fs.readFile(runs, 'utf-8'), 'xxx');
+});
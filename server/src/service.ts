@@ -0,0 +1,303 @@
+/**
+ * `service install|uninstall|run`: start sv2-ui at boot on Windows, and keep
+ * it running.
+ *
+ * A Node.js process can't answer the Windows service control manager without
+ * a native wrapper. `install` registers a Task Scheduler task instead. It
+ * starts at boot as SYSTEM, before anyone logs in, and Task Scheduler starts
+ * it again if it fails. The task runs `service run`, a supervisor that starts
+ * the server as a child and restarts it when it exits, backing off from 1s
+ * to a minute while it keeps failing.
+ *
+ * The task passes `--data-dir` so SYSTEM uses the config directory of the
+ * user who installed it, and appends the server's output to sv2-ui.log
+ * there. Settings go in `--env-file`, which Node reads on startup. The server
+ * writes its PID to sv2-ui.pid, so `uninstall` can stop it too.
+ *
+ * `service run` works on every platform, e.g. under cron's @reboot.
+ */
+
+import { execFile, spawn } from 'child_process';
+import type { ChildProcess } from 'child_process';
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+import { escapeMarkup } from './escape.js';
+
+export type ServiceAction = 'install' | 'uninstall' | 'run';
+
+export interface ServiceArgs {
+  action: ServiceAction;
+  name: string;
+  envFile: string | null;
+  // Passed on to the server.
+  serverArgs: string[];
+}
+
+const SERVICE_ACTIONS: ServiceAction[] = ['install', 'uninstall', 'run'];
+const DEFAULT_TASK_NAME = 'sv2-ui';
+const MIN_RESTART_DELAY_MS = 1000;
+const MAX_RESTART_DELAY_MS = 60_000;
+// A server that ran this long before exiting restarts without backing off.
+const STABLE_RUN_MS = 5 * 60_000;
+
+export function parseServiceArgs(argv: string[]): ServiceArgs {
+  const [action, ...rest] = argv;
+  if (!SERVICE_ACTIONS.includes(action as ServiceAction)) {
+    throw new Error(`service takes one of ${SERVICE_ACTIONS.join(', ')}, got "${action ?? ''}"`);
+  }
+
+  let name = DEFAULT_TASK_NAME;
+  let envFile: string | null = null;
+  const serverArgs: string[] = [];
+  for (let i = 0; i < rest.length; i++) {
+    const arg = rest[i];
+    if (arg === '--name' || arg === '--env-file') {
+      const value = rest[++i]?.trim();
+      if (!value || value.startsWith('--')) throw new Error(`${arg} requires a value`);
+      if (arg === '--name') {
+        if (!/^[\w.-]+$/.test(value)) throw new Error(`--name may only contain letters, digits, ".", "_" and "-", got "${value}"`);
+        name = value;
+      } else {
+        envFile = path.resolve(value);
+      }
+    } else if (arg === '--daemon' || arg === '--pid-file' || arg === '--log-file') {
+      throw new Error(`${arg} is managed by the service; leave it out`);
+    } else if (arg === '--data-dir') {
+      // Already in CONFIG_DIR; the service passes it on resolved.
+      i++;
+    } else if (!arg.startsWith('--data-dir=')) {
+      serverArgs.push(arg);
+    }
+  }
+  return { action: action as ServiceAction, name, envFile, serverArgs };
+}
+
+/**
+ * Quote `arg` for a Windows command line, the way CommandLineToArgvW splits it.
+ */
+export function quoteWindowsArg(arg: string): string {
+  if (arg !== '' && !/[\s"]/.test(arg)) return arg;
+  return `"${arg.replace(/(\\*)"/g, '$1$1\\"').replace(/(\\+)$/, '$1$1')}"`;
+}
+
+export interface TaskDefinition {
+  name: string;
+  command: string;
+  args: string[];
+  workingDirectory: string;
+}
+
+/**
+ * The Task Scheduler definition of the boot task.
+ */
+export function renderTaskXml({ name, command, args, workingDirectory }: TaskDefinition): string {
+  return `<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Description>${escapeMarkup(`${name}: the sv2-ui dashboard, started at boot and restarted on failure`)}</Description>
+  </RegistrationInfo>
+  <Triggers>
+    <BootTrigger>
+      <Enabled>true</Enabled>
+    </BootTrigger>
+  </Triggers>
+  <Principals>
+    <Principal id="Author">
+      <UserId>S-1-5-18</UserId>
+      <RunLevel>HighestAvailable</RunLevel>
+    </Principal>
+  </Principals>
+  <Settings>
+    <MultipleInstancesPolicy>IgnoreNew</MultipleInstancesPolicy>
+    <DisallowStartIfOnBatteries>false</DisallowStartIfOnBatteries>
+    <StopIfGoingOnBatteries>false</StopIfGoingOnBatteries>
+    <StartWhenAvailable>true</StartWhenAvailable>
+    <ExecutionTimeLimit>PT0S</ExecutionTimeLimit>
+    <RestartOnFailure>
+      <Interval>PT1M</Interval>
+      <Count>999</Count>
+    </RestartOnFailure>
+  </Settings>
+  <Actions Context="Author">
+    <Exec>
+      <Command>${escapeMarkup(command)}</Command>
+      <Arguments>${escapeMarkup(args.map(quoteWindowsArg).join(' '))}</Arguments>
+      <WorkingDirectory>${escapeMarkup(workingDirectory)}</WorkingDirectory>
+    </Exec>
+  </Actions>
+</Task>
+`;
+}
+
+function schtasks(args: string[]): Promise<void> {
+  return new Promise((resolve, reject) => {
+    execFile('schtasks.exe', args, { windowsHide: true }, (error, _stdout, stderr) => {
+      if (error) reject(new Error(`schtasks ${args[0]} failed: ${stderr.trim() || error.message}`));
+      else resolve();
+    });
+  });
+}
+
+function assertWindows(action: ServiceAction): void {
+  if (process.platform !== 'win32') {
+    throw new Error(`service ${action} registers a Windows boot task; elsewhere use a systemd unit, or --daemon`);
+  }
+}
+
+export interface ServicePaths {
+  configDir: string;
+  // The server script and the Node options it runs with.
+  script: string;
+  execArgv: string[];
+}
+
+function getPidFile(configDir: string): string {
+  return path.join(configDir, 'sv2-ui.pid');
+}
+
+export async function installService({ name, envFile, serverArgs }: ServiceArgs, { configDir, script, execArgv }: ServicePaths): Promise<string> {
+  assertWindows('install');
+  if (envFile && !fs.existsSync(envFile)) throw new Error(`--env-file ${envFile} does not exist`);
+  const xml = renderTaskXml({
+    name,
+    command: process.execPath,
+    args: [
+      ...(envFile ? [`--env-file=${envFile}`] : []),
+      ...execArgv,
+      script,
+      'service',
+      'run',
+      '--name',
+      name,
+      ...serverArgs,
+      '--data-dir',
+      configDir,
+    ],
+    workingDirectory: path.dirname(script),
+  });
+
+  // schtasks reads the definition in the encoding it declares.
+  const file = path.join(os.tmpdir(), `${name}-task-${process.pid}.xml`);
+  fs.writeFileSync(file, `\ufeff${xml}`, 'utf16le');
+  try {
+    // Reinstalling replaces the running task too.
+    await stopService(name, configDir);
+    await schtasks(['/Create', '/TN', name, '/XML', file, '/F']);
+  } finally {
+    fs.rmSync(file, { force: true });
+  }
+  await schtasks(['/Run', '/TN', name]);
+  return name;
+}
+
+// Ending the task stops the supervisor, but not the server it started.
+async function stopService(name: string, configDir: string): Promise<void> {
+  await schtasks(['/End', '/TN', name]).catch(() => undefined);
+  let pid = 0;
+  try {
+    pid = Number(fs.readFileSync(getPidFile(configDir), 'utf-8').trim());
+  } catch {
+    return;
+  }
+  if (Number.isInteger(pid) && pid > 0) {
+    try {
+      process.kill(pid);
+    } catch {
+      // Already gone.
+    }
+  }
+}
+
+export async function uninstallService({ name }: ServiceArgs, { configDir }: Pick<ServicePaths, 'configDir'>): Promise<void> {
+  assertWindows('uninstall');
+  await stopService(name, configDir);
+  await schtasks(['/Delete', '/TN', name, '/F']);
+}
+
+export interface SupervisorOptions {
+  command: string;
+  args: string[];
+  logFile: string;
+  minDelayMs?: number;
+  maxDelayMs?: number;
+  now?: () => number;
+}
+
+/**
+ * Run `command` and start it again whenever it exits, until `stop`.
+ */
+export function superviseServer({
+  command,
+  args,
+  logFile,
+  minDelayMs = MIN_RESTART_DELAY_MS,
+  maxDelayMs = MAX_RESTART_DELAY_MS,
+  now = Date.now,
+}: SupervisorOptions) {
+  fs.mkdirSync(path.dirname(logFile), { recursive: true });
+  const log = fs.openSync(logFile, 'a');
+  const note = (message: string) => fs.writeSync(log, `[${new Date(now()).toISOString()}] ${message}\n`);
+
+  let child: ChildProcess | null = null;
+  let timer: NodeJS.Timeout | null = null;
+  let stopping = false;
+  let delayMs = minDelayMs;
+  let restarts = 0;
+  let exited: () => void = () => undefined;
+
+  function start(): void {
+    const startedAt = now();
+    child = spawn(command, args, { stdio: ['ignore', log, log], windowsHide: true });
+    child.once('error', (error) => note(`Cannot start sv2-ui: ${error.message}`));
+    child.once('exit', (code, signal) => {
+      child = null;
+      if (stopping) {
+        exited();
+        return;
+      }
+      delayMs = now() - startedAt >= STABLE_RUN_MS ? minDelayMs : delayMs;
+      note(`sv2-ui exited (${signal ?? `code ${code}`}); restarting in ${Math.round(delayMs / 1000)}s`);
+      timer = setTimeout(() => {
+        restarts += 1;
+        start();
+      }, delayMs);
+      delayMs = Math.min(maxDelayMs, delayMs * 2);
+    });
+  }
+
+  function stop(): Promise<void> {
+    stopping = true;
+    if (timer) clearTimeout(timer);
+    return new Promise((resolve) => {
+      const done = () => {
+        fs.closeSync(log);
+        resolve();
+      };
+      if (!child) return done();
+      exited = done;
+      child.kill('SIGTERM');
+    });
+  }
+
+  start();
+  return { stop, restarts: () => restarts };
+}
+
+export type Supervisor = ReturnType<typeof superviseServer>;
+
+/**
+ * `service run`: supervise the server until SIGTERM or SIGINT.
+ */
+export function runService({ serverArgs }: ServiceArgs, { configDir, script, execArgv }: ServicePaths): Promise<void> {
+  const supervisor = superviseServer({
+    command: process.execPath,
+    args: [...execArgv, script, ...serverArgs, '--data-dir', configDir, '--pid-file', getPidFile(configDir)],
+    logFile: path.join(configDir, 'sv2-ui.log'),
+  });
+  return new Promise((resolve) => {
+    const stop = () => void supervisor.stop().then(resolve);
+    process.once('SIGTERM', stop);
+    process.once('SIGINT', stop);
+  });
+}
@@ -0,0 +1,77 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { createSessionMiddleware, createSessionStore, parseCookies, parseSessionConfig, SESSION_COOKIE } from './sessions.js';
+import type { User } from './users.js';
+
+test('parses session lifetimes and cookies', () => {
+  assert.deepEqual(parseSessionConfig({}), { lifetimeMs: 604_800_000, idleMs: 86_400_000 });
+  assert.deepEqual(parseSessionConfig({ SESSION_LIFETIME_SECS: '3600', SESSION_IDLE_SECS: '7200' }), {
+    lifetimeMs: 3_600_000,
+    idleMs: 3_600_000,
+  });
+  assert.throws(() => parseSessionConfig({ SESSION_IDLE_SECS: '0' }), /Invalid SESSION_IDLE_SECS/);
+
+  assert.deepEqual(parseCookies('a=1; sv2_session="x.y"; a=2; bad; c=%E0'), { a: '1', sv2_session: 'x.y' });
+  assert.deepEqual(parseCookies(undefined), {});
+});
+
+test('ends sessions after their lifetime, when idle, or when revoked', () => {
+  let now = 0;
+  const sessions = createSessionStore({ lifetimeMs: 10_000, idleMs: 4_000 }, { now: () => now });
+
+  const { token, expiresAt } = sessions.create('alice');
+  assert.equal(expiresAt, new Date(10_000).toISOString());
+  now = 3_000;
+  assert.equal(sessions.touch(token), 'alice');
+  now = 6_000;
+  assert.equal(sessions.touch(token), 'alice');
+  now = 9_000;
+  assert.equal(sessions.touch(token), 'alice');
+  // Still active, but past its lifetime.
+  now = 10_000;
+  assert.equal(sessions.touch(token), null);
+
+  const idle = sessions.create('alice').token;
+  now = 14_000;
+  assert.equal(sessions.touch(idle), null);
+
+  const forged = sessions.create('alice').token.replace(/\..*/, '.forged');
+  assert.equal(sessions.touch(forged), null);
+  assert.equal(sessions.destroy(forged), false);
+
+  const first = sessions.create('alice').token;
+  const second = sessions.create('bob').token;
+  sessions.revokeUser('alice');
+  assert.equal(sessions.touch(first), null);
+  assert.equal(sessions.touch(second), 'bob');
+  assert.equal(sessions.destroy(second), true);
+  assert.equal(sessions.touch(second), null);
+});
+
+test('signs in requests with a session cookie from the same origin', () => {
+  const sessions = createSessionStore({ lifetimeMs: 60_000, idleMs: 60_000 });
+  const users: Record<string, User> = {
+    u1: { id: 'u1', username: 'alice', role: 'operator', createdAt: '', updatedAt: '' },
+  };
+  const middleware = createSessionMiddleware(sessions, { get: (id) => users[id] ?? null });
+  const token = sessions.create('u1').token;
+
+  const authenticate = (method: string, headers: Record<string, string>) => {
+    const locals: Record<string, unknown> = {};
+    middleware({ method, headers: { host: 'sv2.local:3001', ...headers } } as never, { locals } as never, () => {});
+    return locals;
+  };
+  const cookie = `${SESSION_COOKIE}=${token}`;
+
+  assert.deepEqual(authenticate('GET', { cookie }), { user: 'alice', role: 'operator' });
+  assert.deepEqual(authenticate('POST', { cookie, origin: 'http://sv2.local:3001' }), { user: 'alice', role: 'operator' });
+  assert.deepEqual(authenticate('POST', { cookie, origin: 'http://sv2.local:8080' }), {});
+  assert.deepEqual(authenticate('GET', { cookie, authorization: 'Bearer s3cret' }), {});
+  assert.deepEqual(authenticate('GET', {}), {});
+
+  // Role changes apply to the next request; removed users are signed out.
+  users.u1 = { ...users.u1, role: 'viewer' };
+  assert.deepEqual(authenticate('GET', { cookie }), { user: 'alice', role: 'viewer' });
+  delete users.u1;
+  assert.deepEqual(authenticate('GET', { cookie }), {});
+});
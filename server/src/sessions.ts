@@ -0,0 +1,178 @@
+/**
+ * Cookie sessions for user accounts (see users.ts), so the dashboard can
+ * show its own login form instead of the browser's Basic auth prompt.
+ *
+ * POST /api/v1/auth/login checks a username and password and sets an
+ * httpOnly, SameSite=Strict cookie naming the session, signed with a key
+ * made at startup. A session ends SESSION_LIFETIME_SECS after login, or
+ * SESSION_IDLE_SECS after its last request, whichever comes first.
+ * Sessions are kept in memory, so restarting the server signs everyone out.
+ *
+ * The session's user is looked up on every request, so role changes apply
+ * at once. Removing a user or changing their password ends their sessions.
+ */
+
+import crypto from 'crypto';
+import type { RequestHandler } from 'express';
+import type { UserStore } from './users.js';
+
+export const SESSION_COOKIE = 'sv2_session';
+
+export interface SessionConfig {
+  lifetimeMs: number;
+  idleMs: number;
+}
+
+export const DEFAULT_SESSION_LIFETIME_SECS = 7 * 86_400;
+export const DEFAULT_SESSION_IDLE_SECS = 86_400;
+const MAX_SESSIONS = 10_000;
+const SAFE_METHODS = new Set(['GET', 'HEAD', 'OPTIONS']);
+
+interface Session {
+  userId: string;
+  expiresAt: number;
+  lastSeenAt: number;
+}
+
+/**
+ * Read SESSION_LIFETIME_SECS / SESSION_IDLE_SECS. The idle timeout is capped
+ * at the lifetime.
+ */
+export function parseSessionConfig(env: NodeJS.ProcessEnv = process.env): SessionConfig {
+  const parse = (name: string, fallback: number) => {
+    const raw = env[name]?.trim();
+    if (!raw) return fallback;
+    const value = Number(raw);
+    if (!Number.isFinite(value) || value <= 0) {
+      throw new Error(`Invalid ${name} "${raw}"`);
+    }
+    return value;
+  };
+
+  const lifetimeSecs = parse('SESSION_LIFETIME_SECS', DEFAULT_SESSION_LIFETIME_SECS);
+  const idleSecs = parse('SESSION_IDLE_SECS', DEFAULT_SESSION_IDLE_SECS);
+  return { lifetimeMs: lifetimeSecs * 1000, idleMs: Math.min(idleSecs, lifetimeSecs) * 1000 };
+}
+
+export function parseCookies(header: string | undefined): Record<string, string> {
+  const cookies: Record<string, string> = {};
+  for (const part of (header ?? '').split(';')) {
+    const separator = part.indexOf('=');
+    if (separator <= 0) continue;
+    const name = part.slice(0, separator).trim();
+    const value = part.slice(separator + 1).trim().replace(/^"(.*)"$/, '$1');
+    // The first cookie of a name is the most specific one.
+    if (name in cookies) continue;
+    try {
+      cookies[name] = decodeURIComponent(value);
+    } catch {
+      // Not ours; ignore it.
+    }
+  }
+  return cookies;
+}
+
+export function createSessionStore(
+  config: SessionConfig,
+  { secret = crypto.randomBytes(32), now = Date.now }: { secret?: Buffer; now?: () => number } = {},
+) {
+  const sessions = new Map<string, Session>();
+
+  const sign = (id: string) => crypto.createHmac('sha256', secret).update(id).digest('base64url');
+  const endsAt = (session: Session) => Math.min(session.expiresAt, session.lastSeenAt + config.idleMs);
+
+  function sweep(at: number): void {
+    for (const [id, session] of sessions) {
+      if (endsAt(session) <= at) sessions.delete(id);
+    }
+  }
+
+  // The session id of a cookie value whose signature checks out.
+  function verify(token: string): string | null {
+    const [id, signature, ...rest] = token.split('.');
+    if (!id || !signature || rest.length > 0) return null;
+    const expected = Buffer.from(sign(id));
+    const actual = Buffer.from(signature);
+    return actual.length === expected.length && crypto.timingSafeEqual(actual, expected) ? id : null;
+  }
+
+  /**
+   * Start a session for a user, returning the cookie value and when it ends
+   * at the latest.
+   */
+  function create(userId: string): { token: string; expiresAt: string } {
+    const at = now();
+    sweep(at);
+    if (sessions.size >= MAX_SESSIONS) sessions.delete(sessions.keys().next().value!);
+    const id = crypto.randomBytes(24).toString('base64url');
+    sessions.set(id, { userId, expiresAt: at + config.lifetimeMs, lastSeenAt: at });
+    return { token: `${id}.${sign(id)}`, expiresAt: new Date(at + config.lifetimeMs).toISOString() };
+  }
+
+  /**
+   * The user of a live session, counting this as activity; null when the
+   * cookie is forged or the session has ended.
+   */
+  function touch(token: string): string | null {
+    const id = verify(token);
+    const session = id ? sessions.get(id) : undefined;
+    if (!id || !session) return null;
+    const at = now();
+    if (endsAt(session) <= at) {
+      sessions.delete(id);
+      return null;
+    }
+    session.lastSeenAt = at;
+    return session.userId;
+  }
+
+  function destroy(token: string): boolean {
+    const id = verify(token);
+    return id !== null && sessions.delete(id);
+  }
+
+  /**
+   * End every session of a user, e.g. once they are removed or their
+   * password changes.
+   */
+  function revokeUser(userId: string): void {
+    for (const [id, session] of sessions) {
+      if (session.userId === userId) sessions.delete(id);
+    }
+  }
+
+  return { create, touch, destroy, revokeUser, size: () => sessions.size };
+}
+
+export type SessionStore = ReturnType<typeof createSessionStore>;
+
+/**
+ * Sign in requests that carry a session cookie, setting `res.locals.user`
+ * and the role. Requests with an Authorization header are left to the other
+ * middleware. Changes from another origin don't count, so a page elsewhere
+ * on the same site can't act with the cookie.
+ */
+export function createSessionMiddleware(sessions: SessionStore, users: Pick<UserStore, 'get'>): RequestHandler {
+  return (req, res, next) => {
+    const token = parseCookies(req.headers.cookie)[SESSION_COOKIE];
+    if (!token || req.headers.authorization) return next();
+    if (!SAFE_METHODS.has(req.method) && !isSameOrigin(req.headers.origin, req.headers.host)) return next();
+
+    const userId = sessions.touch(token);
+    const user = userId ? users.get(userId) : null;
+    if (user) {
+      res.locals.user = user.username;
+      res.locals.role = user.role;
+    }
+    next();
+  };
+}
+
+function isSameOrigin(origin: string | undefined, host: string | undefined): boolean {
+  if (origin === undefined) return true;
+  try {
+    return new URL(origin).host === host;
+  } catch {
+    return false;
+  }
+}
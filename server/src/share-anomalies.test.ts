@@ -0,0 +1,105 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { createAlertEngine, ANOMALY_QUIET_MS } from './alerts.js';
+import type { ClientChannelInfo, StackData } from './monitoring.js';
+import { collectChannelShares, detectShareAnomalies, findSharedExtranonces, type ChannelShareSample } from './share-anomalies.js';
+
+function channel(overrides: Partial<ClientChannelInfo> = {}): ClientChannelInfo {
+  return {
+    channel_id: 1,
+    user_identity: 'rig-1',
+    nominal_hashrate: 1e12,
+    target_hex: '00',
+    expected_shares_per_minute: 6,
+    best_diff: 0,
+    blocks_found: 0,
+    shares_accepted: 0,
+    shares_rejected: 0,
+    shares_rejected_by_reason: {},
+    ...overrides,
+  };
+}
+
+function jdData(channels: ClientChannelInfo[]): StackData {
+  return {
+    mode: 'jd',
+    upstream: 'jdc',
+    global: { uptime_secs: 60 },
+    serverChannels: null,
+    sv1Clients: null,
+    sv2Clients: [{ client_id: 7, extended_channels: channels, standard_channels: [] }],
+  };
+}
+
+function sample(overrides: Partial<ChannelShareSample> = {}): ChannelShareSample {
+  return { key: 'c1', userIdentity: 'rig-1', submitted: 100, duplicates: 0, expectedPerMinute: 6, ...overrides };
+}
+
+test('collects server and downstream channel counters', () => {
+  const samples = collectChannelShares(jdData([channel({
+    shares_accepted: 90,
+    shares_rejected: 10,
+    shares_rejected_by_reason: { 'duplicate-share': 4, 'stale-share': 6 },
+  })]));
+  assert.deepEqual(samples, [{
+    key: 'jdc:client:7:1',
+    userIdentity: 'rig-1',
+    submitted: 100,
+    duplicates: 4,
+    expectedPerMinute: 6,
+  }]);
+});
+
+test('flags new duplicate rejects', () => {
+  const anomalies = detectShareAnomalies([sample()], [sample({ submitted: 110, duplicates: 3 })], 60_000);
+  assert.deepEqual(anomalies.map((a) => a.kind), ['duplicate-shares']);
+  assert.match(anomalies[0].message, /rig-1: 3 duplicate share/);
+});
+
+test('flags share rates far above what the difficulty allows', () => {
+  // 6 expected per minute; 300 in one minute is 50x.
+  assert.deepEqual(
+    detectShareAnomalies([sample()], [sample({ submitted: 400 })], 60_000).map((a) => a.kind),
+    ['excess-share-rate'],
+  );
+  // Ordinary variance and small counts are ignored.
+  assert.deepEqual(detectShareAnomalies([sample()], [sample({ submitted: 140 })], 60_000), []);
+  assert.deepEqual(detectShareAnomalies([sample()], [sample({ submitted: 110, expectedPerMinute: 0.1 })], 60_000), []);
+});
+
+test('ignores channels that reset or are new', () => {
+  assert.deepEqual(detectShareAnomalies([sample()], [sample({ submitted: 5, duplicates: 2 })], 60_000), []);
+  assert.deepEqual(detectShareAnomalies([], [sample({ duplicates: 2 })], 60_000), []);
+});
+
+test('finds connections sharing an extranonce', () => {
+  const data: StackData = {
+    ...jdData([]),
+    sv1Clients: {
+      total: 3,
+      items: [
+        { client_id: 1, authorized_worker_name: 'a', user_identity: 'a', target_hex: '00', extranonce1_hex: 'ab' },
+        { client_id: 2, authorized_worker_name: 'b', user_identity: 'b', target_hex: '00', extranonce1_hex: 'ab' },
+        { client_id: 3, authorized_worker_name: 'c', user_identity: 'c', target_hex: '00', extranonce1_hex: 'cd' },
+      ],
+    },
+  };
+  const anomalies = findSharedExtranonces(data);
+  assert.equal(anomalies.length, 1);
+  assert.match(anomalies[0].message, /^a, b share extranonce ab/);
+});
+
+test('anomaly alerts fire once and resolve after a quiet period', () => {
+  const engine = createAlertEngine();
+  const at = (ms: number) => new Date(Date.UTC(2026, 0, 1) + ms).toISOString();
+  const duplicates = (count: number) => jdData([channel({ shares_accepted: 100 + count, shares_rejected: count, shares_rejected_by_reason: { 'duplicate-share': count } })]);
+
+  assert.deepEqual(engine.evaluateAnomalies(duplicates(0), at(0)), []);
+  assert.deepEqual(engine.evaluateAnomalies(duplicates(2), at(30_000)).map((t) => t.status), ['firing']);
+  assert.deepEqual(engine.evaluateAnomalies(duplicates(4), at(60_000)), []);
+  assert.equal(engine.anomalies().length, 1);
+
+  assert.deepEqual(engine.evaluateAnomalies(duplicates(4), at(60_000 + ANOMALY_QUIET_MS - 1)), []);
+  assert.deepEqual(engine.evaluateAnomalies(duplicates(4), at(60_000 + ANOMALY_QUIET_MS)).map((t) => t.status), ['resolved']);
+  assert.equal(engine.anomalies().length, 0);
+});
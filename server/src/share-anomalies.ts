@@ -0,0 +1,145 @@
+/**
+ * Share patterns that point at misconfigured or duplicated miners.
+ *
+ * Two machines mining the same work show up pool-side only as a trickle of
+ * "duplicate share" rejects. The monitoring APIs expose enough to catch the
+ * usual causes earlier:
+ *
+ * - duplicate-shares: a channel's duplicate rejects grew since the last sample
+ * - shared-extranonce: two connections were handed the same extranonce, so
+ *   they search the same space and submit identical shares
+ * - excess-share-rate: a channel submits far more shares than its difficulty
+ *   allows, i.e. more hashrate than it announced is behind it
+ */
+
+import type { StackData } from './monitoring.js';
+
+export type ShareAnomalyKind = 'duplicate-shares' | 'shared-extranonce' | 'excess-share-rate';
+
+export interface ShareAnomaly {
+  kind: ShareAnomalyKind;
+  // Stable identity of what's affected, used to track the anomaly over time.
+  key: string;
+  message: string;
+  params: Record<string, string | number>;
+}
+
+export interface ChannelShareSample {
+  key: string;
+  userIdentity: string;
+  submitted: number;
+  duplicates: number;
+  // Only reported for downstream channels.
+  expectedPerMinute: number | null;
+}
+
+// Share submission is a Poisson process, so allow a wide margin before
+// calling a rate impossible, and require enough shares to be meaningful.
+export const EXCESS_SHARE_RATE_FACTOR = 10;
+const MIN_SHARES_FOR_RATE = 20;
+
+const DUPLICATE_REASON_PATTERN = /duplicate/i;
+
+function countDuplicates(byReason: Record<string, number>): number {
+  return Object.entries(byReason)
+    .filter(([reason]) => DUPLICATE_REASON_PATTERN.test(reason))
+    .reduce((sum, [, count]) => sum + count, 0);
+}
+
+export function collectChannelShares(data: StackData): ChannelShareSample[] {
+  const server = data.serverChannels
+    ? [...data.serverChannels.extended_channels, ...data.serverChannels.standard_channels]
+    : [];
+  const samples: ChannelShareSample[] = server.map((channel) => ({
+    key: `${data.upstream}:server:${channel.channel_id}`,
+    userIdentity: channel.user_identity,
+    submitted: channel.shares_submitted,
+    duplicates: countDuplicates(channel.shares_rejected_by_reason),
+    expectedPerMinute: null,
+  }));
+
+  for (const client of data.sv2Clients ?? []) {
+    for (const channel of [...client.extended_channels, ...client.standard_channels]) {
+      samples.push({
+        key: `jdc:client:${client.client_id}:${channel.channel_id}`,
+        userIdentity: channel.user_identity,
+        submitted: channel.shares_accepted + channel.shares_rejected,
+        duplicates: countDuplicates(channel.shares_rejected_by_reason),
+        expectedPerMinute: channel.expected_shares_per_minute,
+      });
+    }
+  }
+
+  return samples;
+}
+
+/**
+ * Compare per-channel counters between two samples `elapsedMs` apart.
+ */
+export function detectShareAnomalies(
+  previous: ChannelShareSample[],
+  current: ChannelShareSample[],
+  elapsedMs: number,
+): ShareAnomaly[] {
+  const before = new Map(previous.map((sample) => [sample.key, sample]));
+  const anomalies: ShareAnomaly[] = [];
+
+  for (const sample of current) {
+    const prior = before.get(sample.key);
+    // Counters restart when a channel reopens under the same id.
+    if (!prior || sample.submitted < prior.submitted) continue;
+
+    const newDuplicates = sample.duplicates - prior.duplicates;
+    if (newDuplicates > 0) {
+      anomalies.push({
+        kind: 'duplicate-shares',
+        key: `duplicate-shares:${sample.key}`,
+        message: `${sample.userIdentity}: ${newDuplicates} duplicate share(s) rejected. Check for two miners using the same worker.`,
+        params: { worker: sample.userIdentity, count: newDuplicates },
+      });
+    }
+
+    const newShares = sample.submitted - prior.submitted;
+    const minutes = elapsedMs / 60_000;
+    if (sample.expectedPerMinute && minutes > 0 && newShares >= MIN_SHARES_FOR_RATE) {
+      const rate = newShares / minutes;
+      if (rate > sample.expectedPerMinute * EXCESS_SHARE_RATE_FACTOR) {
+        anomalies.push({
+          kind: 'excess-share-rate',
+          key: `excess-share-rate:${sample.key}`,
+          message: `${sample.userIdentity}: ${rate.toFixed(1)} shares/min, expected about ${sample.expectedPerMinute.toFixed(1)}. More hashrate than announced is behind this channel.`,
+          params: { worker: sample.userIdentity, rate: rate.toFixed(1), expected: sample.expectedPerMinute.toFixed(1) },
+        });
+      }
+    }
+  }
+
+  return anomalies;
+}
+
+/**
+ * Connections that were assigned the same extranonce.
+ */
+export function findSharedExtranonces(data: StackData): ShareAnomaly[] {
+  const groups = new Map<string, string[]>();
+  const add = (extranonce: string | undefined, name: string) => {
+    if (!extranonce) return;
+    groups.set(extranonce, [...(groups.get(extranonce) ?? []), name]);
+  };
+
+  for (const client of data.sv1Clients?.items ?? []) {
+    add(client.extranonce1_hex && `sv1:${client.extranonce1_hex}`, client.authorized_worker_name || client.user_identity);
+  }
+  for (const client of data.sv2Clients ?? []) {
+    for (const channel of [...client.extended_channels, ...client.standard_channels]) {
+      add(channel.extranonce_prefix_hex && `sv2:${channel.extranonce_prefix_hex}`, channel.user_identity);
+    }
+  }
+
+  return [...groups].filter(([, names]) => names.length > 1).map(([extranonce, names]) => ({
+    kind: 'shared-extranonce',
+    key: `shared-extranonce:${extranonce}`,
+    message: `${names.join(', ')} share extranonce ${extranonce.split(':')[1]} and will submit identical work.`,
+    params: { workers: names.join(', '), extranonce: extranonce.split(':')[1] },
+  }));
+}
@@ -0,0 +1,76 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { createShareLinkStore, parseShareLinkRequest, renderSharePage } from './share-links.js';
+import type { StackSummary } from './summary.js';
+
+const SUMMARY: StackSummary = {
+  generatedAt: '2026-01-01T00:00:00.000Z',
+  miningMode: 'pool',
+  mode: 'no-jd',
+  network: null,
+  poolName: '<script>alert(1)</script>',
+  online: true,
+  hashrate: 1e12,
+  workers: 3,
+  sharesAccepted: 10,
+  sharesSubmitted: 11,
+  sharesRejected: 1,
+  bestDifficulty: 2048,
+  blocksFound: 0,
+  uptimeSecs: 3600,
+};
+
+async function tempStore() {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-share-links-'));
+  return createShareLinkStore(path.join(dir, 'share-links.json'));
+}
+
+test('validates the share link request body', () => {
+  assert.deepEqual(parseShareLinkRequest(undefined), { view: 'snapshot', ttlHours: 24 });
+  assert.deepEqual(parseShareLinkRequest({ view: 'live', ttlHours: 1 }), { view: 'live', ttlHours: 1 });
+  assert.throws(() => parseShareLinkRequest({ view: 'full' }), /view must be/);
+  assert.throws(() => parseShareLinkRequest({ ttlHours: 0 }), /ttlHours/);
+  assert.throws(() => parseShareLinkRequest({ ttlHours: 24 * 365 }), /ttlHours/);
+});
+
+test('links resolve until they expire and are pruned afterwards', async () => {
+  const store = await tempStore();
+  const now = new Date('2026-01-01T00:00:00.000Z');
+
+  const link = await store.create({ view: 'snapshot', ttlHours: 1, snapshot: SUMMARY }, now);
+  assert.match(link.token, /^[\w-]{24}$/);
+  assert.deepEqual((await store.get(link.token, now))?.snapshot, SUMMARY);
+  assert.equal(await store.get('not-a-real-token-at-all', now), null);
+
+  const later = new Date(now.getTime() + 2 * 3_600_000);
+  assert.equal(await store.get(link.token, later), null);
+  assert.deepEqual(await store.list(later), []);
+});
+
+test('live links never persist a snapshot and can be revoked', async () => {
+  const store = await tempStore();
+  const link = await store.create({ view: 'live', ttlHours: 1, snapshot: SUMMARY });
+
+  assert.equal(link.snapshot, null);
+  assert.equal(await store.revoke(link.token), true);
+  assert.equal(await store.revoke(link.token), false);
+  assert.equal(await store.get(link.token), null);
+});
+
+test('renders an escaped standalone page', async () => {
+  const store = await tempStore();
+  const link = await store.create({ view: 'snapshot', ttlHours: 1, snapshot: SUMMARY });
+  const html = renderSharePage(SUMMARY, link, {
+    hashrate: () => '1.00 TH/s',
+    difficulty: () => '2.05K',
+    uptime: () => '1h 0m',
+  });
+
+  assert.match(html, /1\.00 TH\/s/);
+  assert.match(html, /&lt;script&gt;alert\(1\)&lt;\/script&gt;/);
+  assert.doesNotMatch(html, /<script>/);
+  assert.doesNotMatch(html, /http-equiv="refresh"/);
+});
@@ -0,0 +1,178 @@
+/**
+ * Expiring, unauthenticated share links.
+ *
+ * A link exposes the stack summary read-only at /share/:token, either frozen
+ * at the moment it was minted ('snapshot') or recomputed on every visit
+ * ('live'). Summaries carry no worker identities or addresses.
+ */
+
+import crypto from 'crypto';
+import fs from 'fs/promises';
+import path from 'path';
+import { NETWORK_LABELS } from '@sv2-ui/shared';
+import { formatSummaryValue as show } from './summary.js';
+import type { StackSummary } from './summary.js';
+import { escapeMarkup } from './escape.js';
+
+export type ShareLinkView = 'snapshot' | 'live';
+
+export interface ShareLink {
+  token: string;
+  view: ShareLinkView;
+  createdAt: string;
+  expiresAt: string;
+  snapshot: StackSummary | null;
+}
+
+export const DEFAULT_SHARE_LINK_TTL_HOURS = 24;
+export const MAX_SHARE_LINK_TTL_HOURS = 24 * 30;
+
+const TOKEN_PATTERN = /^[\w-]{16,64}$/;
+
+export function parseShareLinkRequest(body: unknown): { view: ShareLinkView; ttlHours: number } {
+  const { view = 'snapshot', ttlHours = DEFAULT_SHARE_LINK_TTL_HOURS } = (body ?? {}) as {
+    view?: unknown;
+    ttlHours?: unknown;
+  };
+
+  if (view !== 'snapshot' && view !== 'live') {
+    throw new Error('view must be "snapshot" or "live"');
+  }
+  if (typeof ttlHours !== 'number' || !Number.isFinite(ttlHours) || ttlHours <= 0 || ttlHours > MAX_SHARE_LINK_TTL_HOURS) {
+    throw new Error(`ttlHours must be between 0 and ${MAX_SHARE_LINK_TTL_HOURS}`);
+  }
+
+  return { view, ttlHours };
+}
+
+function isExpired(link: ShareLink, now: Date): boolean {
+  return Date.parse(link.expiresAt) <= now.getTime();
+}
+
+export function createShareLinkStore(filePath: string) {
+  async function read(): Promise<ShareLink[]> {
+    try {
+      const links = JSON.parse(await fs.readFile(filePath, 'utf-8')) as ShareLink[];
+      return Array.isArray(links) ? links : [];
+    } catch {
+      return [];
+    }
+  }
+
+  async function write(links: ShareLink[]): Promise<void> {
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    await fs.writeFile(filePath, JSON.stringify(links, null, 2));
+  }
+
+  /**
+   * List active links, dropping expired ones from disk as a side effect.
+   */
+  async function list(now = new Date()): Promise<ShareLink[]> {
+    const links = await read();
+    const active = links.filter((link) => !isExpired(link, now));
+    if (active.length !== links.length) {
+      await write(active);
+    }
+    return active;
+  }
+
+  async function create(
+    { view, ttlHours, snapshot }: { view: ShareLinkView; ttlHours: number; snapshot: StackSummary | null },
+    now = new Date(),
+  ): Promise<ShareLink> {
+    const link: ShareLink = {
+      token: crypto.randomBytes(18).toString('base64url'),
+      view,
+      createdAt: now.toISOString(),
+      expiresAt: new Date(now.getTime() + ttlHours * 3_600_000).toISOString(),
+      snapshot: view === 'snapshot' ? snapshot : null,
+    };
+
+    await write([...(await list(now)), link]);
+    return link;
+  }
+
+  async function get(token: string, now = new Date()): Promise<ShareLink | null> {
+    if (!TOKEN_PATTERN.test(token)) return null;
+    const links = await list(now);
+    return links.find((link) => crypto.timingSafeEqual(
+      crypto.createHash('sha256').update(link.token).digest(),
+      crypto.createHash('sha256').update(token).digest(),
+    )) ?? null;
+  }
+
+  async function revoke(token: string): Promise<boolean> {
+    const links = await read();
+    const remaining = links.filter((link) => link.token !== token);
+    if (remaining.length === links.length) return false;
+    await write(remaining);
+    return true;
+  }
+
+  return { list, create, get, revoke };
+}
+
+export type ShareLinkStore = ReturnType<typeof createShareLinkStore>;
+
+/**
+ * Minimal standalone page for a share link. It doesn't load the SPA bundle,
+ * so visitors never reach the authenticated API surface.
+ */
+export function renderSharePage(
+  summary: StackSummary,
+  link: ShareLink,
+  format: {
+    hashrate: (value: number) => string;
+    difficulty: (value: number) => string;
+    uptime: (value: number) => string;
+  },
+): string {
+  const rows: [string, string][] = [
+    ['Status', summary.online ? 'Online' : 'Offline'],
+    ['Pool', summary.poolName ?? (summary.miningMode === 'solo' ? 'Solo' : '—')],
+    // REDACT_PATHS may have replaced the network with a placeholder string.
+    ...(summary.network ? [['Network', NETWORK_LABELS[summary.network] ?? String(summary.network)] as [string, string]] : []),
+    ['Hashrate', show(summary.hashrate, format.hashrate)],
+    ['Workers', show(summary.workers)],
+    ['Shares accepted', `${show(summary.sharesAccepted)} / ${show(summary.sharesSubmitted)}`],
+    ['Shares rejected', show(summary.sharesRejected)],
+    ['Best difficulty', show(summary.bestDifficulty, format.difficulty)],
+    ['Blocks found', show(summary.blocksFound)],
+    ['Uptime', show(summary.uptimeSecs, format.uptime)],
+  ];
+
+  const body = rows
+    .map(([label, value]) => `      <tr><th>${escapeMarkup(label)}</th><td>${escapeMarkup(value)}</td></tr>`)
+    .join('\n');
+  const caption = link.view === 'live'
+    ? `Live view · updated ${summary.generatedAt}`
+    : `Snapshot taken ${summary.generatedAt}`;
+
+  return `<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <meta name="robots" content="noindex" />${link.view === 'live' ? '\n    <meta http-equiv="refresh" content="30" />' : ''}
+    <title>SV2 Mining Summary</title>
+    <style>
+      body { font-family: system-ui, sans-serif; background: #0b0b0c; color: #e5e5e5; display: flex; justify-content: center; padding: 2rem; }
+      table { border-collapse: collapse; min-width: 20rem; }
+      caption { text-align: left; color: #8a8a8a; padding-bottom: 0.75rem; }
+      th, td { text-align: left; padding: 0.4rem 1rem 0.4rem 0; border-bottom: 1px solid #222; }
+      th { color: #8a8a8a; font-weight: normal; }
+      footer { color: #666; font-size: 0.8rem; margin-top: 1rem; }
+    </style>
+  </head>
+  <body>
+    <main>
+    <table>
+      <caption>${escapeMarkup(caption)}</caption>
+${body}
+    </table>
+    <footer>Link expires ${escapeMarkup(link.expiresAt)}</footer>
+    </main>
+  </body>
+</html>
+`;
+}
@@ -0,0 +1,59 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import type { ServerChannelInfo, StackData } from './monitoring.js';
+import { createShareWindows } from './share-windows.js';
+
+const MINUTE = 60_000;
+const START = Date.parse('2026-01-01T00:00:00.000Z');
+
+const channel = (channel_id: number, user_identity: string, accepted: number, rejected: number): ServerChannelInfo => ({
+  channel_id,
+  user_identity,
+  target_hex: '00',
+  best_diff: 0,
+  blocks_found: 0,
+  shares_acknowledged: accepted,
+  shares_submitted: accepted + rejected,
+  shares_rejected: rejected,
+  shares_rejected_by_reason: {},
+});
+
+const stack = (channels: ServerChannelInfo[] | null): StackData => ({
+  mode: 'no-jd',
+  upstream: 'translator',
+  global: null,
+  serverChannels: channels && { extended_channels: channels, standard_channels: [], total_extended: channels.length, total_standard: 0 },
+  sv1Clients: null,
+  sv2Clients: null,
+});
+
+test('share windows count counter growth between polls, per miner and in total', () => {
+  const windows = createShareWindows();
+  // Counts from before the first poll aren't known to be recent.
+  windows.observe(stack([channel(1, 's19-a', 1000, 10), channel(2, 's19-b', 500, 0)]), START);
+  windows.observe(stack([channel(1, 's19-a', 1100, 12), channel(2, 's19-b', 540, 0)]), START + 10 * MINUTE);
+  windows.observe(stack([channel(1, 's19-a', 1130, 12), channel(2, 's19-b', 540, 0)]), START + 2 * 60 * MINUTE);
+
+  const { coveredSecs, total, miners } = windows.get(START + 2 * 60 * MINUTE);
+  assert.equal(coveredSecs, 7200);
+  assert.deepEqual(total['5m'], { accepted: 30, rejected: 0, rejectRate: 0 });
+  assert.deepEqual(total['24h'], { accepted: 170, rejected: 2, rejectRate: 2 / 172 });
+  assert.deepEqual(miners.map(({ id, '24h': day }) => [id, day.accepted, day.rejected]), [['s19-a', 130, 2], ['s19-b', 40, 0]]);
+  assert.deepEqual(miners[1]['1h'], { accepted: 0, rejected: 0, rejectRate: null });
+});
+
+test('restarted counters and failed polls are not counted twice', () => {
+  const windows = createShareWindows();
+  windows.observe(stack([channel(1, 's19-a', 1000, 10)]), START);
+  // The Translator restarted: the channel's counters start over.
+  windows.observe(stack([channel(1, 's19-a', 20, 1)]), START + MINUTE);
+  // The section failed, then came back unchanged.
+  windows.observe(stack(null), START + 2 * MINUTE);
+  windows.observe(stack([channel(1, 's19-a', 25, 1)]), START + 3 * MINUTE);
+
+  assert.deepEqual(windows.get(START + 3 * MINUTE).total['1h'], { accepted: 25, rejected: 1, rejectRate: 1 / 26 });
+  // Buckets older than a day are dropped.
+  windows.observe(stack([channel(1, 's19-a', 25, 1)]), START + 25 * 60 * MINUTE);
+  assert.deepEqual(windows.get(START + 25 * 60 * MINUTE).total['24h'], { accepted: 0, rejected: 0, rejectRate: null });
+});
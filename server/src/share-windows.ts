@@ -0,0 +1,173 @@
+/**
+ * Accepted and rejected shares over the last 5 minutes, hour and day, in
+ * total and per miner, for the summary API.
+ *
+ * The monitoring APIs only report counters since a channel opened, which
+ * reset whenever the Translator or JDC restarts. Instead, the growth of each
+ * channel's counters between polls is added to one-minute buckets, and the
+ * windows sum the buckets they cover. Totals come from the upstream channels,
+ * like the summary's share counts; miners from the JDC's downstream channels
+ * in JD mode and from the upstream channels, by user identity, otherwise.
+ *
+ * Buckets are kept in memory, so the windows start over when the server
+ * restarts; `coveredSecs` says how much of them has been seen.
+ */
+
+import type { StackData } from './monitoring.js';
+
+export const SHARE_WINDOWS = { '5m': 5 * 60_000, '1h': 3_600_000, '24h': 86_400_000 } as const;
+export type ShareWindow = keyof typeof SHARE_WINDOWS;
+
+const BUCKET_MS = 60_000;
+const MAX_WINDOW_MS = Math.max(...Object.values(SHARE_WINDOWS));
+
+export interface ShareCounts {
+  accepted: number;
+  rejected: number;
+  // Rejected out of accepted plus rejected; null before any share is counted.
+  rejectRate: number | null;
+}
+
+export type ShareWindowCounts = Record<ShareWindow, ShareCounts>;
+
+export interface ShareWindows {
+  // Seconds since counting started, up to the longest window.
+  coveredSecs: number;
+  total: ShareWindowCounts;
+  // An array rather than a map, so REDACT_PATHS can reach the ids.
+  miners: Array<{ id: string } & ShareWindowCounts>;
+}
+
+interface ChannelCounter {
+  key: string;
+  miner: string;
+  accepted: number;
+  rejected: number;
+}
+
+type Tally = { accepted: number; rejected: number };
+
+// Null when the section the counters come from couldn't be fetched.
+function readUpstreamCounters(data: StackData): ChannelCounter[] | null {
+  if (!data.serverChannels) return null;
+  return [...data.serverChannels.extended_channels, ...data.serverChannels.standard_channels].map((channel) => ({
+    key: `server:${channel.channel_id}`,
+    miner: channel.user_identity,
+    accepted: channel.shares_acknowledged,
+    rejected: channel.shares_rejected,
+  }));
+}
+
+function readMinerCounters(data: StackData): ChannelCounter[] | null {
+  if (data.mode !== 'jd') return readUpstreamCounters(data);
+  if (!data.sv2Clients) return null;
+  return data.sv2Clients.flatMap((client) => [...client.extended_channels, ...client.standard_channels].map((channel) => ({
+    key: `jdc:client:${client.client_id}:${channel.channel_id}`,
+    miner: channel.user_identity,
+    accepted: channel.shares_accepted,
+    rejected: channel.shares_rejected,
+  })));
+}
+
+function withRate({ accepted, rejected }: Tally): ShareCounts {
+  const shares = accepted + rejected;
+  return { accepted, rejected, rejectRate: shares > 0 ? rejected / shares : null };
+}
+
+/**
+ * Counts one set of channel counters: the growth of each since the previous
+ * poll goes to `add`, by miner.
+ */
+function createCounterSet() {
+  let previous: Map<string, ChannelCounter> | null = null;
+
+  function observe(counters: ChannelCounter[] | null, add: (miner: string, tally: Tally) => void): void {
+    // Keep the last reading while a section is failing, so it isn't counted again when it's back.
+    if (!counters) return;
+    if (previous) {
+      for (const counter of counters) {
+        const prior = previous.get(counter.key);
+        // A channel opened since the last poll, or reopened with its counters reset, counts from zero.
+        const base = prior && counter.accepted >= prior.accepted && counter.rejected >= prior.rejected
+          ? prior
+          : { accepted: 0, rejected: 0 };
+        const accepted = counter.accepted - base.accepted;
+        const rejected = counter.rejected - base.rejected;
+        if (accepted > 0 || rejected > 0) add(counter.miner, { accepted, rejected });
+      }
+    }
+    previous = new Map(counters.map((counter) => [counter.key, counter]));
+  }
+
+  return { observe };
+}
+
+export function createShareWindows() {
+  const upstream = createCounterSet();
+  const downstream = createCounterSet();
+  // Bucket start -> totals and per-miner tallies.
+  const buckets = new Map<number, { total: Tally; miners: Map<string, Tally> }>();
+  let startedAt: number | null = null;
+
+  function bucket(at: number) {
+    const start = Math.floor(at / BUCKET_MS) * BUCKET_MS;
+    let entry = buckets.get(start);
+    if (!entry) {
+      entry = { total: { accepted: 0, rejected: 0 }, miners: new Map() };
+      buckets.set(start, entry);
+    }
+    return entry;
+  }
+
+  function observe(data: StackData, at: number): void {
+    startedAt ??= at;
+    upstream.observe(readUpstreamCounters(data), (_miner, { accepted, rejected }) => {
+      const { total } = bucket(at);
+      total.accepted += accepted;
+      total.rejected += rejected;
+    });
+    downstream.observe(readMinerCounters(data), (miner, { accepted, rejected }) => {
+      const { miners } = bucket(at);
+      const tally = miners.get(miner) ?? { accepted: 0, rejected: 0 };
+      miners.set(miner, { accepted: tally.accepted + accepted, rejected: tally.rejected + rejected });
+    });
+
+    for (const start of buckets.keys()) {
+      if (start + BUCKET_MS <= at - MAX_WINDOW_MS) buckets.delete(start);
+    }
+  }
+
+  function get(now = Date.now()): ShareWindows {
+    const windows = Object.keys(SHARE_WINDOWS) as ShareWindow[];
+    const total = Object.fromEntries(windows.map((window) => [window, { accepted: 0, rejected: 0 }])) as Record<ShareWindow, Tally>;
+    const miners = new Map<string, Record<ShareWindow, Tally>>();
+
+    for (const [start, entry] of buckets) {
+      for (const window of windows) {
+        // Buckets partly inside a window count whole.
+        if (start + BUCKET_MS <= now - SHARE_WINDOWS[window]) continue;
+        total[window].accepted += entry.total.accepted;
+        total[window].rejected += entry.total.rejected;
+        for (const [miner, tally] of entry.miners) {
+          const counts = miners.get(miner)
+            ?? Object.fromEntries(windows.map((key) => [key, { accepted: 0, rejected: 0 }])) as Record<ShareWindow, Tally>;
+          counts[window].accepted += tally.accepted;
+          counts[window].rejected += tally.rejected;
+          miners.set(miner, counts);
+        }
+      }
+    }
+
+    const toCounts = (tallies: Record<ShareWindow, Tally>) =>
+      Object.fromEntries(windows.map((window) => [window, withRate(tallies[window])])) as ShareWindowCounts;
+    return {
+      coveredSecs: startedAt === null ? 0 : Math.round(Math.min(now - startedAt, MAX_WINDOW_MS) / 1000),
+      total: toCounts(total),
+      miners: [...miners].sort(([a], [b]) => a.localeCompare(b)).map(([id, tallies]) => ({ id, ...toCounts(tallies) })),
+    };
+  }
+
+  return { observe, get };
+}
+
+export type ShareWindowTracker = ReturnType<typeof createShareWindows>;
@@ -0,0 +1,90 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import type { AlertState } from './alerts.js';
+import type { StackData } from './monitoring.js';
+import { buildStateSnapshot, SNAPSHOT_VERSION } from './snapshot.js';
+import type { SnapshotInput } from './snapshot.js';
+import type { StackSummary } from './summary.js';
+
+const ZERO = { accepted: 0, rejected: 0, rejectRate: null };
+
+const SUMMARY: StackSummary = {
+  generatedAt: '2026-01-01T00:00:00.000Z',
+  miningMode: 'pool',
+  mode: 'no-jd',
+  network: null,
+  poolName: 'Pool',
+  online: true,
+  hashrate: 1e12,
+  workers: 1,
+  sharesAccepted: 5,
+  sharesSubmitted: 5,
+  sharesRejected: 0,
+  bestDifficulty: 10,
+  blocksFound: 0,
+  uptimeSecs: 60,
+};
+
+const alert = (id: string, status: AlertState['status']): AlertState => ({
+  rule: { id, kind: 'unreachable', threshold: 0, forMinutes: 5 },
+  status,
+  since: status === 'ok' ? null : SUMMARY.generatedAt,
+  value: null,
+});
+
+const INPUT: SnapshotInput = {
+  generatedAt: SUMMARY.generatedAt,
+  status: {
+    configured: true,
+    running: true,
+    miningMode: 'pool',
+    mode: 'no-jd',
+    poolName: 'Pool',
+    network: null,
+    containers: { translator: null, jdc: null },
+  },
+  health: { status: 'ok', checkedAt: SUMMARY.generatedAt, docker: true, services: [], sv1: null },
+  summary: {
+    ...SUMMARY,
+    freshness: {},
+    errors: [],
+    shareWindows: { coveredSecs: 0, total: { '5m': ZERO, '1h': ZERO, '24h': ZERO }, miners: [] },
+  },
+  alerts: [alert('quiet', 'ok'), alert('down', 'firing'), alert('slow', 'pending')],
+  anomalies: [],
+  data: null,
+  config: { configured: true, setup: null, integrations: { telegram: false }, remoteStacks: [] },
+};
+
+test('the snapshot keeps only active alerts and lists connected miners', () => {
+  const snapshot = buildStateSnapshot(INPUT);
+  assert.equal(snapshot.version, SNAPSHOT_VERSION);
+  assert.equal(snapshot.generatedAt, SUMMARY.generatedAt);
+  assert.deepEqual(snapshot.alerts.active.map((state) => state.rule.id), ['down', 'slow']);
+  assert.deepEqual(snapshot.miners, { sv1: [], sv2: [] });
+  assert.equal('data' in snapshot, false);
+
+  const sv1Client = {
+    client_id: 1,
+    channel_id: null,
+    authorized_worker_name: 'rig1',
+    user_identity: 'user.rig1',
+    hashrate: 1e12,
+    stable_hashrate: true,
+    target_hex: '00',
+    extranonce1_hex: '00',
+    extranonce2_len: 4,
+    version_rolling_mask: null,
+    version_rolling_min_bit: null,
+  };
+  const data: StackData = {
+    mode: 'no-jd',
+    upstream: 'translator',
+    global: null,
+    serverChannels: null,
+    sv1Clients: { items: [sv1Client], offset: 0, limit: 100, total: 1 },
+    sv2Clients: null,
+  };
+  assert.deepEqual(buildStateSnapshot({ ...INPUT, data }).miners, { sv1: [sv1Client], sv2: [] });
+});
@@ -0,0 +1,75 @@
+/**
+ * Everything the server currently knows, in one document for
+ * /api/v1/snapshot: service health, the latest stack summary, active alerts,
+ * connected miners and a summary of the configuration.
+ *
+ * It's meant to be attached to bug reports and picked up by backup tooling,
+ * so it only carries what's already held in memory or on disk. Credentials
+ * from the environment never make it in; integrations are listed as on or
+ * off.
+ */
+
+import type { SetupData } from '@sv2-ui/shared';
+import type { ActiveAnomaly, AlertState } from './alerts.js';
+import type { HealthReport } from './health.js';
+import type { Sv1ClientInfo } from './monitoring-schema.js';
+import type { StackData, Sv2ClientInfo } from './monitoring.js';
+import type { StackErrorReport, StackFreshness } from './poller.js';
+import type { ShareWindows } from './share-windows.js';
+import type { StackSummary } from './summary.js';
+import type { StatusResponse } from './types.js';
+
+export const SNAPSHOT_VERSION = 1;
+
+export interface SnapshotConfig {
+  configured: boolean;
+  setup: SetupData | null;
+  // Which optional integrations are configured, without their credentials.
+  integrations: Record<string, boolean>;
+  remoteStacks: { id: string; name: string }[];
+}
+
+export interface StateSnapshot {
+  version: typeof SNAPSHOT_VERSION;
+  generatedAt: string;
+  status: StatusResponse;
+  health: HealthReport;
+  summary: StackSummary & {
+    freshness: StackFreshness;
+    errors: StackErrorReport[];
+    shareWindows: ShareWindows;
+  };
+  alerts: {
+    active: AlertState[];
+    anomalies: ActiveAnomaly[];
+  };
+  miners: {
+    sv1: Sv1ClientInfo[];
+    sv2: Sv2ClientInfo[];
+  };
+  config: SnapshotConfig;
+}
+
+export interface SnapshotInput extends Omit<StateSnapshot, 'version' | 'generatedAt' | 'alerts' | 'miners'> {
+  generatedAt?: string;
+  alerts: AlertState[];
+  anomalies: ActiveAnomaly[];
+  data: StackData | null;
+}
+
+export function buildStateSnapshot({ generatedAt, alerts, anomalies, data, ...rest }: SnapshotInput): StateSnapshot {
+  return {
+    version: SNAPSHOT_VERSION,
+    generatedAt: generatedAt ?? new Date().toISOString(),
+    ...rest,
+    alerts: {
+      // Rules that are quiet only add noise to a bug report.
+      active: alerts.filter((alert) => alert.status !== 'ok'),
+      anomalies,
+    },
+    miners: {
+      sv1: data?.sv1Clients?.items ?? [],
+      sv2: data?.sv2Clients ?? [],
+    },
+  };
+}
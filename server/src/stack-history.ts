@@ -0,0 +1,57 @@
+/**
+ * Stack totals kept in the history database: one sample per metric after
+ * every poll, so hashrate and share counts can be looked back on (and
+ * exported, see history-export.ts).
+ *
+ * While the stack can't be reached the samples are null, leaving a gap.
+ *
+ * Each SV1 miner's hashrate is kept too, keyed by the worker name the
+ * Translator reports, so a failing machine shows up on its own rather than
+ * as a dip in the total. A miner that isn't connected has no sample.
+ */
+
+import type { HistorySample } from './history.js';
+import type { StackData } from './monitoring.js';
+import type { StackSummary } from './summary.js';
+
+export const MINER_HASHRATE_METRIC = 'miner.hashrate';
+
+export const STACK_METRICS = {
+  'stack.hashrate': (summary: StackSummary) => summary.hashrate,
+  'stack.workers': (summary: StackSummary) => summary.workers,
+  'stack.shares_accepted': (summary: StackSummary) => summary.sharesAccepted,
+  'stack.shares_submitted': (summary: StackSummary) => summary.sharesSubmitted,
+  'stack.shares_rejected': (summary: StackSummary) => summary.sharesRejected,
+  'stack.best_difficulty': (summary: StackSummary) => summary.bestDifficulty,
+} as const;
+
+export type StackMetric = keyof typeof STACK_METRICS;
+
+export function getStackSamples(summary: StackSummary): HistorySample[] {
+  return (Object.keys(STACK_METRICS) as StackMetric[]).map((metric) => ({
+    metric,
+    key: '',
+    at: summary.generatedAt,
+    value: summary.online ? STACK_METRICS[metric](summary) : null,
+  }));
+}
+
+/**
+ * The worker a miner is tracked by: its authorized worker name, or its user
+ * identity when the Translator doesn't report one.
+ */
+export function getMinerId(client: { authorized_worker_name: string; user_identity: string }): string {
+  return client.authorized_worker_name || client.user_identity;
+}
+
+export function getMinerSamples(data: StackData, at: string): HistorySample[] {
+  // Connections sharing a worker name add up, as they're usually one machine reconnecting.
+  const hashrates = new Map<string, number | null>();
+  for (const client of data.sv1Clients?.items ?? []) {
+    const id = getMinerId(client);
+    if (!id) continue;
+    const previous = hashrates.get(id) ?? null;
+    hashrates.set(id, client.hashrate === null ? previous : (previous ?? 0) + client.hashrate);
+  }
+  return [...hashrates].map(([key, value]) => ({ metric: MINER_HASHRATE_METRIC, key, at, value }));
+}
@@ -0,0 +1,99 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { getMonitoringBaseUrl } from './monitoring.js';
+import { createStackMonitors, createStackRegistry, getStackEndpoints, parseRemoteStacks } from './stacks.js';
+import type { StackSummary } from './summary.js';
+
+const prod = {
+  id: 'prod',
+  name: 'Production',
+  network: 'mainnet',
+  mode: 'jd',
+  translatorUrl: 'https://prod.example/translator/api/v1/',
+  jdcUrl: 'https://prod.example/jdc/api/v1',
+};
+const staging = {
+  id: 'staging',
+  name: 'Staging',
+  network: 'testnet4',
+  mode: 'no-jd',
+  translatorUrl: 'http://10.0.0.2:9092/api/v1',
+  jdcUrl: 'http://ignored.example',
+};
+
+function summary(overrides: Partial<StackSummary> = {}): StackSummary {
+  return {
+    generatedAt: '2026-01-01T00:00:00.000Z',
+    miningMode: 'pool',
+    mode: 'no-jd',
+    network: 'testnet4',
+    poolName: 'Staging',
+    online: true,
+    hashrate: 1e12,
+    workers: 1,
+    sharesAccepted: 0,
+    sharesSubmitted: 0,
+    sharesRejected: 0,
+    bestDifficulty: 1000,
+    blocksFound: 0,
+    uptimeSecs: 60,
+    ...overrides,
+  };
+}
+
+test('parses remote stacks and normalizes their URLs', () => {
+  assert.deepEqual(parseRemoteStacks({ stacks: [prod, staging] }), [
+    { ...prod, miningMode: 'pool', translatorUrl: 'https://prod.example/translator/api/v1' },
+    { ...staging, miningMode: 'pool', jdcUrl: null },
+  ]);
+});
+
+test('rejects invalid remote stacks', () => {
+  assert.throws(() => parseRemoteStacks({}), /stacks must be an array/);
+  assert.throws(() => parseRemoteStacks({ stacks: [{ ...prod, id: 'Prod' }] }), /id must be/);
+  assert.throws(() => parseRemoteStacks({ stacks: [prod, prod] }), /used more than once/);
+  assert.throws(() => parseRemoteStacks({ stacks: [{ ...prod, network: 'testnet3' }] }), /network must be one of/);
+  assert.throws(() => parseRemoteStacks({ stacks: [{ ...prod, jdcUrl: undefined }] }), /jdcUrl is required/);
+  assert.throws(() => parseRemoteStacks({ stacks: [{ ...staging, translatorUrl: 'ftp://x' }] }), /must be http\(s\)/);
+});
+
+test('routes monitoring requests to the stack endpoints', () => {
+  const [jd, noJd] = parseRemoteStacks({ stacks: [prod, staging] });
+  assert.equal(getMonitoringBaseUrl('jdc', getStackEndpoints(jd)), 'https://prod.example/jdc/api/v1');
+  assert.equal(getMonitoringBaseUrl('translator', getStackEndpoints(noJd)), 'http://10.0.0.2:9092/api/v1');
+  assert.deepEqual(getStackEndpoints(noJd), { translator: 'http://10.0.0.2:9092/api/v1' });
+});
+
+test('round-trips stacks and falls back to none', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-stacks-'));
+  t.after(() => fs.rm(dir, { recursive: true, force: true }));
+  const registry = createStackRegistry(path.join(dir, 'stacks.json'));
+
+  assert.deepEqual(await registry.get(), []);
+  const stacks = parseRemoteStacks({ stacks: [prod] });
+  await registry.save(stacks);
+  assert.deepEqual(await registry.get(), stacks);
+});
+
+test('keeps event logs and alert state apart per stack', async (t) => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-stacks-'));
+  t.after(() => fs.rm(dir, { recursive: true, force: true }));
+  const monitors = createStackMonitors(dir);
+  const [jd, noJd] = monitors.sync(parseRemoteStacks({ stacks: [prod, staging] }));
+
+  await noJd.eventLog.record(summary());
+  await noJd.eventLog.record(summary({ generatedAt: '2026-01-01T00:00:30.000Z', online: false }));
+  assert.deepEqual((await noJd.eventLog.list()).map((event) => event.kind), ['incident']);
+  assert.deepEqual(await jd.eventLog.list(), []);
+
+  const rules = [{ id: 'down', kind: 'unreachable' as const, threshold: 0, forMinutes: 0 }];
+  assert.equal(noJd.alerts.evaluate(summary({ online: false }), rules).length, 1);
+  assert.equal(jd.alerts.list(rules)[0].status, 'ok');
+
+  // Unchanged stacks keep their monitor; removed ones are dropped.
+  assert.equal(monitors.sync(parseRemoteStacks({ stacks: [staging] }))[0], noJd);
+  assert.equal(monitors.get('prod'), null);
+});
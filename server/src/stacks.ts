@@ -0,0 +1,175 @@
+/**
+ * Remote stacks monitored alongside the local one, e.g. a mainnet production
+ * stack next to a testnet staging stack.
+ *
+ * Stacks live in CONFIG_DIR/stacks.json and are edited through
+ * PUT /api/stacks. Each is reached through its own monitoring API URLs and
+ * keeps its own event log (CONFIG_DIR/stacks/<id>/events.json), latest
+ * summary and alert state, so nothing from one stack leaks into another.
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import { SUPPORTED_NETWORKS } from '@sv2-ui/shared';
+import type { BitcoinNetwork, MiningMode, SetupMode } from '@sv2-ui/shared';
+import { createAlertEngine } from './alerts.js';
+import type { AlertEngine } from './alerts.js';
+import { createEventLog } from './events.js';
+import type { EventLog } from './events.js';
+import type { MonitoringEndpoints } from './monitoring.js';
+import type { StackSummary } from './summary.js';
+
+export interface RemoteStack {
+  id: string;
+  name: string;
+  network: BitcoinNetwork;
+  mode: SetupMode;
+  miningMode: MiningMode;
+  // Monitoring API base URLs, ending in /api/v1.
+  translatorUrl: string;
+  // Required in JD mode, unused otherwise.
+  jdcUrl: string | null;
+}
+
+const STACK_ID_PATTERN = /^[a-z0-9][a-z0-9-]{0,31}$/;
+const MODES: SetupMode[] = ['jd', 'no-jd'];
+const MINING_MODES: MiningMode[] = ['pool', 'solo'];
+
+function parseUrl(value: unknown, field: string): string {
+  let url: URL;
+  try {
+    url = new URL(String(value));
+  } catch {
+    throw new Error(`${field} must be a URL`);
+  }
+  if (url.protocol !== 'http:' && url.protocol !== 'https:') {
+    throw new Error(`${field} must be http(s)`);
+  }
+  return url.toString().replace(/\/+$/, '');
+}
+
+/**
+ * Validate a stacks document.
+ */
+export function parseRemoteStacks(body: unknown): RemoteStack[] {
+  const { stacks } = (body ?? {}) as { stacks?: unknown };
+  if (!Array.isArray(stacks)) {
+    throw new Error('stacks must be an array');
+  }
+
+  const ids = new Set<string>();
+  return stacks.map((value, index) => {
+    const stack = (value ?? {}) as Record<string, unknown>;
+    const field = `stacks[${index}]`;
+
+    if (typeof stack.id !== 'string' || !STACK_ID_PATTERN.test(stack.id)) {
+      throw new Error(`${field}.id must be 1-32 lowercase letters, digits or dashes`);
+    }
+    if (ids.has(stack.id)) {
+      throw new Error(`${field}.id "${stack.id}" is used more than once`);
+    }
+    ids.add(stack.id);
+
+    if (typeof stack.name !== 'string' || !stack.name.trim()) {
+      throw new Error(`${field}.name is required`);
+    }
+    if (!SUPPORTED_NETWORKS.includes(stack.network as BitcoinNetwork)) {
+      throw new Error(`${field}.network must be one of ${SUPPORTED_NETWORKS.join(', ')}`);
+    }
+    if (!MODES.includes(stack.mode as SetupMode)) {
+      throw new Error(`${field}.mode must be one of ${MODES.join(', ')}`);
+    }
+    const miningMode = stack.miningMode ?? 'pool';
+    if (!MINING_MODES.includes(miningMode as MiningMode)) {
+      throw new Error(`${field}.miningMode must be one of ${MINING_MODES.join(', ')}`);
+    }
+
+    const mode = stack.mode as SetupMode;
+    if (mode === 'jd' && !stack.jdcUrl) {
+      throw new Error(`${field}.jdcUrl is required in JD mode`);
+    }
+
+    return {
+      id: stack.id,
+      name: stack.name.trim(),
+      network: stack.network as BitcoinNetwork,
+      mode,
+      miningMode: miningMode as MiningMode,
+      translatorUrl: parseUrl(stack.translatorUrl, `${field}.translatorUrl`),
+      jdcUrl: mode === 'jd' ? parseUrl(stack.jdcUrl, `${field}.jdcUrl`) : null,
+    };
+  });
+}
+
+export function createStackRegistry(filePath: string) {
+  async function get(): Promise<RemoteStack[]> {
+    try {
+      return parseRemoteStacks(JSON.parse(await fs.readFile(filePath, 'utf-8')));
+    } catch {
+      return [];
+    }
+  }
+
+  async function save(stacks: RemoteStack[]): Promise<void> {
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    await fs.writeFile(filePath, JSON.stringify({ stacks }, null, 2));
+  }
+
+  return { get, save };
+}
+
+export type StackRegistry = ReturnType<typeof createStackRegistry>;
+
+export function getStackEndpoints(stack: RemoteStack): MonitoringEndpoints {
+  return stack.jdcUrl
+    ? { translator: stack.translatorUrl, jdc: stack.jdcUrl }
+    : { translator: stack.translatorUrl };
+}
+
+export interface StackMonitor {
+  stack: RemoteStack;
+  eventLog: EventLog;
+  alerts: AlertEngine;
+  // Set by each sample; null until the first one.
+  latest: StackSummary | null;
+}
+
+/**
+ * Per-stack event logs and alert engines under `dir`, kept in step with the
+ * registry by `sync`.
+ */
+export function createStackMonitors(dir: string) {
+  const monitors = new Map<string, StackMonitor>();
+
+  function sync(stacks: RemoteStack[]): StackMonitor[] {
+    for (const id of monitors.keys()) {
+      if (!stacks.some((stack) => stack.id === id)) monitors.delete(id);
+    }
+
+    return stacks.map((stack) => {
+      const existing = monitors.get(stack.id);
+      // Stacks edited in place start from a fresh baseline; their event
+      // history is keyed by id and survives.
+      if (existing && JSON.stringify(existing.stack) === JSON.stringify(stack)) {
+        return existing;
+      }
+
+      const monitor: StackMonitor = {
+        stack,
+        eventLog: createEventLog(path.join(dir, stack.id, 'events.json')),
+        alerts: createAlertEngine(),
+        latest: null,
+      };
+      monitors.set(stack.id, monitor);
+      return monitor;
+    });
+  }
+
+  return {
+    sync,
+    get: (id: string) => monitors.get(id) ?? null,
+    list: () => [...monitors.values()],
+  };
+}
+
+export type StackMonitors = ReturnType<typeof createStackMonitors>;
@@ -0,0 +1,36 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { createStateStore, normalizeSavedState } from './state.js';
+import type { SetupData } from './types.js';
+
+test('older state files without shouldBeRunning keep running when configured', () => {
+  assert.equal(normalizeSavedState({ configured: true }).shouldBeRunning, true);
+  assert.equal(normalizeSavedState({}).shouldBeRunning, false);
+  assert.equal(normalizeSavedState({}).mode, null);
+});
+
+test('saves, loads and clears the setup state, notifying on every change', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-state-'));
+  let changes = 0;
+  const store = createStateStore(path.join(dir, 'config', 'state.json'), { onChange: () => changes++ });
+
+  assert.equal((await store.load()).configured, false);
+
+  const data = { miningMode: 'pool', mode: 'no-jd' } as SetupData;
+  await store.save(data, false);
+  assert.deepEqual(await store.load(), {
+    configured: true,
+    miningMode: 'pool',
+    mode: 'no-jd',
+    data,
+    shouldBeRunning: false,
+  });
+
+  await store.clear();
+  await store.clear();
+  assert.equal((await store.load()).configured, false);
+  assert.equal(changes, 3);
+});
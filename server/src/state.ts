@@ -0,0 +1,91 @@
+/**
+ * The saved setup state: which stack is configured and whether it should be
+ * running. Stored as JSON in CONFIG_DIR/state.json.
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import { normalizeBitcoinCoreVersion } from '@sv2-ui/shared';
+import type { SetupData } from './types.js';
+
+export type SavedState = {
+  configured: boolean;
+  miningMode: 'solo' | 'pool' | null;
+  mode: 'jd' | 'no-jd' | null;
+  data: SetupData | null;
+  shouldBeRunning: boolean;
+};
+
+export interface StateStoreOptions {
+  // Called after the state is saved or cleared.
+  onChange?: () => void;
+}
+
+export function getDefaultState(): SavedState {
+  return { configured: false, miningMode: null, mode: null, data: null, shouldBeRunning: false };
+}
+
+export function normalizeSetupBitcoinCoreVersion(data: SetupData | null): SetupData | null {
+  if (!data?.bitcoin) {
+    return data;
+  }
+
+  return {
+    ...data,
+    bitcoin: {
+      ...data.bitcoin,
+      core_version: normalizeBitcoinCoreVersion(data.bitcoin.core_version),
+    },
+  };
+}
+
+export function normalizeSavedState(state: Partial<SavedState>): SavedState {
+  const configured = state.configured ?? false;
+  return {
+    configured,
+    miningMode: state.miningMode ?? null,
+    mode: state.mode ?? null,
+    data: normalizeSetupBitcoinCoreVersion(state.data ?? null),
+    shouldBeRunning: state.shouldBeRunning ?? configured,
+  };
+}
+
+export function createStateStore(filePath: string, { onChange }: StateStoreOptions = {}) {
+  async function load(): Promise<SavedState> {
+    try {
+      const content = await fs.readFile(filePath, 'utf-8');
+      return normalizeSavedState(JSON.parse(content) as Partial<SavedState>);
+    } catch {
+      return getDefaultState();
+    }
+  }
+
+  async function save(data: SetupData, shouldBeRunning = true): Promise<void> {
+    const normalizedData = normalizeSetupBitcoinCoreVersion(data) ?? data;
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    await fs.writeFile(filePath, JSON.stringify({
+      configured: true,
+      miningMode: normalizedData.miningMode,
+      mode: normalizedData.mode,
+      data: normalizedData,
+      shouldBeRunning,
+    }, null, 2));
+    onChange?.();
+  }
+
+  /**
+   * Forget the setup, as if the server had never been configured.
+   */
+  async function clear(): Promise<void> {
+    try {
+      await fs.unlink(filePath);
+    } catch {
+      // File might not exist, that's fine
+    }
+    onChange?.();
+  }
+
+  return { load, save, clear };
+}
+
+export type StateStore = ReturnType<typeof createStateStore>;
@@ -0,0 +1,85 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import type { AddressInfo } from 'node:net';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import zlib from 'node:zlib';
+import express from 'express';
+import {
+  createStaticAssetHandler,
+  etagMatches,
+  getCacheControl,
+  loadAssetManifest,
+  negotiateEncoding,
+  parseHeadlessMode,
+} from './static-assets.js';
+
+test('prefers brotli, then gzip, honoring q=0', () => {
+  assert.equal(negotiateEncoding('gzip, deflate, br', ['br', 'gzip']), 'br');
+  assert.equal(negotiateEncoding('gzip, br;q=0', ['br', 'gzip']), 'gzip');
+  assert.equal(negotiateEncoding('*', ['gzip']), 'gzip');
+  assert.equal(negotiateEncoding('identity', ['br', 'gzip']), null);
+  assert.equal(negotiateEncoding(undefined, ['br', 'gzip']), null);
+  assert.equal(negotiateEncoding('br', []), null);
+});
+
+test('matches If-None-Match lists and weak validators', () => {
+  assert.equal(etagMatches('"abc", "def"', '"def"'), true);
+  assert.equal(etagMatches('W/"abc"', '"abc"'), true);
+  assert.equal(etagMatches('*', '"abc"'), true);
+  assert.equal(etagMatches('"abc"', '"abc-br"'), false);
+  assert.equal(etagMatches(undefined, '"abc"'), false);
+});
+
+test('only fingerprinted assets are immutable', () => {
+  assert.match(getCacheControl('assets/index-3f9a.js'), /immutable/);
+  assert.equal(getCacheControl('sv2-logo.svg'), 'no-cache');
+});
+
+test('a missing manifest falls back to an empty one', () => {
+  assert.deepEqual(loadAssetManifest(path.join(os.tmpdir(), 'sv2-ui-no-such-dir')), {});
+});
+
+test('serves precompressed variants with ETags and 304s', async (t) => {
+  const uiDir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-static-'));
+  const content = 'console.log("sv2");\n'.repeat(100);
+  await fs.mkdir(path.join(uiDir, 'assets'));
+  await fs.writeFile(path.join(uiDir, 'assets/app.js'), content);
+  await fs.writeFile(path.join(uiDir, 'assets/app.js.br'), zlib.brotliCompressSync(content));
+
+  const app = express();
+  app.use(createStaticAssetHandler(uiDir, {
+    'assets/app.js': { etag: 'hash', size: content.length, encodings: ['br'] },
+  }));
+  app.use((_req, res) => res.status(404).end());
+
+  const server = app.listen(0);
+  t.after(() => server.close());
+  const baseUrl = `http://127.0.0.1:${(server.address() as AddressInfo).port}`;
+
+  const plain = await fetch(`${baseUrl}/assets/app.js`, { headers: { 'Accept-Encoding': 'identity' } });
+  assert.equal(plain.status, 200);
+  assert.equal(plain.headers.get('etag'), '"hash"');
+  assert.match(plain.headers.get('cache-control') ?? '', /immutable/);
+  assert.equal(await plain.text(), content);
+
+  // fetch() advertises br and transparently decodes it.
+  const compressed = await fetch(`${baseUrl}/assets/app.js`);
+  assert.equal(compressed.headers.get('content-encoding'), 'br');
+  assert.equal(compressed.headers.get('etag'), '"hash-br"');
+  assert.equal(await compressed.text(), content);
+
+  const revalidated = await fetch(`${baseUrl}/assets/app.js`, { headers: { 'If-None-Match': '"hash-br"' } });
+  assert.equal(revalidated.status, 304);
+
+  const unlisted = await fetch(`${baseUrl}/assets/other.js`);
+  assert.equal(unlisted.status, 404);
+});
+
+test('parseHeadlessMode reads --headless or HEADLESS', () => {
+  assert.equal(parseHeadlessMode([], {}), false);
+  assert.equal(parseHeadlessMode(['--headless'], {}), true);
+  assert.equal(parseHeadlessMode([], { HEADLESS: 'TRUE' }), true);
+  assert.throws(() => parseHeadlessMode([], { HEADLESS: 'yes' }), /HEADLESS must be true or false/);
+});
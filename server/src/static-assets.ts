@@ -0,0 +1,135 @@
+/**
+ * Static file serving for the built UI.
+ *
+ * The Vite build writes .br/.gz variants plus asset-manifest.json with content
+ * hashes (see precompressAssets in vite.config.ts). Files listed there are
+ * served with strong ETags, conditional 304s, and the best precompressed
+ * variant the client accepts. Anything missing from the manifest (e.g. a
+ * build without one) falls through to express.static.
+ *
+ * With --headless (or HEADLESS=true) no UI is served at all: the mining box
+ * only runs the JSON APIs, pollers, history and exporters, for a dashboard
+ * hosted elsewhere (e.g. a federation of sites, see federation.ts).
+ */
+
+import fs from 'fs';
+import path from 'path';
+import type { RequestHandler } from 'express';
+
+export const ASSET_MANIFEST_FILE = 'asset-manifest.json';
+
+// Vite fingerprints everything under assets/, so those URLs never change content.
+const IMMUTABLE_CACHE_CONTROL = 'public, max-age=31536000, immutable';
+const REVALIDATE_CACHE_CONTROL = 'no-cache';
+
+export interface AssetManifestEntry {
+  etag: string;
+  size: number;
+  encodings: string[];
+}
+
+export type AssetManifest = Record<string, AssetManifestEntry>;
+
+const ENCODING_EXTENSIONS: Record<string, string> = { br: '.br', gzip: '.gz' };
+
+export function parseHeadlessMode(argv: string[] = process.argv.slice(2), env: NodeJS.ProcessEnv = process.env): boolean {
+  if (argv.includes('--headless')) return true;
+  const value = env.HEADLESS?.trim().toLowerCase();
+  if (!value || value === 'false') return false;
+  if (value === 'true') return true;
+  throw new Error(`HEADLESS must be true or false, got "${env.HEADLESS}"`);
+}
+
+export function loadAssetManifest(uiDir: string): AssetManifest {
+  try {
+    return JSON.parse(fs.readFileSync(path.join(uiDir, ASSET_MANIFEST_FILE), 'utf-8')) as AssetManifest;
+  } catch {
+    return {};
+  }
+}
+
+/**
+ * Pick the preferred encoding (brotli, then gzip) the client accepts.
+ */
+export function negotiateEncoding(acceptEncoding: string | undefined, available: string[]): string | null {
+  if (!acceptEncoding) return null;
+
+  const accepted = new Map<string, number>();
+  for (const part of acceptEncoding.split(',')) {
+    const [name, ...params] = part.trim().toLowerCase().split(';');
+    const q = params.map((param) => param.trim()).find((param) => param.startsWith('q='));
+    accepted.set(name, q ? Number(q.slice(2)) : 1);
+  }
+
+  for (const encoding of ['br', 'gzip']) {
+    if (!available.includes(encoding)) continue;
+    const q = accepted.get(encoding) ?? accepted.get('*') ?? 0;
+    if (q > 0) return encoding;
+  }
+
+  return null;
+}
+
+export function etagMatches(ifNoneMatch: string | undefined, etag: string): boolean {
+  if (!ifNoneMatch) return false;
+  if (ifNoneMatch.trim() === '*') return true;
+
+  return ifNoneMatch
+    .split(',')
+    .map((candidate) => candidate.trim().replace(/^W\//, ''))
+    .includes(etag);
+}
+
+export function getCacheControl(assetPath: string): string {
+  return assetPath.startsWith('assets/') ? IMMUTABLE_CACHE_CONTROL : REVALIDATE_CACHE_CONTROL;
+}
+
+function toAssetPath(requestPath: string): string | null {
+  try {
+    const decoded = decodeURIComponent(requestPath).replace(/^\/+/, '');
+    const normalized = path.posix.normalize(decoded);
+    return normalized.startsWith('..') || normalized.includes('\0') ? null : normalized;
+  } catch {
+    return null;
+  }
+}
+
+export function createStaticAssetHandler(uiDir: string, manifest = loadAssetManifest(uiDir)): RequestHandler {
+  return (req, res, next) => {
+    if (req.method !== 'GET' && req.method !== 'HEAD') return next();
+
+    const assetPath = toAssetPath(req.path);
+    // index.html is rendered per request by the SPA fallback.
+    const entry = assetPath && assetPath !== 'index.html' ? manifest[assetPath] : undefined;
+    if (!assetPath || !entry) return next();
+
+    const encoding = negotiateEncoding(req.headers['accept-encoding'], entry.encodings);
+    // Each representation needs its own validator.
+    const etag = `"${entry.etag}${encoding ? `-${encoding}` : ''}"`;
+
+    res.set({
+      ETag: etag,
+      'Cache-Control': getCacheControl(assetPath),
+      Vary: 'Accept-Encoding',
+    });
+
+    if (etagMatches(req.headers['if-none-match'], etag)) {
+      return res.status(304).end();
+    }
+
+    const filePath = path.join(uiDir, assetPath) + (encoding ? ENCODING_EXTENSIONS[encoding] : '');
+    fs.stat(filePath, (error, stats) => {
+      if (error || !stats.isFile()) return next();
+
+      res.type(path.extname(assetPath));
+      if (encoding) res.set('Content-Encoding', encoding);
+      res.set('Content-Length', String(stats.size));
+
+      if (req.method === 'HEAD') return res.end();
+
+      fs.createReadStream(filePath)
+        .on('error', next)
+        .pipe(res);
+    });
+  };
+}
@@ -0,0 +1,115 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import { test } from 'node:test';
+import type { HealthReport } from './health.js';
+import { fetchServerStatus, formatServerStatus, parseStatusArgs } from './status.js';
+import type { StackSummary } from './summary.js';
+
+const summary: StackSummary = {
+  generatedAt: '2026-01-01T00:00:00.000Z',
+  miningMode: 'pool',
+  mode: 'no-jd',
+  network: null,
+  poolName: 'Braiins Pool',
+  online: true,
+  hashrate: 1.5e15,
+  workers: 12,
+  sharesAccepted: 990,
+  sharesSubmitted: 1000,
+  sharesRejected: 10,
+  bestDifficulty: 2.5e12,
+  blocksFound: 0,
+  uptimeSecs: 93_784,
+};
+
+const health: HealthReport = {
+  status: 'degraded',
+  checkedAt: '2026-01-01T00:00:00.000Z',
+  docker: true,
+  services: [
+    {
+      id: 'translator',
+      service: 'translator',
+      label: 'Translator',
+      stack: null,
+      reachable: true,
+      httpStatus: 200,
+      latencyMs: 3,
+      lastSuccessAt: '2026-01-01T00:00:00.000Z',
+      checkedAt: '2026-01-01T00:00:00.000Z',
+      error: null,
+    },
+    {
+      id: 'rig-2/translator',
+      service: 'translator',
+      label: 'Translator',
+      stack: { id: 'rig-2', name: 'Rig 2' },
+      reachable: false,
+      httpStatus: null,
+      latencyMs: null,
+      lastSuccessAt: '2025-12-31T23:55:00.000Z',
+      checkedAt: '2026-01-01T00:00:00.000Z',
+      error: 'fetch failed',
+    },
+  ],
+  sv1: null,
+  sites: [],
+};
+
+test('parseStatusArgs defaults to the local server and reads the token from the environment', () => {
+  assert.deepEqual(parseStatusArgs([], {}), { url: 'http://localhost:3001', token: null, json: false });
+  assert.deepEqual(parseStatusArgs(['--json'], { LISTEN: '0.0.0.0:8080', BASE_PATH: '/sv2/', SV2_UI_TOKEN: 'key' }), {
+    url: 'http://localhost:8080/sv2',
+    token: 'key',
+    json: true,
+  });
+  assert.deepEqual(parseStatusArgs(['https://rig.example/', '--token', 'other'], { SV2_UI_TOKEN: 'key' }), {
+    url: 'https://rig.example',
+    token: 'other',
+    json: false,
+  });
+  assert.throws(() => parseStatusArgs(['rig.example'], {}), /must be http\(s\)/);
+  assert.throws(() => parseStatusArgs(['--watch'], {}), /Unknown status option "--watch"/);
+});
+
+test('the summary and health report are fetched with the token, a 503 health report included', async (t) => {
+  const server = http.createServer((req, res) => {
+    if (req.headers.authorization !== 'Bearer key') {
+      res.writeHead(401, { 'Content-Type': 'application/json' }).end(JSON.stringify({ error: 'Authentication required' }));
+      return;
+    }
+    const [status, body] = req.url === '/sv2/api/summary' ? [200, summary] : [503, { ...health, status: 'down' }];
+    res.writeHead(status, { 'Content-Type': 'application/json' }).end(JSON.stringify(body));
+  }).listen(0, '127.0.0.1');
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const url = `http://127.0.0.1:${(server.address() as AddressInfo).port}/sv2`;
+
+  const status = await fetchServerStatus({ url, token: 'key', json: false });
+  assert.equal(status.summary.workers, 12);
+  assert.equal(status.health.status, 'down');
+
+  await assert.rejects(fetchServerStatus({ url, token: null, json: false }), /HTTP 401: Authentication required \(pass an API key/);
+  await assert.rejects(fetchServerStatus({ url: 'http://127.0.0.1:1', token: null, json: false }), /^Error: Cannot reach sv2-ui at http:\/\/127\.0\.0\.1:1/);
+});
+
+test('the status is printed as tables', () => {
+  assert.equal(formatServerStatus({ url: 'http://localhost:3001', summary, health }, new Date('2026-01-01T00:00:00.000Z')), [
+    'sv2-ui at http://localhost:3001 (pool, no JD, Braiins Pool)',
+    '',
+    'Stack            online',
+    'Health           degraded',
+    'Hashrate         1.50 PH/s',
+    'Miners           12',
+    'Shares           990 accepted of 1000, 10 rejected (1.00%)',
+    'Best difficulty  2.50T',
+    'Blocks found     0',
+    'Uptime           1d 2h 3m',
+    '',
+    'Service           State  Latency  Detail',
+    'Translator        up     3ms',
+    'Rig 2 Translator  down   -        fetch failed, last seen 5m ago',
+    '',
+  ].join('\n'));
+});
@@ -0,0 +1,163 @@
+/**
+ * `status [url]`: the state of a running sv2-ui server in the terminal.
+ *
+ * The command asks the server at `url` (by default the local one, from
+ * LISTEN, PORT and BASE_PATH) for /api/summary and /api/v1/health, and
+ * prints hashrate, miners, shares and the health of each service as a
+ * table. It's what the dashboard shows, for rigs only reachable over SSH.
+ *
+ * Both endpoints are open to viewers. When the server only lets some
+ * clients in, an API key or the admin token goes in `--token` or
+ * SV2_UI_TOKEN.
+ */
+
+import { formatDifficulty, formatHashrate, formatUptime } from '@sv2-ui/shared';
+import { normalizeBasePath } from './base-path.js';
+import type { HealthReport } from './health.js';
+import { formatListenUrl, parseListenAddresses } from './listen.js';
+import type { StackSummary } from './summary.js';
+
+export interface StatusArgs {
+  url: string;
+  token: string | null;
+  json: boolean;
+}
+
+export interface ServerStatus {
+  url: string;
+  summary: StackSummary;
+  health: HealthReport;
+}
+
+const REQUEST_TIMEOUT_MS = 10_000;
+
+function getLocalUrl(env: NodeJS.ProcessEnv): string {
+  const [address] = parseListenAddresses(env.LISTEN, Number(env.PORT || 3001));
+  // A server listening on every interface is reached on loopback.
+  const host = !address.host || address.host === '0.0.0.0' || address.host === '::' ? undefined : address.host;
+  return `${formatListenUrl({ ...address, host })}${normalizeBasePath(env.BASE_PATH)}`;
+}
+
+export function parseStatusArgs(argv: string[], env: NodeJS.ProcessEnv = process.env, command = 'status'): StatusArgs {
+  let url: string | null = null;
+  let token = env.SV2_UI_TOKEN?.trim() || null;
+  let json = false;
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i];
+    if (arg === '--json') {
+      json = true;
+    } else if (arg === '--token') {
+      token = argv[++i]?.trim() || null;
+      if (!token) throw new Error('--token requires a value');
+    } else if (arg.startsWith('--')) {
+      throw new Error(`Unknown ${command} option "${arg}"`);
+    } else if (url) {
+      throw new Error(`${command} takes one URL, got "${url}" and "${arg}"`);
+    } else {
+      if (!/^https?:\/\//.test(arg)) throw new Error(`The sv2-ui URL must be http(s), got "${arg}"`);
+      url = arg;
+    }
+  }
+  return { url: (url ?? getLocalUrl(env)).replace(/\/+$/, ''), token, json };
+}
+
+/**
+ * Request `requestPath` from the server at `url`. Statuses other than
+ * `accept` are thrown as errors, with the server's message.
+ */
+export async function requestServer(url: string, requestPath: string, token: string | null, accept: number[] = [200]): Promise<Response> {
+  let response: Response;
+  try {
+    response = await fetch(`${url}${requestPath}`, {
+      headers: { Accept: 'application/json', ...(token ? { Authorization: `Bearer ${token}` } : {}) },
+      signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+    });
+  } catch (error) {
+    const cause = error instanceof Error && error.cause instanceof Error ? error.cause : error;
+    throw new Error(`Cannot reach sv2-ui at ${url}: ${cause instanceof Error ? cause.message : String(cause)}`);
+  }
+
+  if (!accept.includes(response.status)) {
+    const body = await response.json().catch(() => null) as { error?: unknown } | null;
+    const reason = typeof body?.error === 'string' ? `: ${body.error}` : '';
+    const hint = response.status === 401 || response.status === 403 ? ' (pass an API key with --token or SV2_UI_TOKEN)' : '';
+    throw new Error(`${requestPath} returned HTTP ${response.status}${reason}${hint}`);
+  }
+  return response;
+}
+
+export async function requestServerJson<T>(url: string, requestPath: string, token: string | null, accept: number[] = [200]): Promise<T> {
+  const body = await (await requestServer(url, requestPath, token, accept)).json().catch(() => null) as T | null;
+  if (!body) throw new Error(`${requestPath} did not return JSON; is ${url} an sv2-ui server?`);
+  return body;
+}
+
+export async function fetchServerStatus({ url, token }: StatusArgs): Promise<ServerStatus> {
+  const [summary, health] = await Promise.all([
+    requestServerJson<StackSummary>(url, '/api/summary', token),
+    // The health report comes with a 503 when the stack is down.
+    requestServerJson<HealthReport>(url, '/api/v1/health', token, [200, 503]),
+  ]);
+  return { url, summary, health };
+}
+
+function table(rows: string[][]): string[] {
+  const widths = rows[0].map((_, column) => Math.max(...rows.map((row) => row[column].length)));
+  return rows.map((row) => row.map((cell, column) => cell.padEnd(widths[column])).join('  ').trimEnd());
+}
+
+function describeDown(error: string | null, lastSuccessAt: string | null, now: Date): string {
+  const seen = lastSuccessAt
+    ? `, last seen ${formatUptime(Math.max(0, Math.round((now.getTime() - Date.parse(lastSuccessAt)) / 1000)))} ago`
+    : '';
+  return `${error ?? 'unreachable'}${seen}`;
+}
+
+export function formatServerStatus({ url, summary, health }: ServerStatus, now = new Date()): string {
+  const mode = [
+    summary.miningMode,
+    summary.mode === 'jd' ? 'JD' : summary.mode === 'no-jd' ? 'no JD' : null,
+    summary.poolName,
+  ].filter(Boolean).join(', ');
+  const rejectedShare = summary.sharesSubmitted > 0 ? ` (${(summary.sharesRejected / summary.sharesSubmitted * 100).toFixed(2)}%)` : '';
+
+  const lines = [
+    `sv2-ui at ${url}${mode ? ` (${mode})` : ''}`,
+    '',
+    ...table([
+      ['Stack', summary.online ? 'online' : 'offline'],
+      ['Health', health.status],
+      ['Hashrate', formatHashrate(summary.hashrate)],
+      ['Miners', String(summary.workers)],
+      ['Shares', `${summary.sharesAccepted} accepted of ${summary.sharesSubmitted}, ${summary.sharesRejected} rejected${rejectedShare}`],
+      ['Best difficulty', formatDifficulty(summary.bestDifficulty)],
+      ['Blocks found', String(summary.blocksFound)],
+      ['Uptime', formatUptime(summary.uptimeSecs)],
+    ]),
+  ];
+
+  const services = [
+    ...health.services.map((service) => [
+      service.stack ? `${service.stack.name} ${service.label}` : service.label,
+      service.reachable ? 'up' : 'down',
+      service.latencyMs === null ? '-' : `${service.latencyMs}ms`,
+      service.reachable ? '' : describeDown(service.error, service.lastSuccessAt, now),
+    ]),
+    ...health.sv1 ? [[
+      'SV1 port',
+      health.sv1.ok ? 'up' : 'down',
+      '-',
+      health.sv1.ok ? '' : health.sv1.steps.find((step) => !step.ok)?.error ?? 'self-test failed',
+    ]] : [],
+    ...(health.sites ?? []).map((site) => [
+      `Site ${site.name}`,
+      site.reachable ? site.status ?? 'up' : 'down',
+      site.latencyMs === null ? '-' : `${site.latencyMs}ms`,
+      site.reachable ? '' : describeDown(site.error, site.lastSuccessAt, now),
+    ]),
+  ];
+  if (services.length > 0) lines.push('', ...table([['Service', 'State', 'Latency', 'Detail'], ...services]));
+  if (!health.docker) lines.push('', 'Docker is not reachable from the server.');
+
+  return `${lines.join('\n')}\n`;
+}
@@ -0,0 +1,89 @@
+import assert from 'node:assert/strict';
+import { EventEmitter } from 'node:events';
+import { test } from 'node:test';
+import { applyJsonPatch, createSummaryStream, diffJson } from './summary-stream.js';
+
+function connect(stream: ReturnType<typeof createSummaryStream>, role = 'admin') {
+  const req = Object.assign(new EventEmitter(), { role });
+  const written: string[] = [];
+  const res = { set: () => {}, flushHeaders: () => {}, write: (chunk: string) => written.push(chunk) };
+  stream.handler(req as never, res as never, () => {});
+  const events = () => written
+    .filter((chunk) => chunk.startsWith('event:'))
+    .map((chunk) => {
+      const [, type, data] = /^event: (\w+)\ndata: (.*)\n\n$/.exec(chunk)!;
+      return { type, data: JSON.parse(data) };
+    });
+  return { req, events };
+}
+
+test('diffs documents into JSON Patch operations that rebuild them', () => {
+  const before = { hashrate: 1, poolName: 'Pool', freshness: { jdc: { ageMs: 100 } }, miners: [{ id: 'a', shares: 1 }], 'a/b': 1 };
+  const after = { hashrate: 2, poolName: null, freshness: { jdc: { ageMs: 100 } }, miners: [{ id: 'a', shares: 2 }], 'a/b': 2, blocks: 1 };
+  const ops = diffJson(before, after);
+  assert.deepEqual(ops, [
+    { op: 'replace', path: '/hashrate', value: 2 },
+    { op: 'replace', path: '/poolName', value: null },
+    { op: 'replace', path: '/miners/0/shares', value: 2 },
+    { op: 'replace', path: '/a~1b', value: 2 },
+    { op: 'add', path: '/blocks', value: 1 },
+  ]);
+  assert.deepEqual(applyJsonPatch(before, ops), after);
+  assert.equal(before.hashrate, 1);
+
+  const grown = { miners: [{ id: 'a' }, { id: 'b' }] };
+  assert.deepEqual(diffJson({ miners: [{ id: 'a' }], gone: true }, grown), [
+    { op: 'remove', path: '/gone' },
+    { op: 'replace', path: '/miners', value: grown.miners },
+  ]);
+  assert.deepEqual(diffJson(grown, structuredClone(grown)), []);
+});
+
+test('sends a full summary, then patches, then a full summary again', () => {
+  let clock = 0;
+  const stream = createSummaryStream({ resyncIntervalMs: 60_000, now: () => clock });
+  const early = connect(stream);
+  stream.publish({ hashrate: 1, workers: 2, note: undefined });
+
+  clock = 10_000;
+  stream.publish({ hashrate: 1, workers: 2 });
+  const late = connect(stream);
+  clock = 20_000;
+  stream.publish({ hashrate: 3, workers: 2 });
+  clock = 60_000;
+  stream.publish({ hashrate: 3, workers: 4 });
+
+  assert.deepEqual(early.events(), [
+    { type: 'summary', data: { version: 1, summary: { hashrate: 1, workers: 2 } } },
+    // Nothing changed at version 2, so the patch for 3 applies to 1.
+    { type: 'patch', data: { base: 1, version: 3, ops: [{ op: 'replace', path: '/hashrate', value: 3 }] } },
+    { type: 'summary', data: { version: 4, summary: { hashrate: 3, workers: 4 } } },
+  ]);
+  // Its first summary was only 50s ago.
+  assert.deepEqual(late.events().map(({ type, data }) => [type, data.version]), [['summary', 2], ['patch', 3], ['patch', 4]]);
+
+  assert.equal(stream.size(), 2);
+  early.req.emit('close');
+  assert.equal(stream.size(), 1);
+  late.req.emit('close');
+
+  // Nothing was published while nobody listened, so the next client waits for a poll.
+  const next = connect(stream);
+  assert.deepEqual(next.events(), []);
+  next.req.emit('close');
+});
+
+test('diffs what each client may see', () => {
+  const stream = createSummaryStream({
+    redact: (req, summary) => ((req as unknown as { role: string }).role === 'viewer' ? { ...summary as object, user: '[redacted]' } : summary),
+  });
+  const admin = connect(stream);
+  const viewer = connect(stream, 'viewer');
+  stream.publish({ hashrate: 1, user: 'alice' });
+  stream.publish({ hashrate: 1, user: 'bob' });
+
+  assert.deepEqual(admin.events()[1].data.ops, [{ op: 'replace', path: '/user', value: 'bob' }]);
+  assert.equal(viewer.events().length, 1);
+  admin.req.emit('close');
+  viewer.req.emit('close');
+});
@@ -0,0 +1,204 @@
+/**
+ * Server-sent events stream of the /api/summary document, for dashboards on
+ * slow or metered links (e.g. a cellular modem at a remote site).
+ *
+ * A client gets the whole summary when it connects, then after every poll
+ * only what changed, as a JSON Patch (RFC 6902) against the last document it
+ * was sent. It's sent the whole summary again every `resyncIntervalMs`, so a
+ * client that applied a patch wrongly doesn't drift for long.
+ *
+ *   event: summary  data: {"version":1,"summary":{...}}
+ *   event: patch    data: {"base":1,"version":2,"ops":[{"op":"replace","path":"/hashrate","value":1.2e12}]}
+ *
+ * A patch only applies to the document with version `base`. A client that
+ * sees any other base should reconnect for a fresh summary.
+ */
+
+import type { Request, RequestHandler, Response } from 'express';
+
+export type JsonPatchOperation =
+  | { op: 'add'; path: string; value: unknown }
+  | { op: 'replace'; path: string; value: unknown }
+  | { op: 'remove'; path: string };
+
+export const DEFAULT_SUMMARY_RESYNC_INTERVAL_MS = 60_000;
+// Comment lines keep idle connections from being dropped by proxies.
+const HEARTBEAT_INTERVAL_MS = 25_000;
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+function escapePointer(key: string): string {
+  return key.replace(/~/g, '~0').replace(/\//g, '~1');
+}
+
+function unescapePointer(segment: string): string {
+  return segment.replace(/~1/g, '/').replace(/~0/g, '~');
+}
+
+function isJsonEqual(a: unknown, b: unknown): boolean {
+  if (a === b) return true;
+  if (Array.isArray(a) && Array.isArray(b)) {
+    return a.length === b.length && a.every((item, index) => isJsonEqual(item, b[index]));
+  }
+  if (isPlainObject(a) && isPlainObject(b)) {
+    const keys = Object.keys(a);
+    return keys.length === Object.keys(b).length && keys.every((key) => key in b && isJsonEqual(a[key], b[key]));
+  }
+  return false;
+}
+
+/**
+ * The operations turning `from` into `to`. Objects are compared key by key,
+ * and arrays item by item while their length stays the same; an array that
+ * grew or shrank is replaced whole, since miner lists reorder as often as
+ * they grow.
+ */
+export function diffJson(from: unknown, to: unknown, path = ''): JsonPatchOperation[] {
+  if (isJsonEqual(from, to)) return [];
+
+  if (isPlainObject(from) && isPlainObject(to)) {
+    const ops: JsonPatchOperation[] = [];
+    for (const key of Object.keys(from)) {
+      if (!(key in to)) ops.push({ op: 'remove', path: `${path}/${escapePointer(key)}` });
+    }
+    for (const [key, value] of Object.entries(to)) {
+      const child = `${path}/${escapePointer(key)}`;
+      if (key in from) ops.push(...diffJson(from[key], value, child));
+      else ops.push({ op: 'add', path: child, value });
+    }
+    return ops;
+  }
+
+  if (Array.isArray(from) && Array.isArray(to) && from.length === to.length) {
+    return to.flatMap((item, index) => diffJson(from[index], item, `${path}/${index}`));
+  }
+
+  return [{ op: 'replace', path, value: to }];
+}
+
+/**
+ * Apply operations from diffJson to a copy of `document`.
+ */
+export function applyJsonPatch<T>(document: T, ops: JsonPatchOperation[]): T {
+  let result: unknown = structuredClone(document);
+  for (const operation of ops) {
+    if (operation.path === '') {
+      if (operation.op === 'remove') throw new Error('Cannot remove the whole document');
+      result = structuredClone(operation.value);
+      continue;
+    }
+
+    const segments = operation.path.slice(1).split('/').map(unescapePointer);
+    const key = segments.pop()!;
+    let parent: unknown = result;
+    for (const segment of segments) {
+      parent = (parent as Record<string, unknown> | null)?.[segment];
+    }
+    if (typeof parent !== 'object' || parent === null) throw new Error(`No such path ${operation.path}`);
+
+    const container = parent as Record<string, unknown>;
+    if (operation.op === 'remove') {
+      if (Array.isArray(container)) container.splice(Number(key), 1);
+      else delete container[key];
+    } else if (operation.op === 'add' && Array.isArray(container)) {
+      container.splice(key === '-' ? container.length : Number(key), 0, structuredClone(operation.value));
+    } else {
+      container[key] = structuredClone(operation.value);
+    }
+  }
+  return result as T;
+}
+
+export interface SummaryStreamOptions {
+  // What a given client may see of the summary, e.g. redactForRequest.
+  redact?: (req: Request, summary: unknown) => unknown;
+  resyncIntervalMs?: number;
+  now?: () => number;
+}
+
+interface SummaryStreamClient {
+  req: Request;
+  res: Response;
+  // What this client was last sent, once it has had a full summary.
+  sent: { version: number; summary: unknown; fullAt: number } | null;
+}
+
+function formatEvent(type: 'summary' | 'patch', data: unknown): string {
+  return `event: ${type}\ndata: ${JSON.stringify(data)}\n\n`;
+}
+
+export function createSummaryStream({
+  redact = (_req, summary) => summary,
+  resyncIntervalMs = DEFAULT_SUMMARY_RESYNC_INTERVAL_MS,
+  now = Date.now,
+}: SummaryStreamOptions = {}) {
+  const clients = new Set<SummaryStreamClient>();
+  let latest: unknown = null;
+  let version = 0;
+
+  function sendFull(client: SummaryStreamClient, summary: unknown): void {
+    client.sent = { version, summary, fullAt: now() };
+    client.res.write(formatEvent('summary', { version, summary }));
+  }
+
+  function send(client: SummaryStreamClient): void {
+    const summary = redact(client.req, latest);
+    const { sent } = client;
+    if (!sent || now() - sent.fullAt >= resyncIntervalMs) {
+      sendFull(client, summary);
+      return;
+    }
+
+    const ops = diffJson(sent.summary, summary);
+    // Nothing changed for this client; its base stays where it was.
+    if (ops.length === 0) return;
+    client.res.write(formatEvent('patch', { base: sent.version, version, ops }));
+    client.sent = { ...sent, version, summary };
+  }
+
+  /**
+   * Send the summary from the latest poll to every client.
+   */
+  function publish(summary: unknown): void {
+    // Drops undefined fields, which would otherwise show up as patches, and
+    // keeps later changes to `summary` out of what clients were sent.
+    latest = JSON.parse(JSON.stringify(summary));
+    version += 1;
+    for (const client of clients) send(client);
+  }
+
+  /**
+   * GET handler that keeps the response open as an event stream.
+   */
+  const handler: RequestHandler = (req, res) => {
+    res.set({
+      'Content-Type': 'text/event-stream',
+      'Cache-Control': 'no-cache',
+      Connection: 'keep-alive',
+      // Stop nginx and friends from buffering the stream.
+      'X-Accel-Buffering': 'no',
+    });
+    res.flushHeaders();
+    res.write(': connected\n\n');
+
+    const client: SummaryStreamClient = { req, res, sent: null };
+    clients.add(client);
+    // Otherwise the first summary comes with the next poll.
+    if (latest !== null) send(client);
+    const heartbeat = setInterval(() => res.write(': heartbeat\n\n'), HEARTBEAT_INTERVAL_MS);
+
+    req.on('close', () => {
+      clearInterval(heartbeat);
+      clients.delete(client);
+      // Nothing is published without clients, so the next one would be
+      // sent an old summary.
+      if (clients.size === 0) latest = null;
+    });
+  };
+
+  return { publish, handler, size: () => clients.size };
+}
+
+export type SummaryStream = ReturnType<typeof createSummaryStream>;
@@ -0,0 +1,102 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import type { StackData } from './monitoring.js';
+import { summarizeStack } from './summary.js';
+
+const NOW = new Date('2026-01-01T00:00:00.000Z');
+
+function serverChannel(overrides: Partial<{ best_diff: number; blocks_found: number; shares_acknowledged: number; shares_submitted: number; shares_rejected: number }>) {
+  return {
+    channel_id: 1,
+    user_identity: 'bc1qexample.rig1',
+    target_hex: '00',
+    best_diff: 0,
+    blocks_found: 0,
+    shares_acknowledged: 0,
+    shares_submitted: 0,
+    shares_rejected: 0,
+    shares_rejected_by_reason: {},
+    ...overrides,
+  };
+}
+
+test('summarizes a translator-only stack from sv1 clients and server channels', () => {
+  const data: StackData = {
+    mode: 'no-jd',
+    upstream: 'translator',
+    global: { uptime_secs: 120, sv1_clients: { total_clients: 2, total_hashrate: 0 } },
+    serverChannels: {
+      extended_channels: [serverChannel({ best_diff: 500, shares_acknowledged: 9, shares_submitted: 10, shares_rejected: 1 })],
+      standard_channels: [serverChannel({ best_diff: 800, blocks_found: 1, shares_acknowledged: 3, shares_submitted: 3 })],
+      total_extended: 1,
+      total_standard: 1,
+    },
+    sv1Clients: {
+      total: 2,
+      items: [
+        { client_id: 1, authorized_worker_name: 'rig1', user_identity: 'a', hashrate: 100, target_hex: '00' },
+        { client_id: 2, authorized_worker_name: 'rig2', user_identity: 'b', hashrate: 50, target_hex: '00' },
+      ],
+    },
+    sv2Clients: null,
+  };
+
+  assert.deepEqual(summarizeStack(data, { miningMode: 'pool', poolName: 'Braiins Pool', now: NOW }), {
+    generatedAt: NOW.toISOString(),
+    miningMode: 'pool',
+    mode: 'no-jd',
+    network: null,
+    poolName: 'Braiins Pool',
+    online: true,
+    hashrate: 150,
+    workers: 2,
+    sharesAccepted: 12,
+    sharesSubmitted: 13,
+    sharesRejected: 1,
+    bestDifficulty: 800,
+    blocksFound: 1,
+    uptimeSecs: 120,
+  });
+});
+
+test('uses sv2 client channels for hashrate, best difficulty, and blocks in JD mode', () => {
+  const clientChannel = (best_diff: number, blocks_found: number) => ({
+    channel_id: 1,
+    user_identity: 'worker',
+    nominal_hashrate: 1,
+    target_hex: '00',
+    expected_shares_per_minute: 1,
+    best_diff,
+    blocks_found,
+    shares_accepted: 0,
+    shares_rejected: 0,
+    shares_rejected_by_reason: {},
+  });
+
+  const data: StackData = {
+    mode: 'jd',
+    upstream: 'jdc',
+    global: { uptime_secs: 60, sv2_clients: { total_clients: 1, total_channels: 2, total_hashrate: 1e12 } },
+    serverChannels: { extended_channels: [serverChannel({ best_diff: 1e9 })], standard_channels: [], total_extended: 1, total_standard: 0 },
+    sv1Clients: null,
+    sv2Clients: [{ client_id: 1, extended_channels: [clientChannel(40, 0)], standard_channels: [clientChannel(70, 2)] }],
+  };
+
+  const summary = summarizeStack(data, { miningMode: 'solo', poolName: null, now: NOW });
+  assert.equal(summary.hashrate, 1e12);
+  assert.equal(summary.workers, 2);
+  assert.equal(summary.bestDifficulty, 70);
+  assert.equal(summary.blocksFound, 2);
+});
+
+test('reports an offline stack with zeroed stats when monitoring is unreachable', () => {
+  const summary = summarizeStack(
+    { mode: 'no-jd', upstream: 'translator', global: null, serverChannels: null, sv1Clients: null, sv2Clients: null },
+    { miningMode: null, poolName: null, now: NOW },
+  );
+
+  assert.equal(summary.online, false);
+  assert.equal(summary.hashrate, 0);
+  assert.equal(summary.workers, 0);
+  assert.equal(summary.uptimeSecs, 0);
+});
@@ -0,0 +1,105 @@
+/**
+ * Compact, identity-free summary of the running stack.
+ *
+ * The numbers mirror what the dashboard shows (see UnifiedDashboard.tsx) so
+ * anything built on top of them — share links, badges, notifications — agrees
+ * with the UI.
+ */
+
+import { NETWORK_LABELS } from '@sv2-ui/shared';
+import type { BitcoinNetwork, MiningMode, SetupMode } from '@sv2-ui/shared';
+import type { StackData } from './monitoring.js';
+
+export interface StackSummary {
+  generatedAt: string;
+  miningMode: MiningMode | null;
+  mode: SetupMode | null;
+  // Only known in JD mode, from the configured node; null otherwise.
+  network: BitcoinNetwork | null;
+  poolName: string | null;
+  online: boolean;
+  hashrate: number;
+  workers: number;
+  sharesAccepted: number;
+  sharesSubmitted: number;
+  sharesRejected: number;
+  bestDifficulty: number;
+  blocksFound: number;
+  uptimeSecs: number;
+}
+
+export interface SummaryContext {
+  miningMode: MiningMode | null;
+  poolName: string | null;
+  network?: BitcoinNetwork | null;
+  now?: Date;
+}
+
+interface ChannelStats {
+  best_diff: number;
+  blocks_found: number;
+}
+
+function getClientChannels(data: StackData): ChannelStats[] {
+  if (data.mode === 'jd') {
+    return (data.sv2Clients ?? []).flatMap((client) => [
+      ...client.extended_channels,
+      ...client.standard_channels,
+    ]);
+  }
+
+  return data.serverChannels
+    ? [...data.serverChannels.extended_channels, ...data.serverChannels.standard_channels]
+    : [];
+}
+
+export function summarizeStack(data: StackData, { miningMode, poolName, network = null, now = new Date() }: SummaryContext): StackSummary {
+  const isJdMode = data.mode === 'jd';
+
+  const sv1Items = data.sv1Clients?.items ?? [];
+  const sv1Hashrate = sv1Items.reduce((sum, client) => sum + (client.hashrate ?? 0), 0);
+  const hashrate = isJdMode
+    ? (data.global?.sv2_clients?.total_hashrate || 0)
+    : (data.global?.sv1_clients?.total_hashrate || sv1Hashrate);
+  const workers = isJdMode
+    ? (data.global?.sv2_clients?.total_channels ?? 0)
+    : (data.sv1Clients?.total ?? 0);
+
+  const serverChannels = data.serverChannels
+    ? [...data.serverChannels.extended_channels, ...data.serverChannels.standard_channels]
+    : [];
+  const clientChannels = getClientChannels(data);
+
+  return {
+    generatedAt: now.toISOString(),
+    miningMode,
+    mode: data.mode,
+    network,
+    poolName,
+    online: data.global !== null,
+    hashrate,
+    workers,
+    sharesAccepted: serverChannels.reduce((sum, channel) => sum + channel.shares_acknowledged, 0),
+    sharesSubmitted: serverChannels.reduce((sum, channel) => sum + channel.shares_submitted, 0),
+    sharesRejected: serverChannels.reduce((sum, channel) => sum + channel.shares_rejected, 0),
+    bestDifficulty: clientChannels.reduce((best, channel) => Math.max(best, channel.best_diff), 0),
+    blocksFound: clientChannels.reduce((sum, channel) => sum + channel.blocks_found, 0),
+    uptimeSecs: data.global?.uptime_secs ?? 0,
+  };
+}
+
+/**
+ * Format a summary field for display. Fields may have been replaced by
+ * REDACT_PATHS, so only numbers go through the formatter.
+ */
+export function formatSummaryValue(value: unknown, formatValue: (value: number) => string = String): string {
+  return typeof value === 'number' ? formatValue(value) : String(value ?? '—');
+}
+
+/**
+ * Label for stacks on a test network, so their numbers aren't mistaken for
+ * mainnet ones. Null on mainnet or when the network isn't known.
+ */
+export function getTestNetworkLabel(network: BitcoinNetwork | null | undefined): string | null {
+  return network && network !== 'mainnet' ? NETWORK_LABELS[network] : null;
+}
@@ -0,0 +1,96 @@
+import assert from 'node:assert/strict';
+import net from 'node:net';
+import type { AddressInfo } from 'node:net';
+import { test } from 'node:test';
+import { checkSv1Listener, classifySv1Listener, probeSv1Stratum } from './sv1-listener.js';
+
+test('distinguishes a listener with no miners from a dead one', () => {
+  assert.equal(classifySv1Listener(false, null), 'down');
+  assert.equal(classifySv1Listener(false, 3), 'down');
+  assert.equal(classifySv1Listener(true, 0), 'idle');
+  assert.equal(classifySv1Listener(true, 2), 'active');
+  // Port is up but the count is unknown: don't claim it's idle.
+  assert.equal(classifySv1Listener(true, null), 'active');
+});
+
+test('probes a real listening port', async (t) => {
+  const server = net.createServer((socket) => socket.end());
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  t.after(() => server.close());
+  const { port } = server.address() as AddressInfo;
+
+  const status = await checkSv1Listener({ host: '127.0.0.1', port, countConnections: async () => 0 });
+  assert.equal(status.state, 'idle');
+  assert.equal(status.accepting, true);
+  assert.equal(status.connections, 0);
+});
+
+test('reports a closed port as down with the connection error', async () => {
+  const server = net.createServer();
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  const { port } = server.address() as AddressInfo;
+  await new Promise((resolve) => server.close(resolve));
+
+  const status = await checkSv1Listener({
+    host: '127.0.0.1',
+    port,
+    countConnections: async () => {
+      throw new Error('monitoring API unreachable');
+    },
+  });
+  assert.equal(status.state, 'down');
+  assert.equal(status.connections, null);
+  assert.match(status.error ?? '', /ECONNREFUSED/);
+});
+
+/**
+ * A stratum server answering subscribe, and authorize with `authorized`.
+ */
+async function startStratum(authorized: boolean | null) {
+  const server = net.createServer((socket) => {
+    let buffer = '';
+    socket.on('data', (chunk) => {
+      buffer += chunk.toString();
+      const lines = buffer.split('\n');
+      buffer = lines.pop() ?? '';
+      for (const line of lines) {
+        const { id, method } = JSON.parse(line);
+        if (method === 'mining.subscribe') {
+          socket.write(`${JSON.stringify({ id: null, method: 'mining.set_difficulty', params: [1] })}\n`);
+          socket.write(`${JSON.stringify({ id, result: [[['mining.notify', '1']], '00000001', 8], error: null })}\n`);
+        } else if (authorized !== null) {
+          socket.write(`${JSON.stringify({ id, result: authorized, error: authorized ? null : [24, 'Unauthorized worker', null] })}\n`);
+        }
+      }
+    });
+  });
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  return { server, port: (server.address() as AddressInfo).port };
+}
+
+test('the stratum self-test subscribes and authorizes like a miner', async (t) => {
+  const { server, port } = await startStratum(true);
+  t.after(() => server.close());
+
+  const result = await probeSv1Stratum('127.0.0.1', port, { user: 'alice.rig1' });
+  assert.equal(result.ok, true);
+  assert.deepEqual(result.steps.map(({ step, ok }) => [step, ok]), [['connect', true], ['subscribe', true], ['authorize', true]]);
+  assert.equal(result.extranonce1, '00000001');
+  assert.equal(result.extranonce2Size, 8);
+});
+
+test('the stratum self-test reports rejected and unanswered requests', async (t) => {
+  const rejecting = await startStratum(false);
+  const silent = await startStratum(null);
+  t.after(() => {
+    rejecting.server.close();
+    silent.server.close();
+  });
+
+  const rejected = await probeSv1Stratum('127.0.0.1', rejecting.port);
+  assert.equal(rejected.ok, false);
+  assert.equal(rejected.steps.at(-1)?.error, 'Authorize rejected: Unauthorized worker');
+
+  const unanswered = await probeSv1Stratum('127.0.0.1', silent.port, { timeoutMs: 100 });
+  assert.deepEqual(unanswered.steps.at(-1), { ...unanswered.steps.at(-1), step: 'authorize', ok: false, error: 'No reply within 100ms' });
+});
@@ -0,0 +1,260 @@
+/**
+ * Tracks whether the Translator's SV1 listen port accepts connections and how
+ * many miners are attached.
+ *
+ * "Listener up but zero miners" is its own state: the usual cause of a
+ * dashboard showing 0 hashrate is miners pointed at the wrong host or port,
+ * not a broken Translator.
+ *
+ * The stratum self-test goes one step further and talks to the port as a
+ * miner would, with mining.subscribe and mining.authorize. A listener that
+ * accepts connections but never answers a subscribe (for instance while the
+ * Translator has no upstream channel) fails it.
+ */
+
+import net from 'net';
+
+export type Sv1ListenerState = 'down' | 'idle' | 'active';
+
+export interface Sv1ListenerStatus {
+  state: Sv1ListenerState;
+  accepting: boolean;
+  // null when the Translator monitoring API couldn't be reached.
+  connections: number | null;
+  host: string;
+  port: number;
+  checkedAt: string;
+  error?: string;
+}
+
+export type Sv1ProbeStepName = 'connect' | 'subscribe' | 'authorize';
+
+export interface Sv1ProbeStep {
+  step: Sv1ProbeStepName;
+  ok: boolean;
+  durationMs: number;
+  detail: string | null;
+  error: string | null;
+}
+
+export interface Sv1ProbeResult {
+  ok: boolean;
+  host: string;
+  port: number;
+  user: string;
+  steps: Sv1ProbeStep[];
+  // From the subscribe reply.
+  extranonce1: string | null;
+  extranonce2Size: number | null;
+  checkedAt: string;
+}
+
+const PROBE_TIMEOUT_MS = 2000;
+const STRATUM_TIMEOUT_MS = 5000;
+export const DEFAULT_PROBE_USER = 'sv2-ui-probe';
+
+/**
+ * Open and immediately close a TCP connection.
+ */
+export function probeTcpPort(host: string, port: number, timeoutMs = PROBE_TIMEOUT_MS): Promise<void> {
+  return new Promise((resolve, reject) => {
+    const socket = net.connect({ host, port });
+    socket.setTimeout(timeoutMs);
+    socket.once('connect', () => {
+      socket.destroy();
+      resolve();
+    });
+    socket.once('timeout', () => {
+      socket.destroy();
+      reject(new Error(`Timed out after ${timeoutMs}ms`));
+    });
+    socket.once('error', (error) => {
+      socket.destroy();
+      reject(error);
+    });
+  });
+}
+
+interface StratumMessage {
+  id?: number | string | null;
+  method?: string;
+  result?: unknown;
+  error?: unknown;
+}
+
+/**
+ * Newline-delimited JSON-RPC replies by id; notifications such as
+ * mining.set_difficulty are skipped.
+ */
+function createStratumReader(socket: net.Socket) {
+  let buffer = '';
+  const replies = new Map<number, StratumMessage>();
+  const waiters = new Map<number, { resolve: (message: StratumMessage) => void; reject: (error: Error) => void }>();
+  let failure: Error | null = null;
+
+  const fail = (error: Error) => {
+    failure ??= error;
+    for (const waiter of waiters.values()) waiter.reject(failure);
+    waiters.clear();
+  };
+
+  socket.on('data', (chunk: Buffer) => {
+    buffer += chunk.toString('utf-8');
+    let index;
+    while ((index = buffer.indexOf('\n')) !== -1) {
+      const line = buffer.slice(0, index).trim();
+      buffer = buffer.slice(index + 1);
+      if (!line) continue;
+      let message: StratumMessage;
+      try {
+        message = JSON.parse(line) as StratumMessage;
+      } catch {
+        fail(new Error(`Not a stratum reply: ${line.slice(0, 80)}`));
+        return;
+      }
+      if (typeof message.id !== 'number' || message.method) continue;
+      const waiter = waiters.get(message.id);
+      if (waiter) {
+        waiters.delete(message.id);
+        waiter.resolve(message);
+      } else {
+        replies.set(message.id, message);
+      }
+    }
+  });
+  socket.on('error', fail);
+  socket.on('close', () => fail(new Error('Connection closed by the Translator')));
+
+  return {
+    reply(id: number): Promise<StratumMessage> {
+      const reply = replies.get(id);
+      if (reply) return Promise.resolve(reply);
+      if (failure) return Promise.reject(failure);
+      return new Promise((resolve, reject) => waiters.set(id, { resolve, reject }));
+    },
+  };
+}
+
+function formatStratumError(error: unknown): string {
+  // [code, message, traceback] in most pools, an object in some.
+  if (Array.isArray(error) && typeof error[1] === 'string') return error[1];
+  if (error && typeof error === 'object' && 'message' in error) return String(error.message);
+  return JSON.stringify(error);
+}
+
+export interface Sv1ProbeOptions {
+  user?: string;
+  timeoutMs?: number;
+  now?: () => Date;
+}
+
+/**
+ * Subscribe and authorize on the SV1 port like a miner, reporting each step.
+ */
+export async function probeSv1Stratum(
+  host: string,
+  port: number,
+  { user = DEFAULT_PROBE_USER, timeoutMs = STRATUM_TIMEOUT_MS, now = () => new Date() }: Sv1ProbeOptions = {},
+): Promise<Sv1ProbeResult> {
+  const result: Sv1ProbeResult = {
+    ok: false,
+    host,
+    port,
+    user,
+    steps: [],
+    extranonce1: null,
+    extranonce2Size: null,
+    checkedAt: now().toISOString(),
+  };
+
+  async function step(name: Sv1ProbeStepName, run: () => Promise<string>): Promise<boolean> {
+    const started = Date.now();
+    let timer: NodeJS.Timeout | undefined;
+    try {
+      const detail = await Promise.race([
+        run(),
+        new Promise<never>((_, reject) => {
+          timer = setTimeout(() => reject(new Error(`No reply within ${timeoutMs}ms`)), timeoutMs);
+        }),
+      ]);
+      result.steps.push({ step: name, ok: true, durationMs: Date.now() - started, detail, error: null });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      result.steps.push({ step: name, ok: false, durationMs: Date.now() - started, detail: null, error: message });
+      return false;
+    } finally {
+      clearTimeout(timer);
+    }
+  }
+
+  const socket = net.connect({ host, port });
+  socket.on('error', () => undefined);
+  try {
+    const connected = await step('connect', () => new Promise((resolve, reject) => {
+      socket.once('connect', () => resolve(`Connected to ${host}:${port}`));
+      socket.once('error', reject);
+    }));
+    if (!connected) return result;
+    const reader = createStratumReader(socket);
+
+    const send = (id: number, method: string, params: unknown[]) => {
+      socket.write(`${JSON.stringify({ id, method, params })}\n`);
+      return reader.reply(id);
+    };
+
+    const subscribed = await step('subscribe', async () => {
+      const reply = await send(1, 'mining.subscribe', [DEFAULT_PROBE_USER]);
+      if (reply.error) throw new Error(`Subscribe rejected: ${formatStratumError(reply.error)}`);
+      const [, extranonce1, extranonce2Size] = Array.isArray(reply.result) ? reply.result : [];
+      if (typeof extranonce1 !== 'string' || typeof extranonce2Size !== 'number') {
+        throw new Error('Subscribe reply has no extranonce');
+      }
+      result.extranonce1 = extranonce1;
+      result.extranonce2Size = extranonce2Size;
+      return `Extranonce1 ${extranonce1}, extranonce2 size ${extranonce2Size}`;
+    });
+    if (!subscribed) return result;
+
+    result.ok = await step('authorize', async () => {
+      const reply = await send(2, 'mining.authorize', [user, 'x']);
+      if (reply.error || reply.result !== true) {
+        throw new Error(`Authorize rejected${reply.error ? `: ${formatStratumError(reply.error)}` : ''}`);
+      }
+      return `Authorized as ${user}`;
+    });
+    return result;
+  } finally {
+    socket.destroy();
+  }
+}
+
+export function classifySv1Listener(accepting: boolean, connections: number | null): Sv1ListenerState {
+  if (!accepting) return 'down';
+  return connections === 0 ? 'idle' : 'active';
+}
+
+export interface Sv1ListenerCheck {
+  host: string;
+  port: number;
+  countConnections: () => Promise<number>;
+  now?: () => Date;
+}
+
+export async function checkSv1Listener({ host, port, countConnections, now = () => new Date() }: Sv1ListenerCheck): Promise<Sv1ListenerStatus> {
+  const [probe, count] = await Promise.allSettled([probeTcpPort(host, port), countConnections()]);
+  const accepting = probe.status === 'fulfilled';
+  const connections = count.status === 'fulfilled' ? count.value : null;
+
+  return {
+    state: classifySv1Listener(accepting, connections),
+    accepting,
+    connections,
+    host,
+    port,
+    checkedAt: now().toISOString(),
+    ...(probe.status === 'rejected'
+      ? { error: probe.reason instanceof Error ? probe.reason.message : String(probe.reason) }
+      : {}),
+  };
+}
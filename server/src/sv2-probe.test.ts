@@ -0,0 +1,138 @@
+import assert from 'node:assert/strict';
+import net from 'node:net';
+import { test } from 'node:test';
+
+import { decodeBase58Check } from './base58.js';
+import { generateAuthorityKeypair } from './keygen.js';
+import { createEllSwiftKeypair, decodeEllSwift, ellSwiftEcdh, signSchnorr } from './secp256k1.js';
+import {
+  createByteReader,
+  encodeFrame,
+  encryptFrame,
+  getCertificateMessage,
+  MSG_SETUP_CONNECTION,
+  MSG_SETUP_CONNECTION_ERROR,
+  MSG_SETUP_CONNECTION_SUCCESS,
+  NoiseState,
+  probeSv2,
+  readEncryptedFrame,
+} from './sv2-probe.js';
+
+const authority = generateAuthorityKeypair();
+const NOW = new Date('2026-06-01T00:00:00.000Z');
+const NOW_SECS = NOW.getTime() / 1000;
+
+interface Responder {
+  validFrom?: number;
+  notValidAfter?: number;
+  // SetupConnection.Error code to reject with.
+  rejectWith?: string;
+}
+
+/**
+ * The responder side of the handshake, as a pool runs it.
+ */
+async function startPool({ validFrom = NOW_SECS - 60, notValidAfter = NOW_SECS + 3600, rejectWith }: Responder = {}) {
+  const setups: Buffer[] = [];
+  const respond = async (socket: net.Socket) => {
+    const reader = createByteReader(socket);
+    const noise = new NoiseState();
+    const initiatorKey = await reader.read(64);
+    noise.mixHash(initiatorKey);
+    noise.decryptAndHash(Buffer.alloc(0));
+
+    const ephemeral = createEllSwiftKeypair();
+    const staticKey = createEllSwiftKeypair();
+    noise.mixHash(ephemeral.publicKey);
+    noise.mixKey(ellSwiftEcdh(initiatorKey, ephemeral.publicKey, ephemeral.secretKey, false));
+    const encryptedStatic = noise.encryptAndHash(staticKey.publicKey);
+    noise.mixKey(ellSwiftEcdh(initiatorKey, staticKey.publicKey, staticKey.secretKey, false));
+
+    const fields = Buffer.alloc(10);
+    fields.writeUInt32LE(validFrom, 2);
+    fields.writeUInt32LE(notValidAfter, 6);
+    const message = getCertificateMessage(0, validFrom, notValidAfter, decodeEllSwift(staticKey.publicKey));
+    const signature = signSchnorr(message, decodeBase58Check(authority.secretKey)!);
+    socket.write(Buffer.concat([ephemeral.publicKey, encryptedStatic, noise.encryptAndHash(Buffer.concat([fields, signature]))]));
+
+    const [fromClient, toClient] = noise.split();
+    const { msgType, payload } = await readEncryptedFrame(reader, fromClient);
+    assert.equal(msgType, MSG_SETUP_CONNECTION);
+    setups.push(payload);
+    if (rejectWith) {
+      const code = Buffer.from(rejectWith);
+      socket.write(encryptFrame(toClient, encodeFrame(MSG_SETUP_CONNECTION_ERROR, Buffer.concat([Buffer.alloc(4), Buffer.from([code.length]), code]))));
+    } else {
+      socket.write(encryptFrame(toClient, encodeFrame(MSG_SETUP_CONNECTION_SUCCESS, Buffer.from([2, 0, 4, 0, 0, 0]))));
+    }
+  };
+  // The probe hangs up early when a step fails.
+  const server = net.createServer((socket) => {
+    respond(socket).catch(() => socket.destroy());
+  });
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  return { port: (server.address() as net.AddressInfo).port, setups, close: () => server.close() };
+}
+
+test('probeSv2 completes the handshake and SetupConnection against a pool', async () => {
+  const pool = await startPool();
+  try {
+    const result = await probeSv2({ host: '127.0.0.1', port: pool.port, authorityPubkey: authority.publicKey, protocol: 'mining' }, { now: () => NOW });
+    assert.equal(result.ok, true);
+    assert.deepEqual(result.steps.map(({ step, ok }) => [step, ok]), [
+      ['connect', true],
+      ['handshake', true],
+      ['certificate', true],
+      ['setup-connection', true],
+    ]);
+    assert.equal(result.certificate?.notValidAfter, '2026-06-01T01:00:00.000Z');
+    assert.equal(result.usedVersion, 2);
+    // Protocol 0 (mining), versions 2..2, REQUIRES_VERSION_ROLLING, then the endpoint host.
+    assert.deepEqual([...pool.setups[0].subarray(0, 10)], [0, 2, 0, 2, 0, 4, 0, 0, 0, 9]);
+  } finally {
+    pool.close();
+  }
+});
+
+test('probeSv2 reports a certificate from another authority and expired certificates', async () => {
+  const pool = await startPool({ notValidAfter: NOW_SECS - 1 });
+  try {
+    const target = { host: '127.0.0.1', port: pool.port, authorityPubkey: generateAuthorityKeypair().publicKey, protocol: 'mining' as const };
+    const wrongKey = await probeSv2(target, { now: () => NOW });
+    assert.equal(wrongKey.ok, false);
+    assert.deepEqual(wrongKey.steps.at(-1), {
+      ...wrongKey.steps.at(-1),
+      step: 'certificate',
+      ok: false,
+      error: 'Certificate is not signed by this authority public key',
+    });
+
+    const expired = await probeSv2({ ...target, authorityPubkey: authority.publicKey }, { now: () => NOW });
+    assert.match(expired.steps.at(-1)?.error ?? '', /^Certificate expired at 2026-05-31T23:59:59/);
+  } finally {
+    pool.close();
+  }
+});
+
+test('probeSv2 stops at the step that fails', async () => {
+  const rejecting = await startPool({ rejectWith: 'unsupported-protocol' });
+  const silent = net.createServer(() => {});
+  await new Promise<void>((resolve) => silent.listen(0, '127.0.0.1', resolve));
+  const silentPort = (silent.address() as net.AddressInfo).port;
+  try {
+    const target = { host: '127.0.0.1', authorityPubkey: authority.publicKey, protocol: 'job-declaration' as const };
+    const rejected = await probeSv2({ ...target, port: rejecting.port }, { now: () => NOW });
+    assert.equal(rejected.steps.at(-1)?.error, 'Rejected with "unsupported-protocol"');
+
+    const quiet = await probeSv2({ ...target, port: silentPort }, { timeoutMs: 200 });
+    assert.deepEqual(quiet.steps.map(({ step, ok }) => [step, ok]), [['connect', true], ['handshake', false]]);
+    assert.match(quiet.steps[1].error ?? '', /is this an SV2 port/);
+
+    silent.close();
+    const refused = await probeSv2({ ...target, port: silentPort });
+    assert.deepEqual(refused.steps.map(({ step, ok }) => [step, ok]), [['connect', false]]);
+    await assert.rejects(probeSv2({ ...target, port: 1, authorityPubkey: 'nope' }), /base58check/);
+  } finally {
+    rejecting.close();
+  }
+});
@@ -0,0 +1,462 @@
+/**
+ * An SV2 connection test against a pool or JDS, for telling "wrong address",
+ * "not an SV2 port", "wrong authority key" and "pool refuses us" apart
+ * without reading the Translator's or JDC's logs.
+ *
+ * The probe connects over TCP, runs the initiator side of the Noise NX
+ * handshake (Noise_NX_Secp256k1+EllSwift_ChaChaPoly_SHA256), checks the
+ * server's certificate against the authority public key, then sends
+ * SetupConnection and reads the reply. Each step is reported with its
+ * outcome and timing; the first failure ends the run.
+ */
+
+import crypto from 'crypto';
+import net from 'net';
+import { decodeBase58Check } from './base58.js';
+import { createEllSwiftKeypair, decodeEllSwift, ellSwiftEcdh, verifySchnorr } from './secp256k1.js';
+
+export type Sv2Protocol = 'mining' | 'job-declaration';
+export type Sv2ProbeStepName = 'connect' | 'handshake' | 'certificate' | 'setup-connection';
+
+export interface Sv2ProbeTarget {
+  host: string;
+  port: number;
+  // Base58check authority public key, as in the Translator and JDC configs.
+  authorityPubkey: string;
+  protocol: Sv2Protocol;
+  // SetupConnection flags; defaults per protocol (see DEFAULT_FLAGS).
+  flags?: number;
+}
+
+export interface Sv2ProbeStep {
+  step: Sv2ProbeStepName;
+  ok: boolean;
+  durationMs: number;
+  detail: string | null;
+  error: string | null;
+}
+
+export interface Sv2Certificate {
+  version: number;
+  validFrom: string;
+  notValidAfter: string;
+}
+
+export interface Sv2ProbeResult {
+  ok: boolean;
+  host: string;
+  port: number;
+  protocol: Sv2Protocol;
+  steps: Sv2ProbeStep[];
+  certificate: Sv2Certificate | null;
+  // From SetupConnection.Success.
+  usedVersion: number | null;
+  flags: number | null;
+}
+
+export interface Sv2ProbeOptions {
+  timeoutMs?: number;
+  now?: () => Date;
+  // End the run successfully once this step has passed.
+  stopAfter?: Sv2ProbeStepName;
+}
+
+const PROTOCOL_NAME = 'Noise_NX_Secp256k1+EllSwift_ChaChaPoly_SHA256';
+const DEFAULT_TIMEOUT_MS = 5000;
+const ELLSWIFT_SIZE = 64;
+const MAC_SIZE = 16;
+const SIGNATURE_MESSAGE_SIZE = 74;
+// e, encrypted s and the encrypted certificate.
+const HANDSHAKE_REPLY_SIZE = ELLSWIFT_SIZE + ELLSWIFT_SIZE + MAC_SIZE + SIGNATURE_MESSAGE_SIZE + MAC_SIZE;
+const FRAME_HEADER_SIZE = 6;
+const MAX_CHUNK_SIZE = 65535;
+
+const PROTOCOL_IDS: Record<Sv2Protocol, number> = { mining: 0, 'job-declaration': 1 };
+// REQUIRES_VERSION_ROLLING for mining; no optional features for job declaration.
+export const DEFAULT_FLAGS: Record<Sv2Protocol, number> = { mining: 0b100, 'job-declaration': 0 };
+
+export const MSG_SETUP_CONNECTION = 0x00;
+export const MSG_SETUP_CONNECTION_SUCCESS = 0x01;
+export const MSG_SETUP_CONNECTION_ERROR = 0x02;
+
+/**
+ * The 32-byte x-only key behind a base58check authority public key; null if it isn't one.
+ */
+export function parseAuthorityPubkey(value: string): Buffer | null {
+  const decoded = decodeBase58Check(value);
+  // A 2-byte version prefix, then the key.
+  if (!decoded || decoded.length !== 34) return null;
+  return decoded.subarray(2);
+}
+
+function sha256(...data: Uint8Array[]): Buffer {
+  const hash = crypto.createHash('sha256');
+  for (const chunk of data) hash.update(chunk);
+  return hash.digest();
+}
+
+function hmac(key: Uint8Array, ...data: Uint8Array[]): Buffer {
+  const mac = crypto.createHmac('sha256', key);
+  for (const chunk of data) mac.update(chunk);
+  return mac.digest();
+}
+
+function hkdf2(chainingKey: Uint8Array, inputKeyMaterial: Uint8Array): [Buffer, Buffer] {
+  const tempKey = hmac(chainingKey, inputKeyMaterial);
+  const first = hmac(tempKey, Buffer.from([0x01]));
+  return [first, hmac(tempKey, first, Buffer.from([0x02]))];
+}
+
+/**
+ * ChaChaPoly with the Noise nonce: 4 zero bytes and a little-endian 64-bit counter.
+ */
+export class CipherState {
+  private nonce = 0n;
+
+  constructor(private readonly key: Buffer) {}
+
+  private nextNonce(): Buffer {
+    const nonce = Buffer.alloc(12);
+    nonce.writeBigUInt64LE(this.nonce++, 4);
+    return nonce;
+  }
+
+  encrypt(plaintext: Uint8Array, ad: Uint8Array = Buffer.alloc(0)): Buffer {
+    const cipher = crypto.createCipheriv('chacha20-poly1305', this.key, this.nextNonce(), { authTagLength: MAC_SIZE });
+    cipher.setAAD(ad, { plaintextLength: plaintext.length });
+    return Buffer.concat([cipher.update(plaintext), cipher.final(), cipher.getAuthTag()]);
+  }
+
+  decrypt(ciphertext: Uint8Array, ad: Uint8Array = Buffer.alloc(0)): Buffer {
+    if (ciphertext.length < MAC_SIZE) throw new Error('Ciphertext shorter than its MAC');
+    const decipher = crypto.createDecipheriv('chacha20-poly1305', this.key, this.nextNonce(), { authTagLength: MAC_SIZE });
+    decipher.setAAD(ad, { plaintextLength: ciphertext.length - MAC_SIZE });
+    decipher.setAuthTag(ciphertext.subarray(ciphertext.length - MAC_SIZE));
+    return Buffer.concat([decipher.update(ciphertext.subarray(0, ciphertext.length - MAC_SIZE)), decipher.final()]);
+  }
+}
+
+/**
+ * The Noise symmetric state (h, ck and the handshake key), shared by both roles.
+ */
+export class NoiseState {
+  private h: Buffer;
+  private ck: Buffer;
+  private cipher: CipherState | null = null;
+
+  constructor() {
+    this.ck = sha256(Buffer.from(PROTOCOL_NAME));
+    // MixHash of the empty prologue.
+    this.h = sha256(this.ck);
+  }
+
+  mixHash(data: Uint8Array): void {
+    this.h = sha256(this.h, data);
+  }
+
+  mixKey(inputKeyMaterial: Uint8Array): void {
+    const [ck, key] = hkdf2(this.ck, inputKeyMaterial);
+    this.ck = ck;
+    this.cipher = new CipherState(key);
+  }
+
+  encryptAndHash(plaintext: Uint8Array): Buffer {
+    const ciphertext = this.cipher ? this.cipher.encrypt(plaintext, this.h) : Buffer.from(plaintext);
+    this.mixHash(ciphertext);
+    return ciphertext;
+  }
+
+  decryptAndHash(ciphertext: Uint8Array): Buffer {
+    const plaintext = this.cipher ? this.cipher.decrypt(ciphertext, this.h) : Buffer.from(ciphertext);
+    this.mixHash(ciphertext);
+    return plaintext;
+  }
+
+  /**
+   * The transport ciphers: initiator-to-responder first.
+   */
+  split(): [CipherState, CipherState] {
+    const [first, second] = hkdf2(this.ck, Buffer.alloc(0));
+    return [new CipherState(first), new CipherState(second)];
+  }
+}
+
+/**
+ * The 10 bytes of certificate fields and the 32-byte message the authority signs.
+ */
+export function getCertificateMessage(version: number, validFrom: number, notValidAfter: number, staticKey: Uint8Array): Buffer {
+  const fields = Buffer.alloc(10);
+  fields.writeUInt16LE(version, 0);
+  fields.writeUInt32LE(validFrom, 2);
+  fields.writeUInt32LE(notValidAfter, 6);
+  return sha256(fields, staticKey);
+}
+
+export function encodeFrame(msgType: number, payload: Uint8Array): Buffer {
+  if (payload.length > 0xffffff) throw new Error('SV2 payload too large');
+  const header = Buffer.alloc(FRAME_HEADER_SIZE);
+  header.writeUInt16LE(0, 0);
+  header.writeUInt8(msgType, 2);
+  header.writeUIntLE(payload.length, 3, 3);
+  return Buffer.concat([header, payload]);
+}
+
+/**
+ * An encrypted frame: the header and each payload chunk are sealed separately.
+ */
+export function encryptFrame(cipher: CipherState, frame: Buffer): Buffer {
+  const parts = [cipher.encrypt(frame.subarray(0, FRAME_HEADER_SIZE))];
+  const payload = frame.subarray(FRAME_HEADER_SIZE);
+  for (let offset = 0; offset < payload.length; offset += MAX_CHUNK_SIZE - MAC_SIZE) {
+    parts.push(cipher.encrypt(payload.subarray(offset, offset + MAX_CHUNK_SIZE - MAC_SIZE)));
+  }
+  return Buffer.concat(parts);
+}
+
+function encodeStr0255(value: string): Buffer {
+  const bytes = Buffer.from(value, 'utf-8');
+  if (bytes.length > 255) throw new Error('STR0_255 longer than 255 bytes');
+  return Buffer.concat([Buffer.from([bytes.length]), bytes]);
+}
+
+export function encodeSetupConnection({ host, port, protocol, flags }: Sv2ProbeTarget): Buffer {
+  const fixed = Buffer.alloc(9);
+  fixed.writeUInt8(PROTOCOL_IDS[protocol], 0);
+  // min_version and max_version
+  fixed.writeUInt16LE(2, 1);
+  fixed.writeUInt16LE(2, 3);
+  fixed.writeUInt32LE(flags ?? DEFAULT_FLAGS[protocol], 5);
+  const endpointPort = Buffer.alloc(2);
+  endpointPort.writeUInt16LE(port);
+  return Buffer.concat([
+    fixed,
+    encodeStr0255(host),
+    endpointPort,
+    encodeStr0255('sv2-ui'),
+    encodeStr0255(''),
+    encodeStr0255(''),
+    encodeStr0255('sv2-ui-probe'),
+  ]);
+}
+
+/**
+ * Reads exact byte counts off a socket.
+ */
+export function createByteReader(socket: net.Socket) {
+  let buffer = Buffer.alloc(0);
+  let failure: Error | null = null;
+  let waiter: { length: number; resolve: (bytes: Buffer) => void; reject: (error: Error) => void } | null = null;
+
+  const flush = () => {
+    if (!waiter) return;
+    if (buffer.length >= waiter.length) {
+      const { length, resolve } = waiter;
+      waiter = null;
+      const bytes = buffer.subarray(0, length);
+      buffer = buffer.subarray(length);
+      resolve(bytes);
+    } else if (failure) {
+      const { reject } = waiter;
+      waiter = null;
+      reject(failure);
+    }
+  };
+
+  socket.on('data', (chunk: Buffer) => {
+    buffer = Buffer.concat([buffer, chunk]);
+    flush();
+  });
+  socket.on('error', (error) => {
+    failure ??= error;
+    flush();
+  });
+  socket.on('close', () => {
+    failure ??= new Error('Connection closed by the server');
+    flush();
+  });
+
+  return {
+    read(length: number): Promise<Buffer> {
+      return new Promise((resolve, reject) => {
+        waiter = { length, resolve, reject };
+        flush();
+      });
+    },
+  };
+}
+
+/**
+ * Read one encrypted frame; returns its message type and payload.
+ */
+export async function readEncryptedFrame(
+  reader: ReturnType<typeof createByteReader>,
+  cipher: CipherState,
+): Promise<{ msgType: number; payload: Buffer }> {
+  const header = cipher.decrypt(await reader.read(FRAME_HEADER_SIZE + MAC_SIZE));
+  const msgType = header.readUInt8(2);
+  const length = header.readUIntLE(3, 3);
+  const chunks: Buffer[] = [];
+  for (let remaining = length; remaining > 0; remaining -= MAX_CHUNK_SIZE - MAC_SIZE) {
+    const size = Math.min(remaining, MAX_CHUNK_SIZE - MAC_SIZE);
+    chunks.push(cipher.decrypt(await reader.read(size + MAC_SIZE)));
+  }
+  return { msgType, payload: Buffer.concat(chunks) };
+}
+
+function withTimeout<T>(promise: Promise<T>, timeoutMs: number, message: string): Promise<T> {
+  let timer: NodeJS.Timeout;
+  return Promise.race([
+    promise,
+    new Promise<never>((_, reject) => {
+      timer = setTimeout(() => reject(new Error(message)), timeoutMs);
+    }),
+  ]).finally(() => clearTimeout(timer));
+}
+
+function connect(host: string, port: number, timeoutMs: number): Promise<net.Socket> {
+  return new Promise((resolve, reject) => {
+    const socket = net.connect({ host, port });
+    const timer = setTimeout(() => {
+      socket.destroy();
+      reject(new Error(`No TCP connection within ${timeoutMs}ms`));
+    }, timeoutMs);
+    socket.once('connect', () => {
+      clearTimeout(timer);
+      resolve(socket);
+    });
+    socket.once('error', (error) => {
+      clearTimeout(timer);
+      socket.destroy();
+      reject(error);
+    });
+  });
+}
+
+function toIsoSeconds(seconds: number): string {
+  return new Date(seconds * 1000).toISOString();
+}
+
+export async function probeSv2(target: Sv2ProbeTarget, options: Sv2ProbeOptions = {}): Promise<Sv2ProbeResult> {
+  const timeoutMs = options.timeoutMs ?? DEFAULT_TIMEOUT_MS;
+  const now = options.now ?? (() => new Date());
+  const result: Sv2ProbeResult = {
+    ok: false,
+    host: target.host,
+    port: target.port,
+    protocol: target.protocol,
+    steps: [],
+    certificate: null,
+    usedVersion: null,
+    flags: null,
+  };
+
+  // Runs one step; false once it has failed.
+  async function step(name: Sv2ProbeStepName, run: () => Promise<string>): Promise<boolean> {
+    const started = Date.now();
+    try {
+      const detail = await run();
+      result.steps.push({ step: name, ok: true, durationMs: Date.now() - started, detail, error: null });
+      return true;
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      result.steps.push({ step: name, ok: false, durationMs: Date.now() - started, detail: null, error: message });
+      return false;
+    }
+  }
+
+  const authorityKey = parseAuthorityPubkey(target.authorityPubkey);
+  if (!authorityKey) throw new Error('Authority public key must be base58check-encoded');
+
+  const connection: { socket: net.Socket | null } = { socket: null };
+  try {
+    const connected = await step('connect', async () => {
+      const socket = await connect(target.host, target.port, timeoutMs);
+      connection.socket = socket;
+      return `Connected to ${socket.remoteAddress}:${socket.remotePort}`;
+    });
+    const { socket } = connection;
+    if (!connected || !socket || options.stopAfter === 'connect') {
+      result.ok = connected;
+      return result;
+    }
+    const reader = createByteReader(socket);
+
+    const noise = new NoiseState();
+    let staticKey = Buffer.alloc(0);
+    let certificate = Buffer.alloc(0);
+    const handshaken = await step('handshake', async () => {
+      // -> e
+      const ephemeral = createEllSwiftKeypair();
+      noise.mixHash(ephemeral.publicKey);
+      noise.encryptAndHash(Buffer.alloc(0));
+      socket.write(ephemeral.publicKey);
+
+      // <- e, ee, s, es, certificate
+      const reply = await withTimeout(
+        reader.read(HANDSHAKE_REPLY_SIZE),
+        timeoutMs,
+        `No Noise handshake reply within ${timeoutMs}ms; is this an SV2 port?`,
+      );
+      const remoteEphemeral = reply.subarray(0, ELLSWIFT_SIZE);
+      noise.mixHash(remoteEphemeral);
+      noise.mixKey(ellSwiftEcdh(ephemeral.publicKey, remoteEphemeral, ephemeral.secretKey, true));
+      try {
+        const remoteStatic = noise.decryptAndHash(reply.subarray(ELLSWIFT_SIZE, 2 * ELLSWIFT_SIZE + MAC_SIZE));
+        noise.mixKey(ellSwiftEcdh(ephemeral.publicKey, remoteStatic, ephemeral.secretKey, true));
+        certificate = noise.decryptAndHash(reply.subarray(2 * ELLSWIFT_SIZE + MAC_SIZE));
+        staticKey = decodeEllSwift(remoteStatic);
+      } catch {
+        throw new Error('Handshake reply failed to decrypt; the server does not speak SV2 Noise');
+      }
+      return 'Noise NX handshake completed';
+    });
+    if (!handshaken || options.stopAfter === 'handshake') {
+      result.ok = handshaken;
+      return result;
+    }
+
+    const certified = await step('certificate', async () => {
+      const version = certificate.readUInt16LE(0);
+      const validFrom = certificate.readUInt32LE(2);
+      const notValidAfter = certificate.readUInt32LE(6);
+      result.certificate = { version, validFrom: toIsoSeconds(validFrom), notValidAfter: toIsoSeconds(notValidAfter) };
+
+      const message = getCertificateMessage(version, validFrom, notValidAfter, staticKey);
+      if (!verifySchnorr(message, authorityKey, certificate.subarray(10))) {
+        throw new Error('Certificate is not signed by this authority public key');
+      }
+      const seconds = Math.floor(now().getTime() / 1000);
+      if (seconds < validFrom) throw new Error(`Certificate is not valid until ${result.certificate.validFrom}`);
+      if (seconds > notValidAfter) throw new Error(`Certificate expired at ${result.certificate.notValidAfter}`);
+      return `Signed by the authority key, valid until ${result.certificate.notValidAfter}`;
+    });
+    if (!certified || options.stopAfter === 'certificate') {
+      result.ok = certified;
+      return result;
+    }
+
+    const [sender, receiver] = noise.split();
+    result.ok = await step('setup-connection', async () => {
+      socket.write(encryptFrame(sender, encodeFrame(MSG_SETUP_CONNECTION, encodeSetupConnection(target))));
+      const { msgType, payload } = await withTimeout(
+        readEncryptedFrame(reader, receiver),
+        timeoutMs,
+        `No SetupConnection reply within ${timeoutMs}ms`,
+      );
+      if (msgType === MSG_SETUP_CONNECTION_SUCCESS) {
+        result.usedVersion = payload.readUInt16LE(0);
+        result.flags = payload.readUInt32LE(2);
+        return `Accepted, protocol version ${result.usedVersion}`;
+      }
+      if (msgType === MSG_SETUP_CONNECTION_ERROR) {
+        const flags = payload.readUInt32LE(0);
+        const code = payload.subarray(5, 5 + payload.readUInt8(4)).toString('utf-8');
+        throw new Error(`Rejected with "${code}"${flags ? ` (unsupported flags 0x${flags.toString(16)})` : ''}`);
+      }
+      throw new Error(`Unexpected reply message type 0x${msgType.toString(16).padStart(2, '0')}`);
+    });
+    return result;
+  } finally {
+    connection.socket?.destroy();
+  }
+}
@@ -0,0 +1,87 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import { test } from 'node:test';
+import type { AlertTransition } from './alerts.js';
+import type { StackEvent } from './events.js';
+import { createTelegramNotifier, formatAlertMessage, formatEventMessage, parseTelegramConfig } from './telegram.js';
+
+const TOKEN = '123456:ABC-def_ghi';
+
+const alert: AlertTransition = {
+  id: 'a1',
+  ruleId: 'low',
+  kind: 'hashrate-below',
+  status: 'resolved',
+  message: 'Resolved: Hashrate <1 TH/s',
+  at: '2026-01-01T00:00:00.000Z',
+};
+
+const block: StackEvent = {
+  id: 'e1',
+  kind: 'block-found',
+  title: 'Block found',
+  detail: '1 block(s) found since the services started.',
+  at: '2026-01-01T00:00:00.000Z',
+};
+
+test('parses Telegram configuration', () => {
+  assert.equal(parseTelegramConfig({}), null);
+  assert.deepEqual(parseTelegramConfig({ TELEGRAM_BOT_TOKEN: TOKEN, TELEGRAM_CHAT_ID: '42, -100123' }), {
+    botToken: TOKEN,
+    chatIds: ['42', '-100123'],
+  });
+  assert.throws(() => parseTelegramConfig({ TELEGRAM_BOT_TOKEN: TOKEN }), /TELEGRAM_CHAT_ID/);
+  assert.throws(() => parseTelegramConfig({ TELEGRAM_BOT_TOKEN: 'nope', TELEGRAM_CHAT_ID: '42' }), /TELEGRAM_BOT_TOKEN/);
+});
+
+test('formats escaped HTML messages', () => {
+  assert.equal(formatAlertMessage(alert, { poolName: 'R&D Pool' }), '✅ <b>Resolved</b>\nHashrate &lt;1 TH/s\n<i>R&amp;D Pool</i>');
+  assert.equal(formatAlertMessage(alert, { poolName: 'R&D Pool', network: 'mainnet' }), formatAlertMessage(alert, { poolName: 'R&D Pool' }));
+  assert.match(formatEventMessage(block, { network: 'regtest' }), /<i>Regtest<\/i>$/);
+  assert.equal(formatEventMessage(block), '🎉 <b>Block found</b>\n1 block(s) found since the services started.');
+});
+
+test('sends to every chat and backs off when rate limited', async (t) => {
+  const requests: { url: string; body: { chat_id: string; parse_mode: string } }[] = [];
+  const server = http.createServer((req, res) => {
+    let body = '';
+    req.on('data', (chunk) => { body += chunk; });
+    req.on('end', () => {
+      const parsed = JSON.parse(body);
+      requests.push({ url: req.url ?? '', body: parsed });
+      const limited = parsed.chat_id === '2' && requests.filter((r) => r.body.chat_id === '2').length === 1;
+      res.setHeader('Content-Type', 'application/json');
+      res.statusCode = limited ? 429 : 200;
+      res.end(JSON.stringify(limited ? { ok: false, parameters: { retry_after: 3 } } : { ok: true }));
+    });
+  });
+  server.listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+
+  const delays: number[] = [];
+  const notifier = createTelegramNotifier(
+    { botToken: TOKEN, chatIds: ['1', '2'] },
+    { apiBaseUrl: `http://127.0.0.1:${port}`, sleep: async (ms) => { delays.push(ms); } },
+  );
+  await notifier.notifyEvent(block);
+
+  assert.equal(requests.length, 3);
+  assert.ok(requests.every((r) => r.url === `/bot${TOKEN}/sendMessage` && r.body.parse_mode === 'HTML'));
+  assert.deepEqual(delays, [3000]);
+});
+
+test('surfaces Telegram errors', async (t) => {
+  const server = http.createServer((_req, res) => {
+    res.statusCode = 400;
+    res.end(JSON.stringify({ ok: false, description: 'Bad Request: chat not found' }));
+  });
+  server.listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+
+  const notifier = createTelegramNotifier({ botToken: TOKEN, chatIds: ['1'] }, { apiBaseUrl: `http://127.0.0.1:${port}` });
+  await assert.rejects(notifier.send('hi'), /chat not found/);
+});
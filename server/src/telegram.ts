@@ -0,0 +1,118 @@
+/**
+ * Telegram push notifications for alert transitions and blocks found.
+ *
+ * Configure a bot through @BotFather, then set TELEGRAM_BOT_TOKEN and
+ * TELEGRAM_CHAT_ID (comma-separated for several chats). Messages use
+ * Telegram's HTML parse mode.
+ */
+
+import type { AlertTransition } from './alerts.js';
+import { escapeMarkup } from './escape.js';
+import type { StackEvent } from './events.js';
+import { DEFAULT_MESSAGES, formatAlertText, formatEventText } from './notification-messages.js';
+import type { Messages } from './notification-messages.js';
+import { getTestNetworkLabel } from './summary.js';
+import type { NotificationContext } from './webhooks.js';
+
+export interface TelegramConfig {
+  botToken: string;
+  chatIds: string[];
+}
+
+export interface TelegramNotifierDeps {
+  apiBaseUrl?: string;
+  sleep?: (ms: number) => Promise<void>;
+  messages?: Messages;
+}
+
+const TELEGRAM_API_BASE_URL = 'https://api.telegram.org';
+const SEND_TIMEOUT_MS = 10_000;
+const MAX_ATTEMPTS = 3;
+const BOT_TOKEN_PATTERN = /^\d+:[\w-]+$/;
+
+export function parseTelegramConfig(env: NodeJS.ProcessEnv = process.env): TelegramConfig | null {
+  const botToken = env.TELEGRAM_BOT_TOKEN?.trim();
+  const chatIds = (env.TELEGRAM_CHAT_ID ?? '').split(',').map((id) => id.trim()).filter(Boolean);
+  if (!botToken && chatIds.length === 0) return null;
+
+  if (!botToken || !BOT_TOKEN_PATTERN.test(botToken)) {
+    throw new Error('TELEGRAM_BOT_TOKEN must be a bot token like 123456:ABC-DEF...');
+  }
+  if (chatIds.length === 0) {
+    throw new Error('TELEGRAM_CHAT_ID is required with TELEGRAM_BOT_TOKEN');
+  }
+
+  return { botToken, chatIds };
+}
+
+function withContext(text: string, { poolName, network }: NotificationContext): string {
+  const footer = [poolName, getTestNetworkLabel(network)].filter(Boolean).join(' · ');
+  return footer ? `${text}\n<i>${escapeMarkup(footer)}</i>` : text;
+}
+
+export function formatAlertMessage(
+  alert: AlertTransition,
+  context: NotificationContext = {},
+  messages: Messages = DEFAULT_MESSAGES,
+): string {
+  const heading = alert.status === 'firing'
+    ? `🚨 <b>${escapeMarkup(messages.t('alert.firing'))}</b>`
+    : `✅ <b>${escapeMarkup(messages.t('alert.resolved'))}</b>`;
+  return withContext(`${heading}\n${escapeMarkup(formatAlertText(alert, messages))}`, context);
+}
+
+export function formatEventMessage(
+  event: StackEvent,
+  context: NotificationContext = {},
+  messages: Messages = DEFAULT_MESSAGES,
+): string {
+  const { title, detail } = formatEventText(event, messages);
+  return withContext(`🎉 <b>${escapeMarkup(title)}</b>\n${escapeMarkup(detail)}`, context);
+}
+
+export function createTelegramNotifier(config: TelegramConfig, deps: TelegramNotifierDeps = {}) {
+  const apiBaseUrl = deps.apiBaseUrl ?? TELEGRAM_API_BASE_URL;
+  const sleep = deps.sleep ?? ((ms: number) => new Promise<void>((resolve) => setTimeout(resolve, ms)));
+  const messages = deps.messages ?? DEFAULT_MESSAGES;
+
+  async function sendTo(chatId: string, text: string): Promise<void> {
+    for (let attempt = 1; ; attempt++) {
+      const response = await fetch(`${apiBaseUrl}/bot${config.botToken}/sendMessage`, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ chat_id: chatId, text, parse_mode: 'HTML', disable_web_page_preview: true }),
+        signal: AbortSignal.timeout(SEND_TIMEOUT_MS),
+      });
+      if (response.ok) return;
+
+      const body = await response.json().catch(() => ({})) as {
+        description?: string;
+        parameters?: { retry_after?: number };
+      };
+      // Telegram rate limits per chat and says how long to back off.
+      if (response.status === 429 && attempt < MAX_ATTEMPTS) {
+        await sleep((body.parameters?.retry_after ?? 1) * 1000);
+        continue;
+      }
+      throw new Error(`Telegram sendMessage failed (HTTP ${response.status}): ${body.description ?? 'unknown error'}`);
+    }
+  }
+
+  /**
+   * Send `text` to every configured chat. Rejects with the first failure
+   * after attempting all chats.
+   */
+  async function send(text: string): Promise<void> {
+    const results = await Promise.allSettled(config.chatIds.map((chatId) => sendTo(chatId, text)));
+    const failure = results.find((result): result is PromiseRejectedResult => result.status === 'rejected');
+    if (failure) throw failure.reason;
+  }
+
+  return {
+    send,
+    notifyAlert: (alert: AlertTransition, context?: NotificationContext) => send(formatAlertMessage(alert, context, messages)),
+    notifyEvent: (event: StackEvent, context?: NotificationContext) => send(formatEventMessage(event, context, messages)),
+  };
+}
+
+export type TelegramNotifier = ReturnType<typeof createTelegramNotifier>;
@@ -0,0 +1,88 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import {
+  buildTelemetryReport,
+  createTelemetryReporter,
+  getBucket,
+  getImageTag,
+  HASHRATE_BUCKETS,
+  parseTelemetryConfig,
+  WORKER_BUCKETS,
+} from './telemetry.js';
+import type { TelemetryInput } from './telemetry.js';
+
+const input: TelemetryInput = {
+  mode: 'jd',
+  miningMode: 'pool',
+  network: 'mainnet',
+  hashrate: 42e12,
+  workers: 12,
+  images: { translator: 'registry.local:5000/stratumv2/translator_sv2:v1.2.0', jdc: 'stratumv2/jd_client_sv2:main' },
+};
+
+test('parseTelemetryConfig is off unless TELEMETRY=true with a URL', () => {
+  assert.equal(parseTelemetryConfig({}), null);
+  assert.equal(parseTelemetryConfig({ TELEMETRY: 'false', TELEMETRY_URL: 'https://stats.example/v1' }), null);
+  assert.deepEqual(parseTelemetryConfig({ TELEMETRY: 'true', TELEMETRY_URL: 'https://stats.example/v1' }), {
+    url: 'https://stats.example/v1',
+    intervalMs: 24 * 3_600_000,
+  });
+  assert.throws(() => parseTelemetryConfig({ TELEMETRY: 'yes' }), /TELEMETRY must be true or false/);
+  assert.throws(() => parseTelemetryConfig({ TELEMETRY: 'true' }), /TELEMETRY_URL is required/);
+  assert.throws(
+    () => parseTelemetryConfig({ TELEMETRY: 'true', TELEMETRY_URL: 'https://stats.example', TELEMETRY_INTERVAL_HOURS: '0.5' }),
+    /TELEMETRY_INTERVAL_HOURS must be between 1 and 168/,
+  );
+});
+
+test('reports only buckets, modes and image tags', () => {
+  assert.equal(getBucket(0, HASHRATE_BUCKETS), '0');
+  assert.equal(getBucket(1e15, HASHRATE_BUCKETS), '1-10 PH/s');
+  assert.equal(getBucket(5, WORKER_BUCKETS), '2-5');
+  assert.equal(getImageTag('stratumv2/translator_sv2'), 'latest');
+  assert.equal(getImageTag('localhost:5000/jdc@sha256:abc'), 'digest');
+
+  assert.deepEqual(buildTelemetryReport(input, () => 0.9), {
+    schema: 1,
+    mode: 'jd',
+    miningMode: 'pool',
+    network: 'mainnet',
+    hashrateBucket: '10-100 TH/s',
+    workersBucket: '6-20',
+    versions: { translator: 'v1.2.0', jdc: 'main' },
+  });
+});
+
+test('randomized response replaces a bucket with a random one', () => {
+  // The first draw picks randomizing, the second the bucket.
+  const draws = [0.1, 0, 0.9];
+  const report = buildTelemetryReport(input, () => draws.shift() ?? 0.9);
+  assert.equal(report.hashrateBucket, '0');
+  assert.equal(report.workersBucket, '6-20');
+});
+
+test('reporter records the last report and errors for /api/config', async () => {
+  const sent: unknown[] = [];
+  let fail = false;
+  const reporter = createTelemetryReporter({ url: 'https://stats.example/v1', intervalMs: 3_600_000 }, {
+    collect: async () => input,
+    send: async (_url, report) => {
+      if (fail) throw new Error('HTTP 503');
+      sent.push(report);
+    },
+    now: () => new Date('2026-01-01T00:00:00.000Z'),
+    random: () => 0.9,
+  });
+
+  assert.equal(reporter.status().lastReport, null);
+  const report = await reporter.submit();
+  assert.deepEqual(sent, [report]);
+  assert.equal(reporter.status().lastSentAt, '2026-01-01T00:00:00.000Z');
+  assert.equal(reporter.status().intervalHours, 1);
+
+  fail = true;
+  await assert.rejects(reporter.submit(), /HTTP 503/);
+  assert.equal(reporter.status().lastError, 'HTTP 503');
+  assert.deepEqual(reporter.status().lastReport, report);
+});
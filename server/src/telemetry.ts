@@ -0,0 +1,200 @@
+/**
+ * Opt-in, anonymous deployment stats for the SRI project.
+ *
+ * With TELEMETRY=true, a small report is POSTed to TELEMETRY_URL every
+ * TELEMETRY_INTERVAL_HOURS (24 by default): the deployment and mining mode,
+ * the network, the hashrate and worker count as coarse buckets, and the tags
+ * of the sv2-apps images running. Nothing that identifies the farm is sent:
+ * no pool, addresses, worker names, image registries or instance id.
+ *
+ * The buckets use randomized response: one report in four carries a random
+ * bucket instead, so no single report proves a farm's size, while totals
+ * over many farms can still be corrected for the noise.
+ *
+ * GET /api/config shows whether telemetry is on and the last report sent.
+ */
+
+import type { BitcoinNetwork, MiningMode, SetupMode } from '@sv2-ui/shared';
+import type { MonitoringService } from './monitoring.js';
+
+export interface TelemetryConfig {
+  url: string;
+  intervalMs: number;
+}
+
+export interface TelemetryInput {
+  mode: SetupMode | null;
+  miningMode: MiningMode | null;
+  network: BitcoinNetwork | null;
+  hashrate: number;
+  workers: number;
+  // Image reference per running service, e.g. stratumv2/translator_sv2:main.
+  images: Partial<Record<MonitoringService, string>>;
+}
+
+export interface TelemetryReport {
+  schema: 1;
+  mode: SetupMode | null;
+  miningMode: MiningMode | null;
+  network: BitcoinNetwork | null;
+  hashrateBucket: string;
+  workersBucket: string;
+  // Image tag per running service.
+  versions: Partial<Record<MonitoringService, string>>;
+}
+
+export interface TelemetryStatus {
+  enabled: boolean;
+  url: string | null;
+  intervalHours: number | null;
+  lastSentAt: string | null;
+  lastReport: TelemetryReport | null;
+  lastError: string | null;
+}
+
+export interface TelemetryReporterDeps {
+  // Null while there's nothing to report, e.g. before setup.
+  collect: () => Promise<TelemetryInput | null>;
+  send?: (url: string, report: TelemetryReport) => Promise<void>;
+  now?: () => Date;
+  random?: () => number;
+}
+
+const DEFAULT_INTERVAL_HOURS = 24;
+const MAX_INTERVAL_HOURS = 168;
+const SEND_TIMEOUT_MS = 10_000;
+// Share of reports that carry a uniformly random bucket.
+export const RANDOM_RESPONSE_RATE = 0.25;
+
+// Upper bounds (exclusive) and labels.
+export const HASHRATE_BUCKETS: Array<[number, string]> = [
+  [1, '0'],
+  [1e12, '<1 TH/s'],
+  [1e13, '1-10 TH/s'],
+  [1e14, '10-100 TH/s'],
+  [1e15, '100 TH/s-1 PH/s'],
+  [1e16, '1-10 PH/s'],
+  [Infinity, '10+ PH/s'],
+];
+export const WORKER_BUCKETS: Array<[number, string]> = [
+  [1, '0'],
+  [2, '1'],
+  [6, '2-5'],
+  [21, '6-20'],
+  [101, '21-100'],
+  [501, '101-500'],
+  [Infinity, '500+'],
+];
+
+export const DISABLED_TELEMETRY_STATUS: TelemetryStatus = {
+  enabled: false,
+  url: null,
+  intervalHours: null,
+  lastSentAt: null,
+  lastReport: null,
+  lastError: null,
+};
+
+export function parseTelemetryConfig(env: NodeJS.ProcessEnv = process.env): TelemetryConfig | null {
+  const enabled = env.TELEMETRY?.trim().toLowerCase();
+  if (enabled && enabled !== 'true' && enabled !== 'false') {
+    throw new Error(`TELEMETRY must be true or false, got "${env.TELEMETRY}"`);
+  }
+  if (enabled !== 'true') return null;
+
+  const rawUrl = env.TELEMETRY_URL?.trim();
+  if (!rawUrl) throw new Error('TELEMETRY_URL is required with TELEMETRY=true');
+  let url: URL;
+  try {
+    url = new URL(rawUrl);
+  } catch {
+    throw new Error(`Invalid TELEMETRY_URL "${rawUrl}"`);
+  }
+  if (url.protocol !== 'https:' && url.protocol !== 'http:') {
+    throw new Error('TELEMETRY_URL must be http(s)');
+  }
+
+  const rawHours = env.TELEMETRY_INTERVAL_HOURS?.trim();
+  const hours = rawHours ? Number(rawHours) : DEFAULT_INTERVAL_HOURS;
+  if (!Number.isFinite(hours) || hours < 1 || hours > MAX_INTERVAL_HOURS) {
+    throw new Error(`TELEMETRY_INTERVAL_HOURS must be between 1 and ${MAX_INTERVAL_HOURS}, got "${rawHours}"`);
+  }
+
+  return { url: url.toString(), intervalMs: hours * 3_600_000 };
+}
+
+export function getBucket(value: number, buckets: Array<[number, string]>): string {
+  return (buckets.find(([upper]) => value < upper) ?? buckets[buckets.length - 1])[1];
+}
+
+/**
+ * The tag of an image reference, without the registry or repository.
+ */
+export function getImageTag(image: string): string {
+  const [reference, digest] = image.split('@');
+  const slash = reference.lastIndexOf('/');
+  const colon = reference.lastIndexOf(':');
+  if (colon > slash) return reference.slice(colon + 1);
+  return digest ? 'digest' : 'latest';
+}
+
+export function buildTelemetryReport(input: TelemetryInput, random: () => number = Math.random): TelemetryReport {
+  const bucket = (value: number, buckets: Array<[number, string]>) => (random() < RANDOM_RESPONSE_RATE
+    ? buckets[Math.floor(random() * buckets.length)][1]
+    : getBucket(value, buckets));
+
+  return {
+    schema: 1,
+    mode: input.mode,
+    miningMode: input.miningMode,
+    network: input.network,
+    hashrateBucket: bucket(input.hashrate, HASHRATE_BUCKETS),
+    workersBucket: bucket(input.workers, WORKER_BUCKETS),
+    versions: Object.fromEntries(Object.entries(input.images).flatMap(([service, image]) => (image ? [[service, getImageTag(image)]] : []))),
+  };
+}
+
+async function postReport(url: string, report: TelemetryReport): Promise<void> {
+  const response = await fetch(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(report),
+    signal: AbortSignal.timeout(SEND_TIMEOUT_MS),
+  });
+  if (!response.ok) {
+    throw new Error(`Telemetry submission to ${new URL(url).origin} failed (HTTP ${response.status})`);
+  }
+}
+
+export function createTelemetryReporter(config: TelemetryConfig, deps: TelemetryReporterDeps) {
+  const send = deps.send ?? postReport;
+  const now = deps.now ?? (() => new Date());
+  const random = deps.random ?? Math.random;
+  let status: TelemetryStatus = {
+    ...DISABLED_TELEMETRY_STATUS,
+    enabled: true,
+    url: config.url,
+    intervalHours: config.intervalMs / 3_600_000,
+  };
+
+  /**
+   * Build and send one report; resolves to null when there was nothing to report.
+   */
+  async function submit(): Promise<TelemetryReport | null> {
+    const input = await deps.collect();
+    if (!input) return null;
+    const report = buildTelemetryReport(input, random);
+    try {
+      await send(config.url, report);
+    } catch (error) {
+      status = { ...status, lastError: error instanceof Error ? error.message : String(error) };
+      throw error;
+    }
+    status = { ...status, lastSentAt: now().toISOString(), lastReport: report, lastError: null };
+    return report;
+  }
+
+  return { submit, status: () => status };
+}
+
+export type TelemetryReporter = ReturnType<typeof createTelemetryReporter>;
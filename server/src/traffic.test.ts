@@ -0,0 +1,95 @@
+import assert from 'node:assert/strict';
+import fs from 'node:fs/promises';
+import http from 'node:http';
+import type { AddressInfo } from 'node:net';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+
+import { setContainerOrigins } from './proxy.js';
+import { createReplayer, getReplaySetup, loadRecording, parseTrafficArgs, startRecording, startReplay } from './traffic.js';
+import type { RecordedExchange } from './traffic.js';
+
+async function listen(server: http.Server): Promise<string> {
+  await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+  return `http://127.0.0.1:${(server.address() as AddressInfo).port}`;
+}
+
+test('parseTrafficArgs takes one of --record or --replay with a directory', () => {
+  assert.equal(parseTrafficArgs([]), null);
+  assert.deepEqual(parseTrafficArgs(['--record', 'captures/a']), { mode: 'record', dir: path.resolve('captures/a') });
+  assert.deepEqual(parseTrafficArgs(['--replay=/tmp/b']), { mode: 'replay', dir: '/tmp/b' });
+  assert.throws(() => parseTrafficArgs(['--record']), /--record requires a directory/);
+  assert.throws(() => parseTrafficArgs(['--replay', '--demo']), /--replay requires a directory/);
+  assert.throws(() => parseTrafficArgs(['--record', 'a', '--replay', 'b']), /not together/);
+});
+
+test('the replayer answers with the response recorded at the same offset', () => {
+  let now = 0;
+  const exchange = (offsetMs: number, body: string): RecordedExchange => (
+    { offsetMs, method: 'GET', path: '/api/v1/global', status: 200, contentType: 'application/json', body }
+  );
+  const lookup = createReplayer([exchange(5000, 'b'), exchange(1000, 'a'), exchange(9000, 'c')], { now: () => now });
+
+  assert.equal(lookup('GET', '/api/v1/global')?.body, 'a');
+  now = 6000;
+  assert.equal(lookup('GET', '/api/v1/global')?.body, 'b');
+  now = 60_000;
+  assert.equal(lookup('GET', '/api/v1/global')?.body, 'c');
+  assert.equal(lookup('GET', '/api/v1/server'), undefined);
+  assert.equal(lookup('POST', '/api/v1/global'), undefined);
+});
+
+test('a recorded session replays the same responses, unreachable services included', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-traffic-'));
+  let calls = 0;
+  const upstream = http.createServer((_req, res) => {
+    calls++;
+    res.writeHead(200, { 'Content-Type': 'application/json' });
+    res.end(JSON.stringify({ uptime_secs: calls }));
+  });
+  const closed = http.createServer();
+  const [translatorOrigin, jdcOrigin] = [await listen(upstream), await listen(closed)];
+  closed.close();
+
+  let now = 0;
+  setContainerOrigins({ 'sv2-translator': translatorOrigin, 'sv2-jdc': jdcOrigin });
+  const recorders = await startRecording(dir, { miningMode: 'pool', mode: 'jd', poolName: 'Pool', network: 'signet' }, { now: () => now });
+  try {
+    const first = await fetch(`${recorders.origins['sv2-translator']}/api/v1/global`);
+    assert.deepEqual(await first.json(), { uptime_secs: 1 });
+    now = 10_000;
+    await fetch(`${recorders.origins['sv2-translator']}/api/v1/global`);
+    await assert.rejects(fetch(`${recorders.origins['sv2-jdc']}/api/v1/global`));
+  } finally {
+    await recorders.close();
+    upstream.close();
+    setContainerOrigins({});
+  }
+
+  const recording = await loadRecording(dir);
+  assert.equal(recording.session.recordedAt, new Date(0).toISOString());
+  assert.equal(getReplaySetup(recording.session).pool?.name, 'Pool');
+  assert.equal(getReplaySetup(recording.session).bitcoin?.network, 'signet');
+  assert.deepEqual(recording.exchanges.translator.map(({ offsetMs, status }) => [offsetMs, status]), [[0, 200], [10_000, 200]]);
+  assert.equal(recording.exchanges.jdc[0].status, null);
+
+  now = 0;
+  const servers = await startReplay(recording, { now: () => now });
+  try {
+    const replayed = await fetch(`${servers.origins['sv2-translator']}/api/v1/global`);
+    assert.deepEqual(await replayed.json(), { uptime_secs: 1 });
+    now = 12_000;
+    const later = await fetch(`${servers.origins['sv2-translator']}/api/v1/global`);
+    assert.deepEqual(await later.json(), { uptime_secs: 2 });
+    assert.equal((await fetch(`${servers.origins['sv2-translator']}/api/v1/server`)).status, 404);
+    await assert.rejects(fetch(`${servers.origins['sv2-jdc']}/api/v1/global`));
+  } finally {
+    await servers.close();
+  }
+});
+
+test('loadRecording explains a directory without a session file', async () => {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-traffic-'));
+  await assert.rejects(loadRecording(dir), /is not a recording made with --record/);
+});
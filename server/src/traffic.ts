@@ -0,0 +1,273 @@
+/**
+ * Recording and replaying the monitoring API traffic.
+ *
+ * `--record <dir>` puts a recorder in front of the Translator and JDC
+ * monitoring APIs. Everything the proxies and the poller fetch passes through
+ * it and is appended, with the time it arrived, to <dir>/translator.ndjson and
+ * <dir>/jdc.ndjson. <dir>/session.json notes which kind of stack it was.
+ *
+ * `--replay <dir>` serves a recording instead of the containers. A request
+ * gets the response recorded for the same method and path, at the same
+ * offset into the session, so the dashboard goes through the captured
+ * session again at its original pace, the same way every time. Requests for
+ * paths that were never recorded get a 404.
+ */
+
+import fs from 'fs/promises';
+import path from 'path';
+import type { IncomingMessage, RequestListener } from 'http';
+import type { BitcoinNetwork, MiningMode, SetupMode } from '@sv2-ui/shared';
+import { DEMO_SETUP, serveOnLoopback } from './demo.js';
+import type { LoopbackServers } from './demo.js';
+import { createLogger } from './logger.js';
+import { MONITORING_SERVICES } from './monitoring.js';
+import type { MonitoringService } from './monitoring.js';
+import { getContainerUrl } from './proxy.js';
+import type { SetupData } from './types.js';
+
+const log = createLogger('traffic');
+
+const UPSTREAM_TIMEOUT_MS = 5000;
+const SESSION_FILE = 'session.json';
+
+export type TrafficMode = 'record' | 'replay';
+
+export interface TrafficArgs {
+  mode: TrafficMode;
+  dir: string;
+}
+
+export interface RecordedExchange {
+  // Milliseconds since the recording started.
+  offsetMs: number;
+  method: string;
+  // Path and query, e.g. /api/v1/sv1/clients?offset=0&limit=100.
+  path: string;
+  // Null when the monitoring API couldn't be reached.
+  status: number | null;
+  contentType: string | null;
+  body: string;
+}
+
+export interface RecordedSession {
+  recordedAt: string;
+  miningMode: MiningMode | null;
+  mode: SetupMode | null;
+  poolName: string | null;
+  network: BitcoinNetwork | null;
+}
+
+export interface Recording {
+  session: RecordedSession;
+  exchanges: Record<MonitoringService, RecordedExchange[]>;
+}
+
+export interface TrafficOptions {
+  now?: () => number;
+}
+
+/**
+ * The value of `--record <dir>` or `--replay <dir>` (or `--record=<dir>`), if given.
+ */
+export function parseTrafficArgs(argv: string[] = process.argv.slice(2)): TrafficArgs | null {
+  let found: TrafficArgs | null = null;
+
+  for (let i = 0; i < argv.length; i++) {
+    const match = /^--(record|replay)(?:=(.*))?$/.exec(argv[i]);
+    if (!match) continue;
+
+    const mode = match[1] as TrafficMode;
+    const value = (match[2] ?? argv[i + 1])?.trim();
+    if (!value || (match[2] === undefined && value.startsWith('--'))) throw new Error(`--${mode} requires a directory`);
+    if (found) throw new Error('--record and --replay can only be given once, and not together');
+    found = { mode, dir: path.resolve(value) };
+  }
+
+  return found;
+}
+
+function getRecordingFile(dir: string, service: MonitoringService): string {
+  return path.join(dir, `${service}.ndjson`);
+}
+
+async function readRequestBody(req: IncomingMessage): Promise<Buffer | undefined> {
+  if (req.method === 'GET' || req.method === 'HEAD') return undefined;
+  const chunks: Buffer[] = [];
+  for await (const chunk of req) chunks.push(chunk as Buffer);
+  return chunks.length > 0 ? Buffer.concat(chunks) : undefined;
+}
+
+/**
+ * Pass requests through to `upstream`, appending every exchange to `file`.
+ */
+function createRecorder(upstream: string, file: string, now: () => number, startedAt: number) {
+  // Exchanges are appended one at a time so lines never interleave.
+  let writes = Promise.resolve();
+
+  function record(exchange: RecordedExchange): void {
+    writes = writes
+      .then(() => fs.appendFile(file, `${JSON.stringify(exchange)}\n`))
+      .catch((error) => log.error('Recording failed:', error));
+  }
+
+  const listener: RequestListener = async (req, res) => {
+    const method = req.method ?? 'GET';
+    const url = req.url ?? '/';
+    try {
+      const headers: Record<string, string> = {};
+      for (const name of ['accept', 'content-type', 'x-request-id', 'x-forwarded-for', 'x-forwarded-proto', 'x-forwarded-host']) {
+        const value = req.headers[name];
+        if (typeof value === 'string') headers[name] = value;
+      }
+      const response = await fetch(`${upstream}${url}`, {
+        method,
+        headers,
+        body: await readRequestBody(req),
+        signal: AbortSignal.timeout(UPSTREAM_TIMEOUT_MS),
+      });
+      const body = await response.text();
+      const contentType = response.headers.get('Content-Type');
+      record({ offsetMs: now() - startedAt, method, path: url, status: response.status, contentType, body });
+      res.writeHead(response.status, contentType ? { 'Content-Type': contentType } : {});
+      res.end(body);
+    } catch {
+      record({ offsetMs: now() - startedAt, method, path: url, status: null, contentType: null, body: '' });
+      // Drop the connection, as the unreachable service would have.
+      res.destroy();
+    }
+  };
+
+  return { listener, flush: () => writes };
+}
+
+/**
+ * Start recording to `dir`: write the session file and return recorders to
+ * point the monitoring origins at.
+ */
+export async function startRecording(
+  dir: string,
+  session: Omit<RecordedSession, 'recordedAt'>,
+  { now = Date.now }: TrafficOptions = {},
+): Promise<LoopbackServers> {
+  await fs.mkdir(dir, { recursive: true });
+  const startedAt = now();
+  const recordedSession: RecordedSession = { recordedAt: new Date(startedAt).toISOString(), ...session };
+  await fs.writeFile(path.join(dir, SESSION_FILE), JSON.stringify(recordedSession, null, 2));
+
+  const listeners = {} as Record<MonitoringService, RequestListener>;
+  const flushes: (() => Promise<void>)[] = [];
+  for (const service of Object.keys(MONITORING_SERVICES) as MonitoringService[]) {
+    const file = getRecordingFile(dir, service);
+    await fs.writeFile(file, '');
+    const { containerName, port } = MONITORING_SERVICES[service];
+    const recorder = createRecorder(getContainerUrl(containerName, port), file, now, startedAt);
+    listeners[service] = recorder.listener;
+    flushes.push(recorder.flush);
+  }
+
+  const servers = await serveOnLoopback(listeners);
+  return {
+    origins: servers.origins,
+    close: async () => {
+      await servers.close();
+      await Promise.all(flushes.map((flush) => flush()));
+    },
+  };
+}
+
+export async function loadRecording(dir: string): Promise<Recording> {
+  let session: RecordedSession;
+  try {
+    session = JSON.parse(await fs.readFile(path.join(dir, SESSION_FILE), 'utf-8')) as RecordedSession;
+  } catch (error) {
+    const reason = error instanceof Error ? error.message : String(error);
+    throw new Error(`${dir} is not a recording made with --record (${reason})`);
+  }
+
+  const exchanges = {} as Record<MonitoringService, RecordedExchange[]>;
+  for (const service of Object.keys(MONITORING_SERVICES) as MonitoringService[]) {
+    let content = '';
+    try {
+      content = await fs.readFile(getRecordingFile(dir, service), 'utf-8');
+    } catch {
+      // A service that wasn't part of the stack has no recording.
+    }
+    exchanges[service] = content.split('\n').flatMap((line, index) => {
+      if (!line.trim()) return [];
+      try {
+        return [JSON.parse(line) as RecordedExchange];
+      } catch {
+        throw new Error(`${getRecordingFile(dir, service)}:${index + 1} is not a recorded exchange`);
+      }
+    });
+  }
+
+  return { session, exchanges };
+}
+
+/**
+ * The setup shown while replaying: the recorded kind of stack, with the demo
+ * setup filling in what a recording doesn't keep.
+ */
+export function getReplaySetup(session: RecordedSession): SetupData {
+  return {
+    ...DEMO_SETUP,
+    miningMode: session.miningMode,
+    mode: session.mode,
+    pool: DEMO_SETUP.pool && { ...DEMO_SETUP.pool, name: session.poolName ?? DEMO_SETUP.pool.name },
+    bitcoin: DEMO_SETUP.bitcoin && { ...DEMO_SETUP.bitcoin, network: session.network ?? DEMO_SETUP.bitcoin.network },
+  };
+}
+
+/**
+ * Look up the exchange recorded for a request, as of the same offset into
+ * the session. Before the first recording of a path, its first response is used.
+ */
+export function createReplayer(exchanges: RecordedExchange[], { now = Date.now }: TrafficOptions = {}) {
+  const startedAt = now();
+  const byRequest = new Map<string, RecordedExchange[]>();
+  for (const exchange of [...exchanges].sort((a, b) => a.offsetMs - b.offsetMs)) {
+    const key = `${exchange.method} ${exchange.path}`;
+    byRequest.set(key, [...(byRequest.get(key) ?? []), exchange]);
+  }
+
+  return (method: string, requestPath: string): RecordedExchange | undefined => {
+    const candidates = byRequest.get(`${method} ${requestPath}`);
+    if (!candidates) return undefined;
+    const offset = now() - startedAt;
+    let match = candidates[0];
+    for (const candidate of candidates) {
+      if (candidate.offsetMs > offset) break;
+      match = candidate;
+    }
+    return match;
+  };
+}
+
+function createReplayListener(exchanges: RecordedExchange[], options: TrafficOptions): RequestListener {
+  const lookup = createReplayer(exchanges, options);
+  return (req, res) => {
+    const exchange = lookup(req.method ?? 'GET', req.url ?? '/');
+    if (!exchange) {
+      res.writeHead(404, { 'Content-Type': 'application/json' });
+      res.end(JSON.stringify({ error: 'Not in the recording' }));
+      return;
+    }
+    if (exchange.status === null) {
+      res.destroy();
+      return;
+    }
+    res.writeHead(exchange.status, exchange.contentType ? { 'Content-Type': exchange.contentType } : {});
+    res.end(exchange.body);
+  };
+}
+
+/**
+ * Serve a recording in place of the monitoring APIs.
+ */
+export function startReplay(recording: Recording, options: TrafficOptions = {}): Promise<LoopbackServers> {
+  return serveOnLoopback({
+    translator: createReplayListener(recording.exchanges.translator, options),
+    jdc: createReplayListener(recording.exchanges.jdc, options),
+  });
+}
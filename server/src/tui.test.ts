@@ -0,0 +1,94 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import type { HealthReport } from './health.js';
+import type { StackSummary } from './summary.js';
+import { parseTuiArgs, renderTuiView, sparkline } from './tui.js';
+import type { TuiMiner, TuiView } from './tui.js';
+
+const summary: StackSummary = {
+  generatedAt: '2026-01-01T00:00:00.000Z',
+  miningMode: 'solo',
+  mode: 'jd',
+  network: 'mainnet',
+  poolName: null,
+  online: true,
+  hashrate: 2e14,
+  workers: 3,
+  sharesAccepted: 10,
+  sharesSubmitted: 10,
+  sharesRejected: 0,
+  bestDifficulty: 1e9,
+  blocksFound: 0,
+  uptimeSecs: 600,
+};
+
+const health: HealthReport = { status: 'ok', checkedAt: '2026-01-01T00:00:00.000Z', docker: true, services: [], sv1: null, sites: [] };
+
+function miner(name: string, hashrate: number): TuiMiner {
+  return {
+    client_id: hashrate,
+    channel_id: null,
+    authorized_worker_name: name,
+    user_identity: name,
+    hashrate,
+    stable_hashrate: true,
+    target_hex: '',
+    extranonce1_hex: '',
+    extranonce2_len: 0,
+    version_rolling_mask: null,
+    version_rolling_min_bit: null,
+    label: null,
+  };
+}
+
+test('parseTuiArgs reads --interval and takes the URL and token like status', () => {
+  assert.deepEqual(parseTuiArgs(['--interval', '2', 'http://rig:3001'], { SV2_UI_TOKEN: 'key' }), {
+    url: 'http://rig:3001',
+    token: 'key',
+    intervalMs: 2000,
+  });
+  assert.equal(parseTuiArgs([], {}).intervalMs, 5000);
+  assert.throws(() => parseTuiArgs(['--interval', '0'], {}), /--interval must be/);
+  assert.throws(() => parseTuiArgs(['--json'], {}), /use status --json/);
+  assert.throws(() => parseTuiArgs(['--watch'], {}), /Unknown tui option "--watch"/);
+});
+
+test('the sparkline scales from zero to the peak and keeps the latest samples', () => {
+  assert.equal(sparkline([0, 25, 50, 100], 10), '▁▃▅█');
+  assert.equal(sparkline([100, 0, 100], 2), '▁█');
+  assert.equal(sparkline([0, 0], 5), '▁▁');
+});
+
+test('a frame fits the terminal, with the busiest miners first', () => {
+  const view: TuiView = {
+    status: { url: 'http://localhost:3001', summary, health },
+    error: null,
+    hashrate: [1e14, 2e14],
+    miners: [miner('rig.small', 1e13), miner('rig.big', 1.9e14), miner('rig.mid', 5e13)],
+    alerts: {
+      anomalies: [],
+      transitions: [{ id: 't1', ruleId: 'r1', kind: 'hashrate-below', status: 'firing', message: 'Hashrate 100.00 TH/s below 150.00 TH/s', at: '2025-12-31T23:58:00.000Z' }],
+    },
+    fetchedAt: new Date('2026-01-01T00:00:00.000Z'),
+  };
+
+  const frame = renderTuiView(view, { width: 60, height: 40 });
+  assert.equal(frame[0], `sv2-ui tui  http://localhost:3001${' '.repeat(15)}00:00:00 UTC`);
+  assert.ok(frame.includes('▅█'));
+  assert.ok(frame.includes('FIRING  23:58:00  Hashrate 100.00 TH/s below 150.00 TH/s'));
+  const workers = frame.indexOf('Worker     Hashrate');
+  assert.deepEqual(frame.slice(workers + 1, workers + 4), ['rig.big    190.00 TH/s', 'rig.mid    50.00 TH/s', 'rig.small  10.00 TH/s']);
+  assert.ok(frame.every((line) => [...line].length <= 60));
+
+  // A short terminal lists fewer miners, and says so.
+  const short = renderTuiView(view, { width: 60, height: 24 });
+  assert.equal(short.at(-1), '[q] quit');
+  assert.ok(short.length <= 24);
+  assert.ok(short.some((line) => /^\.\.\. and \d more$/.test(line)));
+
+  assert.deepEqual(renderTuiView({ ...view, status: null, error: 'Cannot reach sv2-ui at http://localhost:3001: connect ECONNREFUSED' }, { width: 120, height: 24 }).slice(1), [
+    '',
+    'Cannot read the server: Cannot reach sv2-ui at http://localhost:3001: connect ECONNREFUSED',
+    'Retrying...',
+  ]);
+});
@@ -0,0 +1,207 @@
+/**
+ * `tui [url]`: a live dashboard in the terminal.
+ *
+ * A full-screen view of a running sv2-ui server, redrawn every
+ * `--interval` seconds: the summary and service health `status` prints, a
+ * sparkline of the stack hashrate, the SV1 miners and the recent alerts. It
+ * reads the same server APIs as the web dashboard, so it shows what the
+ * server's poller and alert engine see. [q] or Ctrl-C quits.
+ *
+ * It takes the URL and `--token` like `status`. Plain ANSI escapes draw it;
+ * any terminal an SSH session gives you will do.
+ */
+
+import { formatHashrate } from '@sv2-ui/shared';
+import type { ActiveAnomaly, AlertTransition } from './alerts.js';
+import { readHistoryCsv } from './federation.js';
+import type { MinerLabel } from './miner-labels.js';
+import type { Sv1ClientInfo, Sv1ClientsResponse } from './monitoring-schema.js';
+import { fetchServerStatus, formatServerStatus, parseStatusArgs, requestServer, requestServerJson } from './status.js';
+import type { ServerStatus, StatusArgs } from './status.js';
+
+export interface TuiArgs extends Omit<StatusArgs, 'json'> {
+  intervalMs: number;
+}
+
+export interface TuiMiner extends Sv1ClientInfo {
+  label?: MinerLabel | null;
+}
+
+export interface TuiView {
+  status: ServerStatus | null;
+  // Why the server couldn't be read; the last view stays on screen.
+  error: string | null;
+  // Hashrate samples, one a minute, oldest first; null without history.
+  hashrate: number[] | null;
+  miners: TuiMiner[] | null;
+  alerts: { anomalies: ActiveAnomaly[]; transitions: AlertTransition[] } | null;
+  fetchedAt: Date;
+}
+
+const DEFAULT_INTERVAL_SECS = 5;
+const HISTORY_METRIC = 'stack.hashrate';
+const SPARK_BLOCKS = '▁▂▃▄▅▆▇█';
+const MAX_ALERT_LINES = 5;
+const ESC = '\x1b[';
+
+export function parseTuiArgs(argv: string[], env: NodeJS.ProcessEnv = process.env): TuiArgs {
+  const rest: string[] = [];
+  let intervalMs = DEFAULT_INTERVAL_SECS * 1000;
+  for (let i = 0; i < argv.length; i++) {
+    if (argv[i] === '--interval') {
+      const secs = Number(argv[++i]);
+      if (!Number.isFinite(secs) || secs < 1 || secs > 3600) throw new Error('--interval must be between 1 and 3600 seconds');
+      intervalMs = secs * 1000;
+    } else if (argv[i] === '--json') {
+      throw new Error('tui has no --json; use status --json');
+    } else {
+      rest.push(argv[i]);
+    }
+  }
+  const { url, token } = parseStatusArgs(rest, env, 'tui');
+  return { url, token, intervalMs };
+}
+
+/**
+ * `values` as one block character each, scaled from zero to their maximum.
+ * Only the last `width` values fit.
+ */
+export function sparkline(values: number[], width: number): string {
+  const shown = values.slice(-Math.max(0, width));
+  const max = Math.max(0, ...shown);
+  return shown
+    .map((value) => SPARK_BLOCKS[max > 0 ? Math.min(SPARK_BLOCKS.length - 1, Math.floor(value / max * SPARK_BLOCKS.length)) : 0])
+    .join('');
+}
+
+// Optional panes go blank instead of taking the whole view down.
+async function orNull<T>(promise: Promise<T>): Promise<T | null> {
+  try {
+    return await promise;
+  } catch {
+    return null;
+  }
+}
+
+export async function fetchTuiView({ url, token }: TuiArgs, minutes: number, now: () => Date = () => new Date()): Promise<TuiView> {
+  const history = new URLSearchParams({ metric: HISTORY_METRIC, range: `${Math.max(1, minutes)}m`, resolution: '1m' });
+  try {
+    const [status, hashrate, miners, alerts] = await Promise.all([
+      fetchServerStatus({ url, token, json: false }),
+      orNull(requestServer(url, `/api/v1/history/export?${history}`, token)
+        .then(async (response) => readHistoryCsv(await response.text(), HISTORY_METRIC).map(({ value }) => value))),
+      orNull(requestServerJson<Sv1ClientsResponse & { items: TuiMiner[] }>(url, '/api/v1/monitoring/translator/sv1/clients', token)
+        .then((clients) => clients.items)),
+      orNull(requestServerJson<NonNullable<TuiView['alerts']>>(url, '/api/alerts', token)),
+    ]);
+    return { status, error: null, hashrate, miners, alerts, fetchedAt: now() };
+  } catch (error) {
+    return { status: null, error: error instanceof Error ? error.message : String(error), hashrate: null, miners: null, alerts: null, fetchedAt: now() };
+  }
+}
+
+function fit(line: string, width: number): string {
+  return [...line].length > width ? `${[...line].slice(0, Math.max(0, width - 1)).join('')}…` : line;
+}
+
+function formatTime(iso: string): string {
+  return new Date(iso).toISOString().slice(11, 19);
+}
+
+/**
+ * One frame for a `width` x `height` terminal, without escape codes.
+ */
+export function renderTuiView(view: TuiView, { width, height }: { width: number; height: number }): string[] {
+  const header = `sv2-ui tui  ${view.status?.url ?? ''}`;
+  const clock = `${formatTime(view.fetchedAt.toISOString())} UTC`;
+  const lines = [`${header}${' '.repeat(Math.max(1, width - header.length - clock.length))}${clock}`];
+  if (view.error) lines.push('', `Cannot read the server: ${view.error}`, 'Retrying...');
+  if (!view.status) return lines.map((line) => fit(line, width));
+
+  // The status tables, without their URL line.
+  lines.push(...formatServerStatus(view.status, view.fetchedAt).trimEnd().split('\n').slice(1));
+
+  lines.push('');
+  if (view.hashrate && view.hashrate.length > 0) {
+    lines.push(`Hashrate history (peak ${formatHashrate(Math.max(...view.hashrate))})`, sparkline(view.hashrate, width));
+  } else {
+    lines.push('Hashrate history unavailable');
+  }
+
+  const alertLines = [
+    ...(view.alerts?.anomalies ?? []).map((anomaly) => `ACTIVE   ${formatTime(anomaly.since)}  ${anomaly.message}`),
+    ...(view.alerts?.transitions ?? []).map((transition) => `${transition.status === 'firing' ? 'FIRING ' : 'resolved'} ${formatTime(transition.at)}  ${transition.message}`),
+  ].slice(0, MAX_ALERT_LINES);
+  const alerts = ['', 'Alerts', ...(view.alerts ? (alertLines.length > 0 ? alertLines : ['None']) : ['Unavailable'])];
+
+  // The miner list takes whatever room the other panes leave.
+  const footer = ['', '[q] quit'];
+  const room = height - lines.length - alerts.length - footer.length - 4;
+  lines.push('', `Miners${view.miners ? ` (${view.miners.length})` : ''}`);
+  if (!view.miners) {
+    lines.push('Unavailable');
+  } else if (view.miners.length === 0) {
+    lines.push('No SV1 miners connected');
+  } else {
+    const miners = [...view.miners].sort((a, b) => (b.hashrate ?? 0) - (a.hashrate ?? 0));
+    const shown = miners.slice(0, Math.max(1, room));
+    const nameWidth = Math.max(6, ...shown.map((miner) => (miner.label?.name ?? miner.authorized_worker_name).length));
+    lines.push(`${'Worker'.padEnd(nameWidth)}  Hashrate`);
+    lines.push(...shown.map((miner) => `${(miner.label?.name ?? miner.authorized_worker_name).padEnd(nameWidth)}  ${formatHashrate(miner.hashrate)}`));
+    if (miners.length > shown.length) lines.push(`... and ${miners.length - shown.length} more`);
+  }
+
+  return [...lines, ...alerts, ...footer].slice(0, height).map((line) => fit(line, width));
+}
+
+export interface TuiTerminal {
+  stdout: NodeJS.WriteStream;
+  stdin: NodeJS.ReadStream;
+}
+
+/**
+ * Draw the dashboard until the user quits.
+ */
+export function runTui(args: TuiArgs, { stdout, stdin }: TuiTerminal = { stdout: process.stdout, stdin: process.stdin }): Promise<void> {
+  let view: TuiView | null = null;
+  let timer: NodeJS.Timeout | null = null;
+  let stopped = false;
+
+  const size = () => ({ width: stdout.columns || 80, height: stdout.rows || 24 });
+  const draw = () => {
+    if (view) stdout.write(`${ESC}H${ESC}2J${renderTuiView(view, size()).join('\r\n')}`);
+  };
+  const refresh = async () => {
+    const next = await fetchTuiView(args, size().width);
+    if (stopped) return;
+    // Keep showing the last good numbers under the error.
+    view = next.status || !view ? next : { ...view, error: next.error, fetchedAt: next.fetchedAt };
+    draw();
+    timer = setTimeout(() => void refresh(), args.intervalMs);
+  };
+
+  return new Promise((resolve) => {
+    const quit = () => {
+      stopped = true;
+      if (timer) clearTimeout(timer);
+      stdout.off('resize', draw);
+      stdin.off('data', onKey);
+      if (stdin.isTTY) stdin.setRawMode(false);
+      stdin.pause();
+      // Back to the normal screen, with the cursor shown again.
+      stdout.write(`${ESC}?25h${ESC}?1049l`);
+      resolve();
+    };
+    const onKey = (key: Buffer) => {
+      const pressed = key.toString();
+      if (pressed === 'q' || pressed === 'Q' || pressed === '\x03') quit();
+    };
+
+    stdout.write(`${ESC}?1049h${ESC}?25l`);
+    if (stdin.isTTY) stdin.setRawMode(true);
+    stdin.on('data', onKey);
+    stdin.resume();
+    stdout.on('resize', draw);
+    void refresh();
+  });
+}
@@ -0,0 +1,60 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import { test } from 'node:test';
+import express from 'express';
+import { createApp } from './app.js';
+import { getRequestRole } from './auth.js';
+import { parseIpFilter } from './ip-filter.js';
+import { getTunnelIdentity, parseTunnelConfig } from './tunnel.js';
+
+test('parseTunnelConfig is off unless set', () => {
+  assert.equal(parseTunnelConfig({}), null);
+  const config = parseTunnelConfig({ TUNNEL_IDENTITY: 'Cloudflare', TUNNEL_ADMIN_USERS: 'Alice@Example.com, bob@example.com' });
+  assert.equal(config?.provider, 'cloudflare');
+  assert.deepEqual([...config!.adminUsers], ['alice@example.com', 'bob@example.com']);
+  assert.equal(config?.connectors.check('127.0.0.1', 'ipv4'), true);
+  assert.throws(() => parseTunnelConfig({ TUNNEL_IDENTITY: 'ngrok' }), /TUNNEL_IDENTITY must be/);
+  assert.throws(() => parseTunnelConfig({ TUNNEL_IDENTITY: 'tailscale', TUNNEL_CONNECTOR_CIDR: 'nope' }), /TUNNEL_CONNECTOR_CIDR/);
+});
+
+test('reads identity from each tunnel\'s headers', () => {
+  assert.deepEqual(getTunnelIdentity('cloudflare', {
+    'cf-connecting-ip': '203.0.113.7',
+    'cf-access-authenticated-user-email': 'Alice@Example.com',
+  }), { user: 'alice@example.com', clientIp: '203.0.113.7' });
+  assert.deepEqual(getTunnelIdentity('cloudflare', { 'cf-connecting-ip': '203.0.113.7' }), { user: null, clientIp: '203.0.113.7' });
+  assert.equal(getTunnelIdentity('cloudflare', {}), null);
+
+  assert.deepEqual(getTunnelIdentity('tailscale', { 'tailscale-user-login': 'alice@github' }), { user: 'alice@github', clientIp: null });
+  assert.deepEqual(getTunnelIdentity('tailscale', { 'tailscale-funnel-request': '?1' }), { user: null, clientIp: null });
+  assert.equal(getTunnelIdentity('tailscale', {}), null);
+});
+
+test('tunnel users get their role and client IP; local requests are untouched', async (t) => {
+  const router = express.Router();
+  router.get('/api/whoami', (req, res) => res.json({ role: getRequestRole(req), ip: req.ip, user: res.locals.tunnelUser ?? null }));
+  const app = createApp({
+    router,
+    trustProxy: undefined,
+    ipFilter: parseIpFilter({ DENY_CIDR: '198.51.100.0/24' }),
+    tunnel: parseTunnelConfig({ TUNNEL_IDENTITY: 'cloudflare', TUNNEL_ADMIN_USERS: 'alice@example.com' }),
+  });
+  const server = http.createServer(app).listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  const url = `http://127.0.0.1:${port}/api/whoami`;
+  const whoami = async (headers: Record<string, string>) => (await fetch(url, { headers })).json();
+
+  assert.deepEqual(await whoami({ 'CF-Connecting-IP': '203.0.113.7', 'Cf-Access-Authenticated-User-Email': 'alice@example.com' }), {
+    role: 'admin',
+    ip: '203.0.113.7',
+    user: 'alice@example.com',
+  });
+  // Anyone else through the tunnel is a viewer, even without ADMIN_TOKEN.
+  assert.equal((await whoami({ 'CF-Connecting-IP': '203.0.113.8' })).role, 'viewer');
+  assert.equal((await whoami({})).role, 'admin');
+
+  // The IP filter sees the tunnel client.
+  assert.equal((await fetch(url, { headers: { 'CF-Connecting-IP': '198.51.100.1' } })).status, 403);
+});
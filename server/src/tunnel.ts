@@ -0,0 +1,101 @@
+/**
+ * Identity from Cloudflare Tunnel or Tailscale Serve/Funnel.
+ *
+ * With TUNNEL_IDENTITY set, requests that arrive through the tunnel connector
+ * (cloudflared or tailscaled, by default on loopback) are identified by the
+ * headers the connector adds:
+ *
+ * - cloudflare: `CF-Connecting-IP` carries the client IP, and
+ *   `Cf-Access-Authenticated-User-Email` the user when Cloudflare Access
+ *   protects the hostname.
+ * - tailscale: `Tailscale-User-Login` carries the tailnet user. Funnel
+ *   requests come from the public internet and carry no user.
+ *
+ * Users listed in TUNNEL_ADMIN_USERS get the admin role; every other tunnel
+ * request is a viewer, even while ADMIN_TOKEN is unset. The headers are only
+ * trusted from TUNNEL_CONNECTOR_CIDR, so clients elsewhere can't forge them.
+ */
+
+import type net from 'net';
+import type { IncomingHttpHeaders } from 'http';
+import type { RequestHandler } from 'express';
+import { resolveRole } from './auth.js';
+import { isIpAllowed, parseCidrList } from './ip-filter.js';
+
+export type TunnelProvider = 'cloudflare' | 'tailscale';
+
+export interface TunnelConfig {
+  provider: TunnelProvider;
+  // Lowercased emails or tailnet logins.
+  adminUsers: Set<string>;
+  connectors: net.BlockList;
+}
+
+export interface TunnelIdentity {
+  user: string | null;
+  clientIp: string | null;
+}
+
+const DEFAULT_CONNECTOR_CIDR = '127.0.0.0/8,::1/128';
+
+export function parseTunnelConfig(env: NodeJS.ProcessEnv = process.env): TunnelConfig | null {
+  const provider = env.TUNNEL_IDENTITY?.trim().toLowerCase();
+  if (!provider) return null;
+  if (provider !== 'cloudflare' && provider !== 'tailscale') {
+    throw new Error(`TUNNEL_IDENTITY must be cloudflare or tailscale, got "${env.TUNNEL_IDENTITY}"`);
+  }
+
+  const adminUsers = new Set((env.TUNNEL_ADMIN_USERS ?? '')
+    .split(',')
+    .map((user) => user.trim().toLowerCase())
+    .filter(Boolean));
+  const connectors = parseCidrList(env.TUNNEL_CONNECTOR_CIDR || DEFAULT_CONNECTOR_CIDR, 'TUNNEL_CONNECTOR_CIDR');
+  return { provider, adminUsers, connectors: connectors! };
+}
+
+function headerValue(headers: IncomingHttpHeaders, name: string): string | null {
+  const value = headers[name];
+  const first = (Array.isArray(value) ? value[0] : value)?.trim();
+  return first || null;
+}
+
+/**
+ * The identity a tunnel request carries, or null when the request didn't
+ * come through the tunnel (e.g. a browser on the same host).
+ */
+export function getTunnelIdentity(provider: TunnelProvider, headers: IncomingHttpHeaders): TunnelIdentity | null {
+  if (provider === 'cloudflare') {
+    const clientIp = headerValue(headers, 'cf-connecting-ip');
+    if (!clientIp) return null;
+    return { user: headerValue(headers, 'cf-access-authenticated-user-email')?.toLowerCase() ?? null, clientIp };
+  }
+
+  const user = headerValue(headers, 'tailscale-user-login')?.toLowerCase() ?? null;
+  if (!user && !headerValue(headers, 'tailscale-funnel-request')) return null;
+  return { user, clientIp: null };
+}
+
+/**
+ * Sets `res.locals.tunnelUser` and the role for tunnel requests, and makes
+ * req.ip the tunnel client's. Runs before the IP filter so ALLOW_CIDR and
+ * DENY_CIDR see the real client.
+ */
+export function createTunnelMiddleware(
+  config: TunnelConfig,
+  adminToken: string | undefined = process.env.ADMIN_TOKEN,
+): RequestHandler {
+  return (req, res, next) => {
+    if (!isIpAllowed({ allow: config.connectors, deny: null }, req.socket.remoteAddress)) return next();
+    const identity = getTunnelIdentity(config.provider, req.headers);
+    if (!identity) return next();
+
+    if (identity.clientIp) {
+      Object.defineProperty(req, 'ip', { value: identity.clientIp, configurable: true });
+    }
+    res.locals.tunnelUser = identity.user;
+    // ADMIN_TOKEN still works through the tunnel.
+    if (adminToken && resolveRole(req.headers.authorization, adminToken) === 'admin') return next();
+    res.locals.role = identity.user && config.adminUsers.has(identity.user) ? 'admin' : 'viewer';
+    next();
+  };
+}
@@ -1,4 +1,4 @@
-import type { MiningMode, SetupMode, HealthStatus, PoolConfig, BitcoinConfig, JdcConfig, TranslatorConfig, SetupData } from '@sv2-ui/shared';
+import type { BitcoinNetwork, MiningMode, SetupMode, HealthStatus, PoolConfig, BitcoinConfig, JdcConfig, TranslatorConfig, SetupData } from '@sv2-ui/shared';
 
 export type { PoolConfig, BitcoinConfig, JdcConfig, TranslatorConfig, SetupData };
 
@@ -17,6 +17,7 @@ export interface StatusResponse {
   miningMode: MiningMode | null;
   mode: SetupMode | null;
   poolName: string | null;
+  network: BitcoinNetwork | null;
   containers: {
     translator: ContainerStatus | null;
     jdc: ContainerStatus | null;
@@ -0,0 +1,69 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import type { AlertTransition } from './alerts.js';
+import { createUpdateChecker, getCurrentDigest, getImageRepository, parseUpdateCheckConfig } from './updates.js';
+
+test('parseUpdateCheckConfig defaults to a 6 hour check without notifications', () => {
+  assert.deepEqual(parseUpdateCheckConfig({}), { intervalMs: 6 * 3_600_000, notify: false });
+  assert.deepEqual(parseUpdateCheckConfig({ UPDATE_CHECK_INTERVAL_HOURS: '0', UPDATE_NOTIFY: 'true' }), { intervalMs: 0, notify: true });
+  assert.throws(() => parseUpdateCheckConfig({ UPDATE_CHECK_INTERVAL_HOURS: 'daily' }), /UPDATE_CHECK_INTERVAL_HOURS must be between/);
+  assert.throws(() => parseUpdateCheckConfig({ UPDATE_NOTIFY: 'yes' }), /UPDATE_NOTIFY must be true or false/);
+});
+
+test('getCurrentDigest matches the repository, ignoring tags and registry ports', () => {
+  assert.equal(getImageRepository('stratumv2/translator_sv2:main'), 'stratumv2/translator_sv2');
+  assert.equal(getImageRepository('localhost:5000/jdc_sv2'), 'localhost:5000/jdc_sv2');
+  assert.equal(getCurrentDigest('stratumv2/translator_sv2:main', [
+    'mirror.local/translator_sv2@sha256:aaa',
+    'stratumv2/translator_sv2@sha256:bbb',
+  ]), 'sha256:bbb');
+  assert.equal(getCurrentDigest('translator:dev', []), null);
+});
+
+test('update checker reports newer images and notifies once per new digest', async () => {
+  const transitions: AlertTransition[] = [];
+  let latest = 'sha256:new';
+  let running = 'sha256:old';
+  const checker = createUpdateChecker({
+    getServices: async () => ['translator', 'jdc'],
+    // No JDC container in this stack.
+    getImage: async (service) => (service === 'translator'
+      ? { image: 'stratumv2/translator_sv2:main', repoDigests: [`stratumv2/translator_sv2@${running}`] }
+      : null),
+    getLatestDigest: async () => latest,
+    onTransitions: (next) => transitions.push(...next),
+    now: () => new Date('2026-01-01T00:00:00.000Z'),
+  });
+
+  const report = await checker.check();
+  assert.equal(report.checkedAt, '2026-01-01T00:00:00.000Z');
+  assert.deepEqual(report.services.map((status) => [status.service, status.currentDigest, status.updateAvailable]), [
+    ['translator', 'sha256:old', true],
+  ]);
+  await checker.check();
+  assert.deepEqual(transitions.map((transition) => [transition.ruleId, transition.status]), [['image-update:translator', 'firing']]);
+
+  running = latest;
+  await checker.check();
+  assert.deepEqual(transitions.map((transition) => transition.status), ['firing', 'resolved']);
+  assert.equal(checker.latest().services[0].updateAvailable, false);
+
+  latest = 'sha256:newer';
+  await checker.check();
+  assert.equal(transitions.at(-1)?.status, 'firing');
+});
+
+test('update checker records registry errors per service', async () => {
+  const checker = createUpdateChecker({
+    getServices: async () => ['translator'],
+    getImage: async () => ({ image: 'stratumv2/translator_sv2:main', repoDigests: ['stratumv2/translator_sv2@sha256:old'] }),
+    getLatestDigest: async () => {
+      throw new Error('registry unreachable');
+    },
+  });
+
+  const [status] = (await checker.check()).services;
+  assert.equal(status.updateAvailable, false);
+  assert.equal(status.error, 'registry unreachable');
+});
@@ -0,0 +1,162 @@
+/**
+ * Checks for newer sv2-apps images.
+ *
+ * Every UPDATE_CHECK_INTERVAL_HOURS the digest of the image each mining
+ * container runs is compared with the one the registry serves for the same
+ * tag, without pulling anything. GET /api/v1/updates reports the result.
+ *
+ * With UPDATE_NOTIFY set, a newly available image is sent through the alert
+ * channels as an `image-update` alert, which resolves once the container runs
+ * the new image.
+ */
+
+import crypto from 'crypto';
+import type { AlertTransition } from './alerts.js';
+import type { MonitoringService } from './monitoring.js';
+
+const DEFAULT_CHECK_INTERVAL_HOURS = 6;
+const MAX_CHECK_INTERVAL_HOURS = 168;
+
+export interface UpdateCheckConfig {
+  // 0 turns the background check off; GET /api/v1/updates then checks on demand.
+  intervalMs: number;
+  notify: boolean;
+}
+
+export interface ServiceImageStatus {
+  service: MonitoringService;
+  image: string;
+  currentDigest: string | null;
+  latestDigest: string | null;
+  updateAvailable: boolean;
+  checkedAt: string;
+  error?: string;
+}
+
+export interface UpdateReport {
+  checkedAt: string | null;
+  services: ServiceImageStatus[];
+}
+
+export interface UpdateCheckerDeps {
+  getServices: () => Promise<MonitoringService[]>;
+  // The image a service's container runs; null when it has no container.
+  getImage: (service: MonitoringService) => Promise<{ image: string; repoDigests: string[] } | null>;
+  getLatestDigest: (image: string) => Promise<string>;
+  onTransitions?: (transitions: AlertTransition[]) => void;
+  now?: () => Date;
+}
+
+export function parseUpdateCheckConfig(env: NodeJS.ProcessEnv = process.env): UpdateCheckConfig {
+  const raw = env.UPDATE_CHECK_INTERVAL_HOURS?.trim();
+  const hours = raw ? Number(raw) : DEFAULT_CHECK_INTERVAL_HOURS;
+  if (!Number.isFinite(hours) || hours < 0 || hours > MAX_CHECK_INTERVAL_HOURS) {
+    throw new Error(`UPDATE_CHECK_INTERVAL_HOURS must be between 0 and ${MAX_CHECK_INTERVAL_HOURS}, got "${raw}"`);
+  }
+
+  const notify = env.UPDATE_NOTIFY?.trim().toLowerCase();
+  if (notify && notify !== 'true' && notify !== 'false') {
+    throw new Error(`UPDATE_NOTIFY must be true or false, got "${env.UPDATE_NOTIFY}"`);
+  }
+  return { intervalMs: hours * 3_600_000, notify: notify === 'true' };
+}
+
+/**
+ * The repository part of an image reference: no tag, no digest.
+ */
+export function getImageRepository(image: string): string {
+  const withoutDigest = image.split('@')[0];
+  const slash = withoutDigest.lastIndexOf('/');
+  const colon = withoutDigest.lastIndexOf(':');
+  // A colon before the last slash is a registry port, not a tag.
+  return colon > slash ? withoutDigest.slice(0, colon) : withoutDigest;
+}
+
+/**
+ * The digest of `image` among an image's RepoDigests, or null for an image
+ * that was built locally and never pulled.
+ */
+export function getCurrentDigest(image: string, repoDigests: string[]): string | null {
+  const repository = getImageRepository(image);
+  const match = repoDigests.find((entry) => getImageRepository(entry) === repository) ?? repoDigests[0];
+  return match?.split('@')[1] ?? null;
+}
+
+export function createUpdateChecker({
+  getServices,
+  getImage,
+  getLatestDigest,
+  onTransitions,
+  now = () => new Date(),
+}: UpdateCheckerDeps) {
+  let report: UpdateReport = { checkedAt: null, services: [] };
+  let inFlight: Promise<UpdateReport> | null = null;
+
+  async function checkService(service: MonitoringService, checkedAt: string): Promise<ServiceImageStatus | null> {
+    const current = await getImage(service);
+    if (!current) return null;
+    const currentDigest = getCurrentDigest(current.image, current.repoDigests);
+    try {
+      const latestDigest = await getLatestDigest(current.image);
+      return {
+        service,
+        image: current.image,
+        currentDigest,
+        latestDigest,
+        updateAvailable: currentDigest !== null && currentDigest !== latestDigest,
+        checkedAt,
+      };
+    } catch (error) {
+      return {
+        service,
+        image: current.image,
+        currentDigest,
+        latestDigest: null,
+        updateAvailable: false,
+        checkedAt,
+        error: error instanceof Error ? error.message : String(error),
+      };
+    }
+  }
+
+  function getTransitions(previous: ServiceImageStatus[], next: ServiceImageStatus[], at: string): AlertTransition[] {
+    const transitions: AlertTransition[] = [];
+    for (const status of next) {
+      const before = previous.find((entry) => entry.service === status.service);
+      const transition = { id: crypto.randomUUID(), ruleId: `image-update:${status.service}`, kind: 'image-update' as const, at };
+      const params = { service: status.service, image: status.image };
+      if (status.updateAvailable && (!before?.updateAvailable || before.latestDigest !== status.latestDigest)) {
+        transitions.push({ ...transition, status: 'firing', message: `A newer ${status.service} image is available (${status.image})`, params });
+      } else if (before?.updateAvailable && !status.updateAvailable && !status.error) {
+        transitions.push({ ...transition, status: 'resolved', message: `${status.service} runs the latest ${status.image}`, params });
+      }
+    }
+    return transitions;
+  }
+
+  async function run(): Promise<UpdateReport> {
+    const checkedAt = now().toISOString();
+    const services = (await Promise.all((await getServices()).map((service) => checkService(service, checkedAt))))
+      .filter((status): status is ServiceImageStatus => status !== null);
+    const transitions = getTransitions(report.services, services, checkedAt);
+    report = { checkedAt, services };
+    if (transitions.length > 0) onTransitions?.(transitions);
+    return report;
+  }
+
+  /**
+   * Check every deployed service now; concurrent callers share one check.
+   */
+  function check(): Promise<UpdateReport> {
+    if (!inFlight) {
+      inFlight = run().finally(() => {
+        inFlight = null;
+      });
+    }
+    return inFlight;
+  }
+
+  return { check, latest: () => report, inFlight: () => (inFlight ? 1 : 0) };
+}
+
+export type UpdateChecker = ReturnType<typeof createUpdateChecker>;
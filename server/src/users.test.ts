@@ -0,0 +1,114 @@
+import assert from 'node:assert/strict';
+import crypto from 'node:crypto';
+import fs from 'node:fs/promises';
+import os from 'node:os';
+import path from 'node:path';
+import { test } from 'node:test';
+import { createTokenBucketLimiter } from './rate-limit.js';
+import { createUserAuthMiddleware, createUserStore, hashPassword, parseUserRequest, verifyPassword } from './users.js';
+
+async function openStore() {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), 'sv2-users-'));
+  return createUserStore(path.join(dir, 'users.db'));
+}
+
+const basic = (username: string, password: string) => `Basic ${Buffer.from(`${username}:${password}`).toString('base64')}`;
+
+async function authenticate(middleware: ReturnType<typeof createUserAuthMiddleware>, authorization?: string) {
+  const locals: Record<string, unknown> = {};
+  await new Promise<void>((resolve) => middleware({ headers: { authorization } } as never, { locals } as never, () => resolve()));
+  return locals;
+}
+
+test('validates user requests and hashes passwords', async () => {
+  assert.deepEqual(parseUserRequest({ username: 'alice', password: 'correct horse', role: 'operator' }), {
+    username: 'alice',
+    password: 'correct horse',
+    role: 'operator',
+  });
+  assert.deepEqual(parseUserRequest({ role: 'viewer' }, { partial: true }), { role: 'viewer' });
+  assert.throws(() => parseUserRequest({ username: 'alice', password: 'short', role: 'viewer' }), /password must be 8-256/);
+  assert.throws(() => parseUserRequest({ username: 'a b', password: 'correct horse', role: 'viewer' }), /username/);
+  assert.throws(() => parseUserRequest({ username: 'alice', password: 'correct horse', role: 'root' }), /viewer, operator, admin/);
+  assert.throws(() => parseUserRequest({}, { partial: true }), /Nothing to update/);
+
+  const hash = await hashPassword('correct horse');
+  assert.notEqual(hash, await hashPassword('correct horse'));
+  assert.equal(await verifyPassword('correct horse', hash), true);
+  assert.equal(await verifyPassword('battery staple', hash), false);
+});
+
+test('always keeps an admin', async () => {
+  const users = await openStore();
+  await assert.rejects(users.create({ username: 'bob', password: 'correct horse', role: 'viewer' }), /first user must be an admin/);
+
+  const alice = await users.create({ username: 'alice', password: 'correct horse', role: 'admin' });
+  const bob = await users.create({ username: 'bob', password: 'battery staple', role: 'viewer' });
+  await assert.rejects(users.create({ username: 'Alice', password: 'correct horse', role: 'viewer' }), /taken/);
+  await assert.rejects(users.update(alice.id, { role: 'operator' }), /last admin/);
+  assert.throws(() => users.remove(alice.id), /last admin/);
+
+  assert.equal((await users.update(bob.id, { role: 'admin' }))?.role, 'admin');
+  assert.equal((await users.update(alice.id, { role: 'operator' }))?.role, 'operator');
+  assert.deepEqual(users.list().map(({ username, role }) => [username, role]), [['alice', 'operator'], ['bob', 'admin']]);
+  assert.equal(await users.update('missing', { role: 'viewer' }), null);
+  assert.equal(users.remove(alice.id), true);
+  // The only account left may go too, which opens the server up again.
+  assert.equal(users.remove(bob.id), true);
+  assert.equal(users.count(), 0);
+});
+
+test('signs users in with Basic credentials and treats everyone else as a viewer', async () => {
+  const users = await openStore();
+  const middleware = createUserAuthMiddleware(users, { adminToken: 's3cret' });
+  // No accounts yet: roles are decided as before.
+  assert.deepEqual(await authenticate(middleware), {});
+
+  const alice = await users.create({ username: 'alice', password: 'correct horse', role: 'admin' });
+  await users.create({ username: 'bob', password: 'battery staple', role: 'operator' });
+  assert.deepEqual(await authenticate(middleware, basic('bob', 'battery staple')), { user: 'bob', role: 'operator' });
+  assert.deepEqual(await authenticate(middleware, basic('BOB', 'battery staple')), { user: 'bob', role: 'operator' });
+  assert.deepEqual(await authenticate(middleware, basic('bob', 'wrong password')), { role: 'viewer' });
+  assert.deepEqual(await authenticate(middleware, basic('carol', 'battery staple')), { role: 'viewer' });
+  assert.deepEqual(await authenticate(middleware), { role: 'viewer' });
+  assert.deepEqual(await authenticate(middleware, 'Bearer s3cret'), {});
+
+  // Changes take effect on the next request, cached or not.
+  assert.deepEqual(await authenticate(middleware, basic('alice', 'correct horse')), { user: 'alice', role: 'admin' });
+  await users.update(alice.id, { password: 'new password' });
+  assert.deepEqual(await authenticate(middleware, basic('alice', 'correct horse')), { role: 'viewer' });
+  assert.deepEqual(await authenticate(middleware, basic('alice', 'new password')), { user: 'alice', role: 'admin' });
+});
+
+test('Basic credential checks share the login rate limit', async () => {
+  const users = await openStore();
+  await users.create({ username: 'alice', password: 'correct horse', role: 'admin' });
+  const limiter = createTokenBucketLimiter({ ratePerSecond: 1 / 6, burst: 2 });
+  const middleware = createUserAuthMiddleware(users, { adminToken: 's3cret', limiter });
+
+  const send = (authorization: string) => new Promise<{ status: number; locals: Record<string, unknown> }>((resolve) => {
+    const locals: Record<string, unknown> = {};
+    const res = {
+      locals,
+      set: () => res,
+      status: (status: number) => ({ json: () => resolve({ status, locals }) }),
+    };
+    middleware({ ip: '10.0.0.1', headers: { authorization } } as never, res as never, () => resolve({ status: 200, locals }));
+  });
+
+  assert.deepEqual(await send(basic('alice', 'correct horse')), { status: 200, locals: { user: 'alice', role: 'admin' } });
+  assert.deepEqual(await send(basic('alice', 'guess 1')), { status: 200, locals: { role: 'viewer' } });
+  assert.equal((await send(basic('alice', 'guess 2'))).status, 429);
+  // Credentials checked a moment ago don't need another check.
+  assert.deepEqual(await send(basic('alice', 'correct horse')), { status: 200, locals: { user: 'alice', role: 'admin' } });
+});
+
+test('unknown usernames are checked against a password hash too', async (t) => {
+  const users = await openStore();
+  await users.create({ username: 'alice', password: 'correct horse', role: 'admin' });
+  const scrypt = t.mock.method(crypto, 'scrypt');
+  assert.equal(await users.authenticate('mallory', 'correct horse'), null);
+  assert.equal(await users.authenticate('alice', 'wrong password'), null);
+  assert.equal(scrypt.mock.callCount(), 2);
+  assert.equal((await users.authenticate('alice', 'correct horse'))?.username, 'alice');
+});
@@ -0,0 +1,308 @@
+/**
+ * User accounts, for setups where more than one person looks after the
+ * stack, such as a household or a mining co-op.
+ *
+ * Accounts live in SQLite at CONFIG_DIR/users.db, each with a role (see
+ * auth.ts). Users sign in with `Authorization: Basic <username:password>`,
+ * or through a login that sets a session cookie (see sessions.ts).
+ * Only a salted scrypt hash of each password is stored.
+ *
+ * Once an account exists, nobody is admin by default: requests without
+ * credentials are viewers even while ADMIN_TOKEN is unset. ADMIN_TOKEN, API
+ * keys, the auth hook and tunnel identities keep working alongside accounts.
+ * There is always at least one admin left, so the accounts can't lock
+ * themselves out.
+ */
+
+import crypto from 'crypto';
+import fs from 'fs';
+import path from 'path';
+import { DatabaseSync } from 'node:sqlite';
+import type { RequestHandler, Response } from 'express';
+import { resolveRole, ROLES } from './auth.js';
+import type { Role } from './auth.js';
+import { parseCredentials } from './auth-hook.js';
+import { createTokenBucketLimiter, LOGIN_RATE_LIMIT, sendRateLimited } from './rate-limit.js';
+import type { TokenBucketLimiter } from './rate-limit.js';
+
+export interface User {
+  id: string;
+  username: string;
+  role: Role;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface UserRequest {
+  username?: string;
+  password?: string;
+  role?: Role;
+}
+
+interface UserRow {
+  id: string;
+  username: string;
+  role: Role;
+  password_hash: string;
+  created_at: number;
+  updated_at: number;
+}
+
+const SCHEMA = `
+CREATE TABLE IF NOT EXISTS users (
+  id TEXT PRIMARY KEY,
+  username TEXT NOT NULL UNIQUE COLLATE NOCASE,
+  role TEXT NOT NULL,
+  password_hash TEXT NOT NULL,
+  created_at INTEGER NOT NULL,
+  updated_at INTEGER NOT NULL
+);
+`;
+
+const USERNAME = /^[A-Za-z0-9_.@-]{1,64}$/;
+const MIN_PASSWORD_LENGTH = 8;
+const MAX_PASSWORD_LENGTH = 256;
+const SCRYPT_KEY_LENGTH = 32;
+// Checked for unknown usernames, so they take as long to reject as wrong passwords.
+const DUMMY_HASH = `scrypt:${Buffer.alloc(16).toString('base64')}:${Buffer.alloc(SCRYPT_KEY_LENGTH).toString('base64')}`;
+// Verified credentials are remembered briefly, since scrypt is slow on purpose.
+const VERIFY_CACHE_MS = 60_000;
+const MAX_CACHE_ENTRIES = 1000;
+
+export class UserError extends Error {
+  constructor(message: string, readonly status = 400) {
+    super(message);
+    this.name = 'UserError';
+  }
+}
+
+/**
+ * Validate a create (every field required) or update (any field) request.
+ */
+export function parseUserRequest(body: unknown, { partial = false } = {}): UserRequest {
+  const { username, password, role } = (body ?? {}) as Record<string, unknown>;
+  const request: UserRequest = {};
+
+  if (username !== undefined || !partial) {
+    if (typeof username !== 'string' || !USERNAME.test(username)) {
+      throw new UserError('username must be 1-64 letters, digits, or _ . @ -');
+    }
+    request.username = username;
+  }
+  if (password !== undefined || !partial) {
+    if (typeof password !== 'string' || password.length < MIN_PASSWORD_LENGTH || password.length > MAX_PASSWORD_LENGTH) {
+      throw new UserError(`password must be ${MIN_PASSWORD_LENGTH}-${MAX_PASSWORD_LENGTH} characters`);
+    }
+    request.password = password;
+  }
+  if (role !== undefined || !partial) {
+    if (!ROLES.includes(role as Role)) {
+      throw new UserError(`role must be one of ${ROLES.join(', ')}`);
+    }
+    request.role = role as Role;
+  }
+  if (partial && Object.keys(request).length === 0) {
+    throw new UserError('Nothing to update; send username, password or role');
+  }
+  return request;
+}
+
+// scrypt is slow on purpose, so it runs on the thread pool rather than the event loop.
+const scrypt = (password: string, salt: Buffer, keyLength: number) => new Promise<Buffer>((resolve, reject) => {
+  crypto.scrypt(password, salt, keyLength, (error, key) => (error ? reject(error) : resolve(key)));
+});
+
+export async function hashPassword(password: string): Promise<string> {
+  const salt = crypto.randomBytes(16);
+  const hash = await scrypt(password, salt, SCRYPT_KEY_LENGTH);
+  return `scrypt:${salt.toString('base64')}:${hash.toString('base64')}`;
+}
+
+export async function verifyPassword(password: string, stored: string): Promise<boolean> {
+  const [scheme, salt, hash] = stored.split(':');
+  if (scheme !== 'scrypt' || !salt || !hash) return false;
+  const expected = Buffer.from(hash, 'base64');
+  const actual = await scrypt(password, Buffer.from(salt, 'base64'), expected.length);
+  return crypto.timingSafeEqual(actual, expected);
+}
+
+const toUser = (row: UserRow): User => ({
+  id: row.id,
+  username: row.username,
+  role: row.role,
+  createdAt: new Date(row.created_at).toISOString(),
+  updatedAt: new Date(row.updated_at).toISOString(),
+});
+
+export function createUserStore(filePath: string, now: () => number = Date.now) {
+  let db: DatabaseSync | null = null;
+  // Bumped on every change, so cached verifications of old passwords and roles are dropped.
+  let generation = 0;
+
+  function open(): DatabaseSync {
+    if (!db) {
+      fs.mkdirSync(path.dirname(filePath), { recursive: true });
+      db = new DatabaseSync(filePath);
+      // No WAL: writes are rare, and backups copy the file as it is (see backup.ts).
+      db.exec(SCHEMA);
+    }
+    return db;
+  }
+
+  function getRow(id: string): UserRow | null {
+    return (open().prepare('SELECT * FROM users WHERE id = ?').get(id) as UserRow | undefined) ?? null;
+  }
+
+  function countAdmins(): number {
+    return (open().prepare("SELECT COUNT(*) AS count FROM users WHERE role = 'admin'").get() as { count: number }).count;
+  }
+
+  function isTaken(username: string, exceptId: string | null): boolean {
+    const row = open().prepare('SELECT id FROM users WHERE username = ?').get(username) as { id: string } | undefined;
+    return row !== undefined && row.id !== exceptId;
+  }
+
+  function list(): User[] {
+    return (open().prepare('SELECT * FROM users ORDER BY username').all() as unknown as UserRow[]).map(toUser);
+  }
+
+  function count(): number {
+    return (open().prepare('SELECT COUNT(*) AS count FROM users').get() as { count: number }).count;
+  }
+
+  function get(id: string): User | null {
+    const row = getRow(id);
+    return row ? toUser(row) : null;
+  }
+
+  async function create({ username, password, role }: Required<UserRequest>): Promise<User> {
+    const passwordHash = await hashPassword(password);
+    // Checked after hashing, so nothing changes in between.
+    if (isTaken(username, null)) throw new UserError(`username "${username}" is taken`, 409);
+    if (role !== 'admin' && count() === 0) throw new UserError('The first user must be an admin', 409);
+    const id = crypto.randomUUID();
+    const at = now();
+    open()
+      .prepare('INSERT INTO users (id, username, role, password_hash, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)')
+      .run(id, username, role, passwordHash, at, at);
+    generation++;
+    return get(id)!;
+  }
+
+  /**
+   * Change a user's name, password or role; null when there is no such user.
+   */
+  async function update(id: string, { username, password, role }: UserRequest): Promise<User | null> {
+    const passwordHash = password === undefined ? null : await hashPassword(password);
+    const row = getRow(id);
+    if (!row) return null;
+    if (username !== undefined && isTaken(username, id)) throw new UserError(`username "${username}" is taken`, 409);
+    if (row.role === 'admin' && role !== undefined && role !== 'admin' && countAdmins() === 1) {
+      throw new UserError('The last admin must stay an admin', 409);
+    }
+    open()
+      .prepare('UPDATE users SET username = ?, role = ?, password_hash = ?, updated_at = ? WHERE id = ?')
+      .run(username ?? row.username, role ?? row.role, passwordHash ?? row.password_hash, now(), id);
+    generation++;
+    return get(id);
+  }
+
+  function remove(id: string): boolean {
+    const row = getRow(id);
+    if (!row) return false;
+    if (row.role === 'admin' && countAdmins() === 1 && count() > 1) {
+      throw new UserError('Remove the other users or make one an admin before removing the last admin', 409);
+    }
+    open().prepare('DELETE FROM users WHERE id = ?').run(id);
+    generation++;
+    return true;
+  }
+
+  /**
+   * The user with these credentials, or null. Unknown usernames take as long
+   * as wrong passwords, so they don't give away which accounts exist.
+   */
+  async function authenticate(username: string, password: string): Promise<User | null> {
+    const row = open().prepare('SELECT * FROM users WHERE username = ?').get(username) as UserRow | undefined;
+    const valid = await verifyPassword(password, row?.password_hash ?? DUMMY_HASH);
+    return row && valid ? toUser(row) : null;
+  }
+
+  return { list, count, get, create, update, remove, authenticate, generation: () => generation };
+}
+
+export type UserStore = ReturnType<typeof createUserStore>;
+
+/**
+ * Sign in requests that send a user's credentials, setting
+ * `res.locals.user` and the role. Once any account exists, other requests
+ * without a role of their own are viewers. Runs before the auth hook, which
+ * checks the Basic credentials that don't belong to a user, and after the
+ * session cookie. Credentials it hasn't checked recently count against
+ * `limiter`, the login rate limit, so Basic auth can't be used to guess
+ * passwords faster than the login form; past it, requests get 429.
+ */
+export function createUserAuthMiddleware(
+  users: UserStore,
+  {
+    adminToken = process.env.ADMIN_TOKEN,
+    now = Date.now,
+    limiter = createTokenBucketLimiter(LOGIN_RATE_LIMIT),
+  }: { adminToken?: string; now?: () => number; limiter?: TokenBucketLimiter } = {},
+): RequestHandler {
+  // Keyed by a hash of the Authorization header, so passwords aren't kept in memory.
+  const cache = new Map<string, { user: User | null; generation: number; expiresAt: number }>();
+
+  function cached(key: string): { user: User | null } | null {
+    const entry = cache.get(key);
+    return entry && entry.generation === users.generation() && entry.expiresAt > now() ? entry : null;
+  }
+
+  async function verify(key: string, username: string, password: string): Promise<User | null> {
+    const generation = users.generation();
+    const user = await users.authenticate(username, password);
+    if (cache.size >= MAX_CACHE_ENTRIES) cache.delete(cache.keys().next().value!);
+    cache.set(key, { user, generation, expiresAt: now() + VERIFY_CACHE_MS });
+    return user;
+  }
+
+  function signIn(res: Response, user: User | null): void {
+    if (user) {
+      res.locals.user = user.username;
+      res.locals.role = user.role;
+    } else {
+      res.locals.role = 'viewer';
+    }
+  }
+
+  return (req, res, next) => {
+    if (res.locals.apiKeyId) return next();
+    // Already signed in with a session cookie (see sessions.ts).
+    if (res.locals.user !== undefined) return next();
+    // Already identified by the tunnel (see tunnel.ts).
+    if (res.locals.tunnelUser !== undefined) return next();
+    if (adminToken && resolveRole(req.headers.authorization, adminToken) === 'admin') return next();
+    if (users.count() === 0) return next();
+
+    const credentials = parseCredentials(req.headers.authorization);
+    if (credentials?.scheme !== 'basic') {
+      res.locals.role = 'viewer';
+      return next();
+    }
+
+    const key = crypto.createHash('sha256').update(req.headers.authorization!).digest('hex');
+    const hit = cached(key);
+    if (hit) {
+      signIn(res, hit.user);
+      return next();
+    }
+    // Each check runs scrypt, so they share the login rate limit.
+    const retryAfterMs = limiter.take(req.ip ?? 'unknown', now());
+    if (retryAfterMs > 0) return sendRateLimited(res, retryAfterMs);
+
+    verify(key, credentials.username!, credentials.password!).then((user) => {
+      signIn(res, user);
+      next();
+    }, next);
+  };
+}
@@ -0,0 +1,108 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+
+import type { StackData } from './monitoring.js';
+import { createVardiffTracker, targetToDifficulty, VARDIFF_DIFFICULTY_METRIC, VARDIFF_SHARE_RATE_METRIC } from './vardiff.js';
+
+const DIFFICULTY_1 = '00000000ffff0000000000000000000000000000000000000000000000000000';
+const DIFFICULTY_2 = '000000007fff8000000000000000000000000000000000000000000000000000';
+
+const stack = (targetHex: string, submitted: number, jdcAccepted: number): StackData => ({
+  mode: 'no-jd',
+  upstream: 'translator',
+  global: null,
+  serverChannels: {
+    extended_channels: [{
+      channel_id: 7,
+      user_identity: 'pool-user',
+      target_hex: targetHex,
+      best_diff: 0,
+      blocks_found: 0,
+      shares_acknowledged: submitted,
+      shares_submitted: submitted,
+      shares_rejected: 0,
+      shares_rejected_by_reason: {},
+    }],
+    standard_channels: [],
+    total_extended: 1,
+    total_standard: 0,
+  },
+  sv1Clients: {
+    items: [{ client_id: 1, channel_id: 7, authorized_worker_name: 's19-a', user_identity: 'pool-user', target_hex: targetHex }],
+    total: 1,
+  },
+  sv2Clients: [{
+    client_id: 3,
+    extended_channels: [{
+      channel_id: 1,
+      user_identity: 'bitaxe',
+      nominal_hashrate: 1e12,
+      target_hex: DIFFICULTY_1,
+      expected_shares_per_minute: 10,
+      best_diff: 0,
+      blocks_found: 0,
+      shares_accepted: jdcAccepted,
+      shares_rejected: 0,
+      shares_rejected_by_reason: {},
+    }],
+    standard_channels: [],
+  }],
+});
+
+test('targetToDifficulty reads big-endian hex targets', () => {
+  assert.equal(targetToDifficulty(DIFFICULTY_1), 1);
+  assert.equal(targetToDifficulty(DIFFICULTY_2), 2);
+  assert.equal(targetToDifficulty('00'), null);
+  assert.equal(targetToDifficulty('not hex'), null);
+});
+
+test('retargets and achieved share rates are tracked between polls', () => {
+  const tracker = createVardiffTracker();
+  const first = tracker.observe(stack(DIFFICULTY_1, 100, 50), '2026-01-01T00:00:00.000Z');
+  assert.deepEqual(first.map(({ metric, key, value }) => [metric, key, value]), [
+    [VARDIFF_DIFFICULTY_METRIC, 'translator:sv1:1', 1],
+    [VARDIFF_DIFFICULTY_METRIC, 'jdc:client:3:1', 1],
+  ]);
+
+  const second = tracker.observe(stack(DIFFICULTY_2, 112, 70), '2026-01-01T00:02:00.000Z');
+  assert.deepEqual(second.map(({ metric, key, value }) => [metric, key, value]), [
+    [VARDIFF_DIFFICULTY_METRIC, 'translator:sv1:1', 2],
+    [VARDIFF_SHARE_RATE_METRIC, 'translator:sv1:1', 6],
+    [VARDIFF_DIFFICULTY_METRIC, 'jdc:client:3:1', 1],
+    [VARDIFF_SHARE_RATE_METRIC, 'jdc:client:3:1', 10],
+  ]);
+
+  const [sv1, jdc] = tracker.list(6);
+  assert.deepEqual(sv1, {
+    key: 'translator:sv1:1',
+    service: 'translator',
+    worker: 's19-a',
+    targetHex: DIFFICULTY_2,
+    difficulty: 2,
+    configuredSharesPerMinute: 6,
+    achievedSharesPerMinute: 6,
+    retargets: 1,
+    lastRetargetAt: '2026-01-01T00:02:00.000Z',
+  });
+  assert.equal(jdc.configuredSharesPerMinute, 10);
+  assert.equal(jdc.retargets, 0);
+  assert.deepEqual(tracker.recentRetargets(), [{
+    at: '2026-01-01T00:02:00.000Z',
+    key: 'translator:sv1:1',
+    worker: 's19-a',
+    fromDifficulty: 1,
+    toDifficulty: 2,
+  }]);
+});
+
+test('SV1 share rates are unknown when miners share an upstream channel', () => {
+  const tracker = createVardiffTracker();
+  const shared = (submitted: number): StackData => {
+    const data = stack(DIFFICULTY_1, submitted, 0);
+    data.sv1Clients!.items.push({ ...data.sv1Clients!.items[0], client_id: 2, authorized_worker_name: 's19-b' });
+    return data;
+  };
+  tracker.observe(shared(100), '2026-01-01T00:00:00.000Z');
+  tracker.observe(shared(130), '2026-01-01T00:01:00.000Z');
+  assert.deepEqual(tracker.list(6).filter((channel) => channel.service === 'translator').map((channel) => channel.achievedSharesPerMinute), [null, null]);
+});
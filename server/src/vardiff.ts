@@ -0,0 +1,198 @@
+/**
+ * What variable difficulty is doing, per downstream channel, for
+ * /api/v1/vardiff: the current target and difficulty, when it was last
+ * retargeted, and the share rate it achieves against the configured one.
+ *
+ * The monitoring APIs only report targets and share counters, so retargets
+ * are seen as a channel's target changing between polls, and the achieved
+ * rate comes from the share counters' growth. Channels covered:
+ *
+ * - Translator SV1 miners. Their shares are counted on the upstream channel
+ *   the Translator opens for them, so the rate is only known when a miner
+ *   has a channel of its own (aggregate_channels off, Translator upstream).
+ * - JDC downstream channels, which report their expected rate themselves.
+ *
+ * Difficulty and achieved rate go to the history database after every
+ * poll, keyed like the channels.
+ */
+
+import type { HistorySample } from './history.js';
+import type { MonitoringService, ServerChannelInfo, StackData } from './monitoring.js';
+import { getMinerId } from './stack-history.js';
+
+export const VARDIFF_DIFFICULTY_METRIC = 'vardiff.difficulty';
+export const VARDIFF_SHARE_RATE_METRIC = 'vardiff.shares_per_minute';
+
+const MAX_RETARGETS = 500;
+
+// Difficulty 1, the target every difficulty is relative to.
+const DIFFICULTY_1_TARGET = 0xffffn << 208n;
+
+export interface VardiffChannel {
+  key: string;
+  service: MonitoringService;
+  worker: string;
+  targetHex: string;
+  difficulty: number | null;
+  // The Translator's shares_per_minute for SV1 miners; the JDC's own figure otherwise.
+  configuredSharesPerMinute: number | null;
+  // Since the previous poll; null until there are two readings or the counters aren't reported.
+  achievedSharesPerMinute: number | null;
+  // Seen since the channel's first poll.
+  retargets: number;
+  lastRetargetAt: string | null;
+}
+
+export interface VardiffRetarget {
+  at: string;
+  key: string;
+  worker: string;
+  fromDifficulty: number | null;
+  toDifficulty: number | null;
+}
+
+interface ChannelReading {
+  key: string;
+  service: MonitoringService;
+  worker: string;
+  targetHex: string;
+  submitted: number | null;
+  configured: number | null;
+}
+
+/**
+ * The difficulty a big-endian hex target stands for; null if it isn't one.
+ */
+export function targetToDifficulty(targetHex: string): number | null {
+  if (!/^[0-9a-f]{1,64}$/i.test(targetHex)) return null;
+  const target = BigInt(`0x${targetHex}`);
+  if (target === 0n) return null;
+  return Number(DIFFICULTY_1_TARGET) / Number(target);
+}
+
+export function readVardiffChannels(data: StackData): ChannelReading[] {
+  const readings: ChannelReading[] = [];
+
+  // Upstream channels opened by the Translator, and how many miners share each.
+  const upstream = new Map<number, ServerChannelInfo>();
+  if (data.upstream === 'translator' && data.serverChannels) {
+    for (const channel of [...data.serverChannels.extended_channels, ...data.serverChannels.standard_channels]) {
+      upstream.set(channel.channel_id, channel);
+    }
+  }
+  const sv1 = data.sv1Clients?.items ?? [];
+  const perChannel = new Map<number, number>();
+  for (const client of sv1) {
+    if (client.channel_id != null) perChannel.set(client.channel_id, (perChannel.get(client.channel_id) ?? 0) + 1);
+  }
+  for (const client of sv1) {
+    const channel = client.channel_id != null && perChannel.get(client.channel_id) === 1
+      ? upstream.get(client.channel_id)
+      : undefined;
+    readings.push({
+      key: `translator:sv1:${client.client_id}`,
+      service: 'translator',
+      worker: getMinerId(client),
+      targetHex: client.target_hex,
+      submitted: channel ? channel.shares_submitted : null,
+      configured: null,
+    });
+  }
+
+  for (const client of data.sv2Clients ?? []) {
+    for (const channel of [...client.extended_channels, ...client.standard_channels]) {
+      readings.push({
+        key: `jdc:client:${client.client_id}:${channel.channel_id}`,
+        service: 'jdc',
+        worker: channel.user_identity,
+        targetHex: channel.target_hex,
+        submitted: channel.shares_accepted + channel.shares_rejected,
+        configured: channel.expected_shares_per_minute,
+      });
+    }
+  }
+
+  return readings;
+}
+
+export function createVardiffTracker({ maxRetargets = MAX_RETARGETS }: { maxRetargets?: number } = {}) {
+  let previous: { at: string; readings: Map<string, ChannelReading> } | null = null;
+  let channels = new Map<string, Omit<VardiffChannel, 'configuredSharesPerMinute'> & { configured: number | null }>();
+  let retargets: VardiffRetarget[] = [];
+
+  /**
+   * Compare a poll's readings with the previous one's, returning the samples to record.
+   */
+  function observe(data: StackData, at: string): HistorySample[] {
+    const readings = readVardiffChannels(data);
+    const elapsedMinutes = previous ? (Date.parse(at) - Date.parse(previous.at)) / 60_000 : 0;
+    const next: typeof channels = new Map();
+    const samples: HistorySample[] = [];
+
+    for (const reading of readings) {
+      const prior = previous?.readings.get(reading.key);
+      const tracked = channels.get(reading.key);
+      const difficulty = targetToDifficulty(reading.targetHex);
+      let count = tracked?.retargets ?? 0;
+      let lastRetargetAt = tracked?.lastRetargetAt ?? null;
+      if (prior && prior.targetHex !== reading.targetHex) {
+        count += 1;
+        lastRetargetAt = at;
+        retargets.push({
+          at,
+          key: reading.key,
+          worker: reading.worker,
+          fromDifficulty: targetToDifficulty(prior.targetHex),
+          toDifficulty: difficulty,
+        });
+      }
+
+      // Counters restart when a channel reopens under the same id.
+      const achieved = prior && prior.submitted !== null && reading.submitted !== null
+        && reading.submitted >= prior.submitted && elapsedMinutes > 0
+        ? (reading.submitted - prior.submitted) / elapsedMinutes
+        : null;
+
+      next.set(reading.key, {
+        key: reading.key,
+        service: reading.service,
+        worker: reading.worker,
+        targetHex: reading.targetHex,
+        difficulty,
+        configured: reading.configured,
+        achievedSharesPerMinute: achieved,
+        retargets: count,
+        lastRetargetAt,
+      });
+      samples.push({ metric: VARDIFF_DIFFICULTY_METRIC, key: reading.key, at, value: difficulty });
+      if (achieved !== null) samples.push({ metric: VARDIFF_SHARE_RATE_METRIC, key: reading.key, at, value: achieved });
+    }
+
+    if (retargets.length > maxRetargets) retargets = retargets.slice(-maxRetargets);
+    channels = next;
+    previous = { at, readings: new Map(readings.map((reading) => [reading.key, reading])) };
+    return samples;
+  }
+
+  /**
+   * Channels in the latest poll. `sharesPerMinute` is the Translator's
+   * configured rate, which its API doesn't report.
+   */
+  function list(sharesPerMinute: number | null): VardiffChannel[] {
+    return [...channels.values()].map(({ configured, ...channel }) => ({
+      ...channel,
+      configuredSharesPerMinute: channel.service === 'translator' ? sharesPerMinute : configured,
+    }));
+  }
+
+  /**
+   * Most recent first.
+   */
+  function recentRetargets(limit = 100): VardiffRetarget[] {
+    return retargets.slice(-limit).reverse();
+  }
+
+  return { observe, list, recentRetargets };
+}
+
+export type VardiffTracker = ReturnType<typeof createVardiffTracker>;
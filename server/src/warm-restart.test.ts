@@ -0,0 +1,65 @@
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import type { MonitoringService } from './monitoring.js';
+import { getRestartOrder, runWarmRestart } from './warm-restart.js';
+import type { WarmRestartDeps, WarmRestartProgress } from './warm-restart.js';
+
+function fakeDeps(overrides: Partial<WarmRestartDeps> = {}) {
+  const calls: string[] = [];
+  let clock = 0;
+  const deps: WarmRestartDeps = {
+    pauseAlerts: () => {
+      calls.push('pause');
+      return () => calls.push('resume');
+    },
+    restart: async (service: MonitoringService) => {
+      calls.push(`restart:${service}`);
+    },
+    isHealthy: async () => true,
+    pollNow: async () => {
+      calls.push('poll');
+    },
+    now: () => new Date(clock),
+    sleep: async (ms) => {
+      clock += ms;
+    },
+    ...overrides,
+  };
+  return { deps, calls };
+}
+
+test('restarts JDC before the Translator in JD mode', () => {
+  assert.deepEqual(getRestartOrder('all', 'jd'), ['jdc', 'translator']);
+  assert.deepEqual(getRestartOrder('all', 'no-jd'), ['translator']);
+  assert.deepEqual(getRestartOrder('translator', 'jd'), ['translator']);
+  assert.throws(() => getRestartOrder('jdc', 'no-jd'), /JD mode/);
+});
+
+test('runs every step in order and reports progress', async () => {
+  const { deps, calls } = fakeDeps();
+  const progress: WarmRestartProgress[] = [];
+
+  assert.equal(await runWarmRestart(['jdc', 'translator'], deps, (entry) => progress.push(entry)), true);
+  assert.deepEqual(calls, ['pause', 'restart:jdc', 'restart:translator', 'poll', 'resume']);
+  assert.deepEqual(
+    progress.filter((entry) => entry.status === 'done').map((entry) => `${entry.step}${entry.service ? `:${entry.service}` : ''}`),
+    ['pause-alerts', 'restart:jdc', 'restart:translator', 'wait-health:jdc', 'wait-health:translator', 'poll-now', 'resume-alerts'],
+  );
+});
+
+test('gives up waiting for health and still resumes alerts', async () => {
+  const { deps, calls } = fakeDeps({ isHealthy: async () => false });
+  const progress: WarmRestartProgress[] = [];
+
+  const success = await runWarmRestart(['translator'], deps, (entry) => progress.push(entry), {
+    healthTimeoutMs: 5000,
+    healthIntervalMs: 1000,
+  });
+
+  assert.equal(success, false);
+  assert.deepEqual(calls, ['pause', 'restart:translator', 'resume']);
+  const failed = progress.find((entry) => entry.status === 'failed');
+  assert.equal(failed?.step, 'wait-health');
+  assert.match(failed?.message ?? '', /Not healthy after 5s/);
+  assert.equal(progress.at(-1)?.step, 'resume-alerts');
+});
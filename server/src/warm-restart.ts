@@ -0,0 +1,103 @@
+/**
+ * Warm restart: the sequence operators otherwise run by hand.
+ *
+ *   pause alerts → restart container(s) → wait for health → poll now → resume alerts
+ *
+ * Each step reports progress through a callback so the route can stream it.
+ * Alerts are resumed even when an earlier step fails.
+ */
+
+import type { MonitoringService } from './monitoring.js';
+
+export type WarmRestartStep = 'pause-alerts' | 'restart' | 'wait-health' | 'poll-now' | 'resume-alerts';
+
+export interface WarmRestartProgress {
+  step: WarmRestartStep;
+  status: 'started' | 'done' | 'failed';
+  service?: MonitoringService;
+  message?: string;
+  at: string;
+}
+
+export interface WarmRestartDeps {
+  pauseAlerts: () => () => void;
+  restart: (service: MonitoringService) => Promise<void>;
+  isHealthy: (service: MonitoringService) => Promise<boolean>;
+  pollNow: () => Promise<void>;
+  now?: () => Date;
+  sleep?: (ms: number) => Promise<void>;
+}
+
+export interface WarmRestartOptions {
+  healthTimeoutMs?: number;
+  healthIntervalMs?: number;
+}
+
+export const DEFAULT_HEALTH_TIMEOUT_MS = 60_000;
+const DEFAULT_HEALTH_INTERVAL_MS = 1000;
+
+/**
+ * Services to restart, in order. JDC goes first so the Translator reconnects
+ * to an already-running upstream (same ordering as startStack).
+ */
+export function getRestartOrder(target: MonitoringService | 'all', mode: 'jd' | 'no-jd' | null): MonitoringService[] {
+  if (target === 'jdc' && mode !== 'jd') {
+    throw new Error('JDC only runs in JD mode');
+  }
+  if (target !== 'all') return [target];
+  return mode === 'jd' ? ['jdc', 'translator'] : ['translator'];
+}
+
+export async function runWarmRestart(
+  services: MonitoringService[],
+  deps: WarmRestartDeps,
+  onProgress: (progress: WarmRestartProgress) => void,
+  { healthTimeoutMs = DEFAULT_HEALTH_TIMEOUT_MS, healthIntervalMs = DEFAULT_HEALTH_INTERVAL_MS }: WarmRestartOptions = {},
+): Promise<boolean> {
+  const now = deps.now ?? (() => new Date());
+  const sleep = deps.sleep ?? ((ms: number) => new Promise<void>((resolve) => setTimeout(resolve, ms)));
+  const report = (progress: Omit<WarmRestartProgress, 'at'>) => onProgress({ ...progress, at: now().toISOString() });
+
+  async function step(name: WarmRestartStep, run: () => Promise<void>, service?: MonitoringService): Promise<void> {
+    report({ step: name, status: 'started', service });
+    try {
+      await run();
+      report({ step: name, status: 'done', service });
+    } catch (error) {
+      report({ step: name, status: 'failed', service, message: error instanceof Error ? error.message : String(error) });
+      throw error;
+    }
+  }
+
+  let resumeAlerts: (() => void) | null = null;
+  try {
+    await step('pause-alerts', async () => {
+      resumeAlerts = deps.pauseAlerts();
+    });
+
+    for (const service of services) {
+      await step('restart', () => deps.restart(service), service);
+    }
+
+    for (const service of services) {
+      await step('wait-health', async () => {
+        const deadline = now().getTime() + healthTimeoutMs;
+        while (!(await deps.isHealthy(service))) {
+          if (now().getTime() >= deadline) {
+            throw new Error(`Not healthy after ${Math.round(healthTimeoutMs / 1000)}s`);
+          }
+          await sleep(healthIntervalMs);
+        }
+      }, service);
+    }
+
+    await step('poll-now', deps.pollNow);
+    return true;
+  } catch {
+    return false;
+  } finally {
+    await step('resume-alerts', async () => {
+      resumeAlerts?.();
+    }).catch(() => undefined);
+  }
+}
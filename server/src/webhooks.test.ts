@@ -0,0 +1,97 @@
+import assert from 'node:assert/strict';
+import http from 'node:http';
+import { test } from 'node:test';
+import type { AlertTransition } from './alerts.js';
+import { createWebhookNotifier, parseWebhookConfig, signWebhookPayload } from './webhooks.js';
+
+const alert: AlertTransition = {
+  id: 'a1',
+  ruleId: 'unreachable',
+  kind: 'unreachable',
+  status: 'firing',
+  message: 'Monitoring API unreachable',
+  at: '2026-01-01T00:00:00.000Z',
+};
+
+interface Received {
+  headers: http.IncomingHttpHeaders;
+  body: string;
+}
+
+async function startReceiver(t: { after: (fn: () => void) => void }, statuses: number[]) {
+  const received: Received[] = [];
+  const server = http.createServer((req, res) => {
+    let body = '';
+    req.on('data', (chunk) => { body += chunk; });
+    req.on('end', () => {
+      received.push({ headers: req.headers, body });
+      res.statusCode = statuses[Math.min(received.length - 1, statuses.length - 1)];
+      res.end();
+    });
+  });
+  server.listen(0);
+  t.after(() => server.close());
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as { port: number };
+  return { url: `http://127.0.0.1:${port}/hook`, received };
+}
+
+const noSleep = async () => undefined;
+
+test('parses webhook configuration', () => {
+  assert.equal(parseWebhookConfig({}), null);
+  assert.deepEqual(parseWebhookConfig({ ALERT_WEBHOOK_URLS: 'https://a.example/x, http://b.lan/y' }), {
+    urls: ['https://a.example/x', 'http://b.lan/y'],
+    secret: null,
+    attempts: 4,
+  });
+  assert.throws(() => parseWebhookConfig({ ALERT_WEBHOOK_URLS: 'ftp://a.example' }), /http/);
+  assert.throws(() => parseWebhookConfig({ ALERT_WEBHOOK_URLS: 'not a url' }), /Invalid/);
+  assert.throws(() => parseWebhookConfig({ ALERT_WEBHOOK_URLS: 'https://a.example', ALERT_WEBHOOK_ATTEMPTS: '0' }), /ATTEMPTS/);
+});
+
+test('posts signed payloads', async (t) => {
+  const receiver = await startReceiver(t, [204]);
+  const now = () => new Date('2026-01-01T00:00:05.000Z');
+  const notifier = createWebhookNotifier({ urls: [receiver.url], secret: 's3cret', attempts: 1 }, { now, sleep: noSleep });
+
+  const [result] = await notifier.notify(alert, { poolName: 'Demo Pool', network: 'signet' });
+
+  assert.deepEqual(result, { url: receiver.url, ok: true, attempts: 1, status: 204 });
+  const [{ headers, body }] = receiver.received;
+  assert.deepEqual(JSON.parse(body), {
+    type: 'alert',
+    alert,
+    poolName: 'Demo Pool',
+    network: 'signet',
+    sentAt: '2026-01-01T00:00:05.000Z',
+  });
+  assert.equal(headers['x-sv2ui-timestamp'], '1767225605');
+  assert.equal(headers['x-sv2ui-signature'], signWebhookPayload('s3cret', 1767225605, body));
+});
+
+test('retries server errors but not client errors', async (t) => {
+  const flaky = await startReceiver(t, [503, 500, 200]);
+  const rejecting = await startReceiver(t, [400]);
+  const delays: number[] = [];
+  const notifier = createWebhookNotifier(
+    { urls: [flaky.url, rejecting.url], secret: null, attempts: 4 },
+    { sleep: async (ms) => { delays.push(ms); } },
+  );
+
+  const [flakyResult, rejectingResult] = await notifier.notify(alert);
+
+  assert.deepEqual([flakyResult.ok, flakyResult.attempts], [true, 3]);
+  assert.deepEqual([rejectingResult.ok, rejectingResult.attempts, rejectingResult.status], [false, 1, 400]);
+  assert.deepEqual(delays, [1000, 4000]);
+  assert.equal(flaky.received[0].headers['x-sv2ui-signature'], undefined);
+});
+
+test('gives up after the configured attempts', async () => {
+  const notifier = createWebhookNotifier({ urls: ['http://127.0.0.1:1/hook'], secret: null, attempts: 2 }, { sleep: noSleep });
+  const [result] = await notifier.notify(alert);
+  assert.equal(result.ok, false);
+  assert.equal(result.attempts, 2);
+  assert.equal(result.status, null);
+  assert.ok(result.error);
+});
@@ -0,0 +1,175 @@
+/**
+ * Webhook delivery for alert transitions and found blocks.
+ *
+ * Each firing/resolved transition, and each block-found event, is POSTed as
+ * JSON to every URL in ALERT_WEBHOOK_URLS. When ALERT_WEBHOOK_SECRET is set, requests carry
+ *
+ *   X-Sv2ui-Timestamp: <unix seconds>
+ *   X-Sv2ui-Signature: sha256=<hex HMAC-SHA256 of "<timestamp>.<body>">
+ *
+ * so receivers can verify the sender and reject replays. Failed deliveries
+ * (network errors, 5xx, 429) are retried with exponential backoff.
+ */
+
+import crypto from 'crypto';
+import type { BitcoinNetwork, SetupMode } from '@sv2-ui/shared';
+import type { AlertTransition } from './alerts.js';
+import type { StackEvent } from './events.js';
+
+export interface WebhookConfig {
+  urls: string[];
+  secret: string | null;
+  attempts: number;
+}
+
+export interface AlertWebhookPayload {
+  type: 'alert';
+  alert: AlertTransition;
+  poolName: string | null;
+  network: BitcoinNetwork | null;
+  sentAt: string;
+}
+
+export interface EventWebhookPayload {
+  type: 'event';
+  event: StackEvent;
+  poolName: string | null;
+  network: BitcoinNetwork | null;
+  sentAt: string;
+}
+
+export interface WebhookDeliveryResult {
+  url: string;
+  ok: boolean;
+  attempts: number;
+  status: number | null;
+  error?: string;
+}
+
+const DEFAULT_ATTEMPTS = 4;
+const RETRY_BASE_DELAY_MS = 1000;
+const DELIVERY_TIMEOUT_MS = 10_000;
+
+export function parseWebhookConfig(env: NodeJS.ProcessEnv = process.env): WebhookConfig | null {
+  const urls = (env.ALERT_WEBHOOK_URLS ?? '').split(',').map((url) => url.trim()).filter(Boolean);
+  if (urls.length === 0) return null;
+
+  for (const url of urls) {
+    let parsed: URL;
+    try {
+      parsed = new URL(url);
+    } catch {
+      throw new Error(`Invalid ALERT_WEBHOOK_URLS entry "${url}"`);
+    }
+    if (parsed.protocol !== 'http:' && parsed.protocol !== 'https:') {
+      throw new Error(`ALERT_WEBHOOK_URLS entry "${url}" must be http(s)`);
+    }
+  }
+
+  const rawAttempts = env.ALERT_WEBHOOK_ATTEMPTS?.trim();
+  const attempts = rawAttempts ? Number(rawAttempts) : DEFAULT_ATTEMPTS;
+  if (!Number.isInteger(attempts) || attempts < 1) {
+    throw new Error(`Invalid ALERT_WEBHOOK_ATTEMPTS "${rawAttempts}"`);
+  }
+
+  return { urls, secret: env.ALERT_WEBHOOK_SECRET || null, attempts };
+}
+
+export function signWebhookPayload(secret: string, timestamp: number, body: string): string {
+  return `sha256=${crypto.createHmac('sha256', secret).update(`${timestamp}.${body}`).digest('hex')}`;
+}
+
+function isRetryable(status: number): boolean {
+  return status >= 500 || status === 429;
+}
+
+export interface NotificationContext {
+  poolName?: string | null;
+  network?: BitcoinNetwork | null;
+  mode?: SetupMode | null;
+}
+
+export interface WebhookNotifierDeps {
+  now?: () => Date;
+  sleep?: (ms: number) => Promise<void>;
+}
+
+export function createWebhookNotifier(config: WebhookConfig, deps: WebhookNotifierDeps = {}) {
+  const now = deps.now ?? (() => new Date());
+  const sleep = deps.sleep ?? ((ms: number) => new Promise<void>((resolve) => setTimeout(resolve, ms)));
+  let inFlight = 0;
+
+  async function deliverTo(url: string, body: string): Promise<WebhookDeliveryResult> {
+    let status: number | null = null;
+    let error: string | undefined;
+
+    for (let attempt = 1; attempt <= config.attempts; attempt++) {
+      if (attempt > 1) {
+        await sleep(RETRY_BASE_DELAY_MS * 4 ** (attempt - 2));
+      }
+
+      // Signed per attempt so the timestamp stays fresh across retries.
+      const timestamp = Math.floor(now().getTime() / 1000);
+      const headers: Record<string, string> = {
+        'Content-Type': 'application/json',
+        'User-Agent': 'sv2-ui-webhooks',
+      };
+      if (config.secret) {
+        headers['X-Sv2ui-Timestamp'] = String(timestamp);
+        headers['X-Sv2ui-Signature'] = signWebhookPayload(config.secret, timestamp, body);
+      }
+
+      try {
+        const response = await fetch(url, {
+          method: 'POST',
+          headers,
+          body,
+          signal: AbortSignal.timeout(DELIVERY_TIMEOUT_MS),
+        });
+        status = response.status;
+        error = undefined;
+        if (response.ok) return { url, ok: true, attempts: attempt, status };
+        if (!isRetryable(status)) return { url, ok: false, attempts: attempt, status };
+      } catch (caught) {
+        status = null;
+        error = caught instanceof Error ? caught.message : String(caught);
+      }
+    }
+
+    return { url, ok: false, attempts: config.attempts, status, ...(error ? { error } : {}) };
+  }
+
+  async function deliver(payload: AlertWebhookPayload | EventWebhookPayload): Promise<WebhookDeliveryResult[]> {
+    const body = JSON.stringify(payload);
+    inFlight += 1;
+    try {
+      return await Promise.all(config.urls.map((url) => deliverTo(url, body)));
+    } finally {
+      inFlight -= 1;
+    }
+  }
+
+  /**
+   * Deliver one transition to every configured URL.
+   */
+  function notify(
+    alert: AlertTransition,
+    { poolName = null, network = null }: NotificationContext = {},
+  ): Promise<WebhookDeliveryResult[]> {
+    return deliver({ type: 'alert', alert, poolName, network, sentAt: now().toISOString() });
+  }
+
+  /**
+   * Deliver one event (a found block) to every configured URL.
+   */
+  function notifyEvent(
+    event: StackEvent,
+    { poolName = null, network = null }: NotificationContext = {},
+  ): Promise<WebhookDeliveryResult[]> {
+    return deliver({ type: 'event', event, poolName, network, sentAt: now().toISOString() });
+  }
+
+  return { notify, notifyEvent, inFlight: () => inFlight };
+}
+
+export type WebhookNotifier = ReturnType<typeof createWebhookNotifier>;
@@ -0,0 +1,95 @@
+import assert from 'node:assert/strict';
+import crypto from 'node:crypto';
+import net from 'node:net';
+import { test } from 'node:test';
+
+import { createBlockNotifier, encodeFrame, encodeGreeting, parseZmqEndpoints, readCoinbaseHeight, readRawBlock } from './zmq.js';
+import type { ZmqBlock } from './zmq.js';
+
+// A header, one transaction: a segwit coinbase whose scriptSig pushes `height`.
+function rawBlock(height: Buffer): Buffer {
+  const header = Buffer.alloc(80, 7);
+  const coinbase = Buffer.concat([
+    Buffer.from('02000000', 'hex'),
+    Buffer.from('0001', 'hex'),
+    Buffer.from([1]),
+    Buffer.alloc(32),
+    Buffer.from('ffffffff', 'hex'),
+    Buffer.from([height.length + 1]),
+    height,
+    Buffer.from([0]),
+  ]);
+  return Buffer.concat([header, Buffer.from([1]), coinbase]);
+}
+
+test('parseZmqEndpoints reads tcp://host:port entries', () => {
+  assert.deepEqual(parseZmqEndpoints({}), []);
+  assert.deepEqual(parseZmqEndpoints({ BITCOIN_ZMQ_URL: 'tcp://127.0.0.1:28332, tcp://[::1]:28333' }), [
+    { host: '127.0.0.1', port: 28332 },
+    { host: '::1', port: 28333 },
+  ]);
+  assert.throws(() => parseZmqEndpoints({ BITCOIN_ZMQ_URL: 'ipc:///tmp/bitcoind' }), /must look like tcp:\/\/host:port/);
+});
+
+test('rawblock gives the block hash and its BIP34 height', () => {
+  const raw = rawBlock(Buffer.from('03a0bb0d', 'hex'));
+  const sha256 = (bytes: Buffer) => crypto.createHash('sha256').update(bytes).digest();
+  assert.deepEqual(readRawBlock(raw), {
+    hash: sha256(sha256(raw.subarray(0, 80))).reverse().toString('hex'),
+    height: 900_000,
+  });
+  assert.equal(readCoinbaseHeight(Buffer.from([0x55])), 5);
+  assert.equal(readCoinbaseHeight(Buffer.from('026a', 'hex')), null);
+});
+
+test('the notifier hears each block once from a ZMTP publisher', async () => {
+  const hash = 'ab'.repeat(32);
+  const subscriptions: string[] = [];
+  const server = net.createServer((socket) => {
+    let received = Buffer.alloc(0);
+    socket.on('data', (chunk) => {
+      received = Buffer.concat([received, chunk]);
+      // Greeting, READY, then one subscription message per topic.
+      const topics = ['hashblock', 'rawblock'].filter((topic) => received.includes(Buffer.concat([Buffer.from([1]), Buffer.from(topic)])));
+      if (topics.length === 2 && subscriptions.length === 0) {
+        subscriptions.push(...topics);
+        const message = (topic: string, body: Buffer) => Buffer.concat([
+          encodeFrame(Buffer.from(topic), 1),
+          encodeFrame(body, 1),
+          encodeFrame(Buffer.from('00000000', 'hex')),
+        ]);
+        socket.write(Buffer.concat([
+          encodeGreeting(),
+          encodeFrame(Buffer.from('\x05READY'), 4),
+          message('hashblock', Buffer.from(hash, 'hex')),
+          message('hashblock', Buffer.from(hash, 'hex')),
+          message('rawblock', rawBlock(Buffer.from('0164', 'hex'))),
+        ]));
+      }
+    });
+  }).listen(0, '127.0.0.1');
+  await new Promise((resolve) => server.once('listening', resolve));
+  const { port } = server.address() as net.AddressInfo;
+
+  const blocks: ZmqBlock[] = [];
+  const done = new Promise<void>((resolve) => {
+    const notifier = createBlockNotifier({
+      endpoints: [{ host: '127.0.0.1', port }],
+      onBlock: (block) => {
+        blocks.push(block);
+        if (blocks.length === 2) {
+          notifier.stop();
+          resolve();
+        }
+      },
+    });
+    notifier.start();
+  });
+  await done;
+  server.close();
+
+  assert.deepEqual(subscriptions, ['hashblock', 'rawblock']);
+  assert.deepEqual(blocks[0], { hash, height: null, topic: 'hashblock' });
+  assert.equal(blocks[1].height, 100);
+  assert.equal(blocks[1].topic, 'rawblock');
+});
@@ -0,0 +1,251 @@
+/**
+ * New-block notifications from bitcoind over ZMQ.
+ *
+ * With BITCOIN_ZMQ_URL set to the node's -zmqpubhashblock or
+ * -zmqpubrawblock endpoint (or both, comma-separated), the backend
+ * subscribes to both topics, so it hears of a block as soon as the node
+ * has it rather than on its next poll. Each new block is pushed to open
+ * dashboards as a `block` message and triggers an immediate poll, so the
+ * template and job data don't lag behind.
+ *
+ * The subscriber speaks just enough ZMTP 3.0 for a SUB socket with the NULL
+ * mechanism, which is what bitcoind offers. It reconnects after
+ * RECONNECT_DELAY_MS whenever the node goes away.
+ */
+
+import crypto from 'crypto';
+import net from 'net';
+import { createByteReader } from './sv2-probe.js';
+
+export const ZMQ_TOPICS = ['hashblock', 'rawblock'] as const;
+export type ZmqTopic = typeof ZMQ_TOPICS[number];
+
+export interface ZmqEndpoint {
+  host: string;
+  port: number;
+}
+
+export interface ZmqBlock {
+  // Display (RPC) byte order.
+  hash: string;
+  // From the coinbase (BIP34); null for `hashblock`, which only carries the hash.
+  height: number | null;
+  topic: ZmqTopic;
+}
+
+export interface BlockNotifierOptions {
+  endpoints: ZmqEndpoint[];
+  onBlock: (block: ZmqBlock) => void;
+  onError?: (endpoint: ZmqEndpoint, error: Error) => void;
+}
+
+const GREETING_SIZE = 64;
+const FLAG_MORE = 0x01;
+const FLAG_LONG = 0x02;
+const FLAG_COMMAND = 0x04;
+const RECONNECT_DELAY_MS = 5_000;
+// Both topics announce the same block; remember enough hashes to drop the repeat.
+const SEEN_HASHES = 16;
+
+export function parseZmqEndpoints(env: NodeJS.ProcessEnv = process.env): ZmqEndpoint[] {
+  const raw = env.BITCOIN_ZMQ_URL?.trim();
+  if (!raw) return [];
+  return raw.split(',').map((entry) => entry.trim()).filter(Boolean).map((entry) => {
+    const match = /^tcp:\/\/(\[[^\]]+\]|[^:/]+):(\d+)$/.exec(entry);
+    const port = match ? Number(match[2]) : NaN;
+    if (!match || port < 1 || port > 65535) {
+      throw new Error(`BITCOIN_ZMQ_URL entries must look like tcp://host:port, got "${entry}"`);
+    }
+    return { host: match[1].replace(/^\[|\]$/g, ''), port };
+  });
+}
+
+/**
+ * A ZMTP 3.0 greeting for the NULL mechanism, as a client.
+ */
+export function encodeGreeting(): Buffer {
+  const greeting = Buffer.alloc(GREETING_SIZE);
+  greeting[0] = 0xff;
+  greeting[9] = 0x7f;
+  greeting[10] = 3;
+  greeting[11] = 0;
+  greeting.write('NULL', 12, 'ascii');
+  return greeting;
+}
+
+export function encodeFrame(body: Buffer, flags = 0): Buffer {
+  if (body.length > 255) {
+    const header = Buffer.alloc(9);
+    header[0] = flags | FLAG_LONG;
+    header.writeBigUInt64BE(BigInt(body.length), 1);
+    return Buffer.concat([header, body]);
+  }
+  return Buffer.concat([Buffer.from([flags, body.length]), body]);
+}
+
+/**
+ * The READY command announcing `socketType`.
+ */
+export function encodeReady(socketType: string): Buffer {
+  const name = Buffer.from('Socket-Type', 'ascii');
+  const value = Buffer.from(socketType, 'ascii');
+  const valueLength = Buffer.alloc(4);
+  valueLength.writeUInt32BE(value.length);
+  const body = Buffer.concat([Buffer.from([5]), Buffer.from('READY', 'ascii'), Buffer.from([name.length]), name, valueLength, value]);
+  return encodeFrame(body, FLAG_COMMAND);
+}
+
+/**
+ * A ZMTP 3.0 subscription: a message of 0x01 followed by the topic.
+ */
+export function encodeSubscribe(topic: string): Buffer {
+  return encodeFrame(Buffer.concat([Buffer.from([1]), Buffer.from(topic, 'ascii')]));
+}
+
+function readVarInt(bytes: Buffer, offset: number): { value: number; offset: number } {
+  const first = bytes[offset];
+  if (first < 0xfd) return { value: first, offset: offset + 1 };
+  if (first === 0xfd) return { value: bytes.readUInt16LE(offset + 1), offset: offset + 3 };
+  if (first === 0xfe) return { value: bytes.readUInt32LE(offset + 1), offset: offset + 5 };
+  return { value: Number(bytes.readBigUInt64LE(offset + 1)), offset: offset + 9 };
+}
+
+/**
+ * The height a coinbase scriptSig starts with (BIP34), or null.
+ */
+export function readCoinbaseHeight(scriptSig: Buffer): number | null {
+  const op = scriptSig[0];
+  if (op === undefined) return null;
+  if (op === 0) return 0;
+  // bitcoind pushes heights 1-16 as OP_1..OP_16.
+  if (op >= 0x51 && op <= 0x60) return op - 0x50;
+  if (op > 6 || scriptSig.length < op + 1) return null;
+  return scriptSig.subarray(1, 1 + op).readUIntLE(0, op);
+}
+
+/**
+ * The hash and height of a serialized block, as sent on `rawblock`.
+ */
+export function readRawBlock(raw: Buffer): { hash: string; height: number | null } {
+  if (raw.length < 80) throw new Error('Block is shorter than its header');
+  const sha256 = (bytes: Buffer) => crypto.createHash('sha256').update(bytes).digest();
+  const hash = Buffer.from(sha256(sha256(raw.subarray(0, 80)))).reverse().toString('hex');
+
+  try {
+    // Header, transaction count, then the coinbase: version, the segwit
+    // marker and flag if present, input count and the null prevout.
+    let offset = readVarInt(raw, 80).offset + 4;
+    if (raw[offset] === 0 && raw[offset + 1] === 1) offset += 2;
+    offset = readVarInt(raw, offset).offset + 36;
+    const script = readVarInt(raw, offset);
+    return { hash, height: readCoinbaseHeight(raw.subarray(script.offset, script.offset + script.value)) };
+  } catch {
+    return { hash, height: null };
+  }
+}
+
+/**
+ * Read ZMTP frames off `reader` into multipart messages, skipping commands.
+ */
+async function* readMessages(reader: ReturnType<typeof createByteReader>): AsyncGenerator<Buffer[]> {
+  let parts: Buffer[] = [];
+  for (;;) {
+    const [flags] = await reader.read(1);
+    const size = flags & FLAG_LONG ? Number((await reader.read(8)).readBigUInt64BE()) : (await reader.read(1))[0];
+    const body = size > 0 ? Buffer.from(await reader.read(size)) : Buffer.alloc(0);
+    if (flags & FLAG_COMMAND) continue;
+    parts.push(body);
+    if (!(flags & FLAG_MORE)) {
+      yield parts;
+      parts = [];
+    }
+  }
+}
+
+/**
+ * Subscribe to `topics` at one endpoint until the connection drops;
+ * resolves or rejects when it does.
+ */
+export async function subscribeOnce(
+  endpoint: ZmqEndpoint,
+  topics: readonly string[],
+  { onMessage, onSocket, onConnected }: {
+    onMessage: (parts: Buffer[]) => void;
+    onSocket?: (socket: net.Socket) => void;
+    // Once the peer's greeting checks out.
+    onConnected?: () => void;
+  },
+): Promise<void> {
+  const socket = net.connect(endpoint);
+  onSocket?.(socket);
+  const reader = createByteReader(socket);
+  try {
+    socket.write(Buffer.concat([encodeGreeting(), encodeReady('SUB'), ...topics.map(encodeSubscribe)]));
+    const greeting = await reader.read(GREETING_SIZE);
+    if (greeting[0] !== 0xff || greeting[9] !== 0x7f || greeting[10] < 3) {
+      throw new Error(`${endpoint.host}:${endpoint.port} is not a ZMTP 3 endpoint`);
+    }
+    onConnected?.();
+    for await (const parts of readMessages(reader)) onMessage(parts);
+  } finally {
+    socket.destroy();
+  }
+}
+
+export function createBlockNotifier({ endpoints, onBlock, onError }: BlockNotifierOptions) {
+  const seen: string[] = [];
+  const sockets = new Set<net.Socket>();
+  const timers = new Set<NodeJS.Timeout>();
+  let stopped = false;
+
+  function handle(parts: Buffer[]): void {
+    const [topic, body] = parts;
+    const name = topic?.toString('ascii');
+    if (!body || (name !== 'hashblock' && name !== 'rawblock')) return;
+    const block: ZmqBlock = name === 'rawblock'
+      ? { ...readRawBlock(body), topic: name }
+      : { hash: body.toString('hex'), height: null, topic: name };
+    if (seen.includes(block.hash)) return;
+    seen.push(block.hash);
+    if (seen.length > SEEN_HASHES) seen.shift();
+    onBlock(block);
+  }
+
+  // `failing` keeps a node that stays down from being reported on every retry.
+  function run(endpoint: ZmqEndpoint, failing = false): void {
+    subscribeOnce(endpoint, ZMQ_TOPICS, {
+      onMessage: handle,
+      onSocket: (socket) => {
+        sockets.add(socket);
+        socket.once('close', () => sockets.delete(socket));
+      },
+      onConnected: () => {
+        failing = false;
+      },
+    }).catch((error: Error) => {
+      if (!stopped && !failing) onError?.(endpoint, error);
+      failing = true;
+    }).finally(() => {
+      if (stopped) return;
+      const timer = setTimeout(() => {
+        timers.delete(timer);
+        run(endpoint, failing);
+      }, RECONNECT_DELAY_MS);
+      timers.add(timer);
+    });
+  }
+
+  function start(): void {
+    for (const endpoint of endpoints) run(endpoint);
+  }
+
+  function stop(): void {
+    stopped = true;
+    for (const timer of timers) clearTimeout(timer);
+    for (const socket of sockets) socket.destroy();
+  }
+
+  return { start, stop, connections: () => sockets.size };
+}
+
+export type BlockNotifier = ReturnType<typeof createBlockNotifier>;
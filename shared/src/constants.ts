@@ -2,7 +2,31 @@ import type { BitcoinCoreVersion, BitcoinNetwork, OperatingSystem } from './type
 
 export const SUPPORTED_BITCOIN_CORE_VERSIONS: BitcoinCoreVersion[] = ['30', '31'];
 
-export const SUPPORTED_NETWORKS: BitcoinNetwork[] = ['mainnet', 'testnet4'];
+export const SUPPORTED_NETWORKS: BitcoinNetwork[] = ['mainnet', 'testnet4', 'signet', 'regtest'];
+
+export const NETWORK_LABELS: Record<BitcoinNetwork, string> = {
+  mainnet: 'Mainnet',
+  testnet4: 'Testnet4',
+  signet: 'Signet',
+  regtest: 'Regtest',
+};
+
+/**
+ * Map the `chain` reported by getblockchaininfo to a supported network.
+ * Returns null for chains sv2-ui doesn't support (e.g. testnet3).
+ */
+export function networkFromChain(chain: string): BitcoinNetwork | null {
+  switch (chain) {
+    case 'main':
+      return 'mainnet';
+    case 'testnet4':
+    case 'signet':
+    case 'regtest':
+      return chain;
+    default:
+      return null;
+  }
+}
 
 export const DEFAULT_BITCOIN_PATHS: Record<OperatingSystem, string> = {
   linux: '~/.bitcoin',
@@ -13,6 +37,8 @@ export const DEFAULT_BITCOIN_PATHS: Record<OperatingSystem, string> = {
 export const RPC_PORTS: Record<BitcoinNetwork, number> = {
   mainnet: 8332,
   testnet4: 48332,
+  signet: 38332,
+  regtest: 18443,
 };
 
 export const CONTAINER_NAMES = {
@@ -40,6 +66,8 @@ export const JDC_MONITORING_PORT = 9091;
 export const DEFAULT_SHARES_PER_MINUTE = 6;
 export const DEFAULT_DOWNSTREAM_EXTRANONCE2_SIZE = 4;
 export const DEFAULT_POOL_PORT = 34254;
+// The JDC reaches the pool's JDS on the pool host at this port.
+export const POOL_JDS_PORT = 3334;
 
 export function computeDefaultSocketPath(dataDir: string, network: BitcoinNetwork): string {
   return network === 'mainnet' ? `${dataDir}/node.sock` : `${dataDir}/${network}/node.sock`;
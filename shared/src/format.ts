@@ -59,3 +59,49 @@ export function mapHostOsToOperatingSystem(hostOs: string): OperatingSystem | nu
   if (normalized === 'umbrel') return 'umbrel';
   return null;
 }
+
+/**
+ * Formats hashrate with appropriate unit (H/s, KH/s, MH/s, GH/s, TH/s, PH/s, EH/s).
+ */
+export function formatHashrate(hashrate: number | null): string {
+  if (hashrate === null || hashrate === 0) return '0 H/s';
+  
+  const units = ['H/s', 'KH/s', 'MH/s', 'GH/s', 'TH/s', 'PH/s', 'EH/s'];
+  const k = 1000;
+  const i = Math.floor(Math.log(hashrate) / Math.log(k));
+  const index = Math.min(i, units.length - 1);
+  
+  return `${(hashrate / Math.pow(k, index)).toFixed(2)} ${units[index]}`;
+}
+
+/**
+ * Formats difficulty as a human-readable string.
+ */
+export function formatDifficulty(diff: number): string {
+  if (diff === 0) return '0';
+  if (diff >= 1e15) return `${(diff / 1e15).toFixed(2)}P`;
+  if (diff >= 1e12) return `${(diff / 1e12).toFixed(2)}T`;
+  if (diff >= 1e9) return `${(diff / 1e9).toFixed(2)}G`;
+  if (diff >= 1e6) return `${(diff / 1e6).toFixed(2)}M`;
+  if (diff >= 1e3) return `${(diff / 1e3).toFixed(2)}K`;
+  return diff.toFixed(2);
+}
+
+/**
+ * Formats uptime in seconds to a human-readable duration string.
+ */
+export function formatUptime(seconds: number): string {
+  if (seconds < 60) return `${seconds}s`;
+  
+  const days = Math.floor(seconds / 86400);
+  const hours = Math.floor((seconds % 86400) / 3600);
+  const minutes = Math.floor((seconds % 3600) / 60);
+  
+  if (days > 0) {
+    return `${days}d ${hours}h ${minutes}m`;
+  }
+  if (hours > 0) {
+    return `${hours}h ${minutes}m`;
+  }
+  return `${minutes}m`;
+}
@@ -1,5 +1,5 @@
 import { useEffect } from 'react';
-import { Switch, Route, useLocation } from 'wouter';
+import { Router as WouterRouter, Switch, Route, useLocation } from 'wouter';
 import { QueryClientProvider } from '@tanstack/react-query';
 import { queryClient } from '@/lib/queryClient';
 import { UnifiedDashboard } from '@/pages/UnifiedDashboard';
@@ -7,6 +7,8 @@ import { Settings } from '@/pages/Settings';
 import { Setup } from '@/pages/Setup';
 import { FAQ } from '@/pages/FAQ';
 import { useSetupStatus } from '@/hooks/useSetupStatus';
+import { useRealtimeRefresh } from '@/hooks/useRealtimeRefresh';
+import { BASE_PATH } from '@/lib/basePath';
 
 /**
  * SV2 Mining Stack UI
@@ -26,6 +28,7 @@ import { useSetupStatus } from '@/hooks/useSetupStatus';
 function Router() {
   const [location, navigate] = useLocation();
   const { isLoading, isOrchestrated, needsSetup } = useSetupStatus();
+  useRealtimeRefresh(isOrchestrated);
 
   // Redirect to setup if needed (only when orchestration backend is present)
   useEffect(() => {
@@ -74,7 +77,9 @@ function Router() {
 function App() {
   return (
     <QueryClientProvider client={queryClient}>
-      <Router />
+      <WouterRouter base={BASE_PATH}>
+        <Router />
+      </WouterRouter>
     </QueryClientProvider>
   );
 }
@@ -2,6 +2,7 @@ import { useEffect, useRef, useCallback } from 'react';
 import { Download } from 'lucide-react';
 import type { ContainerLogLine } from '@/types/log-diagnostics';
 import { cn } from '@/lib/utils';
+import { withBasePath } from '@/lib/basePath';
 
 interface ContainerLogsPanelProps {
   lines: ContainerLogLine[];
@@ -43,7 +44,7 @@ export function ContainerLogsPanel({ lines, isLoading, isJdMode }: ContainerLogs
 
   const handleDownload = useCallback(async () => {
     try {
-      const response = await fetch('/api/logs/raw?tail=all', {
+      const response = await fetch(withBasePath('/api/logs/raw?tail=all'), {
         signal: AbortSignal.timeout(10000),
       });
       if (!response.ok) return;
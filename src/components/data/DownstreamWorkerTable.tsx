@@ -36,6 +36,8 @@ interface DownstreamWorkerTableProps {
   sortDir: 'asc' | 'desc';
   onSort: (key: DownstreamWorkerSortKey) => void;
   showBestDiff?: boolean;
+  /** Extra guidance shown under "No workers connected". */
+  emptyHint?: string | null;
 }
 
 const TABLE_CONTAINER_CLASS_NAME = 'glass-table shadow-sm';
@@ -87,6 +89,7 @@ export function DownstreamWorkerTable({
   sortDir,
   onSort,
   showBestDiff = true,
+  emptyHint,
 }: DownstreamWorkerTableProps) {
   if (isLoading) {
     return (
@@ -103,6 +106,9 @@ export function DownstreamWorkerTable({
       {workers.length === 0 ? (
         <div className="p-8 text-center text-muted-foreground">
           No workers connected
+          {emptyHint && (
+            <p className="mt-2 text-sm">{emptyHint}</p>
+          )}
         </div>
       ) : (
         <Table>
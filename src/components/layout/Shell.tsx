@@ -2,9 +2,12 @@ import { useEffect, useRef, useState } from 'react';
 import { Link, useLocation } from 'wouter';
 import { Sun, Moon, Menu, X, LayoutDashboard, Settings, HelpCircle } from 'lucide-react';
 import { cn, formatUptime } from '@/lib/utils';
+import { withBasePath } from '@/lib/basePath';
 import type { AppMode, AppFeatures } from '@/types/api';
 import { getAppFeatures } from '@/types/api';
 import { useUiConfig } from '@/hooks/useUiConfig';
+import { useSetupStatus } from '@/hooks/useSetupStatus';
+import { NETWORK_LABELS } from '@sv2-ui/shared';
 
 function useTheme() {
   const [isDark, setIsDark] = useState(() => {
@@ -62,6 +65,8 @@ export function Shell({
   const [location] = useLocation();
   const { isDark, toggle } = useTheme();
   const { config } = useUiConfig();
+  const { network } = useSetupStatus();
+  const testNetworkLabel = network && network !== 'mainnet' ? NETWORK_LABELS[network] : null;
   const [menuOpen, setMenuOpen] = useState(false);
   const menuRef = useRef<HTMLDivElement>(null);
 
@@ -100,8 +105,8 @@ export function Shell({
         />
       ) : (
         <img
-          src="/sv2-logo-240x40.png"
-          srcSet="/sv2-logo-240x40.png 1x, /sv2-logo-480x80.png 2x"
+          src={withBasePath('/sv2-logo-240x40.png')}
+          srcSet={`${withBasePath('/sv2-logo-240x40.png')} 1x, ${withBasePath('/sv2-logo-480x80.png')} 2x`}
           alt="Stratum V2"
           width="120"
           height="20"
@@ -165,6 +170,14 @@ export function Shell({
 
           {/* Right side */}
           <div className="ml-auto flex items-center gap-2 min-w-0">
+            {testNetworkLabel && (
+              <span
+                className="shrink-0 rounded border border-yellow-500/40 bg-yellow-500/10 px-1.5 py-0.5 text-[10px] font-semibold uppercase tracking-wide text-yellow-600 dark:text-yellow-400"
+                title={`This stack mines on ${testNetworkLabel}, not mainnet`}
+              >
+                {testNetworkLabel}
+              </span>
+            )}
             {connectionStatus && (
               <>
                 {/* Mobile: dot + uptime only (no status text to save space) */}
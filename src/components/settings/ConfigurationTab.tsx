@@ -34,6 +34,7 @@ import {
   Check,
   X,
 } from 'lucide-react';
+import { withBasePath } from '@/lib/basePath';
 
 function clearPersistedDashboardState() {
   if (typeof window === 'undefined') return;
@@ -165,10 +166,10 @@ export function ConfigurationTab() {
   const handleReset = async () => {
     if (confirm('Delete configuration and stop all services? This cannot be undone.')) {
       try {
-        const response = await fetch('/api/reset', { method: 'POST' });
+        const response = await fetch(withBasePath('/api/reset'), { method: 'POST' });
         if (response.ok) {
           clearDashboardClientState();
-          window.location.href = '/setup';
+          window.location.href = withBasePath('/setup');
         }
       } catch (error) {
         console.error('Reset failed:', error);
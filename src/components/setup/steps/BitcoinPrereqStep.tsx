@@ -14,6 +14,7 @@ import { StepProps, BitcoinConfig } from '../types';
 import { Copy, Check, ExternalLink, Loader2, RotateCw, CheckCircle2, AlertCircle } from 'lucide-react';
 import type { BitcoinRpcDiscoveryResult } from '@/hooks/useBitcoinRpcDiscovery';
 import { useHostEnv } from '@/hooks/useHostEnv';
+import { withBasePath } from '@/lib/basePath';
 
 interface BitcoinPrereqStepProps extends StepProps {
   discoveredNodes: BitcoinRpcDiscoveryResult[];
@@ -106,7 +107,7 @@ export function BitcoinPrereqStep({ data, updateData, onNext, discoveredNodes, i
     const controller = new AbortController();
     const timeoutId = setTimeout(() => controller.abort(), 10_000);
 
-    fetch('/api/validate/bitcoin-socket', {
+    fetch(withBasePath('/api/validate/bitcoin-socket'), {
       method: 'POST',
       headers: { 'Content-Type': 'application/json' },
       body: JSON.stringify({ socket_path: socketPath, network: node.network }),
@@ -1,7 +1,9 @@
 import { useState, useEffect, useRef } from 'react';
 import {
   DEFAULT_BITCOIN_PATHS,
+  NETWORK_LABELS,
   SUPPORTED_BITCOIN_CORE_VERSIONS,
+  SUPPORTED_NETWORKS,
   computeDefaultSocketPath,
   rpcVersionToCoreVersion,
   rpcVersionToDisplayVersion,
@@ -17,6 +19,13 @@ import { UmbrelIcon } from '../icons/UmbrelIcon';
 import { useBitcoinSocketValidation } from '@/hooks/useBitcoinSocketValidation';
 import type { BitcoinRpcDiscoveryResult } from '@/hooks/useBitcoinRpcDiscovery';
 
+const NETWORK_ICON_COLORS: Record<BitcoinNetwork, string> = {
+  mainnet: 'text-orange-500',
+  testnet4: 'text-blue-500',
+  signet: 'text-purple-500',
+  regtest: 'text-muted-foreground',
+};
+
 interface BitcoinSetupProps extends StepProps {
   notice?: string | null;
   onDismissNotice?: () => void;
@@ -234,30 +243,21 @@ export function BitcoinSetup({ data, updateData, onNext, notice, onDismissNotice
       <div role="group" aria-labelledby="network-label">
         <p id="network-label" className="block text-sm font-medium mb-3">Bitcoin Network</p>
         <div className="grid grid-cols-2 gap-3">
-          <button
-            type="button"
-            onClick={() => { setNetwork('mainnet'); resetPath(); }}
-            className={selBtn(network === 'mainnet')}
-            aria-pressed={network === 'mainnet'}
-          >
-            {network === 'mainnet' && <div className="absolute top-3 right-3 w-5 h-5 rounded-full bg-primary flex items-center justify-center" aria-hidden="true"><Check className="w-3 h-3 text-background" /></div>}
-            <div className="flex items-center gap-2">
-              <Bitcoin className="h-4 w-4 text-orange-500" aria-hidden="true" />
-              <span className={`font-medium text-sm ${network === 'mainnet' ? 'text-primary' : ''}`}>Mainnet</span>
-            </div>
-          </button>
-          <button
-            type="button"
-            onClick={() => { setNetwork('testnet4'); resetPath(); }}
-            className={selBtn(network === 'testnet4')}
-            aria-pressed={network === 'testnet4'}
-          >
-            {network === 'testnet4' && <div className="absolute top-3 right-3 w-5 h-5 rounded-full bg-primary flex items-center justify-center" aria-hidden="true"><Check className="w-3 h-3 text-background" /></div>}
-            <div className="flex items-center gap-2">
-              <Bitcoin className="h-4 w-4 text-blue-500" aria-hidden="true" />
-              <span className={`font-medium text-sm ${network === 'testnet4' ? 'text-primary' : ''}`}>Testnet4</span>
-            </div>
-          </button>
+          {SUPPORTED_NETWORKS.map((option) => (
+            <button
+              key={option}
+              type="button"
+              onClick={() => { setNetwork(option); resetPath(); }}
+              className={selBtn(network === option)}
+              aria-pressed={network === option}
+            >
+              {network === option && <div className="absolute top-3 right-3 w-5 h-5 rounded-full bg-primary flex items-center justify-center" aria-hidden="true"><Check className="w-3 h-3 text-background" /></div>}
+              <div className="flex items-center gap-2">
+                <Bitcoin className={`h-4 w-4 ${NETWORK_ICON_COLORS[option]}`} aria-hidden="true" />
+                <span className={`font-medium text-sm ${network === option ? 'text-primary' : ''}`}>{NETWORK_LABELS[option]}</span>
+              </div>
+            </button>
+          ))}
         </div>
       </div>
 
@@ -2,6 +2,7 @@ import { useEffect, useRef, useState } from 'react';
 import type { MiningMode } from '@sv2-ui/shared';
 import type { StepProps } from '../types';
 import { Miner3D, type MinerPhase } from './Miner3D';
+import { withBasePath } from '@/lib/basePath';
 
 export function MiningModeSelection({ updateData, onNext }: StepProps) {
   const [phase, setPhase] = useState<MinerPhase>('idle');
@@ -64,8 +65,8 @@ export function MiningModeSelection({ updateData, onNext }: StepProps) {
         }}
       >
         <img
-          src="/sv2-logo-240x40.png"
-          srcSet="/sv2-logo-240x40.png 1x, /sv2-logo-480x80.png 2x"
+          src={withBasePath('/sv2-logo-240x40.png')}
+          srcSet={`${withBasePath('/sv2-logo-240x40.png')} 1x, ${withBasePath('/sv2-logo-480x80.png')} 2x`}
           alt="Stratum V2"
           width="144"
           height="24"
@@ -7,6 +7,7 @@ import { shouldAggregateTranslatorChannels } from "../poolRules";
 import { isBitcoinSocketError } from "@/lib/bitcoinSocketErrors";
 import { formatHashrate } from "@/lib/utils";
 import { formatBitcoinCoreVersion, normalizeBitcoinCoreVersion } from "@sv2-ui/shared";
+import { withBasePath } from '@/lib/basePath';
 
 interface ReviewStartProps extends StepProps {
   onComplete: () => void;
@@ -18,7 +19,7 @@ async function confirmStackStarted(): Promise<boolean> {
   const timeoutId = setTimeout(() => controller.abort(), 5_000);
 
   try {
-    const response = await fetch("/api/status", { signal: controller.signal });
+    const response = await fetch(withBasePath('/api/status'), { signal: controller.signal });
     if (!response.ok) return false;
 
     const status = await response.json();
@@ -64,7 +65,7 @@ export function ReviewStart({ data, onComplete, onGoToStep }: ReviewStartProps)
       const controller = new AbortController();
       const timeoutId = setTimeout(() => controller.abort(), 300_000);
 
-      const response = await fetch("/api/setup", {
+      const response = await fetch(withBasePath('/api/setup'), {
         method: "POST",
         headers: { "Content-Type": "application/json" },
         body: JSON.stringify(data),
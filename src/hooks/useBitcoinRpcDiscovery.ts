@@ -2,6 +2,7 @@ import { useQuery } from '@tanstack/react-query';
 
 import { rpcVersionToCoreVersion } from '@sv2-ui/shared';
 import type { BitcoinNetwork } from '@sv2-ui/shared';
+import { withBasePath } from '@/lib/basePath';
 
 export interface BitcoinRpcDiscoveryResult {
   valid: true;
@@ -15,7 +16,7 @@ export interface BitcoinRpcDiscoveryResult {
 
 async function discoverBitcoinRpc(): Promise<BitcoinRpcDiscoveryResult[]> {
   try {
-    const response = await fetch('/api/validate/bitcoin-rpc', {
+    const response = await fetch(withBasePath('/api/validate/bitcoin-rpc'), {
       method: 'GET',
       headers: { 'Content-Type': 'application/json' },
     });
@@ -2,6 +2,7 @@ import { useEffect, useState } from 'react';
 import { useQuery } from '@tanstack/react-query';
 import { isRetryableBitcoinSocketError } from '@/lib/bitcoinSocketErrors';
 import type { BitcoinCoreVersion, BitcoinNetwork } from '@sv2-ui/shared';
+import { withBasePath } from '@/lib/basePath';
 
 interface SocketValidationResult {
   valid: boolean;
@@ -23,7 +24,7 @@ async function validateSocket({
     const controller = new AbortController();
     const timeoutId = setTimeout(() => controller.abort(), 120_000);
 
-    const response = await fetch('/api/validate/bitcoin-socket', {
+    const response = await fetch(withBasePath('/api/validate/bitcoin-socket'), {
       method: 'POST',
       headers: { 'Content-Type': 'application/json' },
       body: JSON.stringify({
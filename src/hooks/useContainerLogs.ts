@@ -1,9 +1,10 @@
 import { useQuery } from '@tanstack/react-query';
 import type { ContainerLogsResponse } from '@/types/log-diagnostics';
+import { withBasePath } from '@/lib/basePath';
 
 async function fetchContainerLogs(): Promise<ContainerLogsResponse | null> {
   try {
-    const response = await fetch('/api/logs/raw', {
+    const response = await fetch(withBasePath('/api/logs/raw'), {
       signal: AbortSignal.timeout(3000),
     });
     if (!response.ok) return null;
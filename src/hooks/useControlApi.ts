@@ -1,5 +1,6 @@
 import { useMutation, useQueryClient } from '@tanstack/react-query';
 import type { SetupData } from '@/components/setup/types';
+import { withBasePath } from '@/lib/basePath';
 
 interface ControlResponse {
   success: boolean;
@@ -18,7 +19,7 @@ async function parseControlResponse(response: Response): Promise<ControlResponse
  * Stop all containers
  */
 async function stopServices(): Promise<ControlResponse> {
-  const response = await fetch('/api/stop', {
+  const response = await fetch(withBasePath('/api/stop'), {
     method: 'POST',
   });
   return parseControlResponse(response);
@@ -28,7 +29,7 @@ async function stopServices(): Promise<ControlResponse> {
  * Restart containers with existing config
  */
 async function restartServices(): Promise<ControlResponse> {
-  const response = await fetch('/api/restart', {
+  const response = await fetch(withBasePath('/api/restart'), {
     method: 'POST',
   });
   return parseControlResponse(response);
@@ -38,7 +39,7 @@ async function restartServices(): Promise<ControlResponse> {
  * Reconfigure and restart (used by setup wizard)
  */
 async function setupServices(config: SetupData): Promise<ControlResponse> {
-  const response = await fetch('/api/setup', {
+  const response = await fetch(withBasePath('/api/setup'), {
     method: 'POST',
     headers: { 'Content-Type': 'application/json' },
     body: JSON.stringify(config),
@@ -50,7 +51,7 @@ async function setupServices(config: SetupData): Promise<ControlResponse> {
  * Update configuration and restart (inline edit)
  */
 async function updateConfigService(updates: Partial<SetupData>): Promise<ControlResponse> {
-  const response = await fetch('/api/config', {
+  const response = await fetch(withBasePath('/api/config'), {
     method: 'PUT',
     headers: { 'Content-Type': 'application/json' },
     body: JSON.stringify(updates),
@@ -116,7 +117,7 @@ export function useControlApi() {
  */
 export async function getCurrentConfig(): Promise<SetupData | null> {
   try {
-    const response = await fetch('/api/config');
+    const response = await fetch(withBasePath('/api/config'));
     if (!response.ok) return null;
     const data = await response.json();
     return data.config || null;
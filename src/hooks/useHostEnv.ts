@@ -1,4 +1,5 @@
 import { useState, useEffect } from 'react';
+import { withBasePath } from '@/lib/basePath';
 
 interface HostEnv {
   HOST_OS: string | null;
@@ -12,7 +13,7 @@ export function useHostEnv() {
 
   useEffect(() => {
     let cancelled = false;
-    fetch('/api/env')
+    fetch(withBasePath('/api/env'))
       .then(res => res.json() as Promise<HostEnv>)
       .then(data => {
         if (!cancelled) {
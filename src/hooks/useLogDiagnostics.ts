@@ -1,9 +1,10 @@
 import { useQuery } from '@tanstack/react-query';
 import type { LogDiagnosticsResponse } from '@/types/log-diagnostics';
+import { withBasePath } from '@/lib/basePath';
 
 async function fetchLogDiagnostics(): Promise<LogDiagnosticsResponse | null> {
   try {
-    const response = await fetch('/api/logs/diagnostics', {
+    const response = await fetch(withBasePath('/api/logs/diagnostics'), {
       signal: AbortSignal.timeout(2500),
     });
 
@@ -10,6 +10,8 @@ import type {
   ExtendedChannelInfo,
   StandardChannelInfo,
 } from '@/types/api';
+import { withBasePath } from '@/lib/basePath';
+import { RateLimitedError, rateLimitedErrorFromResponse } from '@/lib/rateLimit';
 
 /**
  * Aggregated client channels data
@@ -31,20 +33,21 @@ export type TemplateMode = 'jd' | 'no-jd' | null;
 /**
  * Get endpoint configuration.
  * 
- * Both development and production use proxy paths through the backend server
- * to avoid CORS issues with the Translator/JDC monitoring APIs.
+ * Both development and production read the Translator/JDC monitoring APIs
+ * through the backend server, which decodes every response into its
+ * versioned v1 schema so upstream changes don't reach the dashboard.
  * 
- * - /translator-api/* -> proxied to localhost:9092
- * - /jdc-api/* -> proxied to localhost:9091
+ * - /api/v1/monitoring/translator/* -> Translator /api/v1/*
+ * - /api/v1/monitoring/jdc/* -> JDC /api/v1/*
  */
 function getEndpoints() {
   return {
     jdc: {
-      base: '/jdc-api/v1',
+      base: withBasePath('/api/v1/monitoring/jdc'),
       label: 'JD Client',
     },
     translator: {
-      base: '/translator-api/v1',
+      base: withBasePath('/api/v1/monitoring/translator'),
       label: 'Translator',
     },
   };
@@ -60,6 +63,15 @@ function getEndpointsCached() {
   return cachedEndpoints;
 }
 
+/**
+ * The error for a failed read, naming the request ID the backend logged
+ * it under.
+ */
+function httpErrorFromResponse(response: Response): Error {
+  const requestId = response.headers.get('X-Request-Id');
+  return new Error(`HTTP ${response.status}${requestId ? ` (request ${requestId})` : ''}`);
+}
+
 /**
  * Fetch data from an endpoint with timeout.
  */
@@ -67,8 +79,11 @@ async function fetchWithTimeout<T>(url: string, timeoutMs = 5000): Promise<T> {
   const response = await fetch(url, {
     signal: AbortSignal.timeout(timeoutMs),
   });
+  if (response.status === 429) {
+    throw await rateLimitedErrorFromResponse(response);
+  }
   if (!response.ok) {
-    throw new Error(`HTTP ${response.status}`);
+    throw httpErrorFromResponse(response);
   }
   return response.json();
 }
@@ -121,7 +136,9 @@ async function fetchAllSv2Clients(baseUrl: string): Promise<Sv2ClientInfo[]> {
         extended_channels: channels.extended_channels,
         standard_channels: channels.standard_channels,
       };
-    } catch {
+    } catch (error) {
+      // Showing empty channels would look like workers dropped off.
+      if (error instanceof RateLimitedError) throw error;
       return {
         ...client,
         extended_channels: [],
@@ -210,8 +227,11 @@ export function useSv1ClientsData(offset = 0, limit = 25, enabled = true) {
         `${endpoints.translator.base}/sv1/clients?offset=${offset}&limit=${limit}`,
         { signal: AbortSignal.timeout(5000) }
       );
+      if (response.status === 429) {
+        throw await rateLimitedErrorFromResponse(response);
+      }
       if (!response.ok) {
-        throw new Error(`HTTP ${response.status}`);
+        throw httpErrorFromResponse(response);
       }
       return response.json() as Promise<Sv1ClientsResponse>;
     },
@@ -0,0 +1,80 @@
+import { useEffect } from 'react';
+import { useQueryClient } from '@tanstack/react-query';
+import { withBasePath } from '@/lib/basePath';
+
+type RefreshTarget = 'translator' | 'jdc' | 'all';
+
+// Query keys fed by each monitoring API (see usePoolData.ts). Pool-level keys
+// are scoped by internal mode, which matches the backend name.
+const REFRESH_QUERY_KEYS: Record<Exclude<RefreshTarget, 'all'>, string[][]> = {
+  translator: [
+    ['pool-global', 'translator'],
+    ['server-channels', 'translator'],
+    ['sv1-clients'],
+    ['translator-server-channels'],
+    ['translator-health'],
+  ],
+  jdc: [
+    ['pool-global', 'jdc'],
+    ['server-channels', 'jdc'],
+    ['sv2-clients', 'jdc'],
+    ['jdc-health'],
+  ],
+};
+
+// Survives remounts so a new stream resumes where the last one stopped.
+// EventSource sends it itself when it reconnects on its own.
+let lastEventId: string | null = null;
+
+function rememberEventId(message: Event) {
+  const { lastEventId: id } = message as MessageEvent<string>;
+  if (id) lastEventId = id;
+}
+
+/**
+ * Listen to the backend's realtime stream and refetch monitoring data as soon
+ * as it signals a refresh (e.g. after POST /api/poll-now).
+ */
+export function useRealtimeRefresh(enabled = true) {
+  const queryClient = useQueryClient();
+
+  useEffect(() => {
+    if (!enabled || typeof EventSource === 'undefined') return;
+
+    const url = withBasePath('/api/realtime');
+    const source = new EventSource(lastEventId ? `${url}?cursor=${encodeURIComponent(lastEventId)}` : url);
+
+    source.addEventListener('refresh', (message) => {
+      rememberEventId(message);
+      let backend: RefreshTarget = 'all';
+      try {
+        backend = (JSON.parse((message as MessageEvent<string>).data) as { backend: RefreshTarget }).backend;
+      } catch {
+        // Refresh everything on a malformed message.
+      }
+
+      const targets = backend === 'all' ? (['translator', 'jdc'] as const) : [backend];
+      for (const target of targets) {
+        for (const queryKey of REFRESH_QUERY_KEYS[target] ?? []) {
+          void queryClient.invalidateQueries({ queryKey });
+        }
+      }
+    });
+
+    source.addEventListener('event', (message) => {
+      rememberEventId(message);
+      // A new stack event means the headline numbers changed.
+      void queryClient.invalidateQueries({ queryKey: ['pool-global'] });
+    });
+
+    source.addEventListener('alert', rememberEventId);
+
+    source.addEventListener('reset', (message) => {
+      rememberEventId(message);
+      // Missed messages couldn't be replayed, so refetch everything.
+      void queryClient.invalidateQueries();
+    });
+
+    return () => source.close();
+  }, [enabled, queryClient]);
+}
@@ -1,4 +1,6 @@
 import { useQuery } from '@tanstack/react-query';
+import type { BitcoinNetwork } from '@sv2-ui/shared';
+import { withBasePath } from '@/lib/basePath';
 
 export interface SetupStatus {
   configured: boolean;
@@ -8,6 +10,8 @@ export interface SetupStatus {
   miningMode: 'solo' | 'pool' | null;
   mode: 'jd' | 'no-jd' | null;
   poolName: string | null;
+  // Only known in JD mode.
+  network?: BitcoinNetwork | null;
   containers: {
     translator: { id: string; name: string; status: string } | null;
     jdc: { id: string; name: string; status: string } | null;
@@ -23,7 +27,7 @@ async function fetchSetupStatus(): Promise<SetupStatus | null> {
     const controller = new AbortController();
     const timeoutId = setTimeout(() => controller.abort(), 1500);
 
-    const response = await fetch('/api/status', {
+    const response = await fetch(withBasePath('/api/status'), {
       signal: controller.signal,
     });
 
@@ -76,6 +80,7 @@ export function useSetupStatus() {
     miningMode: status?.miningMode ?? null,
     mode: status?.mode ?? null,
     poolName: status?.poolName ?? null,
+    network: status?.network ?? null,
     containers: status?.containers ?? { translator: null, jdc: null },
     // User needs setup if: orchestrated mode AND not yet configured
     needsSetup: status !== null && status !== undefined && !status.configured,
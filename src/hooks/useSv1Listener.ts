@@ -0,0 +1,41 @@
+import { useQuery } from '@tanstack/react-query';
+import { withBasePath } from '@/lib/basePath';
+
+export interface Sv1ListenerStatus {
+  state: 'down' | 'idle' | 'active';
+  accepting: boolean;
+  connections: number | null;
+  host: string;
+  port: number;
+  checkedAt: string;
+  error?: string;
+}
+
+async function fetchSv1Listener(): Promise<Sv1ListenerStatus | null> {
+  try {
+    const response = await fetch(withBasePath('/api/sv1-listener'), {
+      signal: AbortSignal.timeout(5000),
+    });
+
+    if (!response.ok) {
+      return null;
+    }
+
+    return response.json();
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Whether the Translator's SV1 port is accepting miners (orchestrated mode only).
+ */
+export function useSv1Listener(enabled = true) {
+  return useQuery({
+    queryKey: ['sv1-listener'],
+    queryFn: fetchSv1Listener,
+    refetchInterval: enabled ? 15000 : false,
+    retry: false,
+    enabled,
+  });
+}
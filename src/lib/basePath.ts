@@ -0,0 +1,24 @@
+/**
+ * Subpath the UI is served under (e.g. `/sv2` behind a reverse proxy).
+ *
+ * The backend injects it into index.html when serving it, as runtime config
+ * and as `<base href>`; the Vite dev server doesn't, in which case the UI
+ * lives at the root.
+ */
+function readBasePath(): string {
+  if (typeof window !== 'undefined' && typeof window.__SV2_UI_CONFIG__?.basePath === 'string') {
+    return window.__SV2_UI_CONFIG__.basePath;
+  }
+  if (typeof document === 'undefined') return '';
+  const href = document.querySelector('base')?.getAttribute('href') ?? '/';
+  return href.replace(/\/+$/, '');
+}
+
+export const BASE_PATH = readBasePath();
+
+/**
+ * Prefix an absolute app path (`/api/status`) with the base path.
+ */
+export function withBasePath(path: string): string {
+  return `${BASE_PATH}${path}`;
+}
@@ -2,6 +2,8 @@
  * Shared pool preset definitions used by both the Setup Wizard and Settings.
  */
 
+import { withBasePath } from '@/lib/basePath';
+
 export interface KnownPool {
   id: string;
   name: string;
@@ -22,7 +24,7 @@ export const POOL_MINING_NO_JD: KnownPool[] = [
     port: 3333,
     authority_public_key: '9awtMD5KQgvRUh2yFbjVeT7b6hjipWcAsQHd6wEhgtDT9soosna',
     description: 'Production SV2 pool by Braiins',
-    logoUrl: '/braiins.svg',
+    logoUrl: withBasePath('/braiins.svg'),
     logoOnDark: true,
   },
 ];
@@ -36,7 +38,7 @@ export const POOL_MINING_JD: KnownPool[] = [
     authority_public_key: '9auqWEzQDVyd2oe1JVGFLMLHZtCo2FFqZwtKA5gd9xbuEu7PH72',
     description: 'Community testing pool. Payouts go to SRI development.',
     badge: 'testing',
-    logoUrl: '/sri-logo.png',
+    logoUrl: withBasePath('/sri-logo.png'),
   },
 ];
 
@@ -48,7 +50,7 @@ export const SOLO_POOLS: KnownPool[] = [
     port: 3333,
     authority_public_key: '9bCoFxTszKCuffyywH5uS5o6WcU4vsjTH2axxc7wE86y2HhvULU',
     description: 'Solo mining pool by Blitzpool',
-    logoUrl: '/blitzpool.svg',
+    logoUrl: withBasePath('/blitzpool.svg'),
   },
   {
     id: 'mkpool',
@@ -57,7 +59,7 @@ export const SOLO_POOLS: KnownPool[] = [
     port: 3340,
     authority_public_key: '9c9aZWzETaiJyqGGUSCn8GqFgTpxs96ert4d4jGeRnvxqRqhZar',
     description: 'Solo mining pool by MKPool',
-    logoUrl: '/mkpool-avatar-navy.svg',
+    logoUrl: withBasePath('/mkpool-avatar-navy.svg'),
   },
   {
     id: 'publicpool',
@@ -66,7 +68,7 @@ export const SOLO_POOLS: KnownPool[] = [
     port: 3333,
     authority_public_key: '9c4zpyJ2ndm4e8sP2uNc1VNCGxYjqaxWS6wUCjk8zFj6njFquH6',
     description: 'Solo mining pool by PublicPool',
-    logoUrl: '/public-pool-logo.svg',
+    logoUrl: withBasePath('/public-pool-logo.svg'),
   },
   {
     id: 'sri-solo',
@@ -75,7 +77,7 @@ export const SOLO_POOLS: KnownPool[] = [
     port: 3333,
     authority_public_key: '9auqWEzQDVyd2oe1JVGFLMLHZtCo2FFqZwtKA5gd9xbuEu7PH72',
     description: 'Community-run solo mining pool',
-    logoUrl: '/sri-logo.png',
+    logoUrl: withBasePath('/sri-logo.png'),
   },
 ];
 
@@ -1,4 +1,5 @@
 import { QueryClient } from '@tanstack/react-query';
+import { RateLimitedError } from './rateLimit';
 
 export const queryClient = new QueryClient({
   defaultOptions: {
@@ -9,7 +10,10 @@ export const queryClient = new QueryClient({
       gcTime: 5 * 60 * 1000, // 5 minutes
       // Retry failed requests
       retry: 3,
-      retryDelay: (attemptIndex) => Math.min(1000 * 2 ** attemptIndex, 30000),
+      // Back off for as long as the backend asks when rate limited
+      retryDelay: (attemptIndex, error) => error instanceof RateLimitedError
+        ? error.retryAfterMs
+        : Math.min(1000 * 2 ** attemptIndex, 30000),
       // Refetch on window focus for fresh data
       refetchOnWindowFocus: true,
     },
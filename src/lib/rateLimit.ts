@@ -0,0 +1,28 @@
+/**
+ * The backend answers over-limit proxy requests with
+ * 429 `{ error, retryAfterSecs }` and a Retry-After header.
+ */
+export class RateLimitedError extends Error {
+  readonly retryAfterMs: number;
+
+  constructor(retryAfterMs: number) {
+    super(`Rate limited, retrying in ${Math.ceil(retryAfterMs / 1000)}s`);
+    this.name = 'RateLimitedError';
+    this.retryAfterMs = retryAfterMs;
+  }
+}
+
+export async function rateLimitedErrorFromResponse(response: Response): Promise<RateLimitedError> {
+  let retryAfterSecs = Number(response.headers.get('Retry-After'));
+
+  try {
+    const body = await response.json() as { retryAfterSecs?: number };
+    if (typeof body.retryAfterSecs === 'number') {
+      retryAfterSecs = body.retryAfterSecs;
+    }
+  } catch {
+    // Fall back to the header.
+  }
+
+  return new RateLimitedError(Number.isFinite(retryAfterSecs) && retryAfterSecs > 0 ? retryAfterSecs * 1000 : 1000);
+}
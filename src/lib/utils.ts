@@ -15,51 +15,7 @@ export function cn(...inputs: ClassValue[]) {
   return twMerge(clsx(inputs));
 }
 
-/**
- * Formats hashrate with appropriate unit (H/s, KH/s, MH/s, GH/s, TH/s, PH/s, EH/s).
- */
-export function formatHashrate(hashrate: number | null): string {
-  if (hashrate === null || hashrate === 0) return '0 H/s';
-  
-  const units = ['H/s', 'KH/s', 'MH/s', 'GH/s', 'TH/s', 'PH/s', 'EH/s'];
-  const k = 1000;
-  const i = Math.floor(Math.log(hashrate) / Math.log(k));
-  const index = Math.min(i, units.length - 1);
-  
-  return `${(hashrate / Math.pow(k, index)).toFixed(2)} ${units[index]}`;
-}
-
-/**
- * Formats difficulty as a human-readable string.
- */
-export function formatDifficulty(diff: number): string {
-  if (diff === 0) return '0';
-  if (diff >= 1e15) return `${(diff / 1e15).toFixed(2)}P`;
-  if (diff >= 1e12) return `${(diff / 1e12).toFixed(2)}T`;
-  if (diff >= 1e9) return `${(diff / 1e9).toFixed(2)}G`;
-  if (diff >= 1e6) return `${(diff / 1e6).toFixed(2)}M`;
-  if (diff >= 1e3) return `${(diff / 1e3).toFixed(2)}K`;
-  return diff.toFixed(2);
-}
-
-/**
- * Formats uptime in seconds to a human-readable duration string.
- */
-export function formatUptime(seconds: number): string {
-  if (seconds < 60) return `${seconds}s`;
-  
-  const days = Math.floor(seconds / 86400);
-  const hours = Math.floor((seconds % 86400) / 3600);
-  const minutes = Math.floor((seconds % 3600) / 60);
-  
-  if (days > 0) {
-    return `${days}d ${hours}h ${minutes}m`;
-  }
-  if (hours > 0) {
-    return `${hours}h ${minutes}m`;
-  }
-  return `${minutes}m`;
-}
+export { formatHashrate, formatDifficulty, formatUptime } from '@sv2-ui/shared';
 
 /**
  * Truncates a hex string for display, showing first and last N characters.
@@ -91,9 +47,18 @@ export function calculateSharesPerMinute(shares: number, uptimeSecs: number): nu
  * Validates a Bitcoin address against the specified network.
  * Supports P2PKH, P2SH, P2WPKH, P2WSH, and P2TR address formats.
  */
+// Testnet4 and signet share testnet's address prefixes (tb1, m/n, 2);
+// regtest has its own bech32 prefix (bcrt1).
+const ADDRESS_NETWORKS: Record<BitcoinNetwork, bitcoin.networks.Network> = {
+  mainnet: bitcoin.networks.bitcoin,
+  testnet4: bitcoin.networks.testnet,
+  signet: bitcoin.networks.testnet,
+  regtest: bitcoin.networks.regtest,
+};
+
 export function isValidBitcoinAddress(addr: string, network: BitcoinNetwork): boolean {
   if (!addr) return false;
-  const btcNetwork = network === 'mainnet' ? bitcoin.networks.bitcoin : bitcoin.networks.testnet;
+  const btcNetwork = ADDRESS_NETWORKS[network];
   try {
     bitcoin.address.toOutputScript(addr, btcNetwork);
     return true;
@@ -108,15 +73,16 @@ export function isValidBitcoinAddress(addr: string, network: BitcoinNetwork): bo
  */
 export function getBitcoinAddressError(addr: string, network: BitcoinNetwork): string | null {
   if (!addr || isValidBitcoinAddress(addr, network)) return null;
-  const otherNetwork = network === 'mainnet' ? 'testnet4' : 'mainnet';
-  return isValidBitcoinAddress(addr, otherNetwork) ? 'Wrong network' : 'Invalid Bitcoin address';
+  const otherNetworks = (Object.keys(ADDRESS_NETWORKS) as BitcoinNetwork[]).filter((other) => other !== network);
+  return otherNetworks.some((other) => isValidBitcoinAddress(addr, other)) ? 'Wrong network' : 'Invalid Bitcoin address';
 }
 
 /**
  * Returns a network-specific address placeholder for form hints.
  */
 export function getBitcoinAddressPlaceholder(network: BitcoinNetwork): string {
-  return network === 'mainnet' ? 'bc1q...' : 'tb1q...';
+  if (network === 'mainnet') return 'bc1q...';
+  return network === 'regtest' ? 'bcrt1q...' : 'tb1q...';
 }
 
 // Pubkeys in pool docs / Discord are almost always shown wrapped in quotes,
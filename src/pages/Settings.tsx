@@ -5,6 +5,7 @@ import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '@/com
 import { Tabs, TabsContent, TabsList, TabsTrigger } from '@/components/ui/tabs';
 import { Label } from '@/components/ui/label';
 import { useUiConfig } from '@/hooks/useUiConfig';
+import { withBasePath } from '@/lib/basePath';
 import { useConnectionStatus } from '@/hooks/useConnectionStatus';
 import { useSetupStatus } from '@/hooks/useSetupStatus';
 import { useContainerLogs } from '@/hooks/useContainerLogs';
@@ -115,7 +116,7 @@ export function Settings() {
                           />
                         ) : (
                           <img
-                            src="/sv2-logo-240x40.png"
+                            src={withBasePath('/sv2-logo-240x40.png')}
                             alt="Default logo"
                             className="h-[18px] w-auto object-contain opacity-60"
                           />
@@ -30,8 +30,10 @@ import { isAggregatedTproxyPoolName } from '@/components/setup/poolRules';
 import { useSetupStatus } from '@/hooks/useSetupStatus';
 import { useConnectionStatus } from '@/hooks/useConnectionStatus';
 import { useLogDiagnostics } from '@/hooks/useLogDiagnostics';
+import { useSv1Listener } from '@/hooks/useSv1Listener';
 import { formatHashrate, formatDifficulty, formatNumber } from '@/lib/utils';
 import type { Sv1ClientInfo } from '@/types/api';
+import { withBasePath } from '@/lib/basePath';
 
 const RANGE_MS: Record<TimeRange, number> = { '5m': 5 * 60_000, '15m': 15 * 60_000, '1h': 60 * 60_000 };
 const RANGE_DESCRIPTIONS: Record<TimeRange, string> = {
@@ -118,6 +120,14 @@ export function UnifiedDashboard() {
   const showError         = poolError || translatorDown || jdcDown;
   const configuredButStopped = isOrchestrated && isConfigured && !isRunning;
 
+  // Explain an empty worker table: listener down vs. up with nobody attached
+  const { data: sv1Listener } = useSv1Listener(isOrchestrated && isRunning);
+  const workerEmptyHint = sv1Listener?.state === 'down'
+    ? `The Translator isn't accepting connections on port ${sv1Listener.port}.`
+    : sv1Listener?.state === 'idle'
+      ? `The Translator is listening on port ${sv1Listener.port}, but no miners are connected. Check that your miners point at this host.`
+      : null;
+
   // log-derived diagnostics from the API
   const { data: logDiagnostics } = useLogDiagnostics();
   const diagnostics = logDiagnostics?.diagnostics ?? [];
@@ -129,7 +139,7 @@ export function UnifiedDashboard() {
     setIsStarting(true);
     setStartError(null);
     try {
-      const response = await fetch('/api/restart', {
+      const response = await fetch(withBasePath('/api/restart'), {
         method: 'POST',
         signal: AbortSignal.timeout(300_000),
       });
@@ -763,6 +773,7 @@ export function UnifiedDashboard() {
               setCurrentPage(1);
             }}
             showBestDiff={!isAggregatedTproxy}
+            emptyHint={workerEmptyHint}
           />
 
           {/* Pagination Footer */}
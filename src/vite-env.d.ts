@@ -10,3 +10,8 @@ interface ImportMetaEnv {
 interface ImportMeta {
   readonly env: ImportMetaEnv;
 }
+
+interface Window {
+  /** Runtime config injected into index.html by the backend. */
+  readonly __SV2_UI_CONFIG__?: { basePath?: string };
+}
@@ -1,12 +1,66 @@
 import { defineConfig } from 'vite';
+import type { Plugin } from 'vite';
 import react from '@vitejs/plugin-react';
 import wasm from 'vite-plugin-wasm';
 import topLevelAwait from 'vite-plugin-top-level-await';
+import crypto from 'crypto';
+import fs from 'fs';
 import path from 'path';
+import zlib from 'zlib';
+
+const COMPRESSIBLE_EXTENSIONS = new Set(['.html', '.js', '.css', '.svg', '.json', '.wasm', '.txt', '.ico']);
+const MIN_COMPRESS_BYTES = 1024;
+
+/**
+ * Write .br/.gz variants and a content-hash manifest next to the build output
+ * so the backend can serve them with ETags without compressing per request.
+ * Read by server/src/static-assets.ts.
+ */
+function precompressAssets(): Plugin {
+  let outDir = 'dist';
+
+  return {
+    name: 'sv2-precompress-assets',
+    apply: 'build',
+    configResolved(config) {
+      outDir = path.resolve(config.root, config.build.outDir);
+    },
+    closeBundle() {
+      const manifest: Record<string, { etag: string; size: number; encodings: string[] }> = {};
+
+      for (const entry of fs.readdirSync(outDir, { recursive: true, encoding: 'utf-8' })) {
+        const file = path.join(outDir, entry);
+        if (!fs.statSync(file).isFile() || /\.(br|gz|map)$/.test(file) || entry === 'asset-manifest.json') continue;
+
+        const content = fs.readFileSync(file);
+        const encodings: string[] = [];
+
+        if (COMPRESSIBLE_EXTENSIONS.has(path.extname(file)) && content.length >= MIN_COMPRESS_BYTES) {
+          fs.writeFileSync(`${file}.br`, zlib.brotliCompressSync(content, {
+            params: { [zlib.constants.BROTLI_PARAM_QUALITY]: zlib.constants.BROTLI_MAX_QUALITY },
+          }));
+          fs.writeFileSync(`${file}.gz`, zlib.gzipSync(content, { level: zlib.constants.Z_BEST_COMPRESSION }));
+          encodings.push('br', 'gzip');
+        }
+
+        manifest[entry.split(path.sep).join('/')] = {
+          etag: crypto.createHash('sha256').update(content).digest('base64url').slice(0, 22),
+          size: content.length,
+          encodings,
+        };
+      }
+
+      fs.writeFileSync(path.join(outDir, 'asset-manifest.json'), JSON.stringify(manifest, null, 2));
+    },
+  };
+}
 
 // https://vite.dev/config/
 export default defineConfig({
-  plugins: [wasm(), topLevelAwait(), react()],
+  // Relative asset URLs let the backend serve the UI under BASE_PATH; it
+  // injects a matching <base href> into index.html.
+  base: './',
+  plugins: [wasm(), topLevelAwait(), react(), precompressAssets()],
   resolve: {
     alias: {
       '@': path.resolve(__dirname, './src'),
@@ -32,6 +86,11 @@ export default defineConfig({
         changeOrigin: true,
         rewrite: (path) => path.replace(/^\/translator-api/, '/api'),
       },
+      // Mempool requests go through the sv2-ui backend, which picks the instance
+      '/mempool-api': {
+        target: 'http://localhost:3001',
+        changeOrigin: true,
+      },
       // Keep metrics proxy for direct access
       '/metrics': {
         target: 'http://localhost:9092',